@@ -5,6 +5,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use ash::vk;
 use ccthw::{
+    gpu_data,
     multisample_renderpass::MultisampleRenderpass,
     vulkan::{
         errors::{InstanceError, VulkanError},
@@ -13,10 +14,12 @@ use ccthw::{
     },
 };
 
-#[derive(Debug, Copy, Clone)]
-pub struct Vertex2D {
-    pub pos: [f32; 2],
-    pub rgba: [f32; 4],
+gpu_data! {
+    #[derive(Debug, Copy, Clone)]
+    pub struct Vertex2D {
+        pub pos: [f32; 2],
+        pub rgba: [f32; 4],
+    }
 }
 
 pub struct Passthrough {
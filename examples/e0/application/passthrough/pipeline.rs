@@ -4,8 +4,8 @@ use ::{
     ccthw::{
         multisample_renderpass::MultisampleRenderpass,
         vulkan::{
-            errors::VulkanError, DescriptorSetLayout, Pipeline, PipelineLayout,
-            RenderDevice, ShaderModule,
+            errors::VulkanError, DescriptorSetLayout, GraphicsPipelineBuilder,
+            Pipeline, PipelineLayout, RenderDevice, ShaderModule,
         },
     },
     std::sync::Arc,
@@ -23,81 +23,6 @@ pub(super) fn create_pipeline(
         vk_dev.clone(),
         std::include_bytes!("../shaders/passthrough.frag.spirv"),
     )?;
-    let vertex_input_state = vk::PipelineVertexInputStateCreateInfo {
-        ..Default::default()
-    };
-    let input_assembly = vk::PipelineInputAssemblyStateCreateInfo {
-        topology: vk::PrimitiveTopology::TRIANGLE_LIST,
-        primitive_restart_enable: 0,
-        ..Default::default()
-    };
-    let extent = msaa_renderpass.msaa_render_target.image.create_info.extent;
-    let viewport = vk::Viewport {
-        x: 0.0,
-        y: 0.0,
-        width: extent.width as f32,
-        height: extent.height as f32,
-        min_depth: 0.0,
-        max_depth: 1.0,
-    };
-    let scissors = vk::Rect2D {
-        offset: vk::Offset2D { x: 0, y: 0 },
-        extent: vk::Extent2D {
-            width: extent.width,
-            height: extent.height,
-        },
-    };
-    let viewport_state = vk::PipelineViewportStateCreateInfo {
-        p_viewports: &viewport,
-        viewport_count: 1,
-        p_scissors: &scissors,
-        scissor_count: 1,
-        ..Default::default()
-    };
-    let raster_state = vk::PipelineRasterizationStateCreateInfo {
-        depth_clamp_enable: 0,
-        rasterizer_discard_enable: 0,
-        polygon_mode: vk::PolygonMode::FILL,
-        line_width: 1.0,
-        cull_mode: vk::CullModeFlags::NONE,
-        front_face: vk::FrontFace::CLOCKWISE,
-        ..Default::default()
-    };
-    let multisample_state = vk::PipelineMultisampleStateCreateInfo {
-        sample_shading_enable: 0,
-        rasterization_samples: msaa_renderpass.samples(),
-        p_sample_mask: std::ptr::null(),
-        min_sample_shading: 1.0,
-        ..Default::default()
-    };
-    let blend_attachment = vk::PipelineColorBlendAttachmentState {
-        color_write_mask: vk::ColorComponentFlags::R
-            | vk::ColorComponentFlags::G
-            | vk::ColorComponentFlags::B
-            | vk::ColorComponentFlags::A,
-        blend_enable: 1,
-        src_color_blend_factor: vk::BlendFactor::SRC_ALPHA,
-        dst_color_blend_factor: vk::BlendFactor::ONE_MINUS_SRC_ALPHA,
-        color_blend_op: vk::BlendOp::ADD,
-        src_alpha_blend_factor: vk::BlendFactor::ONE,
-        dst_alpha_blend_factor: vk::BlendFactor::ZERO,
-        alpha_blend_op: vk::BlendOp::ADD,
-    };
-    let blend_state = vk::PipelineColorBlendStateCreateInfo {
-        p_attachments: &blend_attachment,
-        attachment_count: 1,
-        ..Default::default()
-    };
-    let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo {
-        flags: vk::PipelineDepthStencilStateCreateFlags::empty(),
-        depth_test_enable: 0,
-        stencil_test_enable: 0,
-        ..Default::default()
-    };
-    let stages = [
-        vertex_module.stage_create_info(vk::ShaderStageFlags::VERTEX),
-        fragment_module.stage_create_info(vk::ShaderStageFlags::FRAGMENT),
-    ];
     let descriptor_layout = Arc::new(DescriptorSetLayout::new(
         vk_dev.clone(),
         &[vk::DescriptorSetLayoutBinding {
@@ -113,23 +38,18 @@ pub(super) fn create_pipeline(
         &[descriptor_layout],
         &[],
     )?);
-    let pipeline_create_info = vk::GraphicsPipelineCreateInfo {
-        p_stages: stages.as_ptr(),
-        stage_count: stages.len() as u32,
-        p_vertex_input_state: &vertex_input_state,
-        p_input_assembly_state: &input_assembly,
-        p_viewport_state: &viewport_state,
-        p_rasterization_state: &raster_state,
-        p_multisample_state: &multisample_state,
-        p_color_blend_state: &blend_state,
-        p_depth_stencil_state: &depth_stencil_state,
-        render_pass: msaa_renderpass.render_pass.raw,
-        layout: pipeline_layout.raw,
-        ..Default::default()
-    };
-    Ok(Pipeline::new_graphics_pipeline(
-        pipeline_create_info,
+    let extent = msaa_renderpass.msaa_render_target.image.create_info.extent;
+    Ok(GraphicsPipelineBuilder::new(
+        vk_dev,
         pipeline_layout,
-        vk_dev.clone(),
-    )?)
+        msaa_renderpass.render_pass.raw,
+    )
+    .vertex_shader(&vertex_module)
+    .fragment_shader(&fragment_module)
+    .samples(msaa_renderpass.samples())
+    .static_viewport(vk::Extent2D {
+        width: extent.width,
+        height: extent.height,
+    })
+    .build()?)
 }
@@ -5,6 +5,7 @@ use std::{mem::size_of, sync::Arc};
 use anyhow::Result;
 use ash::vk;
 use ccthw::{
+    gpu_data,
     math::projections,
     multisample_renderpass::MultisampleRenderpass,
     vulkan::{
@@ -14,10 +15,12 @@ use ccthw::{
     },
 };
 
-#[derive(Debug, Copy, Clone)]
-pub struct Vertex2D {
-    pub pos: [f32; 3],
-    pub rgba: [f32; 4],
+gpu_data! {
+    #[derive(Debug, Copy, Clone)]
+    pub struct Vertex2D {
+        pub pos: [f32; 3],
+        pub rgba: [f32; 4],
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
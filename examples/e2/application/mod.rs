@@ -48,8 +48,12 @@ impl Application {
 
         let mut asset_loader =
             AssetLoader::new(vk_dev.clone(), vk_alloc.clone())?;
-        let tex1 = asset_loader.read_texture("assets/example2_tex1.jpg")?;
-        let tex2 = asset_loader.read_texture("assets/example2_tex2.jpg")?;
+        let tex1 = asset_loader
+            .read_texture("assets/example2_tex1.jpg")?
+            .index();
+        let tex2 = asset_loader
+            .read_texture("assets/example2_tex2.jpg")?
+            .index();
 
         // Create per-frame resources and the renderpass
         let frame_pipeline = FramePipeline::new(vk_dev.clone())?;
@@ -6,6 +6,7 @@ use anyhow::Result;
 use ash::vk;
 use ccthw::{
     asset_loader::CombinedImageSampler,
+    gpu_data,
     math::projections,
     multisample_renderpass::MultisampleRenderpass,
     vulkan::{
@@ -15,12 +16,14 @@ use ccthw::{
     },
 };
 
-#[derive(Debug, Copy, Clone)]
-pub struct Vertex2D {
-    pub pos: [f32; 3],
-    pub uv: [f32; 2],
-    pub rgba: [f32; 4],
-    pub tex_index: i32,
+gpu_data! {
+    #[derive(Debug, Copy, Clone)]
+    pub struct Vertex2D {
+        pub pos: [f32; 3],
+        pub uv: [f32; 2],
+        pub rgba: [f32; 4],
+        pub tex_index: i32,
+    }
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -11,12 +11,14 @@ use ::{
 pub enum ExampleMessage {
     ToggleFullscreen,
     AngleSlider(f32),
+    LightTheme(bool),
 }
 
 pub struct ExampleUi {
     em: f32,
     font: Font,
     is_fullscreen: bool,
+    pub light_theme: bool,
     pub angle: f32,
 }
 
@@ -35,6 +37,7 @@ impl ExampleUi {
             em,
             font,
             is_fullscreen: false,
+            light_theme: false,
             angle: 0.0,
         })
     }
@@ -55,16 +58,21 @@ impl UIState for ExampleUi {
             .hover_color(vec4(1.0, 1.0, 1.0, 0.1))
             .pressed_color(vec4(1.0, 1.0, 1.0, 0.5))
             .container()
-            .border(1.0, vec4(0.0, 0.0, 0.0, 0.75), 0)
+            .border(1.0, vec4(0.0, 0.0, 0.0, 0.75), TextureId::WHITE_PIXEL)
             .padding(0.5 * self.em);
 
         let angle_slider = slider(gen_id!(), 0.0, 2.0 * std::f32::consts::PI)
             .on_change(ExampleMessage::AngleSlider)
             .value(self.angle);
 
+        let theme_toggle = toggle(gen_id!(), &self.font, "Light Theme")
+            .initial_value(self.light_theme)
+            .on_toggle(ExampleMessage::LightTheme);
+
         align(
             col()
                 .child(fullscreen_button, Justify::Center)
+                .child(theme_toggle, Justify::Center)
                 .child(label(&self.font, "Sprite Angle"), Justify::Center)
                 .child(angle_slider, Justify::Center)
                 .space_between(SpaceBetween::Fixed(self.em))
@@ -83,6 +91,9 @@ impl UIState for ExampleUi {
             ExampleMessage::AngleSlider(angle) => {
                 self.angle = angle;
             }
+            ExampleMessage::LightTheme(light_theme) => {
+                self.light_theme = light_theme;
+            }
         }
     }
 }
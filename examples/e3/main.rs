@@ -3,13 +3,13 @@ mod example_ui;
 use ::{
     anyhow::Result,
     ccthw::{
-        asset_loader::AssetLoader,
+        asset_loader::{AssetLoader, TextureId},
         demo::{run_application, State},
         glfw_window::GlfwWindow,
         immediate_mode_graphics::{triangles::Frame, Sprite},
         math::projections,
         timing::FrameRateLimit,
-        ui::UI,
+        ui::{Theme, UI},
         vulkan::{MemoryAllocator, RenderDevice},
         Mat4,
     },
@@ -19,7 +19,7 @@ use ::{
 use example_ui::{ExampleMessage, ExampleUi};
 
 struct Example {
-    sprite_texture: i32,
+    sprite_texture: TextureId,
     ui: UI<ExampleUi>,
     app_camera: Mat4,
 }
@@ -57,12 +57,15 @@ impl State for Example {
         let sprite_texture =
             asset_loader.read_texture("assets/texture_orientation.png")?;
 
+        let mut ui = UI::new(
+            window.window.get_framebuffer_size().into(),
+            ExampleUi::new(scale.0, asset_loader)?,
+        );
+        ui.set_clipboard(window.clipboard());
+
         Ok(Self {
             sprite_texture,
-            ui: UI::new(
-                window.window.get_framebuffer_size().into(),
-                ExampleUi::new(scale.0, asset_loader)?,
-            ),
+            ui,
             app_camera: Self::projection(aspect_ratio),
         })
     }
@@ -76,6 +79,13 @@ impl State for Example {
             Some(ExampleMessage::ToggleFullscreen) => {
                 window.toggle_fullscreen()?
             }
+            Some(ExampleMessage::LightTheme(light_theme)) => {
+                self.ui.set_theme(if light_theme {
+                    Theme::light()
+                } else {
+                    Theme::dark()
+                });
+            }
             _ => (),
         }
 
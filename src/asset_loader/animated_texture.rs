@@ -0,0 +1,190 @@
+use ::{
+    image::{
+        codecs::{gif::GifDecoder, png::PngDecoder},
+        AnimationDecoder, Frame, RgbaImage,
+    },
+    std::{
+        cell::Cell, fs::File, io::BufReader, path::Path, rc::Rc, time::Duration,
+    },
+};
+
+use crate::asset_loader::{AssetLoaderError, TextureId};
+
+/// Frames with no delay are shown for this long instead, like browsers do.
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+
+/// One frame of an animated texture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AnimationFrame {
+    /// The frame's texture.
+    pub texture_index: TextureId,
+
+    /// How long the frame is shown before the next one.
+    pub delay: Duration,
+}
+
+/// An AnimatedTexture is a handle to a GIF or APNG which was loaded with
+/// [`super::AssetLoader::read_animated_texture`]. Each frame is its own
+/// texture.
+///
+/// Handles share the asset loader's animation clock, which the demo
+/// Application advances each frame, so [`Self::texture_index`] is always the
+/// frame to draw right now. Animations loop forever.
+#[derive(Debug, Clone)]
+pub struct AnimatedTexture {
+    frames: Rc<[AnimationFrame]>,
+
+    /// The asset loader's animation clock.
+    clock: Rc<Cell<Duration>>,
+
+    /// The clock's time when the animation was last restarted.
+    start: Duration,
+}
+
+impl AnimatedTexture {
+    pub(super) fn new(
+        frames: Vec<AnimationFrame>,
+        clock: Rc<Cell<Duration>>,
+    ) -> Self {
+        let start = clock.get();
+        Self {
+            frames: frames.into(),
+            clock,
+            start,
+        }
+    }
+
+    /// Every frame, in the order they're shown.
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    /// The time it takes to show every frame once.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+
+    /// The index of the frame to draw now.
+    pub fn frame_index(&self) -> usize {
+        let delays = self.frames.iter().map(|frame| frame.delay);
+        frame_at(delays, self.clock.get().saturating_sub(self.start))
+    }
+
+    /// The texture of the frame to draw now.
+    pub fn texture_index(&self) -> TextureId {
+        self.frames
+            .get(self.frame_index())
+            .map_or(TextureId::WHITE_PIXEL, |frame| frame.texture_index)
+    }
+
+    /// Show the first frame again, starting now.
+    pub fn restart(&mut self) {
+        self.start = self.clock.get();
+    }
+}
+
+/// Decode every frame of a GIF or APNG, along with how long each one is
+/// shown. PNGs which aren't animated have a single frame.
+pub(super) fn decode_animation(
+    path: &Path,
+) -> Result<Vec<(RgbaImage, Duration)>, AssetLoaderError> {
+    let reader = BufReader::new(File::open(path)?);
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+    let frames: Vec<Frame> = match extension.as_deref() {
+        Some("gif") => {
+            GifDecoder::new(reader)?.into_frames().collect_frames()?
+        }
+        Some("png") | Some("apng") => {
+            let decoder = PngDecoder::new(reader)?;
+            if decoder.is_apng() {
+                decoder.apng().into_frames().collect_frames()?
+            } else {
+                let image = image::DynamicImage::from_decoder(decoder)?;
+                vec![Frame::new(image.into_rgba8())]
+            }
+        }
+        _ => {
+            return Err(AssetLoaderError::UnsupportedAnimation(path.to_owned()))
+        }
+    };
+    Ok(frames
+        .into_iter()
+        .map(|frame| {
+            let delay = Duration::from(frame.delay());
+            let delay = if delay.is_zero() {
+                DEFAULT_FRAME_DELAY
+            } else {
+                delay
+            };
+            (frame.into_buffer(), delay)
+        })
+        .collect())
+}
+
+/// The frame shown after the given time, looping after the last frame.
+fn frame_at<I>(delays: I, elapsed: Duration) -> usize
+where
+    I: Iterator<Item = Duration> + Clone,
+{
+    let total: Duration = delays.clone().sum();
+    if total.is_zero() {
+        return 0;
+    }
+    let mut remaining =
+        Duration::from_nanos((elapsed.as_nanos() % total.as_nanos()) as u64);
+    let mut last = 0;
+    for (i, delay) in delays.enumerate() {
+        if remaining < delay {
+            return i;
+        }
+        remaining -= delay;
+        last = i;
+    }
+    last
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames_follow_their_delays() {
+        let ms = Duration::from_millis;
+        let delays = [ms(100), ms(50), ms(200)];
+        let at = |elapsed| frame_at(delays.iter().copied(), elapsed);
+
+        assert_eq!(at(ms(0)), 0);
+        assert_eq!(at(ms(120)), 1);
+        assert_eq!(at(ms(150)), 2);
+        assert_eq!(at(ms(360)), 0);
+        assert_eq!(frame_at(std::iter::empty(), ms(10)), 0);
+    }
+
+    #[test]
+    fn test_handles_share_the_clock() {
+        let clock = Rc::new(Cell::new(Duration::from_secs(10)));
+        let mut texture = AnimatedTexture::new(
+            vec![
+                AnimationFrame {
+                    texture_index: TextureId::from_index(3),
+                    delay: Duration::from_millis(100),
+                },
+                AnimationFrame {
+                    texture_index: TextureId::from_index(4),
+                    delay: Duration::from_millis(100),
+                },
+            ],
+            clock.clone(),
+        );
+        assert_eq!(texture.texture_index().index(), 3);
+
+        clock.set(Duration::from_millis(10_150));
+        assert_eq!(texture.texture_index().index(), 4);
+
+        texture.restart();
+        assert_eq!(texture.texture_index().index(), 3);
+    }
+}
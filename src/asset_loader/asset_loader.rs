@@ -4,23 +4,36 @@ use ::{
     image::{
         imageops::{self, FilterType},
         io::Reader,
+        RgbaImage,
     },
-    std::{path::Path, sync::Arc},
+    std::{cell::Cell, path::Path, rc::Rc, sync::Arc, time::Duration},
 };
 
 use crate::{
-    asset_loader::{AssetLoaderError, CombinedImageSampler, MipmapData},
+    asset_loader::{
+        animated_texture::decode_animation, flipbook::sequence_order,
+        AnimatedTexture, AnimationFrame, AssetLoaderError,
+        CombinedImageSampler, Flipbook, MipmapData, TextureId, TextureInfo,
+        TextureTable,
+    },
+    ui::Clipboard,
     vulkan::{
         errors::VulkanError, GpuVec, Image, ImageView, MemoryAllocator,
-        OneTimeSubmitCommandPool, RenderDevice, Sampler,
+        OneTimeSubmitCommandPool, RenderDevice, Sampler, VulkanDebug,
     },
 };
 
 pub struct AssetLoader {
     textures: Vec<CombinedImageSampler>,
+    texture_table: TextureTable,
+    texture_info: Vec<TextureInfo>,
     default_sampler: Arc<Sampler>,
     staging_buffer: GpuVec<u8>,
     command_pool: OneTimeSubmitCommandPool,
+
+    /// The time every [`AnimatedTexture`] is shown at, shared with their
+    /// handles.
+    animation_clock: Rc<Cell<Duration>>,
     vk_alloc: Arc<dyn MemoryAllocator>,
     vk_dev: Arc<RenderDevice>,
 }
@@ -31,34 +44,50 @@ impl AssetLoader {
         vk_dev: Arc<RenderDevice>,
         vk_alloc: Arc<dyn MemoryAllocator>,
     ) -> Result<Self, AssetLoaderError> {
+        let default_sampler =
+            Sampler::linear(vk_dev.clone()).map_err(VulkanError::ImageError)?;
+        default_sampler
+            .set_debug_name("AssetLoader - Default Sampler")
+            .map_err(VulkanError::VulkanDebugError)?;
+        let command_pool = OneTimeSubmitCommandPool::new(
+            vk_dev.clone(),
+            &vk_dev.graphics_queue,
+        )
+        .map_err(VulkanError::CommandBufferError)?;
+        command_pool
+            .set_debug_name("AssetLoader")
+            .map_err(VulkanError::VulkanDebugError)?;
+        let texture_table = TextureTable::new(vk_dev.clone())?;
+        texture_table
+            .set_debug_name("AssetLoader - Texture Table")
+            .map_err(VulkanError::VulkanDebugError)?;
         let mut loader = Self {
             textures: vec![],
-            default_sampler: Arc::new(
-                Sampler::linear(vk_dev.clone())
-                    .map_err(VulkanError::ImageError)?,
-            ),
-            staging_buffer: GpuVec::new(
+            texture_table,
+            texture_info: vec![],
+            default_sampler: Arc::new(default_sampler),
+            staging_buffer: GpuVec::new_named(
                 vk_dev.clone(),
                 vk_alloc.clone(),
                 vk::BufferUsageFlags::TRANSFER_SRC,
                 (8 * 4) * 512 * 512,
+                "AssetLoader - Staging Buffer",
             )
             .map_err(VulkanError::BufferError)?,
-            command_pool: OneTimeSubmitCommandPool::new(
-                vk_dev.clone(),
-                &vk_dev.graphics_queue,
-            )
-            .map_err(VulkanError::CommandBufferError)?,
+            command_pool,
+            animation_clock: Rc::new(Cell::new(Duration::ZERO)),
             vk_alloc,
             vk_dev,
         };
         // The texture with index 0 is always a 1x1 white pixel. This makes it
-        // so a texture_index of 0 operates as if no texturing is applied.
+        // so drawing with TextureId::WHITE_PIXEL operates as if no texturing
+        // is applied.
         loader.create_texture_with_data(&[MipmapData {
             width: 1,
             height: 1,
             data: vec![0xFF, 0xFF, 0xFF, 0xFF],
         }])?;
+        loader.set_texture_name(TextureId::WHITE_PIXEL, "White Pixel")?;
         Ok(loader)
     }
 
@@ -67,15 +96,320 @@ impl AssetLoader {
         &self.textures
     }
 
+    /// Get the device-level table with every texture. Renderers bind the
+    /// table instead of keeping their own list of textures, so the indices
+    /// returned by the loader work in every renderer.
+    pub fn texture_table(&self) -> &TextureTable {
+        &self.texture_table
+    }
+
+    /// Get a description of every texture, in the same order as the
+    /// `textures()` array.
+    pub fn texture_info(&self) -> &[TextureInfo] {
+        &self.texture_info
+    }
+
+    /// Set the name reported for a texture in `texture_info()`. The name is
+    /// also used as the debug name for the texture's image and image view.
+    pub fn set_texture_name(
+        &mut self,
+        texture: TextureId,
+        name: impl Into<String>,
+    ) -> Result<(), AssetLoaderError> {
+        let index = texture.index() as usize;
+        let (info, texture) = match (
+            self.texture_info.get_mut(index),
+            self.textures.get(index),
+        ) {
+            (Some(info), Some(texture)) => (info, texture),
+            _ => return Ok(()),
+        };
+        info.name = name.into();
+
+        let image_view = &texture.image_view;
+        if info.mip_view.is_none() {
+            // mip level views share the source texture's image, so only the
+            // source names it
+            image_view
+                .image
+                .set_debug_name(format!("Texture - {}", info.name))
+                .map_err(VulkanError::VulkanDebugError)?;
+        }
+        image_view
+            .set_debug_name(format!("Texture - {} - View", info.name))
+            .map_err(VulkanError::VulkanDebugError)?;
+        Ok(())
+    }
+
+    /// Create a texture for each mip level of an existing texture. Each
+    /// texture shares the source image but views only a single level, so it
+    /// always renders that level regardless of its size on screen.
+    ///
+    /// This is mostly useful for debugging, e.g. with the
+    /// [`crate::ui::widgets::TextureInspector`].
+    ///
+    /// # Returns
+    ///
+    /// The texture for each mip level, starting with level 0.
+    pub fn create_mip_level_views(
+        &mut self,
+        texture: TextureId,
+    ) -> Result<Vec<TextureId>, AssetLoaderError> {
+        let (image, source_info) = match (
+            self.textures.get(texture.index() as usize),
+            self.texture_info.get(texture.index() as usize),
+        ) {
+            (Some(source), Some(info)) => {
+                (source.image_view.image.clone(), info.clone())
+            }
+            _ => return Err(AssetLoaderError::UnknownTexture(texture)),
+        };
+
+        let mut indices = Vec::with_capacity(source_info.mip_levels as usize);
+        for level in 0..source_info.mip_levels {
+            let image_view = ImageView::new_2d_mip_level(
+                image.clone(),
+                vk::Format::R8G8B8A8_SRGB,
+                vk::ImageAspectFlags::COLOR,
+                level,
+            )
+            .map_err(VulkanError::ImageError)?;
+            let index = self.push_texture(
+                CombinedImageSampler::new(
+                    Arc::new(image_view),
+                    self.default_sampler.clone(),
+                ),
+                TextureInfo {
+                    byte_size: 0,
+                    mip_view: Some((texture, level)),
+                    ..source_info.clone()
+                },
+            )?;
+            self.set_texture_name(
+                index,
+                format!("{} (mip {})", source_info.name, level),
+            )?;
+            indices.push(index);
+        }
+        Ok(indices)
+    }
+
+    /// Get the texture for a single mip level of the given texture, if one
+    /// was created with `create_mip_level_views`.
+    pub fn mip_level_view(
+        &self,
+        texture: TextureId,
+        level: u32,
+    ) -> Option<TextureId> {
+        self.texture_info
+            .iter()
+            .position(|info| info.mip_view == Some((texture, level)))
+            .map(|index| TextureId::from_index(index as i32))
+    }
+
     /// Upload the given mipmap data into a 2d texture.
     ///
     /// # Returns
     ///
-    /// An i32 index for this texture in the the `textures()` array.
+    /// The texture's id, also its index in the `textures()` array.
     pub fn create_texture_with_data(
         &mut self,
         mipmaps: &[MipmapData],
-    ) -> Result<i32, AssetLoaderError> {
+    ) -> Result<TextureId, AssetLoaderError> {
+        let (texture, info) = self.upload_texture(mipmaps)?;
+        let index = self.push_texture(texture, info)?;
+        self.set_texture_name(index, format!("Texture {}", index))?;
+        Ok(index)
+    }
+
+    /// Replace an existing texture with new mipmap data. The texture keeps
+    /// its index and name, so anything which refers to it draws the new
+    /// data from now on. The new data doesn't need to be the same size.
+    ///
+    /// This waits for the device to go idle, so it's meant for occasional
+    /// updates rather than every frame.
+    pub fn replace_texture_with_data(
+        &mut self,
+        texture: TextureId,
+        mipmaps: &[MipmapData],
+    ) -> Result<(), AssetLoaderError> {
+        let index = texture.index() as usize;
+        if index >= self.textures.len() {
+            return Err(AssetLoaderError::UnknownTexture(texture));
+        }
+        let (replacement, info) = self.upload_texture(mipmaps)?;
+        unsafe {
+            // Safe because uploading waits for the device to go idle, so no
+            // frame in flight uses the old texture.
+            self.texture_table.write(index as u32, &replacement);
+        }
+        let name = std::mem::take(&mut self.texture_info[index].name);
+        self.textures[index] = replacement;
+        self.texture_info[index] = info;
+        self.set_texture_name(texture, name)
+    }
+
+    /// Load a texture from the image at the given path.
+    /// Mipmaps are automatically generated for each of the half-size images.
+    /// # Returns
+    ///
+    /// The texture's id, also its index in the `textures()` array.
+    pub fn read_texture<T>(
+        &mut self,
+        path_to_texture_image: T,
+    ) -> Result<TextureId, AssetLoaderError>
+    where
+        T: AsRef<Path>,
+    {
+        let name = path_to_texture_image.as_ref().display().to_string();
+        let loaded = Reader::open(path_to_texture_image)?.decode()?;
+        let mipmaps = Self::generate_mipmaps(&loaded.into_rgba8());
+
+        let texture_index = self.create_texture_with_data(&mipmaps)?;
+        self.set_texture_name(texture_index, name)?;
+        Ok(texture_index)
+    }
+
+    /// Create a texture from an image which is already in memory, with
+    /// mipmaps like [`Self::read_texture`].
+    pub fn create_texture_from_image(
+        &mut self,
+        image: &RgbaImage,
+        name: impl Into<String>,
+    ) -> Result<TextureId, AssetLoaderError> {
+        let mipmaps = Self::generate_mipmaps(image);
+        let texture_index = self.create_texture_with_data(&mipmaps)?;
+        self.set_texture_name(texture_index, name)?;
+        Ok(texture_index)
+    }
+
+    /// Create a texture from the image on the clipboard, e.g.
+    /// [`crate::glfw_window::GlfwWindow::clipboard`]. Returns None when the
+    /// clipboard doesn't have an image.
+    pub fn paste_texture(
+        &mut self,
+        clipboard: &mut dyn Clipboard,
+    ) -> Result<Option<TextureId>, AssetLoaderError> {
+        let image = match clipboard.image() {
+            Some(image) => image,
+            None => return Ok(None),
+        };
+        let name = format!("Pasted Image {}", self.textures.len());
+        self.create_texture_from_image(&image, name).map(Some)
+    }
+
+    /// Load every frame of a GIF or an animated PNG, each as its own
+    /// texture. PNGs which aren't animated have a single frame.
+    ///
+    /// The handle's current frame follows the animation clock, see
+    /// [`Self::advance_animations`].
+    pub fn read_animated_texture<T>(
+        &mut self,
+        path: T,
+    ) -> Result<AnimatedTexture, AssetLoaderError>
+    where
+        T: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let mut frames = vec![];
+        for (i, (image, delay)) in
+            decode_animation(path)?.into_iter().enumerate()
+        {
+            let texture_index =
+                self.create_texture_with_data(&Self::generate_mipmaps(&image))?;
+            self.set_texture_name(
+                texture_index,
+                format!("{} - Frame {}", name, i),
+            )?;
+            frames.push(AnimationFrame {
+                texture_index,
+                delay,
+            });
+        }
+        Ok(AnimatedTexture::new(frames, self.animation_clock.clone()))
+    }
+
+    /// Move every [`AnimatedTexture`] forward by the time since the last
+    /// frame. The demo Application calls this once per frame.
+    pub fn advance_animations(&mut self, delta: Duration) {
+        self.animation_clock.set(self.animation_clock.get() + delta);
+    }
+
+    /// Load a numbered image sequence from a directory as a flipbook. Files
+    /// are ordered by the number at the end of their names, e.g.
+    /// "smoke_1.png", "smoke_2.png", ..., "smoke_10.png", and files without
+    /// a number are ignored. Every frame must be the same size.
+    ///
+    /// The frames are stacked from top to bottom in a single texture, so the
+    /// whole sequence uses one slot in the texture table. Long sequences of
+    /// large frames can exceed the device's maximum texture size.
+    pub fn read_image_sequence<T>(
+        &mut self,
+        dir: T,
+    ) -> Result<Flipbook, AssetLoaderError>
+    where
+        T: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_file() {
+                paths.push(path);
+            }
+        }
+        let paths = sequence_order(paths);
+        if paths.is_empty() {
+            return Err(AssetLoaderError::EmptyImageSequence(dir.to_owned()));
+        }
+
+        let frames = paths
+            .iter()
+            .map(|path| Ok(Reader::open(path)?.decode()?.into_rgba8()))
+            .collect::<Result<Vec<_>, AssetLoaderError>>()?;
+        let (frame_width, frame_height) = frames[0].dimensions();
+        let mut atlas =
+            RgbaImage::new(frame_width, frame_height * frames.len() as u32);
+        for (i, (frame, path)) in frames.iter().zip(&paths).enumerate() {
+            if frame.dimensions() != (frame_width, frame_height) {
+                return Err(AssetLoaderError::MismatchedSequenceFrame {
+                    path: path.clone(),
+                    width: frame.width(),
+                    height: frame.height(),
+                    expected_width: frame_width,
+                    expected_height: frame_height,
+                });
+            }
+            imageops::replace(
+                &mut atlas,
+                frame,
+                0,
+                (i as u32 * frame_height) as i64,
+            );
+        }
+
+        let texture_index =
+            self.create_texture_with_data(&Self::generate_mipmaps(&atlas))?;
+        self.set_texture_name(texture_index, dir.display().to_string())?;
+        Ok(Flipbook {
+            texture_index,
+            frame_count: frames.len() as u32,
+            frame_width,
+            frame_height,
+            frames_per_second: 24.0,
+            looping: true,
+        })
+    }
+}
+
+impl AssetLoader {
+    /// Copy mipmap data into a new device local image. Waits for the upload
+    /// to finish.
+    fn upload_texture(
+        &mut self,
+        mipmaps: &[MipmapData],
+    ) -> Result<(CombinedImageSampler, TextureInfo), AssetLoaderError> {
         let vulkan_image = self.create_empty_2d(
             mipmaps[0].width,
             mipmaps[0].height,
@@ -178,6 +512,14 @@ impl AssetLoader {
             })
             .map_err(VulkanError::CommandBufferError)?;
 
+        let info = TextureInfo {
+            name: String::new(),
+            width: mipmaps[0].width,
+            height: mipmaps[0].height,
+            mip_levels: mipmaps.len() as u32,
+            byte_size: vulkan_image.allocation.byte_size,
+            mip_view: None,
+        };
         let image_view = Arc::new(
             ImageView::new_2d(
                 Arc::new(vulkan_image),
@@ -188,50 +530,35 @@ impl AssetLoader {
         );
         let texture =
             CombinedImageSampler::new(image_view, self.default_sampler.clone());
-        self.textures.push(texture.clone());
-
-        // return the index of the last texture
-        Ok((self.textures.len() - 1) as i32)
+        Ok((texture, info))
     }
 
-    /// Load a texture from the image at the given path.
-    /// Mipmaps are automatically generated for each of the half-size images.
+    /// Add a texture to the end of the textures array and write it into the
+    /// texture table.
+    ///
     /// # Returns
     ///
-    /// An i32 index for this texture in the the `textures()` array.
-    pub fn read_texture<T>(
+    /// The new texture's id.
+    fn push_texture(
         &mut self,
-        path_to_texture_image: T,
-    ) -> Result<i32, AssetLoaderError>
-    where
-        T: AsRef<Path>,
-    {
-        let loaded = Reader::open(path_to_texture_image)?.decode()?;
-        let rgba = loaded.into_rgba8();
-        let (width, height) = (rgba.width(), rgba.height());
-
-        let mipmap_count = Self::compute_mipmap_count(width, height);
-        let mipmaps: Vec<_> = (0..mipmap_count)
-            .map(|i| {
-                let mipmap = imageops::resize(
-                    &rgba,
-                    (width >> i).max(1),
-                    (height >> i).max(1),
-                    FilterType::Triangle,
-                );
-                MipmapData {
-                    width: mipmap.width(),
-                    height: mipmap.height(),
-                    data: mipmap.into_raw(),
-                }
-            })
-            .collect();
-
-        self.create_texture_with_data(&mipmaps)
+        texture: CombinedImageSampler,
+        info: TextureInfo,
+    ) -> Result<TextureId, AssetLoaderError> {
+        let index = self.textures.len() as u32;
+        if index >= self.texture_table.capacity() {
+            return Err(AssetLoaderError::TextureTableFull(
+                self.texture_table.capacity(),
+            ));
+        }
+        unsafe {
+            // Safe because the index is new, so no frame in flight uses it.
+            self.texture_table.write(index, &texture);
+        }
+        self.textures.push(texture);
+        self.texture_info.push(info);
+        Ok(TextureId::from_index(index as i32))
     }
-}
 
-impl AssetLoader {
     fn create_empty_2d(
         &mut self,
         width: u32,
@@ -267,6 +594,27 @@ impl AssetLoader {
 
     /// Compute the number of layers, in addition to the original image, are
     /// required for a complete mipmap stack.
+    /// Build every half-size mipmap for an image, down to a single pixel.
+    fn generate_mipmaps(rgba: &RgbaImage) -> Vec<MipmapData> {
+        let (width, height) = (rgba.width(), rgba.height());
+        let mipmap_count = Self::compute_mipmap_count(width, height);
+        (0..mipmap_count)
+            .map(|i| {
+                let mipmap = imageops::resize(
+                    rgba,
+                    (width >> i).max(1),
+                    (height >> i).max(1),
+                    FilterType::Triangle,
+                );
+                MipmapData {
+                    width: mipmap.width(),
+                    height: mipmap.height(),
+                    data: mipmap.into_raw(),
+                }
+            })
+            .collect()
+    }
+
     fn compute_mipmap_count(width: u32, height: u32) -> u32 {
         let max_dimension = (width as f32).max(height as f32);
         let powers_of_two = max_dimension.log2().floor();
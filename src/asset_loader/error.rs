@@ -1,6 +1,10 @@
-use ::{image::ImageError, std::io, thiserror::Error};
+use ::{
+    image::ImageError,
+    std::{io, path::PathBuf},
+    thiserror::Error,
+};
 
-use crate::vulkan::errors::VulkanError;
+use crate::{asset_loader::TextureId, vulkan::errors::VulkanError};
 
 #[derive(Debug, Error)]
 pub enum AssetLoaderError {
@@ -12,4 +16,25 @@ pub enum AssetLoaderError {
 
     #[error("Unable to decode the texture file into rgba.")]
     UnableToDecodeImage(#[from] ImageError),
+
+    #[error("The texture table is full, it holds at most {0} textures")]
+    TextureTableFull(u32),
+
+    #[error("There is no texture with index {0}")]
+    UnknownTexture(TextureId),
+
+    #[error("{0:?} isn't a GIF or PNG, so it can't be read as an animation")]
+    UnsupportedAnimation(PathBuf),
+
+    #[error("There are no numbered images in {0:?}")]
+    EmptyImageSequence(PathBuf),
+
+    #[error("{path:?} is {width}x{height}, but the sequence's frames are {expected_width}x{expected_height}")]
+    MismatchedSequenceFrame {
+        path: PathBuf,
+        width: u32,
+        height: u32,
+        expected_width: u32,
+        expected_height: u32,
+    },
 }
@@ -0,0 +1,197 @@
+use ::std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{asset_loader::TextureId, ui::primitives::Rect};
+
+/// A Flipbook is an image sequence loaded into a single texture with the
+/// frames stacked from top to bottom, see
+/// [`super::AssetLoader::read_image_sequence`].
+///
+/// Draw a frame with the flipbook's texture index and the frame's uv rect.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Flipbook {
+    /// The texture with every frame.
+    pub texture_index: TextureId,
+
+    /// The number of frames in the sequence.
+    pub frame_count: u32,
+
+    /// The size of each frame in pixels.
+    pub frame_width: u32,
+    pub frame_height: u32,
+
+    /// How quickly the sequence plays. Defaults to 24.
+    pub frames_per_second: f32,
+
+    /// When true the sequence starts over after the last frame, otherwise it
+    /// stops on the last frame. Defaults to true.
+    pub looping: bool,
+}
+
+impl Flipbook {
+    /// The frame shown after playing for the given time.
+    pub fn frame_at(&self, time: Duration) -> u32 {
+        if self.frame_count == 0 {
+            return 0;
+        }
+        let frame = (time.as_secs_f32() * self.frames_per_second.max(0.0))
+            .floor() as u32;
+        if self.looping {
+            frame % self.frame_count
+        } else {
+            frame.min(self.frame_count - 1)
+        }
+    }
+
+    /// The part of the texture covered by a frame.
+    pub fn frame_uv(&self, frame: u32) -> Rect {
+        let count = self.frame_count.max(1) as f32;
+        let frame = frame.min(self.frame_count.saturating_sub(1)) as f32;
+        Rect::new(frame / count, 0.0, (frame + 1.0) / count, 1.0)
+    }
+
+    /// The time it takes to play every frame once.
+    pub fn duration(&self) -> Duration {
+        if self.frames_per_second <= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f32(
+            self.frame_count as f32 / self.frames_per_second,
+        )
+    }
+}
+
+/// FlipbookPlayback tracks how far into a flipbook a sketch is. Advance it
+/// with the frame clock, e.g. the UI's
+/// [`crate::ui::InternalState::frame_delta`], then draw the flipbook's
+/// current frame.
+#[derive(Debug, Copy, Clone)]
+pub struct FlipbookPlayback {
+    elapsed: Duration,
+    playing: bool,
+}
+
+impl FlipbookPlayback {
+    /// Start playing from the first frame.
+    pub fn new() -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            playing: true,
+        }
+    }
+
+    /// Move playback forward by the time since the last frame. Nothing
+    /// happens while playback is paused.
+    pub fn advance(&mut self, delta: Duration) {
+        if self.playing {
+            self.elapsed += delta;
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Go back to the first frame.
+    pub fn restart(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// The flipbook's current frame.
+    pub fn frame(&self, flipbook: &Flipbook) -> u32 {
+        flipbook.frame_at(self.elapsed)
+    }
+
+    /// The uv rect of the flipbook's current frame.
+    pub fn frame_uv(&self, flipbook: &Flipbook) -> Rect {
+        flipbook.frame_uv(self.frame(flipbook))
+    }
+
+    /// True once a flipbook which doesn't loop has shown its last frame for
+    /// its full duration.
+    pub fn is_finished(&self, flipbook: &Flipbook) -> bool {
+        !flipbook.looping && self.elapsed >= flipbook.duration()
+    }
+}
+
+impl Default for FlipbookPlayback {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sort the files in an image sequence by the number at the end of their
+/// names, so "frame_2.png" comes before "frame_10.png". Files without a
+/// number are skipped.
+pub(super) fn sequence_order(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut numbered: Vec<(u64, PathBuf)> = paths
+        .into_iter()
+        .filter_map(|path| Some((sequence_number(&path)?, path)))
+        .collect();
+    numbered.sort();
+    numbered.into_iter().map(|(_, path)| path).collect()
+}
+
+/// The number at the end of a file's name, ignoring its extension.
+fn sequence_number(path: &Path) -> Option<u64> {
+    let stem = path.file_stem()?.to_str()?;
+    let digits = stem.len()
+        - stem
+            .chars()
+            .rev()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+    stem[digits..].parse().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sequence_order_is_numeric() {
+        let paths = ["fx_10.png", "fx_2.png", "notes.txt", "fx_1.png"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        assert_eq!(
+            sequence_order(paths),
+            vec![
+                PathBuf::from("fx_1.png"),
+                PathBuf::from("fx_2.png"),
+                PathBuf::from("fx_10.png")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frames_advance_with_time() {
+        let mut flipbook = Flipbook {
+            texture_index: TextureId::from_index(1),
+            frame_count: 4,
+            frame_width: 32,
+            frame_height: 32,
+            frames_per_second: 10.0,
+            looping: true,
+        };
+        assert_eq!(flipbook.frame_at(Duration::from_millis(250)), 2);
+        assert_eq!(flipbook.frame_at(Duration::from_millis(450)), 0);
+        assert_eq!(flipbook.frame_uv(1), Rect::new(0.25, 0.0, 0.5, 1.0));
+
+        flipbook.looping = false;
+        let mut playback = FlipbookPlayback::new();
+        playback.advance(Duration::from_secs(1));
+        assert_eq!(playback.frame(&flipbook), 3);
+        assert!(playback.is_finished(&flipbook));
+    }
+}
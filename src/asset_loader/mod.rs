@@ -1,9 +1,21 @@
+mod animated_texture;
 mod asset_loader;
 mod combined_image_sampler;
 mod error;
+mod flipbook;
 mod mipmap_data;
+mod texture_id;
+mod texture_info;
+mod texture_table;
 
 pub use self::{
-    asset_loader::AssetLoader, combined_image_sampler::CombinedImageSampler,
-    error::AssetLoaderError, mipmap_data::MipmapData,
+    animated_texture::{AnimatedTexture, AnimationFrame},
+    asset_loader::AssetLoader,
+    combined_image_sampler::CombinedImageSampler,
+    error::AssetLoaderError,
+    flipbook::{Flipbook, FlipbookPlayback},
+    mipmap_data::MipmapData,
+    texture_id::TextureId,
+    texture_info::TextureInfo,
+    texture_table::{TextureTable, TEXTURE_TABLE_SET},
 };
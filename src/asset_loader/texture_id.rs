@@ -0,0 +1,35 @@
+use ::std::fmt;
+
+/// Identifies a texture in the [`super::TextureTable`].
+///
+/// Ids are returned by the [`super::AssetLoader`] when textures are created,
+/// so a sprite or tile can't be handed some unrelated integer by mistake.
+/// Debug builds also check that every id drawn by a frame refers to a
+/// registered texture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct TextureId(i32);
+
+impl TextureId {
+    /// The 1x1 white pixel which every asset loader creates first. Drawing
+    /// with it is the same as drawing without a texture.
+    pub const WHITE_PIXEL: Self = Self(0);
+
+    /// The id for the texture at the given index in the texture table.
+    ///
+    /// Prefer the ids returned by the asset loader, this is for indices that
+    /// come from somewhere else, like a vertex which was already written.
+    pub fn from_index(index: i32) -> Self {
+        Self(index)
+    }
+
+    /// The texture's index in the texture table, as read by shaders.
+    pub fn index(self) -> i32 {
+        self.0
+    }
+}
+
+impl fmt::Display for TextureId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
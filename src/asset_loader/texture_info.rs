@@ -0,0 +1,56 @@
+use crate::asset_loader::TextureId;
+
+/// A description of a single texture owned by the [`super::AssetLoader`].
+///
+/// This is used for diagnostics, e.g. to list every texture and how much gpu
+/// memory it uses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureInfo {
+    /// A human-readable name for the texture. Textures read from disk use
+    /// their path.
+    pub name: String,
+
+    /// The width of the first mip level, in pixels.
+    pub width: u32,
+
+    /// The height of the first mip level, in pixels.
+    pub height: u32,
+
+    /// The number of mip levels in the texture.
+    pub mip_levels: u32,
+
+    /// The size of the gpu memory allocated for the texture, in bytes. Mip
+    /// level views share their source texture's memory so they report 0.
+    pub byte_size: u64,
+
+    /// When this texture is a view of a single mip level of another texture,
+    /// this is the source texture and the level.
+    pub mip_view: Option<(TextureId, u32)>,
+}
+
+impl TextureInfo {
+    /// The size of the given mip level, in pixels.
+    pub fn mip_extent(&self, level: u32) -> (u32, u32) {
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextureInfo;
+
+    #[test]
+    fn test_mip_extent() {
+        let info = TextureInfo {
+            name: "test".to_owned(),
+            width: 512,
+            height: 64,
+            mip_levels: 10,
+            byte_size: 0,
+            mip_view: None,
+        };
+        assert_eq!(info.mip_extent(0), (512, 64));
+        assert_eq!(info.mip_extent(3), (64, 8));
+        assert_eq!(info.mip_extent(9), (1, 1));
+    }
+}
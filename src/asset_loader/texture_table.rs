@@ -0,0 +1,224 @@
+use ::{
+    ash::vk,
+    std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use crate::{
+    asset_loader::CombinedImageSampler,
+    vulkan::{
+        errors::{VulkanDebugError, VulkanError},
+        CommandBuffer, DescriptorPool, DescriptorSet, DescriptorSetLayout,
+        PipelineLayout, RenderDevice, VulkanDebug,
+    },
+};
+
+/// The descriptor set index used by the texture table in every pipeline
+/// layout which samples textures.
+pub const TEXTURE_TABLE_SET: u32 = 0;
+
+/// The most textures the table will hold, devices with lower limits get a
+/// smaller table.
+const MAX_TEXTURES: u32 = 4096;
+
+/// A single device-level descriptor set with an array of every texture.
+///
+/// The set is bound once per frame at [`TEXTURE_TABLE_SET`], so every
+/// renderer samples textures with the same indices and no renderer keeps its
+/// own list of textures. The array is update-after-bind, so textures can be
+/// added while frames which use the table are still in flight.
+///
+/// Shaders declare the table as:
+///
+/// ```glsl
+/// layout(set = 0, binding = 0) uniform sampler2D textures[];
+/// ```
+///
+/// Pipeline layouts must use [`TextureTable::layout`] as their first
+/// descriptor set layout, otherwise binding the pipeline's own sets
+/// disturbs the table.
+pub struct TextureTable {
+    /// The descriptor set with every texture.
+    descriptor_set: DescriptorSet,
+
+    /// The pool owns the descriptor set.
+    descriptor_pool: DescriptorPool,
+
+    /// The layout every pipeline uses for set 0.
+    layout: Arc<DescriptorSetLayout>,
+
+    /// A pipeline layout with only the table, used to bind the set without
+    /// any particular pipeline.
+    pipeline_layout: PipelineLayout,
+
+    /// The number of textures the table can hold.
+    capacity: u32,
+
+    /// One more than the highest index written so far. It's shared with
+    /// renderers so debug builds can check texture ids as they're drawn.
+    registered: Arc<AtomicU32>,
+
+    vk_dev: Arc<RenderDevice>,
+}
+
+impl TextureTable {
+    /// Create an empty texture table sized for the device.
+    pub fn new(vk_dev: Arc<RenderDevice>) -> Result<Self, VulkanError> {
+        let capacity = supported_capacity(&vk_dev);
+        let layout = Arc::new(DescriptorSetLayout::new_with_create_flags(
+            vk_dev.clone(),
+            vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+            &[(
+                vk::DescriptorSetLayoutBinding {
+                    binding: 0,
+                    descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                    descriptor_count: capacity,
+                    stage_flags: vk::ShaderStageFlags::FRAGMENT
+                        | vk::ShaderStageFlags::COMPUTE,
+                    ..Default::default()
+                },
+                vk::DescriptorBindingFlags::PARTIALLY_BOUND
+                    | vk::DescriptorBindingFlags::UPDATE_AFTER_BIND
+                    | vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                    | vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT,
+            )],
+        )?);
+        let descriptor_pool = DescriptorPool::new_with_flags(
+            vk_dev.clone(),
+            vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND,
+            1,
+            &[vk::DescriptorPoolSize {
+                ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                descriptor_count: capacity,
+            }],
+        )?;
+        let descriptor_set = descriptor_pool
+            .allocate_with_variable_counts(&layout, 1, capacity)?
+            .pop()
+            .unwrap();
+        let pipeline_layout = PipelineLayout::new(
+            vk_dev.clone(),
+            std::slice::from_ref(&layout),
+            &[],
+        )?;
+        Ok(Self {
+            descriptor_set,
+            descriptor_pool,
+            layout,
+            pipeline_layout,
+            capacity,
+            registered: Arc::new(AtomicU32::new(0)),
+            vk_dev,
+        })
+    }
+
+    /// The descriptor set layout pipelines use for [`TEXTURE_TABLE_SET`].
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// The number of textures the table can hold.
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// The number of textures written to the table. Every index below this
+    /// refers to a texture.
+    pub fn registered_count(&self) -> u32 {
+        self.registered.load(Ordering::Relaxed)
+    }
+
+    /// A handle to the registered count which follows the table as textures
+    /// are added.
+    pub(crate) fn shared_registered_count(&self) -> Arc<AtomicU32> {
+        self.registered.clone()
+    }
+
+    /// Write a texture into the table.
+    ///
+    /// # Safety
+    ///
+    /// - The texture index must not be used by any frame which is still in
+    ///   flight. New indices are always safe to write.
+    /// - The texture must stay alive until every frame which samples it has
+    ///   finished rendering.
+    pub unsafe fn write(
+        &self,
+        texture_index: u32,
+        texture: &CombinedImageSampler,
+    ) {
+        self.descriptor_set.bind_combined_image_sampler(
+            0,
+            texture_index,
+            &texture.image_view,
+            &texture.sampler,
+        );
+        self.registered
+            .fetch_max(texture_index + 1, Ordering::Relaxed);
+    }
+
+    /// Bind the table at [`TEXTURE_TABLE_SET`]. The binding stays valid for
+    /// every pipeline which uses the table's layout for its first set.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer must be recording.
+    pub unsafe fn bind(
+        &self,
+        cmd: &CommandBuffer,
+        bind_point: vk::PipelineBindPoint,
+    ) {
+        self.vk_dev.logical_device.cmd_bind_descriptor_sets(
+            cmd.raw,
+            bind_point,
+            self.pipeline_layout.raw,
+            TEXTURE_TABLE_SET,
+            &[self.descriptor_set.raw],
+            &[],
+        );
+    }
+}
+
+impl VulkanDebug for TextureTable {
+    fn set_debug_name(
+        &self,
+        debug_name: impl Into<String>,
+    ) -> Result<(), VulkanDebugError> {
+        let name = debug_name.into();
+        self.layout
+            .set_debug_name(format!("{} - Descriptor Set Layout", name))?;
+        self.descriptor_pool
+            .set_debug_name(format!("{} - Descriptor Pool", name))?;
+        self.descriptor_set
+            .set_debug_name(format!("{} - Descriptor Set", name))?;
+        self.pipeline_layout
+            .set_debug_name(format!("{} - Pipeline Layout", name))?;
+        Ok(())
+    }
+}
+
+/// The largest table the device's update-after-bind limits allow, up to
+/// [`MAX_TEXTURES`].
+fn supported_capacity(vk_dev: &RenderDevice) -> u32 {
+    let mut indexing =
+        vk::PhysicalDeviceDescriptorIndexingProperties::default();
+    let mut properties = vk::PhysicalDeviceProperties2 {
+        p_next: &mut indexing
+            as *mut vk::PhysicalDeviceDescriptorIndexingProperties
+            as *mut std::ffi::c_void,
+        ..Default::default()
+    };
+    unsafe {
+        vk_dev.instance.ash.get_physical_device_properties2(
+            vk_dev.physical_device,
+            &mut properties,
+        );
+    }
+    MAX_TEXTURES
+        .min(indexing.max_per_stage_descriptor_update_after_bind_samplers)
+        .min(indexing.max_per_stage_descriptor_update_after_bind_sampled_images)
+        .min(indexing.max_descriptor_set_update_after_bind_samplers)
+        .min(indexing.max_descriptor_set_update_after_bind_sampled_images)
+}
@@ -0,0 +1,205 @@
+use ::std::{fmt, time::Duration};
+
+use crate::immediate_mode_graphics::triangles::FrameStats;
+
+/// Measurements for a single benchmarked frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FrameSample {
+    /// Time spent waiting for a swapchain image and the frame's fence. This
+    /// is GPU or presentation time rather than CPU work.
+    pub wait: Duration,
+
+    /// Time spent in the draw closure, including writing vertices and
+    /// indices into the frame's buffers.
+    pub draw: Duration,
+
+    /// Time spent recording the frame's draw commands.
+    pub record: Duration,
+
+    /// Time spent submitting the commands and presenting the image.
+    pub submit: Duration,
+
+    /// The geometry drawn by the frame.
+    pub stats: FrameStats,
+
+    /// The number of bytes of vertex and index data the frame wrote.
+    pub upload_bytes: u64,
+}
+
+impl FrameSample {
+    /// The frame's CPU time, every phase except waiting.
+    pub fn cpu_time(&self) -> Duration {
+        self.draw + self.record + self.submit
+    }
+}
+
+/// The distribution of one phase's time across every benchmarked frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct PhaseSummary {
+    pub mean: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl PhaseSummary {
+    fn new(mut durations: Vec<Duration>) -> Self {
+        if durations.is_empty() {
+            return Self::default();
+        }
+        durations.sort();
+        let percentile = |p: f32| {
+            durations[((durations.len() - 1) as f32 * p).round() as usize]
+        };
+        Self {
+            mean: durations.iter().sum::<Duration>() / durations.len() as u32,
+            median: percentile(0.5),
+            p95: percentile(0.95),
+            max: *durations.last().unwrap(),
+        }
+    }
+}
+
+/// The results of a [`super::Bench`] run. Warmup frames aren't included.
+///
+/// The report's Display impl prints a table with a row for each phase,
+/// followed by the throughput.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    pub name: String,
+    pub samples: Vec<FrameSample>,
+}
+
+impl BenchReport {
+    pub fn new(name: impl Into<String>, samples: Vec<FrameSample>) -> Self {
+        Self {
+            name: name.into(),
+            samples,
+        }
+    }
+
+    pub fn wait(&self) -> PhaseSummary {
+        self.summarize(|sample| sample.wait)
+    }
+
+    pub fn draw(&self) -> PhaseSummary {
+        self.summarize(|sample| sample.draw)
+    }
+
+    pub fn record(&self) -> PhaseSummary {
+        self.summarize(|sample| sample.record)
+    }
+
+    pub fn submit(&self) -> PhaseSummary {
+        self.summarize(|sample| sample.submit)
+    }
+
+    /// The distribution of each frame's total CPU time.
+    pub fn cpu(&self) -> PhaseSummary {
+        self.summarize(FrameSample::cpu_time)
+    }
+
+    /// Vertices drawn per second of CPU time.
+    pub fn vertices_per_second(&self) -> f64 {
+        let vertices: usize = self
+            .samples
+            .iter()
+            .map(|sample| sample.stats.vertices)
+            .sum();
+        vertices as f64 / self.total_cpu_seconds()
+    }
+
+    /// Bytes of vertex and index data written per second of CPU time.
+    pub fn upload_bytes_per_second(&self) -> f64 {
+        let bytes: u64 =
+            self.samples.iter().map(|sample| sample.upload_bytes).sum();
+        bytes as f64 / self.total_cpu_seconds()
+    }
+
+    fn summarize<F>(&self, phase: F) -> PhaseSummary
+    where
+        F: Fn(&FrameSample) -> Duration,
+    {
+        PhaseSummary::new(self.samples.iter().map(phase).collect())
+    }
+
+    /// Never zero, so rates are finite even for frames which did nothing.
+    fn total_cpu_seconds(&self) -> f64 {
+        let total: Duration = self.samples.iter().map(|s| s.cpu_time()).sum();
+        total.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} frames)", self.name, self.samples.len())?;
+        writeln!(
+            f,
+            "{:<8} {:>12} {:>12} {:>12} {:>12}",
+            "phase", "mean", "median", "p95", "max"
+        )?;
+        let phases = [
+            ("wait", self.wait()),
+            ("draw", self.draw()),
+            ("record", self.record()),
+            ("submit", self.submit()),
+            ("cpu", self.cpu()),
+        ];
+        for (name, summary) in phases {
+            writeln!(
+                f,
+                "{:<8} {:>12?} {:>12?} {:>12?} {:>12?}",
+                name, summary.mean, summary.median, summary.p95, summary.max
+            )?;
+        }
+        writeln!(
+            f,
+            "{:.3} million vertices/sec",
+            self.vertices_per_second() / 1e6
+        )?;
+        write!(
+            f,
+            "{:.1} MiB/sec uploaded",
+            self.upload_bytes_per_second() / (1024.0 * 1024.0)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample(draw_ms: u64, vertices: usize) -> FrameSample {
+        FrameSample {
+            draw: Duration::from_millis(draw_ms),
+            stats: FrameStats {
+                vertices,
+                triangles: vertices / 3,
+                draw_calls: 1,
+            },
+            upload_bytes: 1024 * vertices as u64,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_report_summarizes_samples() {
+        let report = BenchReport::new(
+            "quads",
+            (1..=20).map(|ms| sample(ms, 3000)).collect(),
+        );
+        let draw = report.draw();
+        assert_eq!(draw.max, Duration::from_millis(20));
+        assert_eq!(draw.p95, Duration::from_millis(19));
+        assert_eq!(draw.mean, Duration::from_micros(10_500));
+
+        // 60k vertices in 210ms of cpu time
+        let rate = report.vertices_per_second();
+        assert!((rate - 60_000.0 / 0.21).abs() < 1.0);
+        assert!(report.to_string().starts_with("quads (20 frames)"));
+
+        let empty = BenchReport::new("empty", vec![]);
+        assert_eq!(empty.cpu(), PhaseSummary::default());
+        assert_eq!(empty.vertices_per_second(), 0.0);
+    }
+}
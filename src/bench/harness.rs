@@ -0,0 +1,154 @@
+use ::{
+    anyhow::{Context, Result},
+    ash::vk,
+    std::{mem::size_of, sync::Arc, time::Instant},
+};
+
+use crate::{
+    asset_loader::AssetLoader,
+    bench::{BenchReport, FrameSample},
+    builder_field, builder_field_into,
+    frame_pipeline::FramePipeline,
+    glfw_window::GlfwWindow,
+    immediate_mode_graphics::{triangles::Frame, triangles::Triangles, Vertex},
+    math::DepthMode,
+    multisample_renderpass::MultisampleRenderpass,
+    vulkan,
+};
+
+/// A Bench renders a draw closure in a hidden window for a fixed number of
+/// frames, as fast as the swapchain allows, then reports the time spent in
+/// each part of the frame.
+///
+/// Frames start with a few warmup frames which aren't measured, so buffers
+/// have grown to their final size before timing starts.
+///
+/// Print the report or compare its numbers before and after a change, e.g.
+/// drawing ten thousand sprites or a long paragraph of text each frame.
+pub struct Bench {
+    /// The benchmark's name, used for the window title and the report.
+    name: String,
+
+    /// The number of frames which are measured.
+    frames: u32,
+
+    /// The number of frames drawn before measuring starts.
+    warmup_frames: u32,
+
+    /// The desired number of samples for the render pass.
+    msaa_samples: vk::SampleCountFlags,
+}
+
+impl Bench {
+    /// Create a benchmark which measures 300 frames after 30 warmup frames,
+    /// with 4xMSAA like the demo application.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            frames: 300,
+            warmup_frames: 30,
+            msaa_samples: vk::SampleCountFlags::TYPE_4,
+        }
+    }
+
+    builder_field_into!(name, String);
+    builder_field!(frames, u32);
+    builder_field!(warmup_frames, u32);
+    builder_field!(msaa_samples, vk::SampleCountFlags);
+
+    /// Run the benchmark.
+    ///
+    /// The setup closure runs once, before any frames, and can load fonts
+    /// and textures. Its result is given to the draw closure each frame
+    /// along with the frame number, which counts the warmup frames too.
+    pub fn run<T, Setup, Draw>(
+        self,
+        setup: Setup,
+        mut draw: Draw,
+    ) -> Result<BenchReport>
+    where
+        Setup: FnOnce(&mut AssetLoader) -> Result<T>,
+        Draw: FnMut(&mut T, &mut Frame, u32) -> Result<()>,
+    {
+        let mut glfw_window = GlfwWindow::new_hidden(&self.name)?;
+        let vk_dev = Arc::new(glfw_window.create_vulkan_device()?);
+        let vk_alloc = vulkan::create_default_allocator(vk_dev.clone());
+        let mut frame_pipeline = FramePipeline::new(vk_dev.clone())?;
+        let msaa_renderpass =
+            MultisampleRenderpass::for_current_swapchain_with_samples(
+                vk_dev.clone(),
+                vk_alloc.clone(),
+                self.msaa_samples,
+            )?;
+        let framebuffers = msaa_renderpass.create_swapchain_framebuffers()?;
+        let mut asset_loader =
+            AssetLoader::new(vk_dev.clone(), vk_alloc.clone())?;
+        let mut data = setup(&mut asset_loader)
+            .with_context(|| format!("while setting up {}", self.name))?;
+        let mut triangles = Triangles::new(
+            &msaa_renderpass,
+            asset_loader.texture_table(),
+            vk_alloc.clone(),
+            vk_dev.clone(),
+        )?;
+
+        let mut samples = Vec::with_capacity(self.frames as usize);
+        for frame_number in 0..self.warmup_frames + self.frames {
+            glfw_window.glfw.poll_events();
+
+            let start = Instant::now();
+            let (index, _) = frame_pipeline.begin_frame()?;
+            let wait = start.elapsed();
+
+            let start = Instant::now();
+            let cmds = frame_pipeline.frame_cmds(index);
+            unsafe {
+                msaa_renderpass.begin_renderpass_inline(
+                    cmds,
+                    &framebuffers[index],
+                    [0.0, 0.0, 0.0, 1.0],
+                    DepthMode::Standard.clear_depth(),
+                );
+                asset_loader
+                    .texture_table()
+                    .bind(cmds, vk::PipelineBindPoint::GRAPHICS);
+            }
+            let mut frame = triangles.acquire_frame(index)?;
+            draw(&mut data, &mut frame, frame_number).with_context(|| {
+                format!("while drawing frame {} of {}", frame_number, self.name)
+            })?;
+            let stats = frame.stats();
+            let draw_time = start.elapsed();
+
+            let start = Instant::now();
+            unsafe {
+                triangles.complete_frame(cmds, frame, index)?;
+                msaa_renderpass.end_renderpass(cmds);
+            }
+            let record = start.elapsed();
+
+            let start = Instant::now();
+            frame_pipeline.end_frame(index)?;
+            let submit = start.elapsed();
+
+            if frame_number >= self.warmup_frames {
+                samples.push(FrameSample {
+                    wait,
+                    draw: draw_time,
+                    record,
+                    submit,
+                    stats,
+                    upload_bytes: (stats.vertices * size_of::<Vertex>()
+                        + 3 * stats.triangles * size_of::<u32>())
+                        as u64,
+                });
+            }
+        }
+
+        // nothing can be destroyed while the last frames are in flight
+        unsafe {
+            vk_dev.logical_device.device_wait_idle()?;
+        }
+        Ok(BenchReport::new(self.name, samples))
+    }
+}
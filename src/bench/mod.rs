@@ -0,0 +1,15 @@
+//! A benchmark harness for the immediate mode renderer.
+//!
+//! [`Bench`] renders a draw closure into a hidden window for a fixed number
+//! of frames and reports where each frame's CPU time went, along with the
+//! geometry throughput. It's meant for measuring changes to
+//! [`crate::immediate_mode_graphics::triangles::Frame`], GpuVec, and text
+//! layout, so anything drawn with the UI or Triangles can be benchmarked.
+
+mod bench_report;
+mod harness;
+
+pub use self::{
+    bench_report::{BenchReport, FrameSample, PhaseSummary},
+    harness::Bench,
+};
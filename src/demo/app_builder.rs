@@ -0,0 +1,324 @@
+use ::{
+    anyhow::{Context, Result},
+    ash::vk,
+    std::{path::PathBuf, sync::Arc, time::Duration},
+};
+
+use crate::{
+    asset_loader::TextureTable,
+    builder_field, builder_field_into, builder_field_some,
+    demo::{
+        multiline_format, Application, BackgroundThrottle, RenderPlugin,
+        RenderPluginFactory, State, VisualRegression,
+    },
+    math::{CoordinateSystem, Culling, DepthMode, DesignResolution},
+    multisample_renderpass::MultisampleRenderpass,
+    vulkan::{MemoryAllocator, RenderDevice},
+};
+
+/// An AppBuilder configures the demo [`Application`] before it's created.
+///
+/// Framebuffer resize, window position, focus, iconify, and close events are
+/// always delivered to the State, every other kind of event can be toggled.
+pub struct AppBuilder<S: State> {
+    /// The window's title.
+    pub(super) title: String,
+
+    /// The fps limit used when the application starts. The State can change
+    /// it in `State::init`.
+    pub(super) target_fps: u32,
+
+    /// When set, the fps limit follows the current monitor's refresh rate
+    /// divided by this value instead of the target fps. See
+    /// [`crate::timing::FrameRateLimit::match_refresh_rate`].
+    pub(super) refresh_rate_divisor: Option<u32>,
+
+    /// How to throttle rendering while the window is unfocused or
+    /// minimized. The State can override it, see `State::enter_background`.
+    pub(super) background_throttle: BackgroundThrottle,
+
+    /// The desired number of samples for the render pass. The device's max
+    /// is used when this isn't supported.
+    pub(super) msaa_samples: vk::SampleCountFlags,
+
+    /// The color used to clear the screen each frame.
+    pub(super) clear_color: [f32; 4],
+
+    /// When true, the window's alpha channel is blended with the desktop,
+    /// see [`crate::glfw_window::GlfwWindow::new_transparent`]. Use a clear
+    /// color with a low alpha to see through the window.
+    pub(super) transparent_window: bool,
+
+    /// When true, the application creates the app and ui Triangles layers.
+    /// Without them `State::draw_frame` is never called, so this is only
+    /// useful for applications which render entirely with plugins.
+    pub(super) triangle_layers: bool,
+
+    /// The coordinate system used by the app layer's frames. The UI layer
+    /// always uses [`CoordinateSystem::YDown`].
+    pub(super) coordinate_system: CoordinateSystem,
+
+    /// Which triangles the app layer draws, based on their winding in the
+    /// app layer's coordinate system. The UI layer always draws both sides.
+    pub(super) culling: Culling,
+
+    /// When set, the app layer renders in design coordinates which are fit
+    /// to the window, see [`DesignResolution`]. The UI layer always covers
+    /// the whole window.
+    pub(super) design_resolution: Option<DesignResolution>,
+
+    /// How the depth buffer is cleared each frame. Render plugins which
+    /// depth test should build their pipelines and projections to match.
+    pub(super) depth_mode: DepthMode,
+
+    /// How long sliced jobs can run on the main thread each frame, see
+    /// [`crate::jobs::Jobs::run_frame`].
+    pub(super) job_frame_budget: Duration,
+
+    /// When true, key events are delivered to the State.
+    pub(super) key_polling: bool,
+
+    /// When true, text input events are delivered to the State.
+    pub(super) char_polling: bool,
+
+    /// When true, text input events with the modifier keys held while typing
+    /// are delivered to the State.
+    pub(super) char_mods_polling: bool,
+
+    /// When true, cursor position events are delivered to the State.
+    pub(super) cursor_pos_polling: bool,
+
+    /// When true, mouse button events are delivered to the State.
+    pub(super) mouse_button_polling: bool,
+
+    /// When true, scroll events are delivered to the State.
+    pub(super) scroll_polling: bool,
+
+    /// The font file and scale used by the diagnostics overlay. None
+    /// disables the overlay.
+    pub(super) diagnostics_font: Option<(PathBuf, f32)>,
+
+    /// The key which shows and hides the diagnostics overlay.
+    pub(super) diagnostics_key: glfw::Key,
+
+    /// The key which copies the last presented frame to the system
+    /// clipboard as an image. None disables it.
+    pub(super) copy_screenshot_key: Option<glfw::Key>,
+
+    /// Fragment shader effects added to both Triangles layers, in order, so
+    /// the nth effect has `EffectId::from_index(n)` on either layer.
+    pub(super) effects: Vec<&'static [u8]>,
+
+    /// Functions which create custom renderers after the render pass exists.
+    pub(super) plugins: Vec<RenderPluginFactory>,
+
+    /// When true, a panic hook waits for the device to go idle and logs
+    /// device information with the backtrace.
+    pub(super) panic_hook: bool,
+
+    /// Where crash reports are written when a frame panics. None disables
+    /// crash reports.
+    pub(super) crash_report_directory: Option<PathBuf>,
+
+    /// When set, the application renders in a hidden window and exits after
+    /// comparing a frame with its baseline.
+    pub(super) visual_regression: Option<VisualRegression>,
+
+    _phantom_data: std::marker::PhantomData<S>,
+}
+
+impl<S: State> AppBuilder<S> {
+    /// Create a builder with the default configuration: 60 fps, 4xMSAA, the
+    /// Triangles layers, and key, text, cursor, mouse button, and scroll
+    /// events.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            target_fps: 60,
+            refresh_rate_divisor: None,
+            background_throttle: BackgroundThrottle::Off,
+            msaa_samples: vk::SampleCountFlags::TYPE_4,
+            clear_color: [0.05, 0.05, 0.05, 1.0],
+            transparent_window: false,
+            triangle_layers: true,
+            coordinate_system: CoordinateSystem::YDown,
+            culling: Culling::DoubleSided,
+            design_resolution: None,
+            depth_mode: DepthMode::Standard,
+            job_frame_budget: Duration::from_millis(4),
+            key_polling: true,
+            char_polling: true,
+            char_mods_polling: false,
+            cursor_pos_polling: true,
+            mouse_button_polling: true,
+            scroll_polling: true,
+            diagnostics_font: None,
+            diagnostics_key: glfw::Key::F3,
+            copy_screenshot_key: None,
+            effects: vec![],
+            plugins: vec![],
+            panic_hook: false,
+            crash_report_directory: None,
+            visual_regression: None,
+            _phantom_data: Default::default(),
+        }
+    }
+
+    builder_field_into!(title, String);
+    builder_field!(target_fps, u32);
+    builder_field_some!(refresh_rate_divisor, u32);
+    builder_field!(background_throttle, BackgroundThrottle);
+    builder_field!(msaa_samples, vk::SampleCountFlags);
+    builder_field!(clear_color, [f32; 4]);
+    builder_field!(transparent_window, bool);
+    builder_field!(triangle_layers, bool);
+    builder_field!(coordinate_system, CoordinateSystem);
+    builder_field!(culling, Culling);
+    builder_field_some!(design_resolution, DesignResolution);
+    builder_field!(depth_mode, DepthMode);
+    builder_field!(job_frame_budget, Duration);
+    builder_field!(key_polling, bool);
+    builder_field!(char_polling, bool);
+    builder_field!(char_mods_polling, bool);
+    builder_field!(cursor_pos_polling, bool);
+    builder_field!(mouse_button_polling, bool);
+    builder_field!(scroll_polling, bool);
+    builder_field!(panic_hook, bool);
+    builder_field!(diagnostics_key, glfw::Key);
+
+    /// Copy the last presented frame to the system clipboard when the key is
+    /// pressed, see [`crate::glfw_window::GlfwClipboard`] for the platforms
+    /// which support images. Every frame is captured so the latest one is
+    /// ready to copy.
+    ///
+    /// The hotkey needs key polling, and the key is still delivered to the
+    /// State.
+    pub fn copy_screenshot_key(self, key: glfw::Key) -> Self {
+        Self {
+            copy_screenshot_key: Some(key),
+            ..self
+        }
+    }
+
+    /// Enable the diagnostics overlay, a
+    /// [`crate::ui::widgets::Diagnostics`] panel with the frame rate, recent
+    /// frame times, and the Triangles layers' geometry counts. It's drawn on
+    /// top of the ui layer and toggled with the diagnostics key, F3 unless
+    /// it's changed with [`Self::diagnostics_key`].
+    ///
+    /// The hotkey needs key polling, and the key is still delivered to the
+    /// State.
+    pub fn diagnostics(
+        self,
+        font_path: impl Into<PathBuf>,
+        font_scale: f32,
+    ) -> Self {
+        Self {
+            diagnostics_font: Some((font_path.into(), font_scale)),
+            ..self
+        }
+    }
+
+    /// Add a fragment shader effect to the app and ui layers. Effects get ids
+    /// in the order they're added, starting at 0. See
+    /// [`crate::immediate_mode_graphics::triangles::Triangles::add_effect`]
+    /// for the shader's interface.
+    pub fn effect(mut self, fragment_shader: &'static [u8]) -> Self {
+        self.effects.push(fragment_shader);
+        self
+    }
+
+    /// Add a custom renderer to the application. The plugin is created with
+    /// `RenderPlugin::create` once the render pass exists.
+    pub fn render_plugin<P>(self) -> Self
+    where
+        P: 'static + RenderPlugin,
+    {
+        self.render_plugin_with(
+            |msaa_renderpass, texture_table, vk_dev, vk_alloc| {
+                let plugin = P::create(
+                    msaa_renderpass,
+                    texture_table,
+                    vk_dev,
+                    vk_alloc,
+                )?;
+                Ok(Box::new(plugin))
+            },
+        )
+    }
+
+    /// Add a custom renderer to the application which is built by the given
+    /// function instead of `RenderPlugin::create`. This is useful when the
+    /// plugin needs extra configuration.
+    pub fn render_plugin_with<F>(mut self, factory: F) -> Self
+    where
+        F: 'static
+            + FnOnce(
+                &MultisampleRenderpass,
+                &TextureTable,
+                &Arc<RenderDevice>,
+                &Arc<dyn MemoryAllocator>,
+            ) -> Result<Box<dyn RenderPlugin>>,
+    {
+        self.plugins.push(Box::new(factory));
+        self
+    }
+
+    /// Write a crash report into the directory if composing a frame panics.
+    /// Each report is a new folder with the panic message, the most recent
+    /// log messages, and an image of the last presented frame.
+    ///
+    /// Every frame is copied back from the gpu so it's available for the
+    /// report, which costs some performance.
+    pub fn crash_reports(self, directory: impl Into<PathBuf>) -> Self {
+        Self {
+            crash_report_directory: Some(directory.into()),
+            ..self
+        }
+    }
+
+    /// Render in a hidden window for the check's number of frames, then
+    /// compare the last frame with its baseline and exit. Running the
+    /// application fails if the frame doesn't match.
+    ///
+    /// [`AppBuilder::run`] enables this automatically when the
+    /// [`crate::demo::VISUAL_REGRESSION_ENV`] environment variable is set.
+    pub fn visual_regression(
+        self,
+        visual_regression: VisualRegression,
+    ) -> Self {
+        Self {
+            visual_regression: Some(visual_regression),
+            ..self
+        }
+    }
+
+    /// Create the application.
+    pub fn build(self) -> Result<Application<S>> {
+        Application::from_builder(self)
+    }
+
+    /// Create and run the application with pretty multiline logging. Blocks
+    /// until the application exits and logs the error if it fails.
+    pub fn run(mut self) -> Result<()> {
+        multiline_format::enable_multiline_logging()?;
+        if self.visual_regression.is_none() {
+            self.visual_regression = VisualRegression::from_env(&self.title);
+        }
+
+        let result = self
+            .build()
+            .context("failed to construct the application!")?
+            .run()
+            .context("application exited with an error");
+
+        if let Err(ref error) = result {
+            log::error!(
+                "Application exited unsuccessfully!\n{:?}\n\nroot cause: {:?}",
+                error,
+                error.root_cause()
+            );
+        }
+        result
+    }
+}
@@ -2,8 +2,11 @@ use ::{anyhow::Result, std::sync::Arc};
 
 use crate::{
     asset_loader::AssetLoader,
+    demo::{BackgroundThrottle, SwapchainRegistry, UILayers},
+    frame_pipeline::PixelColor,
     glfw_window::GlfwWindow,
     immediate_mode_graphics::triangles::Frame,
+    jobs::{JobEvent, Jobs},
     timing::FrameRateLimit,
     vulkan::{MemoryAllocator, RenderDevice},
 };
@@ -37,7 +40,72 @@ pub trait State {
         Ok(())
     }
 
-    /// Handle GLFW window events.
+    /// Register any swapchain-dependent resources owned by the state. They're
+    /// rebuilt automatically, in dependency order, after the application's
+    /// own resources and before `rebuild_swapchain_resources` is called.
+    ///
+    /// Called once, right after `init`. Resources which are also used for
+    /// rendering can be shared with an `Rc<RefCell<_>>`.
+    fn register_swapchain_dependents(
+        &mut self,
+        _registry: &mut SwapchainRegistry,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Register UIs for the application to handle, see [`UILayers`]. They're
+    /// drawn on the ui layer after `draw_frame`, and events they capture
+    /// aren't delivered to `handle_event`.
+    ///
+    /// Called once, right after `init`. Keep an `Rc<RefCell<UI<_>>>` to each
+    /// UI to reach its state.
+    fn register_ui_layers(&mut self, _layers: &mut UILayers) -> Result<()> {
+        Ok(())
+    }
+
+    /// Keep a clone of the application's [`Jobs`] to run expensive work
+    /// without hitching the render loop.
+    ///
+    /// Called once, right after `init`.
+    fn register_jobs(&mut self, _jobs: &Jobs) -> Result<()> {
+        Ok(())
+    }
+
+    /// Handle the progress and results of jobs. The application runs its
+    /// jobs once per frame, before the frame is drawn, even while it's
+    /// paused or in the background.
+    fn handle_job_event(&mut self, _event: JobEvent) -> Result<()> {
+        Ok(())
+    }
+
+    /// Request a single rendered pixel, in framebuffer pixels. This is
+    /// checked once per frame after the frame has been drawn.
+    fn pixel_readback_request(&mut self) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Handle a pixel which was read back from a previous frame. Readback
+    /// completes a few frames after the request.
+    fn handle_pixel_readback(&mut self, _pixel: PixelColor) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the window loses focus or is minimized, with the
+    /// throttle configured by the `AppBuilder`. The returned throttle is
+    /// used until the window is back in the foreground, so a simulation
+    /// which needs to keep running can return [`BackgroundThrottle::Off`].
+    ///
+    /// Focus and iconify events are still delivered to `handle_event`, so
+    /// the state can tell when the window returns.
+    fn enter_background(
+        &mut self,
+        throttle: BackgroundThrottle,
+    ) -> Result<BackgroundThrottle> {
+        Ok(throttle)
+    }
+
+    /// Handle GLFW window events. Events captured by one of the State's
+    /// [`UILayers`] aren't delivered.
     fn handle_event(
         &mut self,
         _event: glfw::WindowEvent,
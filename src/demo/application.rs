@@ -1,30 +1,107 @@
 use ::{
     anyhow::{Context, Result},
-    std::sync::Arc,
+    ash::vk,
+    std::{
+        panic::{self, AssertUnwindSafe},
+        path::PathBuf,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
 };
 
 use crate::{
     asset_loader::AssetLoader,
-    demo::State,
-    frame_pipeline::{FrameError, FramePipeline},
-    glfw_window::GlfwWindow,
+    demo::{
+        background_throttle::SUSPENDED_EVENT_FPS,
+        crash_report::write_crash_report,
+        diagnostics_overlay::DiagnosticsOverlay,
+        panic_hook::install_panic_hook, swapchain_dependent::rebuild_and_log,
+        AppBuilder, BackgroundThrottle, RebuildContext, RenderPlugin, State,
+        SwapchainRegistry, UILayers, VisualRegression,
+    },
+    frame_pipeline::{FrameCapture, FrameError, FramePipeline, PixelReadback},
+    glfw_window::{clipboard_image, GlfwWindow},
     immediate_mode_graphics::triangles::Triangles,
+    jobs::{JobEvent, JobId, Jobs},
+    math::{Culling, DepthMode},
     multisample_renderpass::MultisampleRenderpass,
     timing::FrameRateLimit,
-    vulkan::{self, Framebuffer, MemoryAllocator, RenderDevice},
+    ui::{Font, WindowCursor},
+    vulkan::{self, Framebuffer, MemoryAllocator, RenderDevice, VulkanDebug},
 };
 
+/// The built-in Triangles renderers. The app layer is drawn first, then the
+/// ui layer is drawn on top of it.
+struct Layers {
+    app: Triangles,
+    ui: Triangles,
+}
+
 pub struct Application<S: State> {
     // Application state and support
     state: S,
     fps_limit: FrameRateLimit,
     paused: bool,
 
+    /// The throttle configured by the AppBuilder, the State can replace it
+    /// each time the window enters the background.
+    background_throttle: BackgroundThrottle,
+
+    /// The throttle in use while the window is in the background, None
+    /// while it's in the foreground.
+    active_throttle: Option<BackgroundThrottle>,
+
+    /// Limits the frame rate while the window is throttled.
+    background_fps_limit: FrameRateLimit,
+    focused: bool,
+    iconified: bool,
+
+    clear_color: [f32; 4],
+    depth_mode: DepthMode,
+    msaa_samples: vk::SampleCountFlags,
+
+    /// Expensive work started by the State, run once per frame.
+    jobs: Jobs,
+    job_frame_budget: Duration,
+
+    /// Frame rate and geometry stats drawn on top of the ui layer, if the
+    /// AppBuilder enabled them.
+    diagnostics: Option<DiagnosticsOverlay>,
+
     // Vulkan resources
     frame_pipeline: FramePipeline,
-    ui_layer: Triangles,
-    app_layer: Triangles,
-    _asset_loader: AssetLoader,
+    pixel_readback: PixelReadback,
+
+    /// Every frame is captured for crash reports and visual regression
+    /// checks, otherwise only the frame after the screenshot key is pressed.
+    /// None when none of them are enabled.
+    frame_capture: Option<FrameCapture>,
+    crash_report_directory: Option<PathBuf>,
+    visual_regression: Option<VisualRegression>,
+    frames_presented: u32,
+
+    /// The key which copies the next frame to the clipboard.
+    copy_screenshot_key: Option<glfw::Key>,
+
+    /// True from a press of the screenshot key until the next frame is
+    /// presented.
+    screenshot_requested: bool,
+
+    /// The job copying a screenshot to the clipboard. Its events aren't
+    /// delivered to the State.
+    screenshot_job: Option<JobId>,
+
+    plugins: Vec<Box<dyn RenderPlugin>>,
+    swapchain_registry: SwapchainRegistry,
+    ui_layers: UILayers,
+
+    /// Shows the cursor shape the ui layers ask for.
+    window_cursor: WindowCursor,
+    layers: Option<Layers>,
+    asset_loader: AssetLoader,
+
+    /// When the asset loader's animations were last advanced.
+    last_animation_advance: Instant,
     msaa_renderpass: MultisampleRenderpass,
     framebuffers: Vec<Framebuffer>,
     swapchain_needs_rebuild: bool,
@@ -36,61 +113,181 @@ pub struct Application<S: State> {
 }
 
 impl<S: State> Application<S> {
-    /// Create a new application instance.
+    /// Create a new application instance with the default configuration.
     pub fn new() -> Result<Self> {
-        let mut glfw_window = GlfwWindow::new("Swapchain")?;
+        AppBuilder::new("Swapchain").build()
+    }
+
+    /// Create a new application instance with the builder's configuration.
+    pub fn from_builder(builder: AppBuilder<S>) -> Result<Self> {
+        let mut glfw_window = if builder.visual_regression.is_some() {
+            GlfwWindow::new_hidden(&builder.title)?
+        } else if builder.transparent_window {
+            GlfwWindow::new_transparent(&builder.title)?
+        } else {
+            GlfwWindow::new(&builder.title)?
+        };
         let vk_dev = Arc::new(glfw_window.create_vulkan_device()?);
+        if builder.panic_hook {
+            install_panic_hook(&vk_dev);
+        }
         let vk_alloc = vulkan::create_default_allocator(vk_dev.clone());
         let frame_pipeline = FramePipeline::new(vk_dev.clone())?;
-        let mut fps_limit = FrameRateLimit::new(60, 30);
+        let pixel_readback =
+            PixelReadback::new(vk_dev.clone(), vk_alloc.clone())?;
+        let frame_capture = if builder.crash_report_directory.is_some()
+            || builder.visual_regression.is_some()
+            || builder.copy_screenshot_key.is_some()
+        {
+            Some(FrameCapture::new(vk_dev.clone(), vk_alloc.clone())?)
+        } else {
+            None
+        };
+        let mut fps_limit = FrameRateLimit::new(builder.target_fps, 30);
+        if let Some(divisor) = builder.refresh_rate_divisor {
+            fps_limit.match_refresh_rate(divisor);
+        }
+        if let Some(rate) = glfw_window.current_refresh_rate() {
+            fps_limit.set_refresh_rate(rate);
+        }
 
-        glfw_window.window.set_key_polling(true);
-        glfw_window.window.set_framebuffer_size_polling(true);
-        glfw_window.window.set_cursor_pos_polling(true);
-        glfw_window.window.set_mouse_button_polling(true);
+        let window = &mut glfw_window.window;
+        window.set_framebuffer_size_polling(true);
+        window.set_focus_polling(true);
+        window.set_iconify_polling(true);
+        // the refresh rate can change when the window moves to another
+        // monitor
+        window.set_pos_polling(true);
+        window.set_key_polling(builder.key_polling);
+        window.set_char_polling(builder.char_polling);
+        window.set_char_mods_polling(builder.char_mods_polling);
+        window.set_cursor_pos_polling(builder.cursor_pos_polling);
+        window.set_mouse_button_polling(builder.mouse_button_polling);
+        window.set_scroll_polling(builder.scroll_polling);
 
-        let msaa_renderpass = MultisampleRenderpass::for_current_swapchain(
-            vk_dev.clone(),
-            vk_alloc.clone(),
-        )?;
+        let msaa_renderpass =
+            MultisampleRenderpass::for_current_swapchain_with_samples(
+                vk_dev.clone(),
+                vk_alloc.clone(),
+                builder.msaa_samples,
+            )?;
         let framebuffers = msaa_renderpass.create_swapchain_framebuffers()?;
         let mut asset_loader =
             AssetLoader::new(vk_dev.clone(), vk_alloc.clone())?;
 
-        let state = S::init(
+        let mut state = S::init(
             &mut glfw_window,
             &mut fps_limit,
             &mut asset_loader,
             &vk_dev,
             &vk_alloc,
         )?;
+        let diagnostics = match builder.diagnostics_font {
+            Some((path, scale)) => {
+                let font =
+                    Font::from_font_file(&path, scale, &mut asset_loader)
+                        .with_context(|| {
+                            format!(
+                                "unable to load diagnostics font {:?}",
+                                path
+                            )
+                        })?;
+                Some(DiagnosticsOverlay::new(font, builder.diagnostics_key))
+            }
+            None => None,
+        };
+        let mut swapchain_registry = SwapchainRegistry::new();
+        state.register_swapchain_dependents(&mut swapchain_registry)?;
+        let mut ui_layers = UILayers::new();
+        state.register_ui_layers(&mut ui_layers)?;
+        let jobs = Jobs::new();
+        state.register_jobs(&jobs)?;
 
-        let ui_layer = Triangles::new(
-            &msaa_renderpass,
-            asset_loader.textures(),
-            vk_alloc.clone(),
-            vk_dev.clone(),
-        )?;
-        let app_layer = Triangles::new(
-            &msaa_renderpass,
-            asset_loader.textures(),
-            vk_alloc.clone(),
-            vk_dev.clone(),
-        )?;
+        let layers = if builder.triangle_layers {
+            let mut layers = Layers {
+                app: Triangles::new(
+                    &msaa_renderpass,
+                    asset_loader.texture_table(),
+                    vk_alloc.clone(),
+                    vk_dev.clone(),
+                )?,
+                ui: Triangles::new(
+                    &msaa_renderpass,
+                    asset_loader.texture_table(),
+                    vk_alloc.clone(),
+                    vk_dev.clone(),
+                )?,
+            };
+            layers.app.set_coordinate_system(builder.coordinate_system);
+            if builder.culling != Culling::DoubleSided {
+                layers.app.set_culling(&msaa_renderpass, builder.culling)?;
+            }
+            layers.app.set_design_resolution(builder.design_resolution);
+            if builder.depth_mode != DepthMode::Standard {
+                layers
+                    .app
+                    .set_depth_mode(&msaa_renderpass, builder.depth_mode)?;
+                layers
+                    .ui
+                    .set_depth_mode(&msaa_renderpass, builder.depth_mode)?;
+            }
+            for fragment_shader in &builder.effects {
+                layers.app.add_effect(&msaa_renderpass, fragment_shader)?;
+                layers.ui.add_effect(&msaa_renderpass, fragment_shader)?;
+            }
+            layers.app.set_debug_name("App Layer")?;
+            layers.ui.set_debug_name("UI Layer")?;
+            Some(layers)
+        } else {
+            None
+        };
+
+        let mut plugins = Vec::with_capacity(builder.plugins.len());
+        for factory in builder.plugins {
+            plugins.push(factory(
+                &msaa_renderpass,
+                asset_loader.texture_table(),
+                &vk_dev,
+                &vk_alloc,
+            )?);
+        }
 
         Ok(Self {
             // application state
             state,
             fps_limit,
             paused: false,
+            background_throttle: builder.background_throttle,
+            active_throttle: None,
+            background_fps_limit: FrameRateLimit::new(SUSPENDED_EVENT_FPS, 1),
+            focused: true,
+            iconified: false,
+            clear_color: builder.clear_color,
+            depth_mode: builder.depth_mode,
+            msaa_samples: builder.msaa_samples,
+            jobs,
+            job_frame_budget: builder.job_frame_budget,
+            diagnostics,
 
             // vulkan resources
             frame_pipeline,
+            pixel_readback,
+            frame_capture,
+            crash_report_directory: builder.crash_report_directory,
+            visual_regression: builder.visual_regression,
+            frames_presented: 0,
+            copy_screenshot_key: builder.copy_screenshot_key,
+            screenshot_requested: false,
+            screenshot_job: None,
+            plugins,
+            swapchain_registry,
+            ui_layers,
+            window_cursor: WindowCursor::default(),
             msaa_renderpass,
             framebuffers,
-            ui_layer,
-            app_layer,
-            _asset_loader: asset_loader,
+            layers,
+            asset_loader,
+            last_animation_advance: Instant::now(),
             swapchain_needs_rebuild: true,
             vk_dev,
             vk_alloc,
@@ -105,6 +302,7 @@ impl<S: State> Application<S> {
         let event_receiver = self.glfw_window.take_event_receiver()?;
         while !self.glfw_window.window.should_close() {
             self.fps_limit.start_frame();
+            self.background_fps_limit.start_frame();
             for (_, event) in
                 self.glfw_window.flush_window_events(&event_receiver)
             {
@@ -114,51 +312,311 @@ impl<S: State> Application<S> {
                 self.rebuild_swapchain_resources()?;
                 self.swapchain_needs_rebuild = false;
             }
-            if !self.paused {
-                let result = self.compose_frame();
+            for event in self.jobs.run_frame(self.job_frame_budget) {
+                if Some(event.id()) == self.screenshot_job {
+                    self.screenshot_job_event(event);
+                } else {
+                    self.state.handle_job_event(event)?;
+                }
+            }
+            let throttle = self.active_throttle.unwrap_or_default();
+            if !self.paused && throttle != BackgroundThrottle::Suspend {
+                let now = Instant::now();
+                self.asset_loader
+                    .advance_animations(now - self.last_animation_advance);
+                self.last_animation_advance = now;
+                let result = self.compose_frame_or_report_crash();
                 match result {
                     Err(FrameError::SwapchainNeedsRebuild) => {
                         self.swapchain_needs_rebuild = true;
                     }
-                    _ => result?,
+                    _ => {
+                        result?;
+                        self.frames_presented += 1;
+                        if self.screenshot_requested {
+                            self.screenshot_requested = false;
+                            self.copy_screenshot()?;
+                        }
+                        if self.check_visual_regression()? {
+                            break;
+                        }
+                    }
                 }
             }
-            self.fps_limit.sleep_to_limit();
+            match throttle {
+                BackgroundThrottle::Off => self.fps_limit.sleep_to_limit(),
+                _ => self.background_fps_limit.sleep_to_limit(),
+            }
         }
         Ok(())
     }
 
+    /// Start or stop throttling when the window moves between the
+    /// foreground and the background.
+    fn update_background_throttle(&mut self) -> Result<()> {
+        let in_background = !self.focused || self.iconified;
+        match (in_background, self.active_throttle) {
+            (true, None) => {
+                let throttle =
+                    self.state.enter_background(self.background_throttle)?;
+                let fps = match throttle {
+                    BackgroundThrottle::FrameRate(fps) => fps.max(1),
+                    _ => SUSPENDED_EVENT_FPS,
+                };
+                self.background_fps_limit.set_target_fps(fps);
+                self.active_throttle = Some(throttle);
+            }
+            (false, Some(_)) => {
+                self.active_throttle = None;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Copy the frame captured for the screenshot to the system clipboard.
+    /// The image is encoded and handed to the clipboard tool by a background
+    /// job, errors like a missing clipboard tool are logged rather than
+    /// stopping the application.
+    fn copy_screenshot(&mut self) -> Result<()> {
+        let frame_capture = match &self.frame_capture {
+            Some(frame_capture) => frame_capture,
+            None => return Ok(()),
+        };
+        if self.screenshot_job.is_some() {
+            log::warn!("the previous screenshot is still being copied");
+            return Ok(());
+        }
+        let frame = match frame_capture.latest_swapchain_image() {
+            Some(index) => unsafe {
+                // the captured frame can only be read once the gpu is done
+                // with it
+                self.frame_pipeline.wait_for_frame(index)?;
+                frame_capture.latest_frame()?
+            },
+            None => None,
+        };
+        let frame = match frame {
+            Some(frame) => frame,
+            None => {
+                log::warn!("there's no captured frame to copy yet");
+                return Ok(());
+            }
+        };
+        let job =
+            self.jobs.spawn(move |_| {
+                match clipboard_image::write_image(&frame.to_image()) {
+                    Ok(()) => log::info!(
+                        "copied a {}x{} screenshot to the clipboard",
+                        frame.width,
+                        frame.height
+                    ),
+                    Err(error) => {
+                        log::warn!("unable to copy the screenshot: {}", error)
+                    }
+                }
+            });
+        self.screenshot_job = Some(job);
+        Ok(())
+    }
+
+    /// Forget the screenshot job once it's done.
+    fn screenshot_job_event(&mut self, event: JobEvent) {
+        match event {
+            JobEvent::Progress { .. } => (),
+            JobEvent::Finished { .. } => self.screenshot_job = None,
+            JobEvent::Failed { message, .. } => {
+                log::warn!("unable to copy the screenshot: {}", message);
+                self.screenshot_job = None;
+            }
+        }
+    }
+
+    /// Compare the latest frame with its baseline once enough frames have
+    /// been presented.
+    ///
+    /// Returns true when the check is complete and the application should
+    /// exit.
+    fn check_visual_regression(&mut self) -> Result<bool> {
+        let (check, frame_capture) =
+            match (&self.visual_regression, &self.frame_capture) {
+                (Some(check), Some(frame_capture)) => (check, frame_capture),
+                _ => return Ok(false),
+            };
+        if self.frames_presented < check.frame_count() {
+            return Ok(false);
+        }
+        let frame = unsafe {
+            // the captured frame can only be read once the gpu is done with
+            // it
+            self.vk_dev.logical_device.device_wait_idle()?;
+            frame_capture.latest_frame()?
+        }
+        .context("the swapchain doesn't support frame capture")?;
+        let report = check.check_frame(&frame)?;
+        if !report.passed {
+            anyhow::bail!("visual regression {}", report.summary());
+        }
+        log::info!("visual regression {}", report.summary());
+        Ok(true)
+    }
+
+    /// Compose a frame. When crash reports are enabled, a panic writes a
+    /// report before it continues to unwind.
+    fn compose_frame_or_report_crash(&mut self) -> Result<(), FrameError> {
+        if self.crash_report_directory.is_none() {
+            return self.compose_frame();
+        }
+        let payload = match panic::catch_unwind(AssertUnwindSafe(|| {
+            self.compose_frame()
+        })) {
+            Ok(result) => return result,
+            Err(payload) => payload,
+        };
+
+        let directory = self.crash_report_directory.as_ref().unwrap();
+        let frame_capture = self.frame_capture.as_ref().unwrap();
+        let report = unsafe {
+            // the captured frame can only be read once the gpu is done with
+            // it
+            self.vk_dev
+                .logical_device
+                .device_wait_idle()
+                .map_err(anyhow::Error::from)
+                .and_then(|_| Ok(frame_capture.latest_frame()?))
+        }
+        .and_then(|last_frame| {
+            write_crash_report(directory, &*payload, last_frame)
+        });
+        match report {
+            Ok(folder) => log::error!("Wrote a crash report to {:?}", folder),
+            Err(error) => {
+                log::error!("Unable to write a crash report!\n{:?}", error)
+            }
+        }
+        panic::resume_unwind(payload)
+    }
+
     /// Render the applications state in in a three-step process.
     fn compose_frame(&mut self) -> Result<(), FrameError> {
-        let (index, cmds) = self.frame_pipeline.begin_frame()?;
+        let (index, _) = self.frame_pipeline.begin_frame()?;
 
-        unsafe {
-            self.msaa_renderpass.begin_renderpass_inline(
-                cmds,
-                &self.framebuffers[index],
-                [0.05, 0.05, 0.05, 1.0],
-                1.0,
-            );
+        // the frame's fence has been waited on, so any pixel copied out of
+        // this swapchain image last time is ready
+        if let Some(pixel) = self.pixel_readback.take_pixel(index)? {
+            self.state.handle_pixel_readback(pixel)?;
         }
 
-        let mut ui_frame = self
-            .ui_layer
-            .acquire_frame(index)
-            .with_context(|| "unable to acquire ui layer frame")?;
+        for plugin in &mut self.plugins {
+            if plugin.uses_compute() {
+                let compute_cmds = self.frame_pipeline.compute_cmds(index)?;
+                unsafe { plugin.record_compute(compute_cmds, index)? };
+            }
+        }
 
-        let mut app_frame = self
-            .app_layer
-            .acquire_frame(index)
-            .with_context(|| "unable to acquire application layer frame")?;
+        // layers are drawn before the render pass begins so their effect
+        // layers can be rendered offscreen first
+        let mut frames = None;
+        if let Some(ref mut layers) = self.layers {
+            let mut ui_frame = layers
+                .ui
+                .acquire_frame(index)
+                .with_context(|| "unable to acquire ui layer frame")?;
+            ui_frame.push_debug_label("ui layer");
 
-        self.state.draw_frame(&mut app_frame, &mut ui_frame)?;
+            let mut app_frame = layers
+                .app
+                .acquire_frame(index)
+                .with_context(|| "unable to acquire application layer frame")?;
+            app_frame.push_debug_label("application layer");
 
+            self.state.draw_frame(&mut app_frame, &mut ui_frame)?;
+            self.ui_layers.draw_frame(&mut ui_frame)?;
+            if !self.ui_layers.is_empty() {
+                self.window_cursor.apply(
+                    &mut self.glfw_window.window,
+                    self.ui_layers.cursor_shape(),
+                );
+            }
+            if let Some(diagnostics) = &mut self.diagnostics {
+                diagnostics.draw(
+                    &self.fps_limit,
+                    app_frame.stats() + ui_frame.stats(),
+                    self.glfw_window.window.get_framebuffer_size(),
+                    &mut ui_frame,
+                )?;
+            }
+            frames = Some((app_frame, ui_frame));
+        }
+
+        // crash reports and visual regression checks need every frame
+        let capture = self.crash_report_directory.is_some()
+            || self.visual_regression.is_some()
+            || self.screenshot_requested;
+        let cmds = self.frame_pipeline.frame_cmds(index);
         unsafe {
-            self.app_layer.complete_frame(cmds, app_frame, index)?;
-            self.ui_layer.complete_frame(cmds, ui_frame, index)?;
+            let texture_table = self.asset_loader.texture_table();
+            texture_table.bind(cmds, vk::PipelineBindPoint::GRAPHICS);
+            if let (Some(layers), Some((app_frame, ui_frame))) =
+                (&mut self.layers, &mut frames)
+            {
+                layers
+                    .app
+                    .write_effect_passes(cmds, app_frame)
+                    .with_context(|| "while recording application layer")?;
+                layers
+                    .ui
+                    .write_effect_passes(cmds, ui_frame)
+                    .with_context(|| "while recording ui layer")?;
+            }
+
+            self.msaa_renderpass.begin_renderpass_inline(
+                cmds,
+                &self.framebuffers[index],
+                self.clear_color,
+                self.depth_mode.clear_depth(),
+            );
+            texture_table.bind(cmds, vk::PipelineBindPoint::GRAPHICS);
+            for plugin in &mut self.plugins {
+                plugin.record(cmds, index).with_context(|| {
+                    format!("while recording {}", plugin.name())
+                })?;
+            }
+            if !self.plugins.is_empty() {
+                // plugins with their own pipeline layouts can disturb the
+                // table's binding
+                texture_table.bind(cmds, vk::PipelineBindPoint::GRAPHICS);
+            }
+
+            if let (Some(layers), Some((app_frame, ui_frame))) =
+                (&mut self.layers, frames)
+            {
+                layers
+                    .app
+                    .complete_frame(cmds, app_frame, index)
+                    .with_context(|| "while recording application layer")?;
+                layers
+                    .ui
+                    .complete_frame(cmds, ui_frame, index)
+                    .with_context(|| "while recording ui layer")?;
+            }
             self.msaa_renderpass.end_renderpass(cmds);
+            if let Some((x, y)) = self.state.pixel_readback_request() {
+                self.pixel_readback
+                    .write_readback_commands(cmds, index, x, y);
+            }
+            if let (true, Some(frame_capture)) =
+                (capture, &mut self.frame_capture)
+            {
+                frame_capture.write_capture_commands(cmds, index);
+            }
+        }
+        self.frame_pipeline.end_frame(index)?;
+        if let (true, Some(frame_capture)) = (capture, &mut self.frame_capture)
+        {
+            frame_capture.frame_submitted(index);
         }
-        self.frame_pipeline.end_frame(index)
+        Ok(())
     }
 
     /// Rebuild the swapchain and any dependent resources.
@@ -171,25 +629,49 @@ impl<S: State> Application<S> {
             self.vk_dev.logical_device.device_wait_idle()?;
         }
         let (w, h) = self.glfw_window.window.get_framebuffer_size();
-        self.vk_dev.rebuild_swapchain((w as u32, h as u32))?;
+        let start = Instant::now();
+        let framebuffer_size = (w as u32, h as u32);
+        self.vk_dev.rebuild_swapchain(framebuffer_size)?;
         self.frame_pipeline.rebuild_swapchain_resources()?;
+        self.pixel_readback.rebuild_swapchain_resources()?;
+        if let Some(frame_capture) = &mut self.frame_capture {
+            frame_capture.rebuild_swapchain_resources()?;
+        }
 
         // rebuild all dependent vulkan resources
-        self.msaa_renderpass = MultisampleRenderpass::for_current_swapchain(
-            self.vk_dev.clone(),
-            self.vk_alloc.clone(),
-        )?;
+        self.msaa_renderpass =
+            MultisampleRenderpass::for_current_swapchain_with_samples(
+                self.vk_dev.clone(),
+                self.vk_alloc.clone(),
+                self.msaa_samples,
+            )?;
         self.framebuffers =
             self.msaa_renderpass.create_swapchain_framebuffers()?;
-        self.app_layer
-            .rebuild_swapchain_resources(&self.msaa_renderpass)?;
-        self.ui_layer
-            .rebuild_swapchain_resources(&self.msaa_renderpass)?;
-
-        self.state.rebuild_swapchain_resources(
-            &self.glfw_window,
-            (w as u32, h as u32),
-        )
+        log::debug!(
+            "Rebuilt the swapchain and render pass in {:?}",
+            start.elapsed()
+        );
+
+        // everything else only depends on the render pass
+        let context = RebuildContext {
+            msaa_renderpass: &self.msaa_renderpass,
+            framebuffer_size,
+            vk_dev: &self.vk_dev,
+            vk_alloc: &self.vk_alloc,
+        };
+        if let Some(ref mut layers) = self.layers {
+            rebuild_and_log("the app layer", &mut layers.app, &context)?;
+            rebuild_and_log("the ui layer", &mut layers.ui, &context)?;
+        }
+        for (i, plugin) in self.plugins.iter_mut().enumerate() {
+            rebuild_and_log(&format!("render plugin {}", i), plugin, &context)?;
+        }
+        self.swapchain_registry.rebuild_all(&context)?;
+        self.state
+            .rebuild_swapchain_resources(&self.glfw_window, framebuffer_size)?;
+
+        log::debug!("Rebuilt all swapchain resources in {:?}", start.elapsed());
+        Ok(())
     }
 
     /// Handle a GLFW window event.
@@ -203,9 +685,57 @@ impl<S: State> Application<S> {
                 self.paused = w == 0 || h == 0;
                 self.swapchain_needs_rebuild = true;
             }
+            WindowEvent::Focus(focused) => {
+                self.focused = focused;
+                self.update_background_throttle()?;
+            }
+            WindowEvent::Iconify(iconified) => {
+                self.iconified = iconified;
+                self.update_background_throttle()?;
+            }
+            WindowEvent::Pos(..) => {
+                if let Some(rate) = self.glfw_window.current_refresh_rate() {
+                    self.fps_limit.set_refresh_rate(rate);
+                }
+            }
+            WindowEvent::Key(key, _, glfw::Action::Press, _)
+                if Some(key) == self.copy_screenshot_key =>
+            {
+                self.screenshot_requested = true;
+            }
             _ => {}
         }
+        if let Some(diagnostics) = &mut self.diagnostics {
+            diagnostics.handle_event(&event);
+        }
+        if self.ui_layers.handle_event(&event)? {
+            return Ok(());
+        }
 
         self.state.handle_event(event, &mut self.glfw_window)
     }
 }
+
+impl<S: State> Drop for Application<S> {
+    /// Wait for the device, then destroy the renderers which record into each
+    /// frame before the rest of the fields are dropped in declaration order.
+    ///
+    /// When the application is dropped because of a panic, errors are logged
+    /// instead of panicking again, which would abort before any resources are
+    /// cleaned up.
+    fn drop(&mut self) {
+        let idle = unsafe { self.vk_dev.logical_device.device_wait_idle() };
+        match idle {
+            Err(error) if std::thread::panicking() => log::error!(
+                "error while waiting for graphics device idle {:?}",
+                error
+            ),
+            result => {
+                result.expect("error while waiting for graphics device idle")
+            }
+        }
+        self.layers = None;
+        self.plugins.clear();
+        self.frame_capture = None;
+    }
+}
@@ -0,0 +1,19 @@
+/// The frame rate used to keep handling events while rendering is suspended.
+pub(super) const SUSPENDED_EVENT_FPS: u32 = 10;
+
+/// What the [`super::Application`] does while its window is in the
+/// background: unfocused or minimized. GLFW can't tell when a window is
+/// covered by other windows, so minimizing is the closest it gets to
+/// occlusion.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum BackgroundThrottle {
+    /// Keep rendering at the normal frame rate.
+    #[default]
+    Off,
+
+    /// Render at a lower frame rate.
+    FrameRate(u32),
+
+    /// Stop rendering, events are still handled a few times per second.
+    Suspend,
+}
@@ -0,0 +1,74 @@
+use ::{
+    anyhow::{Context, Result},
+    std::{
+        any::Any,
+        fs,
+        path::{Path, PathBuf},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+use crate::{
+    demo::multiline_format, frame_pipeline::CapturedFrame, panic_message,
+};
+
+/// Write a crash report folder inside the given directory. The folder holds
+/// the panic message, the most recent log messages, and the last presented
+/// frame when one was captured.
+///
+/// Returns the path to the new folder.
+pub(super) fn write_crash_report(
+    directory: &Path,
+    panic_payload: &(dyn Any + Send),
+    last_frame: Option<CapturedFrame>,
+) -> Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+    let folder = directory.join(format!("crash-{}", timestamp));
+    fs::create_dir_all(&folder).with_context(|| {
+        format!("unable to create crash report folder {:?}", folder)
+    })?;
+
+    let mut report = format!("panic: {}\n\n", panic_message(panic_payload));
+    report.push_str("most recent logs, oldest first:\n\n");
+    for message in multiline_format::recent_logs() {
+        report.push_str(&message);
+        report.push('\n');
+    }
+    fs::write(folder.join("report.txt"), report)
+        .context("unable to write the crash report")?;
+
+    if let Some(frame) = last_frame {
+        image::save_buffer(
+            folder.join("last_frame.png"),
+            &frame.rgba,
+            frame.width,
+            frame.height,
+            image::ColorType::Rgba8,
+        )
+        .context("unable to save the last frame")?;
+    }
+
+    Ok(folder)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_panic_message_reads_string_payloads() {
+        let formatted: Box<dyn Any + Send> = Box::new(format!("bad {}", 1));
+        let literal: Box<dyn Any + Send> = Box::new("bad 2");
+        let other: Box<dyn Any + Send> = Box::new(3);
+
+        assert_eq!(panic_message(&*formatted), "bad 1");
+        assert_eq!(panic_message(&*literal), "bad 2");
+        assert_eq!(
+            panic_message(&*other),
+            "<the panic payload is not a string>"
+        );
+    }
+}
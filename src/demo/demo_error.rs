@@ -4,4 +4,15 @@ use ::{flexi_logger::FlexiLoggerError, thiserror::Error};
 pub enum DemoError {
     #[error("Unable to enable pretty multiline logging!")]
     MultiLineLogSetupError(#[source] FlexiLoggerError),
+
+    #[error("A swapchain dependent resource named {0} is already registered")]
+    DuplicateSwapchainDependent(String),
+
+    #[error(
+        "{0} depends on {1}, but no resource with that name is registered"
+    )]
+    UnknownSwapchainDependency(String, String),
+
+    #[error("Swapchain dependent resources have a dependency cycle: {0}")]
+    SwapchainDependencyCycle(String),
 }
@@ -0,0 +1,81 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::{Frame, FrameStats},
+    timing::FrameRateLimit,
+    ui::{
+        primitives::Dimensions, ui_screen_space_projection,
+        widgets::Diagnostics, widgets::Widget, Font, InternalState,
+    },
+    vec2,
+};
+
+/// The [`Diagnostics`] panel drawn by the [`super::Application`] on top of
+/// the ui layer. It starts hidden and the hotkey toggles it.
+pub(super) struct DiagnosticsOverlay {
+    font: Font,
+    key: glfw::Key,
+    visible: bool,
+    internal_state: InternalState,
+}
+
+impl DiagnosticsOverlay {
+    pub(super) fn new(font: Font, key: glfw::Key) -> Self {
+        Self {
+            font,
+            key,
+            visible: false,
+            internal_state: InternalState::new(),
+        }
+    }
+
+    /// Toggle the overlay when the hotkey is pressed. The event still goes
+    /// on to the State.
+    pub(super) fn handle_event(&mut self, event: &glfw::WindowEvent) {
+        if let glfw::WindowEvent::Key(key, _, glfw::Action::Press, _) = *event {
+            if key == self.key {
+                self.visible = !self.visible;
+            }
+        }
+    }
+
+    /// Draw the panel in the top left corner of the window.
+    ///
+    /// The frame's projection is set to the same screen space projection the
+    /// UI uses, so this must be called after everything else is drawn.
+    pub(super) fn draw(
+        &mut self,
+        fps_limit: &FrameRateLimit,
+        stats: FrameStats,
+        framebuffer_size: (i32, i32),
+        frame: &mut Frame,
+    ) -> Result<()> {
+        if !self.visible {
+            return Ok(());
+        }
+        let viewport = Dimensions::new(
+            framebuffer_size.0 as f32,
+            framebuffer_size.1 as f32,
+        );
+        frame.set_view_projection(ui_screen_space_projection(viewport))?;
+
+        let mut panel = Diagnostics::new(&self.font, fps_limit, stats);
+        let margin =
+            self.internal_state.theme().padding * self.font.line_height();
+        Widget::<()>::dimensions(
+            &mut panel,
+            &mut self.internal_state,
+            &viewport,
+        );
+        Widget::<()>::set_top_left_position(
+            &mut panel,
+            &mut self.internal_state,
+            vec2(margin, margin),
+        );
+        frame.push_debug_label("diagnostics overlay");
+        let result =
+            Widget::<()>::draw_frame(&panel, &mut self.internal_state, frame);
+        frame.pop_debug_label();
+        result
+    }
+}
@@ -1,30 +1,41 @@
 //! This module defines a standard "demo" application which includes some state.
 
+mod app_builder;
 mod app_state;
 mod application;
+mod background_throttle;
+mod crash_report;
 mod demo_error;
+mod diagnostics_overlay;
 mod multiline_format;
+mod panic_hook;
+mod render_plugin;
+mod swapchain_dependent;
+mod ui_layers;
+mod visual_regression;
 
-use ::anyhow::{Context, Result};
+use ::anyhow::Result;
 
 pub use self::{
-    app_state::State, application::Application, demo_error::DemoError,
+    app_builder::AppBuilder,
+    app_state::State,
+    application::Application,
+    background_throttle::BackgroundThrottle,
+    demo_error::DemoError,
+    render_plugin::{RenderPlugin, RenderPluginFactory},
+    swapchain_dependent::{
+        RebuildContext, SwapchainDependent, SwapchainRegistry,
+    },
+    ui_layers::{UILayer, UILayers},
+    visual_regression::{
+        RegressionReport, VisualRegression, VISUAL_REGRESSION_ENV,
+        VISUAL_REGRESSION_FRAMES_ENV,
+    },
 };
 
+/// Run the application with the default [`AppBuilder`] configuration and the
+/// panic hook. Use the AppBuilder directly to register custom
+/// [`RenderPlugin`]s.
 pub fn run_application<S: State>() -> Result<()> {
-    multiline_format::enable_multiline_logging()?;
-
-    let result = Application::<S>::new()
-        .context("failed to construct the application!")?
-        .run()
-        .context("application exited with an error");
-
-    if let Err(ref error) = result {
-        log::error!(
-            "Application exited unsuccessfully!\n{:?}\n\nroot cause: {:?}",
-            error,
-            error.root_cause()
-        );
-    }
-    result
+    AppBuilder::<S>::new("Swapchain").panic_hook(true).run()
 }
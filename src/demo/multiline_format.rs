@@ -1,11 +1,40 @@
 use ::{
     flexi_logger::{DeferredNow, Logger, Record},
-    std::fmt::Write as FmtWrite,
+    std::{collections::VecDeque, fmt::Write as FmtWrite, sync::Mutex},
     textwrap::{termwidth, Options},
 };
 
 use crate::demo::DemoError;
 
+/// How many of the most recent log messages are kept for crash reports.
+const LOG_HISTORY_CAPACITY: usize = 512;
+
+/// The most recent log messages, oldest first.
+static LOG_HISTORY: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// A copy of the most recent log messages, oldest first. Only messages
+/// formatted with [`multiline_format`] are kept.
+pub(super) fn recent_logs() -> Vec<String> {
+    match LOG_HISTORY.lock() {
+        Ok(history) => history.iter().cloned().collect(),
+        // a panic while logging shouldn't hide the logs from a crash report
+        Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+    }
+}
+
+/// Keep a log message in the history, dropping the oldest message when it's
+/// full.
+fn remember_log(message: &str) {
+    let mut history = match LOG_HISTORY.lock() {
+        Ok(history) => history,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if history.len() == LOG_HISTORY_CAPACITY {
+        history.pop_front();
+    }
+    history.push_back(message.to_owned());
+}
+
 /// Enable multiline logging for this application.
 pub(super) fn enable_multiline_logging() -> Result<(), DemoError> {
     Logger::with_env_or_str("info")
@@ -43,6 +72,7 @@ pub fn multiline_format(
 
     write!(&mut full_line, "{}", &record.args())
         .expect("unable to format log!");
+    remember_log(&full_line);
 
     writeln!(w, "{}", textwrap::fill(&full_line, wrap_options))
 }
@@ -0,0 +1,83 @@
+use ::std::{
+    backtrace::Backtrace,
+    panic,
+    sync::{Arc, Mutex, Once, Weak},
+    thread::{self, ThreadId},
+};
+
+use crate::vulkan::RenderDevice;
+
+/// The device the panic hook waits for.
+struct HookedDevice {
+    device: Weak<RenderDevice>,
+    device_description: String,
+    render_thread: ThreadId,
+}
+
+/// The device from the most recent call to [`install_panic_hook`].
+static HOOKED_DEVICE: Mutex<Option<HookedDevice>> = Mutex::new(None);
+
+static INSTALL: Once = Once::new();
+
+/// Install a panic hook which waits for the device to finish its work, then
+/// logs the panic along with the device description and a backtrace.
+///
+/// Waiting in the hook means the gpu is idle before unwinding starts to drop
+/// frame resources, so a panic mid-frame doesn't cascade into validation
+/// errors about resources which are still in use.
+///
+/// Only panics on the thread which installed the hook, the one which renders,
+/// wait for the device. Waiting idle needs every queue to be externally
+/// synchronized, so other threads, like job workers whose panics are caught
+/// and reported as failed jobs, go straight to the previous hook.
+///
+/// The hook is only installed once. Later calls replace the device it waits
+/// for, so every application doesn't chain another hook. The hook only holds
+/// a weak reference to the device, so it doesn't keep the device alive once
+/// the application is dropped. The previous hook still runs after this one.
+pub(super) fn install_panic_hook(vk_dev: &Arc<RenderDevice>) {
+    *lock_hooked_device() = Some(HookedDevice {
+        device: Arc::downgrade(vk_dev),
+        device_description: vk_dev.describe(),
+        render_thread: thread::current().id(),
+    });
+    INSTALL.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |panic_info| {
+            if let Some(hooked) = &*lock_hooked_device() {
+                if thread::current().id() == hooked.render_thread {
+                    wait_and_log(hooked, panic_info);
+                }
+            }
+            previous_hook(panic_info);
+        }));
+    });
+}
+
+/// Wait for the hooked device, if it's still alive, then log the panic.
+fn wait_and_log(hooked: &HookedDevice, panic_info: &panic::PanicHookInfo) {
+    if let Some(vk_dev) = hooked.device.upgrade() {
+        let idle = unsafe { vk_dev.logical_device.device_wait_idle() };
+        if let Err(error) = idle {
+            log::error!(
+                "Unable to wait for the device after a panic! {:?}",
+                error
+            );
+        }
+    }
+    log::error!(
+        "The application panicked!\n{}\n\ndevice:\n{}\n\nbacktrace:\n{}",
+        panic_info,
+        hooked.device_description,
+        Backtrace::force_capture()
+    );
+}
+
+/// The hooked device. A panic while the lock was held doesn't stop later
+/// panics from being reported.
+fn lock_hooked_device() -> std::sync::MutexGuard<'static, Option<HookedDevice>>
+{
+    HOOKED_DEVICE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,97 @@
+use ::{anyhow::Result, std::sync::Arc};
+
+use crate::{
+    asset_loader::TextureTable,
+    multisample_renderpass::MultisampleRenderpass,
+    vulkan::{CommandBuffer, MemoryAllocator, RenderDevice},
+};
+
+/// A RenderPlugin is a custom Vulkan renderer which the demo Application
+/// drives alongside its built-in Triangles layers.
+///
+/// Plugins are recorded inside of the Application's render pass in the order
+/// they were registered, before the app and ui layers. Plugins which use
+/// compute shaders can also record compute commands which run before the
+/// frame's graphics commands.
+///
+/// The Application binds the AssetLoader's texture table before recording
+/// any plugin. Plugins which sample textures should use the table's layout
+/// as the first set in their pipeline layout, then texture indices from the
+/// AssetLoader work without binding anything else.
+pub trait RenderPlugin {
+    /// Create the plugin once the Application's render pass exists.
+    fn create(
+        msaa_renderpass: &MultisampleRenderpass,
+        texture_table: &TextureTable,
+        vk_dev: &Arc<RenderDevice>,
+        vk_alloc: &Arc<dyn MemoryAllocator>,
+    ) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Rebuild any swapchain-dependent resources, typically the graphics
+    /// pipeline, for the new render pass.
+    fn rebuild_swapchain_resources(
+        &mut self,
+        msaa_renderpass: &MultisampleRenderpass,
+    ) -> Result<()>;
+
+    /// The plugin's name, used in errors while it's recording.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Record the plugin's draw commands for a single frame.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer is in the middle of the Application's render
+    ///   pass, plugins must not begin or end render passes.
+    /// - Any resources used by the commands must stay alive until the frame
+    ///   for this swapchain image has finished rendering.
+    unsafe fn record(
+        &mut self,
+        cmd: &CommandBuffer,
+        swapchain_image_index: usize,
+    ) -> Result<()>;
+
+    /// True when the plugin records compute commands with `record_compute`.
+    /// Frames without any compute work skip the compute submission.
+    fn uses_compute(&self) -> bool {
+        false
+    }
+
+    /// Record the plugin's compute commands for a single frame, e.g.
+    /// particle simulation or mipmap generation.
+    ///
+    /// The commands are submitted to the compute queue before the frame's
+    /// graphics commands, which wait for them to finish. When the device has
+    /// a dedicated compute queue the work overlaps with the previous frame's
+    /// graphics work.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffer belongs to the compute queue's family. Resources
+    ///   shared with the graphics commands must be usable by both families,
+    ///   see `RenderDevice::graphics_and_compute_families`.
+    /// - Any resources used by the commands must stay alive until the frame
+    ///   for this swapchain image has finished rendering.
+    unsafe fn record_compute(
+        &mut self,
+        _cmd: &CommandBuffer,
+        _swapchain_image_index: usize,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A function which creates a RenderPlugin once the Application's render
+/// pass exists.
+pub type RenderPluginFactory = Box<
+    dyn FnOnce(
+        &MultisampleRenderpass,
+        &TextureTable,
+        &Arc<RenderDevice>,
+        &Arc<dyn MemoryAllocator>,
+    ) -> Result<Box<dyn RenderPlugin>>,
+>;
@@ -0,0 +1,260 @@
+use ::{
+    anyhow::Result,
+    std::{cell::RefCell, rc::Rc, sync::Arc, time::Instant},
+};
+
+use crate::{
+    demo::{DemoError, RenderPlugin},
+    immediate_mode_graphics::{triangles::Triangles, VertexFormat},
+    multisample_renderpass::MultisampleRenderpass,
+    vulkan::{MemoryAllocator, RenderDevice},
+};
+
+/// Everything a resource might need when it's rebuilt for a new swapchain.
+pub struct RebuildContext<'a> {
+    /// The render pass which was rebuilt for the new swapchain.
+    pub msaa_renderpass: &'a MultisampleRenderpass,
+
+    /// The new framebuffer size in pixels.
+    pub framebuffer_size: (u32, u32),
+
+    pub vk_dev: &'a Arc<RenderDevice>,
+    pub vk_alloc: &'a Arc<dyn MemoryAllocator>,
+}
+
+/// Resources which need to be rebuilt any time the swapchain is rebuilt.
+///
+/// Resources registered with a [`SwapchainRegistry`] are rebuilt
+/// automatically, so they can't be forgotten.
+pub trait SwapchainDependent {
+    /// Rebuild resources which depend on the swapchain. Every resource this
+    /// one depends on has already been rebuilt.
+    fn rebuild_swapchain_resources(
+        &mut self,
+        context: &RebuildContext,
+    ) -> Result<()>;
+}
+
+/// Shared resources can be registered while the owner keeps a handle for
+/// rendering.
+impl<T: SwapchainDependent> SwapchainDependent for Rc<RefCell<T>> {
+    fn rebuild_swapchain_resources(
+        &mut self,
+        context: &RebuildContext,
+    ) -> Result<()> {
+        self.borrow_mut().rebuild_swapchain_resources(context)
+    }
+}
+
+impl<V: VertexFormat> SwapchainDependent for Triangles<V> {
+    fn rebuild_swapchain_resources(
+        &mut self,
+        context: &RebuildContext,
+    ) -> Result<()> {
+        Triangles::rebuild_swapchain_resources(self, context.msaa_renderpass)?;
+        Ok(())
+    }
+}
+
+impl SwapchainDependent for Box<dyn RenderPlugin> {
+    fn rebuild_swapchain_resources(
+        &mut self,
+        context: &RebuildContext,
+    ) -> Result<()> {
+        self.as_mut()
+            .rebuild_swapchain_resources(context.msaa_renderpass)
+    }
+}
+
+/// Rebuild a single resource and log how long it took at the debug level.
+pub(super) fn rebuild_and_log(
+    name: &str,
+    resource: &mut dyn SwapchainDependent,
+    context: &RebuildContext,
+) -> Result<()> {
+    let start = Instant::now();
+    resource.rebuild_swapchain_resources(context)?;
+    log::debug!(
+        "Rebuilt swapchain resources for {} in {:?}",
+        name,
+        start.elapsed()
+    );
+    Ok(())
+}
+
+struct Entry {
+    name: String,
+    dependencies: Vec<String>,
+    resource: Box<dyn SwapchainDependent>,
+}
+
+/// A SwapchainRegistry owns swapchain-dependent resources and rebuilds them
+/// in dependency order.
+#[derive(Default)]
+pub struct SwapchainRegistry {
+    entries: Vec<Entry>,
+}
+
+impl SwapchainRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a resource with a unique name. The resource is always rebuilt
+    /// after every resource named in `dependencies`.
+    ///
+    /// Dependencies don't need to be registered yet, but every dependency
+    /// must be registered before the next rebuild.
+    pub fn register<R>(
+        &mut self,
+        name: impl Into<String>,
+        dependencies: &[&str],
+        resource: R,
+    ) -> Result<(), DemoError>
+    where
+        R: 'static + SwapchainDependent,
+    {
+        let name = name.into();
+        if self.entries.iter().any(|entry| entry.name == name) {
+            return Err(DemoError::DuplicateSwapchainDependent(name));
+        }
+        self.entries.push(Entry {
+            name,
+            dependencies: dependencies
+                .iter()
+                .map(|&dependency| dependency.to_owned())
+                .collect(),
+            resource: Box::new(resource),
+        });
+        Ok(())
+    }
+
+    /// The number of registered resources.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True when no resources are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rebuild every registered resource in dependency order. Logs how long
+    /// each rebuild took at the debug level.
+    pub fn rebuild_all(&mut self, context: &RebuildContext) -> Result<()> {
+        for index in self.rebuild_order()? {
+            let entry = &mut self.entries[index];
+            rebuild_and_log(&entry.name, entry.resource.as_mut(), context)?;
+        }
+        Ok(())
+    }
+
+    /// Sort the entries so every resource comes after its dependencies.
+    /// Resources without an ordering between them keep their registration
+    /// order.
+    fn rebuild_order(&self) -> Result<Vec<usize>, DemoError> {
+        let index_of = |name: &str| {
+            self.entries.iter().position(|entry| entry.name == name)
+        };
+        let mut dependencies = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let mut indices = vec![];
+            for dependency in &entry.dependencies {
+                let index = index_of(dependency).ok_or_else(|| {
+                    DemoError::UnknownSwapchainDependency(
+                        entry.name.clone(),
+                        dependency.clone(),
+                    )
+                })?;
+                indices.push(index);
+            }
+            dependencies.push(indices);
+        }
+
+        let mut order = Vec::with_capacity(self.entries.len());
+        let mut visited = vec![false; self.entries.len()];
+        while order.len() < self.entries.len() {
+            let ready = (0..self.entries.len()).find(|&i| {
+                !visited[i] && dependencies[i].iter().all(|&d| visited[d])
+            });
+            match ready {
+                Some(i) => {
+                    visited[i] = true;
+                    order.push(i);
+                }
+                None => {
+                    let stuck = (0..self.entries.len())
+                        .filter(|&i| !visited[i])
+                        .map(|i| self.entries[i].name.clone())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(DemoError::SwapchainDependencyCycle(stuck));
+                }
+            }
+        }
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Noop;
+
+    impl SwapchainDependent for Noop {
+        fn rebuild_swapchain_resources(
+            &mut self,
+            _context: &RebuildContext,
+        ) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn names(registry: &SwapchainRegistry) -> Vec<&str> {
+        registry
+            .rebuild_order()
+            .unwrap()
+            .into_iter()
+            .map(|i| registry.entries[i].name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_rebuild_order_follows_dependencies() {
+        let mut registry = SwapchainRegistry::new();
+        registry
+            .register("postprocess", &["scene", "ui"], Noop)
+            .unwrap();
+        registry.register("ui", &[], Noop).unwrap();
+        registry.register("scene", &["ui"], Noop).unwrap();
+        registry.register("debug", &[], Noop).unwrap();
+
+        assert_eq!(
+            names(&registry),
+            vec!["ui", "scene", "postprocess", "debug"]
+        );
+    }
+
+    #[test]
+    fn test_rebuild_order_errors() {
+        let mut registry = SwapchainRegistry::new();
+        registry.register("a", &["b"], Noop).unwrap();
+        assert!(matches!(
+            registry.rebuild_order(),
+            Err(DemoError::UnknownSwapchainDependency(..))
+        ));
+
+        registry.register("b", &["a"], Noop).unwrap();
+        assert!(matches!(
+            registry.rebuild_order(),
+            Err(DemoError::SwapchainDependencyCycle(..))
+        ));
+
+        assert!(matches!(
+            registry.register("a", &[], Noop),
+            Err(DemoError::DuplicateSwapchainDependent(..))
+        ));
+    }
+}
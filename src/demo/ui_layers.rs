@@ -0,0 +1,167 @@
+use ::{
+    anyhow::Result,
+    std::{cell::RefCell, rc::Rc},
+};
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{CursorShape, UIState, UI},
+};
+
+/// A UI which the [`super::Application`] gives events to and draws on the
+/// ui layer. [`UI`] implements it for every UIState.
+pub trait UILayer {
+    /// Handle an event. Returns true when the UI used the event, so layers
+    /// with a lower priority shouldn't see it.
+    fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<bool>;
+
+    /// Draw the UI into the ui layer's frame.
+    fn draw_frame(&mut self, frame: &mut Frame) -> Result<()>;
+
+    /// The cursor shape the UI wants shown.
+    fn cursor_shape(&self) -> CursorShape {
+        CursorShape::Arrow
+    }
+}
+
+impl<C: UIState> UILayer for UI<C> {
+    fn handle_event(&mut self, event: &glfw::WindowEvent) -> Result<bool> {
+        Ok(self.handle_captured_event(event)?.is_captured())
+    }
+
+    fn draw_frame(&mut self, frame: &mut Frame) -> Result<()> {
+        UI::draw_frame(self, frame)
+    }
+
+    fn cursor_shape(&self) -> CursorShape {
+        UI::cursor_shape(self)
+    }
+}
+
+/// A registered UI and its priority.
+struct Entry {
+    priority: i32,
+    layer: Rc<RefCell<dyn UILayer>>,
+}
+
+/// UILayers holds the UIs which the [`super::Application`] handles for the
+/// State, e.g. a HUD on top of a UI placed in the world with
+/// [`UI::set_projection`].
+///
+/// Each event is offered to the UIs from the highest priority to the lowest
+/// and stops at the first UI which captures it, see
+/// [`UI::handle_captured_event`]. Events no UI captured go on to the State.
+/// UIs are drawn from the lowest priority to the highest, so higher
+/// priority UIs are drawn on top.
+///
+/// The State keeps its own `Rc` to each UI to read and change its state.
+#[derive(Default)]
+pub struct UILayers {
+    /// Sorted from the highest priority to the lowest. UIs with the same
+    /// priority keep the order they were added in.
+    entries: Vec<Entry>,
+}
+
+impl UILayers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a UI with a priority. Higher priorities see events first.
+    pub fn add<L>(&mut self, priority: i32, layer: Rc<RefCell<L>>)
+    where
+        L: UILayer + 'static,
+    {
+        let index = self
+            .entries
+            .partition_point(|entry| entry.priority >= priority);
+        self.entries.insert(index, Entry { priority, layer });
+    }
+
+    /// The number of registered UIs.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Offer an event to each UI in priority order. Returns true when one of
+    /// them captured it.
+    pub(super) fn handle_event(
+        &mut self,
+        event: &glfw::WindowEvent,
+    ) -> Result<bool> {
+        for entry in &self.entries {
+            if entry.layer.borrow_mut().handle_event(event)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// The cursor shape asked for by the highest priority UI which wants
+    /// something other than an arrow.
+    pub(super) fn cursor_shape(&self) -> CursorShape {
+        self.entries
+            .iter()
+            .map(|entry| entry.layer.borrow().cursor_shape())
+            .find(|shape| *shape != CursorShape::Arrow)
+            .unwrap_or_default()
+    }
+
+    /// Draw every UI, the highest priority last.
+    pub(super) fn draw_frame(&mut self, frame: &mut Frame) -> Result<()> {
+        for entry in self.entries.iter().rev() {
+            entry.layer.borrow_mut().draw_frame(frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Records the events it's given and captures them when asked to.
+    struct Recorder {
+        name: &'static str,
+        captures: bool,
+        log: Rc<RefCell<Vec<&'static str>>>,
+    }
+
+    impl UILayer for Recorder {
+        fn handle_event(&mut self, _event: &glfw::WindowEvent) -> Result<bool> {
+            self.log.borrow_mut().push(self.name);
+            Ok(self.captures)
+        }
+
+        fn draw_frame(&mut self, _frame: &mut Frame) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_higher_priorities_capture_first() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let recorder = |name, captures| {
+            Rc::new(RefCell::new(Recorder {
+                name,
+                captures,
+                log: log.clone(),
+            }))
+        };
+        let mut layers = UILayers::new();
+        layers.add(0, recorder("world", false));
+        layers.add(10, recorder("hud", false));
+        layers.add(5, recorder("menu", true));
+        layers.add(5, recorder("tooltip", true));
+
+        let captured = layers
+            .handle_event(&glfw::WindowEvent::Scroll(0.0, 1.0))
+            .unwrap();
+        assert!(captured);
+        assert_eq!(*log.borrow(), vec!["hud", "menu"]);
+    }
+}
@@ -0,0 +1,302 @@
+use ::{
+    anyhow::{Context, Result},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+use crate::{builder_field, frame_pipeline::CapturedFrame};
+
+/// The environment variable which enables visual regression for any
+/// application run with [`crate::demo::AppBuilder::run`]. The value is a
+/// directory with a `baseline` folder, results are written to an `output`
+/// folder beside it.
+pub const VISUAL_REGRESSION_ENV: &str = "CCTHW_VISUAL_REGRESSION";
+
+/// The environment variable which overrides the number of frames rendered
+/// before the capture.
+pub const VISUAL_REGRESSION_FRAMES_ENV: &str = "CCTHW_VISUAL_REGRESSION_FRAMES";
+
+/// The largest possible value of the YIQ color delta used by the perceptual
+/// diff.
+const MAX_YIQ_DELTA: f32 = 35215.0;
+
+/// The configuration for a visual regression check.
+///
+/// The application runs in a hidden window for a number of frames, then the
+/// last frame is saved and compared with a baseline image of the same name.
+/// Any example can be checked without code changes by setting
+/// [`VISUAL_REGRESSION_ENV`], e.g.
+///
+/// ```text
+/// CCTHW_VISUAL_REGRESSION=regression cargo run --example e3
+/// ```
+///
+/// Baselines are accepted by copying the output images into the baseline
+/// directory. Scenes should be deterministic for the check to be useful,
+/// e.g. animations should depend on the frame count rather than the clock.
+#[derive(Debug, Clone)]
+pub struct VisualRegression {
+    /// The name used for every output file.
+    name: String,
+
+    /// The number of frames rendered before the capture.
+    frames: u32,
+
+    /// The directory which holds baseline images.
+    baseline_directory: PathBuf,
+
+    /// The directory where screenshots, hashes, and diffs are written.
+    output_directory: PathBuf,
+
+    /// How different two pixels can look before they count as different, in
+    /// the range [0, 1].
+    pixel_threshold: f32,
+
+    /// The fraction of pixels which can differ before the check fails.
+    max_differing_fraction: f32,
+}
+
+/// The result of comparing one frame with its baseline.
+#[derive(Debug, Clone)]
+pub struct RegressionReport {
+    /// The name of the check.
+    pub name: String,
+
+    /// A hash of the captured pixels.
+    pub hash: u64,
+
+    /// A hash of the baseline's pixels. None when there is no baseline.
+    pub baseline_hash: Option<u64>,
+
+    /// The number of pixels which look different from the baseline. Every
+    /// pixel differs when the sizes don't match.
+    pub differing_pixels: usize,
+
+    /// The number of pixels in the captured frame.
+    pub total_pixels: usize,
+
+    /// True when the frame is close enough to the baseline.
+    pub passed: bool,
+}
+
+impl VisualRegression {
+    /// Create a check which renders 10 frames then compares the last one.
+    pub fn new(
+        name: impl AsRef<str>,
+        baseline_directory: impl Into<PathBuf>,
+        output_directory: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            name: file_name(name.as_ref()),
+            frames: 10,
+            baseline_directory: baseline_directory.into(),
+            output_directory: output_directory.into(),
+            pixel_threshold: 0.1,
+            max_differing_fraction: 0.0,
+        }
+    }
+
+    /// Configure a check from [`VISUAL_REGRESSION_ENV`]. None when the
+    /// variable isn't set.
+    pub fn from_env(name: impl AsRef<str>) -> Option<Self> {
+        let directory = PathBuf::from(std::env::var_os(VISUAL_REGRESSION_ENV)?);
+        let check = Self::new(
+            name,
+            directory.join("baseline"),
+            directory.join("output"),
+        );
+        let frames = std::env::var(VISUAL_REGRESSION_FRAMES_ENV)
+            .ok()
+            .and_then(|frames| frames.parse().ok());
+        Some(match frames {
+            Some(frames) => check.frames(frames),
+            None => check,
+        })
+    }
+
+    builder_field!(frames, u32);
+    builder_field!(pixel_threshold, f32);
+    builder_field!(max_differing_fraction, f32);
+
+    /// The number of frames rendered before the capture.
+    pub fn frame_count(&self) -> u32 {
+        self.frames
+    }
+
+    /// Save the frame, its hash, and a diff image if it doesn't match the
+    /// baseline, then report the result.
+    pub fn check_frame(
+        &self,
+        frame: &CapturedFrame,
+    ) -> Result<RegressionReport> {
+        fs::create_dir_all(&self.output_directory).with_context(|| {
+            format!(
+                "unable to create the visual regression output directory {:?}",
+                self.output_directory
+            )
+        })?;
+
+        let screenshot =
+            self.output_directory.join(format!("{}.png", self.name));
+        save_rgba(&screenshot, frame.width, frame.height, &frame.rgba)?;
+        let hash = fnv1a(&frame.rgba);
+        fs::write(
+            self.output_directory.join(format!("{}.hash", self.name)),
+            format!("{:016x}\n", hash),
+        )
+        .context("unable to write the frame hash")?;
+
+        let total_pixels = (frame.width * frame.height) as usize;
+        let mut report = RegressionReport {
+            name: self.name.clone(),
+            hash,
+            baseline_hash: None,
+            differing_pixels: total_pixels,
+            total_pixels,
+            passed: false,
+        };
+
+        let baseline_path =
+            self.baseline_directory.join(format!("{}.png", self.name));
+        if !baseline_path.exists() {
+            return Ok(report);
+        }
+        let baseline = image::open(&baseline_path)
+            .with_context(|| {
+                format!("unable to read the baseline {:?}", baseline_path)
+            })?
+            .into_rgba8();
+        report.baseline_hash = Some(fnv1a(baseline.as_raw()));
+        if report.baseline_hash == Some(hash) {
+            report.differing_pixels = 0;
+            report.passed = true;
+            return Ok(report);
+        }
+        if baseline.dimensions() != (frame.width, frame.height) {
+            return Ok(report);
+        }
+
+        let (diff, differing_pixels) = perceptual_diff(
+            baseline.as_raw(),
+            &frame.rgba,
+            self.pixel_threshold,
+        );
+        save_rgba(
+            &self
+                .output_directory
+                .join(format!("{}.diff.png", self.name)),
+            frame.width,
+            frame.height,
+            &diff,
+        )?;
+        report.differing_pixels = differing_pixels;
+        report.passed = differing_pixels as f32
+            <= self.max_differing_fraction * total_pixels as f32;
+        Ok(report)
+    }
+}
+
+impl RegressionReport {
+    /// A one line summary for the logs.
+    pub fn summary(&self) -> String {
+        match self.baseline_hash {
+            None => format!(
+                "{}: no baseline, copy the output image into the baseline \
+                 directory to accept it",
+                self.name
+            ),
+            Some(_) => format!(
+                "{}: {} of {} pixels differ from the baseline ({})",
+                self.name,
+                self.differing_pixels,
+                self.total_pixels,
+                if self.passed { "passed" } else { "failed" }
+            ),
+        }
+    }
+}
+
+/// Names become file names, so anything other than letters and digits is
+/// replaced.
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn save_rgba(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<()> {
+    image::save_buffer(path, rgba, width, height, image::ColorType::Rgba8)
+        .with_context(|| format!("unable to save {:?}", path))
+}
+
+/// A 64 bit FNV-1a hash. Unlike the std hashers it's stable between builds,
+/// so hashes can be compared with files written by older versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Compare two images of the same size pixel by pixel using the perceived
+/// difference between colors rather than the raw byte difference. Alpha is
+/// ignored because swapchain images are opaque.
+///
+/// # Returns
+///
+/// A diff image, with differing pixels in red over a faded copy of the
+/// expected image, and the number of differing pixels.
+fn perceptual_diff(
+    expected: &[u8],
+    actual: &[u8],
+    threshold: f32,
+) -> (Vec<u8>, usize) {
+    let max_delta = MAX_YIQ_DELTA * threshold * threshold;
+    let mut differing_pixels = 0;
+    let mut diff = Vec::with_capacity(expected.len());
+    for (a, b) in expected.chunks_exact(4).zip(actual.chunks_exact(4)) {
+        if yiq_delta(a, b) > max_delta {
+            differing_pixels += 1;
+            diff.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            let luma = 0.2988953 * a[0] as f32
+                + 0.5866225 * a[1] as f32
+                + 0.1144822 * a[2] as f32;
+            let faded = (255.0 + 0.1 * (luma - 255.0)) as u8;
+            diff.extend_from_slice(&[faded, faded, faded, 255]);
+        }
+    }
+    (diff, differing_pixels)
+}
+
+/// The squared distance between two rgb colors in the YIQ color space,
+/// weighted by how sensitive people are to each channel.
+fn yiq_delta(a: &[u8], b: &[u8]) -> f32 {
+    let (r, g, b) = (
+        a[0] as f32 - b[0] as f32,
+        a[1] as f32 - b[1] as f32,
+        a[2] as f32 - b[2] as f32,
+    );
+    let y = 0.2988953 * r + 0.5866225 * g + 0.1144822 * b;
+    let i = 0.595978 * r - 0.2741761 * g - 0.3218019 * b;
+    let q = 0.2114702 * r - 0.5226171 * g + 0.3111469 * b;
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_perceptual_diff_ignores_small_changes() {
+        let expected = [10, 10, 10, 255, 200, 0, 0, 255, 0, 0, 0, 255];
+        let actual = [12, 11, 10, 255, 0, 0, 200, 255, 0, 0, 0, 0];
+
+        let (diff, differing_pixels) = perceptual_diff(&expected, &actual, 0.1);
+
+        assert_eq!(differing_pixels, 1);
+        assert_eq!(&diff[4..8], &[255, 0, 0, 255]);
+        assert_ne!(fnv1a(&expected), fnv1a(&actual));
+    }
+}
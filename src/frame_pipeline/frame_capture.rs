@@ -0,0 +1,265 @@
+use ::{ash::vk, image::RgbaImage, std::sync::Arc};
+
+use crate::{
+    frame_pipeline::{pixel_readback::PixelLayout, FrameError},
+    vulkan::{
+        errors::VulkanError, Buffer, CommandBuffer, MemoryAllocator,
+        RenderDevice, VulkanDebug,
+    },
+};
+
+/// An rgba8 image read back from a presented swapchain image.
+#[derive(Debug, Clone)]
+pub struct CapturedFrame {
+    pub width: u32,
+    pub height: u32,
+
+    /// The pixels, row by row, with four bytes per pixel in rgba order.
+    pub rgba: Vec<u8>,
+}
+
+impl CapturedFrame {
+    /// A copy of the frame as an image, e.g. to save it or put it on the
+    /// clipboard.
+    pub fn to_image(&self) -> RgbaImage {
+        RgbaImage::from_raw(self.width, self.height, self.rgba.clone())
+            .expect("captured frames have four bytes for every pixel")
+    }
+}
+
+/// FrameCapture copies every rendered swapchain image into host memory so the
+/// last presented frame can be saved even if the application can't render
+/// another one, e.g. after a panic.
+///
+/// Copying the whole image every frame isn't free, so this is only meant to
+/// be enabled when it's needed.
+pub struct FrameCapture {
+    /// One mapped buffer, large enough for an entire image, for each
+    /// swapchain image.
+    buffers: Vec<Buffer>,
+
+    /// The swapchain image most recently submitted with a capture.
+    latest: Option<usize>,
+
+    /// The swapchain's size when the buffers were created.
+    extent: vk::Extent2D,
+
+    /// The current swapchain's pixel layout. None when the swapchain can't be
+    /// read back.
+    layout: Option<PixelLayout>,
+
+    vk_alloc: Arc<dyn MemoryAllocator>,
+    vk_dev: Arc<RenderDevice>,
+}
+
+impl FrameCapture {
+    pub fn new(
+        vk_dev: Arc<RenderDevice>,
+        vk_alloc: Arc<dyn MemoryAllocator>,
+    ) -> Result<Self, FrameError> {
+        let mut frame_capture = Self {
+            buffers: vec![],
+            latest: None,
+            extent: vk::Extent2D::default(),
+            layout: None,
+            vk_alloc,
+            vk_dev,
+        };
+        frame_capture.rebuild_swapchain_resources()?;
+        Ok(frame_capture)
+    }
+
+    /// Rebuild all swapchain-dependent resources. The latest capture is
+    /// dropped because it's the wrong size for the new swapchain.
+    pub fn rebuild_swapchain_resources(&mut self) -> Result<(), FrameError> {
+        let (format, usage, extent) = self.vk_dev.with_swapchain(|swapchain| {
+            (swapchain.format, swapchain.image_usage, swapchain.extent)
+        });
+        self.extent = extent;
+        self.layout = if usage.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+            PixelLayout::for_format(format)
+        } else {
+            None
+        };
+        if self.layout.is_none() {
+            log::warn!(
+                "Frame capture is unavailable for this swapchain, \
+                 format: {:?}, usage: {:?}",
+                format,
+                usage
+            );
+        }
+
+        self.buffers.clear();
+        self.latest = None;
+        let size = (extent.width as u64 * extent.height as u64 * 4).max(4);
+        for i in 0..self.vk_dev.swapchain_image_count() {
+            let mut buffer = Buffer::new(
+                self.vk_dev.clone(),
+                self.vk_alloc.clone(),
+                vk::BufferUsageFlags::TRANSFER_DST,
+                vk::MemoryPropertyFlags::HOST_VISIBLE
+                    | vk::MemoryPropertyFlags::HOST_COHERENT,
+                size,
+            )
+            .map_err(VulkanError::BufferError)?;
+            buffer.map().map_err(VulkanError::BufferError)?;
+            buffer
+                .set_debug_name(format!("Frame Capture {}", i))
+                .map_err(VulkanError::VulkanDebugError)?;
+            self.buffers.push(buffer);
+        }
+        Ok(())
+    }
+
+    /// Mark the swapchain image as the latest capture. Call this once the
+    /// frame which recorded the capture commands has been submitted.
+    pub fn frame_submitted(&mut self, swapchain_image_index: usize) {
+        if self.layout.is_some() {
+            self.latest = Some(swapchain_image_index);
+        }
+    }
+
+    /// The swapchain image of the most recently submitted capture.
+    pub fn latest_swapchain_image(&self) -> Option<usize> {
+        self.latest
+    }
+
+    /// Read the most recently submitted capture.
+    ///
+    /// # Safety
+    ///
+    /// - The captured frame's commands must have finished executing on the
+    ///   gpu, e.g. the device must be idle.
+    pub unsafe fn latest_frame(
+        &self,
+    ) -> Result<Option<CapturedFrame>, FrameError> {
+        let (index, layout) = match (self.latest, self.layout) {
+            (Some(index), Some(layout)) => (index, layout),
+            _ => return Ok(None),
+        };
+        let pixel_count = (self.extent.width * self.extent.height) as usize;
+        let data = self.buffers[index]
+            .data::<u8>()
+            .map_err(VulkanError::BufferError)?;
+        let rgba = data[..pixel_count * 4]
+            .chunks_exact(4)
+            .flat_map(|bytes| {
+                layout.to_rgba8([bytes[0], bytes[1], bytes[2], bytes[3]])
+            })
+            .collect();
+        Ok(Some(CapturedFrame {
+            width: self.extent.width,
+            height: self.extent.height,
+            rgba,
+        }))
+    }
+
+    /// Record commands which copy the entire swapchain image. The request is
+    /// ignored when the swapchain doesn't support readback.
+    ///
+    /// # Safety
+    ///
+    /// - This must be recorded after the frame's render pass has ended, the
+    ///   image is expected to be in the PRESENT_SRC_KHR layout.
+    /// - The copy is not complete until the frame's commands have finished
+    ///   executing on the gpu.
+    pub unsafe fn write_capture_commands(
+        &mut self,
+        cmd: &CommandBuffer,
+        swapchain_image_index: usize,
+    ) {
+        if self.layout.is_none() {
+            return;
+        }
+        let image = self.vk_dev.with_swapchain(|swapchain| {
+            swapchain.images[swapchain_image_index]
+        });
+
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let prepare_read_barrier = vk::ImageMemoryBarrier {
+            src_access_mask: vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+            dst_access_mask: vk::AccessFlags::TRANSFER_READ,
+            old_layout: vk::ImageLayout::PRESENT_SRC_KHR,
+            new_layout: vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            image,
+            subresource_range,
+            ..Default::default()
+        };
+        self.vk_dev.logical_device.cmd_pipeline_barrier(
+            cmd.raw,
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[prepare_read_barrier],
+        );
+
+        let buffer = &self.buffers[swapchain_image_index];
+        let buffer_image_copy = vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            image_offset: vk::Offset3D { x: 0, y: 0, z: 0 },
+            image_extent: vk::Extent3D {
+                width: self.extent.width,
+                height: self.extent.height,
+                depth: 1,
+            },
+        };
+        self.vk_dev.logical_device.cmd_copy_image_to_buffer(
+            cmd.raw,
+            image,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            buffer.raw,
+            &[buffer_image_copy],
+        );
+
+        let prepare_present_barrier = vk::ImageMemoryBarrier {
+            src_access_mask: vk::AccessFlags::TRANSFER_READ,
+            dst_access_mask: vk::AccessFlags::empty(),
+            old_layout: vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            new_layout: vk::ImageLayout::PRESENT_SRC_KHR,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            image,
+            subresource_range,
+            ..Default::default()
+        };
+        let host_read_barrier = vk::BufferMemoryBarrier {
+            src_access_mask: vk::AccessFlags::TRANSFER_WRITE,
+            dst_access_mask: vk::AccessFlags::HOST_READ,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            buffer: buffer.raw,
+            offset: 0,
+            size: vk::WHOLE_SIZE,
+            ..Default::default()
+        };
+        self.vk_dev.logical_device.cmd_pipeline_barrier(
+            cmd.raw,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE
+                | vk::PipelineStageFlags::HOST,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[host_read_barrier],
+            &[prepare_present_barrier],
+        );
+    }
+}
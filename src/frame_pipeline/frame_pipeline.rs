@@ -58,6 +58,41 @@ impl FramePipeline {
         &self.frames[current_image].command_buffer
     }
 
+    /// Borrow the current frame's compute command buffer, beginning it the
+    /// first time it's used in a frame. This is only valid between calls to
+    /// begin_frame and end_frame.
+    ///
+    /// Compute commands are submitted to the compute queue before the frame's
+    /// graphics commands, and the graphics commands wait for them to finish.
+    /// When the device has a dedicated compute queue, this lets one frame's
+    /// compute work overlap with the previous frame's graphics work.
+    ///
+    /// Resources written by compute commands and read by graphics commands
+    /// must be shared between the queue families, see
+    /// [`RenderDevice::graphics_and_compute_families`].
+    pub fn compute_cmds(
+        &mut self,
+        current_image: usize,
+    ) -> Result<&CommandBuffer, FrameError> {
+        let current_frame = &mut self.frames[current_image];
+        if !current_frame.has_compute_work {
+            unsafe {
+                current_frame
+                    .compute_command_buffer
+                    .begin_one_time_submit()
+                    .with_context(|| {
+                        format!(
+                            "Unable to begin the compute command buffer for \
+                             frame {}",
+                            current_image
+                        )
+                    })?;
+            }
+            current_frame.has_compute_work = true;
+        }
+        Ok(&current_frame.compute_command_buffer)
+    }
+
     /// End rendering a single frame. This submits all commands on the graphics
     /// command buffer and schedules the swapchain image for presentation.
     pub fn end_frame(
@@ -86,6 +121,13 @@ impl FramePipeline {
         Ok(())
     }
 
+    /// Wait for the queue submission fence of the frame which last rendered
+    /// the swapchain image. The fence isn't reset, so the frame can begin
+    /// again as usual.
+    pub fn wait_for_frame(&self, index: usize) -> Result<(), FenceError> {
+        self.frames[index].queue_submit_fence.wait()
+    }
+
     /// Wait for every current frame's queue submission fence to be signaled.
     /// This stalls all rendering until the next call to begin_frame.
     pub fn wait_for_all_frames(&mut self) -> Result<(), FenceError> {
@@ -133,6 +175,14 @@ impl FramePipeline {
             .reset()
             .map_err(VulkanError::CommandBufferError)?;
 
+        // graphics commands wait for the compute commands, so the fence also
+        // means the compute commands are done
+        self.frames[index]
+            .compute_command_pool
+            .reset()
+            .map_err(VulkanError::CommandBufferError)?;
+        self.frames[index].has_compute_work = false;
+
         Ok(index)
     }
 
@@ -155,7 +205,51 @@ impl FramePipeline {
         Ok(&current_frame.command_buffer)
     }
 
+    /// Submit the frame's compute commands, if any were recorded.
+    fn submit_compute(&mut self, index: usize) -> Result<(), FrameError> {
+        let current_frame = &self.frames[index];
+        if !current_frame.has_compute_work {
+            return Ok(());
+        }
+        unsafe {
+            current_frame
+                .compute_command_buffer
+                .end_commands()
+                .with_context(|| {
+                    format!(
+                        "Unable to end compute command buffer for frame {}",
+                        index
+                    )
+                })?;
+        }
+        let submit_info = vk::SubmitInfo {
+            command_buffer_count: 1,
+            p_command_buffers: &current_frame.compute_command_buffer.raw,
+            signal_semaphore_count: 1,
+            p_signal_semaphores: &current_frame.compute_semaphore.raw,
+            ..Default::default()
+        };
+        unsafe {
+            self.vk_dev
+                .logical_device
+                .queue_submit(
+                    self.vk_dev.compute_queue.queue,
+                    &[submit_info],
+                    vk::Fence::null(),
+                )
+                .with_context(|| {
+                    format!(
+                        "Unable to submit compute commands on frame {}",
+                        index
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
     fn submit_and_present(&mut self, index: usize) -> Result<(), FrameError> {
+        self.submit_compute(index)?;
+
         let current_frame = &self.frames[index];
         unsafe {
             current_frame
@@ -166,18 +260,25 @@ impl FramePipeline {
                 })?;
         }
 
+        // graphics commands wait for the swapchain image before writing color
+        // and, when there's compute work, for the compute results which
+        // could be read at any stage
+        let mut wait_semaphores =
+            vec![current_frame.acquire_semaphore.as_ref().unwrap().raw];
+        let mut wait_stages =
+            vec![vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+        if current_frame.has_compute_work {
+            wait_semaphores.push(current_frame.compute_semaphore.raw);
+            wait_stages.push(vk::PipelineStageFlags::ALL_COMMANDS);
+        }
+
         // submit the command buffer
-        let wait_stage = vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT;
         let submit_info = vk::SubmitInfo {
             command_buffer_count: 1,
             p_command_buffers: &current_frame.command_buffer.raw,
-            wait_semaphore_count: 1,
-            p_wait_semaphores: &current_frame
-                .acquire_semaphore
-                .as_ref()
-                .unwrap()
-                .raw,
-            p_wait_dst_stage_mask: &wait_stage,
+            wait_semaphore_count: wait_semaphores.len() as u32,
+            p_wait_semaphores: wait_semaphores.as_ptr(),
+            p_wait_dst_stage_mask: wait_stages.as_ptr(),
             signal_semaphore_count: 1,
             p_signal_semaphores: &current_frame.release_semaphore.raw,
             ..Default::default()
@@ -1,7 +1,15 @@
+mod frame_capture;
 mod frame_error;
 mod frame_pipeline;
+mod palette;
 mod per_frame;
+mod pixel_readback;
 
 pub use self::{
-    frame_error::FrameError, frame_pipeline::FramePipeline, per_frame::PerFrame,
+    frame_capture::{CapturedFrame, FrameCapture},
+    frame_error::FrameError,
+    frame_pipeline::FramePipeline,
+    palette::{Dither, Palette},
+    per_frame::PerFrame,
+    pixel_readback::{PixelColor, PixelReadback},
 };
@@ -0,0 +1,232 @@
+use crate::frame_pipeline::CapturedFrame;
+
+/// The 4x4 Bayer threshold matrix used for ordered dithering.
+const BAYER_4X4: [[f32; 4]; 4] = [
+    [0.0, 8.0, 2.0, 10.0],
+    [12.0, 4.0, 14.0, 6.0],
+    [3.0, 11.0, 1.0, 9.0],
+    [15.0, 7.0, 13.0, 5.0],
+];
+
+/// How colors which aren't in a palette are approximated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dither {
+    /// Every pixel becomes the nearest palette color. Gradients turn into
+    /// flat bands.
+    None,
+
+    /// Pixels are offset by a repeating 4x4 Bayer pattern before picking the
+    /// nearest color, for the cross-hatched look of old consoles.
+    Ordered,
+
+    /// Floyd-Steinberg error diffusion. The error of each pixel is spread to
+    /// the pixels right and below it, which gives a noisy but more accurate
+    /// result.
+    FloydSteinberg,
+}
+
+/// A fixed set of colors that captured frames can be quantized to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: Vec<[u8; 3]>,
+}
+
+impl Palette {
+    /// Create a palette from rgb colors, as they're shown on screen.
+    ///
+    /// # Panics
+    ///
+    /// When there are no colors.
+    pub fn new(colors: impl Into<Vec<[u8; 3]>>) -> Self {
+        let colors = colors.into();
+        assert!(!colors.is_empty(), "a palette needs at least one color");
+        Self { colors }
+    }
+
+    /// Parse a palette from hex strings like "#ff004d" or "ff004d".
+    pub fn from_hex(colors: &[&str]) -> Option<Self> {
+        let colors = colors
+            .iter()
+            .map(|hex| {
+                let hex = hex.trim_start_matches('#');
+                if hex.len() != 6 {
+                    return None;
+                }
+                let channel =
+                    |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+                Some([channel(0)?, channel(2)?, channel(4)?])
+            })
+            .collect::<Option<Vec<[u8; 3]>>>()?;
+        if colors.is_empty() {
+            return None;
+        }
+        Some(Self { colors })
+    }
+
+    /// Black and white.
+    pub fn monochrome() -> Self {
+        Self::new(vec![[0, 0, 0], [255, 255, 255]])
+    }
+
+    /// The four greens of the original Game Boy screen.
+    pub fn game_boy() -> Self {
+        Self::new(vec![
+            [15, 56, 15],
+            [48, 98, 48],
+            [139, 172, 15],
+            [155, 188, 15],
+        ])
+    }
+
+    /// The sixteen colors of the PICO-8 fantasy console.
+    pub fn pico8() -> Self {
+        Self::from_hex(&[
+            "000000", "1d2b53", "7e2553", "008751", "ab5236", "5f574f",
+            "c2c3c7", "fff1e8", "ff004d", "ffa300", "ffec27", "00e436",
+            "29adff", "83769c", "ff77a8", "ffccaa",
+        ])
+        .unwrap()
+    }
+
+    pub fn colors(&self) -> &[[u8; 3]] {
+        &self.colors
+    }
+
+    /// The palette color closest to an rgb color. Channels are in the range
+    /// [0, 255] but may fall outside of it after dithering.
+    pub fn nearest(&self, rgb: [f32; 3]) -> [u8; 3] {
+        let distance = |color: &[u8; 3]| {
+            // weight the channels by how sensitive eyes are to them
+            let dr = rgb[0] - color[0] as f32;
+            let dg = rgb[1] - color[1] as f32;
+            let db = rgb[2] - color[2] as f32;
+            0.3 * dr * dr + 0.59 * dg * dg + 0.11 * db * db
+        };
+        *self
+            .colors
+            .iter()
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+            .unwrap()
+    }
+
+    /// How far ordered dithering nudges each channel. Palettes with fewer
+    /// colors have bigger gaps between them, so they need a larger spread.
+    fn ordered_spread(&self) -> f32 {
+        255.0 / (self.colors.len() as f32).cbrt().max(1.0)
+    }
+}
+
+impl CapturedFrame {
+    /// Reduce the frame to a fixed palette, e.g. for retro-styled exports.
+    /// Alpha is left as captured.
+    pub fn quantized(&self, palette: &Palette, dither: Dither) -> Self {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut rgba = self.rgba.clone();
+        let pixel = |x: usize, y: usize| 4 * (y * width + x);
+        match dither {
+            Dither::None | Dither::Ordered => {
+                let spread = palette.ordered_spread();
+                for y in 0..height {
+                    for x in 0..width {
+                        let offset = if dither == Dither::Ordered {
+                            spread
+                                * ((BAYER_4X4[y % 4][x % 4] + 0.5) / 16.0 - 0.5)
+                        } else {
+                            0.0
+                        };
+                        let i = pixel(x, y);
+                        let nearest = palette.nearest([
+                            rgba[i] as f32 + offset,
+                            rgba[i + 1] as f32 + offset,
+                            rgba[i + 2] as f32 + offset,
+                        ]);
+                        rgba[i..i + 3].copy_from_slice(&nearest);
+                    }
+                }
+            }
+            Dither::FloydSteinberg => {
+                let mut values: Vec<[f32; 3]> = self
+                    .rgba
+                    .chunks_exact(4)
+                    .map(|c| [c[0] as f32, c[1] as f32, c[2] as f32])
+                    .collect();
+                for y in 0..height {
+                    for x in 0..width {
+                        let current = values[y * width + x];
+                        let nearest = palette.nearest(current);
+                        let i = pixel(x, y);
+                        rgba[i..i + 3].copy_from_slice(&nearest);
+
+                        let mut spread = |dx: isize, dy: usize, weight: f32| {
+                            let nx = x as isize + dx;
+                            if nx < 0
+                                || nx as usize >= width
+                                || y + dy >= height
+                            {
+                                return;
+                            }
+                            let target =
+                                &mut values[(y + dy) * width + nx as usize];
+                            for c in 0..3 {
+                                target[c] +=
+                                    weight * (current[c] - nearest[c] as f32);
+                            }
+                        };
+                        spread(1, 0, 7.0 / 16.0);
+                        spread(-1, 1, 3.0 / 16.0);
+                        spread(0, 1, 5.0 / 16.0);
+                        spread(1, 1, 1.0 / 16.0);
+                    }
+                }
+            }
+        }
+        Self {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gray_frame(width: u32, height: u32, value: u8) -> CapturedFrame {
+        CapturedFrame {
+            width,
+            height,
+            rgba: [value, value, value, 255].repeat((width * height) as usize),
+        }
+    }
+
+    fn white_pixels(frame: &CapturedFrame) -> usize {
+        frame.rgba.chunks_exact(4).filter(|p| p[0] == 255).count()
+    }
+
+    #[test]
+    fn test_nearest_color() {
+        let palette =
+            Palette::from_hex(&["#000000", "#ff0000", "#ffffff"]).unwrap();
+        assert_eq!(palette.nearest([200.0, 30.0, 10.0]), [255, 0, 0]);
+        assert_eq!(palette.nearest([-40.0, 10.0, 20.0]), [0, 0, 0]);
+        assert_eq!(palette.nearest([240.0, 250.0, 300.0]), [255, 255, 255]);
+        assert!(Palette::from_hex(&["#12345"]).is_none());
+    }
+
+    #[test]
+    fn test_dithering_mixes_palette_colors() {
+        let palette = Palette::monochrome();
+        let gray = gray_frame(8, 8, 128);
+
+        let flat = gray.quantized(&palette, Dither::None);
+        assert_eq!(white_pixels(&flat), 64);
+
+        // both dithers approximate 50% gray with about half white pixels
+        let ordered = gray.quantized(&palette, Dither::Ordered);
+        assert_eq!(white_pixels(&ordered), 32);
+        let diffused = gray.quantized(&palette, Dither::FloydSteinberg);
+        assert!((28..=36).contains(&white_pixels(&diffused)));
+        assert!(diffused.rgba.chunks_exact(4).all(|p| p[3] == 255));
+    }
+}
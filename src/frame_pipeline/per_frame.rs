@@ -24,6 +24,21 @@ pub struct PerFrame {
 
     /// The command pool for operations in this frame.
     pub command_pool: Arc<CommandPool>,
+
+    /// Signalled when the frame's compute commands have completed. The
+    /// graphics commands wait on it when the frame has compute work.
+    pub compute_semaphore: Semaphore,
+
+    /// The command buffer for compute operations in this frame.
+    pub compute_command_buffer: CommandBuffer,
+
+    /// The command pool for compute operations in this frame. It belongs to
+    /// the compute queue's family.
+    pub compute_command_pool: Arc<CommandPool>,
+
+    /// True when compute commands have been recorded for this frame and
+    /// still need to be submitted.
+    pub has_compute_work: bool,
 }
 
 impl PerFrame {
@@ -37,12 +52,22 @@ impl PerFrame {
             Arc::new(CommandPool::new_transient_graphics_pool(vk_dev.clone())?);
         let command_buffer = CommandBuffer::new_primary(command_pool.clone())?;
 
+        let compute_semaphore = Semaphore::new(vk_dev.clone())?;
+        let compute_command_pool =
+            Arc::new(CommandPool::new_transient_compute_pool(vk_dev.clone())?);
+        let compute_command_buffer =
+            CommandBuffer::new_primary(compute_command_pool.clone())?;
+
         Ok(Self {
             acquire_semaphore,
             release_semaphore,
             queue_submit_fence,
             command_pool,
             command_buffer,
+            compute_semaphore,
+            compute_command_buffer,
+            compute_command_pool,
+            has_compute_work: false,
         })
     }
 }
@@ -60,6 +85,12 @@ impl VulkanDebug for PerFrame {
         self.command_pool
             .set_debug_name(format!("{} - Command Pool", name))?;
         self.command_buffer
-            .set_debug_name(format!("{} - Command Buffer", name))
+            .set_debug_name(format!("{} - Command Buffer", name))?;
+        self.compute_semaphore
+            .set_debug_name(format!("{} - Compute Semaphore", name))?;
+        self.compute_command_pool
+            .set_debug_name(format!("{} - Compute Command Pool", name))?;
+        self.compute_command_buffer
+            .set_debug_name(format!("{} - Compute Command Buffer", name))
     }
 }
@@ -0,0 +1,347 @@
+use ::{ash::vk, std::sync::Arc};
+
+use crate::{
+    frame_pipeline::FrameError,
+    math::color::srgb_to_linear,
+    vec4,
+    vulkan::{
+        errors::VulkanError, Buffer, CommandBuffer, MemoryAllocator,
+        RenderDevice, VulkanDebug,
+    },
+    Vec4,
+};
+
+/// A single pixel read back from a presented swapchain image.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PixelColor {
+    /// The pixel's x coordinate in framebuffer pixels.
+    pub x: u32,
+
+    /// The pixel's y coordinate in framebuffer pixels.
+    pub y: u32,
+
+    /// The rgba color as shown on screen. For sRGB swapchains this is the
+    /// gamma-encoded value, which is what most palette tools expect.
+    pub display: Vec4,
+
+    /// The rgba color in the same space shaders write to. Using this as a
+    /// vertex color reproduces the pixel exactly.
+    pub linear: Vec4,
+}
+
+/// The byte order of a supported swapchain format and whether the hardware
+/// applies the sRGB transfer function when writing to it.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct PixelLayout {
+    bgra: bool,
+    srgb: bool,
+}
+
+impl PixelLayout {
+    pub(super) fn for_format(format: vk::Format) -> Option<Self> {
+        match format {
+            vk::Format::B8G8R8A8_SRGB => Some(Self {
+                bgra: true,
+                srgb: true,
+            }),
+            vk::Format::B8G8R8A8_UNORM => Some(Self {
+                bgra: true,
+                srgb: false,
+            }),
+            vk::Format::R8G8B8A8_SRGB => Some(Self {
+                bgra: false,
+                srgb: true,
+            }),
+            vk::Format::R8G8B8A8_UNORM => Some(Self {
+                bgra: false,
+                srgb: false,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Reorder the raw bytes of a single pixel as rgba. The values are left
+    /// as they're shown on screen.
+    pub(super) fn to_rgba8(self, bytes: [u8; 4]) -> [u8; 4] {
+        let [a, b, c, alpha] = bytes;
+        if self.bgra {
+            [c, b, a, alpha]
+        } else {
+            [a, b, c, alpha]
+        }
+    }
+
+    /// Decode the raw bytes of a single pixel.
+    fn decode(&self, x: u32, y: u32, bytes: [u8; 4]) -> PixelColor {
+        let [red, green, blue, alpha] = self.to_rgba8(bytes);
+        let unorm = |value: u8| value as f32 / 255.0;
+        let display = vec4(unorm(red), unorm(green), unorm(blue), unorm(alpha));
+        let linear = if self.srgb {
+            vec4(
+                srgb_to_linear(display.x),
+                srgb_to_linear(display.y),
+                srgb_to_linear(display.z),
+                display.w,
+            )
+        } else {
+            display
+        };
+        PixelColor {
+            x,
+            y,
+            display,
+            linear,
+        }
+    }
+}
+
+/// PixelReadback copies single pixels out of rendered swapchain images so
+/// the application can inspect what was actually drawn.
+///
+/// Each swapchain image has its own small host-visible buffer. A copy is
+/// recorded into a frame's command buffer and the result is available the
+/// next time the same swapchain image is acquired, after its fence has been
+/// waited on.
+pub struct PixelReadback {
+    /// One mapped, single-pixel buffer for each swapchain image.
+    buffers: Vec<Buffer>,
+
+    /// The pixel requested for each swapchain image which hasn't been taken
+    /// yet.
+    pending: Vec<Option<(u32, u32)>>,
+
+    /// The current swapchain's pixel layout. None when the swapchain can't be
+    /// read back.
+    layout: Option<PixelLayout>,
+
+    vk_alloc: Arc<dyn MemoryAllocator>,
+    vk_dev: Arc<RenderDevice>,
+}
+
+impl PixelReadback {
+    pub fn new(
+        vk_dev: Arc<RenderDevice>,
+        vk_alloc: Arc<dyn MemoryAllocator>,
+    ) -> Result<Self, FrameError> {
+        let mut pixel_readback = Self {
+            buffers: vec![],
+            pending: vec![],
+            layout: None,
+            vk_alloc,
+            vk_dev,
+        };
+        pixel_readback.rebuild_swapchain_resources()?;
+        Ok(pixel_readback)
+    }
+
+    /// True when the current swapchain's images can be read back.
+    pub fn is_supported(&self) -> bool {
+        self.layout.is_some()
+    }
+
+    /// Rebuild all swapchain-dependent resources. Any pending readbacks are
+    /// dropped because their images no longer exist.
+    pub fn rebuild_swapchain_resources(&mut self) -> Result<(), FrameError> {
+        let (format, usage) = self.vk_dev.with_swapchain(|swapchain| {
+            (swapchain.format, swapchain.image_usage)
+        });
+        self.layout = if usage.contains(vk::ImageUsageFlags::TRANSFER_SRC) {
+            PixelLayout::for_format(format)
+        } else {
+            None
+        };
+        if self.layout.is_none() {
+            log::warn!(
+                "Pixel readback is unavailable for this swapchain, \
+                 format: {:?}, usage: {:?}",
+                format,
+                usage
+            );
+        }
+
+        self.buffers.clear();
+        self.pending.clear();
+        for i in 0..self.vk_dev.swapchain_image_count() {
+            let mut buffer = Buffer::new(
+                self.vk_dev.clone(),
+                self.vk_alloc.clone(),
+                vk::BufferUsageFlags::TRANSFER_DST,
+                vk::MemoryPropertyFlags::HOST_VISIBLE
+                    | vk::MemoryPropertyFlags::HOST_COHERENT,
+                4,
+            )
+            .map_err(VulkanError::BufferError)?;
+            buffer.map().map_err(VulkanError::BufferError)?;
+            buffer
+                .set_debug_name(format!("Pixel Readback {}", i))
+                .map_err(VulkanError::VulkanDebugError)?;
+            self.buffers.push(buffer);
+            self.pending.push(None);
+        }
+        Ok(())
+    }
+
+    /// Take the pixel which was read back the last time the given swapchain
+    /// image was rendered.
+    ///
+    /// This must only be called after the swapchain image's frame fence has
+    /// been waited on, e.g. right after `FramePipeline::begin_frame` returns
+    /// the image's index.
+    pub fn take_pixel(
+        &mut self,
+        swapchain_image_index: usize,
+    ) -> Result<Option<PixelColor>, FrameError> {
+        let (x, y) = match self.pending[swapchain_image_index].take() {
+            Some(pixel) => pixel,
+            None => return Ok(None),
+        };
+        let layout = match self.layout {
+            Some(layout) => layout,
+            None => return Ok(None),
+        };
+        let data = self.buffers[swapchain_image_index]
+            .data::<u8>()
+            .map_err(VulkanError::BufferError)?;
+        Ok(Some(layout.decode(
+            x,
+            y,
+            [data[0], data[1], data[2], data[3]],
+        )))
+    }
+
+    /// Record commands which copy a single pixel out of the swapchain image.
+    /// Pixels outside of the image are clamped to its edges. The request is
+    /// ignored when the swapchain doesn't support readback.
+    ///
+    /// # Safety
+    ///
+    /// - This must be recorded after the frame's render pass has ended, the
+    ///   image is expected to be in the PRESENT_SRC_KHR layout.
+    /// - The copy is not complete until the frame's commands have finished
+    ///   executing on the gpu.
+    pub unsafe fn write_readback_commands(
+        &mut self,
+        cmd: &CommandBuffer,
+        swapchain_image_index: usize,
+        x: u32,
+        y: u32,
+    ) {
+        if self.layout.is_none() {
+            return;
+        }
+        let (image, extent) = self.vk_dev.with_swapchain(|swapchain| {
+            (swapchain.images[swapchain_image_index], swapchain.extent)
+        });
+        let x = x.min(extent.width.saturating_sub(1));
+        let y = y.min(extent.height.saturating_sub(1));
+
+        let subresource_range = vk::ImageSubresourceRange {
+            aspect_mask: vk::ImageAspectFlags::COLOR,
+            base_mip_level: 0,
+            level_count: 1,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let prepare_read_barrier = vk::ImageMemoryBarrier {
+            src_access_mask: vk::AccessFlags::COLOR_ATTACHMENT_WRITE,
+            dst_access_mask: vk::AccessFlags::TRANSFER_READ,
+            old_layout: vk::ImageLayout::PRESENT_SRC_KHR,
+            new_layout: vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            image,
+            subresource_range,
+            ..Default::default()
+        };
+        self.vk_dev.logical_device.cmd_pipeline_barrier(
+            cmd.raw,
+            vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[],
+            &[prepare_read_barrier],
+        );
+
+        let buffer = &self.buffers[swapchain_image_index];
+        let buffer_image_copy = vk::BufferImageCopy {
+            buffer_offset: 0,
+            buffer_row_length: 0,
+            buffer_image_height: 0,
+            image_subresource: vk::ImageSubresourceLayers {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                mip_level: 0,
+                base_array_layer: 0,
+                layer_count: 1,
+            },
+            image_offset: vk::Offset3D {
+                x: x as i32,
+                y: y as i32,
+                z: 0,
+            },
+            image_extent: vk::Extent3D {
+                width: 1,
+                height: 1,
+                depth: 1,
+            },
+        };
+        self.vk_dev.logical_device.cmd_copy_image_to_buffer(
+            cmd.raw,
+            image,
+            vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            buffer.raw,
+            &[buffer_image_copy],
+        );
+
+        let prepare_present_barrier = vk::ImageMemoryBarrier {
+            src_access_mask: vk::AccessFlags::TRANSFER_READ,
+            dst_access_mask: vk::AccessFlags::empty(),
+            old_layout: vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+            new_layout: vk::ImageLayout::PRESENT_SRC_KHR,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            image,
+            subresource_range,
+            ..Default::default()
+        };
+        let host_read_barrier = vk::BufferMemoryBarrier {
+            src_access_mask: vk::AccessFlags::TRANSFER_WRITE,
+            dst_access_mask: vk::AccessFlags::HOST_READ,
+            src_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            dst_queue_family_index: vk::QUEUE_FAMILY_IGNORED,
+            buffer: buffer.raw,
+            offset: 0,
+            size: vk::WHOLE_SIZE,
+            ..Default::default()
+        };
+        self.vk_dev.logical_device.cmd_pipeline_barrier(
+            cmd.raw,
+            vk::PipelineStageFlags::TRANSFER,
+            vk::PipelineStageFlags::BOTTOM_OF_PIPE
+                | vk::PipelineStageFlags::HOST,
+            vk::DependencyFlags::empty(),
+            &[],
+            &[host_read_barrier],
+            &[prepare_present_barrier],
+        );
+
+        self.pending[swapchain_image_index] = Some((x, y));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_bgra_srgb() {
+        let layout =
+            PixelLayout::for_format(vk::Format::B8G8R8A8_SRGB).unwrap();
+        let pixel = layout.decode(1, 2, [0, 128, 255, 255]);
+
+        assert_eq!(pixel.display.x, 1.0);
+        assert_eq!(pixel.display.z, 0.0);
+        assert!((pixel.linear.y - 0.2158).abs() < 1e-3);
+        assert_eq!(pixel.linear.w, 1.0);
+    }
+}
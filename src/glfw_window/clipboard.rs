@@ -0,0 +1,84 @@
+use ::{
+    image::RgbaImage,
+    std::ffi::{CStr, CString},
+};
+
+use crate::{
+    glfw_window::{clipboard_image, ClipboardError},
+    ui::Clipboard,
+};
+
+/// The system clipboard, accessed through GLFW.
+///
+/// GLFW only supports text, so images are copied with the platform's
+/// clipboard tool: wl-copy and wl-paste on Wayland, xclip on X11, and
+/// PowerShell on Windows. Those tools must be installed for images to work.
+///
+/// The clipboard keeps GLFW initialized, so it can be given to a UI which
+/// outlives the window.
+#[derive(Debug)]
+pub struct GlfwClipboard {
+    _glfw: glfw::Glfw,
+}
+
+impl GlfwClipboard {
+    pub(super) fn new(glfw: &glfw::Glfw) -> Self {
+        Self {
+            _glfw: glfw.clone(),
+        }
+    }
+
+    /// Read the image on the clipboard.
+    pub fn read_image(&self) -> Result<RgbaImage, ClipboardError> {
+        clipboard_image::read_image()
+    }
+
+    /// Replace the clipboard's contents with the image.
+    pub fn write_image(&self, image: &RgbaImage) -> Result<(), ClipboardError> {
+        clipboard_image::write_image(image)
+    }
+}
+
+impl Clipboard for GlfwClipboard {
+    fn text(&mut self) -> Option<String> {
+        // GLFW ignores the window argument, so null is used rather than
+        // holding a pointer to the window which could dangle
+        unsafe {
+            let text = glfw::ffi::glfwGetClipboardString(std::ptr::null_mut());
+            if text.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(text).to_string_lossy().into_owned())
+            }
+        }
+    }
+
+    fn set_text(&mut self, text: &str) {
+        // C strings end at the first nul, so that's where the copy ends too
+        let text = text.split('\0').next().unwrap_or_default();
+        let text = CString::new(text).unwrap_or_default();
+        unsafe {
+            glfw::ffi::glfwSetClipboardString(
+                std::ptr::null_mut(),
+                text.as_ptr(),
+            );
+        }
+    }
+
+    fn image(&mut self) -> Option<RgbaImage> {
+        match self.read_image() {
+            Ok(image) => Some(image),
+            Err(ClipboardError::NoImage) => None,
+            Err(error) => {
+                log::warn!("unable to paste the clipboard image: {}", error);
+                None
+            }
+        }
+    }
+
+    fn set_image(&mut self, image: &RgbaImage) {
+        if let Err(error) = self.write_image(image) {
+            log::warn!("unable to copy the image to the clipboard: {}", error);
+        }
+    }
+}
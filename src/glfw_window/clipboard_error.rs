@@ -0,0 +1,32 @@
+use ::{
+    image::ImageError,
+    std::{io, process::ExitStatus},
+    thiserror::Error,
+};
+
+/// Things which can go wrong while copying images to or from the system
+/// clipboard.
+#[derive(Error, Debug)]
+pub enum ClipboardError {
+    #[error("Clipboard images aren't supported on this platform")]
+    Unsupported,
+
+    #[error("Unable to run {0}, is it installed?")]
+    ToolUnavailable(&'static str, #[source] io::Error),
+
+    #[error("{tool} failed with {status}: {stderr}")]
+    ToolFailed {
+        tool: &'static str,
+        status: ExitStatus,
+        stderr: String,
+    },
+
+    #[error("There's no image on the clipboard")]
+    NoImage,
+
+    #[error("Unable to use the temporary clipboard image file")]
+    TemporaryFile(#[source] io::Error),
+
+    #[error("Unable to encode or decode the clipboard image")]
+    InvalidImage(#[from] ImageError),
+}
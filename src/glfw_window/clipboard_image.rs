@@ -0,0 +1,233 @@
+//! Images on the system clipboard.
+//!
+//! GLFW's clipboard only holds text, so images are copied with each
+//! platform's clipboard tool instead: wl-copy and wl-paste on Wayland, xclip
+//! on X11, and PowerShell on Windows. Images are exchanged as PNGs.
+
+use ::{
+    image::{codecs::png::PngEncoder, ColorType, ImageEncoder, RgbaImage},
+    std::{
+        io::Write,
+        process::{Command, Stdio},
+        sync::atomic::{AtomicU32, Ordering},
+    },
+};
+
+use crate::glfw_window::ClipboardError;
+
+/// The tool used to reach the clipboard.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Backend {
+    Wayland,
+    X11,
+    Windows,
+}
+
+impl Backend {
+    /// The backend for the current session.
+    fn current() -> Option<Self> {
+        Self::select(
+            cfg!(windows),
+            std::env::var_os("WAYLAND_DISPLAY").is_some(),
+            std::env::var_os("DISPLAY").is_some(),
+        )
+    }
+
+    /// Wayland sessions usually run X11 programs too, so Wayland's own
+    /// clipboard is preferred when both are available.
+    fn select(windows: bool, wayland: bool, x11: bool) -> Option<Self> {
+        if windows {
+            Some(Self::Windows)
+        } else if wayland {
+            Some(Self::Wayland)
+        } else if x11 {
+            Some(Self::X11)
+        } else {
+            None
+        }
+    }
+}
+
+/// Read the image on the clipboard.
+pub(super) fn read_image() -> Result<RgbaImage, ClipboardError> {
+    let png = match Backend::current().ok_or(ClipboardError::Unsupported)? {
+        Backend::Wayland => run(
+            "wl-paste",
+            Command::new("wl-paste").args([
+                "--no-newline",
+                "--type",
+                "image/png",
+            ]),
+            None,
+        )?,
+        Backend::X11 => run(
+            "xclip",
+            Command::new("xclip").args([
+                "-selection",
+                "clipboard",
+                "-target",
+                "image/png",
+                "-out",
+            ]),
+            None,
+        )?,
+        Backend::Windows => {
+            let path = temporary_png_path();
+            let script = format!(
+                "$image = [System.Windows.Forms.Clipboard]::GetImage(); \
+                 if ($image) {{ $image.Save({}, \
+                 [System.Drawing.Imaging.ImageFormat]::Png) }}",
+                quoted(&path)
+            );
+            let png = run("powershell", &mut powershell(&script), None)
+                .map(|_| std::fs::read(&path));
+            let _ = std::fs::remove_file(&path);
+            match png? {
+                Ok(png) => png,
+                Err(_) => return Err(ClipboardError::NoImage),
+            }
+        }
+    };
+    if png.is_empty() {
+        return Err(ClipboardError::NoImage);
+    }
+    Ok(
+        image::load_from_memory_with_format(&png, image::ImageFormat::Png)?
+            .into_rgba8(),
+    )
+}
+
+/// Replace the clipboard's contents with the image. This doesn't use GLFW, so
+/// it can run on any thread.
+pub(crate) fn write_image(image: &RgbaImage) -> Result<(), ClipboardError> {
+    let backend = Backend::current().ok_or(ClipboardError::Unsupported)?;
+    let mut png = vec![];
+    PngEncoder::new(&mut png).write_image(
+        image.as_raw(),
+        image.width(),
+        image.height(),
+        ColorType::Rgba8,
+    )?;
+    match backend {
+        Backend::Wayland => {
+            run(
+                "wl-copy",
+                Command::new("wl-copy").args(["--type", "image/png"]),
+                Some(&png),
+            )?;
+        }
+        Backend::X11 => {
+            // xclip keeps running in the background to serve the clipboard
+            // until something else is copied
+            run(
+                "xclip",
+                Command::new("xclip").args([
+                    "-selection",
+                    "clipboard",
+                    "-target",
+                    "image/png",
+                    "-in",
+                ]),
+                Some(&png),
+            )?;
+        }
+        Backend::Windows => {
+            let path = temporary_png_path();
+            let script = format!(
+                "$image = [System.Drawing.Image]::FromFile({}); \
+                 [System.Windows.Forms.Clipboard]::SetImage($image); \
+                 $image.Dispose()",
+                quoted(&path)
+            );
+            let result = std::fs::write(&path, &png)
+                .map_err(ClipboardError::TemporaryFile)
+                .and_then(|_| {
+                    run("powershell", &mut powershell(&script), None)
+                });
+            // the file is removed even if it was only partly written
+            let _ = std::fs::remove_file(&path);
+            result?;
+        }
+    }
+    Ok(())
+}
+
+/// Run a clipboard tool, writing the input to its stdin, and collect its
+/// stdout.
+///
+/// Tools which copy fork into the background to serve the clipboard, and
+/// the fork keeps any pipes open, so only stdin is piped for them.
+fn run(
+    tool: &'static str,
+    command: &mut Command,
+    input: Option<&[u8]>,
+) -> Result<Vec<u8>, ClipboardError> {
+    let piped = |pipe: bool| if pipe { Stdio::piped() } else { Stdio::null() };
+    let mut child = command
+        .stdin(piped(input.is_some()))
+        .stdout(piped(input.is_none()))
+        .stderr(piped(input.is_none()))
+        .spawn()
+        .map_err(|error| ClipboardError::ToolUnavailable(tool, error))?;
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        if let Err(error) = stdin.write_all(input) {
+            // the tool is stopped and reaped so it isn't left as a zombie
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ClipboardError::ToolUnavailable(tool, error));
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| ClipboardError::ToolUnavailable(tool, error))?;
+    if !output.status.success() {
+        return Err(ClipboardError::ToolFailed {
+            tool,
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// A PowerShell command which can use the Windows Forms clipboard, which
+/// needs a single threaded apartment.
+fn powershell(script: &str) -> Command {
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-STA", "-Command"])
+        .arg(format!(
+            "Add-Type -AssemblyName System.Windows.Forms, System.Drawing; {}",
+            script
+        ));
+    command
+}
+
+/// A path as a single quoted PowerShell string.
+fn quoted(path: &std::path::Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', "''"))
+}
+
+/// A unique path for handing a PNG to PowerShell. Paths are never reused by
+/// the process, so copies and pastes which overlap don't share a file.
+fn temporary_png_path() -> std::path::PathBuf {
+    static NEXT: AtomicU32 = AtomicU32::new(0);
+    std::env::temp_dir().join(format!(
+        "ccthw-clipboard-{}-{}.png",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wayland_is_preferred_over_x11() {
+        assert_eq!(Backend::select(false, true, true), Some(Backend::Wayland));
+        assert_eq!(Backend::select(false, false, true), Some(Backend::X11));
+        assert_eq!(Backend::select(true, false, true), Some(Backend::Windows));
+        assert_eq!(Backend::select(false, false, false), None);
+    }
+}
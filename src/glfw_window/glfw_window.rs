@@ -1,7 +1,7 @@
 use ash::{extensions::khr::Surface, vk, vk::Handle};
 
 use crate::{
-    glfw_window::{EventReceiver, WindowError},
+    glfw_window::{EventReceiver, GlfwClipboard, WindowError},
     vulkan::{Instance, RenderDevice, WindowSurface},
 };
 
@@ -23,11 +23,40 @@ pub struct GlfwWindow {
 
     /// The window's size before being put into fullscreen mode.
     window_size: (i32, i32),
+
+    /// True when the window's framebuffer is blended with the desktop.
+    transparent: bool,
 }
 
 impl GlfwWindow {
     /// Initialize the GLFW library and create a new window.
     pub fn new(window_title: &str) -> Result<Self, WindowError> {
+        Self::new_with_options(window_title, true, false)
+    }
+
+    /// Initialize the GLFW library and create a window whose framebuffer's
+    /// alpha channel is blended with the desktop behind it, e.g. for overlay
+    /// windows.
+    ///
+    /// Colors are presented as premultiplied alpha, so clear to a color
+    /// whose channels are already multiplied by its alpha, like
+    /// `[0.0, 0.0, 0.0, 0.0]`. Not every platform supports transparent
+    /// windows, the window is opaque when they aren't.
+    pub fn new_transparent(window_title: &str) -> Result<Self, WindowError> {
+        Self::new_with_options(window_title, true, true)
+    }
+
+    /// Initialize the GLFW library and create a new window which is never
+    /// shown, e.g. for rendering frames without any user interaction.
+    pub fn new_hidden(window_title: &str) -> Result<Self, WindowError> {
+        Self::new_with_options(window_title, false, false)
+    }
+
+    fn new_with_options(
+        window_title: &str,
+        visible: bool,
+        transparent: bool,
+    ) -> Result<Self, WindowError> {
         // Initialize the GLFW library
         let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS)?;
 
@@ -40,6 +69,8 @@ impl GlfwWindow {
             glfw::ClientApiHint::NoApi,
         ));
         glfw.window_hint(glfw::WindowHint::ScaleToMonitor(true));
+        glfw.window_hint(glfw::WindowHint::Visible(visible));
+        glfw.window_hint(glfw::WindowHint::TransparentFramebuffer(transparent));
 
         // Create a windowed application. Fullscreen can always be toggled
         // later.
@@ -56,6 +87,7 @@ impl GlfwWindow {
             event_receiver: Some(event_receiver),
             window_pos,
             window_size,
+            transparent,
         })
     }
 
@@ -78,6 +110,13 @@ impl GlfwWindow {
         glfw::flush_messages(event_receiver)
     }
 
+    /// The system clipboard. Give it to the UI with
+    /// [`UI::set_clipboard`](crate::ui::UI::set_clipboard) so text can be
+    /// copied and pasted between the UI and other programs.
+    pub fn clipboard(&self) -> GlfwClipboard {
+        GlfwClipboard::new(&self.glfw)
+    }
+
     /// Toggle the window in and out of fullcreen mode on the primary monitor.
     pub fn toggle_fullscreen(&mut self) -> Result<(), WindowError> {
         use glfw::WindowMode;
@@ -127,6 +166,42 @@ impl GlfwWindow {
         Ok(())
     }
 
+    /// The refresh rate of the monitor the window is on. Windowed windows use
+    /// the monitor which contains the window's center. None when the window
+    /// isn't on any monitor's current video mode.
+    pub fn current_refresh_rate(&mut self) -> Option<u32> {
+        use glfw::WindowMode;
+
+        let fullscreen_rate = self.window.with_window_mode(|mode| match mode {
+            WindowMode::FullScreen(monitor) => {
+                monitor.get_video_mode().map(|mode| mode.refresh_rate)
+            }
+            WindowMode::Windowed => None,
+        });
+        if fullscreen_rate.is_some() {
+            return fullscreen_rate;
+        }
+
+        let (x, y) = self.window.get_pos();
+        let (w, h) = self.window.get_size();
+        let (center_x, center_y) = (x + w / 2, y + h / 2);
+        self.glfw.with_connected_monitors(|_, monitors| {
+            monitors.iter().find_map(|monitor| {
+                let mode = monitor.get_video_mode()?;
+                let (left, top) = monitor.get_pos();
+                let inside = center_x >= left
+                    && center_x < left + mode.width as i32
+                    && center_y >= top
+                    && center_y < top + mode.height as i32;
+                if inside {
+                    Some(mode.refresh_rate)
+                } else {
+                    None
+                }
+            })
+        })
+    }
+
     /// Create the Vulkan instance and surface for the current window.
     pub fn create_vulkan_device(&self) -> Result<RenderDevice, WindowError> {
         let required_extensions = self
@@ -152,6 +227,7 @@ impl GlfwWindow {
 
         let device = RenderDevice::new(instance, window_surface)
             .map_err(WindowError::UnexpectedRenderDeviceError)?;
+        device.set_transparent_swapchain(self.transparent);
 
         let (w, h) = self.window.get_framebuffer_size();
         device.rebuild_swapchain((w as u32, h as u32))?;
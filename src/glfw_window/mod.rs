@@ -1,5 +1,11 @@
-pub use self::{glfw_window::GlfwWindow, window_error::WindowError};
+pub use self::{
+    clipboard::GlfwClipboard, clipboard_error::ClipboardError,
+    glfw_window::GlfwWindow, window_error::WindowError,
+};
 
+mod clipboard;
+mod clipboard_error;
+pub(crate) mod clipboard_image;
 mod glfw_window;
 mod window_error;
 
@@ -2,18 +2,19 @@ use ::anyhow::Result;
 
 mod sprite;
 mod vertex;
+mod vertex_chunk;
 
 pub mod triangles;
 
-pub use self::{sprite::Sprite, vertex::Vertex};
+pub use self::{
+    sprite::Sprite,
+    vertex::{Vertex, VertexFormat},
+    vertex_chunk::{tessellate_in_parallel, VertexChunk},
+};
 
 /// Types which implement this trait manage a stream of vertices which are
 /// rendered to the screen during the current frame.
-pub trait VertexStream {
+pub trait VertexStream<V = Vertex> {
     /// Push vertices into the frame. Indices index into the given vertex slice.
-    fn push_vertices(
-        &mut self,
-        vertices: &[Vertex],
-        indices: &[u32],
-    ) -> Result<()>;
+    fn push_vertices(&mut self, vertices: &[V], indices: &[u32]) -> Result<()>;
 }
@@ -1,6 +1,7 @@
 use ::anyhow::Result;
 
 use crate::{
+    asset_loader::TextureId,
     immediate_mode_graphics::{Vertex, VertexStream},
     vec2, vec3, vec4, Vec2,
 };
@@ -23,9 +24,9 @@ pub struct Sprite {
     /// The world-space depth to render the sprite at.
     pub depth: f32,
 
-    /// The sprite's texture. This is the index provided by the [`AssetLoader`]
+    /// The sprite's texture. This is the id provided by the [`AssetLoader`]
     /// when reading a texture.
-    pub texture_index: i32,
+    pub texture_index: TextureId,
 }
 
 impl Default for Sprite {
@@ -36,7 +37,7 @@ impl Default for Sprite {
             position: vec2(0.0, 0.0),
             angle_in_radians: 0.0,
             depth: 0.0,
-            texture_index: 0,
+            texture_index: TextureId::WHITE_PIXEL,
         }
     }
 }
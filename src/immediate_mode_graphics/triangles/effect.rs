@@ -0,0 +1,39 @@
+use crate::Vec4;
+
+/// Identifies a fragment shader effect which was added to a
+/// [`super::Triangles`] renderer.
+///
+/// Ids are handed out in the order effects are added, starting at 0, so
+/// renderers which add the same effects in the same order share ids.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct EffectId(u32);
+
+impl EffectId {
+    /// The id for the effect which was added at the given index.
+    pub fn from_index(index: u32) -> Self {
+        Self(index)
+    }
+
+    /// The order this effect was added.
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+/// An effect and the parameters it's composited with.
+///
+/// The meaning of the parameters is up to the effect's shader. For example,
+/// a dissolve transition might use the first component as its progress.
+/// Each layer has its own parameters, so the same effect can be pushed with
+/// different parameters in one frame.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Effect {
+    pub id: EffectId,
+    pub params: Vec4,
+}
+
+impl Effect {
+    pub fn new(id: EffectId, params: Vec4) -> Self {
+        Self { id, params }
+    }
+}
@@ -0,0 +1,446 @@
+use ::{ash::vk, std::sync::Arc};
+
+use crate::{
+    math::DepthMode,
+    multisample_renderpass::MultisampleRenderpass,
+    vulkan::{
+        errors::{VulkanDebugError, VulkanError},
+        Buffer, CommandBuffer, DescriptorPool, DescriptorSet,
+        DescriptorSetLayout, Framebuffer, Image, ImageView, MemoryAllocator,
+        RenderDevice, RenderPass, Sampler, VulkanDebug,
+    },
+    Vec4,
+};
+
+/// The descriptor set index for an effect layer's offscreen target and
+/// parameters. Sets 0 and 1 are the same as the renderer's pipeline.
+pub(super) const EFFECT_SET: u32 = 2;
+
+/// The resources shared by every effect layer's offscreen target.
+///
+/// Targets borrow the multisampled color attachment from the on-screen render
+/// pass and resolve into their own image. Their depth attachment matches the
+/// on-screen one, so the render pass they're drawn with is compatible with
+/// the renderer's pipelines.
+pub(super) struct EffectTargets {
+    /// A render pass which clears the multisampled attachments and resolves
+    /// them into the target's image, ready to be sampled.
+    render_pass: Arc<RenderPass>,
+
+    /// The on-screen render pass's multisampled color attachment.
+    msaa_render_target: Arc<ImageView>,
+
+    /// A depth attachment like the on-screen render pass's, shared by every
+    /// target. Each layer clears it when its pass begins.
+    depth_stencil_target: ImageView,
+
+    /// Decides the value the depth attachment is cleared to.
+    depth_mode: DepthMode,
+
+    /// The layout for each target's descriptor set.
+    layout: Arc<DescriptorSetLayout>,
+
+    /// Effect shaders sample their target with this sampler.
+    sampler: Sampler,
+
+    /// The device allocator.
+    vk_alloc: Arc<dyn MemoryAllocator>,
+
+    /// The vulkan render device.
+    vk_dev: Arc<RenderDevice>,
+}
+
+/// An offscreen image which one effect layer is drawn into, along with the
+/// descriptor set its effect shader reads it through.
+pub(super) struct EffectTarget {
+    /// The descriptor pool owns gpu resources used by the descriptor set.
+    _descriptor_pool: DescriptorPool,
+
+    /// The target's image at binding 0 and the effect's parameters at
+    /// binding 1.
+    descriptor_set: DescriptorSet,
+
+    /// The effect's parameters.
+    params: Buffer,
+
+    /// A framebuffer which resolves into the target's image.
+    framebuffer: Framebuffer,
+
+    /// The single-sampled image the layer is resolved into.
+    image: ImageView,
+}
+
+impl EffectTargets {
+    /// Create the shared resources for targets which match the render
+    /// pass's attachments.
+    pub(super) fn new(
+        msaa_renderpass: &MultisampleRenderpass,
+        depth_mode: DepthMode,
+        layout: Arc<DescriptorSetLayout>,
+        vk_alloc: Arc<dyn MemoryAllocator>,
+        vk_dev: Arc<RenderDevice>,
+    ) -> Result<Self, VulkanError> {
+        let depth_stencil_target = create_image_view(
+            &msaa_renderpass.depth_stencil_target.image.create_info,
+            vk::ImageAspectFlags::DEPTH | vk::ImageAspectFlags::STENCIL,
+            vk_alloc.clone(),
+            vk_dev.clone(),
+        )?;
+        depth_stencil_target
+            .image
+            .set_debug_name("Effect Target Depth Stencil")?;
+        depth_stencil_target
+            .set_debug_name("Effect Target Depth Stencil - View")?;
+        Ok(Self {
+            render_pass: create_render_pass(msaa_renderpass, vk_dev.clone())?,
+            msaa_render_target: msaa_renderpass.msaa_render_target.clone(),
+            depth_stencil_target,
+            depth_mode,
+            layout,
+            sampler: Sampler::linear(vk_dev.clone())?,
+            vk_alloc,
+            vk_dev,
+        })
+    }
+
+    /// Create the layout for a target's descriptor set: the target's image
+    /// as a combined image sampler at binding 0 and the effect's parameters
+    /// in a uniform buffer at binding 1.
+    pub(super) fn create_layout(
+        vk_dev: Arc<RenderDevice>,
+    ) -> Result<Arc<DescriptorSetLayout>, VulkanError> {
+        Ok(Arc::new(DescriptorSetLayout::new(
+            vk_dev,
+            &[
+                vk::DescriptorSetLayoutBinding {
+                    binding: 0,
+                    descriptor_type: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                    descriptor_count: 1,
+                    stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                    ..Default::default()
+                },
+                vk::DescriptorSetLayoutBinding {
+                    binding: 1,
+                    descriptor_type: vk::DescriptorType::UNIFORM_BUFFER,
+                    descriptor_count: 1,
+                    stage_flags: vk::ShaderStageFlags::FRAGMENT,
+                    ..Default::default()
+                },
+            ],
+        )?))
+    }
+
+    /// Allocate a target which covers the whole render target.
+    pub(super) fn create_target(
+        &self,
+        debug_name: impl Into<String>,
+    ) -> Result<EffectTarget, VulkanError> {
+        let msaa_info = &self.msaa_render_target.image.create_info;
+        let create_info = vk::ImageCreateInfo {
+            flags: vk::ImageCreateFlags::empty(),
+            image_type: vk::ImageType::TYPE_2D,
+            extent: msaa_info.extent,
+            mip_levels: 1,
+            array_layers: 1,
+            format: msaa_info.format,
+            samples: vk::SampleCountFlags::TYPE_1,
+            tiling: vk::ImageTiling::OPTIMAL,
+            initial_layout: vk::ImageLayout::UNDEFINED,
+            usage: vk::ImageUsageFlags::COLOR_ATTACHMENT
+                | vk::ImageUsageFlags::SAMPLED,
+            sharing_mode: vk::SharingMode::EXCLUSIVE,
+            ..Default::default()
+        };
+        let image = create_image_view(
+            &create_info,
+            vk::ImageAspectFlags::COLOR,
+            self.vk_alloc.clone(),
+            self.vk_dev.clone(),
+        )?;
+        let framebuffer = Framebuffer::with_attachments(
+            self.vk_dev.clone(),
+            &self.render_pass,
+            &[
+                self.msaa_render_target.raw,
+                self.depth_stencil_target.raw,
+                image.raw,
+            ],
+            vk::Extent2D {
+                width: msaa_info.extent.width,
+                height: msaa_info.extent.height,
+            },
+        )?;
+
+        let mut params = Buffer::new(
+            self.vk_dev.clone(),
+            self.vk_alloc.clone(),
+            vk::BufferUsageFlags::UNIFORM_BUFFER,
+            vk::MemoryPropertyFlags::HOST_VISIBLE
+                | vk::MemoryPropertyFlags::HOST_COHERENT,
+            std::mem::size_of::<[f32; 4]>() as u64,
+        )?;
+        params.map()?;
+
+        let descriptor_pool = DescriptorPool::new(
+            self.vk_dev.clone(),
+            1,
+            &[
+                vk::DescriptorPoolSize {
+                    ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                    descriptor_count: 1,
+                },
+                vk::DescriptorPoolSize {
+                    ty: vk::DescriptorType::UNIFORM_BUFFER,
+                    descriptor_count: 1,
+                },
+            ],
+        )?;
+        let descriptor_set =
+            descriptor_pool.allocate(&self.layout, 1)?.pop().unwrap();
+        unsafe {
+            descriptor_set.bind_combined_image_sampler(
+                0,
+                0,
+                &image,
+                &self.sampler,
+            );
+            descriptor_set.bind_buffer(
+                1,
+                &params.raw,
+                vk::DescriptorType::UNIFORM_BUFFER,
+            );
+        }
+
+        let target = EffectTarget {
+            _descriptor_pool: descriptor_pool,
+            descriptor_set,
+            params,
+            framebuffer,
+            image,
+        };
+        target.set_debug_name(debug_name)?;
+        Ok(target)
+    }
+
+    /// Begin a render pass which draws into the target. Every pixel starts
+    /// out transparent, at the far depth for the depth mode.
+    ///
+    /// # UNSAFE BECAUSE
+    ///
+    /// - No other render pass can be active in the command buffer.
+    /// - The target must not be in use by any other command buffer.
+    pub(super) unsafe fn begin_render_pass(
+        &self,
+        cmd: &CommandBuffer,
+        target: &EffectTarget,
+    ) {
+        let clear_values = [
+            vk::ClearValue {
+                color: vk::ClearColorValue {
+                    float32: [0.0, 0.0, 0.0, 0.0],
+                },
+            },
+            vk::ClearValue {
+                depth_stencil: vk::ClearDepthStencilValue {
+                    depth: self.depth_mode.clear_depth(),
+                    stencil: 0,
+                },
+            },
+        ];
+        let render_pass_begin_info = vk::RenderPassBeginInfo {
+            render_pass: self.render_pass.raw,
+            framebuffer: target.framebuffer.raw,
+            render_area: vk::Rect2D {
+                offset: vk::Offset2D { x: 0, y: 0 },
+                extent: target.framebuffer.extent,
+            },
+            clear_value_count: clear_values.len() as u32,
+            p_clear_values: clear_values.as_ptr(),
+            ..Default::default()
+        };
+        self.vk_dev.logical_device.cmd_begin_render_pass(
+            cmd.raw,
+            &render_pass_begin_info,
+            vk::SubpassContents::INLINE,
+        );
+    }
+
+    /// End the render pass started by [`Self::begin_render_pass`].
+    pub(super) unsafe fn end_render_pass(&self, cmd: &CommandBuffer) {
+        self.vk_dev.logical_device.cmd_end_render_pass(cmd.raw);
+    }
+}
+
+impl EffectTarget {
+    /// Write the parameters the target's effect is composited with.
+    pub(super) fn write_params(&self, params: Vec4) -> Result<(), VulkanError> {
+        self.params.data_mut::<[f32; 4]>()?[0] = params.into();
+        Ok(())
+    }
+
+    /// The descriptor set effect shaders read the target through.
+    pub(super) fn descriptor_set(&self) -> &DescriptorSet {
+        &self.descriptor_set
+    }
+}
+
+impl VulkanDebug for EffectTarget {
+    fn set_debug_name(
+        &self,
+        debug_name: impl Into<String>,
+    ) -> Result<(), VulkanDebugError> {
+        let name = debug_name.into();
+        self._descriptor_pool
+            .set_debug_name(format!("{} - Descriptor Pool", name))?;
+        self.descriptor_set
+            .set_debug_name(format!("{} - Descriptor Set", name))?;
+        self.params.set_debug_name(format!("{} - Params", name))?;
+        self.framebuffer
+            .set_debug_name(format!("{} - Framebuffer", name))?;
+        self.image
+            .image
+            .set_debug_name(format!("{} - Image", name))?;
+        self.image
+            .set_debug_name(format!("{} - Image View", name))?;
+        Ok(())
+    }
+}
+
+/// Allocate an image and a view of the whole image.
+// Image keeps its create info, whose raw pointers make it neither Send nor
+// Sync, but ImageView needs it in an Arc. Targets never leave the render
+// thread.
+#[allow(clippy::arc_with_non_send_sync)]
+fn create_image_view(
+    create_info: &vk::ImageCreateInfo,
+    aspect_mask: vk::ImageAspectFlags,
+    vk_alloc: Arc<dyn MemoryAllocator>,
+    vk_dev: Arc<RenderDevice>,
+) -> Result<ImageView, VulkanError> {
+    let image = Image::new(
+        vk_dev,
+        vk_alloc,
+        create_info,
+        vk::MemoryPropertyFlags::DEVICE_LOCAL,
+    )?;
+    Ok(ImageView::new_2d(
+        Arc::new(image),
+        create_info.format,
+        aspect_mask,
+    )?)
+}
+
+/// Create a render pass with the same attachments as the on-screen render
+/// pass, which makes the two compatible, but which leaves the resolved image
+/// ready to be sampled.
+fn create_render_pass(
+    msaa_renderpass: &MultisampleRenderpass,
+    vk_dev: Arc<RenderDevice>,
+) -> Result<Arc<RenderPass>, VulkanError> {
+    let msaa_info = &msaa_renderpass.msaa_render_target.image.create_info;
+    let depth_info = &msaa_renderpass.depth_stencil_target.image.create_info;
+    let attachments = [
+        vk::AttachmentDescription {
+            flags: vk::AttachmentDescriptionFlags::empty(),
+            format: msaa_info.format,
+            samples: msaa_info.samples,
+            load_op: vk::AttachmentLoadOp::CLEAR,
+            store_op: vk::AttachmentStoreOp::DONT_CARE,
+            stencil_load_op: vk::AttachmentLoadOp::DONT_CARE,
+            stencil_store_op: vk::AttachmentStoreOp::DONT_CARE,
+            initial_layout: vk::ImageLayout::UNDEFINED,
+            final_layout: vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+        },
+        vk::AttachmentDescription {
+            flags: vk::AttachmentDescriptionFlags::empty(),
+            format: depth_info.format,
+            samples: depth_info.samples,
+            load_op: vk::AttachmentLoadOp::CLEAR,
+            store_op: vk::AttachmentStoreOp::DONT_CARE,
+            stencil_load_op: vk::AttachmentLoadOp::DONT_CARE,
+            stencil_store_op: vk::AttachmentStoreOp::DONT_CARE,
+            initial_layout: vk::ImageLayout::UNDEFINED,
+            final_layout: vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+        },
+        vk::AttachmentDescription {
+            flags: vk::AttachmentDescriptionFlags::empty(),
+            format: msaa_info.format,
+            samples: vk::SampleCountFlags::TYPE_1,
+            load_op: vk::AttachmentLoadOp::DONT_CARE,
+            store_op: vk::AttachmentStoreOp::STORE,
+            stencil_load_op: vk::AttachmentLoadOp::DONT_CARE,
+            stencil_store_op: vk::AttachmentStoreOp::DONT_CARE,
+            initial_layout: vk::ImageLayout::UNDEFINED,
+            final_layout: vk::ImageLayout::SHADER_READ_ONLY_OPTIMAL,
+        },
+    ];
+    let color_attachment_reference = vk::AttachmentReference {
+        attachment: 0,
+        layout: vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+    };
+    let depth_stencil_attachment_reference = vk::AttachmentReference {
+        attachment: 1,
+        layout: vk::ImageLayout::DEPTH_STENCIL_ATTACHMENT_OPTIMAL,
+    };
+    let resolve_attachment_reference = vk::AttachmentReference {
+        attachment: 2,
+        layout: vk::ImageLayout::COLOR_ATTACHMENT_OPTIMAL,
+    };
+
+    let attachment_writes = vk::AccessFlags::COLOR_ATTACHMENT_WRITE
+        | vk::AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE;
+    let dependencies = [
+        vk::SubpassDependency {
+            src_subpass: vk::SUBPASS_EXTERNAL,
+            dst_subpass: 0,
+            src_stage_mask: vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+            dst_stage_mask: vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+            src_access_mask: vk::AccessFlags::empty(),
+            dst_access_mask: vk::AccessFlags::COLOR_ATTACHMENT_READ
+                | attachment_writes,
+            dependency_flags: vk::DependencyFlags::empty(),
+        },
+        // the resolved image is sampled by whichever pass composites it, and
+        // the multisampled attachments are cleared again by the next pass
+        vk::SubpassDependency {
+            src_subpass: 0,
+            dst_subpass: vk::SUBPASS_EXTERNAL,
+            src_stage_mask: vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::LATE_FRAGMENT_TESTS,
+            dst_stage_mask: vk::PipelineStageFlags::FRAGMENT_SHADER
+                | vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT
+                | vk::PipelineStageFlags::EARLY_FRAGMENT_TESTS,
+            src_access_mask: attachment_writes,
+            dst_access_mask: vk::AccessFlags::SHADER_READ | attachment_writes,
+            dependency_flags: vk::DependencyFlags::empty(),
+        },
+    ];
+    let subpass = vk::SubpassDescription {
+        flags: vk::SubpassDescriptionFlags::empty(),
+        pipeline_bind_point: vk::PipelineBindPoint::GRAPHICS,
+        input_attachment_count: 0,
+        p_input_attachments: std::ptr::null(),
+        color_attachment_count: 1,
+        p_color_attachments: &color_attachment_reference,
+        p_depth_stencil_attachment: &depth_stencil_attachment_reference,
+        preserve_attachment_count: 0,
+        p_preserve_attachments: std::ptr::null(),
+        p_resolve_attachments: &resolve_attachment_reference,
+    };
+    let render_pass_info = vk::RenderPassCreateInfo {
+        flags: vk::RenderPassCreateFlags::empty(),
+        attachment_count: attachments.len() as u32,
+        p_attachments: attachments.as_ptr(),
+        subpass_count: 1,
+        p_subpasses: &subpass,
+        dependency_count: dependencies.len() as u32,
+        p_dependencies: dependencies.as_ptr(),
+        ..Default::default()
+    };
+
+    let render_pass = Arc::new(RenderPass::new(vk_dev, &render_pass_info)?);
+    render_pass.set_debug_name("Effect Target RenderPass")?;
+    Ok(render_pass)
+}
@@ -1,7 +1,18 @@
 use ::thiserror::Error;
 
+use crate::asset_loader::TextureId;
+
 #[derive(Debug, Error)]
 pub enum ImmediateModeGraphicsError {
     #[error("The Per-Frame resources for swapchain image {} were not available! Did you forget to end the previous frame?", .0)]
     FrameResourcesUnavailable(usize),
+
+    #[error("Effect {} was never added to the renderer which owns this frame", .0)]
+    UnknownEffect(u32),
+
+    #[error("The frame has effect layers which were never drawn, call Triangles::write_effect_passes before the render pass begins")]
+    EffectPassesMissing,
+
+    #[error("A vertex uses texture {}, but the texture table only has {} textures", .0, .1)]
+    UnknownTexture(TextureId, u32),
 }
@@ -1,15 +1,36 @@
-use ::{anyhow::Result, ash::vk, std::sync::Arc};
+use ::{
+    anyhow::{Context, Result},
+    ash::vk,
+    std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use super::{
+    effect_target::{EffectTarget, EffectTargets, EFFECT_SET},
+    recording::RecordedCommand,
+    Effect, FrameRecording, FrameStats, ImmediateModeGraphicsError,
+};
 
 use crate::{
-    asset_loader::CombinedImageSampler,
-    immediate_mode_graphics::{Vertex, VertexStream},
+    asset_loader::TextureId,
+    immediate_mode_graphics::{Vertex, VertexFormat, VertexStream},
+    math::{CoordinateSystem, DesignViewport},
+    ui::primitives::{Dimensions, Rect},
     vulkan::{
-        errors::VulkanError, Buffer, CommandBuffer, DescriptorPool,
-        DescriptorSet, DescriptorSetLayout, GpuVec, MemoryAllocator,
-        PipelineLayout, RenderDevice,
+        errors::{VulkanDebugError, VulkanError},
+        Buffer, CommandBuffer, DescriptorPool, DescriptorSet,
+        DescriptorSetLayout, GpuVec, MemoryAllocator, Pipeline, PipelineLayout,
+        RenderDevice, VulkanDebug,
     },
+    Mat4,
 };
 
+/// The descriptor set index for each frame's vertices and uniform data. Set 0
+/// is the texture table.
+pub(super) const FRAME_SET: u32 = 1;
+
 /// All data sent to the shaders in a Uniform Buffer.
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -18,12 +39,55 @@ pub struct UniformBufferData {
     pub view_projection: [[f32; 4]; 4],
 }
 
+/// A single draw call in one of the frame's passes.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum DrawCommand {
+    /// Draw a range of the frame's indices with the renderer's pipeline.
+    Geometry {
+        /// The first index in the frame's index buffer used by this command.
+        first_index: u32,
+
+        /// The number of indices drawn.
+        index_count: u32,
+
+        /// The region geometry is clipped to. None means the entire render
+        /// target.
+        clip: Option<Rect>,
+    },
+
+    /// Composite an effect layer's offscreen target through the layer's
+    /// effect.
+    Composite {
+        /// The index of the layer in the frame's effect layers.
+        layer: usize,
+
+        /// The region the layer is clipped to. None means the entire
+        /// viewport.
+        clip: Option<Rect>,
+    },
+}
+
+/// Geometry which is drawn into an offscreen target, then composited into
+/// whatever was drawing when the effect was pushed.
+#[derive(Debug, Clone)]
+struct EffectLayer {
+    /// The effect the layer is composited with.
+    effect: Effect,
+
+    /// Every draw call into the layer's target.
+    commands: Vec<DrawCommand>,
+}
+
 /// All resources required to render a single frame's vertices.
-pub struct Frame {
+///
+/// Frames use the built-in [`Vertex`] type unless the [`super::Triangles`]
+/// renderer which owns them was created with a custom vertex format.
+pub struct Frame<V: VertexFormat = Vertex> {
     /// The descriptor pool owns gpu resources used by the descriptor set.
     _descriptor_pool: DescriptorPool,
 
-    /// The descriptor set enables texture indexing.
+    /// The descriptor set with the frame's vertices and uniform data.
+    /// Textures come from the texture table instead.
     descriptor_set: DescriptorSet,
 
     /// This frame's uniform data.
@@ -31,7 +95,7 @@ pub struct Frame {
 
     /// All of the vertices to be rendered on the current frame.
     /// This is cleared each time the frame is acquired.
-    vertex_data: GpuVec<Vertex>,
+    vertex_data: GpuVec<V>,
 
     /// Flag is set to 'true' if the vertex buffer needs to be rebound to the
     /// descriptor set. This occurs when the GpuVec grows and needs to be
@@ -41,16 +105,67 @@ pub struct Frame {
     /// The set of all vertex indices.
     index_data: GpuVec<u32>,
 
+    /// A stack of transforms applied to vertices as they're pushed into the
+    /// frame. Only the top of the stack is used, it already includes every
+    /// transform below it.
+    transforms: Vec<Mat4>,
+
+    /// A stack of clip rects. Like transforms, only the top of the stack is
+    /// used and it is already intersected with every clip rect below it.
+    clip_rects: Vec<Rect>,
+
+    /// Every effect pushed this frame, in the order they were pushed.
+    effect_layers: Vec<EffectLayer>,
+
+    /// A stack of indices into the effect layers. Geometry is drawn into the
+    /// layer on top, or into the render target when the stack is empty.
+    open_layers: Vec<usize>,
+
+    /// An offscreen target for each effect layer. Targets are created the
+    /// first time a frame needs them and reused after that.
+    effect_targets: Vec<EffectTarget>,
+
+    /// Set once the effect layers have been drawn into their targets. The
+    /// frame can't be completed before then.
+    effect_passes_written: bool,
+
+    /// The number of effects added to the renderer which owns this frame.
+    effect_count: u32,
+
+    /// The number of textures in the texture table, shared with the table so
+    /// textures added mid-frame count. Debug builds reject vertices with any
+    /// other texture. None until the frame is acquired.
+    texture_count: Option<Arc<AtomicU32>>,
+
+    /// The draw calls made directly into the render target. A new command
+    /// is started each time the clip rect changes or an effect is pushed.
+    commands: Vec<DrawCommand>,
+
+    /// A copy of the geometry pushed since [`Self::begin_recording`], if the
+    /// frame is recording.
+    recording: Option<FrameRecording<V>>,
+
+    /// A stack of labels naming what's being recorded, like a layer, a
+    /// widget subtree, or a plugin. Errors while recording carry the whole
+    /// stack so they can be traced back to whatever was drawing.
+    debug_labels: Vec<String>,
+
+    /// The coordinate system clip rects are given in.
+    coordinate_system: CoordinateSystem,
+
+    /// Where the design resolution lands in the render target, if the frame
+    /// uses one.
+    design_viewport: Option<DesignViewport>,
+
     /// The Vulkan render device.
     vk_dev: Arc<RenderDevice>,
 }
 
-impl Frame {
+impl<V: VertexFormat> Frame<V> {
     /// Allocate resources and buffers for a single frame.
     pub fn new(
         vk_dev: Arc<RenderDevice>,
         vk_alloc: Arc<dyn MemoryAllocator>,
-        textures: &[CombinedImageSampler],
         descriptor_layout: &DescriptorSetLayout,
     ) -> Result<Self, VulkanError> {
         let descriptor_pool = DescriptorPool::new(
@@ -65,18 +180,10 @@ impl Frame {
                     ty: vk::DescriptorType::UNIFORM_BUFFER,
                     descriptor_count: 1,
                 },
-                vk::DescriptorPoolSize {
-                    ty: vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
-                    descriptor_count: textures.len() as u32,
-                },
             ],
         )?;
         let descriptor_set = descriptor_pool
-            .allocate_with_variable_counts(
-                descriptor_layout,
-                1,
-                textures.len() as u32,
-            )?
+            .allocate(descriptor_layout, 1)?
             .pop()
             .unwrap();
 
@@ -108,20 +215,25 @@ impl Frame {
                 &uniform_data.raw,
                 vk::DescriptorType::UNIFORM_BUFFER,
             );
-            for (texture_index, texture) in textures.iter().enumerate() {
-                descriptor_set.bind_combined_image_sampler(
-                    2,
-                    texture_index as u32,
-                    &texture.image_view,
-                    &texture.sampler,
-                );
-            }
         }
 
         Ok(Self {
             vertex_data,
             vertex_data_needs_rebound: true,
             index_data,
+            transforms: vec![],
+            clip_rects: vec![],
+            effect_layers: vec![],
+            open_layers: vec![],
+            effect_targets: vec![],
+            effect_passes_written: false,
+            effect_count: 0,
+            texture_count: None,
+            commands: vec![],
+            recording: None,
+            debug_labels: vec![],
+            coordinate_system: CoordinateSystem::default(),
+            design_viewport: None,
             uniform_data,
             _descriptor_pool: descriptor_pool,
             descriptor_set,
@@ -134,47 +246,409 @@ impl Frame {
         &mut self,
         view_projection: nalgebra::Matrix4<f32>,
     ) -> Result<()> {
-        self.uniform_data.data_mut::<UniformBufferData>()?[0] =
-            UniformBufferData {
-                view_projection: view_projection.into(),
-            };
+        let result = self
+            .uniform_data
+            .data_mut::<UniformBufferData>()
+            .map(|data| data[0].view_projection = view_projection.into());
+        with_label_context(&self.debug_labels, result.map_err(Into::into))
+    }
+
+    /// Set the coordinate system used for clip rects. Use the same
+    /// coordinate system's [`CoordinateSystem::pixel_projection`] as the
+    /// view projection so geometry and clip rects agree.
+    ///
+    /// Frames use the coordinate system of the [`super::Triangles`] renderer
+    /// each time they're acquired.
+    pub fn set_coordinate_system(
+        &mut self,
+        coordinate_system: CoordinateSystem,
+    ) {
+        self.coordinate_system = coordinate_system;
+    }
+
+    /// The coordinate system used for clip rects.
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.coordinate_system
+    }
+
+    /// Render into only part of the render target, with clip rects given in
+    /// design coordinates. Use the viewport's
+    /// [`DesignViewport::projection`] as the view projection so geometry and
+    /// clip rects agree.
+    ///
+    /// Frames use the design resolution of the [`super::Triangles`]
+    /// renderer each time they're acquired.
+    pub fn set_design_viewport(
+        &mut self,
+        design_viewport: Option<DesignViewport>,
+    ) {
+        self.design_viewport = design_viewport;
+    }
+
+    /// Where the design resolution lands in the render target. None when the
+    /// frame renders into the whole target in pixel coordinates.
+    pub fn design_viewport(&self) -> Option<DesignViewport> {
+        self.design_viewport
+    }
+
+    /// Transform all vertices pushed into the frame until the matching call
+    /// to [`Self::pop_transform`]. Transforms nest: the new transform is
+    /// applied before any transform which is already on the stack.
+    pub fn push_transform(&mut self, transform: Mat4) {
+        let combined = match self.transforms.last() {
+            Some(current) => current * transform,
+            None => transform,
+        };
+        self.transforms.push(combined);
+    }
+
+    /// Remove the most recently pushed transform.
+    pub fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    /// Clip all geometry pushed into the frame to the given rect until the
+    /// matching call to [`Self::pop_clip_rect`]. Clip rects nest: the new
+    /// rect is intersected with any rect which is already on the stack.
+    ///
+    /// The rect is in pixels, in the frame's coordinate system, and is not
+    /// affected by the transform stack. Frames with a design viewport take
+    /// the rect in design coordinates instead.
+    pub fn push_clip_rect(&mut self, rect: Rect) {
+        let clip = match self.clip_rects.last() {
+            Some(current) => current.intersection(rect).unwrap_or(Rect {
+                top_left: rect.top_left,
+                bottom_right: rect.top_left,
+            }),
+            None => rect,
+        };
+        self.clip_rects.push(clip);
+        self.clip_changed();
+    }
+
+    /// Remove the most recently pushed clip rect.
+    pub fn pop_clip_rect(&mut self) {
+        self.clip_rects.pop();
+        self.clip_changed();
+    }
+
+    /// Draw all geometry pushed into the frame into an offscreen target
+    /// until the matching call to [`Self::pop_effect`]. The target is then
+    /// composited through the effect's shader, clipped to the current clip
+    /// rect, in the same order as the geometry around it.
+    ///
+    /// Effects nest: a nested effect's layer is composited into the layer
+    /// around it. Each push gets its own layer and parameters.
+    pub fn push_effect(&mut self, effect: Effect) -> Result<()> {
+        let index = effect.id.index();
+        if index >= self.effect_count {
+            return with_label_context(
+                &self.debug_labels,
+                Err(ImmediateModeGraphicsError::UnknownEffect(index).into()),
+            );
+        }
+        let layer = self.effect_layers.len();
+        let clip = self.clip_rects.last().copied();
+        self.current_commands()
+            .push(DrawCommand::Composite { layer, clip });
+        self.effect_layers.push(EffectLayer {
+            effect,
+            commands: vec![],
+        });
+        self.open_layers.push(layer);
+        if let Some(recording) = &mut self.recording {
+            recording.push_effect(effect);
+        }
         Ok(())
     }
+
+    /// Stop drawing into the most recently pushed effect's layer.
+    pub fn pop_effect(&mut self) {
+        self.open_layers.pop();
+        if let Some(recording) = &mut self.recording {
+            recording.pop_effect();
+        }
+    }
+
+    /// Name what's recorded into the frame until the matching call to
+    /// [`Self::pop_debug_label`]. Labels nest, and any error while recording
+    /// is given the stack as context, e.g.
+    /// "while recording ui layer > settings panel".
+    pub fn push_debug_label(&mut self, label: impl Into<String>) {
+        self.debug_labels.push(label.into());
+    }
+
+    /// Remove the most recently pushed debug label.
+    pub fn pop_debug_label(&mut self) {
+        self.debug_labels.pop();
+    }
+
+    /// The debug labels currently on the stack, outermost first.
+    pub fn debug_labels(&self) -> &[String] {
+        &self.debug_labels
+    }
+
+    /// Keep a copy of all geometry pushed into the frame until the call to
+    /// [`Self::end_recording`]. The recording can be replayed into a later
+    /// frame to draw the same geometry without building it again.
+    ///
+    /// Recorded vertices already have the transform stack applied, clip rects
+    /// and the effects pushed while recording are recorded as they change.
+    pub fn begin_recording(&mut self) {
+        self.recording =
+            Some(FrameRecording::new(self.clip_rects.last().copied()));
+    }
+
+    /// Stop recording and return everything which was pushed since the call
+    /// to [`Self::begin_recording`]. Returns None if the frame wasn't
+    /// recording.
+    pub fn end_recording(&mut self) -> Option<FrameRecording<V>> {
+        self.recording.take()
+    }
+
+    /// Push all of a recording's geometry into the frame, clipped the same
+    /// way it was when it was recorded, and with the effects which were
+    /// pushed while recording. The current transform and clip rect are
+    /// ignored, and are left unchanged afterwards. Geometry outside of the
+    /// recording's own effects is drawn into the current effect layer.
+    pub fn replay(&mut self, recording: &FrameRecording<V>) -> Result<()> {
+        let transforms = std::mem::take(&mut self.transforms);
+        let clip_rects = std::mem::take(&mut self.clip_rects);
+        let open_layers = self.open_layers.len();
+        let mut result = Ok(());
+        for command in recording.commands() {
+            result = match command {
+                RecordedCommand::Geometry(segment) => {
+                    self.clip_rects = segment.clip.into_iter().collect();
+                    self.clip_changed();
+                    self.push_vertices(&segment.vertices, &segment.indices)
+                }
+                RecordedCommand::PushEffect { effect, clip } => {
+                    self.clip_rects = clip.iter().copied().collect();
+                    self.clip_changed();
+                    self.push_effect(*effect)
+                }
+                RecordedCommand::PopEffect => {
+                    self.pop_effect();
+                    Ok(())
+                }
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+
+        // recordings which end inside one of their effects leave it pushed
+        while self.open_layers.len() > open_layers {
+            self.pop_effect();
+        }
+        self.transforms = transforms;
+        self.clip_rects = clip_rects;
+        self.clip_changed();
+        result
+    }
+
+    /// Count the geometry pushed into the frame so far.
+    pub fn stats(&self) -> FrameStats {
+        FrameStats {
+            vertices: self.vertex_data.len(),
+            triangles: self.index_data.len() / 3,
+            draw_calls: self.commands.len()
+                + self
+                    .effect_layers
+                    .iter()
+                    .map(|layer| layer.commands.len())
+                    .sum::<usize>(),
+        }
+    }
+
+    /// Set the number of effects added to the renderer which owns this
+    /// frame. Pushing any other effect is an error.
+    pub(super) fn set_effect_count(&mut self, effect_count: u32) {
+        self.effect_count = effect_count;
+    }
+
+    /// Share the texture table's registered count. In debug builds, pushing
+    /// a vertex with any other texture is an error.
+    pub(super) fn set_texture_count(&mut self, texture_count: Arc<AtomicU32>) {
+        self.texture_count = Some(texture_count);
+    }
+
+    /// The commands for whatever geometry is currently drawn into: the open
+    /// effect layer, or the render target.
+    fn current_commands(&mut self) -> &mut Vec<DrawCommand> {
+        match self.open_layers.last() {
+            Some(&layer) => &mut self.effect_layers[layer].commands,
+            None => &mut self.commands,
+        }
+    }
+
+    /// Let the recording know the clip rect changed.
+    fn clip_changed(&mut self) {
+        if let Some(recording) = &mut self.recording {
+            recording.set_clip(self.clip_rects.last().copied());
+        }
+    }
 }
 
-impl VertexStream for Frame {
+impl<V: VertexFormat> VertexStream<V> for Frame<V> {
     /// Push vertices into the frame. Indices index into the given vertex slice.
-    fn push_vertices(
+    fn push_vertices(&mut self, vertices: &[V], indices: &[u32]) -> Result<()> {
+        let result = self.push_transformed_vertices(vertices, indices);
+        with_label_context(&self.debug_labels, result)
+    }
+}
+
+impl<V: VertexFormat> Frame<V> {
+    /// Push vertices with the current transform applied.
+    fn push_transformed_vertices(
         &mut self,
-        vertices: &[Vertex],
+        vertices: &[V],
         indices: &[u32],
     ) -> Result<()> {
+        if let (true, Some(texture_count)) =
+            (cfg!(debug_assertions), &self.texture_count)
+        {
+            let texture_count = texture_count.load(Ordering::Relaxed);
+            if let Some(texture) = unknown_texture(vertices, texture_count) {
+                return Err(ImmediateModeGraphicsError::UnknownTexture(
+                    texture,
+                    texture_count,
+                )
+                .into());
+            }
+        }
         let base_index = self.vertex_data.len() as u32;
+        let first_index = self.index_data.len() as u32;
+        let transform = self.transforms.last().copied();
         for vertex in vertices {
-            self.push_vertex(*vertex)?;
+            self.push_vertex(transformed(transform, vertex))?;
         }
         for index in indices {
             self.index_data.push_back(base_index + index)?;
         }
+        let clip = self.clip_rects.last().copied();
+        push_geometry(
+            self.current_commands(),
+            clip,
+            first_index,
+            indices.len() as u32,
+        );
+        if let Some(recording) = &mut self.recording {
+            recording.push(
+                vertices.iter().map(|vertex| transformed(transform, vertex)),
+                indices,
+            );
+        }
         Ok(())
     }
 }
 
-impl Frame {
+impl<V: VertexFormat> Frame<V> {
+    /// Draw each effect layer into its offscreen target, creating targets
+    /// the first time they're needed.
+    ///
+    /// # UNSAFE BECAUSE
+    ///
+    /// - No render pass can be active in the command buffer.
+    /// - The texture table must be bound, and the layouts for the pipelines
+    ///   must match the targets.
+    /// - This command is not internally synchronized, it is up to the caller
+    ///   to ensure that the frame's resources are not currently in use by the
+    ///   gpu.
+    pub(super) unsafe fn write_effect_passes(
+        &mut self,
+        cmd: &CommandBuffer,
+        effect_targets: &EffectTargets,
+        pipeline: &Pipeline,
+        effect_pipelines: &[Pipeline],
+        extent: vk::Extent2D,
+        debug_name: &str,
+    ) -> Result<()> {
+        while self.effect_targets.len() < self.effect_layers.len() {
+            let target = effect_targets.create_target(format!(
+                "{} - Effect Target {}",
+                debug_name,
+                self.effect_targets.len()
+            ))?;
+            self.effect_targets.push(target);
+        }
+        self.effect_passes_written = true;
+        if self.effect_layers.is_empty() {
+            return Ok(());
+        }
+
+        self.bind_frame_resources(cmd, pipeline);
+
+        // layers are composited into the layers around them, and those are
+        // always pushed first, so the last layer never depends on a later one
+        for (layer, target) in
+            self.effect_layers.iter().zip(&self.effect_targets).rev()
+        {
+            target.write_params(layer.effect.params)?;
+            effect_targets.begin_render_pass(cmd, target);
+            self.vk_dev.logical_device.cmd_bind_pipeline(
+                cmd.raw,
+                vk::PipelineBindPoint::GRAPHICS,
+                pipeline.raw,
+            );
+            self.write_commands(
+                cmd,
+                &layer.commands,
+                pipeline,
+                effect_pipelines,
+                extent,
+            );
+            effect_targets.end_render_pass(cmd);
+        }
+        Ok(())
+    }
+
     /// Write this frame's draw commands into a given command buffer.
     ///
+    /// Returns an error without writing anything if the frame has effect
+    /// layers which weren't drawn with [`Self::write_effect_passes`].
+    ///
     /// # UNSAFE BECAUSE
     ///
-    /// - This command assumes that the required pipeline has already been
-    ///   bound.
+    /// - This command assumes that the renderer's own pipeline has already
+    ///   been bound. Effect pipelines share the layout for the first two
+    ///   sets, they're bound as needed and the renderer's pipeline is bound
+    ///   again afterwards.
     /// - This command is not internally synchronized, it is up to the caller
     ///   to ensure that the frame's resources are not currently in use by the
     ///   gpu.
+    /// - The extent must match the render target, it is used as the viewport
+    ///   and as the scissor for geometry which isn't clipped.
     pub(super) unsafe fn write_frame_commands(
         &mut self,
         cmd: &CommandBuffer,
-        pipeline_layout: &PipelineLayout,
+        pipeline: &Pipeline,
+        effect_pipelines: &[Pipeline],
+        extent: vk::Extent2D,
+    ) -> Result<(), ImmediateModeGraphicsError> {
+        if !self.effect_layers.is_empty() && !self.effect_passes_written {
+            return Err(ImmediateModeGraphicsError::EffectPassesMissing);
+        }
+        self.bind_frame_resources(cmd, pipeline);
+        self.write_commands(
+            cmd,
+            &self.commands,
+            pipeline,
+            effect_pipelines,
+            extent,
+        );
+        Ok(())
+    }
+
+    /// Bind the frame's descriptor set and index buffer, rebinding the
+    /// vertex buffer to the descriptor set first if it was reallocated.
+    unsafe fn bind_frame_resources(
+        &mut self,
+        cmd: &CommandBuffer,
+        pipeline: &Pipeline,
     ) {
+        let pipeline_layout: &PipelineLayout = &pipeline.pipeline_layout;
         if self.vertex_data_needs_rebound {
             self.rebind_vertex_data();
             self.vertex_data_needs_rebound = false;
@@ -184,7 +658,7 @@ impl Frame {
             cmd.raw,
             vk::PipelineBindPoint::GRAPHICS,
             pipeline_layout.raw,
-            0,
+            FRAME_SET,
             &[self.descriptor_set.raw],
             &[],
         );
@@ -194,24 +668,168 @@ impl Frame {
             0,
             vk::IndexType::UINT32,
         );
-        self.vk_dev.logical_device.cmd_draw_indexed(
+    }
+
+    /// Write the draw calls for one pass, either into the render target or
+    /// into an effect layer's target. The renderer's pipeline must be bound,
+    /// and it's bound again afterwards if a layer was composited.
+    unsafe fn write_commands(
+        &self,
+        cmd: &CommandBuffer,
+        commands: &[DrawCommand],
+        pipeline: &Pipeline,
+        effect_pipelines: &[Pipeline],
+        extent: vk::Extent2D,
+    ) {
+        let viewport = match self.design_viewport {
+            Some(design_viewport) => design_viewport.viewport,
+            None => {
+                Rect::new(0.0, 0.0, extent.height as f32, extent.width as f32)
+            }
+        };
+        self.set_viewport(cmd, viewport);
+
+        let mut compositing = false;
+        for command in commands {
+            match *command {
+                DrawCommand::Geometry {
+                    first_index,
+                    index_count,
+                    clip,
+                } => {
+                    let scissor = match clip {
+                        Some(clip) => clip_to_scissor(
+                            self.to_framebuffer(clip, extent),
+                            extent,
+                        ),
+                        None => vk::Rect2D {
+                            offset: vk::Offset2D { x: 0, y: 0 },
+                            extent,
+                        },
+                    };
+                    if scissor.extent.width == 0 || scissor.extent.height == 0 {
+                        continue;
+                    }
+                    if compositing {
+                        self.vk_dev.logical_device.cmd_bind_pipeline(
+                            cmd.raw,
+                            vk::PipelineBindPoint::GRAPHICS,
+                            pipeline.raw,
+                        );
+                        self.set_viewport(cmd, viewport);
+                        compositing = false;
+                    }
+                    self.vk_dev.logical_device.cmd_set_scissor(
+                        cmd.raw,
+                        0,
+                        &[scissor],
+                    );
+                    self.vk_dev.logical_device.cmd_draw_indexed(
+                        cmd.raw,
+                        index_count,
+                        1,
+                        first_index,
+                        0,
+                        0,
+                    );
+                }
+                DrawCommand::Composite { layer, clip } => {
+                    // unclipped layers still only cover the viewport, where
+                    // all of their geometry is
+                    let scissor = clip_to_scissor(
+                        match clip {
+                            Some(clip) => self.to_framebuffer(clip, extent),
+                            None => viewport,
+                        },
+                        extent,
+                    );
+                    if scissor.extent.width == 0 || scissor.extent.height == 0 {
+                        continue;
+                    }
+                    let id = self.effect_layers[layer].effect.id;
+                    let effect_pipeline =
+                        &effect_pipelines[id.index() as usize];
+                    self.vk_dev.logical_device.cmd_bind_pipeline(
+                        cmd.raw,
+                        vk::PipelineBindPoint::GRAPHICS,
+                        effect_pipeline.raw,
+                    );
+                    self.vk_dev.logical_device.cmd_bind_descriptor_sets(
+                        cmd.raw,
+                        vk::PipelineBindPoint::GRAPHICS,
+                        effect_pipeline.pipeline_layout.raw,
+                        EFFECT_SET,
+                        &[self.effect_targets[layer].descriptor_set().raw],
+                        &[],
+                    );
+
+                    // the fullscreen triangle covers the whole target, so
+                    // its uvs line up with the layer's target
+                    self.set_viewport(cmd, Dimensions::from(extent).as_rect());
+                    compositing = true;
+                    self.vk_dev.logical_device.cmd_set_scissor(
+                        cmd.raw,
+                        0,
+                        &[scissor],
+                    );
+                    self.vk_dev.logical_device.cmd_draw(cmd.raw, 3, 1, 0, 0);
+                }
+            }
+        }
+        if compositing {
+            self.vk_dev.logical_device.cmd_bind_pipeline(
+                cmd.raw,
+                vk::PipelineBindPoint::GRAPHICS,
+                pipeline.raw,
+            );
+        }
+    }
+
+    /// Set the viewport, given in framebuffer pixels.
+    unsafe fn set_viewport(&self, cmd: &CommandBuffer, viewport: Rect) {
+        self.vk_dev.logical_device.cmd_set_viewport(
             cmd.raw,
-            self.index_data.len() as u32,
-            1,
-            0,
-            0,
             0,
+            &[vk::Viewport {
+                x: viewport.left(),
+                y: viewport.top(),
+                width: viewport.width(),
+                height: viewport.height(),
+                min_depth: 0.0,
+                max_depth: 1.0,
+            }],
         );
     }
 
+    /// Convert a clip rect from the frame's coordinates into framebuffer
+    /// pixels.
+    fn to_framebuffer(&self, clip: Rect, extent: vk::Extent2D) -> Rect {
+        match self.design_viewport {
+            Some(design_viewport) => {
+                design_viewport.to_framebuffer(clip, self.coordinate_system)
+            }
+            None => self
+                .coordinate_system
+                .to_framebuffer(clip, extent.height as f32),
+        }
+    }
+
     pub(super) fn clear(&mut self) {
         self.vertex_data.clear();
         self.index_data.clear();
+        self.transforms.clear();
+        self.clip_rects.clear();
+        self.effect_layers.clear();
+        self.open_layers.clear();
+        self.effect_passes_written = false;
+        self.commands.clear();
+        self.recording = None;
+        self.debug_labels.clear();
     }
 
     /// Add a vertex to the vertex buffer.
     /// Automatically updates the needs rebound flag.
-    fn push_vertex(&mut self, vertex: Vertex) -> Result<()> {
+    fn push_vertex(&mut self, vertex: V) -> Result<()> {
         self.vertex_data_needs_rebound |= self.vertex_data.push_back(vertex)?;
         Ok(())
     }
@@ -231,3 +849,167 @@ impl Frame {
         );
     }
 }
+
+/// Give an error the debug label stack as context, if there are any labels.
+fn with_label_context<T>(labels: &[String], result: Result<T>) -> Result<T> {
+    if labels.is_empty() {
+        return result;
+    }
+    result.with_context(|| format!("while recording {}", labels.join(" > ")))
+}
+
+/// Add a range of indices to a pass's commands. The range joins the last
+/// command when that command draws the indices just before it with the same
+/// clip rect, otherwise it starts a new one.
+fn push_geometry(
+    commands: &mut Vec<DrawCommand>,
+    clip: Option<Rect>,
+    first_index: u32,
+    index_count: u32,
+) {
+    if index_count == 0 {
+        return;
+    }
+    if let Some(DrawCommand::Geometry {
+        first_index: last_first_index,
+        index_count: last_index_count,
+        clip: last_clip,
+    }) = commands.last_mut()
+    {
+        if *last_clip == clip
+            && *last_first_index + *last_index_count == first_index
+        {
+            *last_index_count += index_count;
+            return;
+        }
+    }
+    commands.push(DrawCommand::Geometry {
+        first_index,
+        index_count,
+        clip,
+    });
+}
+
+/// The first texture used by the vertices which isn't in a texture table with
+/// the given number of textures.
+fn unknown_texture<V: VertexFormat>(
+    vertices: &[V],
+    texture_count: u32,
+) -> Option<TextureId> {
+    vertices
+        .iter()
+        .filter_map(VertexFormat::texture)
+        .find(|texture| {
+            texture.index() < 0 || texture.index() as u32 >= texture_count
+        })
+}
+
+/// Apply the transform, if there is one, to a vertex's position.
+fn transformed<V: VertexFormat>(transform: Option<Mat4>, vertex: &V) -> V {
+    match transform {
+        Some(transform) => vertex.with_position(transform * vertex.position()),
+        None => *vertex,
+    }
+}
+
+/// Convert a clip rect into a scissor which is entirely inside the render
+/// target. Partially covered pixels are included in the scissor.
+fn clip_to_scissor(clip: Rect, extent: vk::Extent2D) -> vk::Rect2D {
+    match clip.intersection(Dimensions::from(extent).as_rect()) {
+        Some(visible) => visible.into(),
+        None => vk::Rect2D::default(),
+    }
+}
+
+impl<V: VertexFormat> VulkanDebug for Frame<V> {
+    fn set_debug_name(
+        &self,
+        debug_name: impl Into<String>,
+    ) -> Result<(), VulkanDebugError> {
+        let name = debug_name.into();
+        self._descriptor_pool
+            .set_debug_name(format!("{} - Descriptor Pool", name))?;
+        self.descriptor_set
+            .set_debug_name(format!("{} - Descriptor Set", name))?;
+        self.uniform_data
+            .set_debug_name(format!("{} - Uniform Data", name))?;
+        self.vertex_data
+            .set_debug_name(format!("{} - Vertex Data", name))?;
+        self.index_data
+            .set_debug_name(format!("{} - Index Data", name))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_errors_carry_the_label_stack() {
+        let labels = vec!["ui layer".to_owned(), "settings panel".to_owned()];
+        let error = with_label_context::<()>(
+            &labels,
+            Err(anyhow::anyhow!("out of memory")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            format!("{:#}", error),
+            "while recording ui layer > settings panel: out of memory"
+        );
+
+        let error = with_label_context::<()>(
+            &[],
+            Err(anyhow::anyhow!("out of memory")),
+        )
+        .unwrap_err();
+        assert_eq!(format!("{:#}", error), "out of memory");
+    }
+
+    #[test]
+    fn test_geometry_joins_the_previous_command() {
+        let clip = Some(Rect::new(0.0, 0.0, 10.0, 10.0));
+        let mut commands = vec![];
+        push_geometry(&mut commands, None, 0, 6);
+        push_geometry(&mut commands, None, 6, 3);
+        push_geometry(&mut commands, clip, 9, 0);
+        push_geometry(&mut commands, clip, 9, 3);
+        commands.push(DrawCommand::Composite { layer: 0, clip });
+
+        // the layer drew indices 12 through 17, so this can't join anything
+        push_geometry(&mut commands, clip, 18, 3);
+
+        let geometry = |first_index, index_count, clip| DrawCommand::Geometry {
+            first_index,
+            index_count,
+            clip,
+        };
+        assert_eq!(
+            commands,
+            vec![
+                geometry(0, 9, None),
+                geometry(9, 3, clip),
+                DrawCommand::Composite { layer: 0, clip },
+                geometry(18, 3, clip),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_textures_are_found() {
+        let vertex = |index| Vertex {
+            texture_index: index,
+            ..Default::default()
+        };
+        let vertices = [vertex(0), vertex(2), vertex(5), vertex(-1)];
+        assert_eq!(unknown_texture(&vertices[..2], 3), None);
+        assert_eq!(
+            unknown_texture(&vertices, 3),
+            Some(TextureId::from_index(5))
+        );
+        assert_eq!(
+            unknown_texture(&vertices[3..], 3),
+            Some(TextureId::from_index(-1))
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::ops::{Add, AddAssign};
+
+/// Counts of the geometry pushed into a [`super::Frame`], see
+/// [`super::Frame::stats`].
+///
+/// Stats can be added together to get the totals for several frames, e.g.
+/// the demo application's app and ui layers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FrameStats {
+    pub vertices: usize,
+    pub triangles: usize,
+
+    /// The number of indexed draws the frame will record. Draws whose clip
+    /// rect is entirely off screen are counted even though they're skipped.
+    pub draw_calls: usize,
+}
+
+impl Add for FrameStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            vertices: self.vertices + other.vertices,
+            triangles: self.triangles + other.triangles,
+            draw_calls: self.draw_calls + other.draw_calls,
+        }
+    }
+}
+
+impl AddAssign for FrameStats {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
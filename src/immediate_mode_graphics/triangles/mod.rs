@@ -1,35 +1,111 @@
 //! This module defines structs and functions for efficiently rendering
 //! CPU-generated triangles.
 
+mod effect;
+mod effect_target;
 mod error;
 mod frame;
+mod frame_stats;
 mod pipeline;
+mod recording;
 
-use ::{anyhow::Result, ash::vk, std::sync::Arc};
+use ::{
+    anyhow::Result,
+    ash::vk,
+    std::{
+        cell::RefCell,
+        sync::{atomic::AtomicU32, Arc},
+    },
+};
 
-pub use self::{error::ImmediateModeGraphicsError, frame::Frame};
+pub use self::{
+    effect::{Effect, EffectId},
+    error::ImmediateModeGraphicsError,
+    frame::Frame,
+    frame_stats::FrameStats,
+    recording::FrameRecording,
+};
+
+use self::{
+    effect_target::EffectTargets,
+    frame::FRAME_SET,
+    pipeline::{
+        Shaders, PASSTHROUGH_FRAGMENT_SHADER, PASSTHROUGH_VERTEX_SHADER,
+    },
+};
 
 use crate::{
-    asset_loader::CombinedImageSampler,
+    asset_loader::TextureTable,
+    immediate_mode_graphics::{Vertex, VertexFormat},
+    math::{CoordinateSystem, Culling, DepthMode, DesignResolution},
     multisample_renderpass::MultisampleRenderpass,
     vulkan::{
-        errors::VulkanError, CommandBuffer, MemoryAllocator, Pipeline,
-        RenderDevice,
+        errors::{VulkanDebugError, VulkanError},
+        CommandBuffer, DescriptorSetLayout, MemoryAllocator, Pipeline,
+        RenderDevice, VulkanDebug,
     },
 };
 
 /// This type maintains Vulkan resources and buffers for rendering cpu-generated
 /// triangles each frame.
-pub struct Triangles {
-    /// The set of all indexable textures.
-    /// Vertex texture_id's are treated as indexes into this vector.
-    textures: Vec<CombinedImageSampler>,
+///
+/// By default vertices use the built-in [`Vertex`] type and shaders. Use
+/// [`Triangles::with_shaders`] to render a custom [`VertexFormat`] with
+/// shaders which understand it.
+pub struct Triangles<V: VertexFormat = Vertex> {
+    /// The texture table's layout. Vertex texture indices index into the
+    /// table, which is bound by whoever records the frame.
+    texture_table_layout: Arc<DescriptorSetLayout>,
+
+    /// The texture table's registered count, shared with each frame so
+    /// debug builds can reject vertices with unknown texture ids.
+    texture_count: Arc<AtomicU32>,
+
+    /// The shaders used to build the pipeline. They're kept so the pipeline
+    /// can be rebuilt when the swapchain changes.
+    shaders: Shaders,
 
     /// The graphics pipeline used to render vertices.
     pipeline: Pipeline,
 
+    /// The fragment shader for each effect, kept so the effect pipelines can
+    /// be rebuilt when the swapchain changes.
+    effect_shaders: Vec<&'static [u8]>,
+
+    /// A pipeline for each effect, indexed by the effect's id. They
+    /// composite an effect layer's target with the effect's fragment shader.
+    effect_pipelines: Vec<Pipeline>,
+
+    /// The layout effect pipelines use for a layer's target.
+    effect_target_layout: Arc<DescriptorSetLayout>,
+
+    /// Creates the offscreen target each effect layer is drawn into.
+    effect_targets: EffectTargets,
+
     /// All per-frame resources used to render vertices.
-    frames: Vec<Option<Frame>>,
+    frames: Vec<Option<Frame<V>>>,
+
+    /// The size of the render target. Frames use this as the default scissor
+    /// region.
+    extent: vk::Extent2D,
+
+    /// The coordinate system given to each frame when it's acquired.
+    coordinate_system: CoordinateSystem,
+
+    /// Which triangles the pipelines draw, judged in the coordinate system
+    /// the renderer had when the pipelines were built.
+    culling: Culling,
+
+    /// The design resolution fit into the render target for each frame.
+    design_resolution: Option<DesignResolution>,
+
+    /// How the render pass's depth is stored. Effect layers clear their
+    /// depth attachment to match.
+    depth_mode: DepthMode,
+
+    /// The name used as a prefix for the debug names of the pipeline and
+    /// every frame. It's reapplied when the swapchain resources are rebuilt.
+    debug_name: RefCell<String>,
 
     /// The device allocator.
     vk_alloc: Arc<dyn MemoryAllocator>,
@@ -38,69 +114,218 @@ pub struct Triangles {
     vk_dev: Arc<RenderDevice>,
 }
 
-impl Triangles {
+impl Triangles<Vertex> {
     /// Create a new instance which targets the provided renderpass.
-    /// Vertices can reference any texture in the textures array by their index.
-    /// Often texture indices are generated by an instance of an
-    /// [`AssetLoader`].
+    /// Vertices can reference any texture in the texture table by its index,
+    /// usually the table belongs to an [`crate::asset_loader::AssetLoader`].
     pub fn new(
         msaa_renderpass: &MultisampleRenderpass,
-        textures: &[CombinedImageSampler],
+        texture_table: &TextureTable,
         vk_alloc: Arc<dyn MemoryAllocator>,
         vk_dev: Arc<RenderDevice>,
     ) -> Result<Self, VulkanError> {
+        Self::with_shaders(
+            msaa_renderpass,
+            texture_table,
+            PASSTHROUGH_VERTEX_SHADER,
+            PASSTHROUGH_FRAGMENT_SHADER,
+            vk_alloc,
+            vk_dev,
+        )
+    }
+}
+
+impl<V: VertexFormat> Triangles<V> {
+    /// Create a new instance which renders vertices of a custom format with
+    /// the given SPIR-V shaders.
+    ///
+    /// The shaders must use the same descriptor bindings as the built-in
+    /// shaders: textures are the texture table in set 0, vertices are read
+    /// from a storage buffer at set 1 binding 0, and the view projection
+    /// matrix is in a uniform buffer at set 1 binding 1.
+    pub fn with_shaders(
+        msaa_renderpass: &MultisampleRenderpass,
+        texture_table: &TextureTable,
+        vertex_shader: &'static [u8],
+        fragment_shader: &'static [u8],
+        vk_alloc: Arc<dyn MemoryAllocator>,
+        vk_dev: Arc<RenderDevice>,
+    ) -> Result<Self, VulkanError> {
+        let shaders = Shaders {
+            vertex: vertex_shader,
+            fragment: fragment_shader,
+        };
         let pipeline = pipeline::create_pipeline(
             msaa_renderpass,
-            textures.len() as u32,
+            texture_table.layout(),
             false,
+            (Culling::default(), CoordinateSystem::default()),
+            shaders,
+            vk_dev.clone(),
+        )?;
+        let debug_name = String::from("Triangles");
+        let frames = create_frames(&debug_name, &pipeline, &vk_alloc, &vk_dev)?;
+        let effect_target_layout =
+            EffectTargets::create_layout(vk_dev.clone())?;
+        let effect_targets = EffectTargets::new(
+            msaa_renderpass,
+            DepthMode::default(),
+            effect_target_layout.clone(),
+            vk_alloc.clone(),
             vk_dev.clone(),
         )?;
-        let frames = {
-            let mut frames = vec![];
-            for _ in 0..vk_dev.swapchain_image_count() {
-                let frame = Frame::new(
-                    vk_dev.clone(),
-                    vk_alloc.clone(),
-                    textures,
-                    &pipeline.pipeline_layout.descriptor_layouts[0],
-                )?;
-                frames.push(Some(frame));
-            }
-            frames
-        };
         Ok(Self {
-            textures: textures.to_owned(),
+            texture_table_layout: texture_table.layout().clone(),
+            texture_count: texture_table.shared_registered_count(),
+            shaders,
             pipeline,
+            effect_shaders: vec![],
+            effect_pipelines: vec![],
+            effect_target_layout,
+            effect_targets,
             frames,
+            extent: render_target_extent(msaa_renderpass),
+            coordinate_system: CoordinateSystem::default(),
+            culling: Culling::default(),
+            design_resolution: None,
+            depth_mode: DepthMode::default(),
+            debug_name: RefCell::new(debug_name),
             vk_alloc,
             vk_dev,
         })
     }
 
+    /// Set the coordinate system used by frames from now on. See
+    /// [`Frame::set_coordinate_system`].
+    ///
+    /// Culling is judged in the coordinate system the renderer has when its
+    /// pipelines are built, so set the coordinate system before calling
+    /// [`Self::set_culling`].
+    pub fn set_coordinate_system(
+        &mut self,
+        coordinate_system: CoordinateSystem,
+    ) {
+        self.coordinate_system = coordinate_system;
+    }
+
+    /// Fit the design resolution into the render target for frames from now
+    /// on, or render into the whole target with None. See
+    /// [`Frame::set_design_viewport`].
+    pub fn set_design_resolution(
+        &mut self,
+        design_resolution: Option<DesignResolution>,
+    ) {
+        self.design_resolution = design_resolution;
+    }
+
+    /// Set the depth mode the render pass uses, which decides the value
+    /// effect layers clear their depth attachment to. The swapchain
+    /// resources are rebuilt, so this shouldn't be called while a frame is
+    /// being rendered.
+    pub fn set_depth_mode(
+        &mut self,
+        msaa_renderpass: &MultisampleRenderpass,
+        depth_mode: DepthMode,
+    ) -> Result<(), VulkanError> {
+        self.depth_mode = depth_mode;
+        self.rebuild_swapchain_resources(msaa_renderpass)
+    }
+
+    /// Choose which triangles are drawn, based on their winding in the
+    /// renderer's coordinate system. Renderers start out drawing both sides.
+    /// The renderer's pipelines are rebuilt, so this shouldn't be called
+    /// while a frame is being rendered.
+    pub fn set_culling(
+        &mut self,
+        msaa_renderpass: &MultisampleRenderpass,
+        culling: Culling,
+    ) -> Result<()> {
+        self.culling = culling;
+        self.rebuild_pipelines(msaa_renderpass)?;
+        self.pipeline.set_debug_name(format!(
+            "{} - Pipeline",
+            self.debug_name.borrow()
+        ))?;
+        Ok(())
+    }
+
+    /// Add a fragment shader effect which can be applied to any geometry in
+    /// a frame with [`Frame::push_effect`].
+    ///
+    /// Geometry drawn with the effect is rendered into an offscreen target,
+    /// which the shader composites with a fullscreen triangle. The shader
+    /// gets the fragment's uv in the target at location 0. The target is a
+    /// `sampler2D` at set 2 binding 0, and the effect's parameters are a
+    /// `vec4` in a uniform buffer at set 2 binding 1. The target is cleared
+    /// to transparent, so its colors are premultiplied by alpha, and the
+    /// shader's output is blended the same way.
+    pub fn add_effect(
+        &mut self,
+        msaa_renderpass: &MultisampleRenderpass,
+        fragment_shader: &'static [u8],
+    ) -> Result<EffectId> {
+        let effect_pipeline =
+            self.create_effect_pipeline(msaa_renderpass, fragment_shader)?;
+        let id = EffectId::from_index(self.effect_shaders.len() as u32);
+        effect_pipeline.set_debug_name(format!(
+            "{} - Effect {} Pipeline",
+            self.debug_name.borrow(),
+            id.index()
+        ))?;
+        self.effect_shaders.push(fragment_shader);
+        self.effect_pipelines.push(effect_pipeline);
+        Ok(id)
+    }
+
     /// Rebuild only the swapchain-dependent resources for this renderer
     pub fn rebuild_swapchain_resources(
         &mut self,
         msaa_renderpass: &MultisampleRenderpass,
+    ) -> Result<(), VulkanError> {
+        self.rebuild_pipelines(msaa_renderpass)?;
+        self.extent = render_target_extent(msaa_renderpass);
+        self.effect_targets = EffectTargets::new(
+            msaa_renderpass,
+            self.depth_mode,
+            self.effect_target_layout.clone(),
+            self.vk_alloc.clone(),
+            self.vk_dev.clone(),
+        )?;
+        self.frames = create_frames(
+            &self.debug_name.borrow(),
+            &self.pipeline,
+            &self.vk_alloc,
+            &self.vk_dev,
+        )?;
+        Ok(())
+    }
+
+    /// Rebuild the renderer's pipeline and every effect pipeline.
+    fn rebuild_pipelines(
+        &mut self,
+        msaa_renderpass: &MultisampleRenderpass,
     ) -> Result<(), VulkanError> {
         self.pipeline = pipeline::create_pipeline(
             msaa_renderpass,
-            self.textures.len() as u32,
+            &self.texture_table_layout,
             false,
+            (self.culling, self.coordinate_system),
+            self.shaders,
             self.vk_dev.clone(),
         )?;
-        self.frames = {
-            let mut frames = vec![];
-            for _ in 0..self.vk_dev.swapchain_image_count() {
-                let frame = Frame::new(
-                    self.vk_dev.clone(),
-                    self.vk_alloc.clone(),
-                    &self.textures,
-                    &self.pipeline.pipeline_layout.descriptor_layouts[0],
-                )?;
-                frames.push(Some(frame));
-            }
-            frames
-        };
+        let mut effect_pipelines =
+            Vec::with_capacity(self.effect_shaders.len());
+        for (i, fragment_shader) in self.effect_shaders.iter().enumerate() {
+            let effect_pipeline =
+                self.create_effect_pipeline(msaa_renderpass, fragment_shader)?;
+            effect_pipeline.set_debug_name(format!(
+                "{} - Effect {} Pipeline",
+                self.debug_name.borrow(),
+                i
+            ))?;
+            effect_pipelines.push(effect_pipeline);
+        }
+        self.effect_pipelines = effect_pipelines;
         Ok(())
     }
 
@@ -109,26 +334,58 @@ impl Triangles {
     pub fn acquire_frame(
         &mut self,
         swapchain_image_index: usize,
-    ) -> Result<Frame, ImmediateModeGraphicsError> {
+    ) -> Result<Frame<V>, ImmediateModeGraphicsError> {
         let mut frame = self.frames[swapchain_image_index].take().ok_or(
             ImmediateModeGraphicsError::FrameResourcesUnavailable(
                 swapchain_image_index,
             ),
         )?;
         frame.clear();
+        frame.set_effect_count(self.effect_shaders.len() as u32);
+        frame.set_texture_count(self.texture_count.clone());
+        frame.set_coordinate_system(self.coordinate_system);
+        frame.set_design_viewport(self.design_resolution.map(|design| {
+            design.fit(self.extent.width as f32, self.extent.height as f32)
+        }));
         Ok(frame)
     }
 
+    /// Draw the frame's effect layers into their offscreen targets. Frames
+    /// which pushed any effects must do this before
+    /// [`Self::complete_frame`].
+    ///
+    /// # Safety
+    ///
+    /// UNSAFE BECAUSE:
+    ///   - Must be called before the render pass associated with this
+    ///     pipeline is started, outside of any render pass.
+    ///   - Assumes the texture table is bound, see [`TextureTable::bind`].
+    pub unsafe fn write_effect_passes(
+        &mut self,
+        cmd: &CommandBuffer,
+        frame: &mut Frame<V>,
+    ) -> Result<()> {
+        frame.write_effect_passes(
+            cmd,
+            &self.effect_targets,
+            &self.pipeline,
+            &self.effect_pipelines,
+            self.extent,
+            &self.debug_name.borrow(),
+        )
+    }
+
     /// Complete the frame by writing it's draw commands into the given
     /// command buffer.
     ///
     /// UNSAFE BECAUSE:
     ///   - Assumes that the render pass associated with this pipeline has
     ///     already been started in the given command buffer.
+    ///   - Assumes the texture table is bound, see [`TextureTable::bind`].
     pub unsafe fn complete_frame(
         &mut self,
         cmd: &CommandBuffer,
-        mut frame: Frame,
+        mut frame: Frame<V>,
         swapchain_image_index: usize,
     ) -> Result<()> {
         self.vk_dev.logical_device.cmd_bind_pipeline(
@@ -136,8 +393,89 @@ impl Triangles {
             vk::PipelineBindPoint::GRAPHICS,
             self.pipeline.raw,
         );
-        frame.write_frame_commands(cmd, &self.pipeline.pipeline_layout);
+        let result = frame.write_frame_commands(
+            cmd,
+            &self.pipeline,
+            &self.effect_pipelines,
+            self.extent,
+        );
         self.frames[swapchain_image_index] = Some(frame);
+        result?;
+        Ok(())
+    }
+
+    /// Build a pipeline which composites an effect layer's target with the
+    /// effect's fragment shader.
+    fn create_effect_pipeline(
+        &self,
+        msaa_renderpass: &MultisampleRenderpass,
+        fragment_shader: &'static [u8],
+    ) -> Result<Pipeline, VulkanError> {
+        pipeline::create_effect_pipeline(
+            msaa_renderpass,
+            &self.texture_table_layout,
+            &self.effect_target_layout,
+            fragment_shader,
+            self.vk_dev.clone(),
+        )
+    }
+}
+
+impl<V: VertexFormat> VulkanDebug for Triangles<V> {
+    fn set_debug_name(
+        &self,
+        debug_name: impl Into<String>,
+    ) -> Result<(), VulkanDebugError> {
+        let name = debug_name.into();
+        name_resources(&name, &self.pipeline, &self.frames)?;
+        *self.debug_name.borrow_mut() = name;
         Ok(())
     }
 }
+
+/// Create a frame for each swapchain image and give every resource a debug
+/// name.
+fn create_frames<V: VertexFormat>(
+    debug_name: &str,
+    pipeline: &Pipeline,
+    vk_alloc: &Arc<dyn MemoryAllocator>,
+    vk_dev: &Arc<RenderDevice>,
+) -> Result<Vec<Option<Frame<V>>>, VulkanError> {
+    let mut frames = vec![];
+    for _ in 0..vk_dev.swapchain_image_count() {
+        let frame = Frame::new(
+            vk_dev.clone(),
+            vk_alloc.clone(),
+            &pipeline.pipeline_layout.descriptor_layouts[FRAME_SET as usize],
+        )?;
+        frames.push(Some(frame));
+    }
+    name_resources(debug_name, pipeline, &frames)?;
+    Ok(frames)
+}
+
+/// Name the pipeline and each frame which isn't currently acquired.
+fn name_resources<V: VertexFormat>(
+    debug_name: &str,
+    pipeline: &Pipeline,
+    frames: &[Option<Frame<V>>],
+) -> Result<(), VulkanDebugError> {
+    pipeline.set_debug_name(format!("{} - Pipeline", debug_name))?;
+    for (i, frame) in frames.iter().enumerate() {
+        if let Some(frame) = frame {
+            frame.set_debug_name(format!("{} - Frame {}", debug_name, i))?;
+        }
+    }
+    Ok(())
+}
+
+/// Get the 2D size of the renderpass's render target.
+fn render_target_extent(
+    msaa_renderpass: &MultisampleRenderpass,
+) -> vk::Extent2D {
+    let extent = msaa_renderpass.msaa_render_target.image.create_info.extent;
+    vk::Extent2D {
+        width: extent.width,
+        height: extent.height,
+    }
+}
@@ -0,0 +1,202 @@
+use super::Effect;
+
+use crate::{
+    immediate_mode_graphics::{Vertex, VertexFormat},
+    ui::primitives::Rect,
+};
+
+/// Geometry which was pushed into a Frame while it was recording.
+///
+/// Recordings hold a CPU copy of the vertices after transforms were applied,
+/// along with the clip rect for each part of the geometry and the effects
+/// which were pushed around it. Replaying a recording into a later frame
+/// draws exactly the same thing without tessellating it again. See
+/// [`super::Frame::begin_recording`].
+#[derive(Debug, Clone)]
+pub struct FrameRecording<V: VertexFormat = Vertex> {
+    commands: Vec<RecordedCommand<V>>,
+
+    /// The clip rect for geometry pushed from now on.
+    clip: Option<Rect>,
+
+    /// The number of effects pushed while recording which haven't been
+    /// popped yet.
+    open_effects: usize,
+}
+
+/// Everything a recording replays, in the order it was recorded.
+#[derive(Debug, Clone)]
+pub(super) enum RecordedCommand<V> {
+    Geometry(RecordedSegment<V>),
+
+    /// Geometry up to the matching pop is drawn into the effect's own layer,
+    /// which is clipped to the rect.
+    PushEffect {
+        effect: Effect,
+        clip: Option<Rect>,
+    },
+
+    PopEffect,
+}
+
+/// A run of geometry which is drawn with the same clip rect.
+#[derive(Debug, Clone)]
+pub(super) struct RecordedSegment<V> {
+    pub(super) clip: Option<Rect>,
+    pub(super) vertices: Vec<V>,
+
+    /// Indices into this segment's vertices.
+    pub(super) indices: Vec<u32>,
+}
+
+impl<V: VertexFormat> FrameRecording<V> {
+    /// Start a recording whose geometry is clipped to the given rect.
+    pub(super) fn new(clip: Option<Rect>) -> Self {
+        Self {
+            commands: vec![],
+            clip,
+            open_effects: 0,
+        }
+    }
+
+    /// Geometry pushed after this call is clipped to the given rect.
+    pub(super) fn set_clip(&mut self, clip: Option<Rect>) {
+        self.clip = clip;
+    }
+
+    /// Geometry pushed after this call is drawn with the effect, until the
+    /// matching call to [`Self::pop_effect`].
+    pub(super) fn push_effect(&mut self, effect: Effect) {
+        self.open_effects += 1;
+        self.commands.push(RecordedCommand::PushEffect {
+            effect,
+            clip: self.clip,
+        });
+    }
+
+    /// Stop drawing with the most recently pushed effect. Effects which were
+    /// pushed before recording started aren't part of the recording, so
+    /// popping them isn't recorded either.
+    pub(super) fn pop_effect(&mut self) {
+        if self.open_effects > 0 {
+            self.open_effects -= 1;
+            self.commands.push(RecordedCommand::PopEffect);
+        }
+    }
+
+    /// Add already transformed vertices to the recording. Indices index into
+    /// the given vertices.
+    pub(super) fn push(
+        &mut self,
+        vertices: impl IntoIterator<Item = V>,
+        indices: &[u32],
+    ) {
+        let continues_segment = matches!(
+            self.commands.last(),
+            Some(RecordedCommand::Geometry(segment)) if segment.clip == self.clip
+        );
+        if !continues_segment {
+            self.commands
+                .push(RecordedCommand::Geometry(RecordedSegment {
+                    clip: self.clip,
+                    vertices: vec![],
+                    indices: vec![],
+                }));
+        }
+        if let Some(RecordedCommand::Geometry(segment)) =
+            self.commands.last_mut()
+        {
+            let base_index = segment.vertices.len() as u32;
+            segment.vertices.extend(vertices);
+            segment
+                .indices
+                .extend(indices.iter().map(|index| base_index + index));
+        }
+    }
+
+    /// Every command in the recording, in the order it was recorded.
+    pub(super) fn commands(&self) -> &[RecordedCommand<V>] {
+        &self.commands
+    }
+
+    /// The number of vertices in the recording.
+    pub fn vertex_count(&self) -> usize {
+        self.commands
+            .iter()
+            .map(|command| match command {
+                RecordedCommand::Geometry(segment) => segment.vertices.len(),
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::EffectId, *};
+
+    /// The clip rect and indices of each run of geometry.
+    fn segments(recording: &FrameRecording) -> Vec<(Option<Rect>, Vec<u32>)> {
+        recording
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                RecordedCommand::Geometry(segment) => {
+                    Some((segment.clip, segment.indices.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_segments_follow_clip_changes() {
+        let vertex = Vertex::default();
+        let clip = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let mut recording = FrameRecording::new(None);
+        recording.push(vec![vertex, vertex], &[0, 1]);
+        recording.push(vec![vertex], &[0]);
+
+        // nothing is drawn with the first clip, so it never gets a segment
+        recording.set_clip(Some(Rect::new(5.0, 5.0, 6.0, 6.0)));
+        recording.set_clip(Some(clip));
+        recording.push(vec![vertex], &[0]);
+        recording.set_clip(None);
+
+        assert_eq!(
+            segments(&recording),
+            vec![(None, vec![0, 1, 2]), (Some(clip), vec![0])]
+        );
+        assert_eq!(recording.vertex_count(), 4);
+    }
+
+    #[test]
+    fn test_only_effects_pushed_while_recording_are_kept() {
+        let vertex = Vertex::default();
+        let effect = Effect::new(EffectId::from_index(0), Default::default());
+        let mut recording = FrameRecording::new(None);
+        recording.push(vec![vertex], &[0]);
+        recording.push_effect(effect);
+        recording.push(vec![vertex], &[0]);
+        recording.pop_effect();
+
+        // this effect was pushed before the recording started
+        recording.pop_effect();
+        recording.push(vec![vertex], &[0]);
+
+        let kinds: Vec<_> = recording
+            .commands()
+            .iter()
+            .map(|command| match command {
+                RecordedCommand::Geometry(_) => "geometry",
+                RecordedCommand::PushEffect { .. } => "push",
+                RecordedCommand::PopEffect => "pop",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["geometry", "push", "geometry", "pop", "geometry"]
+        );
+        assert_eq!(segments(&recording).len(), 3);
+    }
+}
@@ -0,0 +1,13 @@
+#version 450
+#extension GL_ARB_separate_shader_objects: enable
+
+// Effects are composited with a single triangle which covers the whole
+// framebuffer. uv is (0, 0) at the framebuffer's top left and (1, 1) at its
+// bottom right, so it's also where each fragment lands in the effect's
+// offscreen target.
+layout(location = 0) out vec2 uv;
+
+void main() {
+    uv = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+}
@@ -5,12 +5,16 @@
 layout(location = 0) in vec4 vertex_color;
 layout(location = 1) in vec2 uv;
 layout(location = 2) flat in int texIndex;
+layout(location = 3) in float edge_fade;
 
 layout(location = 0) out vec4 frag_color;
 
-layout(binding = 2) uniform sampler2D textures[];
+layout(set = 0, binding = 0) uniform sampler2D textures[];
 
 void main() {
     vec4 tex_color = texture(textures[nonuniformEXT(texIndex)], uv);
     frag_color = tex_color * vertex_color;
+
+    // antialiased edges fade out as edge_fade goes from 0 to 1
+    frag_color.a *= clamp(1.0 - edge_fade, 0.0, 1.0);
 }
@@ -1,45 +1,63 @@
-use crate::{Vec2, Vec3, Vec4};
+use crate::{
+    assert_gpu_stride, asset_loader::TextureId, gpu_data, vec4,
+    vulkan::GpuData, Vec2, Vec3, Vec4,
+};
 
-/// The supported Per-Vertex graphics data.
-#[repr(C)]
-#[derive(Debug, Copy, Clone)]
-pub struct Vertex {
-    // The vertex position in model space.
-    pub pos: [f32; 4],
+/// Types which can be used as the vertices of a
+/// [`crate::immediate_mode_graphics::triangles::Triangles`] renderer.
+///
+/// Custom vertex types let a renderer's shaders read extra per-vertex
+/// attributes, like normals or a second set of texture coordinates. The
+/// vertex shader reads vertices from the storage buffer at binding 0, so the
+/// type's layout must match the shader's declaration of the buffer.
+pub trait VertexFormat: GpuData {
+    /// The vertex's homogeneous position in model space.
+    fn position(&self) -> Vec4;
 
-    // The rgba color to be applied to this vertex.
-    pub rgba: [f32; 4],
+    /// A copy of the vertex at a new position. Used to apply the frame's
+    /// transform stack as vertices are pushed.
+    fn with_position(&self, position: Vec4) -> Self;
 
-    // The texture coordinate associated with the vertex.
-    pub uv: [f32; 2],
+    /// The texture the vertex samples, if the format has one. Debug builds
+    /// check it against the texture table before the vertex is drawn.
+    fn texture(&self) -> Option<TextureId> {
+        None
+    }
+}
+
+gpu_data! {
+    /// The supported Per-Vertex graphics data.
+    #[derive(Debug, Copy, Clone)]
+    pub struct Vertex {
+        // The vertex position in model space.
+        pub pos: [f32; 4],
+
+        // The rgba color to be applied to this vertex.
+        pub rgba: [f32; 4],
 
-    // The texture index controls which texture will be applied to the vertex
-    // when resterizing.
-    // Defaults to 0.
-    pub texture_index: i32,
+        // The texture coordinate associated with the vertex.
+        pub uv: [f32; 2],
 
-    // Padding required for proper alignment inside the buffer.
-    // See the OpenGL spec for notes regarding structure padding when elements
-    // are stored in a SSBO:
-    // https://www.khronos.org/registry/OpenGL/specs/gl/glspec45.core.pdf#page=159
-    //
-    // The bit that's relevant here:
-    // > The structure may have padding at the end;
-    // > the base offset of the member following the sub-structure is rounded
-    // > up to the next multiple of the base alignment of the structure.
-    //
-    // Where the base alignment of the structure is:
-    // > The base alignment of the structure is N , where N is the largest base
-    // > alignment value of any of its members, and rounded up to the base
-    // > alignment of a vec4.
-    //
-    // In this case, the base alignment is that of a vec4: 16. So the shader
-    // will assume that every Vertex in the SSBO is aligned to 16 bytes. The
-    // easiest way for us to manage that is to pad the structure so it's total
-    // size is a multiple of 16 bytes. Hence, padding.
-    pub _pad: i32,
+        // The texture index controls which texture will be applied to the
+        // vertex when resterizing. It's the index of a [`TextureId`], kept as
+        // a plain i32 to match the shader's layout. Defaults to 0.
+        pub texture_index: i32,
+
+        // How far the vertex is into an antialiased edge's falloff, in
+        // pixels. The fragment shader fades alpha out as the value goes from
+        // 0 to 1, so values at or below 0 are fully opaque. Defaults to 0.
+        //
+        // The field also keeps the structure's size a multiple of 16 bytes,
+        // which std140 requires for every element in a SSBO:
+        // https://www.khronos.org/registry/OpenGL/specs/gl/glspec45.core.pdf#page=159
+        pub edge_fade: f32,
+    }
 }
 
+// the passthrough shader declares its vertex buffer with the std140 layout,
+// which rounds each vertex up to a multiple of 16 bytes
+assert_gpu_stride!(Vertex, 48);
+
 impl Default for Vertex {
     fn default() -> Self {
         Self {
@@ -47,20 +65,38 @@ impl Default for Vertex {
             rgba: [1.0, 1.0, 1.0, 1.0],
             uv: [0.0, 0.0],
             texture_index: 0,
-            _pad: 0,
+            edge_fade: 0.0,
         }
     }
 }
 
 impl Vertex {
     /// Create a new Vertex using nalgebra vectors.
-    pub fn new(pos: Vec3, rgba: Vec4, uv: Vec2, texture_index: i32) -> Vertex {
+    pub fn new(pos: Vec3, rgba: Vec4, uv: Vec2, texture: TextureId) -> Vertex {
         Self {
             pos: [pos.x, pos.y, pos.z, 1.0],
             rgba: rgba.into(),
             uv: uv.into(),
-            texture_index,
-            _pad: 0,
+            texture_index: texture.index(),
+            edge_fade: 0.0,
+        }
+    }
+}
+
+impl VertexFormat for Vertex {
+    fn position(&self) -> Vec4 {
+        let [x, y, z, w] = self.pos;
+        vec4(x, y, z, w)
+    }
+
+    fn with_position(&self, position: Vec4) -> Self {
+        Self {
+            pos: position.into(),
+            ..*self
         }
     }
+
+    fn texture(&self) -> Option<TextureId> {
+        Some(TextureId::from_index(self.texture_index))
+    }
 }
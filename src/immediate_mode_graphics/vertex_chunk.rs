@@ -0,0 +1,121 @@
+use ::{anyhow::Result, rayon::prelude::*};
+
+use crate::immediate_mode_graphics::{Vertex, VertexFormat, VertexStream};
+
+/// The fewest items tessellated together by [`tessellate_in_parallel`]. Small
+/// batches spend more time coordinating threads than building geometry.
+const MIN_ITEMS_PER_CHUNK: usize = 16;
+
+/// A VertexChunk collects vertices and indices without a Frame, so geometry
+/// can be built on any thread and drawn into the Frame later.
+#[derive(Debug, Clone)]
+pub struct VertexChunk<V: VertexFormat = Vertex> {
+    vertices: Vec<V>,
+    indices: Vec<u32>,
+}
+
+impl<V: VertexFormat> VertexChunk<V> {
+    /// Create a new, empty, vertex chunk.
+    pub fn new() -> Self {
+        Self {
+            vertices: vec![],
+            indices: vec![],
+        }
+    }
+
+    /// True when nothing has been pushed into the chunk.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Push the chunk's vertices into another vertex stream. The stream's
+    /// transforms and clip rects apply just like they would have if the
+    /// geometry was drawn directly.
+    pub fn draw(&self, vertices: &mut impl VertexStream<V>) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        vertices.push_vertices(&self.vertices, &self.indices)
+    }
+}
+
+impl<V: VertexFormat> Default for VertexChunk<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: VertexFormat> VertexStream<V> for VertexChunk<V> {
+    /// Push vertices into the chunk. Indices index into the given vertex
+    /// slice.
+    fn push_vertices(&mut self, vertices: &[V], indices: &[u32]) -> Result<()> {
+        let base_index = self.vertices.len() as u32;
+        self.vertices.extend_from_slice(vertices);
+        self.indices
+            .extend(indices.iter().map(|index| base_index + index));
+        Ok(())
+    }
+}
+
+/// Tessellate items on rayon's thread pool.
+///
+/// Items are split into contiguous batches and each batch is tessellated into
+/// its own chunk. Chunks are returned in the same order as the items, so
+/// drawing them one after another gives exactly the same result as
+/// tessellating every item in order on a single thread.
+pub fn tessellate_in_parallel<T, V, F>(
+    items: &[T],
+    tessellate: F,
+) -> Result<Vec<VertexChunk<V>>>
+where
+    T: Sync,
+    V: VertexFormat + Send,
+    F: Fn(&T, &mut VertexChunk<V>) -> Result<()> + Sync,
+{
+    let batches = 4 * rayon::current_num_threads();
+    let batch_size = (items.len() / batches).max(MIN_ITEMS_PER_CHUNK);
+    items
+        .par_chunks(batch_size)
+        .map(|batch| {
+            let mut chunk = VertexChunk::new();
+            for item in batch {
+                tessellate(item, &mut chunk)?;
+            }
+            Ok(chunk)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{asset_loader::TextureId, vec2, vec3, vec4};
+
+    #[test]
+    fn test_parallel_chunks_keep_the_item_order() -> Result<()> {
+        let items: Vec<usize> = (0..1000).collect();
+        let chunks = tessellate_in_parallel(&items, |&i, chunk| {
+            let vertex = Vertex::new(
+                vec3(i as f32, 0.0, 0.0),
+                vec4(1.0, 1.0, 1.0, 1.0),
+                vec2(0.0, 0.0),
+                TextureId::WHITE_PIXEL,
+            );
+            chunk.push_vertices(&[vertex, vertex], &[1, 0])
+        })?;
+        assert!(chunks.len() > 1);
+
+        let mut combined = VertexChunk::new();
+        for chunk in &chunks {
+            chunk.draw(&mut combined)?;
+        }
+        for (i, vertex) in combined.vertices.iter().enumerate() {
+            assert_eq!(vertex.position().x, (i / 2) as f32);
+        }
+        for (i, pair) in combined.indices.chunks(2).enumerate() {
+            assert_eq!(pair, &[2 * i as u32 + 1, 2 * i as u32]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use ::std::{any::Any, fmt};
+
+/// Identifies a job started by [`super::Jobs`]. Ids are never reused.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JobId(u64);
+
+impl JobId {
+    pub(super) fn new(index: u64) -> Self {
+        Self(index)
+    }
+}
+
+impl fmt::Display for JobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Something that happened to a job since the last frame. Cancelled jobs
+/// don't have any more events.
+pub enum JobEvent {
+    /// The job's progress changed, in the range [0, 1].
+    Progress { id: JobId, fraction: f32 },
+
+    /// The job is done. Take the result with [`Self::into_output`].
+    Finished { id: JobId, output: Box<dyn Any> },
+
+    /// The job panicked, so there is no result.
+    Failed { id: JobId, message: String },
+}
+
+impl JobEvent {
+    /// The job this event is about.
+    pub fn id(&self) -> JobId {
+        match *self {
+            Self::Progress { id, .. } => id,
+            Self::Finished { id, .. } => id,
+            Self::Failed { id, .. } => id,
+        }
+    }
+
+    /// The job's result, if this is a Finished event for a job which
+    /// returned a `T`.
+    pub fn into_output<T: Any>(self) -> Option<T> {
+        match self {
+            Self::Finished { output, .. } => {
+                output.downcast().ok().map(|output| *output)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for JobEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Progress { id, fraction } => f
+                .debug_struct("Progress")
+                .field("id", id)
+                .field("fraction", fraction)
+                .finish(),
+            Self::Finished { id, .. } => {
+                f.debug_struct("Finished").field("id", id).finish()
+            }
+            Self::Failed { id, message } => f
+                .debug_struct("Failed")
+                .field("id", id)
+                .field("message", message)
+                .finish(),
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Cooperative jobs for expensive CPU work, like tessellating a huge SVG or
+//! analyzing an image, which would hitch the render loop if it ran in a
+//! single frame.
+//!
+//! Jobs either run on rayon's thread pool, or are sliced into small
+//! steps on the main thread which run within a time budget each frame. Both
+//! kinds report their progress and deliver their result as a [`JobEvent`]
+//! when [`Jobs::run_frame`] is called. The demo Application does that once
+//! per frame and forwards every event for the State's jobs to the State.
+
+mod job_event;
+mod progress;
+mod scheduler;
+
+pub use self::{
+    job_event::{JobEvent, JobId},
+    progress::Progress,
+    scheduler::{Jobs, SlicedJob, Step},
+};
@@ -0,0 +1,42 @@
+use ::std::sync::{
+    atomic::{AtomicBool, AtomicU32, Ordering},
+    Arc,
+};
+
+/// A Progress is shared between a job running on a worker thread and the
+/// [`super::Jobs`] which started it.
+///
+/// Jobs set the fraction of their work which is done, and should check
+/// [`Self::is_cancelled`] every so often so cancelled work stops early.
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    /// The fraction of the work which is done, as f32 bits.
+    fraction: Arc<AtomicU32>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Progress {
+    /// Report the fraction of the work which is done, clamped to [0, 1].
+    pub fn set(&self, fraction: f32) {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+        self.fraction.store(fraction.to_bits(), Ordering::Relaxed);
+    }
+
+    /// The fraction of the work which is done.
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.fraction.load(Ordering::Relaxed))
+    }
+
+    /// True when the job was cancelled and its result will be ignored.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
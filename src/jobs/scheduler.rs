@@ -0,0 +1,371 @@
+use ::std::{
+    any::Any,
+    cell::RefCell,
+    collections::VecDeque,
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+    sync::mpsc::{self, Receiver, Sender},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    jobs::{JobEvent, JobId, Progress},
+    panic_message,
+};
+
+/// What a sliced job did with one step.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Step<T> {
+    /// There's more work to do. The value is the fraction of the work which
+    /// is done, in the range [0, 1].
+    Continue(f32),
+
+    /// The work is done.
+    Done(T),
+}
+
+/// A SlicedJob runs on the main thread, one small step at a time, so it can
+/// use resources which can't be sent to a worker thread.
+pub trait SlicedJob: 'static {
+    type Output: Any;
+
+    /// Do a small piece of the work. Steps are repeated until the frame's
+    /// budget is spent, so each one should take well under the budget.
+    fn step(&mut self) -> Step<Self::Output>;
+}
+
+/// Sliced jobs with their output boxed, so jobs with different outputs can
+/// share a queue.
+trait ErasedJob {
+    fn step(&mut self) -> Step<Box<dyn Any>>;
+}
+
+impl<J: SlicedJob> ErasedJob for J {
+    fn step(&mut self) -> Step<Box<dyn Any>> {
+        match SlicedJob::step(self) {
+            Step::Continue(fraction) => Step::Continue(fraction),
+            Step::Done(output) => Step::Done(Box::new(output)),
+        }
+    }
+}
+
+type WorkerResult = (JobId, Result<Box<dyn Any + Send>, String>);
+
+/// A job which was sent to rayon's thread pool.
+struct WorkerJob {
+    id: JobId,
+    progress: Progress,
+
+    /// The progress which was last delivered as an event.
+    reported: f32,
+}
+
+/// A job which is sliced across frames on the main thread.
+struct QueuedJob {
+    id: JobId,
+    job: Box<dyn ErasedJob>,
+    fraction: f32,
+
+    /// The progress which was last delivered as an event.
+    reported: f32,
+}
+
+struct Scheduler {
+    next_id: u64,
+    results: Sender<WorkerResult>,
+    finished: Receiver<WorkerResult>,
+    workers: Vec<WorkerJob>,
+    sliced: VecDeque<QueuedJob>,
+}
+
+/// Jobs schedules expensive work so it doesn't hitch the render loop.
+///
+/// Jobs is a cheap handle, clones share the same jobs. The demo Application
+/// gives one to the State with [`crate::demo::State::register_jobs`], runs
+/// it each frame, and delivers every event for the State's jobs to
+/// [`crate::demo::State::handle_job_event`].
+///
+/// Cancelled jobs, and jobs which are still running when every handle is
+/// dropped, never deliver a result. Work which is already running on a
+/// worker thread finishes unless it checks [`Progress::is_cancelled`].
+#[derive(Clone)]
+pub struct Jobs {
+    scheduler: Rc<RefCell<Scheduler>>,
+}
+
+impl Jobs {
+    /// Create a scheduler with no jobs. Spawned jobs share rayon's global
+    /// thread pool, so no threads are started here.
+    pub fn new() -> Self {
+        let (results, finished) = mpsc::channel();
+        Self {
+            scheduler: Rc::new(RefCell::new(Scheduler {
+                next_id: 0,
+                results,
+                finished,
+                workers: vec![],
+                sliced: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Run the work on rayon's global thread pool, which is shared with
+    /// parallel tessellation. The work can report its progress, and should
+    /// stop early when it's cancelled. Panics are caught and delivered as a
+    /// [`JobEvent::Failed`] event.
+    pub fn spawn<T, F>(&self, work: F) -> JobId
+    where
+        T: Send + 'static,
+        F: FnOnce(&Progress) -> T + Send + 'static,
+    {
+        let mut scheduler = self.scheduler.borrow_mut();
+        let id = scheduler.next_id();
+        let progress = Progress::default();
+        let worker_progress = progress.clone();
+        let results = scheduler.results.clone();
+        rayon::spawn(move || {
+            if worker_progress.is_cancelled() {
+                return;
+            }
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                work(&worker_progress)
+            }))
+            .map(|output| Box::new(output) as Box<dyn Any + Send>)
+            .map_err(|payload| panic_message(&*payload).to_owned());
+
+            // the result is ignored when every Jobs handle is gone
+            let _ = results.send((id, result));
+        });
+        scheduler.workers.push(WorkerJob {
+            id,
+            progress,
+            reported: 0.0,
+        });
+        id
+    }
+
+    /// Run the job on the main thread, a few steps each frame.
+    pub fn slice(&self, job: impl SlicedJob) -> JobId {
+        let mut scheduler = self.scheduler.borrow_mut();
+        let id = scheduler.next_id();
+        scheduler.sliced.push_back(QueuedJob {
+            id,
+            job: Box::new(job),
+            fraction: 0.0,
+            reported: 0.0,
+        });
+        id
+    }
+
+    /// Stop a job. Returns false when the job already finished or was never
+    /// started by this scheduler.
+    pub fn cancel(&self, id: JobId) -> bool {
+        let mut scheduler = self.scheduler.borrow_mut();
+        if let Some(index) = scheduler.workers.iter().position(|j| j.id == id) {
+            scheduler.workers.remove(index).progress.cancel();
+            return true;
+        }
+        let before = scheduler.sliced.len();
+        scheduler.sliced.retain(|job| job.id != id);
+        scheduler.sliced.len() != before
+    }
+
+    /// The job's progress, in the range [0, 1], or None when it isn't
+    /// running.
+    pub fn progress(&self, id: JobId) -> Option<f32> {
+        let scheduler = self.scheduler.borrow();
+        scheduler
+            .workers
+            .iter()
+            .find(|job| job.id == id)
+            .map(|job| job.progress.get())
+            .or_else(|| {
+                scheduler
+                    .sliced
+                    .iter()
+                    .find(|job| job.id == id)
+                    .map(|job| job.fraction)
+            })
+    }
+
+    /// The number of jobs which haven't finished yet.
+    pub fn len(&self) -> usize {
+        let scheduler = self.scheduler.borrow();
+        scheduler.workers.len() + scheduler.sliced.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Collect results from the thread pool, then step sliced jobs in
+    /// turn until the budget is spent. At least one step runs each frame so
+    /// sliced jobs always make progress.
+    ///
+    /// Returns what happened to each job, finished jobs last.
+    pub fn run_frame(&self, budget: Duration) -> Vec<JobEvent> {
+        let start = Instant::now();
+        let mut finished = self.scheduler.borrow_mut().take_worker_results();
+
+        // Steps run without borrowing the scheduler so they can start or
+        // cancel other jobs.
+        loop {
+            let queued = self.scheduler.borrow_mut().sliced.pop_front();
+            let mut queued = match queued {
+                Some(queued) => queued,
+                None => break,
+            };
+            match queued.job.step() {
+                Step::Continue(fraction) => {
+                    queued.fraction = fraction.clamp(0.0, 1.0);
+                    self.scheduler.borrow_mut().sliced.push_back(queued);
+                }
+                Step::Done(output) => finished.push(JobEvent::Finished {
+                    id: queued.id,
+                    output,
+                }),
+            }
+            if start.elapsed() >= budget {
+                break;
+            }
+        }
+
+        let mut events = self.scheduler.borrow_mut().take_progress();
+        events.extend(finished);
+        events
+    }
+}
+
+impl Default for Jobs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler {
+    fn next_id(&mut self) -> JobId {
+        self.next_id += 1;
+        JobId::new(self.next_id)
+    }
+
+    /// Events for every worker job which finished since the last frame.
+    /// Results from cancelled jobs are dropped.
+    fn take_worker_results(&mut self) -> Vec<JobEvent> {
+        let mut events = vec![];
+        while let Ok((id, result)) = self.finished.try_recv() {
+            let index = match self.workers.iter().position(|j| j.id == id) {
+                Some(index) => index,
+                None => continue,
+            };
+            self.workers.remove(index);
+            events.push(match result {
+                Ok(output) => JobEvent::Finished {
+                    id,
+                    output: output as Box<dyn Any>,
+                },
+                Err(message) => JobEvent::Failed { id, message },
+            });
+        }
+        events
+    }
+
+    /// Events for every running job whose progress changed since the last
+    /// frame.
+    fn take_progress(&mut self) -> Vec<JobEvent> {
+        let mut events = vec![];
+        for job in &mut self.workers {
+            let fraction = job.progress.get();
+            if fraction != job.reported {
+                job.reported = fraction;
+                events.push(JobEvent::Progress {
+                    id: job.id,
+                    fraction,
+                });
+            }
+        }
+        for job in &mut self.sliced {
+            if job.fraction != job.reported {
+                job.reported = job.fraction;
+                events.push(JobEvent::Progress {
+                    id: job.id,
+                    fraction: job.fraction,
+                });
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct CountTo {
+        count: u32,
+        target: u32,
+    }
+
+    impl SlicedJob for CountTo {
+        type Output = u32;
+
+        fn step(&mut self) -> Step<u32> {
+            self.count += 1;
+            if self.count == self.target {
+                Step::Done(self.count)
+            } else {
+                Step::Continue(self.count as f32 / self.target as f32)
+            }
+        }
+    }
+
+    #[test]
+    fn test_sliced_jobs_take_one_step_without_a_budget() {
+        let jobs = Jobs::new();
+        let id = jobs.slice(CountTo {
+            count: 0,
+            target: 4,
+        });
+
+        let events = jobs.run_frame(Duration::ZERO);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            JobEvent::Progress { fraction, .. } if fraction == 0.25
+        ));
+        assert_eq!(jobs.progress(id), Some(0.25));
+
+        // a generous budget runs every remaining step
+        let mut events = jobs.run_frame(Duration::from_secs(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events.pop().unwrap().into_output::<u32>(), Some(4));
+        assert!(jobs.is_empty());
+    }
+
+    #[test]
+    fn test_worker_results_are_delivered() {
+        let jobs = Jobs::new();
+        let sum = jobs.spawn(|_| (1..=10).sum::<u32>());
+        let failed = jobs.spawn(|_| -> u32 { panic!("bad job") });
+        let cancelled = jobs.spawn(|_| 7);
+        assert!(jobs.cancel(cancelled));
+
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut outputs = vec![];
+        while !jobs.is_empty() && Instant::now() < deadline {
+            outputs.extend(jobs.run_frame(Duration::ZERO));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(outputs.len(), 2);
+        for event in outputs {
+            if event.id() == sum {
+                assert_eq!(event.into_output::<u32>(), Some(55));
+            } else {
+                assert_eq!(event.id(), failed);
+                assert!(matches!(
+                    event,
+                    JobEvent::Failed { message, .. } if message == "bad job"
+                ));
+            }
+        }
+    }
+}
@@ -1,10 +1,11 @@
-mod markdown;
-
 pub mod asset_loader;
+pub mod bench;
 pub mod demo;
 pub mod frame_pipeline;
 pub mod glfw_window;
 pub mod immediate_mode_graphics;
+pub mod jobs;
+pub mod markdown;
 pub mod math;
 pub mod multisample_renderpass;
 pub mod timing;
@@ -32,6 +33,19 @@ pub fn vec4(x: f32, y: f32, z: f32, w: f32) -> Vec4 {
     Vec4::new(x, y, z, w)
 }
 
+/// Panic payloads are usually strings, anything else can't be described.
+pub(crate) fn panic_message(
+    panic_payload: &(dyn std::any::Any + Send),
+) -> &str {
+    if let Some(message) = panic_payload.downcast_ref::<&'static str>() {
+        message
+    } else if let Some(message) = panic_payload.downcast_ref::<String>() {
+        message
+    } else {
+        "<the panic payload is not a string>"
+    }
+}
+
 #[allow(unused)]
 macro_rules! builder_field {
     ($field:ident, $field_type:ty) => {
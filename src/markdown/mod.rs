@@ -1,5 +1,9 @@
+mod parse;
+
 use std::fmt;
 
+pub use self::parse::{parse, Block, ListMarker, Span, SpanStyle};
+
 /// Wrapper for &[T] which can be pretty-printed as a markdown list.
 pub struct MdList<'data, T>(pub &'data [T]);
 
@@ -0,0 +1,292 @@
+/// A block of a markdown document.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// A `#` heading. Level 1 is the largest.
+    Heading { level: u8, spans: Vec<Span> },
+
+    /// Consecutive lines of text, joined into a single paragraph.
+    Paragraph(Vec<Span>),
+
+    /// A single list item. Nested items have a larger depth, each level is
+    /// indented by two spaces in the source.
+    ListItem {
+        depth: usize,
+        marker: ListMarker,
+        spans: Vec<Span>,
+    },
+
+    /// The text between two ``` fences, exactly as written.
+    CodeBlock(String),
+}
+
+/// The marker at the start of a list item.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ListMarker {
+    /// A `-`, `*`, or `+` item.
+    Bullet,
+
+    /// A `1.` or `1)` item.
+    Number(u32),
+}
+
+/// A run of text which has the same style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Span {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+/// The inline styles which apply to a Span.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SpanStyle {
+    /// Text between `**` or `__`.
+    pub bold: bool,
+
+    /// Text between `*` or `_`.
+    pub italic: bool,
+
+    /// Text between backticks.
+    pub code: bool,
+}
+
+/// The block which is still collecting lines.
+enum Open<'text> {
+    Nothing,
+    Paragraph(Vec<&'text str>),
+    ListItem {
+        depth: usize,
+        marker: ListMarker,
+        lines: Vec<&'text str>,
+    },
+}
+
+/// Parse the subset of markdown used for help text: headings, paragraphs,
+/// bullet and numbered lists, fenced code blocks, and bold, italic, and code
+/// spans. Anything else is treated as paragraph text.
+pub fn parse(text: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut open = Open::Nothing;
+    let mut code: Option<Vec<&str>> = None;
+
+    for line in text.lines() {
+        if let Some(code_lines) = code.as_mut() {
+            if line.trim_start().starts_with("```") {
+                blocks.push(Block::CodeBlock(code_lines.join("\n")));
+                code = None;
+            } else {
+                code_lines.push(line);
+            }
+            continue;
+        }
+
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            close(&mut blocks, &mut open);
+            code = Some(vec![]);
+        } else if trimmed.is_empty() {
+            close(&mut blocks, &mut open);
+        } else if let Some((level, heading)) = parse_heading(trimmed) {
+            close(&mut blocks, &mut open);
+            blocks.push(Block::Heading {
+                level,
+                spans: parse_spans(heading),
+            });
+        } else if let Some((depth, marker, item)) = parse_list_item(line) {
+            close(&mut blocks, &mut open);
+            open = Open::ListItem {
+                depth,
+                marker,
+                lines: vec![item],
+            };
+        } else {
+            match &mut open {
+                Open::Nothing => open = Open::Paragraph(vec![trimmed]),
+                Open::Paragraph(lines) | Open::ListItem { lines, .. } => {
+                    lines.push(trimmed)
+                }
+            }
+        }
+    }
+
+    // an unterminated fence runs to the end of the document
+    if let Some(code_lines) = code {
+        blocks.push(Block::CodeBlock(code_lines.join("\n")));
+    }
+    close(&mut blocks, &mut open);
+    blocks
+}
+
+/// Finish the open block, if there is one.
+fn close(blocks: &mut Vec<Block>, open: &mut Open) {
+    match std::mem::replace(open, Open::Nothing) {
+        Open::Nothing => (),
+        Open::Paragraph(lines) => {
+            blocks.push(Block::Paragraph(parse_spans(&lines.join(" "))))
+        }
+        Open::ListItem {
+            depth,
+            marker,
+            lines,
+        } => blocks.push(Block::ListItem {
+            depth,
+            marker,
+            spans: parse_spans(&lines.join(" ")),
+        }),
+    }
+}
+
+/// Parse a line like `## Heading` into its level and text.
+fn parse_heading(line: &str) -> Option<(u8, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if level == 0 || level > 6 || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((level as u8, rest.trim().trim_end_matches('#').trim_end()))
+}
+
+/// Parse a line like `  - item` or `1. item` into its depth, marker, and
+/// text.
+fn parse_list_item(line: &str) -> Option<(usize, ListMarker, &str)> {
+    let indent: usize = line
+        .chars()
+        .take_while(|c| c.is_whitespace())
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum();
+    let item = line.trim_start();
+
+    let (marker, rest) = match item.chars().next()? {
+        '-' | '*' | '+' => (ListMarker::Bullet, &item[1..]),
+        _ => {
+            let digits = item.chars().take_while(char::is_ascii_digit).count();
+            let rest = &item[digits..];
+            if digits == 0 || !(rest.starts_with('.') || rest.starts_with(')'))
+            {
+                return None;
+            }
+            (ListMarker::Number(item[..digits].parse().ok()?), &rest[1..])
+        }
+    };
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    Some((indent / 2, marker, rest.trim()))
+}
+
+/// Split text into spans at each `**`, `*`, `__`, `_`, and backtick. Markers
+/// can be escaped with a backslash, and underscores inside words are kept so
+/// identifiers like snake_case read correctly.
+fn parse_spans(text: &str) -> Vec<Span> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut spans = vec![];
+    let mut style = SpanStyle::default();
+    let mut current = String::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '\\' if next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                current.push(next.unwrap());
+                i += 1;
+            }
+            '`' => {
+                push_span(&mut spans, &mut current, style);
+                style.code = !style.code;
+            }
+            _ if style.code => current.push(c),
+            '*' | '_' => {
+                let len = if next == Some(c) { 2 } else { 1 };
+                let before = i.checked_sub(1).map(|b| chars[b]);
+                let after = chars.get(i + len).copied();
+                let is_alphanumeric =
+                    |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+                if c == '_' && is_alphanumeric(before) && is_alphanumeric(after)
+                {
+                    current.extend(&chars[i..i + len]);
+                } else {
+                    push_span(&mut spans, &mut current, style);
+                    if len == 2 {
+                        style.bold = !style.bold;
+                    } else {
+                        style.italic = !style.italic;
+                    }
+                }
+                i += len - 1;
+            }
+            _ => current.push(c),
+        }
+        i += 1;
+    }
+    push_span(&mut spans, &mut current, style);
+    spans
+}
+
+fn push_span(spans: &mut Vec<Span>, current: &mut String, style: SpanStyle) {
+    if !current.is_empty() {
+        spans.push(Span {
+            text: std::mem::take(current),
+            style,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn span(text: &str, bold: bool, italic: bool, code: bool) -> Span {
+        Span {
+            text: text.to_owned(),
+            style: SpanStyle { bold, italic, code },
+        }
+    }
+
+    #[test]
+    fn test_parse_help_text() {
+        let blocks = parse(
+            "# Help ##\n\
+             Press **space** to *pause*,\n\
+             or run `cargo test` on my_file.\n\
+             \n\
+             - first\n\
+             \x20 continued\n\
+             \x20 2) nested\n\
+             ```\n\
+             let x = 1;\n\
+             \n\
+             ```",
+        );
+
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading {
+                    level: 1,
+                    spans: vec![span("Help", false, false, false)],
+                },
+                Block::Paragraph(vec![
+                    span("Press ", false, false, false),
+                    span("space", true, false, false),
+                    span(" to ", false, false, false),
+                    span("pause", false, true, false),
+                    span(", or run ", false, false, false),
+                    span("cargo test", false, false, true),
+                    span(" on my_file.", false, false, false),
+                ]),
+                Block::ListItem {
+                    depth: 0,
+                    marker: ListMarker::Bullet,
+                    spans: vec![span("first continued", false, false, false)],
+                },
+                Block::ListItem {
+                    depth: 1,
+                    marker: ListMarker::Number(2),
+                    spans: vec![span("nested", false, false, false)],
+                },
+                Block::CodeBlock("let x = 1;\n".to_owned()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{vec3, Vec3};
+
+/// Convert a single sRGB-encoded channel, in the range [0, 1], to linear
+/// light.
+pub fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a single linear channel, in the range [0, 1], to its sRGB
+/// encoding.
+pub fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert an rgb color into hue, saturation, and value. Hue is in degrees,
+/// in the range [0, 360). Saturation and value are in the range [0, 1].
+pub fn rgb_to_hsv(rgb: Vec3) -> Vec3 {
+    let max = rgb.x.max(rgb.y).max(rgb.z);
+    let min = rgb.x.min(rgb.y).min(rgb.z);
+    let delta = max - min;
+
+    let hue = if delta <= f32::EPSILON {
+        0.0
+    } else if max == rgb.x {
+        60.0 * ((rgb.y - rgb.z) / delta).rem_euclid(6.0)
+    } else if max == rgb.y {
+        60.0 * ((rgb.z - rgb.x) / delta + 2.0)
+    } else {
+        60.0 * ((rgb.x - rgb.y) / delta + 4.0)
+    };
+    let saturation = if max <= f32::EPSILON {
+        0.0
+    } else {
+        delta / max
+    };
+
+    vec3(hue, saturation, max)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_srgb_round_trip() {
+        for &value in &[0.0, 0.002, 0.2, 0.5, 1.0] {
+            let round_trip = linear_to_srgb(srgb_to_linear(value));
+            assert!((round_trip - value).abs() < 1e-5, "{}", value);
+        }
+        assert!((srgb_to_linear(0.5) - 0.214).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_rgb_to_hsv() {
+        assert_eq!(rgb_to_hsv(vec3(1.0, 0.0, 0.0)), vec3(0.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv(vec3(0.0, 1.0, 0.0)), vec3(120.0, 1.0, 1.0));
+        assert_eq!(rgb_to_hsv(vec3(0.0, 0.0, 0.5)), vec3(240.0, 1.0, 0.5));
+        assert_eq!(rgb_to_hsv(vec3(0.5, 0.5, 0.5)), vec3(0.0, 0.0, 0.5));
+    }
+}
@@ -0,0 +1,87 @@
+use ::ash::vk;
+
+use crate::{math::projections, ui::primitives::Rect, Mat4};
+
+/// Which way is up for coordinates in pixels.
+///
+/// The UI always uses [`CoordinateSystem::YDown`], which matches the screen:
+/// the origin is the top left corner and positive Y points down. Sketches
+/// which prefer math-style coordinates can use [`CoordinateSystem::YUp`]
+/// instead, where the origin is the bottom left corner and positive Y points
+/// up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum CoordinateSystem {
+    /// The origin is the top left corner of the screen, Y points down.
+    #[default]
+    YDown,
+
+    /// The origin is the bottom left corner of the screen, Y points up.
+    YUp,
+}
+
+impl CoordinateSystem {
+    /// Build a projection which maps pixel coordinates in this coordinate
+    /// system to the Vulkan view volume for a render target with the given
+    /// size.
+    pub fn pixel_projection(self, width: f32, height: f32) -> Mat4 {
+        match self {
+            Self::YDown => {
+                projections::ortho(0.0, width, height, 0.0, 0.0, 1.0)
+            }
+            Self::YUp => projections::ortho(0.0, width, 0.0, height, 0.0, 1.0),
+        }
+    }
+
+    /// The front face for pipelines which cull, chosen so triangles wound
+    /// counter-clockwise in this coordinate system are front facing.
+    ///
+    /// Flipping Y mirrors every triangle on screen, so the same triangle is
+    /// wound the opposite way in the other coordinate system.
+    pub fn front_face(self) -> vk::FrontFace {
+        match self {
+            Self::YDown => vk::FrontFace::CLOCKWISE,
+            Self::YUp => vk::FrontFace::COUNTER_CLOCKWISE,
+        }
+    }
+
+    /// Convert a rect in this coordinate system's pixels into framebuffer
+    /// pixels, which are always Y down. A rect's top is its smallest Y, so
+    /// in the Y up coordinate system the top is the lowest edge on screen.
+    pub fn to_framebuffer(self, rect: Rect, framebuffer_height: f32) -> Rect {
+        match self {
+            Self::YDown => rect,
+            Self::YUp => Rect::new(
+                framebuffer_height - rect.bottom(),
+                rect.left(),
+                framebuffer_height - rect.top(),
+                rect.right(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::vec4;
+
+    #[test]
+    fn test_y_up_flips_projection_and_clip_rects() {
+        let y_up = CoordinateSystem::YUp;
+        let bottom_left =
+            y_up.pixel_projection(100.0, 50.0) * vec4(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(bottom_left, vec4(-1.0, 1.0, 0.0, 1.0));
+
+        let top_left = CoordinateSystem::YDown.pixel_projection(100.0, 50.0)
+            * vec4(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(top_left, vec4(-1.0, -1.0, 0.0, 1.0));
+
+        let rect = Rect::new(10.0, 5.0, 20.0, 15.0);
+        assert_eq!(
+            y_up.to_framebuffer(rect, 50.0),
+            Rect::new(30.0, 5.0, 40.0, 15.0)
+        );
+        assert_eq!(CoordinateSystem::YDown.to_framebuffer(rect, 50.0), rect);
+    }
+}
@@ -0,0 +1,73 @@
+use ::ash::vk;
+
+use crate::math::CoordinateSystem;
+
+/// Which triangles are drawn, based on the order of their vertices on
+/// screen.
+///
+/// Winding is judged in a [`CoordinateSystem`], so the same geometry keeps
+/// its front faces when Y is flipped. Geometry which is generated with the
+/// opposite winding, like imported SVG paths or mirrored transforms, can
+/// use [`Culling::DrawClockwise`] instead of being rebuilt.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Culling {
+    /// Every triangle is drawn, whichever way it's wound.
+    #[default]
+    DoubleSided,
+
+    /// Only triangles wound counter-clockwise are drawn.
+    DrawCounterClockwise,
+
+    /// Only triangles wound clockwise are drawn.
+    DrawClockwise,
+}
+
+impl Culling {
+    /// Which faces a pipeline discards.
+    pub fn cull_mode(self) -> vk::CullModeFlags {
+        match self {
+            Self::DoubleSided => vk::CullModeFlags::NONE,
+            Self::DrawCounterClockwise | Self::DrawClockwise => {
+                vk::CullModeFlags::BACK
+            }
+        }
+    }
+
+    /// The winding, in framebuffer coordinates, of the faces which are
+    /// drawn.
+    pub fn front_face(
+        self,
+        coordinate_system: CoordinateSystem,
+    ) -> vk::FrontFace {
+        let counter_clockwise = coordinate_system.front_face();
+        match self {
+            Self::DoubleSided | Self::DrawCounterClockwise => counter_clockwise,
+            Self::DrawClockwise => match counter_clockwise {
+                vk::FrontFace::CLOCKWISE => vk::FrontFace::COUNTER_CLOCKWISE,
+                _ => vk::FrontFace::CLOCKWISE,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_clockwise_culling_flips_the_front_face() {
+        for coordinate_system in
+            [CoordinateSystem::YDown, CoordinateSystem::YUp]
+        {
+            assert_eq!(
+                Culling::DrawCounterClockwise.front_face(coordinate_system),
+                coordinate_system.front_face()
+            );
+            assert_ne!(
+                Culling::DrawClockwise.front_face(coordinate_system),
+                coordinate_system.front_face()
+            );
+        }
+        assert_eq!(Culling::DoubleSided.cull_mode(), vk::CullModeFlags::NONE);
+    }
+}
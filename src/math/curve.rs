@@ -0,0 +1,201 @@
+/// How a curve moves from one point to the next.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Interpolation {
+    /// Hold the point's value until the next point.
+    Constant,
+
+    /// Move in a straight line to the next point.
+    Linear,
+
+    /// Ease out of the point and into the next point.
+    Smooth,
+}
+
+impl Default for Interpolation {
+    /// Defaults to Linear
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Interpolation {
+    /// The next interpolation mode, wrapping back around to the first.
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Constant => Self::Linear,
+            Self::Linear => Self::Smooth,
+            Self::Smooth => Self::Constant,
+        }
+    }
+}
+
+/// A single control point on a [`Curve`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CurvePoint {
+    pub x: f32,
+    pub y: f32,
+
+    /// The interpolation used between this point and the next one.
+    pub interpolation: Interpolation,
+}
+
+impl CurvePoint {
+    pub fn new(x: f32, y: f32, interpolation: Interpolation) -> Self {
+        Self {
+            x,
+            y,
+            interpolation,
+        }
+    }
+}
+
+/// A 1D curve defined by control points. Curves are useful for easing
+/// functions and for any other tweakable mapping from one value to another.
+///
+/// Points are always kept sorted by their x value.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Curve {
+    points: Vec<CurvePoint>,
+}
+
+impl Curve {
+    /// Create a curve without any points. Empty curves always evaluate to 0.
+    pub fn new() -> Self {
+        Self { points: vec![] }
+    }
+
+    /// Create a straight line from (0, 0) to (1, 1).
+    pub fn linear() -> Self {
+        Self::from_points(vec![
+            CurvePoint::new(0.0, 0.0, Interpolation::Linear),
+            CurvePoint::new(1.0, 1.0, Interpolation::Linear),
+        ])
+    }
+
+    /// Create a curve from any set of points.
+    pub fn from_points(mut points: Vec<CurvePoint>) -> Self {
+        points.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self { points }
+    }
+
+    /// The curve's points, sorted by x.
+    pub fn points(&self) -> &[CurvePoint] {
+        &self.points
+    }
+
+    /// Add a point to the curve and return its index.
+    pub fn insert(&mut self, point: CurvePoint) -> usize {
+        let index = self.points.partition_point(|p| p.x <= point.x);
+        self.points.insert(index, point);
+        index
+    }
+
+    /// Remove the point at the given index.
+    pub fn remove(&mut self, index: usize) -> Option<CurvePoint> {
+        if index < self.points.len() {
+            Some(self.points.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Move the point at the given index. Points are re-sorted, so the point's
+    /// new index is returned.
+    pub fn move_point(
+        &mut self,
+        index: usize,
+        x: f32,
+        y: f32,
+    ) -> Option<usize> {
+        let mut point = self.remove(index)?;
+        point.x = x;
+        point.y = y;
+        Some(self.insert(point))
+    }
+
+    /// Change the interpolation for the segment which starts at the point
+    /// with the given index.
+    pub fn set_interpolation(
+        &mut self,
+        index: usize,
+        interpolation: Interpolation,
+    ) {
+        if let Some(point) = self.points.get_mut(index) {
+            point.interpolation = interpolation;
+        }
+    }
+
+    /// Evaluate the curve at x. Values before the first point or after the
+    /// last point are held constant.
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0.0,
+        };
+        if x <= first.x {
+            return first.y;
+        }
+        if x >= last.x {
+            return last.y;
+        }
+
+        let next_index = self.points.partition_point(|p| p.x <= x);
+        let start = &self.points[next_index - 1];
+        let end = &self.points[next_index];
+        let width = end.x - start.x;
+        if width <= f32::EPSILON {
+            return end.y;
+        }
+
+        let t = (x - start.x) / width;
+        let eased = match start.interpolation {
+            Interpolation::Constant => 0.0,
+            Interpolation::Linear => t,
+            Interpolation::Smooth => t * t * (3.0 - 2.0 * t),
+        };
+        start.y + (end.y - start.y) * eased
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_empty_curve_is_zero() {
+        assert_eq!(Curve::new().evaluate(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_evaluate_holds_ends() {
+        let curve = Curve::linear();
+        assert_eq!(curve.evaluate(-1.0), 0.0);
+        assert_eq!(curve.evaluate(2.0), 1.0);
+    }
+
+    #[test]
+    fn test_evaluate_interpolation() {
+        let mut curve = Curve::linear();
+        assert_eq!(curve.evaluate(0.25), 0.25);
+
+        curve.set_interpolation(0, Interpolation::Constant);
+        assert_eq!(curve.evaluate(0.75), 0.0);
+
+        curve.set_interpolation(0, Interpolation::Smooth);
+        assert_eq!(curve.evaluate(0.5), 0.5);
+        assert!(curve.evaluate(0.1) < 0.1);
+    }
+
+    #[test]
+    fn test_points_stay_sorted() {
+        let mut curve = Curve::linear();
+        let index =
+            curve.insert(CurvePoint::new(0.5, 2.0, Interpolation::Linear));
+        assert_eq!(index, 1);
+        assert_eq!(curve.evaluate(0.5), 2.0);
+
+        let moved = curve.move_point(1, 2.0, 0.0);
+        assert_eq!(moved, Some(2));
+        assert_eq!(curve.points()[2].x, 2.0);
+    }
+}
@@ -0,0 +1,36 @@
+use ::ash::vk;
+
+/// How depth values are stored in the depth buffer.
+///
+/// Floating point depth buffers have the most precision near 0, so
+/// [`DepthMode::Reversed`] stores the near plane at 1 and the far plane at 0
+/// to spread precision evenly across the whole depth range. Pipelines, the
+/// projection, and the depth clear value must all agree on the mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DepthMode {
+    /// Near is 0, far is 1, and closer fragments have smaller depth.
+    #[default]
+    Standard,
+
+    /// Near is 1, far is 0, and closer fragments have larger depth. Use
+    /// [`super::projections::reverse_depth`] on the projection.
+    Reversed,
+}
+
+impl DepthMode {
+    /// The value the depth buffer is cleared to, the farthest depth.
+    pub fn clear_depth(self) -> f32 {
+        match self {
+            Self::Standard => 1.0,
+            Self::Reversed => 0.0,
+        }
+    }
+
+    /// The compare op which keeps the closest fragment.
+    pub fn compare_op(self) -> vk::CompareOp {
+        match self {
+            Self::Standard => vk::CompareOp::LESS,
+            Self::Reversed => vk::CompareOp::GREATER,
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use crate::{
+    builder_field,
+    math::{projections, CoordinateSystem},
+    ui::primitives::Rect,
+    vec2, Mat4, Vec2,
+};
+
+/// What to do with the parts of the window which don't match the design
+/// resolution's aspect ratio.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FitMode {
+    /// Show exactly the design area, centered, and leave bars on the sides
+    /// or the top and bottom. The bars show the clear color.
+    #[default]
+    Letterbox,
+
+    /// Fill the window and show more of the scene past the design area's
+    /// edges. The design area is always fully visible and centered.
+    Extend,
+}
+
+/// A DesignResolution lets a sketch work in a fixed size coordinate space no
+/// matter what shape the window is.
+///
+/// The design area is scaled uniformly to fit the framebuffer, so a sketch
+/// looks the same in every window, just bigger or smaller. Coordinates are
+/// design units, with the origin in the corner given by the frame's
+/// [`CoordinateSystem`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DesignResolution {
+    pub width: f32,
+    pub height: f32,
+    pub fit_mode: FitMode,
+}
+
+impl DesignResolution {
+    /// Create a design resolution which letterboxes by default.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            fit_mode: FitMode::Letterbox,
+        }
+    }
+
+    builder_field!(fit_mode, FitMode);
+
+    /// Compute where the design area lands in a framebuffer with the given
+    /// size.
+    pub fn fit(
+        &self,
+        framebuffer_width: f32,
+        framebuffer_height: f32,
+    ) -> DesignViewport {
+        let scale = (framebuffer_width / self.width.max(1.0))
+            .min(framebuffer_height / self.height.max(1.0))
+            .max(f32::EPSILON);
+        match self.fit_mode {
+            FitMode::Letterbox => {
+                let width = (self.width * scale).round();
+                let height = (self.height * scale).round();
+                let left = ((framebuffer_width - width) / 2.0).round();
+                let top = ((framebuffer_height - height) / 2.0).round();
+                DesignViewport {
+                    scale,
+                    viewport: Rect::new(top, left, top + height, left + width),
+                    visible: Rect::new(0.0, 0.0, self.height, self.width),
+                }
+            }
+            FitMode::Extend => {
+                let extra_width = framebuffer_width / scale - self.width;
+                let extra_height = framebuffer_height / scale - self.height;
+                DesignViewport {
+                    scale,
+                    viewport: Rect::new(
+                        0.0,
+                        0.0,
+                        framebuffer_height,
+                        framebuffer_width,
+                    ),
+                    visible: Rect::new(
+                        -extra_height / 2.0,
+                        -extra_width / 2.0,
+                        self.height + extra_height / 2.0,
+                        self.width + extra_width / 2.0,
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// Where a [`DesignResolution`] lands in a particular framebuffer.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DesignViewport {
+    /// Framebuffer pixels per design unit.
+    pub scale: f32,
+
+    /// The part of the framebuffer which is rendered into, in framebuffer
+    /// pixels with Y down.
+    pub viewport: Rect,
+
+    /// The design space which is visible inside the viewport. This is larger
+    /// than the design area when the fit mode is [`FitMode::Extend`].
+    pub visible: Rect,
+}
+
+impl DesignViewport {
+    /// Build a projection which maps design coordinates to the Vulkan view
+    /// volume for the viewport.
+    pub fn projection(&self, coordinate_system: CoordinateSystem) -> Mat4 {
+        let visible = self.visible;
+        match coordinate_system {
+            CoordinateSystem::YDown => projections::ortho(
+                visible.left(),
+                visible.right(),
+                visible.bottom(),
+                visible.top(),
+                0.0,
+                1.0,
+            ),
+            CoordinateSystem::YUp => projections::ortho(
+                visible.left(),
+                visible.right(),
+                visible.top(),
+                visible.bottom(),
+                0.0,
+                1.0,
+            ),
+        }
+    }
+
+    /// Convert a position in framebuffer pixels, like the cursor position,
+    /// into design coordinates.
+    pub fn to_design(
+        &self,
+        pixel: Vec2,
+        coordinate_system: CoordinateSystem,
+    ) -> Vec2 {
+        let x =
+            self.visible.left() + (pixel.x - self.viewport.left()) / self.scale;
+        let y = match coordinate_system {
+            CoordinateSystem::YDown => {
+                self.visible.top()
+                    + (pixel.y - self.viewport.top()) / self.scale
+            }
+            CoordinateSystem::YUp => {
+                self.visible.top()
+                    + (self.viewport.bottom() - pixel.y) / self.scale
+            }
+        };
+        vec2(x, y)
+    }
+
+    /// Convert a rect in design coordinates into framebuffer pixels with Y
+    /// down.
+    pub fn to_framebuffer(
+        &self,
+        rect: Rect,
+        coordinate_system: CoordinateSystem,
+    ) -> Rect {
+        let left = self.viewport.left()
+            + (rect.left() - self.visible.left()) * self.scale;
+        let right = self.viewport.left()
+            + (rect.right() - self.visible.left()) * self.scale;
+        let (top, bottom) = match coordinate_system {
+            CoordinateSystem::YDown => (
+                self.viewport.top()
+                    + (rect.top() - self.visible.top()) * self.scale,
+                self.viewport.top()
+                    + (rect.bottom() - self.visible.top()) * self.scale,
+            ),
+            CoordinateSystem::YUp => (
+                self.viewport.bottom()
+                    - (rect.bottom() - self.visible.top()) * self.scale,
+                self.viewport.bottom()
+                    - (rect.top() - self.visible.top()) * self.scale,
+            ),
+        };
+        Rect::new(top, left, bottom, right)
+    }
+
+    /// True when the framebuffer pixel is inside the viewport, rather than
+    /// in a letterbox bar.
+    pub fn contains(&self, pixel: Vec2) -> bool {
+        self.viewport.contains(pixel)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_letterbox_and_extend_fit_the_design_area() {
+        // a 4:3 design in a 16:9 window is pillarboxed
+        let design = DesignResolution::new(400.0, 300.0);
+        let fit = design.fit(1600.0, 900.0);
+        assert_eq!(fit.scale, 3.0);
+        assert_eq!(fit.viewport, Rect::new(0.0, 200.0, 900.0, 1400.0));
+        assert_eq!(
+            fit.to_design(vec2(200.0, 900.0), CoordinateSystem::YDown),
+            vec2(0.0, 300.0)
+        );
+        assert_eq!(
+            fit.to_design(vec2(200.0, 900.0), CoordinateSystem::YUp),
+            vec2(0.0, 0.0)
+        );
+        assert!(!fit.contains(vec2(100.0, 450.0)));
+
+        // extending shows more of the scene instead of bars
+        let fit = design.fit_mode(FitMode::Extend).fit(1000.0, 300.0);
+        assert_eq!(fit.viewport, Rect::new(0.0, 0.0, 300.0, 1000.0));
+        assert_eq!(fit.visible, Rect::new(0.0, -300.0, 300.0, 700.0));
+        assert_eq!(
+            fit.to_framebuffer(
+                Rect::new(0.0, 0.0, 300.0, 400.0),
+                CoordinateSystem::YUp
+            ),
+            Rect::new(0.0, 300.0, 300.0, 700.0)
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use ::thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ExpressionError {
+    #[error("Unexpected character '{}' at position {}", .0, .1)]
+    UnexpectedCharacter(char, usize),
+
+    #[error("Expected {} at position {}", .0, .1)]
+    Expected(&'static str, usize),
+
+    #[error("Unknown function '{}'", .0)]
+    UnknownFunction(String),
+
+    #[error("{} takes {} arguments, but {} were given", .function, .expected, .found)]
+    WrongArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("Unknown variable '{}'", .0)]
+    UnknownVariable(String),
+
+    #[error("Line {} is not a binding like 'name = expression'", .0)]
+    InvalidBinding(usize),
+
+    #[error("Line {} is not a timeline entry like 'time name value'", .0)]
+    InvalidTimelineEntry(usize),
+}
@@ -0,0 +1,230 @@
+//! A small expression language for binding parameters to values which change
+//! every frame, like `radius = 50 + 20 * sin(t * 2)`.
+//!
+//! Expressions support numbers, variables, `+ - * / % ^`, parentheses, and
+//! the functions sin, cos, tan, abs, sqrt, floor, ceil, fract, min, max,
+//! clamp, mix, step, and smoothstep. `pi` and `tau` are always defined.
+
+mod error;
+mod parse;
+mod timeline;
+
+use std::{collections::HashMap, f32::consts, str::FromStr};
+
+use self::parse::{Node, Operator};
+
+pub use self::{
+    error::ExpressionError,
+    timeline::{ParameterChange, ParameterTimeline},
+};
+
+use crate::Vec2;
+
+/// A parsed expression which can be evaluated any number of times.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            root: parse::parse(source)?,
+        })
+    }
+
+    /// Compute the expression's value with the given variables.
+    pub fn evaluate(
+        &self,
+        variables: &Variables,
+    ) -> Result<f32, ExpressionError> {
+        evaluate(&self.root, variables)
+    }
+}
+
+impl FromStr for Expression {
+    type Err = ExpressionError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::parse(source)
+    }
+}
+
+/// The named values expressions can read.
+///
+/// Nothing is set automatically. A sketch typically sets the time, the
+/// mouse position, and any audio bands or tweak values once per frame
+/// before evaluating its [`Bindings`].
+#[derive(Debug, Clone, Default)]
+pub struct Variables {
+    values: HashMap<String, f32>,
+}
+
+impl Variables {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: f32) {
+        self.values.insert(name.into(), value);
+    }
+
+    /// Set `name_x` and `name_y`, so `set_vec2("mouse", position)` makes
+    /// `mouse_x` and `mouse_y` available.
+    pub fn set_vec2(&mut self, name: &str, value: Vec2) {
+        self.set(format!("{}_x", name), value.x);
+        self.set(format!("{}_y", name), value.y);
+    }
+
+    /// Get a variable's value. The constants are only available to
+    /// expressions.
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.values.get(name).copied()
+    }
+
+    /// Every variable's name and value, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+/// An ordered list of named expressions.
+///
+/// Evaluating the bindings stores each result as a variable, so later
+/// bindings can use earlier ones and the results are read with
+/// [`Variables::get`].
+#[derive(Debug, Clone, Default)]
+pub struct Bindings {
+    bindings: Vec<(String, Expression)>,
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse one binding per line, like `radius = 50 + 20 * sin(t * 2)`.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let mut bindings = Self::new();
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, expression) = line
+                .split_once('=')
+                .ok_or(ExpressionError::InvalidBinding(i + 1))?;
+            let name = name.trim();
+            let is_identifier = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+            if !is_identifier {
+                return Err(ExpressionError::InvalidBinding(i + 1));
+            }
+            bindings = bindings.bind(name, Expression::parse(expression)?);
+        }
+        Ok(bindings)
+    }
+
+    /// Add a binding which is evaluated after every existing binding.
+    pub fn bind(
+        mut self,
+        name: impl Into<String>,
+        expression: Expression,
+    ) -> Self {
+        self.bindings.push((name.into(), expression));
+        self
+    }
+
+    /// Evaluate every binding in order and store the results in the
+    /// variables.
+    pub fn evaluate(
+        &self,
+        variables: &mut Variables,
+    ) -> Result<(), ExpressionError> {
+        for (name, expression) in &self.bindings {
+            let value = expression.evaluate(variables)?;
+            variables.set(name.as_str(), value);
+        }
+        Ok(())
+    }
+}
+
+fn evaluate(
+    node: &Node,
+    variables: &Variables,
+) -> Result<f32, ExpressionError> {
+    Ok(match node {
+        Node::Number(number) => *number,
+        Node::Variable(name) => match (variables.get(name), name.as_str()) {
+            (Some(value), _) => value,
+            (None, "pi") => consts::PI,
+            (None, "tau") => consts::TAU,
+            (None, _) => {
+                return Err(ExpressionError::UnknownVariable(name.clone()))
+            }
+        },
+        Node::Negate(node) => -evaluate(node, variables)?,
+        Node::Binary(op, lhs, rhs) => {
+            let lhs = evaluate(lhs, variables)?;
+            let rhs = evaluate(rhs, variables)?;
+            match op {
+                Operator::Add => lhs + rhs,
+                Operator::Subtract => lhs - rhs,
+                Operator::Multiply => lhs * rhs,
+                Operator::Divide => lhs / rhs,
+                Operator::Remainder => lhs.rem_euclid(rhs),
+                Operator::Power => lhs.powf(rhs),
+            }
+        }
+        Node::Call(function, args) => {
+            let mut values = [0.0; 3];
+            for (value, arg) in values.iter_mut().zip(args) {
+                *value = evaluate(arg, variables)?;
+            }
+            function.apply(&values[..args.len()])
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::vec2;
+
+    #[test]
+    fn test_bindings_are_evaluated_in_order() {
+        let bindings = Bindings::parse(
+            "
+            # a circle which pulses and follows the mouse
+            radius = 50 + 20 * sin(t * pi)
+            x = mouse_x + radius
+            ",
+        )
+        .unwrap();
+
+        let mut variables = Variables::new();
+        variables.set("t", 0.5);
+        variables.set_vec2("mouse", vec2(10.0, 20.0));
+        bindings.evaluate(&mut variables).unwrap();
+        assert_eq!(variables.get("radius"), Some(70.0));
+        assert_eq!(variables.get("x"), Some(80.0));
+
+        assert_eq!(
+            Bindings::parse("radius 50").unwrap_err(),
+            ExpressionError::InvalidBinding(1)
+        );
+        assert_eq!(
+            Expression::parse("band_3 * 2")
+                .unwrap()
+                .evaluate(&variables),
+            Err(ExpressionError::UnknownVariable("band_3".to_owned()))
+        );
+    }
+}
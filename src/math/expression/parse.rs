@@ -0,0 +1,371 @@
+use super::ExpressionError;
+
+/// A parsed expression tree.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Node {
+    Number(f32),
+    Variable(String),
+    Negate(Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+    Power,
+}
+
+/// The built-in functions expressions can call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) enum Function {
+    Sin,
+    Cos,
+    Tan,
+    Abs,
+    Sqrt,
+    Floor,
+    Ceil,
+    Fract,
+    Min,
+    Max,
+    Clamp,
+    Mix,
+    Step,
+    Smoothstep,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "abs" => Self::Abs,
+            "sqrt" => Self::Sqrt,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "fract" => Self::Fract,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "clamp" => Self::Clamp,
+            "mix" => Self::Mix,
+            "step" => Self::Step,
+            "smoothstep" => Self::Smoothstep,
+            _ => return None,
+        })
+    }
+
+    /// The number of arguments the function takes.
+    fn arity(&self) -> usize {
+        match self {
+            Self::Sin
+            | Self::Cos
+            | Self::Tan
+            | Self::Abs
+            | Self::Sqrt
+            | Self::Floor
+            | Self::Ceil
+            | Self::Fract => 1,
+            Self::Min | Self::Max | Self::Step => 2,
+            Self::Clamp | Self::Mix | Self::Smoothstep => 3,
+        }
+    }
+
+    /// Apply the function. The number of arguments was checked when the
+    /// expression was parsed.
+    pub(super) fn apply(&self, args: &[f32]) -> f32 {
+        match self {
+            Self::Sin => args[0].sin(),
+            Self::Cos => args[0].cos(),
+            Self::Tan => args[0].tan(),
+            Self::Abs => args[0].abs(),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Floor => args[0].floor(),
+            Self::Ceil => args[0].ceil(),
+            Self::Fract => args[0] - args[0].floor(),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            Self::Clamp => args[0].max(args[1]).min(args[2]),
+            Self::Mix => args[0] + (args[1] - args[0]) * args[2],
+            Self::Step => {
+                if args[1] < args[0] {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+            Self::Smoothstep => {
+                let range = args[1] - args[0];
+                let t = if range == 0.0 {
+                    Self::Step.apply(&[args[0], args[2]])
+                } else {
+                    ((args[2] - args[0]) / range).clamp(0.0, 1.0)
+                };
+                t * t * (3.0 - 2.0 * t)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f32),
+    Identifier(String),
+    Operator(Operator),
+    Open,
+    Close,
+    Comma,
+}
+
+/// Parse a complete expression.
+pub(super) fn parse(source: &str) -> Result<Node, ExpressionError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        next: 0,
+        end: source.len(),
+    };
+    let node = parser.sum()?;
+    match parser.tokens.get(parser.next) {
+        Some(&(_, position)) => {
+            Err(ExpressionError::Expected("an operator", position))
+        }
+        None => Ok(node),
+    }
+}
+
+/// Split the source into tokens, each paired with the byte position it
+/// starts at.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let mut tokens = vec![];
+    let mut chars = source.char_indices().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        let token = match c {
+            c if c.is_whitespace() => {
+                chars.next();
+                continue;
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || c == '.') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let number = source[position..end].parse().map_err(|_| {
+                    ExpressionError::UnexpectedCharacter(c, position)
+                })?;
+                tokens.push((Token::Number(number), position));
+                continue;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = position;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let name = source[position..end].to_owned();
+                tokens.push((Token::Identifier(name), position));
+                continue;
+            }
+            '+' => Token::Operator(Operator::Add),
+            '-' => Token::Operator(Operator::Subtract),
+            '*' => Token::Operator(Operator::Multiply),
+            '/' => Token::Operator(Operator::Divide),
+            '%' => Token::Operator(Operator::Remainder),
+            '^' => Token::Operator(Operator::Power),
+            '(' => Token::Open,
+            ')' => Token::Close,
+            ',' => Token::Comma,
+            _ => return Err(ExpressionError::UnexpectedCharacter(c, position)),
+        };
+        tokens.push((token, position));
+        chars.next();
+    }
+    Ok(tokens)
+}
+
+/// A recursive descent parser. Each method parses one level of precedence,
+/// from lowest to highest: sums, products, negation, and powers.
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+
+    /// The position reported for errors at the end of the source.
+    end: usize,
+}
+
+impl Parser {
+    fn sum(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.product()?;
+        while let Some(op @ (Operator::Add | Operator::Subtract)) =
+            self.peek_operator()
+        {
+            self.next += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.product()?));
+        }
+        Ok(node)
+    }
+
+    fn product(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.unary()?;
+        while let Some(
+            op @ (Operator::Multiply | Operator::Divide | Operator::Remainder),
+        ) = self.peek_operator()
+        {
+            self.next += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.peek_operator() == Some(Operator::Subtract) {
+            self.next += 1;
+            return Ok(Node::Negate(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, ExpressionError> {
+        let base = self.atom()?;
+        if self.peek_operator() == Some(Operator::Power) {
+            self.next += 1;
+            // powers are right associative and bind tighter than negation
+            // on their left, so -2^2 is -4
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                Operator::Power,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, ExpressionError> {
+        let (token, position) = match self.tokens.get(self.next) {
+            Some((token, position)) => (token.clone(), *position),
+            None => return Err(ExpressionError::Expected("a value", self.end)),
+        };
+        self.next += 1;
+        match token {
+            Token::Number(number) => Ok(Node::Number(number)),
+            Token::Identifier(name) => {
+                if self.peek() != Some(&Token::Open) {
+                    return Ok(Node::Variable(name));
+                }
+                self.next += 1;
+                let function = Function::from_name(&name)
+                    .ok_or(ExpressionError::UnknownFunction(name.clone()))?;
+                let args = self.arguments()?;
+                if args.len() != function.arity() {
+                    return Err(ExpressionError::WrongArgumentCount {
+                        function: name,
+                        expected: function.arity(),
+                        found: args.len(),
+                    });
+                }
+                Ok(Node::Call(function, args))
+            }
+            Token::Open => {
+                let node = self.sum()?;
+                self.expect(Token::Close, "')'")?;
+                Ok(node)
+            }
+            _ => Err(ExpressionError::Expected("a value", position)),
+        }
+    }
+
+    /// Parse a comma separated argument list. The opening parenthesis was
+    /// already consumed.
+    fn arguments(&mut self) -> Result<Vec<Node>, ExpressionError> {
+        let mut args = vec![];
+        if self.peek() == Some(&Token::Close) {
+            self.next += 1;
+            return Ok(args);
+        }
+        loop {
+            args.push(self.sum()?);
+            if self.peek() == Some(&Token::Comma) {
+                self.next += 1;
+            } else {
+                self.expect(Token::Close, "',' or ')'")?;
+                return Ok(args);
+            }
+        }
+    }
+
+    fn expect(
+        &mut self,
+        token: Token,
+        description: &'static str,
+    ) -> Result<(), ExpressionError> {
+        match self.tokens.get(self.next) {
+            Some((next, _)) if *next == token => {
+                self.next += 1;
+                Ok(())
+            }
+            Some(&(_, position)) => {
+                Err(ExpressionError::Expected(description, position))
+            }
+            None => Err(ExpressionError::Expected(description, self.end)),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    fn peek_operator(&self) -> Option<Operator> {
+        match self.peek() {
+            Some(Token::Operator(op)) => Some(*op),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_operators_follow_precedence() {
+        use self::{Node::*, Operator::*};
+
+        let number = |n| Box::new(Number(n));
+        assert_eq!(
+            parse("1 + 2 * -x ^ 2").unwrap(),
+            Binary(
+                Add,
+                number(1.0),
+                Box::new(Binary(
+                    Multiply,
+                    number(2.0),
+                    Box::new(Negate(Box::new(Binary(
+                        Power,
+                        Box::new(Variable("x".to_owned())),
+                        number(2.0)
+                    ))))
+                ))
+            )
+        );
+        assert_eq!(
+            parse("max(1, 2").unwrap_err(),
+            ExpressionError::Expected("',' or ')'", 8)
+        );
+        assert_eq!(
+            parse("noise(1)").unwrap_err(),
+            ExpressionError::UnknownFunction("noise".to_owned())
+        );
+    }
+}
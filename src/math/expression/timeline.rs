@@ -0,0 +1,144 @@
+use std::{collections::HashMap, fmt::Write};
+
+use super::{ExpressionError, Variables};
+
+/// A single recorded change to a parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterChange {
+    /// Seconds since the recording started.
+    pub time: f32,
+    pub name: String,
+    pub value: f32,
+}
+
+/// A ParameterTimeline records how variables change during a live session so
+/// the same automation can be replayed later, e.g. for a final render with a
+/// fixed timestep.
+///
+/// Only changes are stored, so recording every variable every frame is cheap
+/// when most of them hold still. Camera state can be recorded the same way
+/// by keeping it in variables, see [`Variables::set_vec2`].
+#[derive(Debug, Clone, Default)]
+pub struct ParameterTimeline {
+    /// Every change, sorted by time.
+    changes: Vec<ParameterChange>,
+
+    /// The last recorded value for each parameter.
+    latest: HashMap<String, f32>,
+}
+
+impl ParameterTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a parameter's value. Nothing is stored when the value didn't
+    /// change since it was last recorded.
+    ///
+    /// Changes should be recorded in time order, a change with an earlier
+    /// time is still kept sorted but it's compared with the value which was
+    /// recorded last.
+    pub fn record(&mut self, time: f32, name: impl Into<String>, value: f32) {
+        let name = name.into();
+        if self.latest.get(&name) == Some(&value) {
+            return;
+        }
+        self.latest.insert(name.clone(), value);
+        let index = self.changes.partition_point(|change| change.time <= time);
+        self.changes
+            .insert(index, ParameterChange { time, name, value });
+    }
+
+    /// Record the current value of every variable.
+    pub fn record_variables(&mut self, time: f32, variables: &Variables) {
+        let mut names: Vec<_> = variables.iter().collect();
+        // keep the recording deterministic
+        names.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in names {
+            self.record(time, name, value);
+        }
+    }
+
+    /// Set every recorded parameter to the value it had at the given time.
+    /// Parameters which hadn't been recorded yet are left alone.
+    pub fn apply(&self, time: f32, variables: &mut Variables) {
+        let end = self.changes.partition_point(|change| change.time <= time);
+        for change in &self.changes[..end] {
+            variables.set(change.name.as_str(), change.value);
+        }
+    }
+
+    /// Every change, sorted by time.
+    pub fn changes(&self) -> &[ParameterChange] {
+        &self.changes
+    }
+
+    /// The time of the last change.
+    pub fn duration(&self) -> f32 {
+        self.changes.last().map_or(0.0, |change| change.time)
+    }
+
+    /// Write the timeline as text with one `time name value` line per change.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for change in &self.changes {
+            writeln!(text, "{} {} {}", change.time, change.name, change.value)
+                .unwrap();
+        }
+        text
+    }
+
+    /// Read a timeline written by [`Self::to_text`].
+    pub fn from_text(text: &str) -> Result<Self, ExpressionError> {
+        let mut timeline = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let time = parts.next().and_then(|time| time.parse().ok());
+            let name = parts.next();
+            let value = parts.next().and_then(|value| value.parse().ok());
+            match (time, name, value, parts.next()) {
+                (Some(time), Some(name), Some(value), None) => {
+                    timeline.record(time, name, value)
+                }
+                _ => return Err(ExpressionError::InvalidTimelineEntry(i + 1)),
+            }
+        }
+        Ok(timeline)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_replay_matches_the_recorded_values() {
+        let mut variables = Variables::new();
+        let mut timeline = ParameterTimeline::new();
+        for (time, radius) in [(0.0, 10.0), (0.5, 10.0), (1.0, 20.0)] {
+            variables.set("radius", radius);
+            variables.set("speed", 2.0);
+            timeline.record_variables(time, &variables);
+        }
+        // unchanged values aren't stored again
+        assert_eq!(timeline.changes().len(), 3);
+        assert_eq!(timeline.duration(), 1.0);
+
+        let timeline =
+            ParameterTimeline::from_text(&timeline.to_text()).unwrap();
+        let mut replayed = Variables::new();
+        timeline.apply(0.75, &mut replayed);
+        assert_eq!(replayed.get("radius"), Some(10.0));
+        assert_eq!(replayed.get("speed"), Some(2.0));
+        timeline.apply(1.0, &mut replayed);
+        assert_eq!(replayed.get("radius"), Some(20.0));
+
+        assert_eq!(
+            ParameterTimeline::from_text("0.5 radius").unwrap_err(),
+            ExpressionError::InvalidTimelineEntry(1)
+        );
+    }
+}
@@ -1,3 +1,20 @@
+pub mod color;
+pub mod expression;
+
+mod coordinate_system;
+mod culling;
+mod curve;
+mod depth_mode;
+mod design_resolution;
+
+pub use self::{
+    coordinate_system::CoordinateSystem,
+    culling::Culling,
+    curve::{Curve, CurvePoint, Interpolation},
+    depth_mode::DepthMode,
+    design_resolution::{DesignResolution, DesignViewport, FitMode},
+};
+
 pub mod projections {
     use crate::Mat4;
 
@@ -35,4 +52,75 @@ pub mod projections {
             0.0, 0.0, 0.0, 1.0,
         )
     }
+
+    /// Build a perspective projection matrix for a right handed view space
+    /// which looks down negative Z. Depth goes from 0 at the near plane to 1
+    /// at the far plane, and Y is flipped to match Vulkan.
+    pub fn perspective(
+        fov_y_radians: f32,
+        aspect_ratio: f32,
+        near: f32,
+        far: f32,
+    ) -> Mat4 {
+        let f = 1.0 / (0.5 * fov_y_radians).tan();
+        let mz = far / (near - far);
+        let bz = near * far / (near - far);
+        Mat4::new(
+            f / aspect_ratio,
+            0.0,
+            0.0,
+            0.0, //
+            0.0,
+            -f,
+            0.0,
+            0.0, //
+            0.0,
+            0.0,
+            mz,
+            bz, //
+            0.0,
+            0.0,
+            -1.0,
+            0.0,
+        )
+    }
+
+    /// Flip a projection's depth so the near plane is at 1 and the far plane
+    /// is at 0, for use with [`crate::math::DepthMode::Reversed`].
+    pub fn reverse_depth(projection: Mat4) -> Mat4 {
+        // z' = w - z, which maps depth d to 1 - d after the divide by w
+        let flip = Mat4::new(
+            1.0, 0.0, 0.0, 0.0, //
+            0.0, 1.0, 0.0, 0.0, //
+            0.0, 0.0, -1.0, 1.0, //
+            0.0, 0.0, 0.0, 1.0,
+        );
+        flip * projection
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+
+        use crate::vec4;
+
+        #[test]
+        fn test_reversed_depth_puts_near_at_one() {
+            let depth = |projection: Mat4, z: f32| {
+                let clip = projection * vec4(0.0, 0.0, z, 1.0);
+                clip.z / clip.w
+            };
+            let ortho = ortho(-1.0, 1.0, -1.0, 1.0, 0.0, 10.0);
+            assert_eq!(depth(reverse_depth(ortho), 0.0), 1.0);
+            assert_eq!(depth(reverse_depth(ortho), 10.0), 0.0);
+
+            let perspective = perspective(1.0, 1.0, 0.5, 100.0);
+            assert!((depth(perspective, -0.5)).abs() < 1e-6);
+            assert!((depth(perspective, -100.0) - 1.0).abs() < 1e-6);
+            assert!(
+                (depth(reverse_depth(perspective), -0.5) - 1.0).abs() < 1e-6
+            );
+            assert!(depth(reverse_depth(perspective), -100.0).abs() < 1e-6);
+        }
+    }
 }
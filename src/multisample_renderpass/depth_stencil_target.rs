@@ -6,6 +6,7 @@ use crate::{
     },
     vulkan::{
         errors::VulkanError, Image, ImageView, MemoryAllocator, RenderDevice,
+        VulkanDebug,
     },
 };
 
@@ -57,6 +58,11 @@ impl MultisampleRenderpass {
             )
             .map_err(VulkanError::ImageError)?,
         );
+        view.image
+            .set_debug_name("MSAA Depth Stencil Target")
+            .map_err(VulkanError::VulkanDebugError)?;
+        view.set_debug_name("MSAA Depth Stencil Target - View")
+            .map_err(VulkanError::VulkanDebugError)?;
         Ok(view)
     }
 
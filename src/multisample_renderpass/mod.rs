@@ -33,15 +33,33 @@ pub struct MultisampleRenderpass {
 
 impl MultisampleRenderpass {
     /// Create a new multisampled renderpass based on the swapchain's current
-    /// extent and format.
+    /// extent and format. Uses 4xMSAA when the device supports it.
     pub fn for_current_swapchain(
         vk_dev: Arc<RenderDevice>,
         vk_alloc: Arc<dyn MemoryAllocator>,
+    ) -> Result<Self, MultisampleRenderpassError> {
+        Self::for_current_swapchain_with_samples(
+            vk_dev,
+            vk_alloc,
+            vk::SampleCountFlags::TYPE_4,
+        )
+    }
+
+    /// Create a new multisampled renderpass based on the swapchain's current
+    /// extent and format.
+    ///
+    /// The sample count is the smaller of `desired_samples` and the max
+    /// supported by the device.
+    pub fn for_current_swapchain_with_samples(
+        vk_dev: Arc<RenderDevice>,
+        vk_alloc: Arc<dyn MemoryAllocator>,
+        desired_samples: vk::SampleCountFlags,
     ) -> Result<Self, MultisampleRenderpassError> {
         let msaa_render_target =
             MultisampleRenderpass::create_msaa_render_target(
                 vk_dev.clone(),
                 vk_alloc.clone(),
+                desired_samples,
             )?;
         let depth_stencil_target = MultisampleRenderpass::create_depth_target(
             &msaa_render_target,
@@ -149,6 +167,18 @@ impl VulkanDebug for MultisampleRenderpass {
         let name = debug_name.into();
         self.render_pass
             .set_debug_name(format!("MSAARenderPass - {}", name))?;
+        self.msaa_render_target
+            .image
+            .set_debug_name(format!("{} - MSAA Color Target", name))?;
+        self.msaa_render_target
+            .set_debug_name(format!("{} - MSAA Color Target - View", name))?;
+        self.depth_stencil_target
+            .image
+            .set_debug_name(format!("{} - MSAA Depth Stencil Target", name))?;
+        self.depth_stencil_target.set_debug_name(format!(
+            "{} - MSAA Depth Stencil Target - View",
+            name
+        ))?;
         Ok(())
     }
 }
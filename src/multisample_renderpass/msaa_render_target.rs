@@ -4,20 +4,21 @@ use crate::{
     multisample_renderpass::MultisampleRenderpass,
     vulkan::{
         errors::VulkanError, Image, ImageView, MemoryAllocator, RenderDevice,
+        VulkanDebug,
     },
 };
 
 impl MultisampleRenderpass {
     /// Create a multisample render target based on the swapchain's current
-    /// extent and parameters.
+    /// extent and parameters. The sample count is the `desired_samples`, or
+    /// the device's max, whichever is smaller.
     pub(super) fn create_msaa_render_target(
         vk_dev: Arc<RenderDevice>,
         vk_alloc: Arc<dyn MemoryAllocator>,
+        desired_samples: vk::SampleCountFlags,
     ) -> Result<Arc<ImageView>, VulkanError> {
-        let samples = Self::pick_max_supported_msaa_count(
-            &vk_dev,
-            vk::SampleCountFlags::TYPE_4,
-        );
+        let samples =
+            Self::pick_max_supported_msaa_count(&vk_dev, desired_samples);
         let (swap_extent, format) =
             vk_dev.with_swapchain(|swap| (swap.extent, swap.format));
         let create_info = vk::ImageCreateInfo {
@@ -50,6 +51,8 @@ impl MultisampleRenderpass {
             format,
             vk::ImageAspectFlags::COLOR,
         )?);
+        view.image.set_debug_name("MSAA Color Target")?;
+        view.set_debug_name("MSAA Color Target - View")?;
         Ok(view)
     }
 
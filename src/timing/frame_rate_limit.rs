@@ -8,10 +8,20 @@ use std::{
 /// it can cause unreasonably high frame-rates and therefore unexpectedly high
 /// CPU/GPU utilization. To prevent this, a frame rate limit can be imposed
 /// which just sleeps or yields for a bit of time each frame.
+///
+/// The limit can also follow the display's refresh rate, see
+/// [`Self::match_refresh_rate`], so animations don't judder on high refresh
+/// rate displays.
 pub struct FrameRateLimit {
     frames_to_track: usize,
     frame_starts: VecDeque<Instant>,
     target_duration: Duration,
+
+    /// When set, the target is the refresh rate divided by this value.
+    refresh_rate_divisor: Option<u32>,
+
+    /// The most recent refresh rate given to [`Self::set_refresh_rate`].
+    refresh_rate: Option<u32>,
 }
 
 impl FrameRateLimit {
@@ -21,14 +31,40 @@ impl FrameRateLimit {
             frames_to_track,
             frame_starts: VecDeque::with_capacity(frames_to_track),
             target_duration: Duration::from_secs(1) / target_fps,
+            refresh_rate_divisor: None,
+            refresh_rate: None,
         }
     }
 
-    /// Change the targeted FPS count.
+    /// Change the targeted FPS count. This stops matching the refresh rate.
     pub fn set_target_fps(&mut self, target_fps: u32) {
+        self.refresh_rate_divisor = None;
         self.target_duration = Duration::from_secs(1) / target_fps;
     }
 
+    /// Target the display's refresh rate divided by the divisor, e.g. a
+    /// divisor of 2 runs at 72 fps on a 144hz display. The current target is
+    /// kept until a refresh rate is known.
+    pub fn match_refresh_rate(&mut self, divisor: u32) {
+        self.refresh_rate_divisor = Some(divisor.max(1));
+        self.retarget_refresh_rate();
+    }
+
+    /// Tell the limit the current display's refresh rate. This only changes
+    /// the target when matching the refresh rate.
+    ///
+    /// The demo [`crate::demo::Application`] calls this when it starts and
+    /// each time the window moves.
+    pub fn set_refresh_rate(&mut self, refresh_rate: u32) {
+        self.refresh_rate = Some(refresh_rate);
+        self.retarget_refresh_rate();
+    }
+
+    /// The time each frame is given.
+    pub fn target_duration(&self) -> Duration {
+        self.target_duration
+    }
+
     /// Call at the beginning of each frame to establish the start-point when
     /// computing elapsed time.
     pub fn start_frame(&mut self) {
@@ -54,4 +90,66 @@ impl FrameRateLimit {
         let total_duration = Instant::now() - *oldest_frame;
         return total_duration / self.frame_starts.len() as u32;
     }
+
+    /// The time between the starts of each tracked frame, oldest first.
+    /// There is one less frame time than tracked frames because the current
+    /// frame hasn't finished.
+    pub fn frame_times(&self) -> Vec<Duration> {
+        let starts: Vec<Instant> =
+            self.frame_starts.iter().rev().copied().collect();
+        starts.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+
+    /// Update the target from the refresh rate, if it's being matched and
+    /// it's known.
+    fn retarget_refresh_rate(&mut self) {
+        if let (Some(divisor), Some(refresh_rate)) =
+            (self.refresh_rate_divisor, self.refresh_rate)
+        {
+            if refresh_rate > 0 {
+                self.target_duration =
+                    Duration::from_secs(1) * divisor / refresh_rate;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_target_follows_the_refresh_rate() {
+        let mut limit = FrameRateLimit::new(60, 30);
+        limit.set_refresh_rate(144);
+        assert_eq!(limit.target_duration(), Duration::from_secs(1) / 60);
+
+        limit.match_refresh_rate(2);
+        assert_eq!(limit.target_duration(), Duration::from_secs(1) / 72);
+
+        // moving to another monitor retargets
+        limit.set_refresh_rate(120);
+        assert_eq!(limit.target_duration(), Duration::from_secs(1) / 60);
+
+        limit.set_target_fps(30);
+        limit.set_refresh_rate(240);
+        assert_eq!(limit.target_duration(), Duration::from_secs(1) / 30);
+    }
+
+    #[test]
+    fn test_frame_times_are_oldest_first() {
+        let mut limit = FrameRateLimit::new(60, 30);
+        assert!(limit.frame_times().is_empty());
+
+        let start = Instant::now();
+        for offset_ms in [0, 16, 50] {
+            limit
+                .frame_starts
+                .push_front(start + Duration::from_millis(offset_ms));
+        }
+        assert_eq!(
+            limit.frame_times(),
+            vec![Duration::from_millis(16), Duration::from_millis(34)]
+        );
+    }
 }
@@ -0,0 +1,208 @@
+use ::std::{any::Any, collections::HashMap, time::Duration};
+
+use crate::{ui::Id, Vec2, Vec4};
+
+/// How an animated value moves between its start and end.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Easing {
+    /// Move at a constant speed.
+    Linear,
+
+    /// Start slowly and speed up.
+    EaseIn,
+
+    /// Start quickly and slow down. This feels the most responsive for
+    /// reactions to input, like hovering.
+    EaseOut,
+
+    /// Start and end slowly.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Map progress through an animation, in the range [0, 1], to how far
+    /// the value has moved.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2.0 - t),
+            Self::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// How long an animated value takes to reach a new target, and how it gets
+/// there.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Transition {
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub fn new(duration: Duration, easing: Easing) -> Self {
+        Self { duration, easing }
+    }
+
+    /// A transition which jumps straight to the target.
+    pub fn instant() -> Self {
+        Self::new(Duration::ZERO, Easing::Linear)
+    }
+}
+
+impl Default for Transition {
+    /// Defaults to a quick ease out, good for hover and press feedback.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(100), Easing::EaseOut)
+    }
+}
+
+/// A value which can be animated.
+pub trait Animatable: Copy + PartialEq + 'static {
+    /// Interpolate between self and another value. t is in the range [0, 1].
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animatable for Vec2 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animatable for Vec4 {
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+/// A value moving from one place to another.
+#[derive(Debug, Copy, Clone)]
+struct Tween<T> {
+    from: T,
+    to: T,
+
+    /// The UI time when the tween started.
+    start: Duration,
+
+    transition: Transition,
+}
+
+impl<T: Animatable> Tween<T> {
+    /// How far through the tween the given time is, in the range [0, 1].
+    fn progress(&self, time: Duration) -> f32 {
+        let duration = self.transition.duration.as_secs_f32();
+        if duration <= 0.0 {
+            return 1.0;
+        }
+        let elapsed = time.saturating_sub(self.start).as_secs_f32();
+        (elapsed / duration).min(1.0)
+    }
+
+    fn value_at(&self, time: Duration) -> T {
+        let eased = self.transition.easing.apply(self.progress(time));
+        self.from.lerp(self.to, eased)
+    }
+}
+
+/// Every animated widget property, keyed by the widget's id and the
+/// property's name.
+///
+/// Values are computed from the UI's frame clock, so every widget advances
+/// exactly once per frame no matter how many times it asks for a value.
+#[derive(Default)]
+pub(crate) struct Animations {
+    tweens: HashMap<(Id, &'static str), Box<dyn Any>>,
+
+    /// True when a tween was still moving the last time its value was read.
+    animating: bool,
+}
+
+impl Animations {
+    /// The current value of a property which is moving towards the target.
+    ///
+    /// A property starts at its first target without animating. Each time
+    /// the target changes a new tween starts from the current value, so
+    /// interrupted animations don't jump.
+    pub(crate) fn animate<T: Animatable>(
+        &mut self,
+        id: Id,
+        property: &'static str,
+        target: T,
+        transition: Transition,
+        time: Duration,
+    ) -> T {
+        let tween = self
+            .tweens
+            .get_mut(&(id, property))
+            .and_then(|tween| tween.downcast_mut::<Tween<T>>());
+        let tween = match tween {
+            Some(tween) => tween,
+            None => {
+                let tween = Tween {
+                    from: target,
+                    to: target,
+                    start: time,
+                    transition,
+                };
+                self.tweens.insert((id, property), Box::new(tween));
+                return target;
+            }
+        };
+
+        if tween.to != target {
+            *tween = Tween {
+                from: tween.value_at(time),
+                to: target,
+                start: time,
+                transition,
+            };
+        }
+        if tween.progress(time) < 1.0 {
+            self.animating = true;
+        }
+        tween.value_at(time)
+    }
+
+    /// True when any animation was still moving since the last call.
+    pub(crate) fn take_animating(&mut self) -> bool {
+        std::mem::replace(&mut self.animating, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_changing_the_target_tweens_from_the_current_value() {
+        let id = Id::new(1);
+        let transition =
+            Transition::new(Duration::from_millis(100), Easing::Linear);
+        let at = Duration::from_millis;
+        let mut animations = Animations::default();
+
+        assert_eq!(animations.animate(id, "x", 0.0, transition, at(0)), 0.0);
+        assert!(!animations.take_animating());
+
+        assert_eq!(animations.animate(id, "x", 1.0, transition, at(0)), 0.0);
+        assert_eq!(animations.animate(id, "x", 1.0, transition, at(50)), 0.5);
+        assert!(animations.take_animating());
+
+        // turning back halfway starts from where the value is now
+        assert_eq!(animations.animate(id, "x", 0.0, transition, at(50)), 0.5);
+        assert!(animations.take_animating());
+        assert_eq!(animations.animate(id, "x", 0.0, transition, at(150)), 0.0);
+        assert!(!animations.take_animating());
+
+        // other properties of the same widget are independent
+        assert_eq!(animations.animate(id, "y", 2.0, transition, at(150)), 2.0);
+    }
+}
@@ -0,0 +1,57 @@
+//! Copy and paste for widgets which show text, and for images.
+//!
+//! Widgets read and write the clipboard through the UI's
+//! [`InternalState`](crate::ui::InternalState). By default the UI uses a
+//! [`MemoryClipboard`], so text can only be pasted between the UI's own
+//! widgets. Applications share text with other programs by giving the UI the
+//! window's clipboard, see
+//! [`GlfwWindow::clipboard`](crate::glfw_window::GlfwWindow::clipboard).
+
+use ::image::RgbaImage;
+
+/// Applications implement this trait to connect the UI to a system
+/// clipboard.
+pub trait Clipboard {
+    /// The text on the clipboard, if it has any.
+    fn text(&mut self) -> Option<String>;
+
+    /// Replace the clipboard's contents with the text.
+    fn set_text(&mut self, text: &str);
+
+    /// The image on the clipboard, if it has one. Clipboards which only hold
+    /// text never have an image.
+    fn image(&mut self) -> Option<RgbaImage> {
+        None
+    }
+
+    /// Replace the clipboard's contents with the image. Clipboards which
+    /// only hold text ignore it.
+    fn set_image(&mut self, _image: &RgbaImage) {}
+}
+
+/// A clipboard which only exists inside the application.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryClipboard {
+    text: Option<String>,
+    image: Option<RgbaImage>,
+}
+
+impl Clipboard for MemoryClipboard {
+    fn text(&mut self) -> Option<String> {
+        self.text.clone()
+    }
+
+    fn set_text(&mut self, text: &str) {
+        self.text = Some(text.to_owned());
+        self.image = None;
+    }
+
+    fn image(&mut self) -> Option<RgbaImage> {
+        self.image.clone()
+    }
+
+    fn set_image(&mut self, image: &RgbaImage) {
+        self.image = Some(image.clone());
+        self.text = None;
+    }
+}
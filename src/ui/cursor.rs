@@ -0,0 +1,119 @@
+/// The shape of the mouse cursor, which widgets request while the mouse is
+/// over them so it's clear what a click or drag will do.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    /// The regular arrow, used when no widget asks for anything else.
+    #[default]
+    Arrow,
+
+    /// A pointing hand, for things which can be clicked.
+    Hand,
+
+    /// The text insertion beam, for editable text.
+    IBeam,
+
+    /// A crosshair, for picking exact points.
+    Crosshair,
+
+    /// Arrows pointing left and right, for resizing horizontally.
+    HorizontalResize,
+
+    /// Arrows pointing up and down, for resizing vertically.
+    VerticalResize,
+}
+
+impl CursorShape {
+    /// The GLFW cursor with this shape.
+    pub fn standard_cursor(self) -> glfw::StandardCursor {
+        use glfw::StandardCursor;
+
+        match self {
+            Self::Arrow => StandardCursor::Arrow,
+            Self::Hand => StandardCursor::Hand,
+            Self::IBeam => StandardCursor::IBeam,
+            Self::Crosshair => StandardCursor::Crosshair,
+            Self::HorizontalResize => StandardCursor::HResize,
+            Self::VerticalResize => StandardCursor::VResize,
+        }
+    }
+}
+
+/// CursorRequests collects the shapes widgets ask for while they handle an
+/// event.
+///
+/// Widgets ask on every cursor move while the mouse is over them, like
+/// hovering, so the cursor returns to an arrow after a move which nobody
+/// asked about. Requests during other events, like a press which starts a
+/// drag, change the shape right away. The last request wins, so nested
+/// widgets override their parents.
+#[derive(Debug, Default)]
+pub(crate) struct CursorRequests {
+    shape: CursorShape,
+    requested: Option<CursorShape>,
+}
+
+impl CursorRequests {
+    /// Ask for a cursor shape.
+    pub(crate) fn request(&mut self, shape: CursorShape) {
+        self.requested = Some(shape);
+    }
+
+    /// Apply the requests made while an event was handled.
+    pub(crate) fn end_event(&mut self, is_cursor_move: bool) {
+        match self.requested.take() {
+            Some(shape) => self.shape = shape,
+            None if is_cursor_move => self.shape = CursorShape::Arrow,
+            None => (),
+        }
+    }
+
+    /// The shape from the most recent requests.
+    pub(crate) fn shape(&self) -> CursorShape {
+        self.shape
+    }
+}
+
+/// WindowCursor applies cursor shapes to a GLFW window. The window's cursor
+/// is only replaced when the shape changes, so it's fine to apply the shape
+/// every frame.
+#[derive(Debug, Default)]
+pub struct WindowCursor {
+    applied: Option<CursorShape>,
+}
+
+impl WindowCursor {
+    /// Show the shape in the window, if it isn't already shown.
+    pub fn apply(&mut self, window: &mut glfw::Window, shape: CursorShape) {
+        if self.applied == Some(shape) {
+            return;
+        }
+        window
+            .set_cursor(Some(glfw::Cursor::standard(shape.standard_cursor())));
+        self.applied = Some(shape);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cursor_moves_without_requests_reset_the_shape() {
+        let mut requests = CursorRequests::default();
+
+        requests.request(CursorShape::Arrow);
+        requests.request(CursorShape::Hand);
+        requests.end_event(true);
+        assert_eq!(requests.shape(), CursorShape::Hand);
+
+        // other events keep the shape unless something asks
+        requests.end_event(false);
+        assert_eq!(requests.shape(), CursorShape::Hand);
+        requests.request(CursorShape::IBeam);
+        requests.end_event(false);
+        assert_eq!(requests.shape(), CursorShape::IBeam);
+
+        requests.end_event(true);
+        assert_eq!(requests.shape(), CursorShape::Arrow);
+    }
+}
@@ -0,0 +1,87 @@
+use ::std::collections::VecDeque;
+
+use glfw::WindowEvent;
+
+/// Queued window events waiting for the UI to handle them.
+///
+/// High polling rate mice can report hundreds of cursor moves each frame, and
+/// only the last one matters to the UI. The queue merges consecutive events
+/// which replace each other as they arrive. Everything else, like buttons and
+/// keys, is kept in the order it happened.
+#[derive(Debug, Default)]
+pub(crate) struct EventQueue {
+    events: VecDeque<WindowEvent>,
+}
+
+impl EventQueue {
+    /// Add an event to the back of the queue, merging it with the previous
+    /// event when they're the same kind of change:
+    ///
+    /// - consecutive cursor moves keep the latest position
+    /// - consecutive scrolls are added together
+    /// - consecutive resizes keep the latest size
+    pub(crate) fn push(&mut self, event: WindowEvent) {
+        use WindowEvent::{CursorPos, FramebufferSize, Scroll};
+
+        match (self.events.back_mut(), event) {
+            (Some(CursorPos(x, y)), CursorPos(new_x, new_y)) => {
+                *x = new_x;
+                *y = new_y;
+            }
+            (Some(Scroll(x, y)), Scroll(dx, dy)) => {
+                *x += dx;
+                *y += dy;
+            }
+            (
+                Some(FramebufferSize(width, height)),
+                FramebufferSize(new_width, new_height),
+            ) => {
+                *width = new_width;
+                *height = new_height;
+            }
+            (_, event) => self.events.push_back(event),
+        }
+    }
+
+    /// Take up to `count` events from the front of the queue, in the order
+    /// they happened.
+    pub(crate) fn take(&mut self, count: usize) -> Vec<WindowEvent> {
+        let count = count.min(self.events.len());
+        self.events.drain(..count).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use glfw::{Action, Modifiers, MouseButton};
+
+    #[test]
+    fn test_cursor_moves_merge_but_buttons_keep_their_order() {
+        let press = WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            Modifiers::empty(),
+        );
+        let mut queue = EventQueue::default();
+        queue.push(WindowEvent::CursorPos(1.0, 1.0));
+        queue.push(WindowEvent::CursorPos(2.0, 3.0));
+        queue.push(press.clone());
+        queue.push(WindowEvent::Scroll(0.0, 1.0));
+        queue.push(WindowEvent::Scroll(0.0, 2.0));
+        queue.push(WindowEvent::CursorPos(4.0, 5.0));
+
+        let first = queue.take(2);
+        assert_eq!(first, vec![WindowEvent::CursorPos(2.0, 3.0), press]);
+        let rest = queue.take(10);
+        assert_eq!(
+            rest,
+            vec![
+                WindowEvent::Scroll(0.0, 3.0),
+                WindowEvent::CursorPos(4.0, 5.0)
+            ]
+        );
+        assert!(queue.take(10).is_empty());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::ui::{primitives::Rect, Id};
+
+/// A widget which can receive keyboard focus.
+#[derive(Debug, Copy, Clone)]
+struct Focusable {
+    id: Id,
+
+    /// Where the widget is on screen, the focus ring is drawn around it.
+    bounds: Rect,
+
+    /// True when the widget types Tab itself, like a TextArea.
+    uses_tab: bool,
+}
+
+/// Keyboard focus tracks which widget receives key events.
+///
+/// Focusable widgets register themselves each time the UI is laid out. The
+/// order they register in is the tab order, which is the same order the
+/// widgets appear in the view.
+#[derive(Debug, Default)]
+pub(crate) struct Focus {
+    /// The widget with focus, if any.
+    focused: Option<Id>,
+
+    /// True when focus was moved with the keyboard. The focus ring is only
+    /// drawn then, clicking a widget makes it obvious which has focus.
+    visible: bool,
+
+    /// Every focusable widget in tab order, collected during layout.
+    order: Vec<Focusable>,
+}
+
+impl Focus {
+    /// Forget the tab order so it can be collected again during layout.
+    pub(crate) fn begin_layout(&mut self) {
+        self.order.clear();
+    }
+
+    /// Add a widget to the end of the tab order. Widgets which are laid out
+    /// more than once keep their first place so the order stays stable.
+    pub(crate) fn register(&mut self, id: Id, bounds: Rect, uses_tab: bool) {
+        match self.order.iter_mut().find(|focusable| focusable.id == id) {
+            Some(focusable) => focusable.bounds = bounds,
+            None => self.order.push(Focusable {
+                id,
+                bounds,
+                uses_tab,
+            }),
+        }
+    }
+
+    /// True when any widget has focus.
+    pub(crate) fn has_focus(&self) -> bool {
+        self.focused.is_some()
+    }
+
+    pub(crate) fn is_focused(&self, id: &Id) -> bool {
+        self.focused.as_ref() == Some(id)
+    }
+
+    /// Give a widget focus because it was clicked.
+    pub(crate) fn set(&mut self, id: Id) {
+        self.focused = Some(id);
+        self.visible = false;
+    }
+
+    /// Remove focus from a widget, if it has focus.
+    pub(crate) fn release(&mut self, id: &Id) {
+        if self.is_focused(id) {
+            self.focused = None;
+        }
+    }
+
+    /// True when the focused widget types Tab itself.
+    pub(crate) fn focused_uses_tab(&self) -> bool {
+        self.focused_widget()
+            .map(|focusable| focusable.uses_tab)
+            .unwrap_or(false)
+    }
+
+    /// Move focus to the next widget in tab order, or the previous widget
+    /// when going backwards. Focus wraps around at either end.
+    ///
+    /// Returns false when there's nothing which can take focus.
+    pub(crate) fn advance(&mut self, backwards: bool) -> bool {
+        let count = self.order.len();
+        if count == 0 {
+            return false;
+        }
+        let current = self.focused.and_then(|id| {
+            self.order.iter().position(|focusable| focusable.id == id)
+        });
+        let next = match (current, backwards) {
+            (Some(index), false) => (index + 1) % count,
+            (Some(index), true) => (index + count - 1) % count,
+            (None, false) => 0,
+            (None, true) => count - 1,
+        };
+        self.focused = Some(self.order[next].id);
+        self.visible = true;
+        true
+    }
+
+    /// The bounds to draw the focus ring around, when it's visible.
+    pub(crate) fn ring(&self) -> Option<Rect> {
+        if !self.visible {
+            return None;
+        }
+        self.focused_widget().map(|focusable| focusable.bounds)
+    }
+
+    fn focused_widget(&self) -> Option<&Focusable> {
+        let id = self.focused?;
+        self.order.iter().find(|focusable| focusable.id == id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tab_order_wraps_in_both_directions() {
+        let bounds = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let [a, b, c] = [Id::new(1), Id::new(2), Id::new(3)];
+        let mut focus = Focus::default();
+        focus.register(a, bounds, false);
+        focus.register(b, bounds, true);
+        focus.register(c, bounds, false);
+        focus.register(a, bounds, false);
+
+        assert!(focus.advance(true));
+        assert!(focus.is_focused(&c));
+        assert!(focus.ring().is_some());
+        assert!(focus.advance(false));
+        assert!(focus.is_focused(&a));
+
+        // clicking focuses without drawing a ring
+        focus.set(b);
+        assert!(focus.focused_uses_tab());
+        assert!(focus.ring().is_none());
+        focus.advance(false);
+        assert!(focus.is_focused(&c));
+
+        focus.release(&c);
+        focus.begin_layout();
+        assert!(!focus.advance(false));
+    }
+}
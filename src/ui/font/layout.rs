@@ -1,18 +1,21 @@
 use ::ab_glyph::{FontArc, Glyph, PxScaleFont, ScaleFont};
 
-use crate::ui::Font;
+use crate::ui::{
+    frame_arena::{temp_vec, TempVec},
+    Font,
+};
 
 impl Font {
     pub(super) fn layout_text<T>(
         font: &PxScaleFont<FontArc>,
         content: T,
-    ) -> Vec<Glyph>
+    ) -> TempVec<Glyph>
     where
         T: AsRef<str>,
     {
         let v_advance = (font.line_gap() + font.height()).ceil() as u32;
 
-        let mut glyphs = vec![];
+        let mut glyphs = temp_vec();
         let mut line_number = 1u32;
         let mut cursor = ab_glyph::point(0.0, (line_number * v_advance) as f32);
 
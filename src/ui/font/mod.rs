@@ -1,19 +1,31 @@
 mod layout;
 mod rasterize;
+mod sizes;
+mod text_block;
 
 use ::{
     ab_glyph::{Font as AbFont, FontArc, GlyphId, PxScaleFont, ScaleFont},
     anyhow::Result,
-    std::{collections::HashMap, fs::File, io::Read, path::Path},
+    std::{
+        cell::RefCell, collections::HashMap, fs::File, io::Read, path::Path,
+        rc::Rc,
+    },
 };
 
 use crate::{
-    asset_loader::AssetLoader,
-    builder_field,
-    ui::primitives::{Rect, Tile},
-    vec4, Vec4,
+    asset_loader::{AssetLoader, TextureId},
+    builder_field_some,
+    ui::{
+        primitives::{Rect, Tile},
+        Theme,
+    },
+    Vec4,
 };
 
+pub use self::text_block::TextBlock;
+
+use self::sizes::{choose_atlas, Atlas, FontSizes};
+
 /// This struct contains all of the information required to render rasterized
 /// glyphs on screen.
 #[derive(Debug, Clone)]
@@ -24,19 +36,41 @@ pub struct Font {
     /// When a font is constructed, all of the glyphs are rasterized into a
     /// single texture. This keeps track of which texture to use when
     /// generating [`Tiles`] for rendering.
-    texture_index: i32,
+    texture_index: TextureId,
 
     /// When a font is constructed, all of the glyphs are rasterized into a
     /// single texture. This map tracks the texture coordinates for each glyph
     /// supported by the current font.
     glyph_texture_coords: HashMap<GlyphId, Rect>,
 
-    /// The color of the text when rendered.
-    text_color: Vec4,
+    /// The color of the text when rendered. None uses the theme's text
+    /// color.
+    text_color: Option<Vec4>,
+
+    /// Named codepoints for icon fonts, like Font Awesome.
+    icons: HashMap<String, char>,
+
+    /// Extra atlases for text drawn at other sizes, see
+    /// [`Font::build_sized_text_tiles`].
+    sizes: Rc<RefCell<FontSizes>>,
 }
 
 impl Font {
-    builder_field!(text_color, Vec4);
+    builder_field_some!(text_color, Vec4);
+
+    /// The font's text color, or the theme's when the font doesn't have one.
+    ///
+    /// Tiles are built with the default theme's text color when the font
+    /// doesn't have one, widgets recolor them with the UI's theme when
+    /// they're drawn.
+    pub fn text_color_or(&self, theme: &Theme) -> Vec4 {
+        self.text_color.unwrap_or(theme.text_color)
+    }
+
+    /// The font's own text color, None when it uses the theme's.
+    pub(crate) fn own_text_color(&self) -> Option<Vec4> {
+        self.text_color
+    }
 
     /// Create a new font instance by reading the .ttf or .otf font file at the
     /// specified path.
@@ -87,15 +121,39 @@ impl Font {
 
         let texture_index =
             asset_loader.create_texture_with_data(&[rasterized_glyphs])?;
+        asset_loader.set_texture_name(
+            texture_index,
+            format!("Font Atlas ({}px)", font.height().round()),
+        )?;
 
         Ok(Self {
             font,
             texture_index,
             glyph_texture_coords,
-            text_color: vec4(1.0, 1.0, 1.0, 1.0),
+            text_color: None,
+            icons: HashMap::new(),
+            sizes: Rc::new(RefCell::new(FontSizes::default())),
         })
     }
 
+    /// Name codepoints in this font so they can be drawn by name with
+    /// [`crate::ui::widgets::Icon`]. This is meant for icon fonts, e.g.
+    /// `("gear", '\u{f013}')` for Font Awesome's gear.
+    pub fn with_icons<I, N>(mut self, icons: I) -> Self
+    where
+        I: IntoIterator<Item = (N, char)>,
+        N: Into<String>,
+    {
+        self.icons
+            .extend(icons.into_iter().map(|(name, c)| (name.into(), c)));
+        self
+    }
+
+    /// The codepoint for a named icon, if this font has one.
+    pub fn icon(&self, name: &str) -> Option<char> {
+        self.icons.get(name).copied()
+    }
+
     /// Build renderable tiles for the glyphs in the provided string.
     ///
     /// # Returns
@@ -113,11 +171,12 @@ impl Font {
         T: AsRef<str>,
     {
         let glyphs = Self::layout_text(&self.font, content);
+        let text_color = self.text_color_or(&Theme::default());
         let mut tiles = Vec::with_capacity(glyphs.len());
         let mut total_bounds: Option<Rect> = None;
 
         glyphs
-            .into_iter()
+            .iter()
             .filter_map(|glyph| {
                 // only draw glyphs that have an outline
                 self.font
@@ -142,13 +201,13 @@ impl Font {
                     ),
                     uv: texture_coords,
                     texture_index: self.texture_index,
-                    color: self.text_color,
+                    color: text_color,
                     ..Default::default()
                 };
                 tiles.push(tile);
 
                 // compute the updated total bounds rect
-                let glyph_bounds: Rect = self.font.glyph_bounds(&glyph).into();
+                let glyph_bounds: Rect = self.font.glyph_bounds(glyph).into();
                 if let Some(total) = total_bounds.take() {
                     total_bounds = Some(total.expand(glyph_bounds));
                 } else {
@@ -159,10 +218,161 @@ impl Font {
         (tiles, total_bounds.unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0)))
     }
 
+    /// Build renderable tiles for text drawn with a line height of `size`
+    /// pixels instead of the font's own scale. Glyphs are laid out at the
+    /// new size and drawn from whichever of the font's atlases is closest.
+    ///
+    /// When no atlas is close enough the text is still drawn, scaled from
+    /// the nearest atlas, and the size is remembered so
+    /// [`Font::rasterize_pending_sizes`] can build an atlas for it. Every
+    /// clone of the font shares its atlases.
+    pub fn build_sized_text_tiles<T>(
+        &self,
+        content: T,
+        size: f32,
+    ) -> (Vec<Tile>, Rect)
+    where
+        T: AsRef<str>,
+    {
+        let mut sizes = self.sizes.borrow_mut();
+        let (index, within_limits) = choose_atlas(
+            std::iter::once(self.line_height())
+                .chain(sizes.atlases.iter().map(|atlas| atlas.font.height())),
+            size,
+        );
+        if !within_limits {
+            sizes.request(size);
+        }
+        let (atlas_font, texture_index, glyph_texture_coords) = match index {
+            0 => (&self.font, self.texture_index, &self.glyph_texture_coords),
+            i => {
+                let atlas = &sizes.atlases[i - 1];
+                (
+                    &atlas.font,
+                    atlas.texture_index,
+                    &atlas.glyph_texture_coords,
+                )
+            }
+        };
+
+        let layout_font = self.font.clone().with_scale(size);
+        let factor = size / atlas_font.height();
+        let glyphs = Self::layout_text(&layout_font, content);
+        let text_color = self.text_color_or(&Theme::default());
+        let mut tiles = Vec::with_capacity(glyphs.len());
+        let mut total_bounds: Option<Rect> = None;
+        for glyph in glyphs.iter() {
+            let glyph_bounds: Rect = layout_font.glyph_bounds(glyph).into();
+            total_bounds = Some(match total_bounds {
+                Some(total) => total.expand(glyph_bounds),
+                None => glyph_bounds,
+            });
+
+            // the atlas glyph is outlined at the origin, then scaled and
+            // moved to where the glyph was laid out
+            let atlas_glyph = glyph
+                .id
+                .with_scale_and_position(atlas_font.scale(), (0.0, 0.0));
+            let outline = match atlas_font.outline_glyph(atlas_glyph) {
+                Some(outline) => outline,
+                None => continue,
+            };
+            let texture_coords = match glyph_texture_coords.get(&glyph.id) {
+                Some(texture_coords) => *texture_coords,
+                None => continue,
+            };
+            let bounds = outline.px_bounds();
+            let position = glyph.position;
+            tiles.push(Tile {
+                model: Rect::new(
+                    (position.y + factor * bounds.min.y).round(),
+                    (position.x + factor * bounds.min.x).round(),
+                    (position.y + factor * bounds.max.y).round(),
+                    (position.x + factor * bounds.max.x).round(),
+                ),
+                uv: texture_coords,
+                texture_index,
+                color: text_color,
+                ..Default::default()
+            });
+        }
+
+        (tiles, total_bounds.unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0)))
+    }
+
+    /// The bounds [`Font::build_sized_text_tiles`] would return for the same
+    /// text and size. Measuring never asks for another atlas.
+    pub fn sized_text_bounds<T>(&self, content: T, size: f32) -> Rect
+    where
+        T: AsRef<str>,
+    {
+        let layout_font = self.font.clone().with_scale(size);
+        Self::layout_text(&layout_font, content)
+            .iter()
+            .map(|glyph| -> Rect { layout_font.glyph_bounds(glyph).into() })
+            .reduce(|total, bounds| total.expand(bounds))
+            .unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0))
+    }
+
+    /// Rasterize an atlas for each size sized text asked for since the last
+    /// call. Call this outside of building the view, e.g. in the State's
+    /// update, so text which was scaled too far is sharp from then on.
+    ///
+    /// Returns the number of atlases which were created.
+    pub fn rasterize_pending_sizes(
+        &self,
+        asset_loader: &mut AssetLoader,
+    ) -> Result<usize> {
+        let pending = std::mem::take(&mut self.sizes.borrow_mut().pending);
+        for &size in &pending {
+            let font = Self::from_ab_glyph_font(
+                self.font.clone().with_scale(size),
+                asset_loader,
+            )?;
+            self.sizes.borrow_mut().atlases.push(Atlas {
+                font: font.font,
+                texture_index: font.texture_index,
+                glyph_texture_coords: font.glyph_texture_coords,
+            });
+        }
+        Ok(pending.len())
+    }
+
+    /// The width of a single line of text, measured from the start of the
+    /// first glyph to the end of the last glyph's advance. Unlike the bounds
+    /// from [`Font::build_text_tiles`], this includes white space, so it can
+    /// be used to position a cursor after a space.
+    pub fn text_width<T>(&self, content: T) -> f32
+    where
+        T: AsRef<str>,
+    {
+        Self::layout_text(&self.font, content)
+            .last()
+            .map(|glyph| glyph.position.x + self.font.h_advance(glyph.id))
+            .unwrap_or(0.0)
+    }
+
+    /// The texture which holds this font's rasterized glyphs.
+    pub fn texture_index(&self) -> TextureId {
+        self.texture_index
+    }
+
+    /// The texture coordinates of every glyph packed into this font's
+    /// texture.
+    pub fn glyph_texture_coords(&self) -> impl Iterator<Item = &Rect> {
+        self.glyph_texture_coords.values()
+    }
+
     /// Get the computed line height for text rendered with this font.
     pub fn line_height(&self) -> f32 {
         self.font.height()
     }
+
+    /// The distance between the tops of two lines of text, including the
+    /// gap between lines.
+    pub fn line_spacing(&self) -> f32 {
+        (self.font.line_gap() + self.font.height()).ceil()
+    }
 }
 
 impl Into<Rect> for ab_glyph::Rect {
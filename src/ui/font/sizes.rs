@@ -0,0 +1,105 @@
+use ::{
+    ab_glyph::{FontArc, GlyphId, PxScaleFont},
+    std::collections::HashMap,
+};
+
+use crate::{asset_loader::TextureId, ui::primitives::Rect};
+
+/// Text drawn more than this many times larger than its atlas is blurry, so a
+/// larger atlas is requested.
+pub(super) const MAX_UPSCALE: f32 = 1.25;
+
+/// Text drawn less than this fraction of its atlas's size starts to alias,
+/// so a smaller atlas is requested.
+pub(super) const MAX_DOWNSCALE: f32 = 0.5;
+
+/// Glyphs rasterized at one size.
+#[derive(Debug, Clone)]
+pub(super) struct Atlas {
+    pub font: PxScaleFont<FontArc>,
+    pub texture_index: TextureId,
+    pub glyph_texture_coords: HashMap<GlyphId, Rect>,
+}
+
+/// The extra atlases a font has been rasterized into for sized text, shared
+/// by every clone of the font.
+#[derive(Debug, Default)]
+pub(super) struct FontSizes {
+    pub atlases: Vec<Atlas>,
+
+    /// Sizes which sized text asked for because no atlas was close enough.
+    pub pending: Vec<f32>,
+}
+
+impl FontSizes {
+    /// Remember that text was drawn at a size no atlas covers well. Sizes
+    /// which are already pending closely enough aren't added again.
+    pub fn request(&mut self, size: f32) {
+        let size = size.round().max(1.0);
+        if !self
+            .pending
+            .iter()
+            .any(|&pending| within_limits(pending, size))
+        {
+            self.pending.push(size);
+        }
+    }
+}
+
+/// Pick which atlas to draw text of the given size from: the smallest atlas
+/// which is at least as large as the size, or the largest atlas when they're
+/// all smaller. Downscaling keeps more detail than upscaling.
+///
+/// Returns the atlas's index and whether it's within the scaling limits.
+pub(super) fn choose_atlas(
+    atlas_sizes: impl IntoIterator<Item = f32>,
+    size: f32,
+) -> (usize, bool) {
+    let mut larger: Option<(usize, f32)> = None;
+    let mut largest: Option<(usize, f32)> = None;
+    for (i, atlas_size) in atlas_sizes.into_iter().enumerate() {
+        if atlas_size >= size && larger.is_none_or(|(_, s)| atlas_size < s) {
+            larger = Some((i, atlas_size));
+        }
+        if largest.is_none_or(|(_, s)| atlas_size > s) {
+            largest = Some((i, atlas_size));
+        }
+    }
+    match larger.or(largest) {
+        Some((i, atlas_size)) => (i, within_limits(atlas_size, size)),
+        None => (0, false),
+    }
+}
+
+/// True when text of the given size can be drawn from an atlas of the given
+/// size without re-rasterizing.
+fn within_limits(atlas_size: f32, size: f32) -> bool {
+    let factor = size / atlas_size;
+    (MAX_DOWNSCALE..=MAX_UPSCALE).contains(&factor)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_choose_atlas_prefers_downscaling() {
+        let sizes = [16.0, 32.0, 64.0];
+
+        assert_eq!(choose_atlas(sizes, 16.0), (0, true));
+        assert_eq!(choose_atlas(sizes, 20.0), (1, true));
+        assert_eq!(choose_atlas(sizes, 12.0), (0, true));
+
+        // nothing is large enough, so the largest is stretched
+        assert_eq!(choose_atlas(sizes, 72.0), (2, true));
+        assert_eq!(choose_atlas(sizes, 96.0), (2, false));
+
+        // too small for every atlas
+        assert_eq!(choose_atlas(sizes, 6.0), (0, false));
+
+        let mut font_sizes = FontSizes::default();
+        font_sizes.request(96.2);
+        font_sizes.request(100.0);
+        assert_eq!(font_sizes.pending, vec![96.0]);
+    }
+}
@@ -0,0 +1,193 @@
+use ::{
+    ab_glyph::ScaleFont,
+    std::{
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+    },
+};
+
+use crate::{
+    asset_loader::{AssetLoader, AssetLoaderError, MipmapData, TextureId},
+    ui::{
+        primitives::{Rect, Tile},
+        Font, Theme,
+    },
+};
+
+/// A block of text which was rasterized into its own texture.
+///
+/// Drawing a text block takes a single tile no matter how much text it
+/// holds, which makes it a good fit for large paragraphs which rarely
+/// change. The texture holds white glyphs, so the text color is applied
+/// when the tile is built and changing the theme doesn't need a new
+/// texture. Use [`Font::update_text_block`] when the text or the font's
+/// scale changes.
+#[derive(Debug, Clone)]
+pub struct TextBlock {
+    /// The texture which holds the rasterized text.
+    texture_index: TextureId,
+
+    /// The space covered by the texture, relative to the text's origin.
+    model: Rect,
+
+    /// The bounding rect for the text based on font metrics, the same as
+    /// the bounds from [`Font::build_text_tiles`].
+    bounds: Rect,
+
+    /// Identifies the text and font scale which were rasterized.
+    key: u64,
+}
+
+impl TextBlock {
+    /// The texture which holds this block's text.
+    pub fn texture_index(&self) -> TextureId {
+        self.texture_index
+    }
+}
+
+impl Font {
+    /// Rasterize the text into a new texture.
+    ///
+    /// Each text block uses its own slot in the texture table, so blocks
+    /// should be kept and updated rather than rebuilt.
+    pub fn build_text_block<T>(
+        &self,
+        content: T,
+        asset_loader: &mut AssetLoader,
+    ) -> Result<TextBlock, AssetLoaderError>
+    where
+        T: AsRef<str>,
+    {
+        let (pixels, model, bounds) = self.rasterize_text(content.as_ref());
+        let texture_index = asset_loader.create_texture_with_data(&[pixels])?;
+        asset_loader.set_texture_name(
+            texture_index,
+            format!("Text Block ({}px)", self.font.height().round()),
+        )?;
+        Ok(TextBlock {
+            texture_index,
+            model,
+            bounds,
+            key: self.text_block_key(content.as_ref()),
+        })
+    }
+
+    /// Rasterize the text into the block's texture again if the text or
+    /// this font's scale changed since the block was built.
+    ///
+    /// # Returns
+    ///
+    /// True when the texture was updated.
+    pub fn update_text_block<T>(
+        &self,
+        text_block: &mut TextBlock,
+        content: T,
+        asset_loader: &mut AssetLoader,
+    ) -> Result<bool, AssetLoaderError>
+    where
+        T: AsRef<str>,
+    {
+        let key = self.text_block_key(content.as_ref());
+        if key == text_block.key {
+            return Ok(false);
+        }
+        let (pixels, model, bounds) = self.rasterize_text(content.as_ref());
+        asset_loader
+            .replace_texture_with_data(text_block.texture_index, &[pixels])?;
+        *text_block = TextBlock {
+            model,
+            bounds,
+            key,
+            ..*text_block
+        };
+        Ok(true)
+    }
+
+    /// Build the single tile which draws a text block.
+    ///
+    /// # Returns
+    ///
+    /// The same as [`Font::build_text_tiles`]: the tiles, which is just one
+    /// tile here, and the bounding rect for the text based on font metrics.
+    pub fn build_text_block_tiles(
+        &self,
+        text_block: &TextBlock,
+    ) -> (Vec<Tile>, Rect) {
+        let text_color = self.text_color_or(&Theme::default());
+        let tile = Tile {
+            model: text_block.model,
+            texture_index: text_block.texture_index,
+            color: text_color,
+            ..Default::default()
+        };
+        (vec![tile], text_block.bounds)
+    }
+
+    /// Draw every glyph into one image.
+    ///
+    /// # Returns
+    ///
+    /// The image, the space it covers relative to the text's origin, and the
+    /// text's bounds based on font metrics.
+    fn rasterize_text(&self, content: &str) -> (MipmapData, Rect, Rect) {
+        let glyphs = Self::layout_text(&self.font, content);
+        let outlines: Vec<_> = glyphs
+            .iter()
+            .filter_map(|glyph| self.font.outline_glyph(glyph.clone()))
+            .collect();
+
+        let mut model: Option<Rect> = None;
+        let mut bounds: Option<Rect> = None;
+        for glyph in glyphs.iter() {
+            let glyph_bounds: Rect = self.font.glyph_bounds(glyph).into();
+            bounds = Some(match bounds {
+                Some(total) => total.expand(glyph_bounds),
+                None => glyph_bounds,
+            });
+        }
+        for outline in &outlines {
+            let px = outline.px_bounds();
+            let px_bounds = Rect::new(
+                px.min.y.floor(),
+                px.min.x.floor(),
+                px.max.y.ceil(),
+                px.max.x.ceil(),
+            );
+            model = Some(match model {
+                Some(total) => total.expand(px_bounds),
+                None => px_bounds,
+            });
+        }
+        let model = model.unwrap_or(Rect::new(0.0, 0.0, 1.0, 1.0));
+        let bounds = bounds.unwrap_or(Rect::new(0.0, 0.0, 0.0, 0.0));
+
+        let width = model.width().max(1.0) as u32;
+        let height = model.height().max(1.0) as u32;
+        let mut pixels =
+            MipmapData::allocate(width, height, [0xFF, 0xFF, 0xFF, 0x00]);
+        for outline in &outlines {
+            let px = outline.px_bounds();
+            let base_x = (px.min.x.floor() - model.left()) as u32;
+            let base_y = (px.min.y.floor() - model.top()) as u32;
+            outline.draw(|x, y, coverage| {
+                let (x, y) = (base_x + x, base_y + y);
+                if x >= width || y >= height {
+                    return;
+                }
+                // glyphs can overlap, so keep the most coverage
+                let offset = ((y * width + x) * 4 + 3) as usize;
+                let alpha = (0xFF as f32 * coverage) as u8;
+                pixels.data[offset] = pixels.data[offset].max(alpha);
+            });
+        }
+        (pixels, model, bounds)
+    }
+
+    /// Identify the text and this font's scale.
+    fn text_block_key(&self, content: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        self.font.height().to_bits().hash(&mut hasher);
+        hasher.finish()
+    }
+}
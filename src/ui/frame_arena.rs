@@ -0,0 +1,204 @@
+//! A per-frame pool of temporary buffers.
+//!
+//! Layout, text, and path flattening need lots of small vectors which only
+//! live for a moment. Rather than asking the allocator for each of them, a
+//! [`TempVec`] borrows an empty buffer from the current thread's arena and
+//! gives it back, still allocated, when it's dropped. After the first few
+//! frames nearly every temporary vector reuses memory from a previous frame.
+//!
+//! The UI calls [`reset`] once per frame. Buffers which weren't needed during
+//! the frame are released so the arena shrinks back down after an unusually
+//! busy frame. Applications which draw without a UI can call [`reset`]
+//! themselves, the arena still works without it but never shrinks.
+
+use ::std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    iter::FromIterator,
+    ops::{Deref, DerefMut},
+};
+
+thread_local! {
+    static ARENA: RefCell<FrameArena> = RefCell::new(FrameArena::default());
+}
+
+/// A vector whose buffer is borrowed from the frame arena and returned to it
+/// when dropped.
+///
+/// TempVec derefs to a [`Vec`] so it can be used anywhere a vector or slice
+/// is expected. It's meant for values which are built and consumed within a
+/// frame. Keeping one around is harmless, but the buffer isn't reused until
+/// it's dropped.
+#[derive(Debug)]
+pub struct TempVec<T: 'static> {
+    vec: Vec<T>,
+}
+
+/// Borrow an empty vector from the current thread's frame arena.
+pub fn temp_vec<T: 'static>() -> TempVec<T> {
+    let vec = ARENA
+        .try_with(|arena| arena.borrow_mut().take::<T>())
+        .unwrap_or_default();
+    TempVec { vec }
+}
+
+/// Borrow an empty vector with room for at least `capacity` items.
+pub fn temp_vec_with_capacity<T: 'static>(capacity: usize) -> TempVec<T> {
+    let mut vec = temp_vec();
+    vec.reserve(capacity);
+    vec
+}
+
+/// Release the buffers which weren't used since the last reset. Called by
+/// the UI at the start of every frame.
+pub fn reset() {
+    let _ = ARENA.try_with(|arena| arena.borrow_mut().reset());
+}
+
+impl<T: 'static> Deref for TempVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.vec
+    }
+}
+
+impl<T: 'static> DerefMut for TempVec<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.vec
+    }
+}
+
+impl<T: 'static> Drop for TempVec<T> {
+    fn drop(&mut self) {
+        let mut vec = std::mem::take(&mut self.vec);
+        if vec.capacity() == 0 {
+            return;
+        }
+        vec.clear();
+        let _ = ARENA.try_with(|arena| {
+            if let Ok(mut arena) = arena.try_borrow_mut() {
+                arena.give_back(vec);
+            }
+        });
+    }
+}
+
+impl<T: 'static> FromIterator<T> for TempVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = temp_vec();
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<'a, T: 'static> IntoIterator for &'a TempVec<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vec.iter()
+    }
+}
+
+/// The free buffers for every type of item, keyed by the item's type.
+#[derive(Default)]
+struct FrameArena {
+    pools: HashMap<TypeId, Box<dyn ErasedPool>>,
+}
+
+impl FrameArena {
+    fn take<T: 'static>(&mut self) -> Vec<T> {
+        self.pool::<T>().take()
+    }
+
+    fn give_back<T: 'static>(&mut self, vec: Vec<T>) {
+        self.pool::<T>().free.push(vec);
+    }
+
+    fn reset(&mut self) {
+        for pool in self.pools.values_mut() {
+            pool.reset();
+        }
+    }
+
+    fn pool<T: 'static>(&mut self) -> &mut Pool<T> {
+        self.pools
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Pool::<T>::default()))
+            .as_any_mut()
+            .downcast_mut::<Pool<T>>()
+            .unwrap()
+    }
+}
+
+/// The free buffers for one type of item.
+struct Pool<T> {
+    free: Vec<Vec<T>>,
+
+    /// The fewest free buffers the pool had since the last reset. This many
+    /// buffers weren't needed during the frame.
+    least_free: usize,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self {
+            free: vec![],
+            least_free: 0,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    fn take(&mut self) -> Vec<T> {
+        let vec = self.free.pop().unwrap_or_default();
+        self.least_free = self.least_free.min(self.free.len());
+        vec
+    }
+}
+
+/// Lets the arena reset each pool without knowing its item type.
+trait ErasedPool {
+    fn reset(&mut self);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> ErasedPool for Pool<T> {
+    fn reset(&mut self) {
+        self.free.drain(..self.least_free);
+        self.least_free = self.free.len();
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buffers_are_reused_until_a_frame_goes_without_them() {
+        let mut first = temp_vec::<u64>();
+        first.extend(0..100);
+        let buffer = first.as_ptr();
+        drop(first);
+
+        let second = temp_vec::<u64>();
+        assert!(second.is_empty());
+        assert_eq!(second.as_ptr(), buffer);
+        drop(second);
+
+        // the buffer was used during this frame, so it's kept
+        reset();
+        assert_eq!(temp_vec::<u64>().as_ptr(), buffer);
+
+        // ...but not after a frame where nothing needed it
+        reset();
+        reset();
+        assert_eq!(temp_vec::<u64>().capacity(), 0);
+    }
+}
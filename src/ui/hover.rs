@@ -0,0 +1,87 @@
+use ::std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use crate::ui::Id;
+
+/// Hover tracks which widgets the mouse is over and since when, so widgets
+/// can build tooltips, hover animations, and long presses without their own
+/// timers.
+///
+/// Widgets report whether they're hovered as the cursor moves. Widgets which
+/// stop reporting, because they left the view, stop being hovered after the
+/// next cursor move.
+#[derive(Debug, Default)]
+pub(crate) struct Hover {
+    /// The UI time each hovered widget was entered at.
+    entered: HashMap<Id, Duration>,
+
+    /// The widgets which reported being hovered during the current cursor
+    /// move.
+    reported: HashSet<Id>,
+
+    /// True while a cursor move is being dispatched.
+    tracking: bool,
+}
+
+impl Hover {
+    /// Start collecting the widgets which are hovered after a cursor move.
+    pub(crate) fn begin_cursor_move(&mut self) {
+        self.reported.clear();
+        self.tracking = true;
+    }
+
+    /// Forget every widget which didn't report being hovered during the
+    /// cursor move.
+    pub(crate) fn end_cursor_move(&mut self) {
+        let reported = &self.reported;
+        self.entered.retain(|id, _| reported.contains(id));
+        self.tracking = false;
+    }
+
+    /// Record whether a widget is hovered at the given UI time. Widgets keep
+    /// the time they were first entered while they stay hovered.
+    pub(crate) fn set(&mut self, id: Id, hovered: bool, now: Duration) {
+        if hovered {
+            self.entered.entry(id).or_insert(now);
+            if self.tracking {
+                self.reported.insert(id);
+            }
+        } else {
+            self.entered.remove(&id);
+        }
+    }
+
+    /// The UI time the widget was entered at, if it's hovered.
+    pub(crate) fn entered(&self, id: &Id) -> Option<Duration> {
+        self.entered.get(id).copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_widgets_which_stop_reporting_are_not_hovered() {
+        let mut hover = Hover::default();
+        let (a, b) = (Id::new(1), Id::new(2));
+        let seconds = Duration::from_secs;
+
+        hover.begin_cursor_move();
+        hover.set(a, true, seconds(1));
+        hover.set(b, true, seconds(1));
+        hover.end_cursor_move();
+
+        // a is still hovered and keeps its enter time, b has disappeared
+        hover.begin_cursor_move();
+        hover.set(a, true, seconds(2));
+        hover.end_cursor_move();
+        assert_eq!(hover.entered(&a), Some(seconds(1)));
+        assert_eq!(hover.entered(&b), None);
+
+        hover.set(a, false, seconds(3));
+        assert_eq!(hover.entered(&a), None);
+    }
+}
@@ -1,10 +1,55 @@
-use crate::{vec2, Vec2};
+use ::{
+    glfw::{Modifiers, WindowEvent},
+    std::time::{Duration, Instant},
+};
+
+use crate::{ui::MouseButtons, vec2, Vec2};
+
+/// Presses closer together than this, in time, continue a multi-click.
+const MULTI_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Presses further apart than this, in pixels, start a new click.
+const MULTI_CLICK_DISTANCE: f32 = 4.0;
 
 /// This struct holds all persistent UI input state. Things like the position
 /// of the mouse or hotkeys that are still held down.
 #[derive(Debug, Copy, Clone)]
 pub struct Input {
     pub mouse_position: Vec2,
+
+    /// True while the primary mouse button, usually the left one, is held
+    /// down.
+    pub left_button_pressed: bool,
+
+    /// True while the secondary mouse button, usually the right one, is held
+    /// down.
+    pub right_button_pressed: bool,
+
+    /// Which physical mouse buttons are primary, secondary, and middle. The
+    /// UI rewrites mouse button events with it before they're handled, so
+    /// every event widgets see uses `Button1` for the primary button.
+    pub mouse_buttons: MouseButtons,
+
+    /// The modifier keys held during the most recent key, mouse button, or
+    /// text input event.
+    pub modifiers: Modifiers,
+
+    /// The number of quick left presses in the same place, counting the most
+    /// recent one: 1 for a single click, 2 for a double-click, and 3 for a
+    /// triple-click. A fourth press starts over at 1.
+    pub click_count: u32,
+
+    /// The mouse wheel or trackpad offset of the event being handled, or zero
+    /// when it isn't a scroll event. Positive y scrolls up and positive x
+    /// scrolls right, as reported by GLFW.
+    pub scroll: Vec2,
+
+    /// Every scroll offset since the UI last drew a frame, for widgets which
+    /// only care how far the wheel moved in total.
+    pub frame_scroll: Vec2,
+
+    /// When and where the left button was last pressed.
+    last_click: Option<(Instant, Vec2)>,
 }
 
 impl Input {
@@ -12,16 +57,175 @@ impl Input {
     pub fn new() -> Self {
         Self {
             mouse_position: vec2(0.0, 0.0),
+            left_button_pressed: false,
+            right_button_pressed: false,
+            mouse_buttons: MouseButtons::default(),
+            modifiers: Modifiers::empty(),
+            click_count: 0,
+            scroll: vec2(0.0, 0.0),
+            frame_scroll: vec2(0.0, 0.0),
+            last_click: None,
+        }
+    }
+
+    /// Forget the scrolling which happened before this frame.
+    pub(crate) fn end_frame(&mut self) {
+        self.frame_scroll = vec2(0.0, 0.0);
+    }
+
+    /// True when the most recent left press was the second of a
+    /// double-click. Widgets check this while handling the press.
+    pub fn is_double_click(&self) -> bool {
+        self.click_count == 2
+    }
+
+    /// True when the most recent left press was the third of a
+    /// triple-click.
+    pub fn is_triple_click(&self) -> bool {
+        self.click_count == 3
+    }
+
+    /// The character typed by an event, if it's text input.
+    ///
+    /// Text arrives as `WindowEvent::Char` when the window has char polling
+    /// enabled, which the demo Application does by default. Control
+    /// characters aren't text, keys like Backspace arrive as key events
+    /// instead.
+    pub fn typed_char(event: &WindowEvent) -> Option<char> {
+        match *event {
+            WindowEvent::Char(c) if !c.is_control() => Some(c),
+            _ => None,
         }
     }
 
     /// Handle system events to update internal state.
-    pub fn handle_event(&mut self, event: &glfw::WindowEvent) {
+    pub fn handle_event(&mut self, event: &WindowEvent) {
+        self.handle_event_at(event, Instant::now());
+    }
+
+    /// Handle an event which happened at the given time.
+    fn handle_event_at(&mut self, event: &WindowEvent, now: Instant) {
+        use glfw::{Action, MouseButton};
+
+        if let WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            _,
+        ) = *event
+        {
+            self.count_click(now);
+        }
+
+        self.scroll = vec2(0.0, 0.0);
         match *event {
-            glfw::WindowEvent::CursorPos(x, y) => {
+            WindowEvent::CursorPos(x, y) => {
                 self.mouse_position = vec2(x as f32, y as f32);
             }
+            WindowEvent::MouseButton(button, action, modifiers) => {
+                self.modifiers = modifiers;
+                let pressed = action != Action::Release;
+                match button {
+                    MouseButton::Button1 => self.left_button_pressed = pressed,
+                    MouseButton::Button2 => self.right_button_pressed = pressed,
+                    _ => (),
+                }
+            }
+            WindowEvent::Scroll(x, y) => {
+                self.scroll = vec2(x as f32, y as f32);
+                self.frame_scroll += self.scroll;
+            }
+            WindowEvent::Key(_, _, _, modifiers)
+            | WindowEvent::CharModifiers(_, modifiers) => {
+                self.modifiers = modifiers;
+            }
             _ => (),
         }
     }
+
+    /// Count a left press at the mouse position.
+    fn count_click(&mut self, now: Instant) {
+        let continues = self.last_click.is_some_and(|(time, position)| {
+            now.saturating_duration_since(time) < MULTI_CLICK_INTERVAL
+                && (self.mouse_position - position).norm()
+                    <= MULTI_CLICK_DISTANCE
+        });
+        self.click_count = if continues && self.click_count < 3 {
+            self.click_count + 1
+        } else {
+            1
+        };
+        self.last_click = Some((now, self.mouse_position));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use glfw::{Action, Key};
+
+    #[test]
+    fn test_modifiers_follow_key_and_text_events() {
+        let mut input = Input::new();
+        input.handle_event(&WindowEvent::Key(
+            Key::LeftShift,
+            0,
+            Action::Press,
+            Modifiers::Shift,
+        ));
+        assert_eq!(input.modifiers, Modifiers::Shift);
+
+        input.handle_event(&WindowEvent::CharModifiers('a', Modifiers::Alt));
+        assert_eq!(input.modifiers, Modifiers::Alt);
+
+        assert_eq!(Input::typed_char(&WindowEvent::Char('a')), Some('a'));
+        assert_eq!(Input::typed_char(&WindowEvent::Char('\u{8}')), None);
+    }
+
+    #[test]
+    fn test_scroll_offsets_accumulate_until_the_frame_ends() {
+        let mut input = Input::new();
+        input.handle_event(&WindowEvent::Scroll(0.0, 1.0));
+        input.handle_event(&WindowEvent::Scroll(0.5, 2.0));
+        assert_eq!(input.scroll, vec2(0.5, 2.0));
+        assert_eq!(input.frame_scroll, vec2(0.5, 3.0));
+
+        input.handle_event(&WindowEvent::CursorPos(10.0, 10.0));
+        assert_eq!(input.scroll, vec2(0.0, 0.0));
+
+        input.end_frame();
+        assert_eq!(input.frame_scroll, vec2(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_quick_presses_in_place_count_as_multi_clicks() {
+        use glfw::MouseButton;
+
+        let mut input = Input::new();
+        let start = Instant::now();
+        let press = WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            Modifiers::empty(),
+        );
+        let press_at = |input: &mut Input, ms: u64| {
+            input.handle_event_at(&press, start + Duration::from_millis(ms));
+            input.click_count
+        };
+
+        assert_eq!(press_at(&mut input, 0), 1);
+        assert!(!input.is_double_click());
+        assert_eq!(press_at(&mut input, 200), 2);
+        assert!(input.is_double_click());
+        assert_eq!(press_at(&mut input, 400), 3);
+        assert!(input.is_triple_click());
+        assert_eq!(press_at(&mut input, 500), 1);
+
+        // too slow
+        assert_eq!(press_at(&mut input, 1000), 1);
+
+        // too far
+        input.handle_event(&WindowEvent::CursorPos(50.0, 0.0));
+        assert_eq!(press_at(&mut input, 1100), 1);
+    }
 }
@@ -1,24 +1,278 @@
 use ::std::{
     any::{Any, TypeId},
     collections::HashMap,
+    time::{Duration, Instant},
 };
 
-use crate::ui::Id;
+use crate::ui::{
+    animation::{Animatable, Animations, Transition},
+    cursor::CursorRequests,
+    focus::Focus,
+    hover::Hover,
+    press::{HoldRepeat, Presses},
+    primitives::Rect,
+    Clipboard, CursorShape, Id, MemoryClipboard, Theme, UISound,
+};
 
 /// Internal State maintains any widget state that needs to persist between
 /// view rebuilds.
 pub struct InternalState {
     widget_states: HashMap<Id, Box<dyn Any>>,
+
+    /// When the UI was created.
+    start: Instant,
+
+    /// When the current frame started.
+    frame_start: Instant,
+
+    /// The time between the start of the previous frame and the current
+    /// frame.
+    frame_delta: Duration,
+
+    /// Sounds requested by widgets since the UI last played them.
+    sounds: Vec<UISound>,
+
+    /// Where widgets copy text to and paste text from.
+    clipboard: Box<dyn Clipboard>,
+
+    /// Which widget receives key events.
+    focus: Focus,
+
+    /// Which widgets the mouse is over, and since when.
+    hover: Hover,
+
+    /// Which widgets are held down, and since when.
+    presses: Presses,
+
+    /// The cursor shapes widgets asked for.
+    cursor: CursorRequests,
+
+    /// Widget properties which animate towards their targets.
+    animations: Animations,
+
+    /// The default look for widgets.
+    theme: Theme,
 }
 
 impl InternalState {
     /// Create a new, empty, internal state.
     pub fn new() -> Self {
+        let now = Instant::now();
         Self {
             widget_states: HashMap::new(),
+            start: now,
+            frame_start: now,
+            frame_delta: Duration::from_secs(0),
+            sounds: vec![],
+            clipboard: Box::new(MemoryClipboard::default()),
+            focus: Focus::default(),
+            hover: Hover::default(),
+            presses: Presses::default(),
+            cursor: CursorRequests::default(),
+            animations: Animations::default(),
+            theme: Theme::default(),
         }
     }
 
+    /// The UI's theme. Widgets use it for every property which wasn't set
+    /// with a builder method.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Replace the theme. Called by the UI when the theme is switched.
+    pub(crate) fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    /// The time between when the UI was created and the start of the current
+    /// frame. This is the same for every widget drawn in a frame, so it's
+    /// the clock to use for animations.
+    pub fn time(&self) -> Duration {
+        self.frame_start - self.start
+    }
+
+    /// The time between the start of the previous frame and the current
+    /// frame.
+    pub fn frame_delta(&self) -> Duration {
+        self.frame_delta
+    }
+
+    /// Advance the frame clock. Called by the UI once per frame, before the
+    /// view is drawn.
+    pub(crate) fn start_frame(&mut self) {
+        let now = Instant::now();
+        self.frame_delta = now - self.frame_start;
+        self.frame_start = now;
+    }
+
+    /// Request a sound for a UI interaction. Sounds are only heard when the
+    /// UI has sound feedback, otherwise they're discarded.
+    pub fn play_sound(&mut self, sound: UISound) {
+        self.sounds.push(sound);
+    }
+
+    /// Take every sound requested since the last call.
+    pub(crate) fn take_sounds(&mut self) -> Vec<UISound> {
+        ::std::mem::take(&mut self.sounds)
+    }
+
+    /// Ask for a cursor shape while handling an event. Widgets ask on every
+    /// cursor move while the mouse is over them, and the cursor is an arrow
+    /// when nothing asks. The last request for an event wins.
+    pub fn request_cursor(&mut self, shape: CursorShape) {
+        self.cursor.request(shape);
+    }
+
+    /// The cursor shape widgets asked for most recently.
+    pub fn cursor_shape(&self) -> CursorShape {
+        self.cursor.shape()
+    }
+
+    pub(crate) fn cursor_mut(&mut self) -> &mut CursorRequests {
+        &mut self.cursor
+    }
+
+    /// The clipboard used to copy and paste text.
+    pub fn clipboard(&mut self) -> &mut dyn Clipboard {
+        self.clipboard.as_mut()
+    }
+
+    /// Replace the clipboard used to copy and paste text.
+    pub(crate) fn set_clipboard(&mut self, clipboard: Box<dyn Clipboard>) {
+        self.clipboard = clipboard;
+    }
+
+    /// Add a widget to the tab order so it can receive keyboard focus.
+    /// Focusable widgets call this each time they're positioned, with the
+    /// bounds the focus ring should surround.
+    pub fn register_focusable(&mut self, id: Id, bounds: Rect) {
+        self.focus.register(id, bounds, false);
+    }
+
+    /// Add a widget which types Tab itself, like a TextArea, to the tab
+    /// order. Tab doesn't move focus away from the widget, Control+Tab does.
+    pub fn register_focusable_with_tab(&mut self, id: Id, bounds: Rect) {
+        self.focus.register(id, bounds, true);
+    }
+
+    /// True when the widget has keyboard focus. Only the focused widget
+    /// should react to key events.
+    pub fn is_focused(&self, id: &Id) -> bool {
+        self.focus.is_focused(id)
+    }
+
+    /// Give a widget keyboard focus, typically because it was clicked.
+    pub fn set_focus(&mut self, id: Id) {
+        self.focus.set(id);
+    }
+
+    /// Remove keyboard focus from a widget. Nothing happens if another widget
+    /// has focus.
+    pub fn release_focus(&mut self, id: &Id) {
+        self.focus.release(id);
+    }
+
+    /// The UI's keyboard focus.
+    pub(crate) fn focus_mut(&mut self) -> &mut Focus {
+        &mut self.focus
+    }
+
+    /// Report whether the mouse is over a widget. Hovered widgets should
+    /// report themselves on every cursor move, a widget which stops
+    /// reporting, e.g. because it's no longer in the view, stops being
+    /// hovered.
+    pub fn set_hovered(&mut self, id: &Id, hovered: bool) {
+        let now = self.time();
+        self.hover.set(*id, hovered, now);
+    }
+
+    /// True when the widget reported that the mouse is over it.
+    pub fn is_hovered(&self, id: &Id) -> bool {
+        self.hover.entered(id).is_some()
+    }
+
+    /// How long the mouse has been over the widget, on the same clock as
+    /// [`Self::time`]. None when the widget isn't hovered.
+    ///
+    /// Useful for tooltip delays and long presses, compare the duration
+    /// against a threshold each frame.
+    pub fn hover_duration(&self, id: &Id) -> Option<Duration> {
+        self.hover
+            .entered(id)
+            .map(|entered| self.time().saturating_sub(entered))
+    }
+
+    /// The UI's hover tracking.
+    pub(crate) fn hover_mut(&mut self) -> &mut Hover {
+        &mut self.hover
+    }
+
+    /// Report whether a widget is held down, e.g. by the mouse. Pressed
+    /// widgets get a tick event every frame, see [`Self::take_hold_repeat`].
+    ///
+    /// Every press is forgotten when the mouse button is released, so
+    /// widgets which leave the view while pressed don't stay pressed.
+    pub fn set_pressed(&mut self, id: &Id, pressed: bool) {
+        let now = self.time();
+        self.presses.set(*id, pressed, now);
+    }
+
+    /// True when the widget reported that it's held down.
+    pub fn is_pressed(&self, id: &Id) -> bool {
+        self.presses.since(id).is_some()
+    }
+
+    /// How long the widget has been held down, on the same clock as
+    /// [`Self::time`]. None when the widget isn't pressed.
+    pub fn press_duration(&self, id: &Id) -> Option<Duration> {
+        self.presses
+            .since(id)
+            .map(|since| self.time().saturating_sub(since))
+    }
+
+    /// True when a pressed widget has been held long enough to repeat
+    /// again. Widgets call this when they get the UI's tick event, a
+    /// [`glfw::WindowEvent::Refresh`] sent each frame while anything is
+    /// pressed.
+    pub fn take_hold_repeat(&mut self, id: &Id, repeat: HoldRepeat) -> bool {
+        let now = self.time();
+        self.presses.take_repeat(id, now, repeat)
+    }
+
+    /// The UI's press tracking.
+    pub(crate) fn presses_mut(&mut self) -> &mut Presses {
+        &mut self.presses
+    }
+
+    /// Animate one of a widget's properties towards the target, returning
+    /// the value to draw this frame. The property name tells a widget's
+    /// animated values apart.
+    ///
+    /// Properties start at their first target. When the target changes the
+    /// value moves to it over the transition, starting from wherever it is
+    /// now.
+    pub fn animate<T>(
+        &mut self,
+        id: &Id,
+        property: &'static str,
+        target: T,
+        transition: Transition,
+    ) -> T
+    where
+        T: Animatable,
+    {
+        let time = self.time();
+        self.animations
+            .animate(*id, property, target, transition, time)
+    }
+
+    /// True when any animation was still moving since the last call. The UI
+    /// keeps redrawing until animations finish.
+    pub(crate) fn take_animating(&mut self) -> bool {
+        self.animations.take_animating()
+    }
+
     /// Get the state for a Widget's id.
     /// If no state exists, a default instance will be created and inserted.
     pub fn get_state<S>(&mut self, id: &Id) -> &S
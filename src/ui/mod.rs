@@ -8,32 +8,44 @@
 ///!
 use crate::{math, Mat4};
 
+pub mod frame_arena;
 pub mod primitives;
 pub mod widgets;
 
+mod animation;
+mod clipboard;
+mod cursor;
+mod event_queue;
+mod focus;
 mod font;
+mod hover;
 mod id;
 mod input;
 mod internal_state;
+mod mouse_buttons;
+mod press;
+mod sound;
+mod theme;
 mod ui;
 
 pub use self::{
-    font::Font,
+    animation::{Animatable, Easing, Transition},
+    clipboard::{Clipboard, MemoryClipboard},
+    cursor::{CursorShape, WindowCursor},
+    font::{Font, TextBlock},
     id::{id_hash, Id},
     input::Input,
     internal_state::InternalState,
+    mouse_buttons::MouseButtons,
+    press::HoldRepeat,
+    sound::{SoundFeedback, SoundPlayer, UISound},
+    theme::Theme,
     ui::{UIState, UI},
 };
 
 /// Create a new projection matrix which defines the UI Screen Space based
 /// on the given width and height.
 pub fn ui_screen_space_projection(viewport: primitives::Dimensions) -> Mat4 {
-    math::projections::ortho(
-        0.0,
-        viewport.width,
-        viewport.height,
-        0.0,
-        0.0,
-        1.0,
-    )
+    math::CoordinateSystem::YDown
+        .pixel_projection(viewport.width, viewport.height)
 }
@@ -0,0 +1,111 @@
+use ::glfw::{MouseButton, WindowEvent};
+
+/// MouseButtons maps the physical mouse buttons to the roles widgets respond
+/// to.
+///
+/// Widgets always treat `Button1` as the primary button, `Button2` as the
+/// secondary button, and `Button3` as the middle button. The UI rewrites
+/// each mouse button event with this mapping before any widget sees it, so
+/// the buttons can be swapped, e.g. for left-handed users, without changing
+/// any widget.
+///
+/// Most window systems already apply the OS-level left-handed setting
+/// before GLFW reports a button, so the default mapping is usually right.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MouseButtons {
+    /// The physical button which clicks, selects, and drags.
+    pub primary: MouseButton,
+
+    /// The physical button which opens context menus.
+    pub secondary: MouseButton,
+
+    /// The physical button which pans views like the node editor.
+    pub middle: MouseButton,
+}
+
+impl Default for MouseButtons {
+    /// The left button is primary and the right button is secondary.
+    fn default() -> Self {
+        Self {
+            primary: MouseButton::Button1,
+            secondary: MouseButton::Button2,
+            middle: MouseButton::Button3,
+        }
+    }
+}
+
+impl MouseButtons {
+    /// The right button is primary and the left button is secondary.
+    pub fn left_handed() -> Self {
+        Self {
+            primary: MouseButton::Button2,
+            secondary: MouseButton::Button1,
+            middle: MouseButton::Button3,
+        }
+    }
+
+    /// The role of a physical button, as the button widgets expect for it.
+    /// Buttons which don't have a role keep their own number, unless that
+    /// number belongs to a role, then they're ignored with None.
+    pub fn role_of(&self, physical: MouseButton) -> Option<MouseButton> {
+        let roles = [
+            (self.primary, MouseButton::Button1),
+            (self.secondary, MouseButton::Button2),
+            (self.middle, MouseButton::Button3),
+        ];
+        if let Some((_, role)) = roles.iter().find(|(b, _)| *b == physical) {
+            return Some(*role);
+        }
+        if roles.iter().any(|(_, role)| *role == physical) {
+            None
+        } else {
+            Some(physical)
+        }
+    }
+
+    /// Rewrite a mouse button event so its button is the button's role.
+    /// Other events are returned unchanged, and events for buttons which
+    /// are ignored become None.
+    pub fn to_roles(&self, event: &WindowEvent) -> Option<WindowEvent> {
+        match *event {
+            WindowEvent::MouseButton(button, action, modifiers) => self
+                .role_of(button)
+                .map(|role| WindowEvent::MouseButton(role, action, modifiers)),
+            _ => Some(event.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_left_handed_buttons_swap_roles() {
+        let buttons = MouseButtons::left_handed();
+
+        assert_eq!(
+            buttons.role_of(MouseButton::Button2),
+            Some(MouseButton::Button1)
+        );
+        assert_eq!(
+            buttons.role_of(MouseButton::Button1),
+            Some(MouseButton::Button2)
+        );
+        assert_eq!(
+            buttons.role_of(MouseButton::Button5),
+            Some(MouseButton::Button5)
+        );
+
+        // moving a role to another button frees up the role's number
+        let side_middle = MouseButtons {
+            middle: MouseButton::Button4,
+            ..MouseButtons::default()
+        };
+        assert_eq!(
+            side_middle.role_of(MouseButton::Button4),
+            Some(MouseButton::Button3)
+        );
+        assert_eq!(side_middle.role_of(MouseButton::Button3), None);
+    }
+}
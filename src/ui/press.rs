@@ -0,0 +1,149 @@
+use ::std::{collections::HashMap, time::Duration};
+
+use crate::ui::Id;
+
+/// How a widget repeats while it's held down, e.g. a spinner arrow or a
+/// scrollbar's end button.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct HoldRepeat {
+    /// How long the widget is held before the first repeat.
+    pub delay: Duration,
+
+    /// The time between each repeat after the first.
+    pub interval: Duration,
+}
+
+impl HoldRepeat {
+    /// Start repeating after the delay, then repeat the given number of
+    /// times per second.
+    pub fn new(delay: Duration, repeats_per_second: f32) -> Self {
+        Self {
+            delay,
+            interval: Duration::from_secs_f32(
+                1.0 / repeats_per_second.max(0.001),
+            ),
+        }
+    }
+}
+
+impl Default for HoldRepeat {
+    /// Waits 400ms, then repeats 20 times per second.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(400), 20.0)
+    }
+}
+
+/// A widget which is being held down.
+#[derive(Debug, Copy, Clone)]
+struct Held {
+    /// The UI time the widget was pressed at.
+    since: Duration,
+
+    /// The number of repeats which have fired since the press.
+    repeats: u32,
+}
+
+/// Presses tracks which widgets are being held down and since when, so
+/// widgets can repeat while held or react to long presses.
+///
+/// Widgets which disappear while pressed never report their release, so the
+/// UI forgets every press when the mouse button is released.
+#[derive(Debug, Default)]
+pub(crate) struct Presses {
+    held: HashMap<Id, Held>,
+}
+
+impl Presses {
+    /// Record whether a widget is pressed at the given UI time. Widgets keep
+    /// the time they were first pressed while they stay pressed.
+    pub(crate) fn set(&mut self, id: Id, pressed: bool, now: Duration) {
+        if pressed {
+            self.held.entry(id).or_insert(Held {
+                since: now,
+                repeats: 0,
+            });
+        } else {
+            self.held.remove(&id);
+        }
+    }
+
+    /// The UI time the widget was pressed at, if it's pressed.
+    pub(crate) fn since(&self, id: &Id) -> Option<Duration> {
+        self.held.get(id).map(|held| held.since)
+    }
+
+    /// True when the widget has been held long enough for another repeat.
+    /// The first repeat is after the delay, then one every interval. Repeats
+    /// which were missed, e.g. during a slow frame, are skipped rather than
+    /// fired all at once.
+    pub(crate) fn take_repeat(
+        &mut self,
+        id: &Id,
+        now: Duration,
+        repeat: HoldRepeat,
+    ) -> bool {
+        let held = match self.held.get_mut(id) {
+            Some(held) => held,
+            None => return false,
+        };
+        let elapsed =
+            match now.saturating_sub(held.since).checked_sub(repeat.delay) {
+                Some(elapsed) => elapsed,
+                None => return false,
+            };
+        let interval = repeat.interval.max(Duration::from_millis(1));
+        let due = (elapsed.as_nanos() / interval.as_nanos()) as u32 + 1;
+        if due > held.repeats {
+            held.repeats = due;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// True when any widget is pressed.
+    pub(crate) fn any(&self) -> bool {
+        !self.held.is_empty()
+    }
+
+    /// Forget every press.
+    pub(crate) fn clear(&mut self) {
+        self.held.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_repeats_start_after_the_delay() {
+        let mut presses = Presses::default();
+        let id = Id::new(1);
+        let ms = Duration::from_millis;
+        let hold_repeat = HoldRepeat {
+            delay: ms(400),
+            interval: ms(100),
+        };
+        let repeat = |presses: &mut Presses, now| {
+            presses.take_repeat(&id, ms(now), hold_repeat)
+        };
+
+        presses.set(id, true, ms(1000));
+        presses.set(id, true, ms(1200));
+        assert_eq!(presses.since(&id), Some(ms(1000)));
+
+        assert!(!repeat(&mut presses, 1300));
+        assert!(repeat(&mut presses, 1400));
+        assert!(!repeat(&mut presses, 1450));
+        assert!(repeat(&mut presses, 1500));
+
+        // a slow frame skips the missed repeats
+        assert!(repeat(&mut presses, 1990));
+        assert!(!repeat(&mut presses, 1995));
+
+        presses.set(id, false, ms(2000));
+        assert!(!repeat(&mut presses, 3000));
+        assert!(!presses.any());
+    }
+}
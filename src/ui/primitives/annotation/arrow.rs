@@ -0,0 +1,176 @@
+use anyhow::Result;
+
+use crate::{
+    asset_loader::TextureId,
+    immediate_mode_graphics::{Vertex, VertexStream},
+    ui::primitives::Line,
+    vec2, vec3, vec4, Vec2, Vec4,
+};
+
+/// The shape drawn at either end of an [`Arrow`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ArrowHead {
+    /// Nothing is drawn, the line simply ends.
+    None,
+
+    /// Two strokes angled back from the tip, like a chevron.
+    Open,
+
+    /// A solid triangle.
+    Filled,
+
+    /// A short stroke perpendicular to the line. Commonly used for the ends
+    /// of dimension lines.
+    Bar,
+}
+
+impl Default for ArrowHead {
+    /// Defaults to a filled triangle.
+    fn default() -> Self {
+        Self::Filled
+    }
+}
+
+/// An Arrow is a line with configurable decorations at the start and end.
+#[derive(Debug, Copy, Clone)]
+pub struct Arrow {
+    /// Where the arrow starts. The tail is drawn here.
+    pub start: Vec2,
+
+    /// Where the arrow points. The head is drawn here.
+    pub end: Vec2,
+
+    /// The width of the arrow's shaft and strokes. Defaults to 1.0.
+    pub width: f32,
+
+    /// The arrow's world space depth. Defaults to 0.0.
+    pub depth: f32,
+
+    /// The arrow's rgba color, defaults to white.
+    pub color: Vec4,
+
+    /// The shape drawn at the end of the arrow. Defaults to Filled.
+    pub head: ArrowHead,
+
+    /// The shape drawn at the start of the arrow. Defaults to None.
+    pub tail: ArrowHead,
+
+    /// The distance from the tip of a head to its base. Defaults to 10.0.
+    pub head_length: f32,
+
+    /// The total width of a head at its base. Defaults to 8.0.
+    pub head_width: f32,
+}
+
+impl Default for Arrow {
+    fn default() -> Self {
+        Self {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, 0.0),
+            width: 1.0,
+            depth: 0.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+            head: ArrowHead::Filled,
+            tail: ArrowHead::None,
+            head_length: 10.0,
+            head_width: 8.0,
+        }
+    }
+}
+
+impl Arrow {
+    /// Render the arrow.
+    pub fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        let direction = self.end - self.start;
+        let length = direction.norm();
+        if length <= f32::EPSILON {
+            return Ok(());
+        }
+        let forward = direction / length;
+
+        // Filled heads would overlap the shaft, so shorten the shaft to end
+        // at the base of the head. This keeps translucent arrows from showing
+        // a darker seam.
+        let shaft_start = match self.tail {
+            ArrowHead::Filled => {
+                self.start + forward * self.head_length.min(length)
+            }
+            _ => self.start,
+        };
+        let shaft_end = match self.head {
+            ArrowHead::Filled => {
+                self.end - forward * self.head_length.min(length)
+            }
+            _ => self.end,
+        };
+
+        self.stroke()
+            .draw_polyline(&[shaft_start, shaft_end], vertices)?;
+        self.draw_head(self.head, self.end, forward, vertices)?;
+        self.draw_head(self.tail, self.start, -forward, vertices)?;
+
+        Ok(())
+    }
+
+    /// Draw a single arrow head with its tip at the given point, pointing in
+    /// the given direction.
+    fn draw_head(
+        &self,
+        head: ArrowHead,
+        tip: Vec2,
+        forward: Vec2,
+        vertices: &mut impl VertexStream,
+    ) -> Result<()> {
+        let normal = vec2(-forward.y, forward.x);
+        let base = tip - forward * self.head_length;
+        let half_width = 0.5 * self.head_width;
+
+        match head {
+            ArrowHead::None => Ok(()),
+            ArrowHead::Open => {
+                let stroke = self.stroke();
+                stroke.draw_polyline(
+                    &[base + normal * half_width, tip],
+                    vertices,
+                )?;
+                stroke
+                    .draw_polyline(&[base - normal * half_width, tip], vertices)
+            }
+            ArrowHead::Filled => {
+                let left = base + normal * half_width;
+                let right = base - normal * half_width;
+                vertices.push_vertices(
+                    &[
+                        self.vertex(tip, vec2(0.5, 0.0)),
+                        self.vertex(left, vec2(0.0, 1.0)),
+                        self.vertex(right, vec2(1.0, 1.0)),
+                    ],
+                    &[0, 1, 2],
+                )
+            }
+            ArrowHead::Bar => self.stroke().draw_polyline(
+                &[tip + normal * half_width, tip - normal * half_width],
+                vertices,
+            ),
+        }
+    }
+
+    /// The line properties used for every stroke in this arrow.
+    fn stroke(&self) -> Line {
+        Line {
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            ..Default::default()
+        }
+    }
+
+    fn vertex(&self, position: Vec2, uv: Vec2) -> Vertex {
+        Vertex::new(
+            vec3(position.x, position.y, self.depth),
+            self.color,
+            uv,
+            TextureId::WHITE_PIXEL,
+        )
+    }
+}
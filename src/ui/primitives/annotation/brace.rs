@@ -0,0 +1,148 @@
+use anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::VertexStream, ui::primitives::Line, vec2, vec4,
+    Vec2, Vec4,
+};
+
+/// The number of line segments used to approximate each curve in a curly
+/// brace.
+const CURVE_SEGMENTS: usize = 8;
+
+/// The visual style of a [`Brace`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BraceStyle {
+    /// A curly brace with a pointed tip in the middle: `}`
+    Curly,
+
+    /// A square bracket: `]`
+    Square,
+}
+
+impl Default for BraceStyle {
+    /// Defaults to a curly brace.
+    fn default() -> Self {
+        Self::Curly
+    }
+}
+
+/// A Brace groups the span between two points with a curly brace or a square
+/// bracket.
+///
+/// The brace bulges out to the left of the line from start to end. Swap the
+/// points to flip it to the other side.
+#[derive(Debug, Copy, Clone)]
+pub struct Brace {
+    /// The first end of the brace.
+    pub start: Vec2,
+
+    /// The second end of the brace.
+    pub end: Vec2,
+
+    /// How far the brace bulges away from the line between start and end.
+    /// A curly brace's tip extends twice this distance. Defaults to 8.0.
+    pub bulge: f32,
+
+    /// The brace's style. Defaults to Curly.
+    pub style: BraceStyle,
+
+    /// The width of the brace's stroke. Defaults to 1.0.
+    pub width: f32,
+
+    /// The brace's world space depth. Defaults to 0.0.
+    pub depth: f32,
+
+    /// The brace's rgba color, defaults to white.
+    pub color: Vec4,
+}
+
+impl Default for Brace {
+    fn default() -> Self {
+        Self {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, 0.0),
+            bulge: 8.0,
+            style: BraceStyle::Curly,
+            width: 1.0,
+            depth: 0.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl Brace {
+    /// Render the brace.
+    pub fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        let direction = self.end - self.start;
+        let length = direction.norm();
+        if length <= f32::EPSILON {
+            return Ok(());
+        }
+        let normal = vec2(-direction.y, direction.x) / length * self.bulge;
+
+        let points = match self.style {
+            BraceStyle::Square => vec![
+                self.start,
+                self.start + normal,
+                self.end + normal,
+                self.end,
+            ],
+            BraceStyle::Curly => {
+                let midpoint = self.start + 0.5 * direction;
+                let first_quarter = self.start + 0.25 * direction;
+                let last_quarter = self.start + 0.75 * direction;
+                let tip = midpoint + 2.0 * normal;
+
+                let mut points = Vec::with_capacity(4 * CURVE_SEGMENTS + 1);
+                points.push(self.start);
+                quadratic_bezier(
+                    self.start,
+                    self.start + normal,
+                    first_quarter + normal,
+                    &mut points,
+                );
+                quadratic_bezier(
+                    first_quarter + normal,
+                    midpoint + normal,
+                    tip,
+                    &mut points,
+                );
+                quadratic_bezier(
+                    tip,
+                    midpoint + normal,
+                    last_quarter + normal,
+                    &mut points,
+                );
+                quadratic_bezier(
+                    last_quarter + normal,
+                    self.end + normal,
+                    self.end,
+                    &mut points,
+                );
+                points
+            }
+        };
+
+        Line {
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            ..Default::default()
+        }
+        .draw_polyline(&points, vertices)
+    }
+}
+
+/// Append points along a quadratic bezier curve, excluding the start point.
+fn quadratic_bezier(
+    start: Vec2,
+    control: Vec2,
+    end: Vec2,
+    points: &mut Vec<Vec2>,
+) {
+    for i in 1..=CURVE_SEGMENTS {
+        let t = i as f32 / CURVE_SEGMENTS as f32;
+        let inv = 1.0 - t;
+        points.push(inv * inv * start + 2.0 * inv * t * control + t * t * end);
+    }
+}
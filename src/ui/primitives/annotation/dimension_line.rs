@@ -0,0 +1,129 @@
+use anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::VertexStream,
+    ui::{
+        primitives::{annotation::draw_label, Arrow, ArrowHead, Line},
+        Font,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A DimensionLine measures the distance between two points like a technical
+/// drawing: extension lines rise from each point, a double-headed arrow spans
+/// them, and an optional label is centered over the arrow.
+#[derive(Debug, Copy, Clone)]
+pub struct DimensionLine {
+    /// The first measured point.
+    pub start: Vec2,
+
+    /// The second measured point.
+    pub end: Vec2,
+
+    /// How far the dimension arrow sits from the measured points, measured to
+    /// the left of the line from start to end. Defaults to 16.0.
+    pub offset: f32,
+
+    /// How far the extension lines continue past the dimension arrow.
+    /// Defaults to 4.0.
+    pub overshoot: f32,
+
+    /// The shape drawn at both ends of the dimension arrow.
+    /// Defaults to Filled.
+    pub head: ArrowHead,
+
+    /// The width of every stroke. Defaults to 1.0.
+    pub width: f32,
+
+    /// The world space depth. Defaults to 0.0.
+    pub depth: f32,
+
+    /// The rgba color of the strokes, defaults to white. Labels use the
+    /// font's text color.
+    pub color: Vec4,
+}
+
+impl Default for DimensionLine {
+    fn default() -> Self {
+        Self {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, 0.0),
+            offset: 16.0,
+            overshoot: 4.0,
+            head: ArrowHead::Filled,
+            width: 1.0,
+            depth: 0.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl DimensionLine {
+    /// Render the dimension line without a label.
+    pub fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        let normal = match self.normal() {
+            Some(normal) => normal,
+            None => return Ok(()),
+        };
+
+        let stroke = Line {
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            ..Default::default()
+        };
+        let extension = normal * (self.offset + self.overshoot);
+        stroke
+            .draw_polyline(&[self.start, self.start + extension], vertices)?;
+        stroke.draw_polyline(&[self.end, self.end + extension], vertices)?;
+
+        Arrow {
+            start: self.start + normal * self.offset,
+            end: self.end + normal * self.offset,
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            head: self.head,
+            tail: self.head,
+            ..Default::default()
+        }
+        .draw(vertices)
+    }
+
+    /// Render the dimension line with a text label centered just outside of
+    /// the dimension arrow.
+    ///
+    /// Labels are laid out in UI Screen Space, e.g. they assume that +Y
+    /// points down.
+    pub fn draw_with_label<T>(
+        &self,
+        font: &Font,
+        text: T,
+        vertices: &mut impl VertexStream,
+    ) -> Result<()>
+    where
+        T: AsRef<str>,
+    {
+        self.draw(vertices)?;
+
+        let normal = match self.normal() {
+            Some(normal) => normal,
+            None => return Ok(()),
+        };
+        let midpoint = 0.5 * (self.start + self.end);
+        let label_offset = self.offset + 0.75 * font.line_height();
+        draw_label(font, text, midpoint + normal * label_offset, vertices)
+    }
+
+    /// The unit vector which points to the left of the line from start to
+    /// end. None if the points are the same.
+    fn normal(&self) -> Option<Vec2> {
+        let direction = self.end - self.start;
+        let length = direction.norm();
+        if length <= f32::EPSILON {
+            None
+        } else {
+            Some(vec2(-direction.y, direction.x) / length)
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::VertexStream,
+    ui::{
+        primitives::{annotation::draw_label, Arrow, ArrowHead, Line},
+        Font,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A LeaderLine connects a callout label to the thing it describes. The line
+/// runs from the target to an elbow, then a short horizontal landing which
+/// the label sits beside.
+#[derive(Debug, Copy, Clone)]
+pub struct LeaderLine {
+    /// The point being described. The head is drawn here.
+    pub target: Vec2,
+
+    /// Where the leader bends onto the horizontal landing.
+    pub elbow: Vec2,
+
+    /// The length of the horizontal landing. The landing extends away from
+    /// the target. Defaults to 16.0.
+    pub landing_length: f32,
+
+    /// The shape drawn at the target. Defaults to Open.
+    pub head: ArrowHead,
+
+    /// The width of every stroke. Defaults to 1.0.
+    pub width: f32,
+
+    /// The world space depth. Defaults to 0.0.
+    pub depth: f32,
+
+    /// The rgba color of the strokes, defaults to white. Labels use the
+    /// font's text color.
+    pub color: Vec4,
+}
+
+impl Default for LeaderLine {
+    fn default() -> Self {
+        Self {
+            target: vec2(0.0, 0.0),
+            elbow: vec2(0.0, 0.0),
+            landing_length: 16.0,
+            head: ArrowHead::Open,
+            width: 1.0,
+            depth: 0.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+}
+
+impl LeaderLine {
+    /// Render the leader line without a label.
+    pub fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        Arrow {
+            start: self.elbow,
+            end: self.target,
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            head: self.head,
+            tail: ArrowHead::None,
+            ..Default::default()
+        }
+        .draw(vertices)?;
+
+        Line {
+            start: self.elbow,
+            end: self.landing_end(),
+            width: self.width,
+            depth: self.depth,
+            color: self.color,
+            ..Default::default()
+        }
+        .draw(vertices)
+    }
+
+    /// Render the leader line with a text label just past the end of the
+    /// landing.
+    ///
+    /// Labels are laid out in UI Screen Space, e.g. they assume that +Y
+    /// points down.
+    pub fn draw_with_label<T>(
+        &self,
+        font: &Font,
+        text: T,
+        vertices: &mut impl VertexStream,
+    ) -> Result<()>
+    where
+        T: AsRef<str>,
+    {
+        self.draw(vertices)?;
+
+        let (tiles, bounds) = font.build_text_tiles(&text);
+        if tiles.is_empty() {
+            return Ok(());
+        }
+        let padding = 0.25 * font.line_height();
+        let half_width = 0.5 * bounds.width() + padding;
+        let label_center = self.landing_end()
+            + vec2(self.landing_direction() * half_width, 0.0);
+        draw_label(font, text, label_center, vertices)
+    }
+
+    /// The landing always points away from the target horizontally.
+    /// -1.0 is left and 1.0 is right.
+    fn landing_direction(&self) -> f32 {
+        if self.elbow.x < self.target.x {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    fn landing_end(&self) -> Vec2 {
+        self.elbow + vec2(self.landing_direction() * self.landing_length, 0.0)
+    }
+}
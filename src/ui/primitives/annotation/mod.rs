@@ -0,0 +1,39 @@
+//! Primitives for drawing annotated diagrams: arrows, braces, dimension lines,
+//! and leader lines.
+
+mod arrow;
+mod brace;
+mod dimension_line;
+mod leader_line;
+
+use anyhow::Result;
+
+use crate::{immediate_mode_graphics::VertexStream, ui::Font, vec2, Vec2};
+
+pub use self::{
+    arrow::{Arrow, ArrowHead},
+    brace::{Brace, BraceStyle},
+    dimension_line::DimensionLine,
+    leader_line::LeaderLine,
+};
+
+/// Render text so that its bounding box is centered on the given point.
+fn draw_label<T>(
+    font: &Font,
+    text: T,
+    center: Vec2,
+    vertices: &mut impl VertexStream,
+) -> Result<()>
+where
+    T: AsRef<str>,
+{
+    let (mut tiles, bounds) = font.build_text_tiles(text);
+    let bounds_center = 0.5 * (bounds.top_left + bounds.bottom_right);
+    let raw_offset = center - bounds_center;
+    let offset = vec2(raw_offset.x.round(), raw_offset.y.round());
+    for tile in &mut tiles {
+        tile.model = tile.model.translate(offset);
+        tile.fill(vertices)?;
+    }
+    Ok(())
+}
@@ -128,6 +128,18 @@ impl Axis {
         }
     }
 
+    /// Replace the dimension along the current axis.
+    ///
+    /// Horizontal - Keep the original height and replace the width
+    /// Vertical - Keep the original width and replace the height
+    ///
+    pub(super) fn set(&self, original: &Dimensions, value: f32) -> Dimensions {
+        match *self {
+            Axis::Horizontal => Dimensions::new(value, original.height),
+            Axis::Vertical => Dimensions::new(original.width, value),
+        }
+    }
+
     /// Create a vector which points in the direction of the current axis.
     ///
     /// Horizontal - vec2(value, 0.0)
@@ -1,6 +1,13 @@
 mod axis;
 
-use crate::{builder_field, ui::primitives::Dimensions, vec2, Vec2};
+use crate::{
+    builder_field,
+    ui::{
+        frame_arena::{temp_vec_with_capacity, TempVec},
+        primitives::Dimensions,
+    },
+    vec2, Vec2,
+};
 
 pub use self::axis::Axis;
 
@@ -26,6 +33,33 @@ pub enum SpaceBetween {
     EvenSpaceAround,
 }
 
+/// How a child of a [`DimensionList`] is sized along the main axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Sizing {
+    /// The child keeps its natural size.
+    Natural,
+
+    /// The child ignores its natural size and fills a share of the space
+    /// the other children leave over, in proportion to its weight.
+    Flex(f32),
+
+    /// The child keeps its natural size and grows by a share of the space
+    /// left over once every child has been measured, in proportion to its
+    /// weight. Like flex children, but children with more content stay
+    /// bigger.
+    Grow(f32),
+}
+
+impl Sizing {
+    /// The child's share of the leftover space, zero for natural children.
+    fn weight(self) -> f32 {
+        match self {
+            Self::Natural => 0.0,
+            Self::Flex(weight) | Self::Grow(weight) => weight.max(0.0),
+        }
+    }
+}
+
 /// This type represents a collection of objects with dimensions.
 /// It provides methods for computing layout positions for each child given
 /// size constraints and layout parameters.
@@ -136,9 +170,87 @@ impl DimensionList {
             .sub(&self.max_size, &self.total_children_size)
     }
 
+    /// Measure and add every child to the list.
+    ///
+    /// Each child is given as its justification and sizing, see [`Sizing`].
+    /// `measure` is called with each child's index and the space available
+    /// to it, and returns the child's dimensions. Natural and grow children
+    /// are measured first, in order, then the main axis space they leave
+    /// over is split between flex and grow children in proportion to their
+    /// weights.
+    ///
+    pub fn add_children<F>(
+        &mut self,
+        children: &[(Justify, Sizing)],
+        mut measure: F,
+    ) where
+        F: FnMut(usize, &Dimensions) -> Dimensions,
+    {
+        let total_weight: f32 =
+            children.iter().map(|(_, sizing)| sizing.weight()).sum();
+        if total_weight <= 0.0 {
+            let mut remaining_size = self.max_size;
+            for (index, (justify, _)) in children.iter().enumerate() {
+                let child_dimensions = measure(index, &remaining_size);
+                remaining_size =
+                    self.add_child_dimensions(child_dimensions, *justify);
+            }
+            return;
+        }
+
+        let mut sizes = temp_vec_with_capacity(children.len());
+        let mut measured_size = Dimensions::new(0.0, 0.0);
+        for (index, (_, sizing)) in children.iter().enumerate() {
+            if let Sizing::Flex(weight) = *sizing {
+                if weight > 0.0 {
+                    sizes.push(None);
+                    continue;
+                }
+            }
+            let remaining_size = self.main_axis.set(
+                &self.max_size,
+                (self.main_axis.get(&self.max_size)
+                    - self.main_axis.get(&measured_size))
+                .max(0.0),
+            );
+            let child_dimensions = measure(index, &remaining_size);
+            measured_size =
+                self.main_axis.sum(&measured_size, &child_dimensions);
+            sizes.push(Some(child_dimensions));
+        }
+
+        let spacing = match self.space_between {
+            SpaceBetween::Fixed(size) => size * (children.len() - 1) as f32,
+            _ => 0.0,
+        };
+        let leftover_space = (self.main_axis.get(&self.max_size)
+            - self.main_axis.get(&measured_size)
+            - spacing)
+            .max(0.0);
+
+        for (index, (justify, sizing)) in children.iter().enumerate() {
+            let share = leftover_space * sizing.weight() / total_weight;
+            let child_dimensions = match (sizes[index], *sizing) {
+                (Some(natural), Sizing::Grow(_)) if share > 0.0 => {
+                    let size = self.main_axis.get(&natural) + share;
+                    let max_size = self.main_axis.set(&self.max_size, size);
+                    self.main_axis.set(&measure(index, &max_size), size)
+                }
+                (Some(child_dimensions), _) => child_dimensions,
+                (None, _) => {
+                    let max_size = self.main_axis.set(&self.max_size, share);
+                    // the child occupies its whole share, even if it's
+                    // smaller, so the following children don't move
+                    self.main_axis.set(&measure(index, &max_size), share)
+                }
+            };
+            self.add_child_dimensions(child_dimensions, *justify);
+        }
+    }
+
     /// Compute positions - relative to 0,0 in the top left - for each child
     /// element's top left corner.
-    pub fn compute_child_positions(&self) -> Vec<Vec2> {
+    pub fn compute_child_positions(&self) -> TempVec<Vec2> {
         let main_axis_remaining_size = self.main_axis.get(&self.max_size)
             - self.main_axis.get(&self.total_children_size);
         let main_axis_offset = match self.space_between {
@@ -160,7 +272,7 @@ impl DimensionList {
             _ => vec2(0.0, 0.0),
         };
 
-        let mut child_positions = Vec::with_capacity(self.children.len());
+        let mut child_positions = temp_vec_with_capacity(self.children.len());
         for (child, justify) in &self.children {
             let off_axis_remaining_size =
                 self.off_axis.get(&self.total_children_size)
@@ -182,3 +294,58 @@ impl DimensionList {
         child_positions
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flex_children_share_the_remaining_space() {
+        let mut list = DimensionList::horizontal()
+            .space_between(SpaceBetween::Fixed(10.0));
+        list.set_max_size(&Dimensions::new(200.0, 50.0));
+
+        let children = [
+            (Justify::Begin, Sizing::Natural),
+            (Justify::Begin, Sizing::Flex(1.0)),
+            (Justify::Begin, Sizing::Flex(3.0)),
+        ];
+        let mut max_sizes = [Dimensions::new(0.0, 0.0); 3];
+        list.add_children(&children, |index, max_size| {
+            max_sizes[index] = *max_size;
+            Dimensions::new(20.0, 10.0).min(max_size)
+        });
+
+        // 200 wide, less 20 for the fixed child and 20 for the spacing
+        assert_eq!(max_sizes[0], Dimensions::new(200.0, 50.0));
+        assert_eq!(max_sizes[1], Dimensions::new(40.0, 50.0));
+        assert_eq!(max_sizes[2], Dimensions::new(120.0, 50.0));
+        assert_eq!(list.dimensions(), Dimensions::new(200.0, 10.0));
+
+        let positions = list.compute_child_positions();
+        assert_eq!(positions[1], vec2(30.0, 0.0));
+        assert_eq!(positions[2], vec2(80.0, 0.0));
+    }
+
+    #[test]
+    fn test_grow_children_keep_their_natural_size() {
+        let mut list = DimensionList::horizontal();
+        list.set_max_size(&Dimensions::new(200.0, 50.0));
+
+        let natural_widths = [20.0, 60.0, 20.0];
+        let children = [
+            (Justify::Begin, Sizing::Natural),
+            (Justify::Begin, Sizing::Grow(1.0)),
+            (Justify::Begin, Sizing::Grow(1.0)),
+        ];
+        list.add_children(&children, |index, max_size| {
+            Dimensions::new(natural_widths[index], 10.0).min(max_size)
+        });
+
+        // 100 wide is left over, split evenly on top of the natural widths
+        let positions = list.compute_child_positions();
+        assert_eq!(positions[1], vec2(20.0, 0.0));
+        assert_eq!(positions[2], vec2(130.0, 0.0));
+        assert_eq!(list.dimensions(), Dimensions::new(200.0, 10.0));
+    }
+}
@@ -1,3 +1,5 @@
+use ::ash::vk;
+
 use crate::{ui::primitives::Rect, vec2, Vec2};
 
 /// The Dimensions of something on screen.
@@ -25,6 +27,23 @@ impl Dimensions {
         Self::new(self.width.max(other.width), self.height.max(other.height))
     }
 
+    /// Grow by the size of each side of the insets, e.g. to fit a child's
+    /// dimensions plus a Container's margin.
+    pub fn outset(&self, insets: &Rect) -> Self {
+        Self::new(
+            self.width + insets.left() + insets.right(),
+            self.height + insets.top() + insets.bottom(),
+        )
+    }
+
+    /// Shrink by the size of each side of the insets, never below zero.
+    pub fn inset(&self, insets: &Rect) -> Self {
+        Self::new(
+            (self.width - insets.left() - insets.right()).max(0.0),
+            (self.height - insets.top() - insets.bottom()).max(0.0),
+        )
+    }
+
     /// Create a rect with top-left at (0, 0) and dimensions matching these
     /// dimensions.
     pub fn as_rect(&self) -> Rect {
@@ -32,6 +51,22 @@ impl Dimensions {
     }
 }
 
+impl From<vk::Extent2D> for Dimensions {
+    fn from(extent: vk::Extent2D) -> Self {
+        Self::new(extent.width as f32, extent.height as f32)
+    }
+}
+
+impl From<Dimensions> for vk::Extent2D {
+    /// The smallest whole-pixel extent which covers the dimensions.
+    fn from(dimensions: Dimensions) -> Self {
+        vk::Extent2D {
+            width: dimensions.width.ceil().max(0.0) as u32,
+            height: dimensions.height.ceil().max(0.0) as u32,
+        }
+    }
+}
+
 impl Into<Dimensions> for Vec2 {
     fn into(self) -> Dimensions {
         Dimensions {
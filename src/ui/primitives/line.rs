@@ -0,0 +1,294 @@
+use anyhow::Result;
+
+use crate::{
+    asset_loader::TextureId,
+    immediate_mode_graphics::{Vertex, VertexStream},
+    vec2, vec3, vec4, Vec2, Vec4,
+};
+
+/// A Line is a straight stroke between two points which is rendered as a
+/// single quad.
+///
+/// Antialiased lines are one pixel wider on each side and fade out across
+/// that pixel, so thin lines look smooth without MSAA. The ends of the line
+/// are not antialiased, which lets the segments of a polyline meet without
+/// overlapping.
+///
+/// The end of the line can have its own color and width, for gradient or
+/// tapered strokes. The line is drawn as a trapezoid which blends between
+/// the two ends.
+#[derive(Debug, Copy, Clone)]
+pub struct Line {
+    /// The line's starting point.
+    pub start: Vec2,
+
+    /// The line's ending point.
+    pub end: Vec2,
+
+    /// The line's width. Defaults to 1.0.
+    pub width: f32,
+
+    /// The line's width at its end, when it's different from the start.
+    /// Defaults to None, which uses the same width along the whole line.
+    pub end_width: Option<f32>,
+
+    /// The line's world space depth. Defaults to 0.0.
+    pub depth: f32,
+
+    /// The line's rgba color, defaults to white.
+    pub color: Vec4,
+
+    /// The line's rgba color at its end, when it's different from the
+    /// start. Defaults to None, which uses the same color along the whole
+    /// line.
+    pub end_color: Option<Vec4>,
+
+    /// The texture index to use when rendering the line.
+    /// Defaults to [`TextureId::WHITE_PIXEL`].
+    pub texture_index: TextureId,
+
+    /// Fade the line's edges based on the distance from its centerline.
+    /// Defaults to false.
+    pub antialiased: bool,
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, 0.0),
+            width: 1.0,
+            end_width: None,
+            depth: 0.0,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+            end_color: None,
+            texture_index: TextureId::WHITE_PIXEL,
+            antialiased: false,
+        }
+    }
+}
+
+impl Line {
+    /// The width at the end of the line.
+    pub fn width_at_end(&self) -> f32 {
+        self.end_width.unwrap_or(self.width)
+    }
+
+    /// The color at the end of the line.
+    pub fn color_at_end(&self) -> Vec4 {
+        self.end_color.unwrap_or(self.color)
+    }
+
+    /// Render the line.
+    pub fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        let direction = self.end - self.start;
+        let length = direction.norm();
+        if length <= f32::EPSILON {
+            return Ok(());
+        }
+        let normal = vec2(-direction.y, direction.x) / length;
+        if self.antialiased {
+            return self.draw_antialiased(normal, vertices);
+        }
+        let start_offset = normal * 0.5 * self.width;
+        let end_offset = normal * 0.5 * self.width_at_end();
+        let (start_color, end_color) = (self.color, self.color_at_end());
+
+        let corners = [
+            (self.start + start_offset, start_color),
+            (self.end + end_offset, end_color),
+            (self.end - end_offset, end_color),
+            (self.start - start_offset, start_color),
+        ];
+        let uvs = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 0.0),
+            vec2(1.0, 1.0),
+            vec2(0.0, 1.0),
+        ];
+        let mut quad = [Vertex::default(); 4];
+        for (vertex, ((corner, color), uv)) in
+            quad.iter_mut().zip(corners.iter().zip(uvs.iter()))
+        {
+            *vertex = Vertex::new(
+                vec3(corner.x, corner.y, self.depth),
+                *color,
+                *uv,
+                self.texture_index,
+            );
+        }
+
+        vertices.push_vertices(
+            &quad,
+            &[
+                0, 1, 2, // first triangle
+                2, 3, 0, // second triangle
+            ],
+        )
+    }
+
+    /// Render the line as two quads which meet at the centerline. The edge
+    /// fade is linear in the distance from the outer edges, so interpolating
+    /// it across each quad computes the coverage of every fragment. Tapered
+    /// lines fade by the width at each end, which is close enough while
+    /// the taper is gradual.
+    fn draw_antialiased(
+        &self,
+        normal: Vec2,
+        vertices: &mut impl VertexStream,
+    ) -> Result<()> {
+        let start_half_width = 0.5 * self.width;
+        let end_half_width = 0.5 * self.width_at_end();
+        let start_offset = normal * (start_half_width + 1.0);
+        let end_offset = normal * (end_half_width + 1.0);
+
+        // fragments are half covered at the line's true edge, half a pixel
+        // inside the fade
+        let outer_fade = 1.5;
+        let start_center_fade = outer_fade - (start_half_width + 1.0);
+        let end_center_fade = outer_fade - (end_half_width + 1.0);
+        let (start_color, end_color) = (self.color, self.color_at_end());
+
+        let corners = [
+            (self.start + start_offset, vec2(0.0, 0.0), outer_fade),
+            (self.end + end_offset, vec2(1.0, 0.0), outer_fade),
+            (self.end, vec2(1.0, 0.5), end_center_fade),
+            (self.start, vec2(0.0, 0.5), start_center_fade),
+            (self.end - end_offset, vec2(1.0, 1.0), outer_fade),
+            (self.start - start_offset, vec2(0.0, 1.0), outer_fade),
+        ];
+        let mut quads = [Vertex::default(); 6];
+        for (vertex, (corner, uv, edge_fade)) in
+            quads.iter_mut().zip(corners.iter())
+        {
+            // the uv's x is 0 at the start and 1 at the end
+            let color = if uv.x == 0.0 { start_color } else { end_color };
+            *vertex = Vertex {
+                edge_fade: *edge_fade,
+                ..Vertex::new(
+                    vec3(corner.x, corner.y, self.depth),
+                    color,
+                    *uv,
+                    self.texture_index,
+                )
+            };
+        }
+
+        vertices.push_vertices(
+            &quads,
+            &[
+                0, 1, 2, 2, 3, 0, // the quad on the normal's side
+                3, 2, 4, 4, 5, 3, // the quad on the opposite side
+            ],
+        )
+    }
+
+    /// Render a connected sequence of line segments through each of the given
+    /// points. Every segment shares this line's depth and texture. The width
+    /// and color blend from this line's start values at the first point to
+    /// its end values at the last point, by distance along the polyline. The
+    /// start and end fields are ignored.
+    pub fn draw_polyline(
+        &self,
+        points: &[Vec2],
+        vertices: &mut impl VertexStream,
+    ) -> Result<()> {
+        let total: f32 = points
+            .windows(2)
+            .map(|segment| (segment[1] - segment[0]).norm())
+            .sum();
+        let along = |distance: f32| -> (f32, Vec4) {
+            let t = if total > f32::EPSILON {
+                distance / total
+            } else {
+                0.0
+            };
+            (
+                self.width + (self.width_at_end() - self.width) * t,
+                self.color + (self.color_at_end() - self.color) * t,
+            )
+        };
+        let mut distance = 0.0;
+        for segment in points.windows(2) {
+            let (width, color) = along(distance);
+            distance += (segment[1] - segment[0]).norm();
+            let (end_width, end_color) = along(distance);
+            Line {
+                start: segment[0],
+                end: segment[1],
+                width,
+                end_width: Some(end_width),
+                color,
+                end_color: Some(end_color),
+                ..*self
+            }
+            .draw(vertices)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Recorder(Vec<Vertex>);
+
+    impl VertexStream for Recorder {
+        fn push_vertices(
+            &mut self,
+            vertices: &[Vertex],
+            _indices: &[u32],
+        ) -> Result<()> {
+            self.0.extend_from_slice(vertices);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tapered_gradient_line() {
+        let mut recorder = Recorder(vec![]);
+        Line {
+            start: vec2(0.0, 0.0),
+            end: vec2(10.0, 0.0),
+            width: 4.0,
+            end_width: Some(0.0),
+            color: vec4(1.0, 0.0, 0.0, 1.0),
+            end_color: Some(vec4(0.0, 0.0, 1.0, 1.0)),
+            ..Default::default()
+        }
+        .draw(&mut recorder)
+        .unwrap();
+
+        let (start, end) = (recorder.0[0], recorder.0[1]);
+        assert_eq!(start.pos[1].abs(), 2.0);
+        assert_eq!(end.pos[1], 0.0);
+        assert_eq!(start.rgba, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(end.rgba, [0.0, 0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_antialiased_line_fades_across_its_edge() {
+        let mut recorder = Recorder(vec![]);
+        Line {
+            start: vec2(0.0, 0.0),
+            end: vec2(10.0, 0.0),
+            width: 4.0,
+            antialiased: true,
+            ..Default::default()
+        }
+        .draw(&mut recorder)
+        .unwrap();
+
+        let outer = recorder.0[0];
+        let center = recorder.0[3];
+        assert_eq!(outer.pos[1].abs(), 3.0);
+        assert_eq!(center.pos[1], 0.0);
+
+        // interpolate the fade out to the line's true edge, 2px from center
+        let at_edge = center.edge_fade
+            + (outer.edge_fade - center.edge_fade) * (2.0 / 3.0);
+        assert!((at_edge - 0.5).abs() < 1e-5);
+        assert!(center.edge_fade <= 0.0);
+    }
+}
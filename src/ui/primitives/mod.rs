@@ -1,11 +1,17 @@
+mod annotation;
 mod dimension_list;
 mod dimensions;
+mod line;
 mod rect;
 mod tile;
 
 pub use self::{
-    dimension_list::{Axis, DimensionList, Justify, SpaceBetween},
+    annotation::{
+        Arrow, ArrowHead, Brace, BraceStyle, DimensionLine, LeaderLine,
+    },
+    dimension_list::{Axis, DimensionList, Justify, Sizing, SpaceBetween},
     dimensions::Dimensions,
+    line::Line,
     rect::Rect,
-    tile::Tile,
+    tile::{NineSlice, Tile},
 };
@@ -1,3 +1,5 @@
+use ::ash::vk;
+
 use crate::{ui::primitives::Dimensions, vec2, Vec2};
 
 /// Define a rectangular region on the screen.
@@ -87,6 +89,17 @@ impl Rect {
         self.translate(offset)
     }
 
+    /// Round each edge to the nearest whole pixel. Edges which land between
+    /// pixels are blurred by multisampling.
+    pub fn snap_to_pixels(&self) -> Self {
+        Self::new(
+            self.top().round(),
+            self.left().round(),
+            self.bottom().round(),
+            self.right().round(),
+        )
+    }
+
     /// Returns true if the given point is inside the current rectangular
     /// region.
     pub fn contains(&self, point: Vec2) -> bool {
@@ -108,6 +121,158 @@ impl Rect {
             ),
         }
     }
+
+    /// Create a new rect which fully contains both self and the provided rect.
+    /// The same as [`Self::expand`].
+    pub fn union(&self, other: Rect) -> Self {
+        self.expand(other)
+    }
+
+    /// Create a new rect which covers only the region inside both self and
+    /// the provided rect. Returns None when the rects don't overlap.
+    pub fn intersection(&self, other: Rect) -> Option<Self> {
+        let top_left =
+            vec2(self.left().max(other.left()), self.top().max(other.top()));
+        let bottom_right = vec2(
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        );
+        if top_left.x > bottom_right.x || top_left.y > bottom_right.y {
+            None
+        } else {
+            Some(Self {
+                top_left,
+                bottom_right,
+            })
+        }
+    }
+
+    /// Move each edge inwards by the matching side of the insets, like a
+    /// Container's padding: `insets.left()` is how far the left edge moves
+    /// right, `insets.top()` how far the top edge moves down, and so on.
+    ///
+    /// Edges which would cross meet in the middle, so the result is never
+    /// inside out.
+    pub fn inset(&self, insets: Rect) -> Self {
+        let mut left = self.left() + insets.left();
+        let mut right = self.right() - insets.right();
+        if left > right {
+            left = 0.5 * (left + right);
+            right = left;
+        }
+        let mut top = self.top() + insets.top();
+        let mut bottom = self.bottom() - insets.bottom();
+        if top > bottom {
+            top = 0.5 * (top + bottom);
+            bottom = top;
+        }
+        Self::new(top, left, bottom, right)
+    }
+
+    /// Move every edge inwards by the same amount.
+    pub fn inset_by(&self, amount: f32) -> Self {
+        self.inset(Rect::new(amount, amount, amount, amount))
+    }
+
+    /// Move each edge outwards by the matching side of the insets. The
+    /// opposite of [`Self::inset`].
+    pub fn outset(&self, insets: Rect) -> Self {
+        Self::new(
+            self.top() - insets.top(),
+            self.left() - insets.left(),
+            self.bottom() + insets.bottom(),
+            self.right() + insets.right(),
+        )
+    }
+
+    /// Move every edge outwards by the same amount.
+    pub fn outset_by(&self, amount: f32) -> Self {
+        self.outset(Rect::new(amount, amount, amount, amount))
+    }
+
+    /// The point inside the rect which is closest to the given point.
+    pub fn clamp_point(&self, point: Vec2) -> Vec2 {
+        vec2(
+            point.x.clamp(self.left(), self.right().max(self.left())),
+            point.y.clamp(self.top(), self.bottom().max(self.top())),
+        )
+    }
+
+    /// Split the rect into rows of equal height, from top to bottom.
+    pub fn rows(&self, count: usize) -> Vec<Self> {
+        let height = self.height() / count.max(1) as f32;
+        (0..count)
+            .map(|i| {
+                let top = self.top() + i as f32 * height;
+                Self::new(top, self.left(), top + height, self.right())
+            })
+            .collect()
+    }
+
+    /// Split the rect into columns of equal width, from left to right.
+    pub fn columns(&self, count: usize) -> Vec<Self> {
+        let width = self.width() / count.max(1) as f32;
+        (0..count)
+            .map(|i| {
+                let left = self.left() + i as f32 * width;
+                Self::new(self.top(), left, self.bottom(), left + width)
+            })
+            .collect()
+    }
+
+    /// Split the rect into the given height at the top and whatever is left
+    /// below it. The height is clamped to the rect.
+    pub fn split_top(&self, height: f32) -> (Self, Self) {
+        let y = (self.top() + height.max(0.0)).min(self.bottom());
+        (
+            Self::new(self.top(), self.left(), y, self.right()),
+            Self::new(y, self.left(), self.bottom(), self.right()),
+        )
+    }
+
+    /// Split the rect into the given width on the left and whatever is left
+    /// to the right of it. The width is clamped to the rect.
+    pub fn split_left(&self, width: f32) -> (Self, Self) {
+        let x = (self.left() + width.max(0.0)).min(self.right());
+        (
+            Self::new(self.top(), self.left(), self.bottom(), x),
+            Self::new(self.top(), x, self.bottom(), self.right()),
+        )
+    }
+}
+
+impl From<vk::Rect2D> for Rect {
+    fn from(rect: vk::Rect2D) -> Self {
+        let left = rect.offset.x as f32;
+        let top = rect.offset.y as f32;
+        Self::new(
+            top,
+            left,
+            top + rect.extent.height as f32,
+            left + rect.extent.width as f32,
+        )
+    }
+}
+
+impl From<Rect> for vk::Rect2D {
+    /// The smallest whole-pixel region which covers the rect, so partially
+    /// covered pixels are included.
+    fn from(rect: Rect) -> Self {
+        let left = rect.left().floor();
+        let top = rect.top().floor();
+        let right = rect.right().ceil().max(left);
+        let bottom = rect.bottom().ceil().max(top);
+        vk::Rect2D {
+            offset: vk::Offset2D {
+                x: left as i32,
+                y: top as i32,
+            },
+            extent: vk::Extent2D {
+                width: (right - left) as u32,
+                height: (bottom - top) as u32,
+            },
+        }
+    }
 }
 
 #[cfg(test)]
@@ -182,4 +347,83 @@ mod test {
         assert_eq!(expanded.right(), 10.0);
         assert_eq!(expanded.bottom(), 23.0);
     }
+
+    #[test]
+    fn test_intersection() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let other = Rect::new(5.0, 2.0, 23.0, 5.0);
+        let intersection = rect.intersection(other).unwrap();
+
+        assert_eq!(intersection.top(), 5.0);
+        assert_eq!(intersection.left(), 2.0);
+        assert_eq!(intersection.right(), 5.0);
+        assert_eq!(intersection.bottom(), 10.0);
+
+        let outside = Rect::new(20.0, 0.0, 30.0, 10.0);
+        assert_eq!(rect.intersection(outside), None);
+    }
+
+    #[test]
+    fn test_inset_and_outset() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 20.0);
+        let insets = Rect::new(1.0, 2.0, 3.0, 4.0);
+
+        assert_eq!(rect.inset(insets), Rect::new(1.0, 2.0, 7.0, 16.0));
+        assert_eq!(rect.inset(insets).outset(insets), rect);
+        assert_eq!(rect.outset_by(1.0), Rect::new(-1.0, -1.0, 11.0, 21.0));
+
+        // insets bigger than the rect collapse it at the middle
+        assert_eq!(rect.inset_by(8.0), Rect::new(5.0, 8.0, 5.0, 12.0));
+    }
+
+    #[test]
+    fn test_clamp_point() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 20.0);
+
+        assert_eq!(rect.clamp_point(vec2(5.0, 5.0)), vec2(5.0, 5.0));
+        assert_eq!(rect.clamp_point(vec2(-5.0, 50.0)), vec2(0.0, 10.0));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let rect = Rect::new(0.0, 0.0, 30.0, 20.0);
+
+        let rows = rect.rows(3);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[1], Rect::new(10.0, 0.0, 20.0, 20.0));
+
+        let columns = rect.columns(2);
+        assert_eq!(columns[1], Rect::new(0.0, 10.0, 30.0, 20.0));
+        assert!(rect.columns(0).is_empty());
+
+        let (top, rest) = rect.split_top(40.0);
+        assert_eq!(top, rect);
+        assert_eq!(rest.height(), 0.0);
+
+        let (left, rest) = rect.split_left(5.0);
+        assert_eq!(left, Rect::new(0.0, 0.0, 30.0, 5.0));
+        assert_eq!(rest, Rect::new(0.0, 5.0, 30.0, 20.0));
+    }
+
+    #[test]
+    fn test_vk_rect_conversion() {
+        let scissor: vk::Rect2D = Rect::new(0.5, -1.2, 10.1, 20.0).into();
+
+        assert_eq!(scissor.offset, vk::Offset2D { x: -2, y: 0 });
+        assert_eq!(
+            scissor.extent,
+            vk::Extent2D {
+                width: 22,
+                height: 11
+            }
+        );
+        assert_eq!(Rect::from(scissor), Rect::new(0.0, -2.0, 11.0, 20.0));
+    }
+
+    #[test]
+    fn test_snap_to_pixels() {
+        let rect = Rect::new(0.4, 2.5, 10.6, 12.49).snap_to_pixels();
+
+        assert_eq!(rect, Rect::new(0.0, 3.0, 11.0, 12.0));
+    }
 }
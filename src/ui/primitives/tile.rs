@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use crate::{
+    asset_loader::TextureId,
     immediate_mode_graphics::{Vertex, VertexStream},
     ui::primitives::Rect,
     vec2, vec3, vec4, Vec4,
@@ -30,8 +31,35 @@ pub struct Tile {
     pub outline_width: f32,
 
     /// The texture index to use when rendering the tile.
-    /// Defaults to 0.
-    pub texture_index: i32,
+    /// Defaults to [`TextureId::WHITE_PIXEL`].
+    pub texture_index: TextureId,
+}
+
+/// How a tile's texture is split into nine parts for [`Tile::nine_slice`].
+///
+/// Insets use the same layout as a container's margin: each side of the
+/// rect is the size of that edge.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NineSlice {
+    /// The size of each edge on screen.
+    pub insets: Rect,
+
+    /// The size of each edge in the tile's uv coordinates.
+    pub uv_insets: Rect,
+
+    /// When false the center isn't drawn, leaving only the frame.
+    pub fill_center: bool,
+}
+
+impl NineSlice {
+    /// Use the same inset on every side. The center is filled.
+    pub fn uniform(inset: f32, uv_inset: f32) -> Self {
+        Self {
+            insets: Rect::new(inset, inset, inset, inset),
+            uv_insets: Rect::new(uv_inset, uv_inset, uv_inset, uv_inset),
+            fill_center: true,
+        }
+    }
 }
 
 impl Default for Tile {
@@ -42,7 +70,7 @@ impl Default for Tile {
             depth: 0.0,
             color: vec4(1.0, 1.0, 1.0, 1.0),
             outline_width: 1.0,
-            texture_index: 0,
+            texture_index: TextureId::WHITE_PIXEL,
         }
     }
 }
@@ -83,6 +111,88 @@ impl Tile {
         )
     }
 
+    /// Fill the tile with its texture split into nine parts. The corners
+    /// keep their size, the edges stretch along their length, and the
+    /// center stretches to fill the rest.
+    pub fn nine_slice(
+        &self,
+        vertices: &mut impl VertexStream,
+        slice: &NineSlice,
+    ) -> Result<()> {
+        for tile in self.nine_slice_tiles(slice) {
+            tile.fill(vertices)?;
+        }
+        Ok(())
+    }
+
+    /// Build the tiles drawn by [`Self::nine_slice`], row by row from the
+    /// top left. Insets shrink evenly when the tile is too small for them.
+    pub fn nine_slice_tiles(&self, slice: &NineSlice) -> Vec<Tile> {
+        let fit = |size: f32, begin: f32, end: f32| {
+            let total = begin + end;
+            if total > size && total > 0.0 {
+                (begin * size / total, end * size / total)
+            } else {
+                (begin, end)
+            }
+        };
+        let (left, right) = fit(
+            self.model.width(),
+            slice.insets.left(),
+            slice.insets.right(),
+        );
+        let (top, bottom) = fit(
+            self.model.height(),
+            slice.insets.top(),
+            slice.insets.bottom(),
+        );
+
+        let xs = [
+            self.model.left(),
+            self.model.left() + left,
+            self.model.right() - right,
+            self.model.right(),
+        ];
+        let ys = [
+            self.model.top(),
+            self.model.top() + top,
+            self.model.bottom() - bottom,
+            self.model.bottom(),
+        ];
+        let us = [
+            self.uv.left(),
+            self.uv.left() + slice.uv_insets.left(),
+            self.uv.right() - slice.uv_insets.right(),
+            self.uv.right(),
+        ];
+        let vs = [
+            self.uv.top(),
+            self.uv.top() + slice.uv_insets.top(),
+            self.uv.bottom() - slice.uv_insets.bottom(),
+            self.uv.bottom(),
+        ];
+
+        let mut tiles = Vec::with_capacity(9);
+        for row in 0..3 {
+            for col in 0..3 {
+                if row == 1 && col == 1 && !slice.fill_center {
+                    continue;
+                }
+                tiles.push(Tile {
+                    model: Rect::new(
+                        ys[row],
+                        xs[col],
+                        ys[row + 1],
+                        xs[col + 1],
+                    ),
+                    uv: Rect::new(vs[row], us[col], vs[row + 1], us[col + 1]),
+                    ..*self
+                });
+            }
+        }
+        tiles
+    }
+
     pub fn outline(&self, vertices: &mut impl VertexStream) -> Result<()> {
         let outline_properties = Tile {
             depth: self.depth,
@@ -215,3 +325,33 @@ impl Tile {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_nine_slice_corners_keep_their_size() {
+        let tile = Tile {
+            model: Rect::new(0.0, 0.0, 100.0, 50.0),
+            uv: Rect::new(0.0, 0.0, 1.0, 1.0),
+            ..Default::default()
+        };
+        let tiles = tile.nine_slice_tiles(&NineSlice::uniform(10.0, 0.25));
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(tiles[0].model, Rect::new(0.0, 0.0, 10.0, 10.0));
+        assert_eq!(tiles[0].uv, Rect::new(0.0, 0.0, 0.25, 0.25));
+        assert_eq!(tiles[4].model, Rect::new(10.0, 10.0, 90.0, 40.0));
+        assert_eq!(tiles[8].uv, Rect::new(0.75, 0.75, 1.0, 1.0));
+
+        // insets shrink to fit, and the frame can be drawn without a center
+        let slice = NineSlice {
+            fill_center: false,
+            ..NineSlice::uniform(40.0, 0.25)
+        };
+        let tiles = tile.nine_slice_tiles(&slice);
+        assert_eq!(tiles.len(), 8);
+        assert_eq!(tiles[0].model, Rect::new(0.0, 0.0, 40.0, 25.0));
+        assert_eq!(tiles[2].model, Rect::new(0.0, 25.0, 40.0, 50.0));
+    }
+}
@@ -0,0 +1,112 @@
+//! Optional audio feedback for UI interactions.
+//!
+//! This library has no audio subsystem of its own. Instead, widgets report
+//! interactions as [`UISound`]s and the UI forwards them to whatever
+//! [`SoundPlayer`] the application provides, along with the volume configured
+//! for that kind of sound.
+
+use crate::builder_field;
+
+/// The kinds of interaction which can produce a sound.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum UISound {
+    /// The cursor moved onto an interactive widget.
+    Hover,
+
+    /// A button was clicked.
+    Click,
+
+    /// A checkbox, or similar, changed value.
+    Toggle,
+
+    /// Input was rejected, like an out of range value typed into a slider.
+    Error,
+}
+
+/// Applications implement this trait with their audio library of choice to
+/// hear UI sounds.
+pub trait SoundPlayer {
+    /// Play the sound at the given volume, from 0 to 1.
+    fn play(&mut self, sound: UISound, volume: f32);
+}
+
+/// Sound feedback plays UI sounds through a player with a volume for each
+/// kind of sound. Set a volume to 0 to silence that sound.
+pub struct SoundFeedback {
+    player: Box<dyn SoundPlayer>,
+    hover_volume: f32,
+    click_volume: f32,
+    toggle_volume: f32,
+    error_volume: f32,
+}
+
+impl SoundFeedback {
+    /// Create sound feedback which plays every sound through the player.
+    pub fn new<P>(player: P) -> Self
+    where
+        P: SoundPlayer + 'static,
+    {
+        Self {
+            player: Box::new(player),
+            hover_volume: 0.25,
+            click_volume: 1.0,
+            toggle_volume: 1.0,
+            error_volume: 1.0,
+        }
+    }
+
+    builder_field!(hover_volume, f32);
+    builder_field!(click_volume, f32);
+    builder_field!(toggle_volume, f32);
+    builder_field!(error_volume, f32);
+
+    /// The volume used for a kind of sound.
+    pub fn volume(&self, sound: UISound) -> f32 {
+        match sound {
+            UISound::Hover => self.hover_volume,
+            UISound::Click => self.click_volume,
+            UISound::Toggle => self.toggle_volume,
+            UISound::Error => self.error_volume,
+        }
+    }
+
+    /// Play each sound, skipping any which are silenced.
+    pub(crate) fn play_all<I>(&mut self, sounds: I)
+    where
+        I: IntoIterator<Item = UISound>,
+    {
+        for sound in sounds {
+            let volume = self.volume(sound).clamp(0.0, 1.0);
+            if volume > 0.0 {
+                self.player.play(sound, volume);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use ::std::{cell::RefCell, rc::Rc};
+
+    struct Recorder(Rc<RefCell<Vec<(UISound, f32)>>>);
+
+    impl SoundPlayer for Recorder {
+        fn play(&mut self, sound: UISound, volume: f32) {
+            self.0.borrow_mut().push((sound, volume));
+        }
+    }
+
+    #[test]
+    fn test_sounds_play_at_their_volume_and_silenced_sounds_are_skipped() {
+        let played = Rc::new(RefCell::new(vec![]));
+        let mut feedback = SoundFeedback::new(Recorder(played.clone()))
+            .hover_volume(0.0)
+            .click_volume(0.5);
+
+        feedback.play_all(vec![UISound::Hover, UISound::Click]);
+
+        assert_eq!(*played.borrow(), vec![(UISound::Click, 0.5)]);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{vec4, Vec4};
+
+/// A Theme holds the default look for widgets.
+///
+/// Widgets read the theme from [`crate::ui::InternalState::theme`] when
+/// they're laid out and drawn, so each field is only a default: builder
+/// methods like `Button::color` still override it. Set the theme with
+/// [`crate::ui::UI::set_theme`], the view is rebuilt with the new theme
+/// immediately.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Theme {
+    /// Text, and widgets drawn like text such as check marks.
+    pub text_color: Vec4,
+
+    /// Panels which hold other widgets, like dropdown lists and menus.
+    pub panel_color: Vec4,
+
+    /// Interactive widgets at rest, like buttons and menu bars.
+    pub widget_color: Vec4,
+
+    /// Interactive widgets under the mouse.
+    pub hover_color: Vec4,
+
+    /// Interactive widgets while they're pressed.
+    pub pressed_color: Vec4,
+
+    /// Values and emphasis, like a switched on toggle or a focused field.
+    pub accent_color: Vec4,
+
+    /// Selected text, rows, and items.
+    pub selection_color: Vec4,
+
+    /// The background behind editable values, like text inputs.
+    pub field_color: Vec4,
+
+    /// Subtle highlights drawn over other widgets, like hovered rows.
+    pub highlight_color: Vec4,
+
+    /// Secondary details, like borders and arrows.
+    pub detail_color: Vec4,
+
+    /// The suggested font size in points, before scaling for the display.
+    pub font_size: f32,
+
+    /// The space between a widget's edge and its contents, as a fraction of
+    /// the font's line height.
+    pub padding: f32,
+
+    /// When true, widgets which support it draw with rounded corners.
+    pub rounded_corners: bool,
+
+    /// When true, widgets are positioned on whole pixels and containers
+    /// round their edges so borders and text aren't blurred by multisampling.
+    /// Turn it off for UIs which animate smoothly between positions.
+    pub pixel_snapping: bool,
+}
+
+impl Theme {
+    /// Light text on dark widgets.
+    pub fn dark() -> Self {
+        Self {
+            text_color: vec4(1.0, 1.0, 1.0, 1.0),
+            panel_color: vec4(0.05, 0.05, 0.05, 1.0),
+            widget_color: vec4(0.1, 0.1, 0.1, 1.0),
+            hover_color: vec4(0.3, 0.3, 0.3, 1.0),
+            pressed_color: vec4(0.5, 0.5, 0.5, 1.0),
+            accent_color: vec4(0.3, 0.6, 1.0, 1.0),
+            selection_color: vec4(0.3, 0.6, 1.0, 0.4),
+            field_color: vec4(0.0, 0.0, 0.0, 0.5),
+            highlight_color: vec4(1.0, 1.0, 1.0, 0.1),
+            detail_color: vec4(0.6, 0.6, 0.6, 1.0),
+            font_size: 16.0,
+            padding: 0.25,
+            rounded_corners: true,
+            pixel_snapping: true,
+        }
+    }
+
+    /// Dark text on light widgets.
+    pub fn light() -> Self {
+        Self {
+            text_color: vec4(0.1, 0.1, 0.1, 1.0),
+            panel_color: vec4(0.95, 0.95, 0.95, 1.0),
+            widget_color: vec4(0.85, 0.85, 0.85, 1.0),
+            hover_color: vec4(0.75, 0.75, 0.75, 1.0),
+            pressed_color: vec4(0.6, 0.6, 0.6, 1.0),
+            accent_color: vec4(0.2, 0.45, 0.9, 1.0),
+            selection_color: vec4(0.2, 0.45, 0.9, 0.3),
+            field_color: vec4(1.0, 1.0, 1.0, 0.9),
+            highlight_color: vec4(0.0, 0.0, 0.0, 0.08),
+            detail_color: vec4(0.4, 0.4, 0.4, 1.0),
+            ..Self::dark()
+        }
+    }
+}
+
+impl Default for Theme {
+    /// Defaults to the dark theme.
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_light_theme_keeps_the_dark_theme_metrics() {
+        assert_eq!(Theme::default(), Theme::dark());
+        assert_ne!(Theme::light().text_color, Theme::dark().text_color);
+        assert_eq!(Theme::light().font_size, Theme::dark().font_size);
+        assert_eq!(Theme::light().padding, Theme::dark().padding);
+    }
+}
@@ -1,15 +1,24 @@
 use crate::{
-    immediate_mode_graphics::triangles::Frame,
+    immediate_mode_graphics::triangles::{Frame, FrameRecording},
     ui::{
-        primitives::{Dimensions, Rect},
+        event_queue::EventQueue,
+        frame_arena,
+        primitives::{Dimensions, Rect, Tile},
         ui_screen_space_projection,
-        widgets::{Element, Widget},
-        Input, InternalState,
+        widgets::{Element, OverlayEvent, Widget},
+        Clipboard, CursorShape, Input, InternalState, MouseButtons,
+        SoundFeedback, Theme,
     },
-    vec2, Mat4,
+    vec2, vec4, Mat4, Vec2, Vec4,
 };
 
-use ::anyhow::Result;
+use ::{
+    anyhow::Result,
+    std::time::{Duration, Instant},
+};
+
+/// The space between a focused widget and its focus ring.
+const FOCUS_RING_GAP: f32 = 2.0;
 
 pub trait UIState {
     type Message;
@@ -19,6 +28,26 @@ pub trait UIState {
 
     /// Update user state based on the given message.
     fn update(&mut self, message: &Self::Message);
+
+    /// Build elements which are drawn above the view, at absolute positions
+    /// in screen space rather than inside the normal layout. This is useful
+    /// for popups and drag previews which follow the mouse.
+    ///
+    /// Each element's top left corner is placed at its position and the
+    /// element is sized to fit between there and the bottom right of the
+    /// screen. Later elements are drawn on top of earlier ones, and every
+    /// element sees events before the view does. Clicks and scrolls over an
+    /// element don't reach the view.
+    fn overlay(&self) -> Vec<(Vec2, Element<Self::Message>)> {
+        vec![]
+    }
+}
+
+/// An element from [`UIState::overlay`] and where it landed on screen.
+struct OverlayElement<Message> {
+    position: Vec2,
+    element: Element<Message>,
+    bounds: Rect,
 }
 
 /// The entrypoint for every UI. It manages internal state and knows how to
@@ -26,22 +55,63 @@ pub trait UIState {
 pub struct UI<C: UIState> {
     viewport: Rect,
     projection: Mat4,
+
+    /// When set, the UI is drawn with this projection instead of the screen
+    /// space projection, e.g. to place it in the world.
+    custom_projection: Option<Mat4>,
+
+    /// The space covered by the view's root widget after the last layout.
+    view_bounds: Rect,
     custom: C,
     current_view: Element<C::Message>,
+
+    /// Elements drawn above the view at absolute positions.
+    current_overlay: Vec<OverlayElement<C::Message>>,
     internal_state: InternalState,
     input: Input,
+    sound_feedback: Option<SoundFeedback>,
+    queued_events: EventQueue,
+    max_events_per_frame: usize,
+    focus_ring_color: Vec4,
+
+    /// How long the UI can go between redraws when nothing has changed.
+    /// None means the UI is redrawn every frame.
+    idle_redraw_interval: Option<Duration>,
+
+    /// The geometry from the last time the UI was drawn, kept when idle
+    /// redraws are limited.
+    last_recording: Option<FrameRecording>,
+
+    /// When the UI was last drawn rather than replayed.
+    last_redraw: Instant,
+
+    /// True when something happened which can change how the UI looks.
+    needs_redraw: bool,
 }
 
 impl<C: UIState> UI<C> {
     /// Create a new UI instance with the given viewport width and height.
     pub fn new(viewport: Dimensions, custom_ui: C) -> Self {
+        let internal_state = InternalState::new();
+        let focus_ring_color = internal_state.theme().accent_color;
         let mut ui = Self {
             viewport: Rect::new(0.0, 0.0, viewport.height, viewport.width),
             projection: ui_screen_space_projection(viewport),
+            custom_projection: None,
+            view_bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
             current_view: custom_ui.view(),
+            current_overlay: overlay_elements(&custom_ui),
             custom: custom_ui,
-            internal_state: InternalState::new(),
+            internal_state,
             input: Input::new(),
+            sound_feedback: None,
+            queued_events: EventQueue::default(),
+            max_events_per_frame: 256,
+            focus_ring_color,
+            idle_redraw_interval: None,
+            last_recording: None,
+            last_redraw: Instant::now(),
+            needs_redraw: true,
         };
         ui.layout();
         ui
@@ -55,7 +125,17 @@ impl<C: UIState> UI<C> {
     ) -> Result<Option<C::Message>> {
         use glfw::WindowEvent;
 
+        self.needs_redraw = true;
+        let event =
+            match self.input.mouse_buttons.to_roles(&self.to_ui_space(event)) {
+                Some(event) => event,
+                None => return Ok(None),
+            };
+        let event = &event;
         self.input.handle_event(event);
+        if self.move_focus(event) {
+            return Ok(None);
+        }
         match *event {
             WindowEvent::FramebufferSize(width, height) => {
                 self.viewport =
@@ -66,11 +146,26 @@ impl<C: UIState> UI<C> {
             _ => (),
         }
 
-        let message_opt = self.current_view.handle_event(
-            &mut self.internal_state,
-            &self.input,
-            event,
-        )?;
+        let is_cursor_move = matches!(*event, WindowEvent::CursorPos(..));
+        if is_cursor_move {
+            self.internal_state.hover_mut().begin_cursor_move();
+        }
+        let message_opt = self.dispatch_event(event);
+        if is_cursor_move {
+            self.internal_state.hover_mut().end_cursor_move();
+        }
+        self.internal_state.cursor_mut().end_event(is_cursor_move);
+        if matches!(
+            *event,
+            WindowEvent::MouseButton(
+                glfw::MouseButton::Button1,
+                glfw::Action::Release,
+                _
+            )
+        ) {
+            self.internal_state.presses_mut().clear();
+        }
+        let message_opt = message_opt?;
 
         if let Some(message) = &message_opt {
             self.custom.update(message);
@@ -78,10 +173,171 @@ impl<C: UIState> UI<C> {
         } else {
             self.layout();
         }
+        self.play_sounds();
 
         Ok(message_opt)
     }
 
+    /// Handle an event like [`Self::handle_event`], and report whether the
+    /// UI used it so UIs stacked below this one, or the rest of the
+    /// application, can ignore it.
+    ///
+    /// The event is captured when it produces a message, moves keyboard
+    /// focus, is a key or typed character while a widget has focus, or is a
+    /// mouse button or scroll over the view or an overlay element. The view
+    /// covers only as much of the screen as its root widget, so a root which
+    /// fills the screen captures every click. Resizes and cursor moves are
+    /// never captured.
+    pub fn handle_captured_event(
+        &mut self,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<C::Message>> {
+        use glfw::WindowEvent;
+
+        let is_keyboard = matches!(
+            *event,
+            WindowEvent::Key(..)
+                | WindowEvent::Char(..)
+                | WindowEvent::CharModifiers(..)
+        );
+        let mouse_position = self.input.mouse_position;
+        let over_ui = self.view_bounds.contains(mouse_position)
+            || self
+                .current_overlay
+                .iter()
+                .any(|overlay| overlay.bounds.contains(mouse_position));
+        let had_focus = self.internal_state.focus_mut().has_focus();
+
+        let message_opt = self.handle_event(event)?;
+
+        // keys are captured while any widget has focus, including when Tab
+        // moves focus into the UI or Escape releases it
+        let has_focus = self.internal_state.focus_mut().has_focus();
+        let captures = match *event {
+            WindowEvent::MouseButton(..) | WindowEvent::Scroll(..) => over_ui,
+            _ => is_keyboard && (had_focus || has_focus),
+        };
+        if captures || message_opt.is_some() {
+            Ok(OverlayEvent::Captured(message_opt))
+        } else {
+            Ok(OverlayEvent::Ignored)
+        }
+    }
+
+    /// Draw the UI with a projection other than the screen space projection,
+    /// e.g. a camera's view projection to place the UI in the world. The UI
+    /// is laid out in screen space as usual and lies on the z = 0 plane, and
+    /// cursor positions are projected onto that plane so the UI stays
+    /// interactive. None restores the screen space projection.
+    ///
+    /// The frame's projection is still the screen space projection, the
+    /// custom projection is applied to the UI's vertices as they're pushed,
+    /// so every UI can share one frame. Clip rects are in screen space, so
+    /// widgets which clip their contents only line up without a custom
+    /// projection.
+    pub fn set_projection(&mut self, projection: Option<Mat4>) {
+        self.custom_projection = projection;
+        self.needs_redraw = true;
+    }
+
+    /// Queue a GLFW input event to be handled by
+    /// [`Self::handle_queued_events`].
+    ///
+    /// Queued cursor moves, scrolls, and resizes are merged with the event
+    /// before them when it's the same kind, so a fast mouse costs one event
+    /// per frame rather than hundreds. Buttons, keys, and typed characters
+    /// are always handled in the order they happened.
+    pub fn queue_event(&mut self, event: glfw::WindowEvent) {
+        self.queued_events.push(event);
+    }
+
+    /// Handle queued events in order, returning every message they produced.
+    ///
+    /// At most [`Self::set_max_events_per_frame`] events are handled each
+    /// call. Any others stay queued for the next call, so a burst of input
+    /// can't stall a frame.
+    pub fn handle_queued_events(&mut self) -> Result<Vec<C::Message>> {
+        let mut messages = vec![];
+        for event in self.queued_events.take(self.max_events_per_frame) {
+            if let Some(message) = self.handle_event(&event)? {
+                messages.push(message);
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Limit how many queued events are handled by each call to
+    /// [`Self::handle_queued_events`]. Defaults to 256.
+    pub fn set_max_events_per_frame(&mut self, max_events_per_frame: usize) {
+        self.max_events_per_frame = max_events_per_frame.max(1);
+    }
+
+    /// Switch to a new theme. The view is rebuilt right away so every widget
+    /// is laid out with the theme's defaults, and the focus ring uses the
+    /// theme's accent color.
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.internal_state.set_theme(theme);
+        self.focus_ring_color = theme.accent_color;
+        self.needs_redraw = true;
+        self.flush();
+    }
+
+    /// The theme widgets are drawn with.
+    pub fn theme(&self) -> &Theme {
+        self.internal_state.theme()
+    }
+
+    /// The color of the ring drawn around the widget with keyboard focus.
+    /// The ring is only drawn after focus is moved with Tab. Defaults to the
+    /// theme's accent color.
+    pub fn set_focus_ring_color(&mut self, focus_ring_color: Vec4) {
+        self.focus_ring_color = focus_ring_color;
+    }
+
+    /// Limit how often the UI is redrawn while nothing changes. Between
+    /// redraws each frame reuses the geometry from the last redraw, so the UI
+    /// costs almost no CPU time in sketches which are busy with other work.
+    ///
+    /// Events and changes through [`Self::state_mut`] cause a redraw on the
+    /// next frame regardless of the rate, and animations started with
+    /// [`InternalState::animate`] redraw every frame until they finish. The
+    /// UI's clock and per-frame input still advance on replayed frames. None
+    /// redraws every frame, which is the default.
+    pub fn set_idle_redraw_rate(&mut self, redraws_per_second: Option<f32>) {
+        self.idle_redraw_interval = redraws_per_second
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| Duration::from_secs_f32(1.0 / rate));
+        self.last_recording = None;
+    }
+
+    /// Play sounds for interactions like hovering and clicking widgets.
+    /// The UI is silent by default.
+    pub fn set_sound_feedback(&mut self, sound_feedback: SoundFeedback) {
+        self.sound_feedback = Some(sound_feedback);
+    }
+
+    /// Copy and paste text with the given clipboard. By default the UI has
+    /// its own clipboard which other programs can't see.
+    pub fn set_clipboard<B>(&mut self, clipboard: B)
+    where
+        B: Clipboard + 'static,
+    {
+        self.internal_state.set_clipboard(Box::new(clipboard));
+    }
+
+    /// Choose which physical mouse buttons widgets treat as primary,
+    /// secondary, and middle, e.g. [`MouseButtons::left_handed`].
+    pub fn set_mouse_buttons(&mut self, mouse_buttons: MouseButtons) {
+        self.input.mouse_buttons = mouse_buttons;
+    }
+
+    /// The cursor shape the UI's widgets asked for. Show it once per frame
+    /// with a [`crate::ui::WindowCursor`], the demo Application does this
+    /// for its [`crate::demo::UILayers`].
+    pub fn cursor_shape(&self) -> CursorShape {
+        self.internal_state.cursor_shape()
+    }
+
     /// Get a reference to the current UI state.
     pub fn state(&self) -> &C {
         &self.custom
@@ -89,6 +345,7 @@ impl<C: UIState> UI<C> {
 
     /// Get a reference to the current UI state.
     pub fn state_mut(&mut self) -> &mut C {
+        self.needs_redraw = true;
         &mut self.custom
     }
 
@@ -102,29 +359,299 @@ impl<C: UIState> UI<C> {
     /// to render to this frame.
     ///
     pub fn draw_frame(&mut self, frame: &mut Frame) -> Result<()> {
+        if self.internal_state.presses_mut().any() {
+            // held widgets, like buttons which repeat, check the time
+            self.handle_event(&glfw::WindowEvent::Refresh)?;
+        }
+        frame.set_view_projection(self.projection)?;
+        if let Some(recording) = self.reusable_recording() {
+            frame.replay(recording)?;
+
+            // replayed frames are still frames, so the next redraw's frame
+            // delta and scrolling only cover the frame before it
+            self.internal_state.start_frame();
+            self.input.end_frame();
+            return Ok(());
+        }
+
+        let screen_to_custom = self.custom_projection.and_then(|projection| {
+            Some(self.projection.try_inverse()? * projection)
+        });
+        if let Some(transform) = screen_to_custom {
+            frame.push_transform(transform);
+        }
+        if self.idle_redraw_interval.is_some() {
+            frame.begin_recording();
+        }
+        let result = self.redraw(frame);
+        let recording = frame.end_recording();
+        if screen_to_custom.is_some() {
+            frame.pop_transform();
+        }
+        result?;
+
+        self.last_recording = recording;
+        self.last_redraw = Instant::now();
+        self.input.end_frame();
+
+        // keep redrawing every frame until animations settle
+        self.needs_redraw = self.internal_state.take_animating();
+        Ok(())
+    }
+}
+
+impl<C: UIState> UI<C> {
+    /// Build, layout, and draw the view.
+    fn redraw(&mut self, frame: &mut Frame) -> Result<()> {
+        self.internal_state.start_frame();
+        frame_arena::reset();
         self.flush();
 
-        frame.set_view_projection(self.projection)?;
         self.current_view
             .draw_frame(&mut self.internal_state, frame)?;
+        self.current_view
+            .draw_overlay(&mut self.internal_state, frame)?;
+        for overlay in &self.current_overlay {
+            overlay
+                .element
+                .draw_frame(&mut self.internal_state, frame)?;
+            overlay
+                .element
+                .draw_overlay(&mut self.internal_state, frame)?;
+        }
+
+        if let Some(bounds) = self.internal_state.focus_mut().ring() {
+            Tile {
+                model: Rect::new(
+                    bounds.top() - FOCUS_RING_GAP,
+                    bounds.left() - FOCUS_RING_GAP,
+                    bounds.bottom() + FOCUS_RING_GAP,
+                    bounds.right() + FOCUS_RING_GAP,
+                ),
+                color: self.focus_ring_color,
+                outline_width: 2.0,
+                ..Default::default()
+            }
+            .outline(frame)?;
+        }
         Ok(())
     }
-}
 
-impl<C: UIState> UI<C> {
+    /// Give an event to the overlay elements and the view, top to bottom,
+    /// until something captures it or returns a message.
+    fn dispatch_event(
+        &mut self,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<C::Message>> {
+        use glfw::WindowEvent;
+
+        // overlays are drawn on top of everything else, so they get the first
+        // chance to handle each event
+        for overlay in self.current_overlay.iter_mut().rev() {
+            let result = overlay.element.handle_overlay_event(
+                &mut self.internal_state,
+                &self.input,
+                event,
+            )?;
+            if let OverlayEvent::Captured(message_opt) = result {
+                return Ok(message_opt);
+            }
+        }
+        let overlay_result = self.current_view.handle_overlay_event(
+            &mut self.internal_state,
+            &self.input,
+            event,
+        )?;
+        if let OverlayEvent::Captured(message_opt) = overlay_result {
+            return Ok(message_opt);
+        }
+
+        for overlay in self.current_overlay.iter_mut().rev() {
+            let message_opt = overlay.element.handle_event(
+                &mut self.internal_state,
+                &self.input,
+                event,
+            )?;
+            if message_opt.is_some() {
+                return Ok(message_opt);
+            }
+        }
+
+        let mouse_position = self.input.mouse_position;
+        let over_overlay = self
+            .current_overlay
+            .iter()
+            .any(|overlay| overlay.bounds.contains(mouse_position));
+        match *event {
+            WindowEvent::MouseButton(..) | WindowEvent::Scroll(..)
+                if over_overlay =>
+            {
+                Ok(None)
+            }
+            _ => self.current_view.handle_event(
+                &mut self.internal_state,
+                &self.input,
+                event,
+            ),
+        }
+    }
+
+    /// Move cursor events from the window into the UI's space when it's
+    /// drawn with a custom projection. Cursors which don't land on the UI's
+    /// plane are moved far off screen.
+    fn to_ui_space(&self, event: &glfw::WindowEvent) -> glfw::WindowEvent {
+        use glfw::WindowEvent;
+
+        match (event, self.custom_projection) {
+            (&WindowEvent::CursorPos(x, y), Some(projection)) => {
+                let position = unproject_to_ui_plane(
+                    self.projection,
+                    projection,
+                    vec2(x as f32, y as f32),
+                )
+                .unwrap_or(vec2(f32::MIN, f32::MIN));
+                WindowEvent::CursorPos(position.x as f64, position.y as f64)
+            }
+            _ => event.clone(),
+        }
+    }
+
+    /// The last redraw's geometry, if it can be drawn again rather than
+    /// redrawing the view.
+    fn reusable_recording(&self) -> Option<&FrameRecording> {
+        let interval = self.idle_redraw_interval?;
+        if self.needs_redraw || self.last_redraw.elapsed() >= interval {
+            return None;
+        }
+        self.last_recording.as_ref()
+    }
+
     /// For the UI view to be regenerated and update the layout.
     /// This happens automatically after every update and before every frame.
     fn flush(&mut self) {
         self.current_view = self.custom.view();
+        self.current_overlay = overlay_elements(&self.custom);
         self.layout();
     }
 
+    /// Tab moves keyboard focus to the next widget, Shift+Tab to the previous
+    /// widget. Widgets which type tabs keep them unless Control is held.
+    ///
+    /// Returns true when focus moved, the event isn't given to the view.
+    fn move_focus(&mut self, event: &glfw::WindowEvent) -> bool {
+        use glfw::{Action, Key, Modifiers, WindowEvent};
+
+        let modifiers = match *event {
+            WindowEvent::Key(
+                Key::Tab,
+                _,
+                Action::Press | Action::Repeat,
+                modifiers,
+            ) => modifiers,
+            _ => return false,
+        };
+        let focus = self.internal_state.focus_mut();
+        if focus.focused_uses_tab() && !modifiers.contains(Modifiers::Control) {
+            return false;
+        }
+        focus.advance(modifiers.contains(Modifiers::Shift))
+    }
+
+    /// Play every sound requested by widgets. Sounds are discarded when
+    /// there's no sound feedback so they don't pile up.
+    fn play_sounds(&mut self) {
+        let sounds = self.internal_state.take_sounds();
+        if let Some(sound_feedback) = &mut self.sound_feedback {
+            sound_feedback.play_all(sounds);
+        }
+    }
+
     /// Layout the Widgets into a single UI.
     fn layout(&mut self) {
-        let _root_widget_dimensions = self
+        self.internal_state.focus_mut().begin_layout();
+        let root_widget_dimensions = self
             .current_view
             .dimensions(&mut self.internal_state, &self.viewport.dimensions());
         self.current_view
             .set_top_left_position(&mut self.internal_state, vec2(0.0, 0.0));
+        self.view_bounds = root_widget_dimensions.as_rect();
+
+        for overlay in &mut self.current_overlay {
+            let max_size = Dimensions::new(
+                (self.viewport.right() - overlay.position.x).max(0.0),
+                (self.viewport.bottom() - overlay.position.y).max(0.0),
+            );
+            let dimensions = overlay
+                .element
+                .dimensions(&mut self.internal_state, &max_size);
+            overlay.element.set_top_left_position(
+                &mut self.internal_state,
+                overlay.position,
+            );
+            overlay.bounds =
+                dimensions.as_rect().set_top_left_position(overlay.position);
+        }
+    }
+}
+
+/// Find where a point on screen lands on the z = 0 plane of a UI drawn with
+/// a custom projection. The point is in screen space, which the screen
+/// projection maps to normalized device coordinates.
+///
+/// Returns None when the plane is edge on, or the projection can't be
+/// inverted.
+fn unproject_to_ui_plane(
+    screen_projection: Mat4,
+    projection: Mat4,
+    point: Vec2,
+) -> Option<Vec2> {
+    let ndc = screen_projection * vec4(point.x, point.y, 0.0, 1.0);
+    let inverse = projection.try_inverse()?;
+    let unproject = |depth: f32| {
+        let p = inverse * vec4(ndc.x, ndc.y, depth, 1.0);
+        p.xyz() / p.w
+    };
+    let (near, far) = (unproject(0.0), unproject(1.0));
+    let direction = far - near;
+    if direction.z.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = -near.z / direction.z;
+    let hit = near + t * direction;
+    Some(vec2(hit.x, hit.y))
+}
+
+/// Build the custom UI's overlay elements. They're positioned by the next
+/// layout.
+fn overlay_elements<C: UIState>(custom: &C) -> Vec<OverlayElement<C::Message>> {
+    custom
+        .overlay()
+        .into_iter()
+        .map(|(position, element)| OverlayElement {
+            position,
+            element,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_unproject_to_ui_plane() {
+        let screen = ui_screen_space_projection(Dimensions::new(800.0, 600.0));
+        let close = |a: Vec2, b: Vec2| (a - b).norm() < 1e-3;
+
+        let same = unproject_to_ui_plane(screen, screen, vec2(100.0, 200.0));
+        assert!(close(same.unwrap(), vec2(100.0, 200.0)));
+
+        // the UI is drawn shifted right and down, so the cursor lands further
+        // up and left on the UI
+        let shifted =
+            screen * Mat4::new_translation(&crate::vec3(10.0, 20.0, 0.0));
+        let hit = unproject_to_ui_plane(screen, shifted, vec2(100.0, 200.0));
+        assert!(close(hit.unwrap(), vec2(90.0, 180.0)));
     }
 }
@@ -5,7 +5,7 @@ use crate::{
     immediate_mode_graphics::triangles::Frame,
     ui::{
         primitives::Dimensions,
-        widgets::{Element, Widget},
+        widgets::{Element, OverlayEvent, Widget},
         Input, InternalState,
     },
     vec2, Vec2,
@@ -118,6 +118,24 @@ impl<Message, W: Widget<Message>> Widget<Message> for Align<Message, W> {
             position + self.child_offset,
         );
     }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)
+    }
 }
 
 impl<Message, W> Into<Element<Message>> for Align<Message, W>
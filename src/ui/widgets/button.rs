@@ -5,10 +5,10 @@ use crate::{
     immediate_mode_graphics::triangles::Frame,
     ui::{
         primitives::{Dimensions, Rect, Tile},
-        widgets::{Element, Widget},
-        Id, Input, InternalState,
+        widgets::{Element, OverlayEvent, Widget},
+        CursorShape, HoldRepeat, Id, Input, InternalState, Transition, UISound,
     },
-    vec2, vec4, Vec2, Vec4,
+    vec2, Vec2, Vec4,
 };
 
 /// A Button's state is stored in the UI InternalState so it's activity is
@@ -27,7 +27,12 @@ impl Default for ButtonState {
     }
 }
 
-/// A Button is a UI widget which can fire a message when clicked.
+/// A Button is a UI widget which can fire a message when clicked. Buttons
+/// can also be clicked with Space or Enter while they have keyboard focus.
+///
+/// Buttons with [`Button::repeat_on_hold`] fire as soon as they're pressed
+/// instead of when they're released, then keep firing while they're held,
+/// like spinner arrows. Held keys repeat at the keyboard's repeat rate.
 pub struct Button<Message> {
     /// The ID uniquely identifies this button when constructing and modifying
     /// state.
@@ -40,16 +45,22 @@ pub struct Button<Message> {
     background: Rect,
 
     /// The button's default color.
-    color: Vec4,
+    color: Option<Vec4>,
 
     /// The button's hover color.
-    hover_color: Vec4,
+    hover_color: Option<Vec4>,
 
     /// The button's color when pressed.
-    pressed_color: Vec4,
+    pressed_color: Option<Vec4>,
+
+    /// How the color changes between the default, hover, and pressed colors.
+    transition: Transition,
 
     /// The message to send when a button click is detected.
     on_click: Option<Message>,
+
+    /// How the button repeats while it's held, if it does.
+    repeat_on_hold: Option<HoldRepeat>,
 }
 
 impl<Message> Button<Message> {
@@ -61,18 +72,22 @@ impl<Message> Button<Message> {
             id,
             child: child.into(),
             background: Rect::new(0.0, 0.0, 0.0, 0.0),
-            color: vec4(0.1, 0.1, 0.1, 1.0),
-            hover_color: vec4(0.3, 0.3, 0.3, 1.0),
-            pressed_color: vec4(0.5, 0.5, 0.5, 1.0),
+            color: None,
+            hover_color: None,
+            pressed_color: None,
+            transition: Transition::default(),
             on_click: None,
+            repeat_on_hold: None,
         }
     }
 
     builder_field!(id, Id);
-    builder_field!(color, Vec4);
-    builder_field!(hover_color, Vec4);
-    builder_field!(pressed_color, Vec4);
+    builder_field_some!(color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(pressed_color, Vec4);
+    builder_field!(transition, Transition);
     builder_field_some!(on_click, Message);
+    builder_field_some!(repeat_on_hold, HoldRepeat);
 }
 
 impl<Message> Widget<Message> for Button<Message>
@@ -86,14 +101,19 @@ where
         input: &Input,
         event: &glfw::WindowEvent,
     ) -> Result<Option<Message>> {
-        use glfw::{Action, MouseButton, WindowEvent};
+        use glfw::{Action, Key, MouseButton, WindowEvent};
 
+        let focused = internal_state.is_focused(&self.id);
         let state = internal_state.get_state_mut::<ButtonState>(&self.id);
+        let mut sound = None;
+        let mut hovered = false;
         let message = match *event {
             WindowEvent::CursorPos(x, y) => {
                 if self.background.contains(vec2(x as f32, y as f32)) {
+                    hovered = true;
                     if *state == ButtonState::Inactive {
                         *state = ButtonState::Hover;
+                        sound = Some(UISound::Hover);
                     }
                 } else {
                     *state = ButtonState::Inactive;
@@ -107,9 +127,45 @@ where
             ) => {
                 if *state == ButtonState::Hover {
                     *state = ButtonState::Pressed;
+                    internal_state.set_focus(self.id);
+                    if self.repeat_on_hold.is_some() {
+                        internal_state.set_pressed(&self.id, true);
+                        sound = Some(UISound::Click);
+                        self.on_click
+                    } else {
+                        None
+                    }
+                } else {
+                    None
                 }
-                None
             }
+            WindowEvent::Refresh => match self.repeat_on_hold {
+                // the UI's tick while the button is held
+                Some(repeat)
+                    if *state == ButtonState::Pressed
+                        && self.background.contains(input.mouse_position)
+                        && internal_state
+                            .take_hold_repeat(&self.id, repeat) =>
+                {
+                    self.on_click
+                }
+                _ => None,
+            },
+            WindowEvent::Key(
+                Key::Space | Key::Enter | Key::KpEnter,
+                _,
+                Action::Press,
+                _,
+            ) if focused => {
+                sound = Some(UISound::Click);
+                self.on_click
+            }
+            WindowEvent::Key(
+                Key::Space | Key::Enter | Key::KpEnter,
+                _,
+                Action::Repeat,
+                _,
+            ) if focused && self.repeat_on_hold.is_some() => self.on_click,
             WindowEvent::MouseButton(
                 MouseButton::Button1,
                 Action::Release,
@@ -121,8 +177,15 @@ where
                     } else {
                         *state = ButtonState::Inactive;
                     }
-                    // this button was active, therefore this is a 'click'
-                    self.on_click
+                    if self.repeat_on_hold.is_some() {
+                        // repeating buttons already fired when pressed
+                        internal_state.set_pressed(&self.id, false);
+                        None
+                    } else {
+                        // this button was active, therefore this is a 'click'
+                        sound = Some(UISound::Click);
+                        self.on_click
+                    }
                 } else {
                     // the release was unrelated to the button
                     None
@@ -130,6 +193,12 @@ where
             }
             _ => None,
         };
+        if let Some(sound) = sound {
+            internal_state.play_sound(sound);
+        }
+        if hovered {
+            internal_state.request_cursor(CursorShape::Hand);
+        }
         Ok(message)
     }
 
@@ -139,12 +208,21 @@ where
         internal_state: &mut InternalState,
         frame: &mut Frame,
     ) -> Result<()> {
+        let theme = *internal_state.theme();
         let state = internal_state.get_state::<ButtonState>(&self.id);
-        let color = match *state {
-            ButtonState::Inactive => self.color,
-            ButtonState::Hover => self.hover_color,
-            ButtonState::Pressed => self.pressed_color,
+        let target_color = match *state {
+            ButtonState::Inactive => self.color.unwrap_or(theme.widget_color),
+            ButtonState::Hover => self.hover_color.unwrap_or(theme.hover_color),
+            ButtonState::Pressed => {
+                self.pressed_color.unwrap_or(theme.pressed_color)
+            }
         };
+        let color = internal_state.animate(
+            &self.id,
+            "color",
+            target_color,
+            self.transition,
+        );
         Tile {
             model: self.background,
             color,
@@ -174,8 +252,27 @@ where
     ) {
         let offset = position - self.background.top_left;
         self.background = self.background.translate(offset);
+        internal_state.register_focusable(self.id, self.background);
         self.child.set_top_left_position(internal_state, position);
     }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)
+    }
 }
 
 impl<Message> Into<Element<Message>> for Button<Message>
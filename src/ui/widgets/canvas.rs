@@ -0,0 +1,156 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect},
+        widgets::{Element, Widget},
+        Input, InternalState,
+    },
+    Vec2,
+};
+
+/// Draws a canvas's contents within its bounds.
+type DrawFn = dyn Fn(Rect, &mut Frame) -> Result<()>;
+
+/// Turns an event into a message, given the canvas's bounds.
+type EventFn<Message> =
+    dyn Fn(Rect, &Input, &glfw::WindowEvent) -> Option<Message>;
+
+/// A Canvas reserves space in the layout for custom drawing. Each frame the
+/// draw closure is given the canvas's bounds and the UI's frame, so
+/// application graphics can be drawn inside UI panels.
+///
+/// Drawing is clipped to the canvas's bounds. The frame uses the UI's screen
+/// space projection, so positions are in pixels from the top left of the
+/// window.
+pub struct Canvas<Message> {
+    /// The space occupied by the canvas on screen.
+    bounds: Rect,
+
+    /// The canvas's size, or None to fill the available space.
+    size: Option<Dimensions>,
+
+    /// When true, drawing outside of the canvas's bounds is clipped.
+    clipped: bool,
+
+    draw: Box<DrawFn>,
+    on_event: Option<Box<EventFn<Message>>>,
+}
+
+impl<Message> Canvas<Message> {
+    /// Create a canvas which fills the available space and draws with the
+    /// given closure.
+    pub fn new<F>(draw_fn: F) -> Self
+    where
+        F: 'static + Fn(Rect, &mut Frame) -> Result<()>,
+    {
+        Self {
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            size: None,
+            clipped: true,
+            draw: Box::new(draw_fn),
+            on_event: None,
+        }
+    }
+
+    builder_field!(clipped, bool);
+
+    /// Give the canvas a fixed size. It's shrunk to fit when there isn't
+    /// enough space.
+    pub fn size(self, width: f32, height: f32) -> Self {
+        Self {
+            size: Some(Dimensions::new(width, height)),
+            ..self
+        }
+    }
+
+    /// Handle events with the canvas's bounds, e.g. to pick things drawn on
+    /// the canvas with the mouse. Every event is given to the closure, not
+    /// just events over the canvas.
+    pub fn on_event<F>(self, on_event_fn: F) -> Self
+    where
+        F: 'static + Fn(Rect, &Input, &glfw::WindowEvent) -> Option<Message>,
+    {
+        Self {
+            on_event: Some(Box::new(on_event_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Canvas<Message> {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(self
+            .on_event
+            .as_ref()
+            .and_then(|on_event| on_event(self.bounds, input, event)))
+    }
+
+    fn draw_frame(
+        &self,
+        _internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        if !self.clipped {
+            return (self.draw)(self.bounds, frame);
+        }
+        frame.push_clip_rect(self.bounds);
+        let result = (self.draw)(self.bounds, frame);
+        frame.pop_clip_rect();
+        result
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.bounds = self.size.unwrap_or(*max_size).min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for Canvas<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_canvas_fills_unless_sized() {
+        let mut internal_state = InternalState::new();
+        let max_size = Dimensions::new(200.0, 100.0);
+
+        let mut fill = Canvas::<()>::new(|_, _| Ok(()));
+        let size =
+            Widget::dimensions(&mut fill, &mut internal_state, &max_size);
+        assert_eq!((size.width, size.height), (200.0, 100.0));
+
+        let mut sized = Canvas::<()>::new(|_, _| Ok(())).size(50.0, 300.0);
+        let size =
+            Widget::dimensions(&mut sized, &mut internal_state, &max_size);
+        assert_eq!((size.width, size.height), (50.0, 100.0));
+    }
+}
@@ -0,0 +1,260 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{button::ButtonState, Element, Label, Widget},
+        Font, Id, Input, InternalState, UISound,
+    },
+    vec2, Vec2, Vec4,
+};
+
+/// A Checkbox's state is stored in the UI InternalState so the checked value
+/// persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CheckboxState {
+    /// The checked value. This is None until the user first toggles the
+    /// checkbox, in which case the Checkbox's initial value is used.
+    checked: Option<bool>,
+
+    /// The mouse interaction state.
+    interaction: ButtonState,
+}
+
+/// A Checkbox is a toggleable box with a text label. Clicking anywhere on the
+/// box or the label toggles the value, so does Space or Enter while the
+/// checkbox has keyboard focus.
+pub struct Checkbox<Message> {
+    /// The ID uniquely identifies this checkbox when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The label rendered beside the box.
+    label: Label,
+
+    /// The space occupied by the entire checkbox on screen.
+    bounds: Rect,
+
+    /// The space occupied by the box.
+    check_box: Rect,
+
+    /// The horizontal space between the box and the label.
+    spacing: f32,
+
+    /// The value used before the user has toggled the checkbox.
+    initial_value: bool,
+
+    /// The color of the box's outline and check mark.
+    color: Option<Vec4>,
+
+    /// The color of the box's background when hovered.
+    hover_color: Option<Vec4>,
+
+    /// The function used to build a message when the value is toggled.
+    on_toggle: Option<Box<dyn Fn(bool) -> Message>>,
+}
+
+impl<Message> Checkbox<Message> {
+    pub fn new<T>(id: Id, font: &Font, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            id,
+            label: Label::new(font, text),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            check_box: Rect::new(0.0, 0.0, 0.0, 0.0),
+            spacing: 0.5 * font.line_height(),
+            initial_value: false,
+            color: None,
+            hover_color: None,
+            on_toggle: None,
+        }
+    }
+
+    builder_field!(initial_value, bool);
+    builder_field!(spacing, f32);
+    builder_field_some!(color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+
+    /// Set the function used to build a message when the checkbox is toggled.
+    /// The function is given the new value.
+    pub fn on_toggle<F>(self, on_toggle_fn: F) -> Self
+    where
+        F: 'static + Fn(bool) -> Message,
+    {
+        Self {
+            on_toggle: Some(Box::new(on_toggle_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Checkbox<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, Key, MouseButton, WindowEvent};
+
+        let focused = internal_state.is_focused(&self.id);
+        let state = internal_state.get_state_mut::<CheckboxState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                if self.bounds.contains(vec2(x as f32, y as f32)) {
+                    if state.interaction == ButtonState::Inactive {
+                        state.interaction = ButtonState::Hover;
+                        internal_state.play_sound(UISound::Hover);
+                    }
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+                Ok(None)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if state.interaction == ButtonState::Hover {
+                    state.interaction = ButtonState::Pressed;
+                    internal_state.set_focus(self.id);
+                }
+                Ok(None)
+            }
+            WindowEvent::Key(
+                Key::Space | Key::Enter | Key::KpEnter,
+                _,
+                Action::Press,
+                _,
+            ) if focused => {
+                let checked = !state.checked.unwrap_or(self.initial_value);
+                state.checked = Some(checked);
+                internal_state.play_sound(UISound::Toggle);
+                Ok(self.on_toggle.as_ref().map(|on_toggle| on_toggle(checked)))
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                if state.interaction != ButtonState::Pressed {
+                    // the release was unrelated to the checkbox
+                    return Ok(None);
+                }
+                if !self.bounds.contains(input.mouse_position) {
+                    // the press started here, but the mouse was released
+                    // somewhere else so it doesn't count as a click
+                    state.interaction = ButtonState::Inactive;
+                    return Ok(None);
+                }
+                state.interaction = ButtonState::Hover;
+
+                let checked = !state.checked.unwrap_or(self.initial_value);
+                state.checked = Some(checked);
+                internal_state.play_sound(UISound::Toggle);
+                Ok(self.on_toggle.as_ref().map(|on_toggle| on_toggle(checked)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<CheckboxState>(&self.id);
+
+        if state.interaction != ButtonState::Inactive {
+            Tile {
+                model: self.check_box,
+                color: self.hover_color.unwrap_or(theme.highlight_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        Tile {
+            model: self.check_box,
+            color: self.color.unwrap_or(theme.text_color),
+            outline_width: (0.1 * self.check_box.width()).max(1.0).round(),
+            ..Default::default()
+        }
+        .outline(frame)?;
+
+        if state.checked.unwrap_or(self.initial_value) {
+            let inset = 0.25 * self.check_box.width();
+            Tile {
+                model: Rect::new(
+                    self.check_box.top() + inset,
+                    self.check_box.left() + inset,
+                    self.check_box.bottom() - inset,
+                    self.check_box.right() - inset,
+                ),
+                color: self.color.unwrap_or(theme.text_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        Widget::<Message>::draw_frame(&self.label, internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let label_max_size = Dimensions::new(
+            0f32.max(max_size.width - self.spacing),
+            max_size.height,
+        );
+        let label_dimensions = Widget::<Message>::dimensions(
+            &mut self.label,
+            internal_state,
+            &label_max_size,
+        );
+
+        // The box is a square which matches the label's height.
+        let box_size = label_dimensions.height;
+        self.check_box = Dimensions::new(box_size, box_size).as_rect();
+
+        self.bounds = Dimensions::new(
+            box_size + self.spacing + label_dimensions.width,
+            box_size,
+        )
+        .min(max_size)
+        .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        internal_state.register_focusable(self.id, self.bounds);
+        self.check_box = self.check_box.set_top_left_position(position);
+        Widget::<Message>::set_top_left_position(
+            &mut self.label,
+            internal_state,
+            position + vec2(self.check_box.width() + self.spacing, 0.0),
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for Checkbox<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
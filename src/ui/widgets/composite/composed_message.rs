@@ -4,7 +4,7 @@ use crate::{
     immediate_mode_graphics::triangles::Frame,
     ui::{
         primitives::Dimensions,
-        widgets::{Element, Widget},
+        widgets::{Element, OverlayEvent, Widget},
         Input, InternalState,
     },
     Vec2,
@@ -63,6 +63,28 @@ impl<I, E> Widget<ComposedMessage<I, E>> for ComposedElement<E> {
     ) {
         self.0.set_top_left_position(internal_state, position);
     }
+
+    /// Allow the underlying Element to handle overlay events. Any resulting
+    /// messages are automatically wrapped into ComposedElement::External.
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<ComposedMessage<I, E>>> {
+        self.0
+            .handle_overlay_event(internal_state, input, event)
+            .map(|result| result.map(ComposedMessage::External))
+    }
+
+    /// Allow the underlying Element to draw its overlay.
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.0.draw_overlay(internal_state, frame)
+    }
 }
 
 impl<I, E> Into<Element<ComposedMessage<I, E>>> for Element<E>
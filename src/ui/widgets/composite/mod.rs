@@ -6,7 +6,7 @@ use crate::{
     immediate_mode_graphics::triangles::Frame,
     ui::{
         primitives::Dimensions,
-        widgets::{Element, Widget},
+        widgets::{Element, OverlayEvent, Widget},
         Id, Input, InternalState,
     },
     Vec2,
@@ -97,14 +97,10 @@ where
                 let state = internal_state
                     .get_state_mut::<CW::State>(self.composite.id());
                 self.composite.update(state, internal)?;
-                return Ok(None);
-            }
-            Some(ComposedMessage::External(ext)) => {
-                return Ok(Some(ext));
-            }
-            None => {
-                return Ok(None);
+                Ok(None)
             }
+            Some(ComposedMessage::External(ext)) => Ok(Some(ext)),
+            None => Ok(None),
         }
     }
 
@@ -150,4 +146,51 @@ where
             .unwrap()
             .set_top_left_position(internal_state, position);
     }
+
+    /// Overlay events are handled just like regular events: internal
+    /// messages update the composite widget's state and external messages are
+    /// bubbled out.
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<EMessage>> {
+        if self.current_view.is_none() {
+            let current_state =
+                internal_state.get_state::<CW::State>(self.composite.id());
+            self.current_view = Some(self.composite.view(current_state));
+        }
+        let result = self.current_view.as_mut().unwrap().handle_overlay_event(
+            internal_state,
+            input,
+            event,
+        )?;
+        match result {
+            OverlayEvent::Ignored => Ok(OverlayEvent::Ignored),
+            OverlayEvent::Captured(Some(ComposedMessage::Internal(
+                internal,
+            ))) => {
+                let state = internal_state
+                    .get_state_mut::<CW::State>(self.composite.id());
+                self.composite.update(state, internal)?;
+                Ok(OverlayEvent::Captured(None))
+            }
+            OverlayEvent::Captured(Some(ComposedMessage::External(ext))) => {
+                Ok(OverlayEvent::Captured(Some(ext)))
+            }
+            OverlayEvent::Captured(None) => Ok(OverlayEvent::Captured(None)),
+        }
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.current_view
+            .as_ref()
+            .unwrap()
+            .draw_overlay(internal_state, frame)
+    }
 }
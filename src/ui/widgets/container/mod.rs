@@ -3,11 +3,12 @@ mod constraint;
 use ::anyhow::Result;
 
 use crate::{
-    builder_field,
-    immediate_mode_graphics::triangles::Frame,
+    asset_loader::TextureId,
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::{Effect, EffectId, Frame},
     ui::{
-        primitives::{Dimensions, Rect, Tile},
-        widgets::{Element, Widget},
+        primitives::{Dimensions, NineSlice, Rect, Tile},
+        widgets::{Element, OverlayEvent, Widget},
         Input, InternalState,
     },
     vec2, vec4, Vec2, Vec4,
@@ -21,10 +22,35 @@ pub struct Container<Message, Widget> {
     margin: Rect,
     padding: Rect,
     border: Option<Tile>,
+
+    /// When set, the border is drawn as a nine-slice of its texture rather
+    /// than as an outline.
+    border_slice: Option<NineSlice>,
+
     background: Tile,
     max_width: Constraint,
     max_height: Constraint,
 
+    /// The smallest width the container shrinks to, including its padding,
+    /// border, and margin. The background grows to fill it when the child
+    /// is smaller. Defaults to 0.0.
+    min_width: f32,
+
+    /// The smallest height the container shrinks to, see `min_width`.
+    min_height: f32,
+
+    /// The fragment shader effect applied to the container and everything
+    /// inside it.
+    effect: Option<Effect>,
+
+    /// When true, the child is clipped to the container's background so it
+    /// can't draw outside of the container.
+    clip: bool,
+
+    /// When true, the background and border are rounded to whole pixels.
+    /// None uses the theme's [`crate::ui::Theme::pixel_snapping`].
+    pixel_snapping: Option<bool>,
+
     /// The Widget contained by this container
     pub child: Widget,
 
@@ -41,6 +67,7 @@ impl<Message, Widget> Container<Message, Widget> {
 
             ///! Border defaults to disabled
             border: None,
+            border_slice: None,
 
             ///! The Background defaults to fully transparent.
             background: Tile {
@@ -50,6 +77,11 @@ impl<Message, Widget> Container<Message, Widget> {
 
             max_width: Default::default(),
             max_height: Default::default(),
+            min_width: 0.0,
+            min_height: 0.0,
+            effect: None,
+            clip: false,
+            pixel_snapping: None,
 
             child: widget,
             _phantom_data: Default::default(),
@@ -58,6 +90,31 @@ impl<Message, Widget> Container<Message, Widget> {
 
     builder_field!(max_width, Constraint);
     builder_field!(max_height, Constraint);
+    builder_field!(min_width, f32);
+    builder_field!(min_height, f32);
+    builder_field_some!(effect, Effect);
+    builder_field!(clip, bool);
+    builder_field_some!(pixel_snapping, bool);
+
+    /// Draw the container and its child into an offscreen target, then
+    /// composite it through an effect. The effect must have been added to
+    /// the UI's renderer, see
+    /// [`crate::immediate_mode_graphics::triangles::Triangles::add_effect`].
+    pub fn with_effect(self, id: EffectId, params: Vec4) -> Self {
+        self.effect(Effect::new(id, params))
+    }
+
+    /// Make the container exactly the given size, whatever the size of its
+    /// child, as long as there's room for it.
+    pub fn fixed(self, width: f32, height: f32) -> Self {
+        Self {
+            max_width: Constraint::FixedMaxSize(width),
+            max_height: Constraint::FixedMaxSize(height),
+            min_width: width,
+            min_height: height,
+            ..self
+        }
+    }
 
     /// Set the margin on all sides.
     pub fn margin(self, margin: f32) -> Self {
@@ -67,6 +124,44 @@ impl<Message, Widget> Container<Message, Widget> {
         }
     }
 
+    /// Set the margin on each side.
+    pub fn margin_each(
+        self,
+        top: f32,
+        left: f32,
+        bottom: f32,
+        right: f32,
+    ) -> Self {
+        Self {
+            margin: Rect::new(top, left, bottom, right),
+            ..self
+        }
+    }
+
+    /// Set the margin on the top side, the other sides are unchanged.
+    pub fn margin_top(mut self, margin: f32) -> Self {
+        self.margin.top_left.y = margin;
+        self
+    }
+
+    /// Set the margin on the left side, the other sides are unchanged.
+    pub fn margin_left(mut self, margin: f32) -> Self {
+        self.margin.top_left.x = margin;
+        self
+    }
+
+    /// Set the margin on the bottom side, the other sides are unchanged.
+    pub fn margin_bottom(mut self, margin: f32) -> Self {
+        self.margin.bottom_right.y = margin;
+        self
+    }
+
+    /// Set the margin on the right side, the other sides are unchanged.
+    pub fn margin_right(mut self, margin: f32) -> Self {
+        self.margin.bottom_right.x = margin;
+        self
+    }
+
     /// Set the padding on all sides.
     pub fn padding(self, padding: f32) -> Self {
         Self {
@@ -75,8 +170,51 @@ impl<Message, Widget> Container<Message, Widget> {
         }
     }
 
+    /// Set the padding on each side.
+    pub fn padding_each(
+        self,
+        top: f32,
+        left: f32,
+        bottom: f32,
+        right: f32,
+    ) -> Self {
+        Self {
+            padding: Rect::new(top, left, bottom, right),
+            ..self
+        }
+    }
+
+    /// Set the padding on the top side, the other sides are unchanged.
+    pub fn padding_top(mut self, padding: f32) -> Self {
+        self.padding.top_left.y = padding;
+        self
+    }
+
+    /// Set the padding on the left side, the other sides are unchanged.
+    pub fn padding_left(mut self, padding: f32) -> Self {
+        self.padding.top_left.x = padding;
+        self
+    }
+
+    /// Set the padding on the bottom side, the other sides are unchanged.
+    pub fn padding_bottom(mut self, padding: f32) -> Self {
+        self.padding.bottom_right.y = padding;
+        self
+    }
+
+    /// Set the padding on the right side, the other sides are unchanged.
+    pub fn padding_right(mut self, padding: f32) -> Self {
+        self.padding.bottom_right.x = padding;
+        self
+    }
+
     /// Set all of the border properties.
-    pub fn border(self, width: f32, color: Vec4, texture_index: i32) -> Self {
+    pub fn border(
+        self,
+        width: f32,
+        color: Vec4,
+        texture_index: TextureId,
+    ) -> Self {
         Self {
             border: Some(Tile {
                 outline_width: width,
@@ -88,8 +226,18 @@ impl<Message, Widget> Container<Message, Widget> {
         }
     }
 
+    /// Draw the border as a nine-slice of its texture so textured frames
+    /// keep their corners at any size. The slice covers the border's whole
+    /// width, so its insets usually match the border width.
+    pub fn nine_slice_border(self, slice: NineSlice) -> Self {
+        Self {
+            border_slice: Some(slice),
+            ..self
+        }
+    }
+
     /// Set all available background properties.
-    pub fn background(self, color: Vec4, texture_index: i32) -> Self {
+    pub fn background(self, color: Vec4, texture_index: TextureId) -> Self {
         Self {
             background: Tile {
                 color,
@@ -116,6 +264,22 @@ where
         input: &Input,
         event: &glfw::WindowEvent,
     ) -> Result<Option<Message>> {
+        use glfw::{Action, WindowEvent};
+
+        if self.clip {
+            // clicks and scrolling outside the clip rect can't reach the
+            // hidden parts of the child
+            let hidden = match *event {
+                WindowEvent::MouseButton(_, Action::Press, _)
+                | WindowEvent::Scroll(..) => {
+                    !self.background.model.contains(input.mouse_position)
+                }
+                _ => false,
+            };
+            if hidden {
+                return Ok(None);
+            }
+        }
         self.child.handle_event(internal_state, input, event)
     }
 
@@ -124,13 +288,43 @@ where
         internal_state: &mut InternalState,
         frame: &mut Frame,
     ) -> Result<()> {
+        if let Some(effect) = self.effect {
+            frame.push_effect(effect)?;
+        }
+
         self.background.fill(frame)?;
 
         if let Some(border) = &self.border {
-            border.outline(frame)?;
+            match &self.border_slice {
+                Some(slice) => {
+                    // the border's model runs through the middle of the
+                    // border, the slice covers all of it
+                    let half_width = 0.5 * border.outline_width;
+                    let model = Rect::new(
+                        border.model.top() - half_width,
+                        border.model.left() - half_width,
+                        border.model.bottom() + half_width,
+                        border.model.right() + half_width,
+                    );
+                    Tile { model, ..*border }.nine_slice(frame, slice)?;
+                }
+                None => border.outline(frame)?,
+            }
         }
 
-        self.child.draw_frame(internal_state, frame)
+        if self.clip {
+            frame.push_clip_rect(self.background.model);
+            let result = self.child.draw_frame(internal_state, frame);
+            frame.pop_clip_rect();
+            result?;
+        } else {
+            self.child.draw_frame(internal_state, frame)?;
+        }
+
+        if self.effect.is_some() {
+            frame.pop_effect();
+        }
+        Ok(())
     }
 
     fn dimensions(
@@ -160,11 +354,18 @@ where
         let child_dimensions =
             self.child.dimensions(internal_state, &max_child_dimensions);
 
+        // The minimum size wins over the max constraints, but never asks for
+        // more space than is available. Small children are given the extra
+        // space inside the margin so the background fills the container.
+        let min_size =
+            Dimensions::new(self.min_width, self.min_height).min(max_size);
+        let child_dimensions = child_dimensions.max(&Dimensions::new(
+            min_size.width - horizonal_inset,
+            min_size.height - vertical_inset,
+        ));
+
         // Update the background size
-        let background_dimensions = Dimensions::new(
-            child_dimensions.width + self.margin.left() + self.margin.right(),
-            child_dimensions.height + self.margin.top() + self.margin.bottom(),
-        );
+        let background_dimensions = child_dimensions.outset(&self.margin);
         self.background.model = background_dimensions.as_rect();
 
         // Update the border size
@@ -180,7 +381,7 @@ where
             child_dimensions.width + horizonal_inset,
             child_dimensions.height + vertical_inset,
         );
-        total_dimensions.min(&adjusted_max_size)
+        total_dimensions.min(&adjusted_max_size).max(&min_size)
     }
 
     fn set_top_left_position(
@@ -205,12 +406,55 @@ where
             .background
             .model
             .set_top_left_position(background_top_left);
+        if self
+            .pixel_snapping
+            .unwrap_or(internal_state.theme().pixel_snapping)
+        {
+            // the border runs through the middle of its width around the
+            // background, so it follows the snapped background
+            self.background.model = self.background.model.snap_to_pixels();
+            if let Some(border) = &mut self.border {
+                let half_width = 0.5 * border_width;
+                border.model = Rect::new(
+                    self.background.model.top() - half_width,
+                    self.background.model.left() - half_width,
+                    self.background.model.bottom() + half_width,
+                    self.background.model.right() + half_width,
+                );
+            }
+        }
 
         // Set the child position
         let child_top_left = background_top_left + self.margin.top_left;
         self.child
             .set_top_left_position(internal_state, child_top_left);
     }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        // overlays belong to the subtree, so transitions apply to them too
+        if let Some(effect) = self.effect {
+            frame.push_effect(effect)?;
+        }
+        self.child.draw_overlay(internal_state, frame)?;
+        if self.effect.is_some() {
+            frame.pop_effect();
+        }
+        Ok(())
+    }
 }
 
 impl<Message, Child> Into<Element<Message>> for Container<Message, Child>
@@ -234,3 +478,35 @@ impl<Message, W: Widget<Message>> WithContainer<Message, W> for W {
         Container::new(self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ui::widgets::Spacer;
+
+    #[test]
+    fn test_each_side_is_inset_separately() {
+        let mut internal_state = InternalState::new();
+        let mut container = Spacer::new(10.0, 10.0)
+            .container()
+            .padding_each(1.0, 2.0, 3.0, 4.0)
+            .margin(5.0)
+            .margin_right(0.0);
+        let size = Widget::<()>::dimensions(
+            &mut container,
+            &mut internal_state,
+            &Dimensions::new(100.0, 100.0),
+        );
+        assert_eq!((size.width, size.height), (21.0, 24.0));
+
+        // the child starts inside the top and left sides
+        Widget::<()>::set_top_left_position(
+            &mut container,
+            &mut internal_state,
+            vec2(0.0, 0.0),
+        );
+        assert_eq!(container.background.model.top_left, vec2(2.0, 1.0));
+        assert_eq!(container.background.model.bottom_right, vec2(17.0, 21.0));
+    }
+}
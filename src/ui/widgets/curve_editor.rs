@@ -0,0 +1,371 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    math::{Curve, CurvePoint, Interpolation},
+    ui::{
+        frame_arena::TempVec,
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{Element, Widget},
+        Id, Input, InternalState,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// The number of grid cells along each axis of the editor.
+const GRID_DIVISIONS: usize = 4;
+
+/// A CurveEditor's state is stored in the UI InternalState so drags persist
+/// between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct CurveEditorState {
+    /// The index of the point being dragged.
+    dragging: Option<usize>,
+
+    /// The index of the point under the mouse.
+    hovered: Option<usize>,
+}
+
+/// A CurveEditor displays a [`Curve`] and lets the user edit its points.
+///
+/// The editor works on a copy of the application's curve. Every edit emits
+/// the entire updated curve so the application can replace its own copy.
+///
+/// # Controls
+///
+/// - Drag a point to move it.
+/// - Click an empty spot to add a point.
+/// - Right-click a point to remove it.
+/// - Middle-click a point to cycle its interpolation mode.
+pub struct CurveEditor<Message> {
+    /// The ID uniquely identifies this editor when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The curve being edited.
+    curve: Curve,
+
+    /// The space occupied by the editor on screen.
+    bounds: Rect,
+
+    /// The range of x values shown by the editor.
+    x_range: (f32, f32),
+
+    /// The range of y values shown by the editor.
+    y_range: (f32, f32),
+
+    /// The ratio of the editor's width to its height.
+    aspect_ratio: f32,
+
+    /// The size of the square drawn for each point.
+    point_size: f32,
+
+    /// The color behind the curve.
+    background_color: Vec4,
+
+    /// The color of the grid lines.
+    grid_color: Vec4,
+
+    /// The color of the curve and its points.
+    color: Vec4,
+
+    /// The color of the point under the mouse.
+    hover_color: Vec4,
+
+    /// The function used to build a message when the curve is edited.
+    on_change: Option<Box<dyn Fn(Curve) -> Message>>,
+}
+
+impl<Message> CurveEditor<Message> {
+    pub fn new(id: Id, curve: Curve) -> Self {
+        Self {
+            id,
+            curve,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            x_range: (0.0, 1.0),
+            y_range: (0.0, 1.0),
+            aspect_ratio: 2.0,
+            point_size: 8.0,
+            background_color: vec4(0.08, 0.08, 0.08, 1.0),
+            grid_color: vec4(1.0, 1.0, 1.0, 0.1),
+            color: vec4(0.3, 0.7, 1.0, 1.0),
+            hover_color: vec4(1.0, 1.0, 1.0, 1.0),
+            on_change: None,
+        }
+    }
+
+    builder_field!(aspect_ratio, f32);
+    builder_field!(point_size, f32);
+    builder_field!(background_color, Vec4);
+    builder_field!(grid_color, Vec4);
+    builder_field!(color, Vec4);
+    builder_field!(hover_color, Vec4);
+
+    /// Set the range of x values shown by the editor. Points can't be dragged
+    /// outside of this range.
+    pub fn x_range(self, min: f32, max: f32) -> Self {
+        Self {
+            x_range: (min, max),
+            ..self
+        }
+    }
+
+    /// Set the range of y values shown by the editor. Points can't be dragged
+    /// outside of this range.
+    pub fn y_range(self, min: f32, max: f32) -> Self {
+        Self {
+            y_range: (min, max),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user edits the
+    /// curve. The function is given the updated curve.
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(Curve) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> CurveEditor<Message> {
+    /// Convert a point on the curve into screen space. Y values increase up
+    /// the screen.
+    fn to_screen(&self, x: f32, y: f32) -> Vec2 {
+        let (x_min, x_max) = self.x_range;
+        let (y_min, y_max) = self.y_range;
+        let tx = (x - x_min) / (x_max - x_min);
+        let ty = (y - y_min) / (y_max - y_min);
+        vec2(
+            self.bounds.left() + tx * self.bounds.width(),
+            self.bounds.bottom() - ty * self.bounds.height(),
+        )
+    }
+
+    /// Convert a screen-space point into the curve's coordinates, clamped to
+    /// the editor's ranges.
+    fn to_curve(&self, point: Vec2) -> Vec2 {
+        let (x_min, x_max) = self.x_range;
+        let (y_min, y_max) = self.y_range;
+        let tx = ((point.x - self.bounds.left())
+            / self.bounds.width().max(1.0))
+        .clamp(0.0, 1.0);
+        let ty = ((self.bounds.bottom() - point.y)
+            / self.bounds.height().max(1.0))
+        .clamp(0.0, 1.0);
+        vec2(x_min + tx * (x_max - x_min), y_min + ty * (y_max - y_min))
+    }
+
+    /// Get the point under the mouse. The grab area is larger than the drawn
+    /// point to make the points easier to hit.
+    fn point_at(&self, mouse: Vec2) -> Option<usize> {
+        let grab_size = 2.0 * self.point_size;
+        self.curve.points().iter().position(|point| {
+            let center = self.to_screen(point.x, point.y);
+            Rect::centered_at(center.x, center.y, grab_size, grab_size)
+                .contains(mouse)
+        })
+    }
+
+    fn changed(&self) -> Option<Message> {
+        self.on_change
+            .as_ref()
+            .map(|on_change| on_change(self.curve.clone()))
+    }
+}
+
+impl<Message> Widget<Message> for CurveEditor<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mut state = *internal_state.get_state::<CurveEditorState>(&self.id);
+        let mouse = input.mouse_position;
+        let inside = self.bounds.contains(mouse);
+
+        let message_opt = match *event {
+            WindowEvent::CursorPos(..) => match state.dragging {
+                Some(index) => {
+                    let position = self.to_curve(mouse);
+                    state.dragging =
+                        self.curve.move_point(index, position.x, position.y);
+                    state.hovered = state.dragging;
+                    self.changed()
+                }
+                None => {
+                    state.hovered = self.point_at(mouse);
+                    None
+                }
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if inside => match self.point_at(mouse) {
+                Some(index) => {
+                    state.dragging = Some(index);
+                    None
+                }
+                None => {
+                    let position = self.to_curve(mouse);
+                    let index = self.curve.insert(CurvePoint::new(
+                        position.x,
+                        position.y,
+                        Interpolation::default(),
+                    ));
+                    state.dragging = Some(index);
+                    state.hovered = Some(index);
+                    self.changed()
+                }
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button2,
+                Action::Press,
+                _,
+            ) if inside => match self.point_at(mouse) {
+                Some(index) => {
+                    self.curve.remove(index);
+                    state.hovered = None;
+                    self.changed()
+                }
+                None => None,
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button3,
+                Action::Press,
+                _,
+            ) if inside => match self.point_at(mouse) {
+                Some(index) => {
+                    let next = self.curve.points()[index].interpolation.next();
+                    self.curve.set_interpolation(index, next);
+                    self.changed()
+                }
+                None => None,
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = None;
+                None
+            }
+            _ => None,
+        };
+
+        *internal_state.get_state_mut::<CurveEditorState>(&self.id) = state;
+        Ok(message_opt)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<CurveEditorState>(&self.id);
+
+        Tile {
+            model: self.bounds,
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let grid_line = Line {
+            color: self.grid_color,
+            ..Default::default()
+        };
+        for i in 0..=GRID_DIVISIONS {
+            let t = i as f32 / GRID_DIVISIONS as f32;
+            let x = self.bounds.left() + t * self.bounds.width();
+            let y = self.bounds.top() + t * self.bounds.height();
+            grid_line.draw_polyline(
+                &[vec2(x, self.bounds.top()), vec2(x, self.bounds.bottom())],
+                frame,
+            )?;
+            grid_line.draw_polyline(
+                &[vec2(self.bounds.left(), y), vec2(self.bounds.right(), y)],
+                frame,
+            )?;
+        }
+
+        // sample the curve about every other pixel
+        let (x_min, x_max) = self.x_range;
+        let samples = (0.5 * self.bounds.width()).max(2.0) as usize;
+        let points: TempVec<Vec2> = (0..=samples)
+            .map(|i| {
+                let x = x_min + (x_max - x_min) * i as f32 / samples as f32;
+                let y = self
+                    .curve
+                    .evaluate(x)
+                    .clamp(self.y_range.0, self.y_range.1);
+                self.to_screen(x, y)
+            })
+            .collect();
+        Line {
+            width: 2.0,
+            color: self.color,
+            ..Default::default()
+        }
+        .draw_polyline(&points, frame)?;
+
+        for (index, point) in self.curve.points().iter().enumerate() {
+            let center = self.to_screen(point.x, point.y);
+            let color = if state.hovered == Some(index) {
+                self.hover_color
+            } else {
+                self.color
+            };
+            Tile {
+                model: Rect::centered_at(
+                    center.x,
+                    center.y,
+                    self.point_size,
+                    self.point_size,
+                ),
+                color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let width = max_size.width;
+        let height = width / self.aspect_ratio.max(f32::EPSILON);
+        self.bounds = Dimensions::new(width, height).min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for CurveEditor<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,225 @@
+use ::{anyhow::Result, std::time::Duration};
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::{Frame, FrameStats},
+    timing::FrameRateLimit,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Label, Widget},
+        Font, Input, InternalState, Theme,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// The narrowest the frame time graph is drawn.
+const MIN_GRAPH_WIDTH: f32 = 160.0;
+
+/// A Diagnostics panel shows the frame rate, a graph of recent frame times,
+/// and how much geometry was drawn.
+///
+/// The graph's height is twice the frame rate limit's target, with a line at
+/// the target. Frames which took longer than the target are drawn in the
+/// slow frame color.
+///
+/// The demo [`crate::demo::Application`] can draw one on top of everything
+/// else, see [`crate::demo::AppBuilder::diagnostics`].
+pub struct Diagnostics {
+    text: Label,
+    text_size: Dimensions,
+
+    /// Recent frame times in seconds, oldest first.
+    frame_times: Vec<f32>,
+
+    /// The frame rate limit's target frame time in seconds.
+    target: f32,
+
+    /// The space occupied by the panel on screen.
+    bounds: Rect,
+
+    /// The font's line height, which the theme's padding is scaled by.
+    line_height: f32,
+
+    padding: Option<f32>,
+    graph_height: f32,
+    background_color: Option<Vec4>,
+    graph_color: Option<Vec4>,
+    slow_frame_color: Vec4,
+    target_color: Option<Vec4>,
+}
+
+impl Diagnostics {
+    /// Create a panel for the frame rate limit's recent frames and the
+    /// given geometry counts.
+    pub fn new(
+        font: &Font,
+        fps_limit: &FrameRateLimit,
+        stats: FrameStats,
+    ) -> Self {
+        let frame_times: Vec<f32> = fps_limit
+            .frame_times()
+            .iter()
+            .map(Duration::as_secs_f32)
+            .collect();
+        let target = fps_limit.target_duration().as_secs_f32();
+        let average = if frame_times.is_empty() {
+            target
+        } else {
+            frame_times.iter().sum::<f32>() / frame_times.len() as f32
+        };
+        let text = Label::new(
+            font,
+            format!(
+                "{:.0} fps ({:.2} ms)\n{} vertices, {} triangles\n{} draw calls",
+                1.0 / average.max(f32::EPSILON),
+                average * 1000.0,
+                stats.vertices,
+                stats.triangles,
+                stats.draw_calls,
+            ),
+        );
+        Self {
+            text,
+            text_size: Dimensions::new(0.0, 0.0),
+            frame_times,
+            target,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            line_height: font.line_height(),
+            padding: None,
+            graph_height: 3.0 * font.line_height(),
+            background_color: None,
+            graph_color: None,
+            slow_frame_color: vec4(0.9, 0.3, 0.2, 1.0),
+            target_color: None,
+        }
+    }
+
+    builder_field_some!(padding, f32);
+    builder_field!(graph_height, f32);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(graph_color, Vec4);
+    builder_field!(slow_frame_color, Vec4);
+    builder_field_some!(target_color, Vec4);
+
+    /// The space between the panel's edges, the text, and the graph.
+    fn themed_padding(&self, theme: &Theme) -> f32 {
+        self.padding.unwrap_or(theme.padding * self.line_height)
+    }
+
+    /// The region covered by the frame time graph.
+    fn graph_bounds(&self, theme: &Theme) -> Rect {
+        let padding = self.themed_padding(theme);
+        let top = self.bounds.top() + 2.0 * padding + self.text_size.height;
+        Rect::new(
+            top,
+            self.bounds.left() + padding,
+            top + self.graph_height,
+            self.bounds.right() - padding,
+        )
+    }
+}
+
+impl<Message> Widget<Message> for Diagnostics {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        Tile {
+            model: self.bounds,
+            color: self.background_color.unwrap_or(theme.panel_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Widget::<Message>::draw_frame(&self.text, internal_state, frame)?;
+
+        let graph = self.graph_bounds(&theme);
+        let max_time = 2.0 * self.target.max(f32::EPSILON);
+        let bar_width = graph.width() / self.frame_times.len().max(1) as f32;
+        for (i, &time) in self.frame_times.iter().enumerate() {
+            let left = graph.left() + i as f32 * bar_width;
+            let height = graph.height() * (time / max_time).min(1.0);
+            Tile {
+                model: Rect::new(
+                    (graph.bottom() - height).round(),
+                    left.round(),
+                    graph.bottom(),
+                    (left + bar_width).round(),
+                ),
+                color: if time > self.target {
+                    self.slow_frame_color
+                } else {
+                    self.graph_color.unwrap_or(theme.accent_color)
+                },
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        let target_y = (graph.top() + 0.5 * graph.height()).round();
+        Tile {
+            model: Rect::new(
+                target_y,
+                graph.left(),
+                target_y + 1.0,
+                graph.right(),
+            ),
+            color: self.target_color.unwrap_or(theme.detail_color),
+            ..Default::default()
+        }
+        .fill(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.text_size = Widget::<Message>::dimensions(
+            &mut self.text,
+            internal_state,
+            max_size,
+        );
+        let padding = self.themed_padding(internal_state.theme());
+        let width = self.text_size.width.max(MIN_GRAPH_WIDTH);
+        let height = self.text_size.height + self.graph_height;
+        self.bounds =
+            Dimensions::new(width + 2.0 * padding, height + 3.0 * padding)
+                .min(max_size)
+                .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let padding = self.themed_padding(internal_state.theme());
+        self.bounds = self.bounds.set_top_left_position(position);
+        Widget::<Message>::set_top_left_position(
+            &mut self.text,
+            internal_state,
+            position + vec2(padding, padding),
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for Diagnostics
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,440 @@
+use ::anyhow::Result;
+
+use crate::{
+    asset_loader::TextureId,
+    builder_field_some,
+    immediate_mode_graphics::{triangles::Frame, Vertex, VertexStream},
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{button::ButtonState, Element, Label, OverlayEvent, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, vec3, Vec2, Vec4,
+};
+
+/// A Dropdown's state is stored in the UI InternalState so the open list
+/// persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct DropdownState {
+    /// True when the list of options is visible.
+    open: bool,
+
+    /// The mouse interaction state for the dropdown's header.
+    interaction: ButtonState,
+
+    /// The option under the mouse while the list is open.
+    hovered_option: Option<usize>,
+
+    /// The option the mouse was pressed on while the list is open.
+    pressed_option: Option<usize>,
+}
+
+/// A Dropdown shows the currently selected option. Clicking it opens a list
+/// of every option in the overlay, on top of the rest of the UI.
+pub struct Dropdown<Message> {
+    /// The ID uniquely identifies this dropdown when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// A label for each option, positioned inside the list.
+    options: Vec<Label>,
+
+    /// The index of the selected option.
+    selected: Option<usize>,
+
+    /// A copy of the selected option's label, positioned inside the header.
+    selected_label: Option<Label>,
+
+    /// The space occupied by the dropdown's header on screen.
+    header: Rect,
+
+    /// The space occupied by the open list of options on screen.
+    list: Rect,
+
+    /// The space occupied by each option in the open list.
+    option_rows: Vec<Rect>,
+
+    /// The space between the text and the edges of each row. None uses the
+    /// theme's padding.
+    padding: Option<f32>,
+
+    /// The font's line height, used to size each row.
+    line_height: f32,
+
+    /// The header's default color.
+    color: Option<Vec4>,
+
+    /// The color of the header and of options when hovered.
+    hover_color: Option<Vec4>,
+
+    /// The background color of the list of options.
+    list_color: Option<Vec4>,
+
+    /// The color of the open/closed indicator and the list border.
+    accent_color: Option<Vec4>,
+
+    /// The function used to build a message when an option is selected.
+    on_select: Option<Box<dyn Fn(usize) -> Message>>,
+}
+
+impl<Message> Dropdown<Message> {
+    pub fn new<T, I>(id: Id, font: &Font, options: I) -> Self
+    where
+        T: AsRef<str>,
+        I: IntoIterator<Item = T>,
+    {
+        let line_height = font.line_height();
+        Self {
+            id,
+            options: options
+                .into_iter()
+                .map(|option| Label::new(font, option))
+                .collect(),
+            selected: None,
+            selected_label: None,
+            header: Rect::new(0.0, 0.0, 0.0, 0.0),
+            list: Rect::new(0.0, 0.0, 0.0, 0.0),
+            option_rows: vec![],
+            padding: None,
+            line_height,
+            color: None,
+            hover_color: None,
+            list_color: None,
+            accent_color: None,
+            on_select: None,
+        }
+    }
+
+    builder_field_some!(selected, usize);
+    builder_field_some!(padding, f32);
+    builder_field_some!(color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(list_color, Vec4);
+    builder_field_some!(accent_color, Vec4);
+
+    /// Set the function used to build a message when the user picks an
+    /// option. The function is given the option's index.
+    pub fn on_select<F>(self, on_select_fn: F) -> Self
+    where
+        F: 'static + Fn(usize) -> Message,
+    {
+        Self {
+            on_select: Some(Box::new(on_select_fn)),
+            ..self
+        }
+    }
+
+    /// The space between the text and the edges of each row.
+    fn themed_padding(&self, theme: &Theme) -> f32 {
+        self.padding.unwrap_or(theme.padding * self.line_height)
+    }
+
+    /// The height of the header and of each option in the list.
+    fn row_height(&self, theme: &Theme) -> f32 {
+        self.line_height + 2.0 * self.themed_padding(theme)
+    }
+
+    /// Draw a small triangle on the right side of the header which points
+    /// down when closed and up when open.
+    fn draw_indicator(
+        &self,
+        open: bool,
+        theme: &Theme,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let size = 0.5 * self.line_height;
+        let center = vec2(
+            self.header.right() - self.themed_padding(theme) - 0.5 * size,
+            0.5 * (self.header.top() + self.header.bottom()),
+        );
+        let (tip_y, base_y) = if open {
+            (center.y - 0.25 * size, center.y + 0.25 * size)
+        } else {
+            (center.y + 0.25 * size, center.y - 0.25 * size)
+        };
+        let vertex = |x: f32, y: f32| {
+            Vertex::new(
+                vec3(x, y, 0.0),
+                self.accent_color.unwrap_or(theme.detail_color),
+                vec2(0.0, 0.0),
+                TextureId::WHITE_PIXEL,
+            )
+        };
+        frame.push_vertices(
+            &[
+                vertex(center.x - 0.5 * size, base_y),
+                vertex(center.x + 0.5 * size, base_y),
+                vertex(center.x, tip_y),
+            ],
+            &[0, 1, 2],
+        )
+    }
+}
+
+impl<Message> Widget<Message> for Dropdown<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<DropdownState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                if self.header.contains(vec2(x as f32, y as f32)) {
+                    if state.interaction == ButtonState::Inactive {
+                        state.interaction = ButtonState::Hover;
+                    }
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if state.interaction == ButtonState::Hover => {
+                state.interaction = ButtonState::Pressed;
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) if state.interaction == ButtonState::Pressed => {
+                if self.header.contains(input.mouse_position) {
+                    state.interaction = ButtonState::Hover;
+                    state.open = !state.open;
+                    state.hovered_option = None;
+                    state.pressed_option = None;
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<DropdownState>(&self.id);
+        let color = if state.open || state.interaction != ButtonState::Inactive
+        {
+            self.hover_color.unwrap_or(theme.hover_color)
+        } else {
+            self.color.unwrap_or(theme.widget_color)
+        };
+        Tile {
+            model: self.header,
+            color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        if let Some(label) = &self.selected_label {
+            Widget::<Message>::draw_frame(label, internal_state, frame)?;
+        }
+
+        self.draw_indicator(state.open, &theme, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let theme = *internal_state.theme();
+        let padding = self.themed_padding(&theme);
+        let widest_option = self
+            .options
+            .iter_mut()
+            .map(|option| {
+                Widget::<Message>::dimensions(option, internal_state, max_size)
+                    .width
+            })
+            .fold(0.0, f32::max);
+        let indicator_width = 0.5 * self.line_height + padding;
+
+        self.selected_label = self
+            .selected
+            .and_then(|index| self.options.get(index))
+            .cloned();
+
+        self.header = Dimensions::new(
+            widest_option + 2.0 * padding + indicator_width,
+            self.row_height(&theme),
+        )
+        .min(max_size)
+        .as_rect();
+        self.header.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let theme = *internal_state.theme();
+        self.header = self.header.set_top_left_position(position);
+        let padding = self.themed_padding(&theme);
+        let text_offset = vec2(padding, padding);
+
+        if let Some(label) = &mut self.selected_label {
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                position + text_offset,
+            );
+        }
+
+        let row_height = self.row_height(&theme);
+        let width = self.header.width();
+        self.option_rows.clear();
+        for (index, option) in self.options.iter_mut().enumerate() {
+            let row_top_left = vec2(
+                self.header.left(),
+                self.header.bottom() + index as f32 * row_height,
+            );
+            self.option_rows.push(
+                Dimensions::new(width, row_height)
+                    .as_rect()
+                    .set_top_left_position(row_top_left),
+            );
+            Widget::<Message>::set_top_left_position(
+                option,
+                internal_state,
+                row_top_left + text_offset,
+            );
+        }
+        self.list = Rect::new(
+            self.header.bottom(),
+            self.header.left(),
+            self.header.bottom() + self.options.len() as f32 * row_height,
+            self.header.right(),
+        );
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<DropdownState>(&self.id);
+        if !state.open {
+            return Ok(OverlayEvent::Ignored);
+        }
+
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                let mouse = vec2(x as f32, y as f32);
+                state.hovered_option =
+                    self.option_rows.iter().position(|row| row.contains(mouse));
+
+                // mouse movement still reaches the rest of the UI so hover
+                // states stay up to date
+                Ok(OverlayEvent::Ignored)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if self.list.contains(input.mouse_position) {
+                    state.pressed_option = state.hovered_option;
+                    Ok(OverlayEvent::Captured(None))
+                } else {
+                    if !self.header.contains(input.mouse_position) {
+                        // clicking anywhere else closes the list, the header
+                        // handles toggling on its own
+                        state.open = false;
+                    }
+                    Ok(OverlayEvent::Ignored)
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                if !self.list.contains(input.mouse_position) {
+                    return Ok(OverlayEvent::Ignored);
+                }
+                let pressed_option = state.pressed_option.take();
+                match state.hovered_option {
+                    Some(index) if pressed_option == Some(index) => {
+                        state.open = false;
+                        state.hovered_option = None;
+                        Ok(OverlayEvent::Captured(
+                            self.on_select
+                                .as_ref()
+                                .map(|on_select| on_select(index)),
+                        ))
+                    }
+                    _ => Ok(OverlayEvent::Captured(None)),
+                }
+            }
+            _ => Ok(OverlayEvent::Ignored),
+        }
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<DropdownState>(&self.id);
+        if !state.open || self.options.is_empty() {
+            return Ok(());
+        }
+
+        Tile {
+            model: self.list,
+            color: self.list_color.unwrap_or(theme.panel_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let highlighted = state
+            .hovered_option
+            .or(self.selected)
+            .and_then(|index| self.option_rows.get(index));
+        if let Some(row) = highlighted {
+            Tile {
+                model: *row,
+                color: self.hover_color.unwrap_or(theme.hover_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        for option in &self.options {
+            Widget::<Message>::draw_frame(option, internal_state, frame)?;
+        }
+
+        Tile {
+            model: self.list,
+            color: self.accent_color.unwrap_or(theme.detail_color),
+            ..Default::default()
+        }
+        .outline(frame)
+    }
+}
+
+impl<Message> Into<Element<Message>> for Dropdown<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
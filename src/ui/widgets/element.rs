@@ -2,24 +2,60 @@ use ::anyhow::Result;
 
 use crate::{
     immediate_mode_graphics::triangles::Frame,
-    ui::{primitives::Dimensions, widgets::Widget, Input, InternalState},
-    Vec2,
+    ui::{
+        primitives::Dimensions,
+        widgets::{OverlayEvent, Widget},
+        Input, InternalState,
+    },
+    vec2, Vec2,
 };
 
 /// An Element is a type-erased widget.
 /// Elements allow UI objects to hold a variety of Widget implementations and
 /// dynamically dispatch function calls as needed.
 ///
+/// Elements round the positions given to their widget to whole pixels when
+/// the theme's [`crate::ui::Theme::pixel_snapping`] is enabled.
 pub struct Element<Message> {
     pub(crate) widget: Box<dyn Widget<Message>>,
+
+    /// Names the element's subtree in errors while it's drawn, see
+    /// [`Frame::push_debug_label`].
+    debug_label: Option<String>,
 }
 
 impl<Message> Element<Message> {
     pub fn new(widget: impl Widget<Message> + 'static) -> Self {
         Self {
             widget: Box::new(widget),
+            debug_label: None,
+        }
+    }
+
+    /// Name the element's subtree. Errors while it's drawn say which
+    /// subtree was being drawn.
+    pub fn debug_label(self, label: impl Into<String>) -> Self {
+        Self {
+            debug_label: Some(label.into()),
+            ..self
         }
     }
+
+    /// Call the draw function with the element's debug label pushed onto the
+    /// frame's stack.
+    fn labeled<F>(&self, frame: &mut Frame, draw_fn: F) -> Result<()>
+    where
+        F: FnOnce(&mut Frame) -> Result<()>,
+    {
+        let label = match &self.debug_label {
+            Some(label) => label,
+            None => return draw_fn(frame),
+        };
+        frame.push_debug_label(label.clone());
+        let result = draw_fn(frame);
+        frame.pop_debug_label();
+        result
+    }
 }
 
 impl<Message> Widget<Message> for Element<Message> {
@@ -37,7 +73,9 @@ impl<Message> Widget<Message> for Element<Message> {
         internal_state: &mut InternalState,
         frame: &mut Frame,
     ) -> Result<()> {
-        self.widget.draw_frame(internal_state, frame)
+        self.labeled(frame, |frame| {
+            self.widget.draw_frame(internal_state, frame)
+        })
     }
 
     fn dimensions(
@@ -53,6 +91,31 @@ impl<Message> Widget<Message> for Element<Message> {
         internal_state: &mut InternalState,
         position: Vec2,
     ) {
+        let position = if internal_state.theme().pixel_snapping {
+            vec2(position.x.round(), position.y.round())
+        } else {
+            position
+        };
         self.widget.set_top_left_position(internal_state, position)
     }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.widget
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.labeled(frame, |frame| {
+            self.widget.draw_overlay(internal_state, frame)
+        })
+    }
 }
@@ -0,0 +1,367 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    math::color::{linear_to_srgb, rgb_to_hsv},
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{button::ButtonState, Element, Label, OverlayEvent, Widget},
+        Font, Id, Input, InternalState,
+    },
+    vec2, vec3, vec4, Vec2, Vec4,
+};
+
+/// An Eyedropper's state is stored in the UI InternalState so it stays armed
+/// between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct EyedropperState {
+    /// True when the next click anywhere in the window picks a color.
+    armed: bool,
+
+    /// The mouse interaction state for the eyedropper itself.
+    interaction: ButtonState,
+
+    /// The mouse position while armed, used to draw the crosshair.
+    mouse_position: Vec2,
+}
+
+/// An Eyedropper shows a color swatch with its RGBA and HSV values. Clicking
+/// the eyedropper arms it, then the next click anywhere in the window emits
+/// the clicked position so the application can read back the rendered pixel.
+///
+/// Colors are linear, e.g. the `linear` value of a
+/// [`crate::frame_pipeline::PixelColor`]. The swatch draws the color exactly,
+/// while the text shows the 8-bit sRGB values seen on screen.
+///
+/// # Controls
+///
+/// - Click the eyedropper to arm it.
+/// - Click anywhere to pick the color under the mouse.
+/// - Right-click to cancel.
+pub struct Eyedropper<Message> {
+    /// The ID uniquely identifies this eyedropper when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The font used to build the value labels.
+    font: Font,
+
+    /// The color shown in the swatch.
+    color: Option<Vec4>,
+
+    /// The text rendered beside the swatch, one label per line.
+    labels: Vec<Label>,
+
+    /// The space occupied by the entire eyedropper on screen.
+    bounds: Rect,
+
+    /// The space occupied by the swatch.
+    swatch: Rect,
+
+    /// The horizontal space between the swatch and the labels.
+    spacing: f32,
+
+    /// The color of the swatch's outline and the crosshair.
+    accent_color: Vec4,
+
+    /// The color behind the eyedropper when hovered or armed.
+    hover_color: Vec4,
+
+    /// The function used to build a message when a position is picked.
+    on_pick: Option<Box<dyn Fn(Vec2) -> Message>>,
+}
+
+impl<Message> Eyedropper<Message> {
+    pub fn new(id: Id, font: &Font) -> Self {
+        Self {
+            id,
+            font: font.clone(),
+            color: None,
+            labels: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            swatch: Rect::new(0.0, 0.0, 0.0, 0.0),
+            spacing: 0.5 * font.line_height(),
+            accent_color: vec4(1.0, 1.0, 1.0, 1.0),
+            hover_color: vec4(1.0, 1.0, 1.0, 0.1),
+            on_pick: None,
+        }
+    }
+
+    builder_field_some!(color, Vec4);
+    builder_field!(spacing, f32);
+    builder_field!(accent_color, Vec4);
+    builder_field!(hover_color, Vec4);
+
+    /// Set the function used to build a message when the user clicks while
+    /// the eyedropper is armed. The function is given the clicked position
+    /// in UI screen space.
+    pub fn on_pick<F>(self, on_pick_fn: F) -> Self
+    where
+        F: 'static + Fn(Vec2) -> Message,
+    {
+        Self {
+            on_pick: Some(Box::new(on_pick_fn)),
+            ..self
+        }
+    }
+
+    /// The text shown beside the swatch.
+    fn describe(&self, armed: bool) -> Vec<String> {
+        if armed {
+            return vec!["Click to pick a color".to_owned()];
+        }
+        let color = match self.color {
+            Some(color) => color,
+            None => return vec!["No color".to_owned()],
+        };
+
+        let display = vec3(
+            linear_to_srgb(color.x.clamp(0.0, 1.0)),
+            linear_to_srgb(color.y.clamp(0.0, 1.0)),
+            linear_to_srgb(color.z.clamp(0.0, 1.0)),
+        );
+        let byte = |value: f32| (value * 255.0).round() as u8;
+        let hsv = rgb_to_hsv(display);
+        vec![
+            format!(
+                "RGBA {} {} {} {}",
+                byte(display.x),
+                byte(display.y),
+                byte(display.z),
+                byte(color.w.clamp(0.0, 1.0)),
+            ),
+            format!("HSV {:.0} {:.2} {:.2}", hsv.x, hsv.y, hsv.z),
+        ]
+    }
+}
+
+impl<Message> Widget<Message> for Eyedropper<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<EyedropperState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                if self.bounds.contains(vec2(x as f32, y as f32)) {
+                    if state.interaction == ButtonState::Inactive {
+                        state.interaction = ButtonState::Hover;
+                    }
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if state.interaction == ButtonState::Hover => {
+                state.interaction = ButtonState::Pressed;
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) if state.interaction == ButtonState::Pressed => {
+                if self.bounds.contains(input.mouse_position) {
+                    state.interaction = ButtonState::Hover;
+                    state.armed = true;
+                    state.mouse_position = input.mouse_position;
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<EyedropperState>(&self.id);
+
+        if state.armed || state.interaction != ButtonState::Inactive {
+            Tile {
+                model: self.bounds,
+                color: self.hover_color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        if let Some(color) = self.color {
+            Tile {
+                model: self.swatch,
+                color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        Tile {
+            model: self.swatch,
+            color: self.accent_color,
+            ..Default::default()
+        }
+        .outline(frame)?;
+
+        for label in &self.labels {
+            Widget::<Message>::draw_frame(label, internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let armed = internal_state.get_state::<EyedropperState>(&self.id).armed;
+        let font = &self.font;
+        self.labels = self
+            .describe(armed)
+            .iter()
+            .map(|text| Label::new(font, text))
+            .collect();
+
+        let label_max_size = Dimensions::new(
+            0f32.max(max_size.width - self.spacing),
+            max_size.height,
+        );
+        let mut text_size = Dimensions::new(0.0, 0.0);
+        for label in &mut self.labels {
+            let size = Widget::<Message>::dimensions(
+                label,
+                internal_state,
+                &label_max_size,
+            );
+            text_size.width = text_size.width.max(size.width);
+            text_size.height += size.height;
+        }
+
+        // The swatch is a square which fits two lines of text.
+        let swatch_size = text_size.height.max(2.0 * self.font.line_height());
+        self.swatch = Dimensions::new(swatch_size, swatch_size).as_rect();
+
+        self.bounds = Dimensions::new(
+            swatch_size + self.spacing + text_size.width,
+            swatch_size,
+        )
+        .min(max_size)
+        .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.swatch = self.swatch.set_top_left_position(position);
+
+        let mut label_position =
+            position + vec2(self.swatch.width() + self.spacing, 0.0);
+        for label in &mut self.labels {
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                label_position,
+            );
+            label_position.y += self.font.line_height();
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<EyedropperState>(&self.id);
+        if !state.armed {
+            return Ok(OverlayEvent::Ignored);
+        }
+
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.mouse_position = input.mouse_position;
+                Ok(OverlayEvent::Ignored)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                state.armed = false;
+                Ok(OverlayEvent::Captured(
+                    self.on_pick
+                        .as_ref()
+                        .map(|on_pick| on_pick(input.mouse_position)),
+                ))
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button2,
+                Action::Press,
+                _,
+            ) => {
+                state.armed = false;
+                Ok(OverlayEvent::Captured(None))
+            }
+            _ => Ok(OverlayEvent::Ignored),
+        }
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<EyedropperState>(&self.id);
+        if !state.armed {
+            return Ok(());
+        }
+
+        // a crosshair with a gap in the middle so the picked pixel stays
+        // visible
+        let center = state.mouse_position;
+        let gap = 3.0;
+        let size = self.font.line_height();
+        let line = Line {
+            color: self.accent_color,
+            ..Default::default()
+        };
+        for &direction in &[
+            vec2(1.0, 0.0),
+            vec2(-1.0, 0.0),
+            vec2(0.0, 1.0),
+            vec2(0.0, -1.0),
+        ] {
+            Line {
+                start: center + direction * gap,
+                end: center + direction * size,
+                ..line
+            }
+            .draw(frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Eyedropper<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
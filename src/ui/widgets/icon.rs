@@ -0,0 +1,116 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect},
+        widgets::{Element, Label, Widget},
+        Font, Input, InternalState,
+    },
+    vec2, Vec2,
+};
+
+/// An Icon draws a single glyph from an icon font, centered in a square which
+/// is as tall as the font's line height. Every icon takes the same space, so
+/// rows of icon buttons line up no matter how wide each glyph is.
+///
+/// Icons are looked up by the names given to [`Font::with_icons`].
+pub struct Icon {
+    glyph: Label,
+
+    /// The glyph's size, measured when the icon is laid out.
+    glyph_size: Dimensions,
+
+    /// The square which holds the glyph.
+    bounds: Rect,
+}
+
+impl Icon {
+    /// Create an icon for a named glyph. Names the font doesn't know are
+    /// drawn as text so a missing icon is easy to spot.
+    pub fn new(font: &Font, name: &str) -> Self {
+        match font.icon(name) {
+            Some(codepoint) => Self::from_codepoint(font, codepoint),
+            None => Self::with_label(font, Label::new(font, name)),
+        }
+    }
+
+    /// Create an icon for a glyph by its codepoint.
+    pub fn from_codepoint(font: &Font, codepoint: char) -> Self {
+        let mut buffer = [0; 4];
+        let glyph = Label::new(font, codepoint.encode_utf8(&mut buffer));
+        Self::with_label(font, glyph)
+    }
+
+    fn with_label(font: &Font, glyph: Label) -> Self {
+        let size = font.line_height();
+        Self {
+            glyph,
+            glyph_size: Dimensions::new(0.0, 0.0),
+            bounds: Dimensions::new(size, size).as_rect(),
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Icon {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        Widget::<Message>::draw_frame(&self.glyph, internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.glyph_size = Widget::<Message>::dimensions(
+            &mut self.glyph,
+            internal_state,
+            max_size,
+        );
+        // glyphs which are wider than the line, like a missing icon's name,
+        // widen the icon rather than overflowing it
+        let size = self.bounds.height();
+        self.bounds =
+            Dimensions::new(size.max(self.glyph_size.width), size).as_rect();
+        self.bounds.dimensions().min(max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        let offset = vec2(
+            0.5 * (self.bounds.width() - self.glyph_size.width),
+            0.5 * (self.bounds.height() - self.glyph_size.height),
+        );
+        Widget::<Message>::set_top_left_position(
+            &mut self.glyph,
+            internal_state,
+            position + offset,
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for Icon
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,292 @@
+use ::anyhow::Result;
+
+use crate::{
+    asset_loader::{AssetLoader, TextureId},
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Widget},
+        Input, InternalState,
+    },
+    vec4, Vec2, Vec4,
+};
+
+/// How an Image is sized inside the space its parent allows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImageFit {
+    /// Use the image's size in pixels. Images which are too large are scaled
+    /// down to fit, keeping their aspect ratio.
+    Intrinsic,
+
+    /// Scale the image up or down to fit the available space, keeping its
+    /// aspect ratio. The whole image is always visible.
+    Fit,
+
+    /// Fill the available space, keeping the image's aspect ratio. Whatever
+    /// doesn't fit is cropped evenly from both sides.
+    Cover,
+
+    /// Fill the available space, stretching the image if the aspect ratios
+    /// don't match.
+    Stretch,
+}
+
+/// An Image displays a texture, or part of a texture, inside the layout.
+///
+/// The part of the texture is selected with [`Image::uv`], which makes it
+/// possible to show a single sprite from an atlas. The image's intrinsic
+/// size is the size of that part in pixels.
+pub struct Image<Message> {
+    /// The texture, usually from an [`AssetLoader`].
+    texture_index: TextureId,
+
+    /// The size of the entire texture in pixels.
+    texture_size: Dimensions,
+
+    /// The part of the texture to show, in texture coordinates.
+    uv: Rect,
+
+    /// How the image is sized.
+    fit: ImageFit,
+
+    /// The color multiplied with the texture.
+    color: Vec4,
+
+    /// The space occupied by the image on screen.
+    bounds: Rect,
+
+    /// The part of the texture which is drawn. This is smaller than `uv`
+    /// when the image is cropped to cover its bounds.
+    visible_uv: Rect,
+
+    _phantom_data: std::marker::PhantomData<Message>,
+}
+
+impl<Message> Image<Message> {
+    /// Create an image for a texture which is the given size in pixels.
+    pub fn new(texture_index: TextureId, width: f32, height: f32) -> Self {
+        let full_texture = Rect::new(0.0, 0.0, 1.0, 1.0);
+        Self {
+            texture_index,
+            texture_size: Dimensions::new(width, height),
+            uv: full_texture,
+            fit: ImageFit::Intrinsic,
+            color: vec4(1.0, 1.0, 1.0, 1.0),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            visible_uv: full_texture,
+            _phantom_data: Default::default(),
+        }
+    }
+
+    /// Create an image for one of the asset loader's textures, using the
+    /// texture's size.
+    pub fn for_texture(
+        asset_loader: &AssetLoader,
+        texture_index: TextureId,
+    ) -> Self {
+        let (width, height) = asset_loader
+            .texture_info()
+            .get(texture_index.index() as usize)
+            .map(|info| (info.width as f32, info.height as f32))
+            .unwrap_or((0.0, 0.0));
+        Self::new(texture_index, width, height)
+    }
+
+    builder_field!(fit, ImageFit);
+    builder_field!(color, Vec4);
+
+    /// Show only part of the texture. The rect is in texture coordinates,
+    /// where (0, 0) is the texture's top left and (1, 1) its bottom right.
+    pub fn uv(self, uv: Rect) -> Self {
+        Self { uv, ..self }
+    }
+
+    /// Show only part of the texture, given in pixels from the texture's top
+    /// left. Useful for picking a sprite out of an atlas.
+    pub fn pixel_region(self, region: Rect) -> Self {
+        let (width, height) = (
+            self.texture_size.width.max(1.0),
+            self.texture_size.height.max(1.0),
+        );
+        let uv = Rect::new(
+            region.top() / height,
+            region.left() / width,
+            region.bottom() / height,
+            region.right() / width,
+        );
+        self.uv(uv)
+    }
+}
+
+impl<Message> Widget<Message> for Image<Message> {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        _internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        Tile {
+            model: self.bounds,
+            uv: self.visible_uv,
+            color: self.color,
+            texture_index: self.texture_index,
+            ..Default::default()
+        }
+        .fill(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let region = Dimensions::new(
+            self.texture_size.width * self.uv.width(),
+            self.texture_size.height * self.uv.height(),
+        );
+        let (size, visible_uv) = fit_image(self.fit, region, self.uv, max_size);
+        self.visible_uv = visible_uv;
+        self.bounds = size.min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for Image<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Compute the size of an image and the part of its texture which is
+/// visible.
+///
+/// Parents use f32::MAX for unbounded axes, e.g. a scroll view's height, so
+/// those axes are sized as if the image was fit to the bounded axes.
+fn fit_image(
+    fit: ImageFit,
+    region: Dimensions,
+    uv: Rect,
+    max_size: &Dimensions,
+) -> (Dimensions, Rect) {
+    if region.width <= 0.0 || region.height <= 0.0 {
+        return (Dimensions::new(0.0, 0.0), uv);
+    }
+
+    let bounded = |max: f32| max.is_finite() && max < f32::MAX;
+    let mut fit_scale = f32::MAX;
+    if bounded(max_size.width) {
+        fit_scale = fit_scale.min(max_size.width / region.width);
+    }
+    if bounded(max_size.height) {
+        fit_scale = fit_scale.min(max_size.height / region.height);
+    }
+    if fit_scale == f32::MAX {
+        fit_scale = 1.0;
+    }
+    let scaled = |scale: f32| {
+        Dimensions::new(region.width * scale, region.height * scale)
+    };
+    let fill = || {
+        let fitted = scaled(fit_scale);
+        Dimensions::new(
+            if bounded(max_size.width) {
+                max_size.width
+            } else {
+                fitted.width
+            },
+            if bounded(max_size.height) {
+                max_size.height
+            } else {
+                fitted.height
+            },
+        )
+    };
+
+    match fit {
+        ImageFit::Intrinsic => (scaled(fit_scale.min(1.0)), uv),
+        ImageFit::Fit => (scaled(fit_scale), uv),
+        ImageFit::Stretch => (fill(), uv),
+        ImageFit::Cover => {
+            let target = fill();
+            let scale = (target.width / region.width)
+                .max(target.height / region.height);
+            let visible_width = target.width / (region.width * scale);
+            let visible_height = target.height / (region.height * scale);
+            // signed, so flipped uvs are cropped the same way
+            let crop_x = 0.5 * (1.0 - visible_width) * (uv.right() - uv.left());
+            let crop_y =
+                0.5 * (1.0 - visible_height) * (uv.bottom() - uv.top());
+            let visible_uv = Rect::new(
+                uv.top() + crop_y,
+                uv.left() + crop_x,
+                uv.bottom() - crop_y,
+                uv.right() - crop_x,
+            );
+            (target, visible_uv)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_dimensions(actual: Dimensions, width: f32, height: f32) {
+        assert!(
+            (actual.width - width).abs() < 1e-4
+                && (actual.height - height).abs() < 1e-4,
+            "expected {}x{}, got {:?}",
+            width,
+            height,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_fit_modes_size_a_wide_image() {
+        let region = Dimensions::new(200.0, 100.0);
+        let uv = Rect::new(0.0, 0.0, 1.0, 1.0);
+        let max = Dimensions::new(100.0, 100.0);
+
+        let (intrinsic, _) = fit_image(ImageFit::Intrinsic, region, uv, &max);
+        assert_dimensions(intrinsic, 100.0, 50.0);
+
+        let (fit, _) = fit_image(
+            ImageFit::Fit,
+            region,
+            uv,
+            &Dimensions::new(400.0, f32::MAX),
+        );
+        assert_dimensions(fit, 400.0, 200.0);
+
+        let (stretch, _) = fit_image(ImageFit::Stretch, region, uv, &max);
+        assert_dimensions(stretch, 100.0, 100.0);
+
+        let (cover, visible_uv) = fit_image(ImageFit::Cover, region, uv, &max);
+        assert_dimensions(cover, 100.0, 100.0);
+        assert!((visible_uv.left() - 0.25).abs() < 1e-4);
+        assert!((visible_uv.right() - 0.75).abs() < 1e-4);
+        assert_eq!(visible_uv.top(), 0.0);
+        assert_eq!(visible_uv.bottom(), 1.0);
+    }
+}
@@ -0,0 +1,379 @@
+use ::{anyhow::Result, std::f32::consts::PI};
+
+use crate::{
+    asset_loader::TextureId,
+    builder_field, builder_field_some,
+    immediate_mode_graphics::{triangles::Frame, Vertex, VertexStream},
+    ui::{
+        primitives::{Dimensions, Line, Rect},
+        widgets::{Element, Widget},
+        Id, Input, InternalState,
+    },
+    vec2, vec3, Vec2, Vec4,
+};
+
+/// The angle of the knob's min value, measured clockwise from the right in
+/// screen space. The knob sweeps clockwise from the bottom left to the
+/// bottom right.
+const START_ANGLE: f32 = 0.75 * PI;
+
+/// The angle covered between the knob's min and max values.
+const SWEEP: f32 = 1.5 * PI;
+
+/// The number of segments used for the knob's full circle.
+const CIRCLE_SEGMENTS: u32 = 32;
+
+/// How a knob's value follows the mouse while it's dragged.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum KnobDrag {
+    /// Dragging up increases the value, dragging down decreases it.
+    Vertical,
+
+    /// The knob points at the mouse as it circles the knob.
+    Arc,
+}
+
+/// A knob's drag is kept in the UI InternalState between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct KnobState {
+    /// The mouse's height and the knob's value when the drag started, None
+    /// when the knob isn't being dragged.
+    drag_start: Option<(f32, f32)>,
+}
+
+/// A Knob is a rotary control for a value in a range, common in audio-style
+/// parameter panels. Dragging the knob emits the new value.
+pub struct Knob<Message> {
+    id: Id,
+    min: f32,
+    max: f32,
+    value: f32,
+    bounds: Rect,
+    diameter: f32,
+    drag: KnobDrag,
+
+    /// The vertical distance, in pixels, which drags the knob from its min
+    /// to its max value.
+    drag_distance: f32,
+
+    /// The width of the ring which shows the value.
+    ring_width: f32,
+    color: Option<Vec4>,
+    hover_color: Option<Vec4>,
+    track_color: Option<Vec4>,
+    value_color: Option<Vec4>,
+    indicator_color: Option<Vec4>,
+    on_change: Option<Box<dyn Fn(f32) -> Message>>,
+}
+
+impl<Message> Knob<Message> {
+    pub fn new(id: Id, min: f32, max: f32) -> Self {
+        Self {
+            id,
+            min,
+            max,
+            value: min,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            diameter: 48.0,
+            drag: KnobDrag::Vertical,
+            drag_distance: 200.0,
+            ring_width: 4.0,
+            color: None,
+            hover_color: None,
+            track_color: None,
+            value_color: None,
+            indicator_color: None,
+            on_change: None,
+        }
+    }
+
+    builder_field!(diameter, f32);
+    builder_field!(drag, KnobDrag);
+    builder_field!(drag_distance, f32);
+    builder_field!(ring_width, f32);
+    builder_field_some!(color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(track_color, Vec4);
+    builder_field_some!(value_color, Vec4);
+    builder_field_some!(indicator_color, Vec4);
+
+    /// Set the knob's value. Clamped to the min/max values.
+    pub fn value(self, value: f32) -> Self {
+        Self {
+            value: clamp_to_range(value, self.min, self.max),
+            ..self
+        }
+    }
+
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(f32) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    fn center(&self) -> Vec2 {
+        vec2(
+            0.5 * (self.bounds.left() + self.bounds.right()),
+            0.5 * (self.bounds.top() + self.bounds.bottom()),
+        )
+    }
+
+    /// The knob's value as a fraction of its range.
+    fn normalized(&self) -> f32 {
+        if self.max == self.min {
+            0.0
+        } else {
+            (self.value - self.min) / (self.max - self.min)
+        }
+    }
+
+    /// The value for the mouse's position during a drag.
+    fn value_at(&self, mouse: Vec2, drag_start: (f32, f32)) -> f32 {
+        let (start_y, start_value) = drag_start;
+        let value = match self.drag {
+            KnobDrag::Vertical => {
+                let range = self.max - self.min;
+                let moved = (start_y - mouse.y) / self.drag_distance.max(1.0);
+                start_value + moved * range
+            }
+            KnobDrag::Arc => {
+                let offset = mouse - self.center();
+                let t = normalized_at_angle(offset.y.atan2(offset.x));
+                self.min + t * (self.max - self.min)
+            }
+        };
+        clamp_to_range(value, self.min, self.max)
+    }
+
+    /// Fill the part of a ring between two angles.
+    fn fill_arc(
+        &self,
+        frame: &mut Frame,
+        radius: f32,
+        start: f32,
+        end: f32,
+        color: Vec4,
+    ) -> Result<()> {
+        let segments = ((CIRCLE_SEGMENTS as f32 * (end - start) / (2.0 * PI))
+            .ceil() as u32)
+            .max(1);
+        let center = self.center();
+        let inner = (radius - self.ring_width).max(0.0);
+        let mut vertices = Vec::with_capacity(2 * (segments as usize + 1));
+        for i in 0..=segments {
+            let angle = start + (end - start) * (i as f32 / segments as f32);
+            let direction = vec2(angle.cos(), angle.sin());
+            for r in [radius, inner] {
+                let point = center + r * direction;
+                vertices.push(Vertex::new(
+                    vec3(point.x, point.y, 0.0),
+                    color,
+                    vec2(0.0, 0.0),
+                    TextureId::WHITE_PIXEL,
+                ));
+            }
+        }
+        let indices: Vec<u32> = (0..segments)
+            .flat_map(|i| {
+                let outer = 2 * i;
+                vec![
+                    outer,
+                    outer + 2,
+                    outer + 1,
+                    outer + 1,
+                    outer + 2,
+                    outer + 3,
+                ]
+            })
+            .collect();
+        frame.push_vertices(&vertices, &indices)
+    }
+
+    /// Fill a circle as a fan around its center.
+    fn fill_circle(
+        &self,
+        frame: &mut Frame,
+        radius: f32,
+        color: Vec4,
+    ) -> Result<()> {
+        let center = self.center();
+        let vertex = |point: Vec2| {
+            Vertex::new(
+                vec3(point.x, point.y, 0.0),
+                color,
+                vec2(0.0, 0.0),
+                TextureId::WHITE_PIXEL,
+            )
+        };
+        let mut vertices = Vec::with_capacity(CIRCLE_SEGMENTS as usize + 1);
+        vertices.push(vertex(center));
+        for i in 0..CIRCLE_SEGMENTS {
+            let angle = 2.0 * PI * (i as f32 / CIRCLE_SEGMENTS as f32);
+            vertices
+                .push(vertex(center + radius * vec2(angle.cos(), angle.sin())));
+        }
+        let indices: Vec<u32> = (0..CIRCLE_SEGMENTS)
+            .flat_map(|i| vec![0, i + 1, (i + 1) % CIRCLE_SEGMENTS + 1])
+            .collect();
+        frame.push_vertices(&vertices, &indices)
+    }
+}
+
+impl<Message> Widget<Message> for Knob<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let radius = 0.5 * self.bounds.width().min(self.bounds.height());
+        let is_over = (input.mouse_position - self.center()).norm() <= radius;
+        if let WindowEvent::CursorPos(..) = *event {
+            internal_state.set_hovered(&self.id, is_over);
+        }
+        let state = internal_state.get_state_mut::<KnobState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {}
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if is_over => {
+                state.drag_start = Some((input.mouse_position.y, self.value));
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.drag_start = None;
+            }
+            _ => return Ok(None),
+        }
+
+        let drag_start = match state.drag_start {
+            Some(drag_start) => drag_start,
+            None => return Ok(None),
+        };
+        let value = self.value_at(input.mouse_position, drag_start);
+        Ok(self.on_change.as_ref().map(|on_change| on_change(value)))
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<KnobState>(&self.id);
+        let hovered = internal_state.is_hovered(&self.id);
+        let radius = 0.5 * self.bounds.width().min(self.bounds.height());
+        let value_angle = START_ANGLE + SWEEP * self.normalized();
+
+        self.fill_arc(
+            frame,
+            radius,
+            START_ANGLE,
+            START_ANGLE + SWEEP,
+            self.track_color.unwrap_or(theme.field_color),
+        )?;
+        self.fill_arc(
+            frame,
+            radius,
+            START_ANGLE,
+            value_angle,
+            self.value_color.unwrap_or(theme.accent_color),
+        )?;
+
+        let body = (radius - 2.0 * self.ring_width).max(0.0);
+        let body_color = if hovered || state.drag_start.is_some() {
+            self.hover_color.unwrap_or(theme.hover_color)
+        } else {
+            self.color.unwrap_or(theme.widget_color)
+        };
+        self.fill_circle(frame, body, body_color)?;
+
+        let direction = vec2(value_angle.cos(), value_angle.sin());
+        Line {
+            start: self.center() + 0.3 * body * direction,
+            end: self.center() + 0.9 * body * direction,
+            width: 0.5 * self.ring_width,
+            color: self.indicator_color.unwrap_or(theme.text_color),
+            antialiased: true,
+            ..Default::default()
+        }
+        .draw(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.bounds = Dimensions::new(self.diameter, self.diameter)
+            .min(max_size)
+            .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for Knob<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Clamp a value to a range whose ends can be in either order.
+fn clamp_to_range(value: f32, min: f32, max: f32) -> f32 {
+    value.clamp(min.min(max), min.max(max))
+}
+
+/// The fraction of the knob's sweep at an angle, measured clockwise from the
+/// right in screen space. Angles in the gap at the bottom of the knob snap
+/// to the closest end.
+fn normalized_at_angle(angle: f32) -> f32 {
+    let from_start = (angle - START_ANGLE).rem_euclid(2.0 * PI);
+    if from_start <= SWEEP {
+        from_start / SWEEP
+    } else if from_start - SWEEP < 0.5 * (2.0 * PI - SWEEP) {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalized_at_angle() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+
+        // straight up is half way
+        assert!(close(normalized_at_angle(-0.5 * PI), 0.5));
+        assert!(close(normalized_at_angle(START_ANGLE), 0.0));
+        assert!(close(normalized_at_angle(0.25 * PI), 1.0));
+
+        // the gap at the bottom snaps to the nearest end
+        assert!(close(normalized_at_angle(0.4 * PI), 1.0));
+        assert!(close(normalized_at_angle(0.6 * PI), 0.0));
+    }
+}
@@ -5,14 +5,78 @@ use crate::{
     ui::{
         primitives::{Dimensions, Rect, Tile},
         widgets::{Element, Widget},
-        Font, Input, InternalState,
+        Font, Id, Input, InternalState, TextBlock, Theme,
     },
-    vec2, Vec2,
+    vec2, Vec2, Vec4,
 };
 
+/// The sizes of text tried by a fitted label before it settles on one.
+const FIT_ITERATIONS: u32 = 16;
+
+/// The largest size a fitted label will scale its text to.
+const MAX_FIT_SIZE: f32 = 4096.0;
+
+#[derive(Clone)]
 pub struct Label {
     glyph_tiles: Vec<Tile>,
     bounds: Rect,
+
+    /// The font's text color. None draws the text with the theme's text
+    /// color.
+    text_color: Option<Vec4>,
+
+    /// Only selectable labels keep their text, so plain labels stay small.
+    selectable: Option<Box<Selectable>>,
+
+    /// Fitted labels keep their text too, so it can be rebuilt at the size
+    /// which fits each layout.
+    fitted: Option<Box<Fitted>>,
+}
+
+/// How a fitted label scales its text to the space its parent gives it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LabelFit {
+    /// Scale the text up or down until it fills the space, e.g. for
+    /// responsive headings.
+    Fill,
+
+    /// Keep the font's size unless the text would overflow the space, then
+    /// scale it down until it fits.
+    Shrink,
+}
+
+/// Everything a fitted label needs to rebuild its text at a new size.
+#[derive(Clone)]
+struct Fitted {
+    font: Font,
+    text: String,
+    fit: LabelFit,
+}
+
+/// Everything a selectable label needs to map the mouse to its text.
+#[derive(Clone)]
+struct Selectable {
+    id: Id,
+    font: Font,
+    text: String,
+    selection_color: Option<Vec4>,
+}
+
+/// A selectable label's selection is stored in the UI InternalState so it
+/// persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct LabelSelectionState {
+    /// True after the label is clicked, until anything else is clicked.
+    focused: bool,
+
+    /// True while the mouse is held down to select text.
+    dragging: bool,
+
+    /// The end of the selection which doesn't move, as a character index.
+    anchor: usize,
+
+    /// The end of the selection which follows the mouse.
+    cursor: usize,
 }
 
 impl Label {
@@ -25,28 +89,320 @@ impl Label {
         Self {
             glyph_tiles,
             bounds,
+            text_color: font.own_text_color(),
+            selectable: None,
+            fitted: None,
+        }
+    }
+
+    /// Create a label whose text is `size` pixels tall rather than the
+    /// font's own line height, so headings and captions can share one font.
+    /// See [`Font::build_sized_text_tiles`] for when the font needs to
+    /// rasterize another atlas.
+    pub fn sized<T>(font: &Font, content: T, size: f32) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let (glyph_tiles, bounds) = font.build_sized_text_tiles(content, size);
+        Self {
+            glyph_tiles,
+            bounds,
+            text_color: font.own_text_color(),
+            selectable: None,
+            fitted: None,
+        }
+    }
+
+    /// Create a label which picks its text size each layout so the text
+    /// fits the space its parent gives it, see [`LabelFit`]. Sizes come
+    /// from [`Font::build_sized_text_tiles`].
+    ///
+    /// Inside a parent which doesn't limit the label's size, like a scroll
+    /// view, the font's own size is used.
+    pub fn fitted<T>(font: &Font, content: T, fit: LabelFit) -> Self
+    where
+        T: Into<String>,
+    {
+        let text = content.into();
+        Self {
+            fitted: Some(Box::new(Fitted {
+                font: font.clone(),
+                text: text.clone(),
+                fit,
+            })),
+            ..Self::new(font, text)
+        }
+    }
+
+    /// Create a label which draws a pre-rendered text block as a single
+    /// tile. The label is laid out exactly like a label with the same text.
+    pub fn from_text_block(font: &Font, text_block: &TextBlock) -> Self {
+        let (glyph_tiles, bounds) = font.build_text_block_tiles(text_block);
+        Self {
+            glyph_tiles,
+            bounds,
+            text_color: font.own_text_color(),
+            selectable: None,
+            fitted: None,
+        }
+    }
+
+    /// Create a label whose text can be selected with the mouse and copied
+    /// to the UI's clipboard with Ctrl+C. Ctrl+A selects all of the text
+    /// after the label is clicked. The id is used to store the selection.
+    pub fn selectable<T>(id: Id, font: &Font, content: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let text = content.into();
+        Self {
+            selectable: Some(Box::new(Selectable {
+                id,
+                font: font.clone(),
+                selection_color: None,
+                text: text.clone(),
+            })),
+            ..Self::new(font, text)
+        }
+    }
+
+    /// The color behind selected text. Only selectable labels have a
+    /// selection. Defaults to the theme's selection color.
+    pub fn selection_color(mut self, selection_color: Vec4) -> Self {
+        if let Some(selectable) = self.selectable.as_deref_mut() {
+            selectable.selection_color = Some(selection_color);
+        }
+        self
+    }
+}
+
+impl Fitted {
+    /// The text size which best fits the space.
+    fn size(&self, max_size: &Dimensions) -> f32 {
+        let font_size = self.font.line_height();
+        let fits = |size: f32| {
+            let bounds = self.font.sized_text_bounds(&self.text, size);
+            bounds.width() <= max_size.width
+                && bounds.height() <= max_size.height
+        };
+        let largest = largest_fitting_size(
+            font_size.max(max_size.height.min(MAX_FIT_SIZE)),
+            fits,
+        );
+        match self.fit {
+            LabelFit::Fill => largest,
+            LabelFit::Shrink => largest.min(font_size),
+        }
+    }
+}
+
+/// Binary search for the largest size up to `max` which fits. The size
+/// shrinks towards zero when nothing fits.
+fn largest_fitting_size(max: f32, fits: impl Fn(f32) -> bool) -> f32 {
+    if fits(max) {
+        return max;
+    }
+    let (mut low, mut high) = (0.0, max);
+    for _ in 0..FIT_ITERATIONS {
+        let size = 0.5 * (low + high);
+        if fits(size) {
+            low = size;
+        } else {
+            high = size;
         }
     }
+    low
+}
+
+impl Selectable {
+    /// The character index closest to a point in screen space.
+    fn position_at(&self, bounds: &Rect, point: Vec2) -> usize {
+        let lines: Vec<&str> = self.text.split('\n').collect();
+        let line_index = ((point.y - bounds.top()) / self.font.line_spacing())
+            .floor()
+            .clamp(0.0, (lines.len() - 1) as f32)
+            as usize;
+        let line_start: usize = lines[..line_index]
+            .iter()
+            .map(|line| line.chars().count() + 1)
+            .sum();
+
+        let line: Vec<char> = lines[line_index].chars().collect();
+        let x = point.x - bounds.left();
+        let column = (0..=line.len())
+            .map(|column| (column, (self.width(&line[..column]) - x).abs()))
+            .fold((0, f32::MAX), |closest, candidate| {
+                if candidate.1 < closest.1 {
+                    candidate
+                } else {
+                    closest
+                }
+            })
+            .0;
+        line_start + column
+    }
+
+    fn width(&self, chars: &[char]) -> f32 {
+        self.font.text_width(chars.iter().collect::<String>())
+    }
+
+    fn handle_event(
+        &self,
+        state: &mut LabelSelectionState,
+        internal_state: &mut InternalState,
+        bounds: &Rect,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) {
+        use glfw::{Action, Key, Modifiers, MouseButton, WindowEvent};
+
+        match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                modifiers,
+            ) => {
+                let was_focused = state.focused;
+                state.focused = bounds.contains(input.mouse_position);
+                state.dragging = state.focused;
+                if state.focused {
+                    state.cursor =
+                        self.position_at(bounds, input.mouse_position);
+                    if !(was_focused && modifiers.contains(Modifiers::Shift)) {
+                        state.anchor = state.cursor;
+                    }
+                } else {
+                    state.anchor = state.cursor;
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = false;
+            }
+            WindowEvent::CursorPos(..) if state.dragging => {
+                state.cursor = self.position_at(bounds, input.mouse_position);
+            }
+            WindowEvent::Key(key, _, Action::Press, modifiers)
+                if state.focused
+                    && modifiers
+                        .intersects(Modifiers::Control | Modifiers::Super) =>
+            {
+                let start = state.anchor.min(state.cursor);
+                let end = state.anchor.max(state.cursor);
+                match key {
+                    Key::A => {
+                        state.anchor = 0;
+                        state.cursor = self.text.chars().count();
+                    }
+                    Key::C if start != end => {
+                        let selected: String = self
+                            .text
+                            .chars()
+                            .skip(start)
+                            .take(end - start)
+                            .collect();
+                        internal_state.clipboard().set_text(&selected);
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Highlight the selected part of each line.
+    fn draw_selection(
+        &self,
+        state: &LabelSelectionState,
+        bounds: &Rect,
+        theme: &Theme,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let start = state.anchor.min(state.cursor);
+        let end = state.anchor.max(state.cursor);
+        if start == end {
+            return Ok(());
+        }
+
+        let mut line_start = 0;
+        for (line_index, line) in self.text.split('\n').enumerate() {
+            let line: Vec<char> = line.chars().collect();
+            let line_end = line_start + line.len();
+            if start <= line_end && end > line_start {
+                let from = start.max(line_start) - line_start;
+                let to = end.min(line_end) - line_start;
+                let top =
+                    bounds.top() + line_index as f32 * self.font.line_spacing();
+                Tile {
+                    model: Rect::new(
+                        top,
+                        bounds.left() + self.width(&line[..from]),
+                        top + self.font.line_height(),
+                        bounds.left() + self.width(&line[..to]),
+                    ),
+                    color: self
+                        .selection_color
+                        .unwrap_or(theme.selection_color),
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+            line_start = line_end + 1;
+        }
+        Ok(())
+    }
 }
 
 impl<Message> Widget<Message> for Label {
-    /// Labels do not react to events.
+    /// Labels only react to events when their text is selectable, and never
+    /// emit messages.
     fn handle_event(
         &mut self,
-        _internal_state: &mut InternalState,
-        _input: &Input,
-        _event: &glfw::WindowEvent,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
     ) -> Result<Option<Message>> {
+        if let Some(selectable) = &self.selectable {
+            let mut state = *internal_state
+                .get_state::<LabelSelectionState>(&selectable.id);
+            selectable.handle_event(
+                &mut state,
+                internal_state,
+                &self.bounds,
+                input,
+                event,
+            );
+            *internal_state.get_state_mut(&selectable.id) = state;
+        }
         Ok(None)
     }
 
     fn draw_frame(
         &self,
-        _internal_state: &mut InternalState,
+        internal_state: &mut InternalState,
         frame: &mut Frame,
     ) -> Result<()> {
+        if let Some(selectable) = &self.selectable {
+            let state = *internal_state
+                .get_state::<LabelSelectionState>(&selectable.id);
+            selectable.draw_selection(
+                &state,
+                &self.bounds,
+                internal_state.theme(),
+                frame,
+            )?;
+        }
+        let text_color =
+            self.text_color.unwrap_or(internal_state.theme().text_color);
         for tile in &self.glyph_tiles {
-            tile.fill(frame)?;
+            Tile {
+                color: text_color,
+                ..*tile
+            }
+            .fill(frame)?;
         }
         Ok(())
     }
@@ -56,7 +412,14 @@ impl<Message> Widget<Message> for Label {
         _internal_state: &mut InternalState,
         max_size: &Dimensions,
     ) -> Dimensions {
-        if self.glyph_tiles.len() == 0 {
+        if let Some(fitted) = &self.fitted {
+            let size = fitted.size(max_size);
+            let (glyph_tiles, bounds) =
+                fitted.font.build_sized_text_tiles(&fitted.text, size);
+            self.glyph_tiles = glyph_tiles;
+            self.bounds = bounds;
+        }
+        if self.glyph_tiles.is_empty() {
             (0, 0).into()
         } else {
             self.bounds.dimensions().min(max_size)
@@ -68,7 +431,7 @@ impl<Message> Widget<Message> for Label {
         _internal_state: &mut InternalState,
         position: Vec2,
     ) {
-        if self.glyph_tiles.len() == 0 {
+        if self.glyph_tiles.is_empty() {
             return;
         }
 
@@ -79,6 +442,7 @@ impl<Message> Widget<Message> for Label {
         for tile in &mut self.glyph_tiles {
             tile.model = tile.model.translate(offset);
         }
+        self.bounds = self.bounds.translate(offset);
     }
 }
 
@@ -90,3 +454,21 @@ where
         Element::new(self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_largest_fitting_size() {
+        // text which is twice as wide as it is tall, in a 100x30 box
+        let fits = |size: f32| 2.0 * size <= 100.0 && size <= 30.0;
+        assert_eq!(largest_fitting_size(30.0, fits), 30.0);
+
+        let fits = |size: f32| 4.0 * size <= 100.0 && size <= 30.0;
+        let size = largest_fitting_size(30.0, fits);
+        assert!(size <= 25.0 && size > 24.99, "{}", size);
+
+        assert_eq!(largest_fitting_size(30.0, |_| false), 0.0);
+    }
+}
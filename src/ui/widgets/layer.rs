@@ -0,0 +1,185 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::Dimensions,
+        widgets::{Element, OverlayEvent, Widget},
+        Input, InternalState,
+    },
+    Vec2,
+};
+
+/// A Layer places each child at a fixed position relative to the layer's top
+/// left corner, rather than arranging them in a row or column. Children can
+/// overlap. Children with a higher z index are drawn on top and see events
+/// first, children with the same z index are drawn in the order they were
+/// added.
+///
+/// Ordering is done by sorting, not with the depth buffer, so translucent
+/// children still blend with whatever is below them.
+///
+/// The layer fills all of the available space. This makes it useful as the
+/// root of a UI with floating panels, like draggable windows, which shouldn't
+/// be part of the normal layout flow.
+pub struct Layer<Message> {
+    /// Every child with its position, sorted by z index.
+    children: Vec<(Element<Message>, Vec2)>,
+
+    /// The z index for each child.
+    z_indices: Vec<i32>,
+}
+
+impl<Message> Default for Layer<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message> Layer<Message> {
+    pub fn new() -> Self {
+        Self {
+            children: vec![],
+            z_indices: vec![],
+        }
+    }
+
+    /// Add a child element on top of the existing children with a z index
+    /// of 0.
+    pub fn child<W>(self, child: W, position: Vec2) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.child_with_z(child, position, 0)
+    }
+
+    /// Add a child element on top of every existing child with the same or
+    /// a lower z index, and below every child with a higher one.
+    pub fn child_with_z<W>(mut self, child: W, position: Vec2, z: i32) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        let index = self.z_indices.partition_point(|&other| other <= z);
+        self.z_indices.insert(index, z);
+        self.children.insert(index, (child.into(), position));
+        self
+    }
+}
+
+impl<Message> Widget<Message> for Layer<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        // later children are drawn on top, so they see events first
+        for (child, _) in self.children.iter_mut().rev() {
+            if let Some(message) =
+                child.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for (child, _) in &self.children {
+            child.draw_frame(internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        for (child, position) in &mut self.children {
+            // children are limited to the space between their position and
+            // the layer's bottom right corner
+            let remaining_size = Dimensions::new(
+                (max_size.width - position.x).max(0.0),
+                (max_size.height - position.y).max(0.0),
+            );
+            child.dimensions(internal_state, &remaining_size);
+        }
+        *max_size
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        for (child, child_pos) in &mut self.children {
+            child.set_top_left_position(internal_state, position + *child_pos);
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        for (child, _) in self.children.iter_mut().rev() {
+            let result =
+                child.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for (child, _) in &self.children {
+            child.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Layer<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::vec2;
+
+    #[test]
+    fn test_children_are_sorted_by_z_index() {
+        let layer = Layer::<()>::new()
+            .child_with_z(Layer::new(), vec2(0.0, 0.0), 1)
+            .child(Layer::new(), vec2(1.0, 0.0))
+            .child_with_z(Layer::new(), vec2(2.0, 0.0), -1)
+            .child_with_z(Layer::new(), vec2(3.0, 0.0), 1)
+            .child(Layer::new(), vec2(4.0, 0.0));
+
+        let order: Vec<f32> = layer
+            .children
+            .iter()
+            .map(|(_, position)| position.x)
+            .collect();
+        assert_eq!(order, vec![2.0, 1.0, 4.0, 0.0, 3.0]);
+        assert_eq!(layer.z_indices, vec![-1, 0, 0, 1, 1]);
+    }
+}
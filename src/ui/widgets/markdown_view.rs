@@ -0,0 +1,543 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    markdown::{self, Block, ListMarker, Span},
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Widget},
+        Font, Input, InternalState, Theme,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A MarkdownView renders a markdown document, wrapping text to the width its
+/// parent allows.
+///
+/// Headings, paragraphs, lists, code blocks, and bold, italic, and code spans
+/// are supported, see [`markdown::parse`]. Every style can be given its own
+/// font. Without one, bold text is drawn twice with a one pixel offset,
+/// italic text is drawn in the emphasis color, and headings are drawn in
+/// bold with the heading color.
+pub struct MarkdownView {
+    /// The parsed document.
+    blocks: Vec<Block>,
+
+    /// The font used for paragraphs and lists.
+    font: Font,
+
+    /// The font used for headings.
+    heading_font: Option<Font>,
+
+    /// The font used for bold text.
+    bold_font: Option<Font>,
+
+    /// The font used for italic text.
+    italic_font: Option<Font>,
+
+    /// The font used for code spans and code blocks.
+    code_font: Option<Font>,
+
+    /// The color of headings. None uses the theme's text color.
+    heading_color: Option<Vec4>,
+
+    /// The color of italic text when there is no italic font.
+    emphasis_color: Vec4,
+
+    /// The color of code spans and code blocks.
+    code_color: Vec4,
+
+    /// The color behind code spans and code blocks.
+    code_background: Vec4,
+
+    /// How far each level of a list is indented, in pixels.
+    indent: f32,
+
+    /// The words of every block, built the first time the view is laid out.
+    words: Option<Vec<BlockWords>>,
+
+    /// Every tile in the document, positioned by the last layout.
+    tiles: Vec<Tile>,
+
+    /// The space occupied by the document.
+    bounds: Rect,
+}
+
+/// A piece of text which is never split across lines.
+struct Word {
+    /// The word's glyphs.
+    tiles: Vec<Tile>,
+
+    /// The bounds of the glyphs, based on font metrics.
+    bounds: Rect,
+
+    /// True when there was white space between this word and the last.
+    space_before: bool,
+
+    /// True when this word always starts a new line.
+    break_before: bool,
+
+    /// The color of the rect drawn behind the word.
+    background: Option<Vec4>,
+}
+
+/// The words for a single block, ready to be wrapped.
+struct BlockWords {
+    /// The words, in order.
+    words: Vec<Word>,
+
+    /// The list marker, drawn to the left of the first line.
+    marker: Option<Word>,
+
+    /// The left side of every line.
+    indent: f32,
+
+    /// The distance between lines.
+    line_height: f32,
+
+    /// The width of a space between words.
+    space_width: f32,
+
+    /// The color behind the whole block, used for code blocks.
+    background: Option<Vec4>,
+}
+
+impl MarkdownView {
+    /// Parse the markdown text into a view which uses the font for all text.
+    pub fn new<T>(font: &Font, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            blocks: markdown::parse(text.as_ref()),
+            font: font.clone(),
+            heading_font: None,
+            bold_font: None,
+            italic_font: None,
+            code_font: None,
+            heading_color: None,
+            emphasis_color: vec4(0.75, 0.85, 1.0, 1.0),
+            code_color: vec4(0.9, 0.8, 0.6, 1.0),
+            code_background: vec4(0.0, 0.0, 0.0, 0.3),
+            indent: 2.0 * font.line_height(),
+            words: None,
+            tiles: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    builder_field_some!(heading_color, Vec4);
+    builder_field!(emphasis_color, Vec4);
+    builder_field!(code_color, Vec4);
+    builder_field!(code_background, Vec4);
+    builder_field!(indent, f32);
+
+    /// Use a separate font for headings, usually a larger one.
+    pub fn heading_font(self, font: &Font) -> Self {
+        Self {
+            heading_font: Some(font.clone()),
+            ..self
+        }
+    }
+
+    /// Use a separate font for bold text.
+    pub fn bold_font(self, font: &Font) -> Self {
+        Self {
+            bold_font: Some(font.clone()),
+            ..self
+        }
+    }
+
+    /// Use a separate font for italic text.
+    pub fn italic_font(self, font: &Font) -> Self {
+        Self {
+            italic_font: Some(font.clone()),
+            ..self
+        }
+    }
+
+    /// Use a separate font for code, usually a monospace one.
+    pub fn code_font(self, font: &Font) -> Self {
+        Self {
+            code_font: Some(font.clone()),
+            ..self
+        }
+    }
+
+    /// Build the words for every block in the document.
+    fn build_words(&self, theme: &Theme) -> Vec<BlockWords> {
+        let heading_color = self.heading_color.unwrap_or(theme.text_color);
+        let code_font = self.code_font.as_ref().unwrap_or(&self.font);
+        self.blocks
+            .iter()
+            .map(|block| match block {
+                Block::Heading { spans, .. } => {
+                    let font = self.heading_font.as_ref().unwrap_or(&self.font);
+                    let bold = self.heading_font.is_none();
+                    BlockWords {
+                        words: self.build_span_words(
+                            font,
+                            spans,
+                            theme,
+                            |word| {
+                                word.recolor(heading_color);
+                                if bold {
+                                    word.embolden();
+                                }
+                            },
+                        ),
+                        ..BlockWords::new(font, 0.0)
+                    }
+                }
+                Block::Paragraph(spans) => BlockWords {
+                    words: self.build_span_words(
+                        &self.font,
+                        spans,
+                        theme,
+                        |_| (),
+                    ),
+                    ..BlockWords::new(&self.font, 0.0)
+                },
+                Block::ListItem {
+                    depth,
+                    marker,
+                    spans,
+                } => {
+                    let marker = match marker {
+                        ListMarker::Bullet => {
+                            let bullet =
+                                Word::new(&self.font, "\u{2022}", theme);
+                            if bullet.tiles.is_empty() {
+                                Word::new(&self.font, "-", theme)
+                            } else {
+                                bullet
+                            }
+                        }
+                        ListMarker::Number(n) => {
+                            Word::new(&self.font, format!("{}.", n), theme)
+                        }
+                    };
+                    BlockWords {
+                        words: self.build_span_words(
+                            &self.font,
+                            spans,
+                            theme,
+                            |_| (),
+                        ),
+                        marker: Some(marker),
+                        ..BlockWords::new(
+                            &self.font,
+                            (*depth + 1) as f32 * self.indent,
+                        )
+                    }
+                }
+                Block::CodeBlock(code) => {
+                    let padding = code_font.line_height() * 0.25;
+                    let block = BlockWords::new(code_font, padding);
+                    let words = code
+                        .lines()
+                        .map(|line| {
+                            let mut word = Word::new(code_font, line, theme);
+                            word.recolor(self.code_color);
+                            word.break_before = true;
+
+                            // white space has no glyphs, so leading
+                            // indentation is added to the bounds
+                            let indentation: usize = line
+                                .chars()
+                                .take_while(|c| c.is_whitespace())
+                                .map(|c| if c == '\t' { 4 } else { 1 })
+                                .sum();
+                            let b = word.bounds;
+                            word.bounds = Rect::new(
+                                b.top(),
+                                b.left()
+                                    - indentation as f32 * block.space_width,
+                                b.bottom(),
+                                b.right(),
+                            );
+                            word
+                        })
+                        .collect();
+                    BlockWords {
+                        words,
+                        background: Some(self.code_background),
+                        ..block
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Build the words for a run of spans. Plain text uses the base font.
+    /// Each word is styled, then passed to `style_word` so blocks can apply
+    /// their own style.
+    fn build_span_words<F>(
+        &self,
+        base_font: &Font,
+        spans: &[Span],
+        theme: &Theme,
+        style_word: F,
+    ) -> Vec<Word>
+    where
+        F: Fn(&mut Word),
+    {
+        let mut words = vec![];
+        let mut space_before = false;
+        for span in spans {
+            let style = span.style;
+            let font = if style.code {
+                self.code_font.as_ref()
+            } else if style.bold {
+                self.bold_font.as_ref()
+            } else if style.italic {
+                self.italic_font.as_ref()
+            } else {
+                None
+            };
+            let has_font = font.is_some();
+            let font = font.unwrap_or(base_font);
+
+            let mut push_word = |text: &str, space_before: bool| {
+                let mut word = Word::new(font, text, theme);
+                word.space_before = space_before;
+                if style.code {
+                    word.recolor(self.code_color);
+                    word.background = Some(self.code_background);
+                } else {
+                    // bold italic text only has the bold font
+                    if style.italic && (style.bold || !has_font) {
+                        word.recolor(self.emphasis_color);
+                    }
+                    if style.bold && !has_font {
+                        word.embolden();
+                    }
+                }
+                style_word(&mut word);
+                words.push(word);
+            };
+
+            if style.code {
+                // code spans are never wrapped
+                push_word(span.text.trim(), space_before);
+                space_before = span.text.ends_with(char::is_whitespace);
+                continue;
+            }
+            for (i, text) in span.text.split(char::is_whitespace).enumerate() {
+                space_before |= i > 0;
+                if !text.is_empty() {
+                    push_word(text, space_before);
+                    space_before = false;
+                }
+            }
+        }
+        words
+    }
+
+    /// Wrap every block to the width and position the tiles, starting at the
+    /// origin.
+    fn layout(&mut self, max_width: f32, theme: &Theme) {
+        let words = match self.words.take() {
+            Some(words) => words,
+            None => self.build_words(theme),
+        };
+        let bounded = max_width < f32::MAX;
+        let block_spacing = self.font.line_height() * 0.5;
+
+        let mut tiles = vec![];
+        let mut width: f32 = 0.0;
+        let mut top = 0.0;
+        for (i, block) in words.iter().enumerate() {
+            if i > 0 {
+                top += block_spacing;
+            }
+            let background_index = tiles.len();
+            let padding = if block.background.is_some() {
+                block.indent
+            } else {
+                0.0
+            };
+
+            let line_top = top + padding;
+            if let Some(marker) = &block.marker {
+                let right = block.indent - block.space_width;
+                marker.place(
+                    vec2(right - marker.bounds.width(), line_top),
+                    &mut tiles,
+                );
+            }
+
+            let mut lines = 1;
+            let mut x = block.indent;
+            let mut right = block.indent;
+            for (j, word) in block.words.iter().enumerate() {
+                let mut gap = if word.space_before && x > block.indent {
+                    block.space_width
+                } else {
+                    0.0
+                };
+                let overflows = x > block.indent
+                    && x + gap + word.bounds.width() > max_width;
+                if (word.break_before && j > 0) || overflows {
+                    lines += 1;
+                    x = block.indent;
+                    gap = 0.0;
+                }
+                let line_top =
+                    top + padding + (lines - 1) as f32 * block.line_height;
+                word.place(vec2(x + gap, line_top), &mut tiles);
+                x += gap + word.bounds.width();
+                right = right.max(x);
+            }
+
+            let bottom = top + padding * 2.0 + lines as f32 * block.line_height;
+            if let Some(color) = block.background {
+                let block_right = if bounded {
+                    max_width.max(right + padding)
+                } else {
+                    right + padding
+                };
+                tiles.insert(
+                    background_index,
+                    Tile {
+                        model: Rect::new(top, 0.0, bottom, block_right),
+                        color,
+                        ..Default::default()
+                    },
+                );
+                right = block_right;
+            }
+            width = width.max(right);
+            top = bottom;
+        }
+
+        self.tiles = tiles;
+        self.bounds = Rect::new(0.0, 0.0, top, width.min(max_width));
+        self.words = Some(words);
+    }
+}
+
+impl BlockWords {
+    /// An empty block which uses the font's metrics.
+    fn new(font: &Font, indent: f32) -> Self {
+        let (_, spaced) = font.build_text_tiles("x x");
+        let (_, unspaced) = font.build_text_tiles("xx");
+        Self {
+            words: vec![],
+            marker: None,
+            indent,
+            line_height: font.line_height(),
+            space_width: spaced.width() - unspaced.width(),
+            background: None,
+        }
+    }
+}
+
+impl Word {
+    /// A word in the font's text color, or the theme's when the font
+    /// doesn't have one.
+    fn new(font: &Font, text: impl AsRef<str>, theme: &Theme) -> Self {
+        let (tiles, bounds) = font.build_text_tiles(text);
+        let mut word = Self {
+            tiles,
+            bounds,
+            space_before: false,
+            break_before: false,
+            background: None,
+        };
+        word.recolor(font.text_color_or(theme));
+        word
+    }
+
+    fn recolor(&mut self, color: Vec4) {
+        for tile in &mut self.tiles {
+            tile.color = color;
+        }
+    }
+
+    /// Thicken the glyphs by drawing them again one pixel to the right.
+    fn embolden(&mut self) {
+        let offset = vec2(1.0, 0.0);
+        let shifted: Vec<Tile> = self
+            .tiles
+            .iter()
+            .map(|tile| Tile {
+                model: tile.model.translate(offset),
+                ..*tile
+            })
+            .collect();
+        self.tiles.extend(shifted);
+    }
+
+    /// Add the word's tiles with its top left at the position.
+    fn place(&self, position: Vec2, tiles: &mut Vec<Tile>) {
+        let raw_offset = position - self.bounds.top_left;
+        let offset = vec2(raw_offset.x.round(), raw_offset.y.round());
+        if let Some(color) = self.background {
+            tiles.push(Tile {
+                model: self.bounds.translate(offset),
+                color,
+                ..Default::default()
+            });
+        }
+        tiles.extend(self.tiles.iter().map(|tile| Tile {
+            model: tile.model.translate(offset),
+            ..*tile
+        }));
+    }
+}
+
+impl<Message> Widget<Message> for MarkdownView {
+    /// Markdown views do not react to events.
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        _internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for tile in &self.tiles {
+            tile.fill(frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.layout(max_size.width, internal_state.theme());
+        self.bounds.dimensions().min(max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let raw_offset = position - self.bounds.top_left;
+        let offset = vec2(raw_offset.x.round(), raw_offset.y.round());
+        for tile in &mut self.tiles {
+            tile.model = tile.model.translate(offset);
+        }
+        self.bounds = self.bounds.translate(offset);
+    }
+}
+
+impl<Message> Into<Element<Message>> for MarkdownView
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
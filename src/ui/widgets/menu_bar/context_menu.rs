@@ -0,0 +1,281 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect},
+        widgets::{Element, OverlayEvent, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    Vec2, Vec4,
+};
+
+use super::{
+    menu::Menu,
+    popup::{self, MenuStyle, PopupEvent, PopupState},
+};
+
+/// A ContextMenu's state is stored in the UI InternalState so the menu stays
+/// open between views.
+#[derive(Debug, Clone, Default)]
+struct ContextMenuState {
+    /// True while the menu is open.
+    open: bool,
+
+    /// The menu's top left corner, where the cursor was when it opened.
+    position: Vec2,
+
+    /// The menu's open submenus and hovered entries.
+    popup: PopupState,
+}
+
+/// A ContextMenu wraps another widget and opens a floating menu at the
+/// cursor when the widget is right-clicked. The menu is drawn in the overlay
+/// so it's always on top of the rest of the UI.
+///
+/// # Controls
+///
+/// - Right-click the widget to open the menu. Right-clicking again moves it.
+/// - Hover a submenu entry to open it.
+/// - Click an item to emit its message and close the menu.
+/// - Press Escape, or click outside of the menu, to close it.
+pub struct ContextMenu<Message, W> {
+    /// The ID uniquely identifies this context menu when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The widget which opens the menu when right-clicked.
+    child: W,
+
+    /// The menu opened at the cursor. Its title isn't shown.
+    menu: Menu<Message>,
+
+    /// The space occupied by the child on screen.
+    bounds: Rect,
+
+    /// The font's line height, used to size each row.
+    line_height: f32,
+
+    /// The space between text and the edges of each row. None uses the
+    /// theme's padding.
+    padding: Option<f32>,
+
+    /// The color behind hovered entries.
+    hover_color: Option<Vec4>,
+
+    /// The color behind the open menu.
+    panel_color: Option<Vec4>,
+
+    /// The color of menu outlines, separators, and submenu arrows.
+    accent_color: Option<Vec4>,
+}
+
+impl<Message, W> ContextMenu<Message, W> {
+    /// Create a new context menu for the given widget.
+    pub fn new(id: Id, child: W, font: &Font, menu: Menu<Message>) -> Self {
+        let line_height = font.line_height();
+        Self {
+            id,
+            child,
+            menu,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            line_height,
+            padding: None,
+            hover_color: None,
+            panel_color: None,
+            accent_color: None,
+        }
+    }
+
+    builder_field_some!(padding, f32);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(panel_color, Vec4);
+    builder_field_some!(accent_color, Vec4);
+
+    fn style(&self, theme: &Theme) -> MenuStyle {
+        let padding = self.padding.unwrap_or(theme.padding * self.line_height);
+        MenuStyle {
+            padding,
+            row_height: self.line_height + 2.0 * padding,
+            arrow_space: self.line_height,
+            hover_color: self.hover_color.unwrap_or(theme.hover_color),
+            panel_color: self.panel_color.unwrap_or(theme.panel_color),
+            accent_color: self.accent_color.unwrap_or(theme.detail_color),
+        }
+    }
+}
+
+impl<Message, W> Widget<Message> for ContextMenu<Message, W>
+where
+    W: Widget<Message>,
+{
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button2,
+                Action::Press,
+                _,
+            ) if self.bounds.contains(input.mouse_position) => {
+                *internal_state.get_state_mut::<ContextMenuState>(&self.id) =
+                    ContextMenuState {
+                        open: true,
+                        position: input.mouse_position,
+                        popup: PopupState::default(),
+                    };
+            }
+            _ => (),
+        }
+        self.child.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let style = self.style(internal_state.theme());
+        popup::measure(&mut self.menu, internal_state, style);
+        let dimensions = self.child.dimensions(internal_state, max_size);
+        self.bounds = dimensions.as_rect();
+        dimensions
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.child.set_top_left_position(internal_state, position);
+
+        let state = internal_state
+            .get_state::<ContextMenuState>(&self.id)
+            .clone();
+        if state.open {
+            let style = self.style(internal_state.theme());
+            popup::layout(
+                &mut self.menu,
+                internal_state,
+                style,
+                state.position,
+                &state.popup.submenus,
+            );
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        let mut state = internal_state
+            .get_state::<ContextMenuState>(&self.id)
+            .clone();
+        if !state.open {
+            return self.child.handle_overlay_event(
+                internal_state,
+                input,
+                event,
+            );
+        }
+
+        let result = match popup::handle_event(
+            &self.menu,
+            &mut state.popup,
+            input,
+            event,
+        ) {
+            PopupEvent::Ignored => OverlayEvent::Ignored,
+            PopupEvent::Captured => OverlayEvent::Captured(None),
+            PopupEvent::Escaped => {
+                state = ContextMenuState::default();
+                OverlayEvent::Captured(None)
+            }
+            PopupEvent::PressedOutside => {
+                // the click still reaches the rest of the UI, so a
+                // right-click on the child reopens the menu at the cursor
+                state = ContextMenuState::default();
+                OverlayEvent::Ignored
+            }
+            PopupEvent::Selected(message) => {
+                state = ContextMenuState::default();
+                OverlayEvent::Captured(Some(message))
+            }
+        };
+        *internal_state.get_state_mut::<ContextMenuState>(&self.id) = state;
+
+        match result {
+            OverlayEvent::Ignored => {
+                self.child
+                    .handle_overlay_event(internal_state, input, event)
+            }
+            captured => Ok(captured),
+        }
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)?;
+
+        let state = internal_state
+            .get_state::<ContextMenuState>(&self.id)
+            .clone();
+        if !state.open {
+            return Ok(());
+        }
+        let style = self.style(internal_state.theme());
+        popup::draw(&self.menu, &state.popup, internal_state, frame, style)
+    }
+}
+
+impl<Message, W> Into<Element<Message>> for ContextMenu<Message, W>
+where
+    Message: 'static,
+    W: 'static + Widget<Message>,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Define an extra associated method which wraps any widget with a context
+/// menu.
+pub trait WithContextMenu<Message, W: Widget<Message>> {
+    fn context_menu(
+        self,
+        id: Id,
+        font: &Font,
+        menu: Menu<Message>,
+    ) -> ContextMenu<Message, W>;
+}
+
+impl<Message, W: Widget<Message>> WithContextMenu<Message, W> for W {
+    fn context_menu(
+        self,
+        id: Id,
+        font: &Font,
+        menu: Menu<Message>,
+    ) -> ContextMenu<Message, W> {
+        ContextMenu::new(id, self, font, menu)
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{
+    ui::{primitives::Rect, widgets::Label, Font},
+    Vec2,
+};
+
+/// A single entry in a [`Menu`].
+pub(super) enum MenuEntry<Message> {
+    /// An item which emits a message when clicked.
+    Item {
+        /// The item's text.
+        label: Label,
+
+        /// The function used to build the item's message.
+        on_click: Box<dyn Fn() -> Message>,
+
+        /// The space occupied by the item on screen.
+        bounds: Rect,
+    },
+
+    /// An item which opens a nested menu when hovered.
+    Submenu {
+        /// The nested menu, its title is used as the entry's text.
+        menu: Menu<Message>,
+
+        /// The space occupied by the entry on screen.
+        bounds: Rect,
+    },
+
+    /// A horizontal line between groups of entries.
+    Separator {
+        /// The space occupied by the separator on screen.
+        bounds: Rect,
+    },
+}
+
+impl<Message> MenuEntry<Message> {
+    /// The space occupied by the entry on screen.
+    pub(super) fn bounds(&self) -> Rect {
+        match self {
+            MenuEntry::Item { bounds, .. }
+            | MenuEntry::Submenu { bounds, .. }
+            | MenuEntry::Separator { bounds } => *bounds,
+        }
+    }
+
+    /// The entry's text, if it has any.
+    pub(super) fn label(&self) -> Option<&Label> {
+        match self {
+            MenuEntry::Item { label, .. } => Some(label),
+            MenuEntry::Submenu { menu, .. } => Some(&menu.title),
+            MenuEntry::Separator { .. } => None,
+        }
+    }
+
+    /// The entry's text and bounds, for use when laying out the menu.
+    pub(super) fn parts_mut(&mut self) -> (Option<&mut Label>, &mut Rect) {
+        match self {
+            MenuEntry::Item { label, bounds, .. } => (Some(label), bounds),
+            MenuEntry::Submenu { menu, bounds } => {
+                (Some(&mut menu.title), bounds)
+            }
+            MenuEntry::Separator { bounds } => (None, bounds),
+        }
+    }
+}
+
+/// A pull-down menu in a [`super::MenuBar`], or the menu opened by a
+/// [`super::ContextMenu`]. Menus can be nested with [`Menu::submenu`].
+pub struct Menu<Message> {
+    /// The menu's title, shown in the menu bar or in the parent menu. Context
+    /// menus don't show their title.
+    pub(super) title: Label,
+
+    /// The space occupied by the title in the menu bar.
+    pub(super) title_bounds: Rect,
+
+    /// The menu's entries, from top to bottom.
+    pub(super) entries: Vec<MenuEntry<Message>>,
+
+    /// The space occupied by the open menu on screen.
+    pub(super) panel: Rect,
+}
+
+impl<Message> Menu<Message> {
+    /// Create a new menu without any entries.
+    pub fn new<T>(font: &Font, title: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            title: Label::new(font, title),
+            title_bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            entries: vec![],
+            panel: Rect::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Add an item which emits a message when clicked.
+    pub fn item<T, F>(mut self, font: &Font, text: T, on_click_fn: F) -> Self
+    where
+        T: AsRef<str>,
+        F: 'static + Fn() -> Message,
+    {
+        self.entries.push(MenuEntry::Item {
+            label: Label::new(font, text),
+            on_click: Box::new(on_click_fn),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        });
+        self
+    }
+
+    /// Add a nested menu which opens when its entry is hovered.
+    pub fn submenu(mut self, menu: Menu<Message>) -> Self {
+        self.entries.push(MenuEntry::Submenu {
+            menu,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        });
+        self
+    }
+
+    /// Add a line between groups of entries.
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator {
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        });
+        self
+    }
+
+    /// The nested menu at the given entry, if the entry is a submenu.
+    pub(super) fn submenu_at(&self, index: usize) -> Option<&Menu<Message>> {
+        match self.entries.get(index) {
+            Some(MenuEntry::Submenu { menu, .. }) => Some(menu),
+            _ => None,
+        }
+    }
+
+    /// The nested menu at the given entry, if the entry is a submenu.
+    pub(super) fn submenu_at_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Menu<Message>> {
+        match self.entries.get_mut(index) {
+            Some(MenuEntry::Submenu { menu, .. }) => Some(menu),
+            _ => None,
+        }
+    }
+
+    /// The index of the entry under the given point.
+    pub(super) fn entry_at(&self, point: Vec2) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| entry.bounds().contains(point))
+    }
+}
@@ -0,0 +1,332 @@
+//! A menu bar shows a row of menu titles. Clicking a title opens its menu in
+//! the overlay, on top of the rest of the UI.
+//!
+//! Like other widgets, the menu bar is rebuilt with the rest of the view
+//! each frame. Which menus are open is stored in the UI InternalState, and
+//! each menu item provides a function which builds the message emitted when
+//! it's clicked.
+//!
+//! The same menus can be opened at the cursor by wrapping a widget in a
+//! [`ContextMenu`].
+
+mod context_menu;
+mod menu;
+mod popup;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, OverlayEvent, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, Vec2, Vec4,
+};
+
+pub use self::{
+    context_menu::{ContextMenu, WithContextMenu},
+    menu::Menu,
+};
+
+use self::popup::{MenuStyle, PopupEvent, PopupState};
+
+/// A MenuBar's state is stored in the UI InternalState so open menus persist
+/// between views.
+#[derive(Debug, Clone, Default)]
+struct MenuBarState {
+    /// The index of the open menu in the bar, None when every menu is
+    /// closed.
+    open: Option<usize>,
+
+    /// The open menu's submenus and hovered entries.
+    popup: PopupState,
+
+    /// The menu title under the cursor.
+    hovered_title: Option<usize>,
+}
+
+/// A MenuBar fills the available width with a row of menu titles. Menus can
+/// contain items, separators, and nested submenus.
+///
+/// # Controls
+///
+/// - Click a title to open or close its menu. While a menu is open, hovering
+///   another title opens that menu instead.
+/// - Hover a submenu entry to open it.
+/// - Click an item to emit its message and close every menu.
+/// - Press Escape, or click outside of the menus, to close them.
+pub struct MenuBar<Message> {
+    /// The ID uniquely identifies this menu bar when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The menus in the bar, from left to right.
+    menus: Vec<Menu<Message>>,
+
+    /// The space occupied by the bar on screen.
+    bounds: Rect,
+
+    /// The font's line height, used to size each row.
+    line_height: f32,
+
+    /// The space between text and the edges of each row. None uses the
+    /// theme's padding.
+    padding: Option<f32>,
+
+    /// The color behind the bar.
+    bar_color: Option<Vec4>,
+
+    /// The color behind open titles and hovered entries.
+    hover_color: Option<Vec4>,
+
+    /// The color behind open menus.
+    panel_color: Option<Vec4>,
+
+    /// The color of menu outlines, separators, and submenu arrows.
+    accent_color: Option<Vec4>,
+}
+
+impl<Message> MenuBar<Message> {
+    /// Create a new menu bar without any menus.
+    pub fn new(id: Id, font: &Font) -> Self {
+        let line_height = font.line_height();
+        Self {
+            id,
+            menus: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            line_height,
+            padding: None,
+            bar_color: None,
+            hover_color: None,
+            panel_color: None,
+            accent_color: None,
+        }
+    }
+
+    builder_field_some!(padding, f32);
+    builder_field_some!(bar_color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(panel_color, Vec4);
+    builder_field_some!(accent_color, Vec4);
+
+    /// Add a menu to the right side of the bar.
+    pub fn menu(mut self, menu: Menu<Message>) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    fn style(&self, theme: &Theme) -> MenuStyle {
+        let padding = self.padding.unwrap_or(theme.padding * self.line_height);
+        MenuStyle {
+            padding,
+            row_height: self.line_height + 2.0 * padding,
+            arrow_space: self.line_height,
+            hover_color: self.hover_color.unwrap_or(theme.hover_color),
+            panel_color: self.panel_color.unwrap_or(theme.panel_color),
+            accent_color: self.accent_color.unwrap_or(theme.detail_color),
+        }
+    }
+
+    /// The index of the menu title under the given point.
+    fn title_at(&self, point: Vec2) -> Option<usize> {
+        self.menus
+            .iter()
+            .position(|menu| menu.title_bounds.contains(point))
+    }
+}
+
+impl<Message> Widget<Message> for MenuBar<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let hovered_title = self.title_at(input.mouse_position);
+        let state = internal_state.get_state_mut::<MenuBarState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.hovered_title = hovered_title;
+                match (state.open, hovered_title) {
+                    (Some(open), Some(title)) if open != title => {
+                        state.open = Some(title);
+                        state.popup = PopupState::default();
+                    }
+                    _ => (),
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if let Some(title) = hovered_title {
+                    if state.open == Some(title) {
+                        state.open = None;
+                    } else {
+                        state.open = Some(title);
+                    }
+                    state.popup = PopupState::default();
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = internal_state.get_state::<MenuBarState>(&self.id).clone();
+        Tile {
+            model: self.bounds,
+            color: self.bar_color.unwrap_or(theme.widget_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        for (index, menu) in self.menus.iter().enumerate() {
+            if state.open == Some(index) || state.hovered_title == Some(index) {
+                Tile {
+                    model: menu.title_bounds,
+                    color: self.hover_color.unwrap_or(theme.hover_color),
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+            Widget::<Message>::draw_frame(&menu.title, internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let style = self.style(internal_state.theme());
+        for menu in &mut self.menus {
+            let title_size = Widget::<Message>::dimensions(
+                &mut menu.title,
+                internal_state,
+                max_size,
+            );
+            menu.title_bounds = Dimensions::new(
+                title_size.width + 4.0 * style.padding,
+                style.row_height,
+            )
+            .as_rect();
+            popup::measure(menu, internal_state, style);
+        }
+
+        self.bounds = Dimensions::new(max_size.width, style.row_height)
+            .min(max_size)
+            .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let style = self.style(internal_state.theme());
+        self.bounds = self.bounds.set_top_left_position(position);
+
+        let mut left = position.x;
+        for menu in &mut self.menus {
+            menu.title_bounds = menu
+                .title_bounds
+                .set_top_left_position(vec2(left, position.y));
+            Widget::<Message>::set_top_left_position(
+                &mut menu.title,
+                internal_state,
+                vec2(left + 2.0 * style.padding, position.y + style.padding),
+            );
+            left = menu.title_bounds.right();
+        }
+
+        let state = internal_state.get_state::<MenuBarState>(&self.id).clone();
+        let bottom = self.bounds.bottom();
+        if let Some(menu) = state.open.and_then(|i| self.menus.get_mut(i)) {
+            let menu_position = vec2(menu.title_bounds.left(), bottom);
+            popup::layout(
+                menu,
+                internal_state,
+                style,
+                menu_position,
+                &state.popup.submenus,
+            );
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        let mut state =
+            internal_state.get_state::<MenuBarState>(&self.id).clone();
+        let menu = match state.open.and_then(|i| self.menus.get(i)) {
+            Some(menu) => menu,
+            None => return Ok(OverlayEvent::Ignored),
+        };
+
+        let result =
+            match popup::handle_event(menu, &mut state.popup, input, event) {
+                PopupEvent::Ignored => OverlayEvent::Ignored,
+                PopupEvent::Captured => OverlayEvent::Captured(None),
+                PopupEvent::Escaped => {
+                    state = MenuBarState::default();
+                    OverlayEvent::Captured(None)
+                }
+                PopupEvent::PressedOutside => {
+                    if self.title_at(input.mouse_position).is_none() {
+                        // clicking anywhere else closes the menus, the
+                        // titles handle toggling on their own
+                        state = MenuBarState::default();
+                    }
+                    OverlayEvent::Ignored
+                }
+                PopupEvent::Selected(message) => {
+                    state = MenuBarState::default();
+                    OverlayEvent::Captured(Some(message))
+                }
+            };
+        *internal_state.get_state_mut::<MenuBarState>(&self.id) = state;
+        Ok(result)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let style = self.style(internal_state.theme());
+        let state = internal_state.get_state::<MenuBarState>(&self.id).clone();
+        match state.open.and_then(|i| self.menus.get(i)) {
+            Some(menu) => {
+                popup::draw(menu, &state.popup, internal_state, frame, style)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for MenuBar<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,355 @@
+//! Layout, input, and drawing for a menu which is open in the overlay.
+//!
+//! Both the [`super::MenuBar`] and the [`super::ContextMenu`] show menus the
+//! same way, they only differ in where the menu opens and what closes it.
+
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::Widget,
+        Input, InternalState,
+    },
+    vec2, Vec2, Vec4,
+};
+
+use super::menu::{Menu, MenuEntry};
+
+/// The state of an open menu and its open submenus.
+#[derive(Debug, Clone, Default)]
+pub(super) struct PopupState {
+    /// The path to the deepest open submenu. Each index is a submenu entry
+    /// in the previous menu, starting with the root menu. Empty when only the
+    /// root menu is open.
+    pub(super) submenus: Vec<usize>,
+
+    /// The depth and index of the entry under the cursor.
+    pub(super) hovered_entry: Option<(usize, usize)>,
+
+    /// The depth and index of the entry the mouse was pressed on.
+    pub(super) pressed_entry: Option<(usize, usize)>,
+}
+
+/// The outcome of an event which reached an open menu.
+pub(super) enum PopupEvent<Message> {
+    /// The event didn't affect the menu.
+    Ignored,
+
+    /// The event was used by the menu.
+    Captured,
+
+    /// A mouse button was pressed outside of the open menus.
+    PressedOutside,
+
+    /// Escape was pressed.
+    Escaped,
+
+    /// An item was clicked.
+    Selected(Message),
+}
+
+/// Sizes and colors shared by every open menu.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct MenuStyle {
+    /// The space between text and the edges of each row.
+    pub(super) padding: f32,
+
+    /// The height of each item and submenu entry.
+    pub(super) row_height: f32,
+
+    /// The space reserved on the right of each entry for the submenu arrow.
+    pub(super) arrow_space: f32,
+
+    /// The color behind hovered entries.
+    pub(super) hover_color: Vec4,
+
+    /// The color behind open menus.
+    pub(super) panel_color: Vec4,
+
+    /// The color of menu outlines, separators, and submenu arrows.
+    pub(super) accent_color: Vec4,
+}
+
+/// Each open menu, from the root menu to the deepest submenu.
+pub(super) fn open_menus<'a, Message>(
+    root: &'a Menu<Message>,
+    submenus: &[usize],
+) -> Vec<&'a Menu<Message>> {
+    let mut menus = vec![root];
+    let mut current = root;
+    for &entry in submenus {
+        match current.submenu_at(entry) {
+            Some(submenu) => {
+                menus.push(submenu);
+                current = submenu;
+            }
+            None => break,
+        }
+    }
+    menus
+}
+
+/// The depth and index of the open menu entry under the given point.
+/// Deeper menus are drawn on top, so they're checked first.
+fn entry_at<Message>(
+    root: &Menu<Message>,
+    submenus: &[usize],
+    point: Vec2,
+) -> Option<(usize, usize)> {
+    open_menus(root, submenus)
+        .iter()
+        .enumerate()
+        .rev()
+        .find(|(_, menu)| menu.panel.contains(point))
+        .and_then(|(depth, menu)| {
+            menu.entry_at(point).map(|index| (depth, index))
+        })
+}
+
+/// Compute the size of the menu, and of each of its submenus.
+pub(super) fn measure<Message>(
+    menu: &mut Menu<Message>,
+    internal_state: &mut InternalState,
+    style: MenuStyle,
+) {
+    let mut width: f32 = 0.0;
+    let mut height = 2.0 * style.padding;
+    for entry in &mut menu.entries {
+        if let MenuEntry::Submenu { menu, .. } = entry {
+            measure(menu, internal_state, style);
+        }
+        let (label, bounds) = entry.parts_mut();
+        let row_height = match label {
+            Some(label) => {
+                let label_size = Widget::<Message>::dimensions(
+                    label,
+                    internal_state,
+                    &Dimensions::new(f32::MAX, f32::MAX),
+                );
+                width = width.max(label_size.width);
+                style.row_height
+            }
+            None => 2.0 * style.padding,
+        };
+        *bounds = Dimensions::new(0.0, row_height).as_rect();
+        height += row_height;
+    }
+
+    let width = width + 2.0 * style.padding + style.arrow_space;
+    for entry in &mut menu.entries {
+        let (_, bounds) = entry.parts_mut();
+        *bounds = Dimensions::new(width, bounds.height()).as_rect();
+    }
+    menu.panel = Dimensions::new(width, height).as_rect();
+}
+
+/// Position the open menu's entries, then the open submenu to the right of
+/// its entry.
+pub(super) fn layout<Message>(
+    menu: &mut Menu<Message>,
+    internal_state: &mut InternalState,
+    style: MenuStyle,
+    position: Vec2,
+    submenus: &[usize],
+) {
+    menu.panel = menu.panel.set_top_left_position(position);
+
+    let mut top = position.y + style.padding;
+    for entry in &mut menu.entries {
+        let (label, bounds) = entry.parts_mut();
+        *bounds = bounds.set_top_left_position(vec2(position.x, top));
+        if let Some(label) = label {
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                vec2(position.x + style.padding, top + style.padding),
+            );
+        }
+        top += bounds.height();
+    }
+
+    if let Some(&index) = submenus.first() {
+        let row = match menu.entries.get(index) {
+            Some(entry) => entry.bounds(),
+            None => return,
+        };
+        if let Some(submenu) = menu.submenu_at_mut(index) {
+            layout(
+                submenu,
+                internal_state,
+                style,
+                vec2(row.right(), row.top() - style.padding),
+                &submenus[1..],
+            );
+        }
+    }
+}
+
+/// Update the open menu's state for the event.
+pub(super) fn handle_event<Message>(
+    root: &Menu<Message>,
+    state: &mut PopupState,
+    input: &Input,
+    event: &glfw::WindowEvent,
+) -> PopupEvent<Message> {
+    use glfw::{Action, Key, MouseButton, WindowEvent};
+
+    let hovered = entry_at(root, &state.submenus, input.mouse_position);
+    match *event {
+        WindowEvent::CursorPos(..) => {
+            state.hovered_entry = hovered;
+            if let Some((depth, index)) = hovered {
+                // hovering an entry closes any deeper submenus, unless the
+                // entry opens one itself
+                state.submenus.truncate(depth);
+                let menu = open_menus(root, &state.submenus)[depth];
+                if menu.submenu_at(index).is_some() {
+                    state.submenus.push(index);
+                }
+            }
+
+            // mouse movement still reaches the rest of the UI so hover
+            // states stay up to date
+            PopupEvent::Ignored
+        }
+        WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
+            PopupEvent::Escaped
+        }
+        WindowEvent::MouseButton(button, Action::Press, _) => {
+            if hovered.is_none() {
+                PopupEvent::PressedOutside
+            } else {
+                if button == MouseButton::Button1 {
+                    state.pressed_entry = hovered;
+                }
+                PopupEvent::Captured
+            }
+        }
+        WindowEvent::MouseButton(MouseButton::Button1, Action::Release, _) => {
+            match hovered {
+                Some((depth, index)) => {
+                    let pressed = state.pressed_entry.take();
+                    let menu = open_menus(root, &state.submenus)[depth];
+                    match &menu.entries[index] {
+                        MenuEntry::Item { on_click, .. }
+                            if pressed == hovered =>
+                        {
+                            PopupEvent::Selected(on_click())
+                        }
+                        _ => PopupEvent::Captured,
+                    }
+                }
+                None => PopupEvent::Ignored,
+            }
+        }
+        _ => PopupEvent::Ignored,
+    }
+}
+
+/// Draw the root menu and each of its open submenus.
+pub(super) fn draw<Message>(
+    root: &Menu<Message>,
+    state: &PopupState,
+    internal_state: &mut InternalState,
+    frame: &mut Frame,
+    style: MenuStyle,
+) -> Result<()> {
+    let menus = open_menus(root, &state.submenus);
+    for (depth, menu) in menus.iter().enumerate() {
+        // highlight the hovered entry and the entry of the open submenu
+        let mut highlighted = vec![];
+        if let Some(&submenu) = state.submenus.get(depth) {
+            highlighted.push(submenu);
+        }
+        match state.hovered_entry {
+            Some((hovered_depth, index)) if hovered_depth == depth => {
+                highlighted.push(index);
+            }
+            _ => (),
+        }
+        draw_menu(menu, internal_state, frame, style, &highlighted)?;
+    }
+    Ok(())
+}
+
+fn draw_menu<Message>(
+    menu: &Menu<Message>,
+    internal_state: &mut InternalState,
+    frame: &mut Frame,
+    style: MenuStyle,
+    highlighted: &[usize],
+) -> Result<()> {
+    Tile {
+        model: menu.panel,
+        color: style.panel_color,
+        ..Default::default()
+    }
+    .fill(frame)?;
+
+    for (index, entry) in menu.entries.iter().enumerate() {
+        let bounds = entry.bounds();
+        if highlighted.contains(&index) && entry.label().is_some() {
+            Tile {
+                model: bounds,
+                color: style.hover_color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        if let Some(label) = entry.label() {
+            Widget::<Message>::draw_frame(label, internal_state, frame)?;
+        }
+        match entry {
+            MenuEntry::Submenu { .. } => {
+                draw_submenu_arrow(frame, style, bounds)?;
+            }
+            MenuEntry::Separator { .. } => {
+                let y = (0.5 * (bounds.top() + bounds.bottom())).round();
+                Line {
+                    start: vec2(bounds.left() + style.padding, y),
+                    end: vec2(bounds.right() - style.padding, y),
+                    color: style.accent_color,
+                    ..Default::default()
+                }
+                .draw(frame)?;
+            }
+            MenuEntry::Item { .. } => (),
+        }
+    }
+
+    Tile {
+        model: menu.panel,
+        color: style.accent_color,
+        ..Default::default()
+    }
+    .outline(frame)
+}
+
+fn draw_submenu_arrow(
+    frame: &mut Frame,
+    style: MenuStyle,
+    row: Rect,
+) -> Result<()> {
+    let size = 0.2 * style.arrow_space;
+    let center = vec2(
+        row.right() - style.padding - 0.5 * style.arrow_space,
+        0.5 * (row.top() + row.bottom()),
+    );
+    let tip = center + vec2(0.5 * size, 0.0);
+    let line = Line {
+        color: style.accent_color,
+        width: 1.5,
+        ..Default::default()
+    };
+    for &side in &[-1.0, 1.0] {
+        Line {
+            start: center + vec2(-0.5 * size, size * side),
+            end: tip,
+            ..line
+        }
+        .draw(frame)?;
+    }
+    Ok(())
+}
@@ -1,14 +1,44 @@
 mod align;
 mod button;
+mod canvas;
+mod checkbox;
 mod col;
 mod composite;
 mod container;
+mod curve_editor;
+mod diagnostics;
+mod dropdown;
 mod element;
-mod hsplit;
+mod eyedropper;
+mod icon;
+mod image;
+mod knob;
 mod label;
+mod layer;
+mod markdown_view;
+mod menu_bar;
+mod modal;
+mod node_editor;
+mod number_input;
+mod progress_bar;
 mod row;
+mod rulers;
+mod scope;
+mod scroll_view;
 mod slider;
+mod spacer;
+mod split;
+mod stack;
+mod table;
+mod text_area;
+mod text_input;
+mod texture_inspector;
+mod timeline_editor;
+mod toggle;
+mod tooltip;
+mod tree_view;
 mod window;
+mod wrap;
 
 pub mod prelude;
 
@@ -23,15 +53,47 @@ use crate::{
 pub use self::{
     align::{Align, HAlignment, VAlignment},
     button::Button,
+    canvas::Canvas,
+    checkbox::Checkbox,
     col::Col,
     composite::{ComposedElement, ComposedMessage, Composite, CompositeWidget},
     container::{Constraint, Container, WithContainer},
+    curve_editor::CurveEditor,
+    diagnostics::Diagnostics,
+    dropdown::Dropdown,
     element::Element,
-    hsplit::HSplit,
-    label::Label,
+    eyedropper::Eyedropper,
+    icon::Icon,
+    image::{Image, ImageFit},
+    knob::{Knob, KnobDrag},
+    label::{Label, LabelFit},
+    layer::Layer,
+    markdown_view::MarkdownView,
+    menu_bar::{ContextMenu, Menu, MenuBar, WithContextMenu},
+    modal::Modal,
+    node_editor::{
+        Connection, Node, NodeEditor, Port, PortDirection, PortRef, PortType,
+    },
+    number_input::NumberInput,
+    progress_bar::ProgressBar,
     row::Row,
-    slider::Slider,
+    rulers::{Measurement, Rulers},
+    scope::{Scope, Signal},
+    scroll_view::{ListView, ScrollView, Scrollbar},
+    slider::{RangeSlider, Slider},
+    spacer::Spacer,
+    split::{HSplit, VSplit},
+    stack::Stack,
+    table::{SortOrder, Table},
+    text_area::TextArea,
+    text_input::TextInput,
+    texture_inspector::TextureInspector,
+    timeline_editor::{TimelineEdit, TimelineEditor, Track},
+    toggle::Toggle,
+    tooltip::{Tooltip, WithTooltip},
+    tree_view::{TreeNode, TreeView},
     window::Window,
+    wrap::Wrap,
 };
 
 /// Widgets are UI building blocks. Widgets ar responsible for handling system
@@ -54,6 +116,10 @@ pub use self::{
 ///
 pub trait Widget<Message> {
     /// Handle events for this widget.
+    ///
+    /// Every window event the UI handles is given to the view, including
+    /// text input. Use [`Input::typed_char`] to read typed characters, and
+    /// the input's modifiers for the keys held while typing.
     fn handle_event(
         &mut self,
         internal_state: &mut InternalState,
@@ -84,4 +150,65 @@ pub trait Widget<Message> {
         internal_state: &mut InternalState,
         position: Vec2,
     );
+
+    /// Handle events for anything this widget draws in the overlay.
+    ///
+    /// Overlays are drawn on top of every other widget, so they get the first
+    /// chance to handle each event. The event only reaches [`handle_event`]
+    /// if every overlay ignores it.
+    ///
+    /// Widgets with children must forward this call to each child.
+    fn handle_overlay_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        Ok(OverlayEvent::Ignored)
+    }
+
+    /// Render geometry which must appear on top of every other widget, e.g.
+    /// a dropdown's list of options. This is called after the entire UI has
+    /// been drawn with [`draw_frame`].
+    ///
+    /// Widgets with children must forward this call to each child.
+    fn draw_overlay(
+        &self,
+        _internal_state: &mut InternalState,
+        _frame: &mut Frame,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The result of offering an event to a widget's overlay.
+#[derive(Debug, Copy, Clone)]
+pub enum OverlayEvent<Message> {
+    /// The overlay didn't use the event, so it continues on to the rest of
+    /// the UI.
+    Ignored,
+
+    /// The overlay used the event and no other widget will see it. The
+    /// overlay can optionally emit a message.
+    Captured(Option<Message>),
+}
+
+impl<Message> OverlayEvent<Message> {
+    /// Transform the captured message, if there is one.
+    pub fn map<T, F>(self, f: F) -> OverlayEvent<T>
+    where
+        F: FnOnce(Message) -> T,
+    {
+        match self {
+            Self::Ignored => OverlayEvent::Ignored,
+            Self::Captured(message_opt) => {
+                OverlayEvent::Captured(message_opt.map(f))
+            }
+        }
+    }
+
+    /// True when the event was captured by an overlay.
+    pub fn is_captured(&self) -> bool {
+        matches!(self, Self::Captured(_))
+    }
 }
@@ -0,0 +1,246 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, OverlayEvent, Widget},
+        Input, InternalState,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A Modal shows a dialog centered on top of the rest of the UI. While the
+/// dialog is open everything underneath is dimmed and no longer receives
+/// events.
+///
+/// The Modal takes up all available space, so it's typically used as the
+/// root of the view.
+///
+/// # Controls
+///
+/// - Press Escape, or click outside of the dialog, to dismiss it.
+/// - Press Enter to confirm.
+pub struct Modal<Message> {
+    /// The UI underneath the dialog.
+    content: Element<Message>,
+
+    /// The dialog shown when the modal is open.
+    dialog: Element<Message>,
+
+    /// True when the dialog is visible.
+    open: bool,
+
+    /// The space occupied by the modal on screen.
+    bounds: Rect,
+
+    /// The space occupied by the dialog on screen.
+    dialog_bounds: Rect,
+
+    /// The color drawn over the content while the dialog is open.
+    dim_color: Vec4,
+
+    /// When true, clicking outside of the dialog dismisses it.
+    dismiss_on_click_outside: bool,
+
+    /// The function used to build a message when the dialog is dismissed.
+    on_dismiss: Option<Box<dyn Fn() -> Message>>,
+
+    /// The function used to build a message when the dialog is confirmed.
+    on_confirm: Option<Box<dyn Fn() -> Message>>,
+}
+
+impl<Message> Modal<Message> {
+    pub fn new<C, D>(content: C, dialog: D) -> Self
+    where
+        C: Into<Element<Message>>,
+        D: Into<Element<Message>>,
+    {
+        Self {
+            content: content.into(),
+            dialog: dialog.into(),
+            open: true,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            dialog_bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            dim_color: vec4(0.0, 0.0, 0.0, 0.6),
+            dismiss_on_click_outside: true,
+            on_dismiss: None,
+            on_confirm: None,
+        }
+    }
+
+    builder_field!(open, bool);
+    builder_field!(dim_color, Vec4);
+    builder_field!(dismiss_on_click_outside, bool);
+
+    /// Set the function used to build a message when the user dismisses the
+    /// dialog.
+    pub fn on_dismiss<F>(self, on_dismiss_fn: F) -> Self
+    where
+        F: 'static + Fn() -> Message,
+    {
+        Self {
+            on_dismiss: Some(Box::new(on_dismiss_fn)),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user confirms the
+    /// dialog.
+    pub fn on_confirm<F>(self, on_confirm_fn: F) -> Self
+    where
+        F: 'static + Fn() -> Message,
+    {
+        Self {
+            on_confirm: Some(Box::new(on_confirm_fn)),
+            ..self
+        }
+    }
+
+    fn dismissed(&self) -> Option<Message> {
+        self.on_dismiss.as_ref().map(|on_dismiss| on_dismiss())
+    }
+
+    fn confirmed(&self) -> Option<Message> {
+        self.on_confirm.as_ref().map(|on_confirm| on_confirm())
+    }
+}
+
+impl<Message> Widget<Message> for Modal<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        if self.open {
+            // events only reach the content while the dialog is closed, the
+            // dialog itself is handled in the overlay
+            return Ok(None);
+        }
+        self.content.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.content.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.content.dimensions(internal_state, max_size);
+        if self.open {
+            let dialog_dimensions =
+                self.dialog.dimensions(internal_state, max_size);
+            self.dialog_bounds = dialog_dimensions.as_rect();
+        }
+        self.bounds = max_size.as_rect();
+        *max_size
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.content.set_top_left_position(internal_state, position);
+        if self.open {
+            let dialog_position = position
+                + vec2(
+                    0.5 * (self.bounds.width() - self.dialog_bounds.width()),
+                    0.5 * (self.bounds.height() - self.dialog_bounds.height()),
+                );
+            let dialog_position =
+                vec2(dialog_position.x.round(), dialog_position.y.round());
+            self.dialog_bounds =
+                self.dialog_bounds.set_top_left_position(dialog_position);
+            self.dialog
+                .set_top_left_position(internal_state, dialog_position);
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        use glfw::{Action, Key, MouseButton, WindowEvent};
+
+        if !self.open {
+            return self.content.handle_overlay_event(
+                internal_state,
+                input,
+                event,
+            );
+        }
+
+        // the dialog's own overlays, like an open dropdown, come first
+        let dialog_overlay =
+            self.dialog
+                .handle_overlay_event(internal_state, input, event)?;
+        if dialog_overlay.is_captured() {
+            return Ok(dialog_overlay);
+        }
+
+        let message = match *event {
+            WindowEvent::Key(Key::Escape, _, Action::Press, _) => {
+                self.dismissed()
+            }
+            WindowEvent::Key(Key::Enter, _, Action::Press, _)
+            | WindowEvent::Key(Key::KpEnter, _, Action::Press, _) => {
+                self.confirmed()
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if self.dismiss_on_click_outside
+                && !self.dialog_bounds.contains(input.mouse_position) =>
+            {
+                self.dismissed()
+            }
+            _ => self.dialog.handle_event(internal_state, input, event)?,
+        };
+
+        // every event is captured so nothing underneath the dialog reacts
+        Ok(OverlayEvent::Captured(message))
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.content.draw_overlay(internal_state, frame)?;
+        if !self.open {
+            return Ok(());
+        }
+
+        Tile {
+            model: self.bounds,
+            color: self.dim_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        self.dialog.draw_frame(internal_state, frame)?;
+        self.dialog.draw_overlay(internal_state, frame)
+    }
+}
+
+impl<Message> Into<Element<Message>> for Modal<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,734 @@
+//! A node editor is a pannable, zoomable canvas of boxes connected by wires.
+//!
+//! The editor does not own the graph. The application provides the nodes and
+//! connections each time the view is built, and the editor emits messages
+//! when the user connects or disconnects ports so the application can update
+//! its own model. Node positions and the canvas's pan and zoom are stored in
+//! the UI's internal state.
+
+mod node;
+mod port;
+mod wire;
+
+use ::{anyhow::Result, std::collections::HashMap};
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::{tessellate_in_parallel, triangles::Frame},
+    ui::{
+        frame_arena::TempVec,
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, OverlayEvent, Widget},
+        Id, Input, InternalState,
+    },
+    vec2, vec3, vec4, Mat4, Vec2, Vec4,
+};
+
+pub use self::{
+    node::Node,
+    port::{Connection, Port, PortDirection, PortRef, PortType},
+};
+
+use self::wire::Wire;
+
+/// Editors with at least this many wires tessellate them on a thread pool.
+/// Fewer wires are quicker to build on the UI thread.
+const PARALLEL_WIRE_COUNT: usize = 64;
+
+/// How the user is currently interacting with the editor.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Interaction {
+    Idle,
+
+    /// The canvas is being dragged. The last screen-space mouse position is
+    /// kept so the pan can follow the mouse.
+    Panning {
+        last_position: Vec2,
+    },
+
+    /// A node is being dragged by its title bar.
+    DraggingNode {
+        node: Id,
+        grab_offset: Vec2,
+    },
+
+    /// A new wire is being dragged out of a port.
+    DraggingWire {
+        anchor: PortRef,
+        direction: PortDirection,
+    },
+}
+
+impl Default for Interaction {
+    /// Defaults to Idle
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+/// A NodeEditor's state is stored in the UI InternalState so the canvas view
+/// and node positions persist between views.
+#[derive(Debug, Clone)]
+pub struct NodeEditorState {
+    /// The screen-space offset of the canvas origin from the editor's top
+    /// left corner.
+    pan: Vec2,
+
+    /// The canvas scale factor.
+    zoom: f32,
+
+    /// Positions for nodes which the user has dragged.
+    node_positions: HashMap<Id, Vec2>,
+
+    /// The current interaction.
+    interaction: Interaction,
+
+    /// The wire under the mouse, if any.
+    hovered_wire: Option<Connection>,
+
+    /// The most recent mouse position in canvas space.
+    canvas_mouse: Vec2,
+}
+
+impl Default for NodeEditorState {
+    fn default() -> Self {
+        Self {
+            pan: vec2(0.0, 0.0),
+            zoom: 1.0,
+            node_positions: HashMap::new(),
+            interaction: Interaction::Idle,
+            hovered_wire: None,
+            canvas_mouse: vec2(0.0, 0.0),
+        }
+    }
+}
+
+/// A NodeEditor lays out [`Node`]s on a canvas and draws [`Connection`]s
+/// between their ports.
+///
+/// # Controls
+///
+/// - Drag a node's title bar to move it.
+/// - Drag from a port to another port of the same type to connect them.
+/// - Drag a connected input away from its port, or click a wire, to
+///   disconnect it.
+/// - Drag the empty canvas with the left or middle mouse button to pan.
+/// - Scroll to zoom around the mouse.
+///
+/// The editor fills all of the space it's given. Nodes are not clipped to the
+/// editor's bounds.
+pub struct NodeEditor<Message> {
+    /// The ID uniquely identifies this editor when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The nodes, drawn back to front.
+    nodes: Vec<Node<Message>>,
+
+    /// The wires between nodes.
+    connections: Vec<Connection>,
+
+    /// The space occupied by the editor on screen.
+    bounds: Rect,
+
+    /// The color of the canvas behind the nodes.
+    background_color: Vec4,
+
+    /// The color used for a wire under the mouse.
+    hover_color: Vec4,
+
+    /// The width of each wire in canvas space.
+    wire_width: f32,
+
+    /// The smallest allowed zoom.
+    min_zoom: f32,
+
+    /// The largest allowed zoom.
+    max_zoom: f32,
+
+    /// Builds a message when the user connects two ports.
+    on_connect: Option<Box<dyn Fn(Connection) -> Message>>,
+
+    /// Builds a message when the user removes a connection.
+    on_disconnect: Option<Box<dyn Fn(Connection) -> Message>>,
+
+    /// Builds a message when the user finishes dragging a node.
+    on_node_moved: Option<Box<dyn Fn(Id, Vec2) -> Message>>,
+}
+
+impl<Message> NodeEditor<Message> {
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            nodes: vec![],
+            connections: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            background_color: vec4(0.05, 0.05, 0.05, 1.0),
+            hover_color: vec4(1.0, 1.0, 0.6, 1.0),
+            wire_width: 2.0,
+            min_zoom: 0.25,
+            max_zoom: 4.0,
+            on_connect: None,
+            on_disconnect: None,
+            on_node_moved: None,
+        }
+    }
+
+    builder_field!(background_color, Vec4);
+    builder_field!(hover_color, Vec4);
+    builder_field!(wire_width, f32);
+    builder_field!(min_zoom, f32);
+    builder_field!(max_zoom, f32);
+
+    /// Add a node to the editor. Nodes added later are drawn on top.
+    pub fn node(mut self, node: Node<Message>) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Add a connection between two ports.
+    pub fn connection(mut self, connection: Connection) -> Self {
+        self.connections.push(connection);
+        self
+    }
+
+    /// Set the function used to build a message when the user drags a wire
+    /// between two compatible ports.
+    pub fn on_connect<F>(self, on_connect_fn: F) -> Self
+    where
+        F: 'static + Fn(Connection) -> Message,
+    {
+        Self {
+            on_connect: Some(Box::new(on_connect_fn)),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user removes an
+    /// existing connection.
+    pub fn on_disconnect<F>(self, on_disconnect_fn: F) -> Self
+    where
+        F: 'static + Fn(Connection) -> Message,
+    {
+        Self {
+            on_disconnect: Some(Box::new(on_disconnect_fn)),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user drops a node
+    /// after dragging it. The function is given the node's id and its new
+    /// canvas-space position.
+    pub fn on_node_moved<F>(self, on_node_moved_fn: F) -> Self
+    where
+        F: 'static + Fn(Id, Vec2) -> Message,
+    {
+        Self {
+            on_node_moved: Some(Box::new(on_node_moved_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> NodeEditor<Message> {
+    /// Convert a screen-space point into canvas space.
+    fn to_canvas(&self, state: &NodeEditorState, point: Vec2) -> Vec2 {
+        (point - self.bounds.top_left - state.pan) / state.zoom
+    }
+
+    /// The transform from canvas space into screen space.
+    fn canvas_transform(&self, state: &NodeEditorState) -> Mat4 {
+        let origin = self.bounds.top_left + state.pan;
+        Mat4::new_translation(&vec3(origin.x, origin.y, 0.0))
+            * Mat4::new_nonuniform_scaling(&vec3(state.zoom, state.zoom, 1.0))
+    }
+
+    /// Convert the input and event into canvas space so they can be passed
+    /// to the widgets inside each node.
+    fn to_canvas_event(
+        &self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> (Input, glfw::WindowEvent) {
+        let state = internal_state.get_state::<NodeEditorState>(&self.id);
+        let mut canvas_input = *input;
+        canvas_input.mouse_position =
+            self.to_canvas(state, input.mouse_position);
+        let canvas_event = match *event {
+            glfw::WindowEvent::CursorPos(x, y) => {
+                let canvas = self.to_canvas(state, vec2(x as f32, y as f32));
+                glfw::WindowEvent::CursorPos(canvas.x as f64, canvas.y as f64)
+            }
+            _ => event.clone(),
+        };
+        (canvas_input, canvas_event)
+    }
+
+    fn find_node(&self, id: Id) -> Option<&Node<Message>> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Get the canvas-space position of a port.
+    fn port_anchor(
+        &self,
+        port_ref: PortRef,
+        direction: PortDirection,
+    ) -> Option<Vec2> {
+        self.find_node(port_ref.node)
+            .and_then(|node| node.port(direction, port_ref.port))
+            .map(|port| port.anchor)
+    }
+
+    /// Get the frontmost port under a canvas-space point.
+    fn port_at(&self, point: Vec2) -> Option<(PortRef, PortDirection)> {
+        self.nodes.iter().rev().find_map(|node| {
+            node.port_at(point).map(|(direction, port)| {
+                let port_ref = PortRef {
+                    node: node.id,
+                    port,
+                };
+                (port_ref, direction)
+            })
+        })
+    }
+
+    /// Get the polyline for a connection's wire in canvas space.
+    fn connection_points(
+        &self,
+        connection: &Connection,
+    ) -> Option<TempVec<Vec2>> {
+        let output =
+            self.port_anchor(connection.output, PortDirection::Output)?;
+        let input = self.port_anchor(connection.input, PortDirection::Input)?;
+        Some(wire::wire_points(output, input))
+    }
+
+    /// The color of a connection's wire: the hover color when it's under the
+    /// mouse, otherwise the color of the output port.
+    fn wire_color(
+        &self,
+        state: &NodeEditorState,
+        connection: &Connection,
+    ) -> Vec4 {
+        if state.hovered_wire == Some(*connection) {
+            return self.hover_color;
+        }
+        self.find_node(connection.output.node)
+            .and_then(|node| {
+                node.port(PortDirection::Output, connection.output.port)
+            })
+            .map(|port| port.color)
+            .unwrap_or(self.hover_color)
+    }
+
+    /// Get the wire closest to a canvas-space point, if it's close enough
+    /// to count as under the mouse.
+    fn wire_at(
+        &self,
+        state: &NodeEditorState,
+        point: Vec2,
+    ) -> Option<Connection> {
+        // the tolerance is a few pixels in screen space, regardless of zoom
+        let tolerance = (0.5 * self.wire_width + 4.0 / state.zoom).max(0.0);
+        self.connections
+            .iter()
+            .filter_map(|connection| {
+                let points = self.connection_points(connection)?;
+                let distance = wire::distance_to_polyline(point, &points);
+                Some((*connection, distance))
+            })
+            .filter(|(_, distance)| *distance <= tolerance)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(connection, _)| connection)
+    }
+
+    /// Build a connection when a wire is dropped on a compatible port.
+    fn complete_wire(
+        &self,
+        anchor: PortRef,
+        direction: PortDirection,
+        point: Vec2,
+    ) -> Option<Connection> {
+        let (target, target_direction) = self.port_at(point)?;
+        if target_direction == direction || target.node == anchor.node {
+            return None;
+        }
+        let anchor_type = self
+            .find_node(anchor.node)?
+            .port(direction, anchor.port)?
+            .port_type;
+        let target_type = self
+            .find_node(target.node)?
+            .port(target_direction, target.port)?
+            .port_type;
+        if anchor_type != target_type {
+            return None;
+        }
+        let connection = match direction {
+            PortDirection::Output => Connection {
+                output: anchor,
+                input: target,
+            },
+            PortDirection::Input => Connection {
+                output: target,
+                input: anchor,
+            },
+        };
+        if self.connections.contains(&connection) {
+            None
+        } else {
+            Some(connection)
+        }
+    }
+
+    /// Decide what a left click on the canvas does.
+    fn begin_interaction(
+        &self,
+        state: &mut NodeEditorState,
+        mouse: Vec2,
+    ) -> Option<Message> {
+        let canvas_mouse = self.to_canvas(state, mouse);
+
+        if let Some((port_ref, direction)) = self.port_at(canvas_mouse) {
+            let existing = self
+                .connections
+                .iter()
+                .find(|connection| connection.input == port_ref)
+                .copied();
+            return match (direction, existing) {
+                (PortDirection::Input, Some(connection)) => {
+                    // pick the wire up off of the input so it can be dropped
+                    // somewhere else
+                    state.interaction = Interaction::DraggingWire {
+                        anchor: connection.output,
+                        direction: PortDirection::Output,
+                    };
+                    self.on_disconnect
+                        .as_ref()
+                        .map(|on_disconnect| on_disconnect(connection))
+                }
+                _ => {
+                    state.interaction = Interaction::DraggingWire {
+                        anchor: port_ref,
+                        direction,
+                    };
+                    None
+                }
+            };
+        }
+
+        if let Some(connection) = state.hovered_wire.take() {
+            return self
+                .on_disconnect
+                .as_ref()
+                .map(|on_disconnect| on_disconnect(connection));
+        }
+
+        let node_under_mouse = self
+            .nodes
+            .iter()
+            .rev()
+            .find(|node| node.bounds.contains(canvas_mouse));
+        match node_under_mouse {
+            Some(node) if node.header.contains(canvas_mouse) => {
+                state.interaction = Interaction::DraggingNode {
+                    node: node.id,
+                    grab_offset: canvas_mouse - node.header.top_left,
+                };
+            }
+            Some(_) => {
+                // clicks on a node's body belong to the node's content
+            }
+            None => {
+                state.interaction = Interaction::Panning {
+                    last_position: mouse,
+                };
+            }
+        }
+        None
+    }
+
+    /// Finish the current interaction when the mouse is released.
+    fn end_interaction(
+        &self,
+        state: &mut NodeEditorState,
+        mouse: Vec2,
+    ) -> Option<Message> {
+        let canvas_mouse = self.to_canvas(state, mouse);
+        let interaction = std::mem::take(&mut state.interaction);
+        match interaction {
+            Interaction::DraggingWire { anchor, direction } => {
+                let connection =
+                    self.complete_wire(anchor, direction, canvas_mouse)?;
+                self.on_connect
+                    .as_ref()
+                    .map(|on_connect| on_connect(connection))
+            }
+            Interaction::DraggingNode { node, .. } => {
+                let position = *state.node_positions.get(&node)?;
+                self.on_node_moved
+                    .as_ref()
+                    .map(|on_node_moved| on_node_moved(node, position))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<Message> Widget<Message> for NodeEditor<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let (canvas_input, canvas_event) =
+            self.to_canvas_event(internal_state, input, event);
+        let idle = internal_state
+            .get_state::<NodeEditorState>(&self.id)
+            .interaction
+            == Interaction::Idle;
+
+        let is_click = matches!(*event, WindowEvent::MouseButton(..));
+        let inside = self.bounds.contains(input.mouse_position);
+        if idle && (inside || !is_click) {
+            for node in self.nodes.iter_mut().rev() {
+                let message_opt = node.handle_event(
+                    internal_state,
+                    &canvas_input,
+                    &canvas_event,
+                )?;
+                if message_opt.is_some() {
+                    return Ok(message_opt);
+                }
+            }
+        }
+
+        let mut state = internal_state
+            .get_state_mut::<NodeEditorState>(&self.id)
+            .clone();
+        state.canvas_mouse = canvas_input.mouse_position;
+
+        let message_opt = match *event {
+            WindowEvent::CursorPos(x, y) => {
+                let mouse = vec2(x as f32, y as f32);
+                match state.interaction {
+                    Interaction::Panning { last_position } => {
+                        state.pan += mouse - last_position;
+                        state.interaction = Interaction::Panning {
+                            last_position: mouse,
+                        };
+                    }
+                    Interaction::DraggingNode { node, grab_offset } => {
+                        state
+                            .node_positions
+                            .insert(node, state.canvas_mouse - grab_offset);
+                    }
+                    Interaction::Idle if inside => {
+                        state.hovered_wire =
+                            self.wire_at(&state, state.canvas_mouse);
+                    }
+                    _ => {
+                        state.hovered_wire = None;
+                    }
+                }
+                None
+            }
+            WindowEvent::Scroll(_, y_offset) if inside => {
+                let mouse = input.mouse_position;
+                let anchor = self.to_canvas(&state, mouse);
+                state.zoom = (state.zoom * 1.1f32.powf(y_offset as f32))
+                    .clamp(self.min_zoom, self.max_zoom);
+
+                // keep the canvas point under the mouse fixed while zooming
+                state.pan = mouse - self.bounds.top_left - anchor * state.zoom;
+                None
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if inside && state.interaction == Interaction::Idle => {
+                self.begin_interaction(&mut state, input.mouse_position)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button3,
+                Action::Press,
+                _,
+            ) if inside && state.interaction == Interaction::Idle => {
+                state.interaction = Interaction::Panning {
+                    last_position: input.mouse_position,
+                };
+                None
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1 | MouseButton::Button3,
+                Action::Release,
+                _,
+            ) => self.end_interaction(&mut state, input.mouse_position),
+            _ => None,
+        };
+
+        *internal_state.get_state_mut::<NodeEditorState>(&self.id) = state;
+        Ok(message_opt)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = internal_state
+            .get_state::<NodeEditorState>(&self.id)
+            .clone();
+
+        Tile {
+            model: self.bounds,
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        frame.push_transform(self.canvas_transform(&state));
+
+        let wires: Vec<Wire> = self
+            .connections
+            .iter()
+            .filter_map(|connection| {
+                Some(Wire {
+                    output: self.port_anchor(
+                        connection.output,
+                        PortDirection::Output,
+                    )?,
+                    input: self
+                        .port_anchor(connection.input, PortDirection::Input)?,
+                    width: self.wire_width,
+                    color: self.wire_color(&state, connection),
+                })
+            })
+            .collect();
+        if wires.len() >= PARALLEL_WIRE_COUNT {
+            let chunks =
+                tessellate_in_parallel(&wires, |wire, chunk| wire.draw(chunk))?;
+            for chunk in &chunks {
+                chunk.draw(frame)?;
+            }
+        } else {
+            for wire in &wires {
+                wire.draw(frame)?;
+            }
+        }
+
+        for node in &self.nodes {
+            node.draw_frame(internal_state, frame)?;
+        }
+
+        if let Interaction::DraggingWire { anchor, direction } =
+            state.interaction
+        {
+            if let Some(anchor_position) = self.port_anchor(anchor, direction) {
+                let (output, input) = match direction {
+                    PortDirection::Output => {
+                        (anchor_position, state.canvas_mouse)
+                    }
+                    PortDirection::Input => {
+                        (state.canvas_mouse, anchor_position)
+                    }
+                };
+                Wire {
+                    output,
+                    input,
+                    width: self.wire_width,
+                    color: self.hover_color,
+                }
+                .draw(frame)?;
+            }
+        }
+
+        frame.pop_transform();
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let positions: Vec<Vec2> = {
+            let state = internal_state.get_state::<NodeEditorState>(&self.id);
+            self.nodes
+                .iter()
+                .map(|node| {
+                    state
+                        .node_positions
+                        .get(&node.id)
+                        .copied()
+                        .unwrap_or(node.position)
+                })
+                .collect()
+        };
+        for (node, position) in self.nodes.iter_mut().zip(positions) {
+            node.layout(internal_state, position, max_size);
+        }
+
+        self.bounds = max_size.as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        // Nodes are positioned in canvas space, so only the editor's bounds
+        // need to move.
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        let (canvas_input, canvas_event) =
+            self.to_canvas_event(internal_state, input, event);
+        for node in self.nodes.iter_mut().rev() {
+            let result = node.handle_overlay_event(
+                internal_state,
+                &canvas_input,
+                &canvas_event,
+            )?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = internal_state.get_state::<NodeEditorState>(&self.id);
+        frame.push_transform(self.canvas_transform(state));
+        for node in &self.nodes {
+            node.draw_overlay(internal_state, frame)?;
+        }
+        frame.pop_transform();
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for NodeEditor<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,346 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{
+            node_editor::{Port, PortDirection},
+            Element, Label, OverlayEvent, Widget,
+        },
+        Font, Id, Input, InternalState,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A Node is a single box in the node editor. Nodes have a title bar which
+/// can be used to drag the node around the canvas, a list of input ports on
+/// the left, a list of output ports on the right, and optional widget content
+/// below the ports.
+pub struct Node<Message> {
+    /// The ID uniquely identifies this node in the editor. Connections refer
+    /// to nodes by ID.
+    pub(super) id: Id,
+
+    /// The node's position in canvas space. Once the user drags the node the
+    /// editor remembers the dragged position instead.
+    pub(super) position: Vec2,
+
+    /// The ports which accept connections.
+    pub(super) inputs: Vec<Port>,
+
+    /// The ports which connections start from.
+    pub(super) outputs: Vec<Port>,
+
+    /// The space occupied by the whole node in canvas space.
+    pub(super) bounds: Rect,
+
+    /// The space occupied by the title bar in canvas space.
+    pub(super) header: Rect,
+
+    /// The node's title, rendered in the header.
+    title: Label,
+
+    /// Widget content rendered below the ports.
+    content: Option<Element<Message>>,
+
+    /// The font's line height, used to space the node's contents.
+    line_height: f32,
+
+    /// The color of the node's body.
+    color: Vec4,
+
+    /// The color of the node's title bar.
+    header_color: Vec4,
+}
+
+impl<Message> Node<Message> {
+    pub fn new<T>(id: Id, font: &Font, title: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            id,
+            position: vec2(0.0, 0.0),
+            inputs: vec![],
+            outputs: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            header: Rect::new(0.0, 0.0, 0.0, 0.0),
+            title: Label::new(font, title),
+            content: None,
+            line_height: font.line_height(),
+            color: vec4(0.15, 0.15, 0.15, 1.0),
+            header_color: vec4(0.3, 0.3, 0.4, 1.0),
+        }
+    }
+
+    builder_field!(position, Vec2);
+    builder_field!(color, Vec4);
+    builder_field!(header_color, Vec4);
+
+    /// Add an input port to the node.
+    pub fn input(mut self, port: Port) -> Self {
+        self.inputs.push(port);
+        self
+    }
+
+    /// Add an output port to the node.
+    pub fn output(mut self, port: Port) -> Self {
+        self.outputs.push(port);
+        self
+    }
+
+    /// Set the widget rendered inside the node, below its ports.
+    pub fn content<W>(self, content: W) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        Self {
+            content: Some(content.into()),
+            ..self
+        }
+    }
+
+    /// The half-width of the square drawn for each port.
+    pub(super) fn port_radius(&self) -> f32 {
+        0.25 * self.line_height
+    }
+
+    /// Get the port which contains the given canvas-space point.
+    /// The grab area is twice the size of the drawn port to make the ports
+    /// easier to hit.
+    pub(super) fn port_at(
+        &self,
+        point: Vec2,
+    ) -> Option<(PortDirection, usize)> {
+        let grab_size = 4.0 * self.port_radius();
+        let hit = |port: &Port| {
+            Rect::centered_at(
+                port.anchor.x,
+                port.anchor.y,
+                grab_size,
+                grab_size,
+            )
+            .contains(point)
+        };
+        if let Some(index) = self.inputs.iter().position(hit) {
+            return Some((PortDirection::Input, index));
+        }
+        self.outputs
+            .iter()
+            .position(hit)
+            .map(|index| (PortDirection::Output, index))
+    }
+
+    /// Get the port with the given direction and index.
+    pub(super) fn port(
+        &self,
+        direction: PortDirection,
+        index: usize,
+    ) -> Option<&Port> {
+        match direction {
+            PortDirection::Input => self.inputs.get(index),
+            PortDirection::Output => self.outputs.get(index),
+        }
+    }
+
+    /// Forward an event to the node's content. The input and event are
+    /// already in canvas space.
+    pub(super) fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        match &mut self.content {
+            Some(content) => content.handle_event(internal_state, input, event),
+            None => Ok(None),
+        }
+    }
+
+    /// Forward an overlay event to the node's content. The input and event
+    /// are already in canvas space.
+    pub(super) fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        match &mut self.content {
+            Some(content) => {
+                content.handle_overlay_event(internal_state, input, event)
+            }
+            None => Ok(OverlayEvent::Ignored),
+        }
+    }
+
+    /// Render the overlay for the node's content. The editor is responsible
+    /// for transforming the frame into canvas space.
+    pub(super) fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        match &self.content {
+            Some(content) => content.draw_overlay(internal_state, frame),
+            None => Ok(()),
+        }
+    }
+
+    /// Compute the node's size and position all of its contents with the top
+    /// left corner at the given canvas-space position.
+    pub(super) fn layout(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+        max_content_size: &Dimensions,
+    ) {
+        let padding = 0.25 * self.line_height;
+        let row_height = self.line_height + padding;
+
+        let title_dimensions = Widget::<Message>::dimensions(
+            &mut self.title,
+            internal_state,
+            max_content_size,
+        );
+        let max_input_width = Self::max_label_width(
+            &mut self.inputs,
+            internal_state,
+            max_content_size,
+        );
+        let max_output_width = Self::max_label_width(
+            &mut self.outputs,
+            internal_state,
+            max_content_size,
+        );
+        let content_dimensions = match &mut self.content {
+            Some(content) => {
+                content.dimensions(internal_state, max_content_size)
+            }
+            None => Dimensions::new(0.0, 0.0),
+        };
+
+        let width = (title_dimensions.width + 2.0 * padding)
+            .max(max_input_width + max_output_width + 6.0 * padding)
+            .max(content_dimensions.width + 2.0 * padding);
+        let header_height =
+            title_dimensions.height.max(self.line_height) + 2.0 * padding;
+
+        self.header = Rect::new(
+            position.y,
+            position.x,
+            position.y + header_height,
+            position.x + width,
+        );
+        Widget::<Message>::set_top_left_position(
+            &mut self.title,
+            internal_state,
+            position + vec2(padding, padding),
+        );
+
+        let ports_top = self.header.bottom() + padding;
+        let label_offset = 2.0 * padding;
+        for (row, port) in self.inputs.iter_mut().enumerate() {
+            let row_top = ports_top + row as f32 * row_height;
+            port.anchor = vec2(position.x, row_top + 0.5 * self.line_height);
+            Widget::<Message>::set_top_left_position(
+                &mut port.label,
+                internal_state,
+                vec2(position.x + label_offset, row_top),
+            );
+        }
+        for (row, port) in self.outputs.iter_mut().enumerate() {
+            let row_top = ports_top + row as f32 * row_height;
+            let right = position.x + width;
+            let label_width = Widget::<Message>::dimensions(
+                &mut port.label,
+                internal_state,
+                max_content_size,
+            )
+            .width;
+            port.anchor = vec2(right, row_top + 0.5 * self.line_height);
+            Widget::<Message>::set_top_left_position(
+                &mut port.label,
+                internal_state,
+                vec2(right - label_offset - label_width, row_top),
+            );
+        }
+
+        let rows = self.inputs.len().max(self.outputs.len());
+        let mut bottom = ports_top + rows as f32 * row_height;
+        if let Some(content) = &mut self.content {
+            content.set_top_left_position(
+                internal_state,
+                vec2(position.x + padding, bottom),
+            );
+            bottom += content_dimensions.height + padding;
+        }
+
+        self.bounds =
+            Rect::new(position.y, position.x, bottom, position.x + width);
+    }
+
+    /// Render the node. Coordinates are in canvas space, so the editor is
+    /// responsible for transforming the frame before calling this method.
+    pub(super) fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        Tile {
+            model: self.bounds,
+            color: self.color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Tile {
+            model: self.header,
+            color: self.header_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Widget::<Message>::draw_frame(&self.title, internal_state, frame)?;
+
+        let port_size = 2.0 * self.port_radius();
+        for port in self.inputs.iter().chain(self.outputs.iter()) {
+            Tile {
+                model: Rect::centered_at(
+                    port.anchor.x,
+                    port.anchor.y,
+                    port_size,
+                    port_size,
+                ),
+                color: port.color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+            Widget::<Message>::draw_frame(&port.label, internal_state, frame)?;
+        }
+
+        if let Some(content) = &self.content {
+            content.draw_frame(internal_state, frame)?;
+        }
+
+        Ok(())
+    }
+
+    /// Compute the width of the widest port label.
+    fn max_label_width(
+        ports: &mut [Port],
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> f32 {
+        ports
+            .iter_mut()
+            .map(|port| {
+                Widget::<Message>::dimensions(
+                    &mut port.label,
+                    internal_state,
+                    max_size,
+                )
+                .width
+            })
+            .fold(0.0, f32::max)
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{
+    builder_field,
+    ui::{widgets::Label, Font, Id},
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// The type of data that flows through a port. Ports can only be connected
+/// when their types match.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PortType(pub &'static str);
+
+/// Data flows out of output ports and into input ports.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum PortDirection {
+    Input,
+    Output,
+}
+
+/// Identifies a single port on a single node in the editor.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PortRef {
+    /// The id of the node which owns the port.
+    pub node: Id,
+
+    /// The index of the port in the node's list of inputs or outputs.
+    pub port: usize,
+}
+
+/// A connection always runs from one node's output to another node's input.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Connection {
+    pub output: PortRef,
+    pub input: PortRef,
+}
+
+/// A named, typed, connection point on the side of a node.
+pub struct Port {
+    /// The port's name, rendered inside the node beside the port.
+    pub(super) label: Label,
+
+    /// The port's data type.
+    pub(super) port_type: PortType,
+
+    /// The color of the port and of any wires leaving it.
+    pub(super) color: Vec4,
+
+    /// The center of the port in canvas space. This is computed when the
+    /// editor is laid out.
+    pub(super) anchor: Vec2,
+}
+
+impl Port {
+    /// Create a new port with the given name and type.
+    pub fn new<T>(font: &Font, name: T, port_type: PortType) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            label: Label::new(font, name),
+            port_type,
+            color: vec4(0.8, 0.8, 0.8, 1.0),
+            anchor: vec2(0.0, 0.0),
+        }
+    }
+
+    builder_field!(color, Vec4);
+}
@@ -0,0 +1,103 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::VertexStream,
+    ui::{frame_arena::TempVec, primitives::Line},
+    vec2, Vec2, Vec4,
+};
+
+/// The number of line segments used to approximate each wire.
+const WIRE_SEGMENTS: usize = 24;
+
+/// The smallest horizontal distance between a wire's end and its bezier
+/// control point. This keeps short wires from looking like straight lines.
+const MIN_HANDLE_LENGTH: f32 = 32.0;
+
+/// A wire which is ready to be drawn. Wires only hold plain data so they can
+/// be tessellated on any thread.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Wire {
+    pub(super) output: Vec2,
+    pub(super) input: Vec2,
+    pub(super) width: f32,
+    pub(super) color: Vec4,
+}
+
+impl Wire {
+    pub(super) fn draw(&self, vertices: &mut impl VertexStream) -> Result<()> {
+        Line {
+            width: self.width,
+            color: self.color,
+            ..Default::default()
+        }
+        .draw_polyline(&wire_points(self.output, self.input), vertices)
+    }
+}
+
+/// Build the polyline for a wire from an output port to an input port.
+///
+/// Wires are cubic bezier curves which leave the output heading right and
+/// arrive at the input heading right.
+pub(super) fn wire_points(output: Vec2, input: Vec2) -> TempVec<Vec2> {
+    let handle = (0.5 * (input.x - output.x).abs()).max(MIN_HANDLE_LENGTH);
+    let control_a = output + vec2(handle, 0.0);
+    let control_b = input - vec2(handle, 0.0);
+
+    (0..=WIRE_SEGMENTS)
+        .map(|i| {
+            let t = i as f32 / WIRE_SEGMENTS as f32;
+            let inv = 1.0 - t;
+            inv * inv * inv * output
+                + 3.0 * inv * inv * t * control_a
+                + 3.0 * inv * t * t * control_b
+                + t * t * t * input
+        })
+        .collect()
+}
+
+/// The shortest distance between a point and any segment of a polyline.
+pub(super) fn distance_to_polyline(point: Vec2, polyline: &[Vec2]) -> f32 {
+    polyline
+        .windows(2)
+        .map(|segment| distance_to_segment(point, segment[0], segment[1]))
+        .fold(f32::INFINITY, f32::min)
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let length_squared = segment.norm_squared();
+    if length_squared <= f32::EPSILON {
+        return (point - start).norm();
+    }
+    let t = ((point - start).dot(&segment) / length_squared).clamp(0.0, 1.0);
+    (point - (start + t * segment)).norm()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_wire_points_end_at_ports() {
+        let output = vec2(10.0, 20.0);
+        let input = vec2(200.0, -40.0);
+        let points = wire_points(output, input);
+
+        assert_eq!(points.first(), Some(&output));
+        assert!((points.last().unwrap() - input).norm() < 1e-3);
+    }
+
+    #[test]
+    fn test_distance_to_polyline() {
+        let polyline = [vec2(0.0, 0.0), vec2(10.0, 0.0), vec2(10.0, 10.0)];
+
+        assert_eq!(distance_to_polyline(vec2(5.0, 3.0), &polyline), 3.0);
+        assert_eq!(distance_to_polyline(vec2(12.0, 5.0), &polyline), 2.0);
+        assert_eq!(distance_to_polyline(vec2(-4.0, 3.0), &polyline), 5.0);
+    }
+
+    #[test]
+    fn test_distance_to_empty_polyline() {
+        assert_eq!(distance_to_polyline(vec2(0.0, 0.0), &[]), f32::INFINITY);
+    }
+}
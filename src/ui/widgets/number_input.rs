@@ -0,0 +1,579 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{
+            text_input::text_edit::{edit_text, text_before_cursor, TextEdit},
+            Element, Label, Widget,
+        },
+        Font, Id, Input, InternalState, UISound,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// The parts of a NumberInput which respond to the mouse.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Part {
+    Decrement,
+    Value,
+    Increment,
+}
+
+/// A drag which started on the value.
+#[derive(Debug, Copy, Clone)]
+struct Drag {
+    /// The cursor's x position when the drag started.
+    start_x: f32,
+
+    /// The value when the drag started.
+    start_value: f32,
+
+    /// True once the cursor has moved far enough to count as a drag rather
+    /// than a click.
+    moved: bool,
+}
+
+/// The values a NumberInput can take.
+#[derive(Debug, Copy, Clone)]
+struct NumberRange {
+    min: f32,
+    max: f32,
+
+    /// How much each button press, or drag step, changes the value.
+    step: f32,
+
+    /// When true, values are rounded to whole numbers.
+    integer: bool,
+}
+
+impl NumberRange {
+    /// Clamp the value to the range, rounding it in integer mode.
+    fn constrain(&self, value: f32) -> f32 {
+        let value = if self.integer { value.round() } else { value };
+        value.clamp(self.min, self.max)
+    }
+
+    /// The step actually used to change the value. Integer steps are never
+    /// less than 1.
+    fn step(&self) -> f32 {
+        if self.integer {
+            self.step.round().max(1.0)
+        } else {
+            self.step
+        }
+    }
+
+    /// Move the value by a whole number of steps, staying in the range.
+    fn offset(&self, value: f32, steps: f32) -> f32 {
+        self.constrain(value + steps.trunc() * self.step())
+    }
+}
+
+/// A NumberInput's state is stored in the UI InternalState so drags and
+/// typed text persist between views.
+#[derive(Debug, Clone, Default)]
+struct NumberInputState {
+    hovered: Option<Part>,
+    pressed: Option<Part>,
+    drag: Option<Drag>,
+
+    /// Some while a value is being typed, with the cursor's character
+    /// index and whether the last submitted text was rejected.
+    text_entry: Option<(String, usize, bool)>,
+}
+
+/// A NumberInput adjusts a number between a min and a max in fixed steps.
+///
+/// # Controls
+///
+/// - Click the - and + buttons to step the value down or up.
+/// - Drag the value left or right to step it continuously.
+/// - Click the value without dragging to type an exact value when text entry
+///   is enabled. Enter commits, Escape or clicking elsewhere cancels.
+///
+/// In integer mode every value is rounded to a whole number.
+pub struct NumberInput<Message> {
+    id: Id,
+    font: Font,
+    range: NumberRange,
+    value: f32,
+
+    /// The number of decimal places shown for non-integer values.
+    precision: usize,
+
+    /// How far the cursor moves to drag the value by one step.
+    pixels_per_step: f32,
+
+    /// When true, clicking the value allows typing an exact value.
+    text_entry: bool,
+
+    /// The background color behind the value.
+    background_color: Option<Vec4>,
+
+    /// The color of the buttons.
+    button_color: Option<Vec4>,
+
+    /// The color of a part while hovered.
+    hover_color: Option<Vec4>,
+
+    bounds: Rect,
+    decrement: Rect,
+    value_area: Rect,
+    increment: Rect,
+    value_label: Label,
+    decrement_label: Label,
+    increment_label: Label,
+
+    on_change: Option<Box<dyn Fn(f32) -> Message>>,
+}
+
+impl<Message> NumberInput<Message> {
+    pub fn new(id: Id, font: &Font, min: f32, max: f32) -> Self {
+        Self {
+            id,
+            font: font.clone(),
+            range: NumberRange {
+                min: min.min(max),
+                max: min.max(max),
+                step: 1.0,
+                integer: false,
+            },
+            value: min.min(max),
+            precision: 2,
+            pixels_per_step: 4.0,
+            text_entry: true,
+            background_color: None,
+            button_color: None,
+            hover_color: None,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            decrement: Rect::new(0.0, 0.0, 0.0, 0.0),
+            value_area: Rect::new(0.0, 0.0, 0.0, 0.0),
+            increment: Rect::new(0.0, 0.0, 0.0, 0.0),
+            value_label: Label::new(font, ""),
+            decrement_label: Label::new(font, "-"),
+            increment_label: Label::new(font, "+"),
+            on_change: None,
+        }
+    }
+
+    builder_field!(precision, usize);
+    builder_field!(pixels_per_step, f32);
+    builder_field!(text_entry, bool);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(button_color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+
+    /// Set how much each button press, or drag step, changes the value.
+    pub fn step(self, step: f32) -> Self {
+        let range = NumberRange { step, ..self.range };
+        Self { range, ..self }
+    }
+
+    /// Round every value to a whole number. The step is rounded too, but is
+    /// never less than 1.
+    pub fn integer(self, integer: bool) -> Self {
+        let range = NumberRange {
+            integer,
+            ..self.range
+        };
+        let value = range.constrain(self.value);
+        Self {
+            range,
+            value,
+            ..self
+        }
+    }
+
+    /// Set the input's value. Clamped to the min/max values.
+    pub fn value(self, value: f32) -> Self {
+        let value = self.range.constrain(value);
+        Self { value, ..self }
+    }
+
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(f32) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    /// The value as it's displayed.
+    fn format_value(&self, value: f32) -> String {
+        if self.range.integer {
+            format!("{}", value as i64)
+        } else {
+            format!("{:.*}", self.precision, value)
+        }
+    }
+
+    /// Parse typed text as a value. None if it isn't a number in the range.
+    fn parse_value(&self, text: &str) -> Option<f32> {
+        let value = text.trim().parse::<f32>().ok()?;
+        if value >= self.range.min && value <= self.range.max {
+            Some(self.range.constrain(value))
+        } else {
+            None
+        }
+    }
+
+    /// The part of the input under the point, if any.
+    fn part_at(&self, point: Vec2) -> Option<Part> {
+        if self.decrement.contains(point) {
+            Some(Part::Decrement)
+        } else if self.increment.contains(point) {
+            Some(Part::Increment)
+        } else if self.value_area.contains(point) {
+            Some(Part::Value)
+        } else {
+            None
+        }
+    }
+
+    /// A message for the new value, unless the value didn't change.
+    fn change_to(&self, value: f32) -> Option<Message> {
+        if value == self.value {
+            return None;
+        }
+        self.on_change.as_ref().map(|on_change| on_change(value))
+    }
+
+    /// Apply an event to the typed text. Returns the message for a
+    /// committed value, if any.
+    fn handle_text_entry_event(
+        &self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Option<Message> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<NumberInputState>(&self.id);
+        if let WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            _,
+        ) = *event
+        {
+            if !self.value_area.contains(input.mouse_position) {
+                state.text_entry = None;
+            }
+            return None;
+        }
+
+        let (text, cursor, invalid) = state.text_entry.as_mut()?;
+        match edit_text(text, cursor, event) {
+            TextEdit::Changed(edited) => {
+                *text = edited;
+                *invalid = false;
+                None
+            }
+            TextEdit::Submit => match self.parse_value(text) {
+                Some(value) => {
+                    state.text_entry = None;
+                    self.change_to(value)
+                }
+                None => {
+                    *invalid = true;
+                    internal_state.play_sound(UISound::Error);
+                    None
+                }
+            },
+            TextEdit::Cancel => {
+                state.text_entry = None;
+                None
+            }
+            TextEdit::Moved | TextEdit::Ignored => None,
+        }
+    }
+}
+
+impl<Message> Widget<Message> for NumberInput<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let is_editing = internal_state
+            .get_state::<NumberInputState>(&self.id)
+            .text_entry
+            .is_some();
+        if is_editing {
+            return Ok(self.handle_text_entry_event(
+                internal_state,
+                input,
+                event,
+            ));
+        }
+
+        let part = self.part_at(input.mouse_position);
+        let state = internal_state.get_state_mut::<NumberInputState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                let newly_hovered = part.is_some() && part != state.hovered;
+                state.hovered = part;
+                let drag = state.drag.as_mut().map(|drag| {
+                    let dragged_far_enough =
+                        (input.mouse_position.x - drag.start_x).abs()
+                            >= self.pixels_per_step;
+                    drag.moved = drag.moved || dragged_far_enough;
+                    *drag
+                });
+                if newly_hovered && part != Some(Part::Value) {
+                    internal_state.play_sound(UISound::Hover);
+                }
+                Ok(drag.and_then(|drag| {
+                    let pixels = input.mouse_position.x - drag.start_x;
+                    self.change_to(self.range.offset(
+                        drag.start_value,
+                        pixels / self.pixels_per_step,
+                    ))
+                }))
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                state.pressed = part;
+                if part == Some(Part::Value) {
+                    state.drag = Some(Drag {
+                        start_x: input.mouse_position.x,
+                        start_value: self.value,
+                        moved: false,
+                    });
+                }
+                Ok(None)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                let pressed = state.pressed.take();
+                let drag = state.drag.take();
+                if pressed != part {
+                    // the press and release weren't on the same part
+                    return Ok(None);
+                }
+                match part {
+                    Some(Part::Decrement) => {
+                        internal_state.play_sound(UISound::Click);
+                        Ok(self.change_to(self.range.offset(self.value, -1.0)))
+                    }
+                    Some(Part::Increment) => {
+                        internal_state.play_sound(UISound::Click);
+                        Ok(self.change_to(self.range.offset(self.value, 1.0)))
+                    }
+                    Some(Part::Value) => {
+                        let clicked = drag.map(|drag| !drag.moved);
+                        if self.text_entry && clicked == Some(true) {
+                            let text = self.format_value(self.value);
+                            state.text_entry = Some((
+                                text.clone(),
+                                text.chars().count(),
+                                false,
+                            ));
+                        }
+                        Ok(None)
+                    }
+                    None => Ok(None),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = internal_state
+            .get_state::<NumberInputState>(&self.id)
+            .clone();
+
+        let value_color = match &state.text_entry {
+            Some((_, _, true)) => vec4(0.5, 0.0, 0.0, 0.5),
+            _ => self.background_color.unwrap_or(theme.field_color),
+        };
+        Tile {
+            model: self.value_area,
+            color: value_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        for (part, model) in &[
+            (Part::Decrement, self.decrement),
+            (Part::Increment, self.increment),
+        ] {
+            let color = if state.hovered == Some(*part) {
+                self.hover_color.unwrap_or(theme.hover_color)
+            } else {
+                self.button_color.unwrap_or(theme.widget_color)
+            };
+            Tile {
+                model: *model,
+                color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        Widget::<Message>::draw_frame(
+            &self.decrement_label,
+            internal_state,
+            frame,
+        )?;
+        Widget::<Message>::draw_frame(
+            &self.increment_label,
+            internal_state,
+            frame,
+        )?;
+
+        frame.push_clip_rect(self.value_area);
+        Widget::<Message>::draw_frame(
+            &self.value_label,
+            internal_state,
+            frame,
+        )?;
+        if let Some((text, cursor, _)) = &state.text_entry {
+            let (_, before) = self
+                .font
+                .build_text_tiles(text_before_cursor(text, *cursor));
+            let (_, all) = self.font.build_text_tiles(text);
+            let left = self.value_area.left()
+                + 0.5 * (self.value_area.width() - all.width());
+            let x = (left + before.width()).round();
+            Line {
+                start: vec2(x, self.value_area.top()),
+                end: vec2(x, self.value_area.bottom()),
+                color: vec4(1.0, 1.0, 1.0, 0.9),
+                ..Default::default()
+            }
+            .draw(frame)?;
+        }
+        frame.pop_clip_rect();
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let text = match &internal_state
+            .get_state::<NumberInputState>(&self.id)
+            .text_entry
+        {
+            Some((text, _, _)) => text.clone(),
+            None => self.format_value(self.value),
+        };
+        self.value_label = Label::new(&self.font, text);
+
+        let unbounded = Dimensions::new(f32::MAX, f32::MAX);
+        for label in &mut [
+            &mut self.value_label,
+            &mut self.decrement_label,
+            &mut self.increment_label,
+        ] {
+            Widget::<Message>::dimensions(*label, internal_state, &unbounded);
+        }
+
+        // the buttons are squares at either end
+        let height =
+            (1.5 * self.font.line_height()).round().min(max_size.height);
+        self.bounds = Dimensions::new(max_size.width, height).as_rect();
+        self.decrement = Dimensions::new(height, height).as_rect();
+        self.increment = self.decrement;
+        self.value_area =
+            Dimensions::new((max_size.width - 2.0 * height).max(0.0), height)
+                .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.decrement = self.decrement.set_top_left_position(position);
+        self.value_area = self.value_area.set_top_left_position(
+            position + vec2(self.decrement.width(), 0.0),
+        );
+        self.increment = self
+            .increment
+            .set_top_left_position(vec2(self.value_area.right(), position.y));
+
+        // every label is centered in its part
+        let text_height = self.font.line_height();
+        let parts = [
+            (&mut self.decrement_label, self.decrement),
+            (&mut self.value_label, self.value_area),
+            (&mut self.increment_label, self.increment),
+        ];
+        for (label, area) in parts {
+            let size = Widget::<Message>::dimensions(
+                label,
+                internal_state,
+                &Dimensions::new(f32::MAX, f32::MAX),
+            );
+            let offset = vec2(
+                0.5 * (area.width() - size.width),
+                0.5 * (area.height() - text_height),
+            );
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                (area.top_left + offset).map(f32::round),
+            );
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for NumberInput<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_offset_moves_whole_steps_within_the_range() {
+        let float = NumberRange {
+            min: 0.0,
+            max: 1.0,
+            step: 0.1,
+            integer: false,
+        };
+        assert!((float.offset(0.5, 2.5) - 0.7).abs() < 1e-5);
+        assert!((float.offset(0.5, -2.5) - 0.3).abs() < 1e-5);
+        assert_eq!(float.offset(0.5, 100.0), 1.0);
+
+        let integer = NumberRange {
+            min: -10.0,
+            max: 10.0,
+            step: 0.4,
+            integer: true,
+        };
+        assert_eq!(integer.constrain(2.6), 3.0);
+        assert_eq!(integer.offset(3.0, -2.0), 1.0);
+        assert_eq!(integer.offset(3.0, -50.0), -10.0);
+    }
+}
@@ -1,15 +1,34 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::{Frame, FrameStats},
+    timing::FrameRateLimit,
+    ui::primitives::Rect,
+};
+
 pub use crate::{
+    asset_loader::TextureId,
     gen_id,
+    math::Curve,
     ui::{
         id::id_hash,
         primitives::{Axis, Justify, SpaceBetween},
         widgets::{
-            Align, Button, Col, Constraint, Container, Element, HAlignment,
-            HSplit, Label, Row, Slider, VAlignment, Widget, Window,
-            WithContainer,
+            Align, Button, Canvas, Checkbox, Col, Connection, Constraint,
+            Container, ContextMenu, CurveEditor, Diagnostics, Dropdown,
+            Element, Eyedropper, HAlignment, HSplit, Icon, Image, ImageFit,
+            Knob, KnobDrag, Label, LabelFit, Layer, ListView, MarkdownView,
+            Menu, MenuBar, Modal, Node, NodeEditor, NumberInput, Port, PortRef,
+            PortType, ProgressBar, RangeSlider, Row, Rulers, Scope, ScrollView,
+            Scrollbar, Signal, Slider, SortOrder, Spacer, Stack, Table,
+            TextArea, TextInput, TextureInspector, TimelineEdit,
+            TimelineEditor, Toggle, Tooltip, Track, TreeNode, TreeView,
+            VAlignment, VSplit, Widget, Window, WithContainer, WithContextMenu,
+            WithTooltip, Wrap,
         },
-        Font, Id,
+        CursorShape, Font, HoldRepeat, Id,
     },
+    Mat4,
 };
 
 /// Wrap a widget with an align widget.
@@ -45,6 +64,97 @@ where
     )
 }
 
+/// Create a button which shows a named icon from the font.
+pub fn icon_button<Message>(font: &Font, name: &str) -> Button<Message>
+where
+    Message: 'static,
+{
+    let id = gen_id!(name);
+    Button::new(
+        id,
+        icon(font, name)
+            .container()
+            .padding(font.line_height() * 0.25),
+    )
+}
+
+/// Create an image which shows a texture that's the given size in pixels.
+pub fn image<Message>(
+    texture_index: TextureId,
+    width: f32,
+    height: f32,
+) -> Image<Message> {
+    Image::new(texture_index, width, height)
+}
+
+/// Create a checkbox with a text label.
+pub fn checkbox<Message, T>(id: Id, font: &Font, text: T) -> Checkbox<Message>
+where
+    T: AsRef<str>,
+{
+    Checkbox::new(id, font, text)
+}
+
+/// Create an animated on/off switch with a text label.
+pub fn toggle<Message, T>(id: Id, font: &Font, text: T) -> Toggle<Message>
+where
+    T: AsRef<str>,
+{
+    Toggle::new(id, font, text)
+}
+
+/// Create an editor for the given curve.
+pub fn curve_editor<Message>(id: Id, curve: Curve) -> CurveEditor<Message> {
+    CurveEditor::new(id, curve)
+}
+
+/// Create a canvas which fills the available space and draws with the given
+/// closure.
+pub fn canvas<Message, F>(draw_fn: F) -> Canvas<Message>
+where
+    F: 'static + Fn(Rect, &mut Frame) -> Result<()>,
+{
+    Canvas::new(draw_fn)
+}
+
+/// Create a diagnostics panel for the frame rate limit's recent frames and
+/// the given geometry counts.
+pub fn diagnostics(
+    font: &Font,
+    fps_limit: &FrameRateLimit,
+    stats: FrameStats,
+) -> Diagnostics {
+    Diagnostics::new(font, fps_limit, stats)
+}
+
+/// Create a dropdown with a text option for each item.
+pub fn dropdown<Message, T, I>(
+    id: Id,
+    font: &Font,
+    options: I,
+) -> Dropdown<Message>
+where
+    T: AsRef<str>,
+    I: IntoIterator<Item = T>,
+{
+    Dropdown::new(id, font, options)
+}
+
+/// Create an eyedropper without a color.
+pub fn eyedropper<Message>(id: Id, font: &Font) -> Eyedropper<Message> {
+    Eyedropper::new(id, font)
+}
+
+/// Create an icon from one of the font's named glyphs.
+pub fn icon(font: &Font, name: &str) -> Icon {
+    Icon::new(font, name)
+}
+
+/// Create a knob which picks a value between min and max.
+pub fn knob<Message>(id: Id, min: f32, max: f32) -> Knob<Message> {
+    Knob::new(id, min, max)
+}
+
 /// Create a text label.
 pub fn label<T>(font: &Font, text: T) -> Label
 where
@@ -53,6 +163,38 @@ where
     Label::new(font, text)
 }
 
+/// Create a text label which is `size` pixels tall.
+pub fn sized_label<T>(font: &Font, text: T, size: f32) -> Label
+where
+    T: AsRef<str>,
+{
+    Label::sized(font, text, size)
+}
+
+/// Create a text label which is scaled to fit the space it's given.
+pub fn fitted_label<T>(font: &Font, text: T, fit: LabelFit) -> Label
+where
+    T: Into<String>,
+{
+    Label::fitted(font, text, fit)
+}
+
+/// Create a text label which can be selected and copied.
+pub fn selectable_label<T>(id: Id, font: &Font, text: T) -> Label
+where
+    T: Into<String>,
+{
+    Label::selectable(id, font, text)
+}
+
+/// Create a view which renders markdown text.
+pub fn markdown_view<T>(font: &Font, text: T) -> MarkdownView
+where
+    T: AsRef<str>,
+{
+    MarkdownView::new(font, text)
+}
+
 /// Create a column of widgets.
 pub fn col<Message>() -> Col<Message> {
     Col::new()
@@ -63,10 +205,168 @@ pub fn row<Message>() -> Row<Message> {
     Row::new()
 }
 
-pub fn hsplit<Message>() -> HSplit<Message> {
-    HSplit::new()
+/// Create a stack of widgets which are drawn on top of each other.
+pub fn stack<Message>() -> Stack<Message> {
+    Stack::new()
+}
+
+/// Create a row of widgets which wraps onto new lines.
+pub fn wrap<Message>() -> Wrap<Message> {
+    Wrap::new()
+}
+
+/// Create rulers which label the world space shown by a view projection.
+pub fn rulers<Message>(
+    id: Id,
+    font: &Font,
+    view_projection: Mat4,
+) -> Rulers<Message> {
+    Rulers::new(id, font, view_projection)
+}
+
+/// Create empty space with a fixed size.
+pub fn spacer(width: f32, height: f32) -> Spacer {
+    Spacer::new(width, height)
+}
+
+/// Create a split with a left and right side.
+pub fn hsplit<Message>(id: Id) -> HSplit<Message> {
+    HSplit::new(id)
+}
+
+/// Create a split with a top and bottom.
+pub fn vsplit<Message>(id: Id) -> VSplit<Message> {
+    VSplit::new(id)
+}
+
+/// Create a layer of absolutely positioned widgets.
+pub fn layer<Message>() -> Layer<Message> {
+    Layer::new()
+}
+
+/// Create a menu bar with no menus.
+pub fn menu_bar<Message>(id: Id, font: &Font) -> MenuBar<Message> {
+    MenuBar::new(id, font)
+}
+
+/// Show a dialog on top of the given content.
+pub fn modal<Message, C, D>(content: C, dialog: D) -> Modal<Message>
+where
+    C: Into<Element<Message>>,
+    D: Into<Element<Message>>,
+{
+    Modal::new(content, dialog)
+}
+
+/// Create an empty node editor.
+pub fn node_editor<Message>(id: Id) -> NodeEditor<Message> {
+    NodeEditor::new(id)
+}
+
+/// Create a scope which plots the given signal.
+pub fn scope<Message>(id: Id, font: &Font, signal: &Signal) -> Scope<Message> {
+    Scope::new(id, font, signal)
+}
+
+/// Create a numeric input with -/+ buttons for values in the range
+/// [min, max].
+pub fn number_input<Message>(
+    id: Id,
+    font: &Font,
+    min: f32,
+    max: f32,
+) -> NumberInput<Message> {
+    NumberInput::new(id, font, min, max)
+}
+
+/// Create a scrolling list which only builds the rows that are visible.
+pub fn list_view<Message, F>(
+    id: Id,
+    item_count: usize,
+    row_height: f32,
+    build_row: F,
+) -> ListView<Message>
+where
+    F: Fn(usize) -> Element<Message> + 'static,
+{
+    ListView::new(id, item_count, row_height, build_row)
+}
+
+/// Create a progress bar which is `progress` complete, in the range [0, 1].
+pub fn progress_bar<Message>(progress: f32) -> ProgressBar<Message> {
+    ProgressBar::new(progress)
+}
+
+/// Wrap the given widget in a vertically scrolling view.
+pub fn scroll_view<Message, E>(id: Id, child: E) -> ScrollView<Message>
+where
+    E: Into<Element<Message>>,
+{
+    ScrollView::new(id, child)
+}
+
+/// Create a scrollbar along the axis which emits normalized positions.
+pub fn scrollbar<Message>(id: Id, axis: Axis) -> Scrollbar<Message> {
+    Scrollbar::new(id, axis)
 }
 
 pub fn slider<Message>(id: Id, min: f32, max: f32) -> Slider<Message> {
     Slider::new(id, min, max)
 }
+
+/// Create a slider with two handles which selects a low and a high value.
+pub fn range_slider<Message>(
+    id: Id,
+    min: f32,
+    max: f32,
+) -> RangeSlider<Message> {
+    RangeSlider::new(id, min, max)
+}
+
+/// Create a multiline text area which shows the given text.
+pub fn text_area<Message, T>(id: Id, font: &Font, value: T) -> TextArea<Message>
+where
+    T: Into<String>,
+{
+    TextArea::new(id, font, value)
+}
+
+/// Create a single line text input which shows the given text.
+pub fn text_input<Message, T>(
+    id: Id,
+    font: &Font,
+    value: T,
+) -> TextInput<Message>
+where
+    T: Into<String>,
+{
+    TextInput::new(id, font, value)
+}
+
+/// Create a table with no columns or rows.
+pub fn table<Message>(id: Id, font: &Font) -> Table<Message> {
+    Table::new(id, font)
+}
+
+/// Create a developer panel which lists the asset loader's textures.
+pub fn texture_inspector<Message>(
+    id: Id,
+    font: &Font,
+    asset_loader: &crate::asset_loader::AssetLoader,
+) -> TextureInspector<Message> {
+    TextureInspector::new(id, font, asset_loader)
+}
+
+/// Create a timeline editor with no tracks.
+pub fn timeline_editor<Message>(
+    id: Id,
+    font: &Font,
+    duration: f32,
+) -> TimelineEditor<Message> {
+    TimelineEditor::new(id, font, duration)
+}
+
+/// Create a tree view with no nodes.
+pub fn tree_view<Message>(id: Id, font: &Font) -> TreeView<Message> {
+    TreeView::new(id, font)
+}
@@ -0,0 +1,285 @@
+use ::{anyhow::Result, std::f32::consts::FRAC_PI_2};
+
+use crate::{
+    asset_loader::TextureId,
+    builder_field, builder_field_some,
+    immediate_mode_graphics::{triangles::Frame, Vertex, VertexStream},
+    ui::{
+        frame_arena::{temp_vec_with_capacity, TempVec},
+        primitives::{Dimensions, Rect},
+        widgets::{Element, Widget},
+        Input, InternalState, Theme,
+    },
+    vec2, vec3, Vec2, Vec4,
+};
+
+/// The number of segments used to approximate each rounded corner.
+const CORNER_SEGMENTS: u32 = 6;
+
+/// A ProgressBar shows how much of a task is complete. It fills all of the
+/// available width.
+///
+/// Determinate bars fill from the left based on their progress.
+/// Indeterminate bars are used when the amount of work isn't known, they
+/// animate a segment which sweeps across the bar.
+pub struct ProgressBar<Message> {
+    /// The progress in the range [0, 1], or None when the bar is
+    /// indeterminate.
+    progress: Option<f32>,
+
+    /// The space occupied by the bar on screen.
+    bounds: Rect,
+
+    /// The bar's height.
+    thickness: f32,
+
+    /// When true, the ends of the bar and the fill are fully rounded. None
+    /// uses the theme's rounded corners.
+    rounded: Option<bool>,
+
+    /// The fraction of the bar covered by the indeterminate segment.
+    segment_length: f32,
+
+    /// The number of times per second the indeterminate segment sweeps
+    /// across the bar.
+    sweeps_per_second: f32,
+
+    /// The color of the completed portion of the bar.
+    fill_color: Option<Vec4>,
+
+    /// The color of the bar behind the fill.
+    background_color: Option<Vec4>,
+
+    _phantom_data: std::marker::PhantomData<Message>,
+}
+
+impl<Message> ProgressBar<Message> {
+    /// Create a determinate progress bar. Progress is clamped to [0, 1].
+    pub fn new(progress: f32) -> Self {
+        Self {
+            progress: Some(progress.clamp(0.0, 1.0)),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            thickness: 12.0,
+            rounded: None,
+            segment_length: 0.3,
+            sweeps_per_second: 0.75,
+            fill_color: None,
+            background_color: None,
+            _phantom_data: Default::default(),
+        }
+    }
+
+    /// Create an indeterminate progress bar.
+    pub fn indeterminate() -> Self {
+        Self {
+            progress: None,
+            ..Self::new(0.0)
+        }
+    }
+
+    builder_field!(thickness, f32);
+    builder_field_some!(rounded, bool);
+    builder_field!(segment_length, f32);
+    builder_field!(sweeps_per_second, f32);
+    builder_field_some!(fill_color, Vec4);
+    builder_field_some!(background_color, Vec4);
+
+    /// The horizontal range covered by the fill, as fractions of the bar's
+    /// width.
+    fn fill_range(&self, internal_state: &InternalState) -> (f32, f32) {
+        match self.progress {
+            Some(progress) => (0.0, progress),
+            None => {
+                // the segment starts fully off the left side and ends fully
+                // off the right side, so it enters and leaves smoothly
+                let t = (internal_state.time().as_secs_f32()
+                    * self.sweeps_per_second)
+                    .fract();
+                let start =
+                    t * (1.0 + self.segment_length) - self.segment_length;
+                (
+                    start.clamp(0.0, 1.0),
+                    (start + self.segment_length).clamp(0.0, 1.0),
+                )
+            }
+        }
+    }
+
+    /// Fill a rect with rounded corners if the bar is rounded.
+    fn fill(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        rect: Rect,
+        color: Vec4,
+    ) -> Result<()> {
+        let radius = if self.rounded.unwrap_or(theme.rounded_corners) {
+            0.5 * rect.width().min(rect.height())
+        } else {
+            0.0
+        };
+        let outline = rounded_rect_outline(rect, radius, CORNER_SEGMENTS);
+
+        // the shape is convex so it can be drawn as a fan around its center
+        let center = vec2(
+            0.5 * (rect.left() + rect.right()),
+            0.5 * (rect.top() + rect.bottom()),
+        );
+        let vertex = |point: Vec2| {
+            Vertex::new(
+                vec3(point.x, point.y, 0.0),
+                color,
+                vec2(0.0, 0.0),
+                TextureId::WHITE_PIXEL,
+            )
+        };
+        let mut vertices = Vec::with_capacity(outline.len() + 1);
+        vertices.push(vertex(center));
+        vertices.extend(outline.iter().map(|&point| vertex(point)));
+
+        let count = outline.len() as u32;
+        let indices: Vec<u32> = (0..count)
+            .flat_map(|i| vec![0, i + 1, (i + 1) % count + 1])
+            .collect();
+        frame.push_vertices(&vertices, &indices)
+    }
+}
+
+impl<Message> Widget<Message> for ProgressBar<Message> {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        self.fill(
+            frame,
+            &theme,
+            self.bounds,
+            self.background_color.unwrap_or(theme.highlight_color),
+        )?;
+
+        let (start, end) = self.fill_range(internal_state);
+        if end - start <= f32::EPSILON {
+            return Ok(());
+        }
+        let width = self.bounds.width();
+        let fill = Rect::new(
+            self.bounds.top(),
+            (self.bounds.left() + start * width).round(),
+            self.bounds.bottom(),
+            (self.bounds.left() + end * width).round(),
+        );
+        self.fill(
+            frame,
+            &theme,
+            fill,
+            self.fill_color.unwrap_or(theme.accent_color),
+        )
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.bounds = Dimensions::new(max_size.width, self.thickness)
+            .min(max_size)
+            .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for ProgressBar<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Compute the points around the edge of a rect with rounded corners, in
+/// clockwise order starting from the top-left corner.
+fn rounded_rect_outline(
+    rect: Rect,
+    radius: f32,
+    segments: u32,
+) -> TempVec<Vec2> {
+    if radius <= 0.0 {
+        return [
+            vec2(rect.left(), rect.top()),
+            vec2(rect.right(), rect.top()),
+            vec2(rect.right(), rect.bottom()),
+            vec2(rect.left(), rect.bottom()),
+        ]
+        .iter()
+        .copied()
+        .collect();
+    }
+
+    // each corner's arc center and starting angle, with y pointing down
+    let corners = [
+        (
+            vec2(rect.left() + radius, rect.top() + radius),
+            2.0 * FRAC_PI_2,
+        ),
+        (
+            vec2(rect.right() - radius, rect.top() + radius),
+            3.0 * FRAC_PI_2,
+        ),
+        (vec2(rect.right() - radius, rect.bottom() - radius), 0.0),
+        (
+            vec2(rect.left() + radius, rect.bottom() - radius),
+            FRAC_PI_2,
+        ),
+    ];
+    let mut points =
+        temp_vec_with_capacity(corners.len() * (segments as usize + 1));
+    for &(center, start_angle) in &corners {
+        for i in 0..=segments {
+            let angle = start_angle + FRAC_PI_2 * (i as f32 / segments as f32);
+            points.push(center + radius * vec2(angle.cos(), angle.sin()));
+        }
+    }
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rounded_rect_outline_stays_inside_rect() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 120.0);
+        let points = rounded_rect_outline(rect, 10.0, 4);
+
+        assert_eq!(points.len(), 4 * 5);
+        for point in &points {
+            assert!(point.x >= rect.left() - 1e-4, "{:?}", point);
+            assert!(point.x <= rect.right() + 1e-4, "{:?}", point);
+            assert!(point.y >= rect.top() - 1e-4, "{:?}", point);
+            assert!(point.y <= rect.bottom() + 1e-4, "{:?}", point);
+        }
+
+        // a fully rounded end touches the middle of the left edge
+        assert!((points[0] - vec2(20.0, 20.0)).norm() < 1e-4);
+    }
+}
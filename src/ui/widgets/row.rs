@@ -3,8 +3,11 @@ use ::anyhow::Result;
 use crate::{
     immediate_mode_graphics::triangles::Frame,
     ui::{
-        primitives::{DimensionList, Dimensions, Justify, SpaceBetween},
-        widgets::{Element, Widget},
+        frame_arena::TempVec,
+        primitives::{
+            DimensionList, Dimensions, Justify, Sizing, SpaceBetween,
+        },
+        widgets::{Element, OverlayEvent, Spacer, Widget},
         Input, InternalState,
     },
     Vec2,
@@ -13,7 +16,7 @@ use crate::{
 /// A Row is a collection of wigets which is arranged in a single horizontal
 /// row.
 pub struct Row<Message> {
-    children: Vec<(Element<Message>, Justify)>,
+    children: Vec<(Element<Message>, Justify, Sizing)>,
     child_dimensions: DimensionList,
 }
 
@@ -39,9 +42,54 @@ impl<Message> Row<Message> {
     where
         W: Into<Element<Message>>,
     {
-        self.children.push((child.into(), justify));
+        self.children.push((child.into(), justify, Sizing::Natural));
         self
     }
+
+    /// Add a child element to the end of the row which grows to fill the
+    /// space left over by the other children. The space is split between
+    /// flex children in proportion to their weights.
+    pub fn flex_child<W>(
+        mut self,
+        child: W,
+        justify: Justify,
+        weight: f32,
+    ) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.children
+            .push((child.into(), justify, Sizing::Flex(weight)));
+        self
+    }
+
+    /// Add a child element to the end of the row which keeps its natural
+    /// size and grows by a share of the space left over once every child
+    /// has been measured. The space is split between grow and flex children
+    /// in proportion to their weights.
+    pub fn grow_child<W>(
+        mut self,
+        child: W,
+        justify: Justify,
+        weight: f32,
+    ) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.children
+            .push((child.into(), justify, Sizing::Grow(weight)));
+        self
+    }
+
+    /// Add a spacer to the end of the row which takes a share of the space
+    /// left over by the other children, pushing the children on either side
+    /// of it apart. The space is split in proportion to the weights.
+    pub fn spacer(self, weight: f32) -> Self
+    where
+        Message: 'static,
+    {
+        self.flex_child(Spacer::fill_horizontal(), Justify::Begin, weight)
+    }
 }
 
 impl<Message> Widget<Message> for Row<Message> {
@@ -51,7 +99,7 @@ impl<Message> Widget<Message> for Row<Message> {
         input: &Input,
         event: &glfw::WindowEvent,
     ) -> Result<Option<Message>> {
-        for (child, _) in &mut self.children {
+        for (child, _, _) in &mut self.children {
             if let Some(message) =
                 child.handle_event(internal_state, input, event)?
             {
@@ -66,7 +114,7 @@ impl<Message> Widget<Message> for Row<Message> {
         internal_state: &mut InternalState,
         frame: &mut Frame,
     ) -> Result<()> {
-        for (child, _) in &self.children {
+        for (child, _, _) in &self.children {
             child.draw_frame(internal_state, frame)?;
         }
         Ok(())
@@ -83,16 +131,16 @@ impl<Message> Widget<Message> for Row<Message> {
 
         self.child_dimensions.set_max_size(max_size);
 
-        let mut remaining_size = *max_size;
-
-        for (child, justify) in &mut self.children {
-            let child_bounds =
-                child.dimensions(internal_state, &remaining_size);
-
-            remaining_size = self
-                .child_dimensions
-                .add_child_dimensions(child_bounds, *justify);
-        }
+        let layout: TempVec<(Justify, Sizing)> = self
+            .children
+            .iter()
+            .map(|(_, justify, sizing)| (*justify, *sizing))
+            .collect();
+        let children = &mut self.children;
+        self.child_dimensions
+            .add_children(&layout, |index, child_max_size| {
+                children[index].0.dimensions(internal_state, child_max_size)
+            });
 
         self.child_dimensions.dimensions()
     }
@@ -103,12 +151,40 @@ impl<Message> Widget<Message> for Row<Message> {
         position: Vec2,
     ) {
         let positions = self.child_dimensions.compute_child_positions();
-        for ((child, _), child_pos) in
+        for ((child, _, _), child_pos) in
             self.children.iter_mut().zip(positions.iter())
         {
             child.set_top_left_position(internal_state, position + child_pos);
         }
     }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        // later children are drawn on top, so they see overlay events first
+        for (child, _, _) in self.children.iter_mut().rev() {
+            let result =
+                child.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for (child, _, _) in &self.children {
+            child.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
 }
 
 impl<Message> Into<Element<Message>> for Row<Message>
@@ -0,0 +1,512 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{timeline_editor::tick_step, Element, OverlayEvent, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, vec4, Mat4, Vec2, Vec4,
+};
+
+/// The minimum space between ruler ticks, in pixels.
+const MIN_TICK_SPACING: f32 = 64.0;
+
+/// A distance measured between two points in world space.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Measurement {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl Measurement {
+    /// The world space distance between the points.
+    pub fn distance(&self) -> f32 {
+        (self.end - self.start).norm()
+    }
+
+    /// The direction from start to end in degrees, counter-clockwise from
+    /// the positive X axis, in the range (-180, 180].
+    pub fn angle_degrees(&self) -> f32 {
+        let delta = self.end - self.start;
+        delta.y.atan2(delta.x).to_degrees()
+    }
+}
+
+/// The Rulers' measurement is stored in the UI InternalState so it stays on
+/// screen between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct RulersState {
+    /// The first clicked point, in world space.
+    start: Option<Vec2>,
+
+    /// The second clicked point, in world space. The measurement follows the
+    /// mouse until this is set.
+    end: Option<Vec2>,
+
+    /// The mouse position in screen space.
+    mouse_position: Vec2,
+}
+
+/// Rulers draw world space coordinates along the top and left edges of the
+/// screen, on top of the rest of the UI.
+///
+/// The rulers are given the app layer's view projection and assume it
+/// renders into the space the rulers fill, usually the whole window, with an
+/// axis aligned camera. Ticks land on round world space values so layouts
+/// can be lined up precisely.
+///
+/// # Controls
+///
+/// While measuring is enabled:
+///
+/// - Click to start a measurement.
+/// - Click again to finish it, the distance and angle are shown.
+/// - Right-click to clear the measurement.
+pub struct Rulers<Message> {
+    /// The ID uniquely identifies these rulers when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The font used for tick labels and measurements.
+    font: Font,
+
+    /// Maps world space to the Vulkan view volume.
+    view_projection: Mat4,
+
+    /// Maps the Vulkan view volume back to world space.
+    inverse_view_projection: Mat4,
+
+    /// The space the camera renders into.
+    bounds: Rect,
+
+    /// When true, clicks measure distances instead of reaching the UI.
+    measuring: bool,
+
+    /// The color behind the rulers. None uses the theme's panel color,
+    /// slightly transparent.
+    background_color: Option<Vec4>,
+
+    /// The color of ruler ticks.
+    tick_color: Option<Vec4>,
+
+    /// The color of the measurement line.
+    measure_color: Option<Vec4>,
+
+    /// The function used to build a message when a measurement is
+    /// finished.
+    on_measure: Option<Box<dyn Fn(Measurement) -> Message>>,
+}
+
+impl<Message> Rulers<Message> {
+    pub fn new(id: Id, font: &Font, view_projection: Mat4) -> Self {
+        Self {
+            id,
+            font: font.clone(),
+            view_projection,
+            inverse_view_projection: view_projection
+                .try_inverse()
+                .unwrap_or_else(Mat4::identity),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            measuring: false,
+            background_color: None,
+            tick_color: None,
+            measure_color: None,
+            on_measure: None,
+        }
+    }
+
+    builder_field!(measuring, bool);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(tick_color, Vec4);
+    builder_field_some!(measure_color, Vec4);
+
+    /// Set the function used to build a message when a measurement is
+    /// finished.
+    pub fn on_measure<F>(self, on_measure_fn: F) -> Self
+    where
+        F: 'static + Fn(Measurement) -> Message,
+    {
+        Self {
+            on_measure: Some(Box::new(on_measure_fn)),
+            ..self
+        }
+    }
+
+    fn to_world(&self, pixel: Vec2) -> Vec2 {
+        pixel_to_world(&self.inverse_view_projection, self.bounds, pixel)
+    }
+
+    fn to_pixel(&self, world: Vec2) -> Vec2 {
+        world_to_pixel(&self.view_projection, self.bounds, world)
+    }
+
+    /// The color behind the rulers and measurements.
+    fn themed_background_color(&self, theme: &Theme) -> Vec4 {
+        let panel = theme.panel_color;
+        self.background_color
+            .unwrap_or_else(|| vec4(panel.x, panel.y, panel.z, 0.85))
+    }
+
+    /// Draw text with its top left corner at the given position.
+    fn draw_text(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        text: &str,
+        top_left: Vec2,
+    ) -> Result<()> {
+        let (tiles, bounds) = self.font.build_text_tiles(text);
+        let offset = top_left - bounds.top_left;
+        for mut tile in tiles {
+            tile.color = self.font.text_color_or(theme);
+            tile.model = tile
+                .model
+                .translate(vec2(offset.x.round(), offset.y.round()));
+            tile.fill(frame)?;
+        }
+        Ok(())
+    }
+
+    /// The tick values, and how many decimals to label them with, covering
+    /// the world space range between a and b.
+    fn ticks(a: f32, b: f32, pixels: f32) -> (Vec<f32>, usize) {
+        let (low, high) = (a.min(b), a.max(b));
+        let step = tick_step(high - low, pixels, MIN_TICK_SPACING);
+        if !step.is_finite() || step <= 0.0 {
+            return (vec![], 0);
+        }
+        let decimals = (-step.log10().floor()).max(0.0) as usize;
+        let mut ticks = vec![];
+        let mut tick = (low / step).ceil() * step;
+        while tick <= high {
+            ticks.push(tick);
+            tick += step;
+        }
+        (ticks, decimals)
+    }
+
+    /// Draw both rulers.
+    fn draw_rulers(&self, frame: &mut Frame, theme: &Theme) -> Result<()> {
+        let padding = 0.25 * self.font.line_height();
+        let thickness = self.font.line_height() + 2.0 * padding;
+        let top_left = self.to_world(self.bounds.top_left);
+        let bottom_right = self.to_world(self.bounds.bottom_right);
+
+        let (x_ticks, x_decimals) =
+            Self::ticks(top_left.x, bottom_right.x, self.bounds.width());
+        let (y_ticks, y_decimals) =
+            Self::ticks(top_left.y, bottom_right.y, self.bounds.height());
+        let y_labels: Vec<String> = y_ticks
+            .iter()
+            .map(|tick| format!("{:.*}", y_decimals, tick))
+            .collect();
+        let left_width = y_labels
+            .iter()
+            .map(|label| self.font.text_width(label) + 2.0 * padding)
+            .fold(thickness, f32::max)
+            .round();
+
+        let top_ruler = Rect::new(
+            self.bounds.top(),
+            self.bounds.left(),
+            self.bounds.top() + thickness,
+            self.bounds.right(),
+        );
+        let left_ruler = Rect::new(
+            top_ruler.bottom(),
+            self.bounds.left(),
+            self.bounds.bottom(),
+            self.bounds.left() + left_width,
+        );
+        for model in [top_ruler, left_ruler] {
+            Tile {
+                model,
+                color: self.themed_background_color(theme),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        for tick in x_ticks {
+            let x = self.to_pixel(vec2(tick, top_left.y)).x.round();
+            if x < left_ruler.right() {
+                continue;
+            }
+            Tile {
+                model: Rect::new(
+                    top_ruler.bottom() - padding,
+                    x,
+                    top_ruler.bottom(),
+                    x + 1.0,
+                ),
+                color: self.tick_color.unwrap_or(theme.detail_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+            let label = format!("{:.*}", x_decimals, tick);
+            self.draw_text(
+                frame,
+                theme,
+                &label,
+                vec2(x + padding, top_ruler.top()),
+            )?;
+        }
+
+        for (tick, label) in y_ticks.into_iter().zip(y_labels) {
+            let y = self.to_pixel(vec2(top_left.x, tick)).y.round();
+            if y < left_ruler.top() {
+                continue;
+            }
+            Tile {
+                model: Rect::new(
+                    y,
+                    left_ruler.right() - padding,
+                    y + 1.0,
+                    left_ruler.right(),
+                ),
+                color: self.tick_color.unwrap_or(theme.detail_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+            self.draw_text(
+                frame,
+                theme,
+                &label,
+                vec2(left_ruler.left() + padding, y),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Draw the measurement line with its distance and angle.
+    fn draw_measurement(
+        &self,
+        state: &RulersState,
+        frame: &mut Frame,
+        theme: &Theme,
+    ) -> Result<()> {
+        let start = match state.start {
+            Some(start) => start,
+            None => return Ok(()),
+        };
+        let end = state
+            .end
+            .unwrap_or_else(|| self.to_world(state.mouse_position));
+        let measurement = Measurement { start, end };
+
+        let start_pixel = self.to_pixel(start);
+        let end_pixel = self.to_pixel(end);
+        Line {
+            start: start_pixel,
+            end: end_pixel,
+            width: 2.0,
+            color: self.measure_color.unwrap_or(theme.accent_color),
+            antialiased: true,
+            ..Default::default()
+        }
+        .draw(frame)?;
+        for point in [start_pixel, end_pixel] {
+            Tile {
+                model: Rect::centered_at(point.x, point.y, 6.0, 6.0),
+                color: self.measure_color.unwrap_or(theme.accent_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+
+        let text = format!(
+            "{:.3} at {:.1}\u{b0}",
+            measurement.distance(),
+            measurement.angle_degrees()
+        );
+        let padding = 0.25 * self.font.line_height();
+        let position = end_pixel + vec2(2.0 * padding, 2.0 * padding);
+        Tile {
+            model: Rect::new(
+                position.y,
+                position.x,
+                position.y + self.font.line_height() + 2.0 * padding,
+                position.x + self.font.text_width(&text) + 2.0 * padding,
+            ),
+            color: self.themed_background_color(theme),
+            ..Default::default()
+        }
+        .fill(frame)?;
+        self.draw_text(frame, theme, &text, position + vec2(padding, padding))
+    }
+}
+
+impl<Message> Widget<Message> for Rulers<Message> {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    /// Rulers are drawn in the overlay so they're on top of everything.
+    fn draw_frame(
+        &self,
+        _internal_state: &mut InternalState,
+        _frame: &mut Frame,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Rulers fill all of the available space.
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.bounds = max_size.as_rect();
+        *max_size
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        if !self.measuring {
+            return Ok(OverlayEvent::Ignored);
+        }
+        let mut state = *internal_state.get_state::<RulersState>(&self.id);
+        let mouse = input.mouse_position;
+        let result = match *event {
+            WindowEvent::CursorPos(..) => {
+                state.mouse_position = mouse;
+                OverlayEvent::Ignored
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if self.bounds.contains(mouse) => {
+                let point = self.to_world(mouse);
+                match (state.start, state.end) {
+                    (Some(start), None) => {
+                        state.end = Some(point);
+                        let measurement = Measurement { start, end: point };
+                        OverlayEvent::Captured(
+                            self.on_measure
+                                .as_ref()
+                                .map(|on_measure| on_measure(measurement)),
+                        )
+                    }
+                    _ => {
+                        state.start = Some(point);
+                        state.end = None;
+                        OverlayEvent::Captured(None)
+                    }
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button2,
+                Action::Press,
+                _,
+            ) if state.start.is_some() => {
+                state.start = None;
+                state.end = None;
+                OverlayEvent::Captured(None)
+            }
+            _ => OverlayEvent::Ignored,
+        };
+        *internal_state.get_state_mut::<RulersState>(&self.id) = state;
+        Ok(result)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        self.draw_rulers(frame, &theme)?;
+        if self.measuring {
+            let state = *internal_state.get_state::<RulersState>(&self.id);
+            self.draw_measurement(&state, frame, &theme)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Rulers<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Convert a position in screen space into world space. The viewport is the
+/// space the camera renders into.
+fn pixel_to_world(inverse: &Mat4, viewport: Rect, pixel: Vec2) -> Vec2 {
+    let ndc = vec4(
+        2.0 * (pixel.x - viewport.left()) / viewport.width().max(1.0) - 1.0,
+        2.0 * (pixel.y - viewport.top()) / viewport.height().max(1.0) - 1.0,
+        0.0,
+        1.0,
+    );
+    let world = inverse * ndc;
+    vec2(world.x, world.y) / world.w
+}
+
+/// Convert a position in world space into screen space. The viewport is the
+/// space the camera renders into.
+fn world_to_pixel(view_projection: &Mat4, viewport: Rect, world: Vec2) -> Vec2 {
+    let clip = view_projection * vec4(world.x, world.y, 0.0, 1.0);
+    let ndc = vec2(clip.x, clip.y) / clip.w;
+    vec2(
+        viewport.left() + 0.5 * (ndc.x + 1.0) * viewport.width(),
+        viewport.top() + 0.5 * (ndc.y + 1.0) * viewport.height(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::math::projections;
+
+    #[test]
+    fn test_world_and_screen_space_round_trip() {
+        // a math-style camera showing x in [-2, 2] and y in [-1, 1]
+        let camera = projections::ortho(-2.0, 2.0, -1.0, 1.0, 0.0, 1.0);
+        let inverse = camera.try_inverse().unwrap();
+        let viewport = Rect::new(0.0, 0.0, 100.0, 200.0);
+
+        assert_eq!(
+            pixel_to_world(&inverse, viewport, vec2(0.0, 0.0)),
+            vec2(-2.0, 1.0)
+        );
+        assert_eq!(
+            world_to_pixel(&camera, viewport, vec2(1.0, -0.5)),
+            vec2(150.0, 75.0)
+        );
+
+        let measurement = Measurement {
+            start: vec2(0.0, 0.0),
+            end: vec2(0.0, -2.0),
+        };
+        assert_eq!(measurement.distance(), 2.0);
+        assert_eq!(measurement.angle_degrees(), -90.0);
+    }
+}
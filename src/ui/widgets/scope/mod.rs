@@ -0,0 +1,421 @@
+mod signal;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{Element, Label, Widget},
+        Font, Id, Input, InternalState,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+pub use self::signal::Signal;
+
+/// A Scope's state is stored in the UI InternalState so pausing persists
+/// between views.
+#[derive(Debug, Clone, Default)]
+pub struct ScopeState {
+    /// True when the scope is showing the snapshot instead of the live
+    /// signal.
+    paused: bool,
+
+    /// A copy of the signal taken when the scope was paused. The allocation
+    /// is reused each time the scope is paused.
+    snapshot: Vec<f32>,
+
+    /// The mouse's x position while it's over the scope.
+    mouse_x: Option<f32>,
+}
+
+/// The samples which fall into a single column of the plot.
+#[derive(Debug, Copy, Clone)]
+struct Column {
+    x: f32,
+    min: f32,
+    max: f32,
+    mean: f32,
+}
+
+/// A Scope plots a rolling window of a streamed [`Signal`]. New samples enter
+/// on the right and the vertical range scales to fit the visible samples.
+///
+/// When there are more samples than pixels, each column of the plot shows
+/// the range of its samples as a band with a line through their mean.
+///
+/// # Controls
+///
+/// - Click the scope to pause or resume it.
+/// - Hover a paused scope to inspect the samples under the mouse.
+pub struct Scope<Message> {
+    /// The ID uniquely identifies this scope when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The signal being plotted.
+    signal: Signal,
+
+    /// The font used for inspection labels.
+    font: Font,
+
+    /// The space occupied by the scope on screen.
+    bounds: Rect,
+
+    /// A fixed vertical range. The range is fit to the samples when None.
+    range: Option<(f32, f32)>,
+
+    /// The ratio of the scope's width to its height.
+    aspect_ratio: f32,
+
+    /// The color behind the plot.
+    background_color: Vec4,
+
+    /// The color of the line through each column's mean.
+    color: Vec4,
+
+    /// The color of each column's min/max band.
+    band_color: Vec4,
+
+    /// The color of the inspection cursor and the border of a paused scope.
+    inspect_color: Vec4,
+
+    _phantom_data: std::marker::PhantomData<Message>,
+}
+
+impl<Message> Scope<Message> {
+    pub fn new(id: Id, font: &Font, signal: &Signal) -> Self {
+        Self {
+            id,
+            signal: signal.clone(),
+            font: font.clone(),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            range: None,
+            aspect_ratio: 4.0,
+            background_color: vec4(0.08, 0.08, 0.08, 1.0),
+            color: vec4(0.3, 1.0, 0.4, 1.0),
+            band_color: vec4(0.3, 1.0, 0.4, 0.3),
+            inspect_color: vec4(1.0, 0.8, 0.2, 1.0),
+            _phantom_data: std::marker::PhantomData,
+        }
+    }
+
+    builder_field!(aspect_ratio, f32);
+    builder_field!(background_color, Vec4);
+    builder_field!(color, Vec4);
+    builder_field!(band_color, Vec4);
+    builder_field!(inspect_color, Vec4);
+
+    /// Always show the given range of values instead of fitting the range to
+    /// the samples.
+    pub fn range(self, min: f32, max: f32) -> Self {
+        Self {
+            range: Some((min, max)),
+            ..self
+        }
+    }
+
+    /// The number of columns in the plot.
+    fn column_count(&self) -> usize {
+        self.signal
+            .capacity()
+            .min(self.bounds.width().max(1.0) as usize)
+    }
+
+    /// Compute the samples which fall into the given column. The window is
+    /// always the signal's full capacity, so columns on the left are empty
+    /// until the signal fills up.
+    fn column<F>(&self, column: usize, len: usize, sample: &F) -> Option<Column>
+    where
+        F: Fn(usize) -> f32,
+    {
+        let capacity = self.signal.capacity();
+        let columns = self.column_count();
+        let per_column = capacity as f32 / columns as f32;
+        let missing = capacity - len.min(capacity);
+
+        let start = ((column as f32 * per_column) as usize).max(missing);
+        let end = (((column + 1) as f32 * per_column) as usize).min(capacity);
+        if start >= end {
+            return None;
+        }
+
+        let mut min = f32::MAX;
+        let mut max = f32::MIN;
+        let mut sum = 0.0;
+        for slot in start..end {
+            let value = sample(slot - missing);
+            min = min.min(value);
+            max = max.max(value);
+            sum += value;
+        }
+        Some(Column {
+            x: self.bounds.left()
+                + (column as f32 + 0.5) * self.bounds.width() / columns as f32,
+            min,
+            max,
+            mean: sum / (end - start) as f32,
+        })
+    }
+
+    /// The vertical range of the plot. Flat signals are given some room so
+    /// they show up in the middle of the scope.
+    fn value_range<F>(&self, len: usize, sample: &F) -> (f32, f32)
+    where
+        F: Fn(usize) -> f32,
+    {
+        if let Some(range) = self.range {
+            return range;
+        }
+        match signal::min_max((0..len).map(sample)) {
+            Some((min, max)) if max - min > f32::EPSILON => (min, max),
+            Some((min, _)) => (min - 0.5, min + 0.5),
+            None => (0.0, 1.0),
+        }
+    }
+
+    /// Convert a value into a screen-space y position.
+    fn to_screen_y(&self, value: f32, range: (f32, f32)) -> f32 {
+        let (min, max) = range;
+        let t = ((value - min) / (max - min)).clamp(0.0, 1.0);
+        self.bounds.bottom() - t * self.bounds.height()
+    }
+
+    /// Draw the plot of every sample.
+    fn draw_samples<F>(
+        &self,
+        len: usize,
+        sample: F,
+        frame: &mut Frame,
+    ) -> Result<()>
+    where
+        F: Fn(usize) -> f32,
+    {
+        let range = self.value_range(len, &sample);
+        let column_width = self.bounds.width() / self.column_count() as f32;
+        let line = Line {
+            width: 1.5,
+            color: self.color,
+            ..Default::default()
+        };
+
+        let mut previous: Option<Vec2> = None;
+        for index in 0..self.column_count() {
+            let column = match self.column(index, len, &sample) {
+                Some(column) => column,
+                None => {
+                    previous = None;
+                    continue;
+                }
+            };
+
+            let top = self.to_screen_y(column.max, range);
+            let bottom = self.to_screen_y(column.min, range);
+            if bottom - top >= 1.0 {
+                Tile {
+                    model: Rect::new(
+                        top,
+                        column.x - 0.5 * column_width,
+                        bottom,
+                        column.x + 0.5 * column_width,
+                    ),
+                    color: self.band_color,
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+
+            let point = vec2(column.x, self.to_screen_y(column.mean, range));
+            if let Some(start) = previous {
+                Line {
+                    start,
+                    end: point,
+                    ..line
+                }
+                .draw(frame)?;
+            }
+            previous = Some(point);
+        }
+
+        Ok(())
+    }
+
+    /// Describe the samples under the mouse.
+    fn inspect<F>(&self, mouse_x: f32, len: usize, sample: F) -> Option<Column>
+    where
+        F: Fn(usize) -> f32,
+    {
+        let t = (mouse_x - self.bounds.left()) / self.bounds.width().max(1.0);
+        let columns = self.column_count();
+        let index = ((t * columns as f32) as usize).min(columns - 1);
+        self.column(index, len, &sample)
+    }
+
+    /// Draw a cursor and a label for the inspected column.
+    fn draw_inspection(
+        &self,
+        internal_state: &mut InternalState,
+        column: Column,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        Line {
+            start: vec2(column.x, self.bounds.top()),
+            end: vec2(column.x, self.bounds.bottom()),
+            color: self.inspect_color,
+            ..Default::default()
+        }
+        .draw(frame)?;
+
+        let text = if column.max - column.min > f32::EPSILON {
+            format!(
+                "{:.3} ({:.3} to {:.3})",
+                column.mean, column.min, column.max
+            )
+        } else {
+            format!("{:.3}", column.mean)
+        };
+        let mut label = Label::new(&self.font, text);
+        let padding = 0.25 * self.font.line_height();
+        let label_size = Widget::<Message>::dimensions(
+            &mut label,
+            internal_state,
+            &self.bounds.dimensions(),
+        );
+
+        // keep the label on the side of the cursor with more room
+        let x = if column.x + padding + label_size.width < self.bounds.right() {
+            column.x + padding
+        } else {
+            column.x - padding - label_size.width
+        };
+        let position = vec2(x, self.bounds.top() + padding);
+        Widget::<Message>::set_top_left_position(
+            &mut label,
+            internal_state,
+            position,
+        );
+
+        Tile {
+            model: Rect::new(
+                position.y - padding,
+                position.x - padding,
+                position.y + label_size.height + padding,
+                position.x + label_size.width + padding,
+            ),
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Widget::<Message>::draw_frame(&label, internal_state, frame)
+    }
+}
+
+impl<Message> Widget<Message> for Scope<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state = internal_state.get_state_mut::<ScopeState>(&self.id);
+        let inside = self.bounds.contains(input.mouse_position);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.mouse_x = if inside {
+                    Some(input.mouse_position.x)
+                } else {
+                    None
+                };
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if inside => {
+                state.paused = !state.paused;
+                if state.paused {
+                    self.signal.copy_into(&mut state.snapshot);
+                }
+            }
+            _ => (),
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        Tile {
+            model: self.bounds,
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let state = internal_state.get_state::<ScopeState>(&self.id);
+        let paused = state.paused;
+        let inspected = if paused {
+            let snapshot = &state.snapshot;
+            self.draw_samples(snapshot.len(), |i| snapshot[i], frame)?;
+            state.mouse_x.and_then(|mouse_x| {
+                self.inspect(mouse_x, snapshot.len(), |i| snapshot[i])
+            })
+        } else {
+            let signal = &self.signal;
+            self.draw_samples(
+                signal.len(),
+                |i| signal.get(i).unwrap_or(0.0),
+                frame,
+            )?;
+            None
+        };
+
+        if let Some(column) = inspected {
+            self.draw_inspection(internal_state, column, frame)?;
+        }
+        if paused {
+            Tile {
+                model: self.bounds,
+                color: self.inspect_color,
+                ..Default::default()
+            }
+            .outline(frame)?;
+        }
+
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let width = max_size.width;
+        let height = width / self.aspect_ratio.max(f32::EPSILON);
+        self.bounds = Dimensions::new(width, height).min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+    }
+}
+
+impl<Message> Into<Element<Message>> for Scope<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
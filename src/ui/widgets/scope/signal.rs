@@ -0,0 +1,126 @@
+use ::std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+/// A Signal is a fixed-size ring buffer of streamed samples. Once the signal
+/// is full, each new sample replaces the oldest one, so pushing never
+/// allocates.
+///
+/// Cloning a Signal is cheap and every clone shares the same samples. The
+/// application keeps one copy to push samples into while each view gives a
+/// clone to a [`super::Scope`].
+#[derive(Debug, Clone)]
+pub struct Signal {
+    samples: Rc<RefCell<VecDeque<f32>>>,
+    capacity: usize,
+}
+
+impl Signal {
+    /// Create an empty signal which holds up to `capacity` samples.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: Rc::new(RefCell::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// The maximum number of samples kept by the signal.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of samples currently in the signal.
+    pub fn len(&self) -> usize {
+        self.samples.borrow().len()
+    }
+
+    /// True when the signal doesn't have any samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.borrow().is_empty()
+    }
+
+    /// Add a sample to the signal, dropping the oldest sample when full.
+    pub fn push(&self, value: f32) {
+        let mut samples = self.samples.borrow_mut();
+        if samples.len() == self.capacity {
+            samples.pop_front();
+        }
+        samples.push_back(value);
+    }
+
+    /// Remove every sample from the signal.
+    pub fn clear(&self) {
+        self.samples.borrow_mut().clear();
+    }
+
+    /// Get the sample at the given index. Index 0 is the oldest sample.
+    pub fn get(&self, index: usize) -> Option<f32> {
+        self.samples.borrow().get(index).copied()
+    }
+
+    /// Get the most recent sample.
+    pub fn latest(&self) -> Option<f32> {
+        self.samples.borrow().back().copied()
+    }
+
+    /// Replace the contents of `target` with a copy of every sample, oldest
+    /// first. The target's allocation is reused when it is large enough.
+    pub fn copy_into(&self, target: &mut Vec<f32>) {
+        target.clear();
+        target.extend(self.samples.borrow().iter());
+    }
+
+    /// Get the smallest and largest samples, or None when the signal is
+    /// empty.
+    pub fn min_max(&self) -> Option<(f32, f32)> {
+        min_max(self.samples.borrow().iter().copied())
+    }
+}
+
+/// Get the smallest and largest values, or None when there aren't any values.
+pub(super) fn min_max<I>(values: I) -> Option<(f32, f32)>
+where
+    I: IntoIterator<Item = f32>,
+{
+    values.into_iter().fold(None, |range, value| match range {
+        Some((min, max)) => Some((value.min(min), value.max(max))),
+        None => Some((value, value)),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_push_drops_oldest_when_full() {
+        let signal = Signal::new(3);
+        for value in 0..5 {
+            signal.push(value as f32);
+        }
+
+        assert_eq!(signal.len(), 3);
+        assert_eq!(signal.get(0), Some(2.0));
+        assert_eq!(signal.latest(), Some(4.0));
+    }
+
+    #[test]
+    fn test_clones_share_samples() {
+        let signal = Signal::new(4);
+        let clone = signal.clone();
+        signal.push(1.0);
+
+        assert_eq!(clone.len(), 1);
+        assert_eq!(clone.latest(), Some(1.0));
+    }
+
+    #[test]
+    fn test_min_max() {
+        let signal = Signal::new(8);
+        assert_eq!(signal.min_max(), None);
+
+        for &value in &[3.0, -1.0, 7.0, 2.0] {
+            signal.push(value);
+        }
+        assert_eq!(signal.min_max(), Some((-1.0, 7.0)));
+    }
+}
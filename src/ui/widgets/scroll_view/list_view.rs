@@ -0,0 +1,274 @@
+use ::{anyhow::Result, std::ops::Range};
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect},
+        widgets::{Element, OverlayEvent, Widget},
+        Id, Input, InternalState,
+    },
+    vec2, Vec2, Vec4,
+};
+
+use super::scrollbar::{
+    max_offset, scrollbar_field, ScrollViewState, ViewScrollbar,
+};
+
+/// A ListView is a scrolling list of rows which all have the same height.
+///
+/// Rows are created on demand by a function of the row's index, and only the
+/// rows inside the viewport exist at any time. This keeps input handling and
+/// vertex counts proportional to the size of the view rather than the size
+/// of the list, so lists with many thousands of items stay cheap.
+pub struct ListView<Message> {
+    /// The ID uniquely identifies this list when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The number of rows in the list.
+    item_count: usize,
+
+    /// The height of every row, in pixels.
+    row_height: f32,
+
+    /// Creates the widget for a row.
+    build_row: Box<dyn Fn(usize) -> Element<Message>>,
+
+    /// The widgets for the visible rows.
+    rows: Vec<Element<Message>>,
+
+    /// The indices of the rows which currently exist.
+    visible: Range<usize>,
+
+    /// The size rows were laid out with.
+    row_size: Dimensions,
+
+    /// The visible region of the list on screen.
+    viewport: Rect,
+
+    /// The scrollbar beside the viewport.
+    scrollbar: ViewScrollbar,
+
+    /// How many pixels the list moves for each step of the mouse wheel.
+    scroll_speed: f32,
+}
+
+impl<Message> ListView<Message> {
+    pub fn new<F>(
+        id: Id,
+        item_count: usize,
+        row_height: f32,
+        build_row: F,
+    ) -> Self
+    where
+        F: Fn(usize) -> Element<Message> + 'static,
+    {
+        Self {
+            id,
+            item_count,
+            row_height,
+            build_row: Box::new(build_row),
+            rows: vec![],
+            visible: 0..0,
+            row_size: Dimensions::new(0.0, 0.0),
+            viewport: Rect::new(0.0, 0.0, 0.0, 0.0),
+            scrollbar: ViewScrollbar::new(),
+            scroll_speed: 40.0,
+        }
+    }
+
+    builder_field!(scroll_speed, f32);
+    scrollbar_field!(scrollbar_width, f32);
+    scrollbar_field!(track_color, Vec4);
+    scrollbar_field!(thumb_color, Vec4);
+    scrollbar_field!(thumb_hover_color, Vec4);
+
+    /// The height of every row together.
+    fn content_height(&self) -> f32 {
+        self.item_count as f32 * self.row_height
+    }
+
+    /// Scroll the list to the given offset, clamped to the content. Rows are
+    /// only rebuilt when a different set of rows becomes visible.
+    fn scroll_to(&mut self, internal_state: &mut InternalState, offset: f32) {
+        let content_height = self.content_height();
+        let offset = offset
+            .clamp(0.0, max_offset(content_height, self.viewport.height()));
+        internal_state
+            .get_state_mut::<ScrollViewState>(&self.id)
+            .offset = offset;
+
+        let visible = visible_rows(
+            offset,
+            self.viewport.height(),
+            self.row_height,
+            self.item_count,
+        );
+        let row_size = Dimensions::new(self.viewport.width(), self.row_height);
+        if visible != self.visible || row_size != self.row_size {
+            self.rows = visible.clone().map(|i| (self.build_row)(i)).collect();
+            for row in &mut self.rows {
+                row.dimensions(internal_state, &row_size);
+            }
+            self.visible = visible;
+            self.row_size = row_size;
+        }
+
+        for (row, index) in self.rows.iter_mut().zip(self.visible.clone()) {
+            let top = index as f32 * self.row_height - offset;
+            row.set_top_left_position(
+                internal_state,
+                self.viewport.top_left + vec2(0.0, top),
+            );
+        }
+        self.scrollbar.layout(self.viewport, content_height, offset);
+    }
+}
+
+impl<Message> Widget<Message> for ListView<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        let mut state = *internal_state.get_state::<ScrollViewState>(&self.id);
+        let captured = self.scrollbar.handle_event(
+            &mut state,
+            self.scroll_speed,
+            input,
+            event,
+        );
+        *internal_state.get_state_mut::<ScrollViewState>(&self.id) = state;
+        self.scroll_to(internal_state, state.offset);
+
+        if captured || self.scrollbar.is_hidden_from_content(input, event) {
+            return Ok(None);
+        }
+        for row in &mut self.rows {
+            if let Some(message) =
+                row.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        frame.push_clip_rect(self.viewport);
+        let result = self
+            .rows
+            .iter()
+            .try_for_each(|row| row.draw_frame(internal_state, frame));
+        frame.pop_clip_rect();
+        result?;
+
+        let state = *internal_state.get_state::<ScrollViewState>(&self.id);
+        self.scrollbar.draw(&state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let content_height = self.content_height();
+        let scrollbar_width =
+            self.scrollbar.width_for(content_height, max_size.height);
+        self.viewport = Dimensions::new(
+            (max_size.width - scrollbar_width).max(0.0),
+            content_height.min(max_size.height),
+        )
+        .as_rect();
+        Dimensions::new(
+            self.viewport.width() + scrollbar_width,
+            self.viewport.height(),
+        )
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.viewport = self.viewport.set_top_left_position(position);
+        let offset =
+            internal_state.get_state::<ScrollViewState>(&self.id).offset;
+
+        // the list may have shrunk since the last layout
+        self.scroll_to(internal_state, offset);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        for row in self.rows.iter_mut().rev() {
+            let result =
+                row.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for row in &self.rows {
+            row.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for ListView<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// The indices of the rows which overlap a viewport scrolled down by
+/// `offset` pixels.
+fn visible_rows(
+    offset: f32,
+    viewport_height: f32,
+    row_height: f32,
+    item_count: usize,
+) -> Range<usize> {
+    if row_height <= 0.0 || viewport_height <= 0.0 {
+        return 0..0;
+    }
+    let first = ((offset / row_height).floor() as usize).min(item_count);
+    let last = (((offset + viewport_height) / row_height).ceil() as usize)
+        .min(item_count);
+    first..last
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_visible_rows() {
+        assert_eq!(visible_rows(0.0, 100.0, 20.0, 10_000), 0..5);
+        assert_eq!(visible_rows(30.0, 100.0, 20.0, 10_000), 1..7);
+        assert_eq!(visible_rows(190.0, 100.0, 20.0, 12), 9..12);
+        assert_eq!(visible_rows(0.0, 100.0, 20.0, 0), 0..0);
+        assert_eq!(visible_rows(0.0, 0.0, 20.0, 10), 0..0);
+    }
+}
@@ -0,0 +1,201 @@
+mod list_view;
+mod scrollbar;
+mod thumb;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect},
+        widgets::{Element, OverlayEvent, Widget},
+        Id, Input, InternalState,
+    },
+    vec2, Vec2, Vec4,
+};
+
+use self::scrollbar::{
+    max_offset, scrollbar_field, ScrollViewState, ViewScrollbar,
+};
+
+pub use self::{list_view::ListView, scrollbar::Scrollbar};
+
+/// The height offered to a ScrollView's child. Children are allowed to be
+/// much taller than the view, but widgets which fill all available space
+/// still need a finite limit.
+const MAX_CONTENT_HEIGHT: f32 = 100_000.0;
+
+/// A ScrollView hosts a child which can be taller than the view itself.
+/// Anything outside of the view is clipped, and the content can be scrolled
+/// with the mouse wheel or by dragging the scrollbar.
+pub struct ScrollView<Message> {
+    /// The ID uniquely identifies this scroll view when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The scrolling content.
+    child: Element<Message>,
+
+    /// The visible region of the content on screen.
+    viewport: Rect,
+
+    /// The size of the child after layout.
+    content: Dimensions,
+
+    /// The scrollbar beside the viewport.
+    scrollbar: ViewScrollbar,
+
+    /// How many pixels the content moves for each step of the mouse wheel.
+    scroll_speed: f32,
+}
+
+impl<Message> ScrollView<Message> {
+    pub fn new<E>(id: Id, child: E) -> Self
+    where
+        E: Into<Element<Message>>,
+    {
+        Self {
+            id,
+            child: child.into(),
+            viewport: Rect::new(0.0, 0.0, 0.0, 0.0),
+            content: Dimensions::new(0.0, 0.0),
+            scrollbar: ViewScrollbar::new(),
+            scroll_speed: 40.0,
+        }
+    }
+
+    scrollbar_field!(scrollbar_width, f32);
+    scrollbar_field!(track_color, Vec4);
+    scrollbar_field!(thumb_color, Vec4);
+    scrollbar_field!(thumb_hover_color, Vec4);
+
+    builder_field!(scroll_speed, f32);
+
+    /// Scroll the content to the given offset, clamped to the content.
+    fn scroll_to(&mut self, internal_state: &mut InternalState, offset: f32) {
+        let offset = offset.clamp(
+            0.0,
+            max_offset(self.content.height, self.viewport.height()),
+        );
+        internal_state
+            .get_state_mut::<ScrollViewState>(&self.id)
+            .offset = offset;
+
+        self.child.set_top_left_position(
+            internal_state,
+            self.viewport.top_left - vec2(0.0, offset),
+        );
+        self.scrollbar
+            .layout(self.viewport, self.content.height, offset);
+    }
+}
+
+impl<Message> Widget<Message> for ScrollView<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        let mut state = *internal_state.get_state::<ScrollViewState>(&self.id);
+        let captured = self.scrollbar.handle_event(
+            &mut state,
+            self.scroll_speed,
+            input,
+            event,
+        );
+        *internal_state.get_state_mut::<ScrollViewState>(&self.id) = state;
+        self.scroll_to(internal_state, state.offset);
+
+        if captured || self.scrollbar.is_hidden_from_content(input, event) {
+            return Ok(None);
+        }
+        self.child.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        frame.push_clip_rect(self.viewport);
+        let result = self.child.draw_frame(internal_state, frame);
+        frame.pop_clip_rect();
+        result?;
+
+        let state = *internal_state.get_state::<ScrollViewState>(&self.id);
+        self.scrollbar.draw(&state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let unbounded = Dimensions::new(max_size.width, MAX_CONTENT_HEIGHT);
+        self.content = self.child.dimensions(internal_state, &unbounded);
+
+        let scrollbar_width = self
+            .scrollbar
+            .width_for(self.content.height, max_size.height);
+        if scrollbar_width > 0.0 {
+            // make room for the scrollbar and lay the child out again
+            let narrow = Dimensions::new(
+                (max_size.width - scrollbar_width).max(0.0),
+                MAX_CONTENT_HEIGHT,
+            );
+            self.content = self.child.dimensions(internal_state, &narrow);
+        }
+
+        self.viewport = Dimensions::new(
+            self.content.width,
+            self.content.height.min(max_size.height),
+        )
+        .as_rect();
+        Dimensions::new(
+            self.viewport.width() + scrollbar_width,
+            self.viewport.height(),
+        )
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.viewport = self.viewport.set_top_left_position(position);
+        let offset =
+            internal_state.get_state::<ScrollViewState>(&self.id).offset;
+
+        // the content may have shrunk since the last layout
+        self.scroll_to(internal_state, offset);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)
+    }
+}
+
+impl<Message> Into<Element<Message>> for ScrollView<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,380 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions, Rect, Tile},
+        widgets::{button::ButtonState, Element, Widget},
+        Id, Input, InternalState,
+    },
+    vec4, Vec2, Vec4,
+};
+
+use super::thumb::{Thumb, ThumbState};
+
+/// A Scrollbar is a track with a draggable thumb, along either axis. It
+/// doesn't scroll anything itself: dragging the thumb, clicking the track,
+/// or using the mouse wheel over it emits the new position, normalized to
+/// the range [0, 1], and the view passes it back in with
+/// [`Scrollbar::position`].
+///
+/// The [`super::ScrollView`] and [`super::ListView`] use the same thumb for
+/// their own scrollbars.
+pub struct Scrollbar<Message> {
+    id: Id,
+    thumb: Thumb,
+
+    /// The thumb's position, 0 at the top or left and 1 at the bottom or
+    /// right.
+    position: f32,
+
+    /// How much of the content is visible, which sets the thumb's length.
+    visible_fraction: f32,
+
+    /// The space occupied by the scrollbar on screen.
+    bounds: Rect,
+
+    /// The scrollbar's size across its axis.
+    thickness: f32,
+
+    /// How far one step of the mouse wheel moves the thumb.
+    scroll_step: f32,
+
+    track_color: Vec4,
+    thumb_color: Vec4,
+    thumb_hover_color: Vec4,
+    on_scroll: Option<Box<dyn Fn(f32) -> Message>>,
+}
+
+impl<Message> Scrollbar<Message> {
+    /// Create a scrollbar along the axis. It fills the available length.
+    pub fn new(id: Id, axis: Axis) -> Self {
+        Self {
+            id,
+            thumb: Thumb::new(axis),
+            position: 0.0,
+            visible_fraction: 0.1,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            thickness: 12.0,
+            scroll_step: 0.05,
+            track_color: vec4(0.0, 0.0, 0.0, 0.3),
+            thumb_color: vec4(0.5, 0.5, 0.5, 0.8),
+            thumb_hover_color: vec4(0.7, 0.7, 0.7, 0.9),
+            on_scroll: None,
+        }
+    }
+
+    builder_field!(thickness, f32);
+    builder_field!(scroll_step, f32);
+    builder_field!(track_color, Vec4);
+    builder_field!(thumb_color, Vec4);
+    builder_field!(thumb_hover_color, Vec4);
+
+    /// Set the thumb's position. Clamped to [0, 1].
+    pub fn position(self, position: f32) -> Self {
+        Self {
+            position: position.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Set how much of the content is visible, in the range [0, 1]. The
+    /// thumb's length is this fraction of the track, and a fully visible
+    /// scrollbar can't be moved.
+    pub fn visible_fraction(self, visible_fraction: f32) -> Self {
+        Self {
+            visible_fraction: visible_fraction.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Build the message emitted with the thumb's new position.
+    pub fn on_scroll<F>(self, on_scroll_fn: F) -> Self
+    where
+        F: 'static + Fn(f32) -> Message,
+    {
+        Self {
+            on_scroll: Some(Box::new(on_scroll_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Scrollbar<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        let state = internal_state.get_state_mut::<ThumbState>(&self.id);
+        let moved_to = match *event {
+            glfw::WindowEvent::Scroll(_, y)
+                if self.thumb.is_enabled()
+                    && self.bounds.contains(input.mouse_position) =>
+            {
+                Some(
+                    (self.position - y as f32 * self.scroll_step)
+                        .clamp(0.0, 1.0),
+                )
+            }
+            _ => self.thumb.handle_event(state, input, event).moved_to,
+        };
+        Ok(moved_to.and_then(|position| {
+            self.on_scroll.as_ref().map(|on_scroll| on_scroll(position))
+        }))
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<ThumbState>(&self.id);
+        Tile {
+            model: self.bounds,
+            color: self.track_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Tile {
+            model: self.thumb.thumb(),
+            color: if state.button == ButtonState::Inactive {
+                self.thumb_color
+            } else {
+                self.thumb_hover_color
+            },
+            ..Default::default()
+        }
+        .fill(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let size = match self.thumb.axis() {
+            Axis::Horizontal => Dimensions::new(max_size.width, self.thickness),
+            Axis::Vertical => Dimensions::new(self.thickness, max_size.height),
+        };
+        self.bounds = size.min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.thumb.layout(
+            self.bounds,
+            self.visible_fraction,
+            self.position,
+            self.thickness,
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for Scrollbar<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// The scroll position shared by every widget with a scrollbar. It's stored
+/// in the UI InternalState so the position persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ScrollViewState {
+    /// How far the content has been scrolled down, in pixels.
+    pub(super) offset: f32,
+
+    /// The mouse interaction state for the scrollbar's thumb.
+    thumb: ThumbState,
+}
+
+/// A vertical scrollbar beside a viewport. The scrollbar only knows about
+/// the size of the content, so the widget which owns it decides how the
+/// content is positioned for each offset.
+pub(super) struct ViewScrollbar {
+    /// The visible region of the content on screen.
+    viewport: Rect,
+
+    /// The height of all of the content.
+    content_height: f32,
+
+    /// The track and draggable thumb.
+    thumb: Thumb,
+
+    /// The width of the scrollbar. The scrollbar only takes up space when the
+    /// content is taller than the view.
+    pub(super) scrollbar_width: f32,
+
+    /// The color of the track.
+    pub(super) track_color: Vec4,
+
+    /// The color of the thumb.
+    pub(super) thumb_color: Vec4,
+
+    /// The color of the thumb when hovered or dragged.
+    pub(super) thumb_hover_color: Vec4,
+}
+
+impl ViewScrollbar {
+    pub(super) fn new() -> Self {
+        Self {
+            viewport: Rect::new(0.0, 0.0, 0.0, 0.0),
+            content_height: 0.0,
+            thumb: Thumb::new(Axis::Vertical),
+            scrollbar_width: 12.0,
+            track_color: vec4(0.0, 0.0, 0.0, 0.3),
+            thumb_color: vec4(0.5, 0.5, 0.5, 0.8),
+            thumb_hover_color: vec4(0.7, 0.7, 0.7, 0.9),
+        }
+    }
+
+    /// The width taken up beside a viewport of the given height.
+    pub(super) fn width_for(
+        &self,
+        content_height: f32,
+        viewport_height: f32,
+    ) -> f32 {
+        if content_height > viewport_height {
+            self.scrollbar_width
+        } else {
+            0.0
+        }
+    }
+
+    /// The furthest the content can be scrolled.
+    pub(super) fn max_offset(&self) -> f32 {
+        max_offset(self.content_height, self.viewport.height())
+    }
+
+    /// True when the content doesn't fit in the view.
+    pub(super) fn is_scrollable(&self) -> bool {
+        self.max_offset() > 0.0
+    }
+
+    /// Position the track to the right of the viewport and the thumb for the
+    /// offset.
+    pub(super) fn layout(
+        &mut self,
+        viewport: Rect,
+        content_height: f32,
+        offset: f32,
+    ) {
+        self.viewport = viewport;
+        self.content_height = content_height;
+        let track = Rect::new(
+            viewport.top(),
+            viewport.right(),
+            viewport.bottom(),
+            viewport.right() + self.scrollbar_width,
+        );
+        let visible = viewport.height() / content_height.max(1.0);
+        let t = if self.max_offset() > 0.0 {
+            offset / self.max_offset()
+        } else {
+            0.0
+        };
+        self.thumb.layout(track, visible, t, self.scrollbar_width);
+    }
+
+    /// Update the scroll state based on the event. The offset is not clamped.
+    /// Returns true when the event was used by the scrollbar and should not
+    /// reach the content.
+    pub(super) fn handle_event(
+        &self,
+        state: &mut ScrollViewState,
+        scroll_speed: f32,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> bool {
+        use glfw::WindowEvent;
+
+        let mouse = input.mouse_position;
+        if let WindowEvent::Scroll(_, y) = *event {
+            let over = self.viewport.contains(mouse)
+                || self.thumb.track().contains(mouse);
+            if self.is_scrollable() && over {
+                state.offset -= y as f32 * scroll_speed;
+            }
+            return false;
+        }
+        let response = self.thumb.handle_event(&mut state.thumb, input, event);
+        if let Some(t) = response.moved_to {
+            state.offset = t * self.max_offset();
+        }
+        response.captured
+    }
+
+    /// Mouse buttons are only forwarded to the content when the mouse is over
+    /// the visible part of it. Otherwise clipped widgets could still be
+    /// clicked.
+    pub(super) fn is_hidden_from_content(
+        &self,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> bool {
+        use glfw::{Action, WindowEvent};
+
+        match *event {
+            WindowEvent::MouseButton(_, Action::Press, _)
+            | WindowEvent::Scroll(..) => {
+                !self.viewport.contains(input.mouse_position)
+            }
+            _ => false,
+        }
+    }
+
+    /// Draw the track and thumb, if the content is scrollable.
+    pub(super) fn draw(
+        &self,
+        state: &ScrollViewState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        if !self.is_scrollable() {
+            return Ok(());
+        }
+        Tile {
+            model: self.thumb.track(),
+            color: self.track_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Tile {
+            model: self.thumb.thumb(),
+            color: if state.thumb.button == ButtonState::Inactive {
+                self.thumb_color
+            } else {
+                self.thumb_hover_color
+            },
+            ..Default::default()
+        }
+        .fill(frame)
+    }
+}
+
+/// The furthest content of the given height can be scrolled inside a
+/// viewport.
+pub(super) fn max_offset(content_height: f32, viewport_height: f32) -> f32 {
+    (content_height - viewport_height).max(0.0)
+}
+
+/// Define a builder method which sets one of the widget's scrollbar style
+/// fields.
+macro_rules! scrollbar_field {
+    ($field_name:ident, $field_type:ty) => {
+        pub fn $field_name(mut self, $field_name: $field_type) -> Self {
+            self.scrollbar.$field_name = $field_name;
+            self
+        }
+    };
+}
+pub(super) use scrollbar_field;
@@ -0,0 +1,218 @@
+use crate::{
+    ui::{
+        primitives::{Axis, Dimensions, Rect},
+        widgets::button::ButtonState,
+        Input,
+    },
+    vec2, Vec2,
+};
+
+/// The mouse interaction with a scrollbar's thumb. It's stored in the UI
+/// InternalState so a drag persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub(super) struct ThumbState {
+    pub(super) button: ButtonState,
+
+    /// The distance along the track from the start of the thumb to the mouse
+    /// when the thumb was grabbed.
+    grab_offset: f32,
+}
+
+/// How a thumb responded to an event.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(super) struct ThumbEvent {
+    /// True when the event was used by the thumb and shouldn't reach
+    /// anything else.
+    pub(super) captured: bool,
+
+    /// The thumb's new position, in the range [0, 1], if it was moved.
+    pub(super) moved_to: Option<f32>,
+}
+
+/// The draggable thumb in a scrollbar's track, along either axis. Positions
+/// are normalized: 0 puts the thumb at the top or left of the track and 1
+/// at the bottom or right.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Thumb {
+    axis: Axis,
+
+    /// The region the thumb can move within.
+    track: Rect,
+
+    /// The thumb itself.
+    thumb: Rect,
+
+    /// False when all of the content is visible, so there's nothing to
+    /// scroll.
+    enabled: bool,
+}
+
+impl Thumb {
+    pub(super) fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            track: Rect::new(0.0, 0.0, 0.0, 0.0),
+            thumb: Rect::new(0.0, 0.0, 0.0, 0.0),
+            enabled: false,
+        }
+    }
+
+    pub(super) fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    pub(super) fn track(&self) -> Rect {
+        self.track
+    }
+
+    pub(super) fn thumb(&self) -> Rect {
+        self.thumb
+    }
+
+    pub(super) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Position the thumb in the track. The thumb's length is the visible
+    /// fraction of the track, but never shorter than the minimum length.
+    pub(super) fn layout(
+        &mut self,
+        track: Rect,
+        visible_fraction: f32,
+        position: f32,
+        min_length: f32,
+    ) {
+        self.track = track;
+        self.enabled = visible_fraction < 1.0;
+        let length = self.track_length();
+        let thumb_length =
+            (visible_fraction * length).clamp(min_length.min(length), length);
+        let start = position.clamp(0.0, 1.0) * (length - thumb_length);
+        self.thumb = match self.axis {
+            Axis::Horizontal => Dimensions::new(thumb_length, track.height())
+                .as_rect()
+                .set_top_left_position(track.top_left + vec2(start, 0.0)),
+            Axis::Vertical => Dimensions::new(track.width(), thumb_length)
+                .as_rect()
+                .set_top_left_position(track.top_left + vec2(0.0, start)),
+        };
+    }
+
+    /// Update the thumb's state based on the event.
+    pub(super) fn handle_event(
+        &self,
+        state: &mut ThumbState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> ThumbEvent {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mouse = input.mouse_position;
+        let mut response = ThumbEvent {
+            captured: false,
+            moved_to: None,
+        };
+        match *event {
+            WindowEvent::CursorPos(..) => match state.button {
+                ButtonState::Pressed => {
+                    response.moved_to = Some(self.position_for_thumb_start(
+                        self.along(mouse) - state.grab_offset,
+                    ));
+                }
+                _ if self.enabled && self.thumb.contains(mouse) => {
+                    state.button = ButtonState::Hover;
+                }
+                _ => {
+                    state.button = ButtonState::Inactive;
+                }
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if self.enabled && self.track.contains(mouse) => {
+                if self.thumb.contains(mouse) {
+                    state.grab_offset = self.along(mouse) - self.thumb_start();
+                } else {
+                    // clicking the track jumps the thumb to the mouse, then
+                    // the thumb can be dragged from there
+                    state.grab_offset = 0.5 * self.thumb_length();
+                    response.moved_to = Some(self.position_for_thumb_start(
+                        self.along(mouse) - state.grab_offset,
+                    ));
+                }
+                state.button = ButtonState::Pressed;
+                response.captured = true;
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) if state.button == ButtonState::Pressed => {
+                state.button = if self.thumb.contains(mouse) {
+                    ButtonState::Hover
+                } else {
+                    ButtonState::Inactive
+                };
+                response.captured = true;
+            }
+            _ => (),
+        }
+        response
+    }
+
+    /// The distance of a point along the track's axis.
+    fn along(&self, point: Vec2) -> f32 {
+        match self.axis {
+            Axis::Horizontal => point.x,
+            Axis::Vertical => point.y,
+        }
+    }
+
+    fn track_length(&self) -> f32 {
+        match self.axis {
+            Axis::Horizontal => self.track.width(),
+            Axis::Vertical => self.track.height(),
+        }
+    }
+
+    fn thumb_length(&self) -> f32 {
+        match self.axis {
+            Axis::Horizontal => self.thumb.width(),
+            Axis::Vertical => self.thumb.height(),
+        }
+    }
+
+    fn thumb_start(&self) -> f32 {
+        self.along(self.thumb.top_left)
+    }
+
+    /// Convert the start of the thumb into a normalized position.
+    fn position_for_thumb_start(&self, thumb_start: f32) -> f32 {
+        let travel = self.track_length() - self.thumb_length();
+        if travel <= 0.0 {
+            return 0.0;
+        }
+        ((thumb_start - self.along(self.track.top_left)) / travel)
+            .clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_horizontal_thumbs_move_along_x() {
+        let mut thumb = Thumb::new(Axis::Horizontal);
+        thumb.layout(Rect::new(10.0, 0.0, 20.0, 100.0), 0.2, 0.5, 5.0);
+
+        // the thumb is 20 pixels long, so it travels 80 pixels
+        assert_eq!(thumb.thumb(), Rect::new(10.0, 40.0, 20.0, 60.0));
+        assert_eq!(thumb.position_for_thumb_start(40.0), 0.5);
+        assert_eq!(thumb.position_for_thumb_start(200.0), 1.0);
+
+        thumb.layout(Rect::new(10.0, 0.0, 20.0, 100.0), 1.0, 0.5, 5.0);
+        assert!(!thumb.is_enabled());
+    }
+}
@@ -0,0 +1,456 @@
+//! Sliders pick values from a range by dragging a handle along a line.
+//!
+//! The [`Slider`] picks a single value and [`RangeSlider`] picks a low and a
+//! high value with two handles. Both can be horizontal or vertical.
+
+mod range_slider;
+mod track;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions, Line, Rect, Tile},
+        widgets::{
+            text_input::text_edit::{edit_text, text_before_cursor, TextEdit},
+            Element, Label, Widget,
+        },
+        Font, Id, Input, InternalState, UISound,
+    },
+    vec2, vec4, Vec2,
+};
+
+pub use self::range_slider::RangeSlider;
+
+use self::track::{denormalize, normalize, Track};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum SliderState {
+    #[default]
+    Inactive,
+    Focused,
+    Active,
+}
+
+/// The text typed while a slider is in text-entry mode.
+#[derive(Debug, Clone)]
+struct TextEntry {
+    text: String,
+
+    /// The cursor's position, as a character index into the text.
+    cursor: usize,
+
+    /// True when the submitted text wasn't a number in the slider's range.
+    invalid: bool,
+}
+
+/// Everything a slider keeps in the UI InternalState between views.
+#[derive(Debug, Clone, Default)]
+struct SliderInternalState {
+    state: SliderState,
+
+    /// Some while the value is being typed.
+    text_entry: Option<TextEntry>,
+}
+
+/// A Slider picks a single value in a range. Like the other value widgets it
+/// doesn't own its value: each drag or typed commit emits the new value
+/// through [`Slider::on_change`] and the view passes the updated value back
+/// in with [`Slider::value`].
+pub struct Slider<Message> {
+    id: Id,
+    min: f32,
+    max: f32,
+    value: f32,
+    cursor: Rect,
+    track: Track,
+    height_ratio: f32,
+    on_change: Option<Box<dyn Fn(f32) -> Message>>,
+
+    /// The font used for text-entry mode. Text entry is disabled when this is
+    /// None.
+    font: Option<Font>,
+
+    /// The typed text's label, rebuilt each layout while editing.
+    text_label: Option<Label>,
+}
+
+impl<Message> Slider<Message> {
+    pub fn new(id: Id, min: f32, max: f32) -> Self {
+        Self {
+            id,
+            min,
+            max,
+            value: min,
+            cursor: Rect::new(0.0, 0.0, 0.0, 0.0),
+            track: Track::new(Axis::Horizontal),
+            height_ratio: 1.0 / 10.0,
+            on_change: None,
+            font: None,
+            text_label: None,
+        }
+    }
+
+    builder_field!(height_ratio, f32);
+
+    /// Set the axis the slider's handle moves along. Vertical sliders have
+    /// their min value at the bottom, like a fader. Sliders are horizontal by
+    /// default.
+    pub fn axis(self, axis: Axis) -> Self {
+        Self {
+            track: Track::new(axis),
+            ..self
+        }
+    }
+
+    /// Set the slider's value. Clamped to the min/max values.
+    pub fn value(self, value: f32) -> Self {
+        Self {
+            value: value.clamp(self.min, self.max),
+            ..self
+        }
+    }
+
+    /// Build the message emitted with the slider's new value when it's
+    /// dragged or a typed value is committed. The value is already clamped
+    /// to the slider's range.
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(f32) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    /// Allow double-clicking the slider to type an exact value. Enter commits
+    /// the value if it's a number in the slider's range, Escape or clicking
+    /// elsewhere cancels.
+    ///
+    /// Typed characters are only received when the window has char polling
+    /// enabled, see [`Input::typed_char`].
+    pub fn text_entry(self, font: &Font) -> Self {
+        Self {
+            font: Some(font.clone()),
+            ..self
+        }
+    }
+
+    /// Parse typed text as a value. None if the text isn't a number in the
+    /// slider's range.
+    fn parse_value(&self, text: &str) -> Option<f32> {
+        let value = text.trim().parse::<f32>().ok()?;
+        let (low, high) = (self.min.min(self.max), self.min.max(self.max));
+        if value >= low && value <= high {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Apply an event to the typed text. Returns the committed value, if any.
+    fn handle_text_entry_event(
+        &self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Option<f32> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let slider_state =
+            internal_state.get_state_mut::<SliderInternalState>(&self.id);
+        if let WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            _,
+        ) = *event
+        {
+            if !self.track.bounds().contains(input.mouse_position) {
+                slider_state.text_entry = None;
+            }
+            return None;
+        }
+
+        let entry = slider_state.text_entry.as_mut()?;
+        match edit_text(&entry.text, &mut entry.cursor, event) {
+            TextEdit::Changed(text) => {
+                entry.text = text;
+                entry.invalid = false;
+                None
+            }
+            TextEdit::Submit => match self.parse_value(&entry.text) {
+                Some(value) => {
+                    slider_state.text_entry = None;
+                    Some(value)
+                }
+                None => {
+                    entry.invalid = true;
+                    internal_state.play_sound(UISound::Error);
+                    None
+                }
+            },
+            TextEdit::Cancel => {
+                slider_state.text_entry = None;
+                None
+            }
+            TextEdit::Moved | TextEdit::Ignored => None,
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Slider<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let is_editing = internal_state
+            .get_state::<SliderInternalState>(&self.id)
+            .text_entry
+            .is_some();
+        if is_editing {
+            let committed =
+                self.handle_text_entry_event(internal_state, input, event);
+            return Ok(committed
+                .and_then(|value| self.on_change.as_ref().map(|f| f(value))));
+        }
+
+        let slider_state =
+            internal_state.get_state_mut::<SliderInternalState>(&self.id);
+
+        if let WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            _,
+        ) = *event
+        {
+            if self.track.bounds().contains(input.mouse_position)
+                && input.is_double_click()
+                && self.font.is_some()
+            {
+                let text = format!("{}", self.value);
+                slider_state.text_entry = Some(TextEntry {
+                    cursor: text.chars().count(),
+                    text,
+                    invalid: false,
+                });
+                slider_state.state = SliderState::Focused;
+                return Ok(None);
+            }
+        }
+
+        let current_state = &mut slider_state.state;
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                if self.track.bounds().contains(vec2(x as f32, y as f32)) {
+                    if current_state == &SliderState::Inactive {
+                        *current_state = SliderState::Focused;
+                    }
+                } else {
+                    if current_state == &SliderState::Focused {
+                        *current_state = SliderState::Inactive;
+                    }
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => match current_state {
+                SliderState::Focused
+                    if self.track.bounds().contains(input.mouse_position) =>
+                {
+                    *current_state = SliderState::Active;
+                }
+                _ => (),
+            },
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) if current_state == &SliderState::Active => {
+                if self.track.bounds().contains(input.mouse_position) {
+                    *current_state = SliderState::Focused;
+                } else {
+                    *current_state = SliderState::Inactive;
+                }
+            }
+            _ => (),
+        }
+
+        if current_state == &SliderState::Active {
+            let new_value = denormalize(
+                self.track.normalized_at(input.mouse_position),
+                self.min,
+                self.max,
+            );
+
+            if let Some(on_change) = &self.on_change {
+                Ok(Some(on_change(new_value)))
+            } else {
+                Ok(None)
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let text_entry = internal_state
+            .get_state::<SliderInternalState>(&self.id)
+            .text_entry
+            .clone();
+        if let (Some(entry), Some(label), Some(font)) =
+            (text_entry, &self.text_label, &self.font)
+        {
+            let background = if entry.invalid {
+                vec4(0.5, 0.0, 0.0, 0.5)
+            } else {
+                vec4(0.0, 0.0, 0.0, 0.5)
+            };
+            Tile {
+                model: self.track.bounds(),
+                color: background,
+                ..Default::default()
+            }
+            .fill(frame)?;
+
+            frame.push_clip_rect(self.track.bounds());
+            Widget::<Message>::draw_frame(label, internal_state, frame)?;
+            let (_, before) = font.build_text_tiles(text_before_cursor(
+                &entry.text,
+                entry.cursor,
+            ));
+            let x = (self.track.bounds().left() + before.width()).round();
+            Line {
+                start: vec2(x, self.track.bounds().top()),
+                end: vec2(x, self.track.bounds().bottom()),
+                color: vec4(1.0, 1.0, 1.0, 0.9),
+                ..Default::default()
+            }
+            .draw(frame)?;
+            frame.pop_clip_rect();
+            return Ok(());
+        }
+
+        Tile {
+            model: self.track.line(),
+            color: vec4(0.0, 0.0, 0.0, 0.5),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        Tile {
+            model: self.cursor,
+            color: vec4(0.5, 0.5, 0.5, 0.9),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let text_entry = &internal_state
+            .get_state::<SliderInternalState>(&self.id)
+            .text_entry;
+        self.text_label = match (text_entry, &self.font) {
+            (Some(entry), Some(font)) => Some(Label::new(font, &entry.text)),
+            _ => None,
+        };
+        if let Some(label) = &mut self.text_label {
+            Widget::<Message>::dimensions(
+                label,
+                internal_state,
+                &Dimensions::new(f32::MAX, f32::MAX),
+            );
+        }
+
+        self.track.dimensions(max_size, self.height_ratio)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.track.set_top_left_position(position);
+        self.cursor = self
+            .track
+            .handle_at(normalize(self.value, self.min, self.max));
+
+        if let (Some(label), Some(font)) = (&mut self.text_label, &self.font) {
+            // center the typed text vertically in the slider
+            let bounds = self.track.bounds();
+            let offset = 0.5 * (bounds.height() - font.line_height());
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                position + vec2(0.0, offset),
+            );
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for Slider<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dragging_emits_the_new_value() {
+        use glfw::{Action, Modifiers, MouseButton, WindowEvent};
+
+        let mut internal_state = InternalState::new();
+        let mut slider = Slider::new(Id::new(1), 10.0, 20.0)
+            .value(10.0)
+            .on_change(|value| value);
+        Widget::dimensions(
+            &mut slider,
+            &mut internal_state,
+            &Dimensions::new(100.0, 100.0),
+        );
+        Widget::set_top_left_position(
+            &mut slider,
+            &mut internal_state,
+            vec2(0.0, 0.0),
+        );
+
+        let mut input = Input::new();
+        input.mouse_position = vec2(50.0, 5.0);
+        let mut send = |event| {
+            slider
+                .handle_event(&mut internal_state, &input, &event)
+                .unwrap()
+        };
+        assert_eq!(send(WindowEvent::CursorPos(50.0, 5.0)), None);
+        let pressed = send(WindowEvent::MouseButton(
+            MouseButton::Button1,
+            Action::Press,
+            Modifiers::empty(),
+        ));
+        assert!((pressed.unwrap() - 15.0).abs() < 1e-3);
+    }
+}
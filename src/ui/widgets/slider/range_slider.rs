@@ -0,0 +1,262 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions, Rect, Tile},
+        widgets::{
+            slider::track::{denormalize, normalize, Track},
+            Element, Widget,
+        },
+        Id, Input, InternalState,
+    },
+    vec4, Vec2, Vec4,
+};
+
+/// One of a RangeSlider's two handles.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum RangeHandle {
+    Low,
+    High,
+}
+
+/// A RangeSlider's state is stored in the UI InternalState so a drag
+/// persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct RangeSliderState {
+    /// The handle being dragged, if any.
+    dragging: Option<RangeHandle>,
+
+    /// True while the cursor is over the slider.
+    hovered: bool,
+}
+
+/// A slider with two handles which picks a low and a high value from a
+/// range. The low value is never above the high value.
+///
+/// Pressing the slider grabs whichever handle is closest to the cursor.
+pub struct RangeSlider<Message> {
+    id: Id,
+    min: f32,
+    max: f32,
+    low: f32,
+    high: f32,
+    track: Track,
+    low_handle: Rect,
+    high_handle: Rect,
+
+    /// The part of the line between the handles.
+    selection: Rect,
+
+    height_ratio: f32,
+
+    /// The color of the line between the handles.
+    selection_color: Vec4,
+
+    on_change: Option<Box<dyn Fn(f32, f32) -> Message>>,
+}
+
+impl<Message> RangeSlider<Message> {
+    /// Create a new range slider which selects the whole range.
+    pub fn new(id: Id, min: f32, max: f32) -> Self {
+        Self {
+            id,
+            min,
+            max,
+            low: min,
+            high: max,
+            track: Track::new(Axis::Horizontal),
+            low_handle: Rect::new(0.0, 0.0, 0.0, 0.0),
+            high_handle: Rect::new(0.0, 0.0, 0.0, 0.0),
+            selection: Rect::new(0.0, 0.0, 0.0, 0.0),
+            height_ratio: 1.0 / 10.0,
+            selection_color: vec4(0.3, 0.6, 1.0, 0.8),
+            on_change: None,
+        }
+    }
+
+    builder_field!(height_ratio, f32);
+    builder_field!(selection_color, Vec4);
+
+    /// Set the axis the handles move along. Vertical sliders have their min
+    /// value at the bottom. Sliders are horizontal by default.
+    pub fn axis(self, axis: Axis) -> Self {
+        Self {
+            track: Track::new(axis),
+            ..self
+        }
+    }
+
+    /// Set the selected values. Both are clamped to the min/max values and
+    /// they're swapped if low is above high.
+    pub fn values(self, low: f32, high: f32) -> Self {
+        let (lowest, highest) =
+            (self.min.min(self.max), self.min.max(self.max));
+        let low = low.clamp(lowest, highest);
+        let high = high.clamp(lowest, highest);
+        Self {
+            low: low.min(high),
+            high: low.max(high),
+            ..self
+        }
+    }
+
+    /// Emit a message with the new low and high values while a handle is
+    /// dragged.
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(f32, f32) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    /// The handle closest to the normalized position. When the handles
+    /// overlap, the low handle is picked below them and the high handle
+    /// above, so they can always be pulled apart.
+    fn closest_handle(&self, normalized: f32) -> RangeHandle {
+        let low = normalize(self.low, self.min, self.max);
+        let high = normalize(self.high, self.min, self.max);
+        let to_low = (normalized - low).abs();
+        let to_high = (normalized - high).abs();
+        if to_low < to_high || (to_low == to_high && normalized < low) {
+            RangeHandle::Low
+        } else {
+            RangeHandle::High
+        }
+    }
+}
+
+impl<Message> Widget<Message> for RangeSlider<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let bounds = self.track.bounds();
+        let normalized = self.track.normalized_at(input.mouse_position);
+        let state = internal_state.get_state_mut::<RangeSliderState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.hovered = bounds.contains(input.mouse_position);
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if bounds.contains(input.mouse_position) => {
+                state.dragging = Some(self.closest_handle(normalized));
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = None;
+            }
+            _ => (),
+        }
+
+        let value = denormalize(normalized, self.min, self.max);
+        let (low, high) = match state.dragging {
+            None => return Ok(None),
+            Some(RangeHandle::Low) => (value.min(self.high), self.high),
+            Some(RangeHandle::High) => (self.low, value.max(self.low)),
+        };
+        Ok(self
+            .on_change
+            .as_ref()
+            .map(|on_change| on_change(low, high)))
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<RangeSliderState>(&self.id);
+        Tile {
+            model: self.track.line(),
+            color: vec4(0.0, 0.0, 0.0, 0.5),
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Tile {
+            model: self.selection,
+            color: self.selection_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        for (handle, model) in &[
+            (RangeHandle::Low, self.low_handle),
+            (RangeHandle::High, self.high_handle),
+        ] {
+            let active = state.dragging == Some(*handle);
+            let color = if active || (state.hovered && state.dragging.is_none())
+            {
+                vec4(0.7, 0.7, 0.7, 0.9)
+            } else {
+                vec4(0.5, 0.5, 0.5, 0.9)
+            };
+            Tile {
+                model: *model,
+                color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.track.dimensions(max_size, self.height_ratio)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.track.set_top_left_position(position);
+        let low = normalize(self.low, self.min, self.max);
+        let high = normalize(self.high, self.min, self.max);
+        self.low_handle = self.track.handle_at(low);
+        self.high_handle = self.track.handle_at(high);
+        self.selection = self.track.span(low, high);
+    }
+}
+
+impl<Message> Into<Element<Message>> for RangeSlider<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_handles_can_be_pulled_apart() {
+        let slider =
+            RangeSlider::<()>::new(Id::new(1), 0.0, 10.0).values(5.0, 5.0);
+
+        assert_eq!(slider.closest_handle(0.2), RangeHandle::Low);
+        assert_eq!(slider.closest_handle(0.8), RangeHandle::High);
+        assert_eq!(slider.closest_handle(0.5), RangeHandle::High);
+    }
+}
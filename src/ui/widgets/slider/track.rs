@@ -0,0 +1,189 @@
+use crate::{
+    ui::primitives::{Axis, Dimensions, Rect},
+    vec2, Vec2,
+};
+
+/// The line a slider's handles move along and the space around it. Shared by
+/// the [`super::Slider`] and [`super::RangeSlider`] so both work along either
+/// axis.
+///
+/// Positions along the track are normalized, 0 is the left end of a
+/// horizontal track and the bottom of a vertical track.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Track {
+    axis: Axis,
+
+    /// The space occupied by the whole slider on screen.
+    bounds: Rect,
+
+    /// The line the handles move along.
+    line: Rect,
+
+    /// The size of each handle.
+    handle: Dimensions,
+}
+
+impl Track {
+    pub(super) fn new(axis: Axis) -> Self {
+        Self {
+            axis,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            line: Rect::new(0.0, 0.0, 0.0, 0.0),
+            handle: Dimensions::new(0.0, 0.0),
+        }
+    }
+
+    pub(super) fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    pub(super) fn line(&self) -> Rect {
+        self.line
+    }
+
+    /// A size with the given length along the track and thickness across it.
+    fn along_axis(&self, length: f32, thickness: f32) -> Dimensions {
+        match self.axis {
+            Axis::Horizontal => Dimensions::new(length, thickness),
+            Axis::Vertical => Dimensions::new(thickness, length),
+        }
+    }
+
+    /// Size the track to fill the available length. The track's thickness is
+    /// the thickness ratio times its length, and the line's thickness is the
+    /// same ratio of the track's thickness.
+    pub(super) fn dimensions(
+        &mut self,
+        max_size: &Dimensions,
+        thickness_ratio: f32,
+    ) -> Dimensions {
+        let (length, thickness) = match self.axis {
+            Axis::Horizontal => (
+                max_size.width,
+                max_size.height.min(max_size.width * thickness_ratio),
+            ),
+            Axis::Vertical => (
+                max_size.height,
+                max_size.width.min(max_size.height * thickness_ratio),
+            ),
+        };
+        self.bounds = self.along_axis(length, thickness).as_rect();
+        self.handle = Dimensions::new(thickness * 0.9, thickness * 0.9);
+
+        let handle_length = thickness * 0.9;
+        self.line = self
+            .along_axis(length - handle_length, thickness * thickness_ratio)
+            .as_rect();
+
+        self.bounds.dimensions()
+    }
+
+    /// Move the track and center the line inside of it.
+    pub(super) fn set_top_left_position(&mut self, position: Vec2) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        let line_offset = vec2(
+            0.5 * (self.bounds.width() - self.line.width()),
+            0.5 * (self.bounds.height() - self.line.height()),
+        );
+        self.line = self.line.set_top_left_position(position + line_offset);
+    }
+
+    /// The normalized position along the line which is closest to the point.
+    pub(super) fn normalized_at(&self, point: Vec2) -> f32 {
+        let normalized = match self.axis {
+            Axis::Horizontal => {
+                (point.x - self.line.left()) / self.line.width()
+            }
+            Axis::Vertical => {
+                (self.line.bottom() - point.y) / self.line.height()
+            }
+        };
+        if normalized.is_finite() {
+            normalized.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The space occupied by a handle at the normalized position.
+    pub(super) fn handle_at(&self, normalized: f32) -> Rect {
+        let center = match self.axis {
+            Axis::Horizontal => vec2(
+                self.line.left() + normalized * self.line.width(),
+                self.bounds.top() + 0.5 * self.bounds.height(),
+            ),
+            Axis::Vertical => vec2(
+                self.bounds.left() + 0.5 * self.bounds.width(),
+                self.line.bottom() - normalized * self.line.height(),
+            ),
+        };
+        Rect::centered_at(
+            center.x,
+            center.y,
+            self.handle.width,
+            self.handle.height,
+        )
+    }
+
+    /// The part of the line between two normalized positions.
+    pub(super) fn span(&self, start: f32, end: f32) -> Rect {
+        let (start, end) = (start.min(end), start.max(end));
+        match self.axis {
+            Axis::Horizontal => Rect::new(
+                self.line.top(),
+                self.line.left() + start * self.line.width(),
+                self.line.bottom(),
+                self.line.left() + end * self.line.width(),
+            ),
+            Axis::Vertical => Rect::new(
+                self.line.bottom() - end * self.line.height(),
+                self.line.left(),
+                self.line.bottom() - start * self.line.height(),
+                self.line.right(),
+            ),
+        }
+    }
+}
+
+/// Map a value in the range to 0 at min and 1 at max.
+pub(super) fn normalize(value: f32, min: f32, max: f32) -> f32 {
+    let range = max - min;
+    if range == 0.0 {
+        0.0
+    } else {
+        ((value - min) / range).clamp(0.0, 1.0)
+    }
+}
+
+/// Map 0 to min and 1 to max.
+pub(super) fn denormalize(normalized: f32, min: f32, max: f32) -> f32 {
+    min + normalized * (max - min)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vertical_tracks_increase_upwards() {
+        let mut track = Track::new(Axis::Vertical);
+        track.dimensions(&Dimensions::new(100.0, 110.0), 0.1);
+        track.set_top_left_position(vec2(0.0, 0.0));
+
+        // the handle is 9.9 pixels, so the line covers the rest of the length
+        let line = track.line();
+        assert!((line.height() - 100.1).abs() < 1e-3);
+        assert_eq!(track.normalized_at(vec2(0.0, line.bottom())), 0.0);
+        assert_eq!(track.normalized_at(vec2(0.0, -50.0)), 1.0);
+
+        let top = track.handle_at(1.0);
+        assert!((top.top() + 0.5 * top.height() - line.top()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_normalize_is_relative_to_min() {
+        assert_eq!(normalize(15.0, 10.0, 20.0), 0.5);
+        assert_eq!(denormalize(0.5, 10.0, 20.0), 15.0);
+        assert_eq!(normalize(1.0, 1.0, 1.0), 0.0);
+    }
+}
@@ -0,0 +1,155 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::Dimensions,
+        widgets::{Element, Widget},
+        Input, InternalState,
+    },
+    Vec2,
+};
+
+/// A Spacer is empty space in a layout. It never draws or handles events.
+///
+/// Each side is either a fixed size or fills all of the space it's given.
+/// Filling spacers should be added to a [`super::Row`] or [`super::Col`]
+/// with `flex_child`, or `spacer`, so they only take the space left over by
+/// their siblings and push them apart.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Spacer {
+    /// The spacer's width, or None to fill the available width.
+    width: Option<f32>,
+
+    /// The spacer's height, or None to fill the available height.
+    height: Option<f32>,
+}
+
+impl Spacer {
+    /// Create a spacer with a fixed size.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width: Some(width.max(0.0)),
+            height: Some(height.max(0.0)),
+        }
+    }
+
+    /// Create a spacer which is `width` wide and has no height, for gaps in
+    /// a row.
+    pub fn horizontal(width: f32) -> Self {
+        Self::new(width, 0.0)
+    }
+
+    /// Create a spacer which is `height` tall and has no width, for gaps in
+    /// a column.
+    pub fn vertical(height: f32) -> Self {
+        Self::new(0.0, height)
+    }
+
+    /// Create a spacer which fills the available width and has no height.
+    pub fn fill_horizontal() -> Self {
+        Self {
+            width: None,
+            height: Some(0.0),
+        }
+    }
+
+    /// Create a spacer which fills the available height and has no width.
+    pub fn fill_vertical() -> Self {
+        Self {
+            width: Some(0.0),
+            height: None,
+        }
+    }
+
+    /// Create a spacer which fills all of the available space.
+    pub fn fill() -> Self {
+        Self {
+            width: None,
+            height: None,
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Spacer {
+    fn handle_event(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _input: &Input,
+        _event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        _internal_state: &mut InternalState,
+        _frame: &mut Frame,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        _internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        Dimensions::new(
+            self.width.unwrap_or(max_size.width),
+            self.height.unwrap_or(max_size.height),
+        )
+        .min(max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        _internal_state: &mut InternalState,
+        _position: Vec2,
+    ) {
+    }
+}
+
+impl<Message> Into<Element<Message>> for Spacer
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ui::{
+        primitives::Justify,
+        widgets::{Canvas, Row},
+    };
+
+    #[test]
+    fn test_spacers_push_siblings_apart() {
+        let mut internal_state = InternalState::new();
+        let max_size = Dimensions::new(200.0, 100.0);
+        let mut measure = |spacer: &mut Spacer| {
+            let size = Widget::<()>::dimensions(
+                spacer,
+                &mut internal_state,
+                &max_size,
+            );
+            (size.width, size.height)
+        };
+        assert_eq!(measure(&mut Spacer::new(20.0, 300.0)), (20.0, 100.0));
+        assert_eq!(measure(&mut Spacer::fill_horizontal()), (200.0, 0.0));
+        assert_eq!(measure(&mut Spacer::fill()), (200.0, 100.0));
+
+        // the spacer takes the leftover width but not the row's height
+        let square = || Canvas::<()>::new(|_, _| Ok(())).size(10.0, 10.0);
+        let mut row = Row::new()
+            .child(square(), Justify::Begin)
+            .spacer(1.0)
+            .child(square(), Justify::Begin);
+        let size = row.dimensions(&mut internal_state, &max_size);
+        assert_eq!((size.width, size.height), (200.0, 10.0));
+    }
+}
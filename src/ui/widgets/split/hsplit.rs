@@ -0,0 +1,145 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions},
+        widgets::{split::SplitPanes, Element, OverlayEvent, Widget},
+        Id, Input, InternalState,
+    },
+    Vec2, Vec4,
+};
+
+/// A widget which splits the available area horizontally for a left side and
+/// right side widget. Drag the handle between the sides to resize them.
+pub struct HSplit<Message> {
+    panes: SplitPanes<Message>,
+}
+
+impl<Message> HSplit<Message> {
+    /// Create a new split which gives each side half of the space until the
+    /// handle is dragged.
+    pub fn new(id: Id) -> Self {
+        Self {
+            panes: SplitPanes::new(id, Axis::Horizontal),
+        }
+    }
+
+    /// Set the widget which occupies the left side of the hsplit.
+    pub fn left<E>(self, element: E) -> Self
+    where
+        E: Into<Element<Message>>,
+    {
+        Self {
+            panes: self.panes.first(element.into()),
+        }
+    }
+
+    /// Set the widget which occupies the right side of the hsplit.
+    pub fn right<E>(self, element: E) -> Self
+    where
+        E: Into<Element<Message>>,
+    {
+        Self {
+            panes: self.panes.second(element.into()),
+        }
+    }
+
+    /// Set the fraction of the width given to the left side before the
+    /// handle is dragged.
+    pub fn ratio(self, ratio: f32) -> Self {
+        Self {
+            panes: self.panes.ratio(ratio),
+        }
+    }
+
+    /// Set the narrowest either side can be dragged to.
+    pub fn min_pane_size(self, min_pane_size: f32) -> Self {
+        Self {
+            panes: self.panes.min_pane_size(min_pane_size),
+        }
+    }
+
+    /// Set the width of the handle between the sides.
+    pub fn handle_thickness(self, handle_thickness: f32) -> Self {
+        Self {
+            panes: self.panes.handle_thickness(handle_thickness),
+        }
+    }
+
+    /// Set the color of the handle between the sides.
+    pub fn handle_color(self, handle_color: Vec4) -> Self {
+        Self {
+            panes: self.panes.handle_color(handle_color),
+        }
+    }
+
+    /// Set the color of the handle while it's hovered or dragged.
+    pub fn handle_hover_color(self, handle_hover_color: Vec4) -> Self {
+        Self {
+            panes: self.panes.handle_hover_color(handle_hover_color),
+        }
+    }
+}
+
+impl<Message> Widget<Message> for HSplit<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        self.panes.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.panes.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.panes.dimensions(internal_state, max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.panes.set_top_left_position(internal_state, position)
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.panes
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.panes.draw_overlay(internal_state, frame)
+    }
+}
+
+impl<Message> Into<Element<Message>> for HSplit<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
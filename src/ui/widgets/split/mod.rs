@@ -0,0 +1,371 @@
+//! Split widgets divide the available space between two panes with a handle
+//! which can be dragged to resize them.
+//!
+//! [`HSplit`] places its panes side by side and [`VSplit`] stacks them. Both
+//! share the same implementation, which works along either axis. The split's
+//! ratio is stored in the UI InternalState so it persists between views.
+
+mod hsplit;
+mod vsplit;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions, Rect, Tile},
+        widgets::{Element, OverlayEvent, Widget},
+        CursorShape, Id, Input, InternalState,
+    },
+    vec2, Vec2, Vec4,
+};
+
+pub use self::{hsplit::HSplit, vsplit::VSplit};
+
+/// A split's state is stored in the UI InternalState so the ratio persists
+/// between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct SplitState {
+    /// The fraction of the space given to the first pane. None until the
+    /// handle is dragged for the first time, the split's initial ratio is
+    /// used until then.
+    ratio: Option<f32>,
+
+    /// True while the handle is being dragged.
+    dragging: bool,
+
+    /// True while the cursor is over the handle.
+    hovered: bool,
+}
+
+/// The two panes and the handle between them, arranged along an axis.
+pub(super) struct SplitPanes<Message> {
+    id: Id,
+    axis: Axis,
+    first: Option<Element<Message>>,
+    second: Option<Element<Message>>,
+
+    /// The space occupied by the whole split on screen.
+    bounds: Rect,
+
+    /// The space occupied by the handle on screen.
+    handle: Rect,
+
+    /// The fraction of the space given to the first pane before the handle
+    /// has been dragged.
+    ratio: f32,
+
+    /// The smallest size either pane can be dragged to, along the axis.
+    min_pane_size: f32,
+
+    /// The handle's size along the axis.
+    handle_thickness: f32,
+
+    /// The handle's color.
+    handle_color: Option<Vec4>,
+
+    /// The handle's color while it's hovered or dragged.
+    handle_hover_color: Option<Vec4>,
+}
+
+impl<Message> SplitPanes<Message> {
+    fn new(id: Id, axis: Axis) -> Self {
+        Self {
+            id,
+            axis,
+            first: None,
+            second: None,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            handle: Rect::new(0.0, 0.0, 0.0, 0.0),
+            ratio: 0.5,
+            min_pane_size: 16.0,
+            handle_thickness: 4.0,
+            handle_color: None,
+            handle_hover_color: None,
+        }
+    }
+
+    builder_field!(min_pane_size, f32);
+    builder_field!(handle_thickness, f32);
+    builder_field_some!(handle_color, Vec4);
+    builder_field_some!(handle_hover_color, Vec4);
+
+    /// Set the initial fraction of the space given to the first pane.
+    fn ratio(self, ratio: f32) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    fn first(self, first: Element<Message>) -> Self {
+        Self {
+            first: Some(first),
+            ..self
+        }
+    }
+
+    fn second(self, second: Element<Message>) -> Self {
+        Self {
+            second: Some(second),
+            ..self
+        }
+    }
+
+    /// The length of a size along the split's axis.
+    fn along_axis(&self, dimensions: Dimensions) -> f32 {
+        match self.axis {
+            Axis::Horizontal => dimensions.width,
+            Axis::Vertical => dimensions.height,
+        }
+    }
+
+    /// A size with the given length along the split's axis which fills the
+    /// split across the axis.
+    fn pane_dimensions(&self, length: f32) -> Dimensions {
+        match self.axis {
+            Axis::Horizontal => Dimensions::new(length, self.bounds.height()),
+            Axis::Vertical => Dimensions::new(self.bounds.width(), length),
+        }
+    }
+
+    /// An offset of the given length along the split's axis.
+    fn offset(&self, length: f32) -> Vec2 {
+        match self.axis {
+            Axis::Horizontal => vec2(length, 0.0),
+            Axis::Vertical => vec2(0.0, length),
+        }
+    }
+
+    /// The space which can be divided between the panes.
+    fn available_length(&self) -> f32 {
+        (self.along_axis(self.bounds.dimensions()) - self.handle_thickness)
+            .max(0.0)
+    }
+
+    /// The ratio which puts the center of the handle under the cursor.
+    fn ratio_at(&self, point: Vec2) -> f32 {
+        let available = self.available_length();
+        if available <= 0.0 {
+            return self.ratio;
+        }
+        let start = match self.axis {
+            Axis::Horizontal => point.x - self.bounds.left(),
+            Axis::Vertical => point.y - self.bounds.top(),
+        };
+        let min_ratio = (self.min_pane_size / available).min(0.5);
+        ((start - 0.5 * self.handle_thickness) / available)
+            .clamp(min_ratio, 1.0 - min_ratio)
+    }
+}
+
+impl<Message> Widget<Message> for SplitPanes<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mut state = *internal_state.get_state::<SplitState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.hovered = self.handle.contains(input.mouse_position);
+                if state.dragging {
+                    state.ratio = Some(self.ratio_at(input.mouse_position));
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if state.hovered => {
+                state.dragging = true;
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = false;
+            }
+            _ => (),
+        }
+        *internal_state.get_state_mut::<SplitState>(&self.id) = state;
+
+        if let Some(elem) = &mut self.first {
+            if let Some(message) =
+                elem.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        if let Some(elem) = &mut self.second {
+            if let Some(message) =
+                elem.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+
+        // asked after the panes so a drag which crosses them keeps the
+        // resize cursor
+        if matches!(*event, WindowEvent::CursorPos(..))
+            && (state.hovered || state.dragging)
+        {
+            internal_state.request_cursor(match self.axis {
+                Axis::Horizontal => CursorShape::HorizontalResize,
+                Axis::Vertical => CursorShape::VerticalResize,
+            });
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        if let Some(elem) = &self.first {
+            elem.draw_frame(internal_state, frame)?;
+        }
+        if let Some(elem) = &self.second {
+            elem.draw_frame(internal_state, frame)?;
+        }
+
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<SplitState>(&self.id);
+        let color = if state.hovered || state.dragging {
+            self.handle_hover_color.unwrap_or(theme.detail_color)
+        } else {
+            self.handle_color.unwrap_or(theme.highlight_color)
+        };
+        Tile {
+            model: self.handle,
+            color,
+            ..Default::default()
+        }
+        .fill(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        // splits always occupy all available space
+        self.bounds = max_size.as_rect();
+
+        let ratio = internal_state
+            .get_state::<SplitState>(&self.id)
+            .ratio
+            .unwrap_or(self.ratio);
+        let available = self.available_length();
+        let first_length = (ratio * available).round();
+        let first_size = self.pane_dimensions(first_length);
+        let second_size = self.pane_dimensions(available - first_length);
+
+        if let Some(elem) = &mut self.first {
+            elem.dimensions(internal_state, &first_size);
+        }
+        if let Some(elem) = &mut self.second {
+            elem.dimensions(internal_state, &second_size);
+        }
+        self.handle = self
+            .pane_dimensions(self.handle_thickness)
+            .as_rect()
+            .translate(self.offset(first_length));
+
+        *max_size
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let handle_offset = self.handle.top_left - self.bounds.top_left;
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.handle =
+            self.handle.set_top_left_position(position + handle_offset);
+
+        if let Some(elem) = &mut self.first {
+            elem.set_top_left_position(internal_state, position)
+        }
+        let second_position =
+            self.handle.top_left + self.offset(self.handle_thickness);
+        if let Some(elem) = &mut self.second {
+            elem.set_top_left_position(internal_state, second_position)
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        if let Some(elem) = &mut self.second {
+            let result =
+                elem.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        if let Some(elem) = &mut self.first {
+            let result =
+                elem.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        if let Some(elem) = &self.first {
+            elem.draw_overlay(internal_state, frame)?;
+        }
+        if let Some(elem) = &self.second {
+            elem.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ratio_at_centers_the_handle_and_respects_min_pane_size() {
+        let mut panes = SplitPanes::<()>::new(Id::new(1), Axis::Horizontal)
+            .handle_thickness(4.0)
+            .min_pane_size(10.0);
+        panes.bounds = Rect::new(0.0, 100.0, 50.0, 204.0);
+
+        // 100 pixels are available, the handle's center is 2 pixels past the
+        // end of the first pane
+        assert!((panes.ratio_at(vec2(127.0, 0.0)) - 0.25).abs() < 1e-5);
+        assert!((panes.ratio_at(vec2(0.0, 0.0)) - 0.1).abs() < 1e-5);
+        assert!((panes.ratio_at(vec2(500.0, 0.0)) - 0.9).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_vertical_ratio_at_follows_the_cursor_height() {
+        let mut panes = SplitPanes::<()>::new(Id::new(1), Axis::Vertical)
+            .handle_thickness(4.0)
+            .min_pane_size(10.0);
+        panes.bounds = Rect::new(20.0, 0.0, 124.0, 50.0);
+
+        // horizontal cursor movement doesn't change a vertical split's ratio
+        assert!((panes.ratio_at(vec2(0.0, 97.0)) - 0.75).abs() < 1e-5);
+        assert!((panes.ratio_at(vec2(500.0, 97.0)) - 0.75).abs() < 1e-5);
+    }
+}
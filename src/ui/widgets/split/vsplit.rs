@@ -0,0 +1,145 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Axis, Dimensions},
+        widgets::{split::SplitPanes, Element, OverlayEvent, Widget},
+        Id, Input, InternalState,
+    },
+    Vec2, Vec4,
+};
+
+/// A widget which splits the available area vertically for a top and bottom
+/// widget. Drag the handle between them to resize them.
+pub struct VSplit<Message> {
+    panes: SplitPanes<Message>,
+}
+
+impl<Message> VSplit<Message> {
+    /// Create a new split which gives the top and bottom half of the space
+    /// until the handle is dragged.
+    pub fn new(id: Id) -> Self {
+        Self {
+            panes: SplitPanes::new(id, Axis::Vertical),
+        }
+    }
+
+    /// Set the widget which occupies the top of the vsplit.
+    pub fn top<E>(self, element: E) -> Self
+    where
+        E: Into<Element<Message>>,
+    {
+        Self {
+            panes: self.panes.first(element.into()),
+        }
+    }
+
+    /// Set the widget which occupies the bottom of the vsplit.
+    pub fn bottom<E>(self, element: E) -> Self
+    where
+        E: Into<Element<Message>>,
+    {
+        Self {
+            panes: self.panes.second(element.into()),
+        }
+    }
+
+    /// Set the fraction of the height given to the top before the handle is
+    /// dragged.
+    pub fn ratio(self, ratio: f32) -> Self {
+        Self {
+            panes: self.panes.ratio(ratio),
+        }
+    }
+
+    /// Set the shortest the top or bottom can be dragged to.
+    pub fn min_pane_size(self, min_pane_size: f32) -> Self {
+        Self {
+            panes: self.panes.min_pane_size(min_pane_size),
+        }
+    }
+
+    /// Set the height of the handle between the top and bottom.
+    pub fn handle_thickness(self, handle_thickness: f32) -> Self {
+        Self {
+            panes: self.panes.handle_thickness(handle_thickness),
+        }
+    }
+
+    /// Set the color of the handle between the top and bottom.
+    pub fn handle_color(self, handle_color: Vec4) -> Self {
+        Self {
+            panes: self.panes.handle_color(handle_color),
+        }
+    }
+
+    /// Set the color of the handle while it's hovered or dragged.
+    pub fn handle_hover_color(self, handle_hover_color: Vec4) -> Self {
+        Self {
+            panes: self.panes.handle_hover_color(handle_hover_color),
+        }
+    }
+}
+
+impl<Message> Widget<Message> for VSplit<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        self.panes.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.panes.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.panes.dimensions(internal_state, max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.panes.set_top_left_position(internal_state, position)
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.panes
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.panes.draw_overlay(internal_state, frame)
+    }
+}
+
+impl<Message> Into<Element<Message>> for VSplit<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,238 @@
+use ::anyhow::Result;
+
+use crate::{
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::Dimensions,
+        widgets::{Element, HAlignment, OverlayEvent, VAlignment, Widget},
+        Input, InternalState,
+    },
+    vec2, Vec2,
+};
+
+/// One of a stack's children and where it's aligned.
+struct StackChild<Message> {
+    element: Element<Message>,
+    h_alignment: HAlignment,
+    v_alignment: VAlignment,
+
+    /// Moves the child from its aligned position, e.g. to let a badge hang
+    /// over the corner of an icon.
+    offset: Vec2,
+
+    /// The child's size from the last layout.
+    size: Dimensions,
+}
+
+/// A Stack draws its children on top of each other in the same space, like
+/// a badge over an icon or a caption over an image. Each child is aligned
+/// within the stack on its own.
+///
+/// The stack is as big as its largest child. Later children are drawn on
+/// top and see events first.
+pub struct Stack<Message> {
+    children: Vec<StackChild<Message>>,
+    size: Dimensions,
+}
+
+impl<Message> Default for Stack<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message> Stack<Message> {
+    pub fn new() -> Self {
+        Self {
+            children: vec![],
+            size: Dimensions::new(0.0, 0.0),
+        }
+    }
+
+    /// Add a child on top of the existing children.
+    pub fn child<W>(
+        self,
+        child: W,
+        h_alignment: HAlignment,
+        v_alignment: VAlignment,
+    ) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.child_with_offset(child, h_alignment, v_alignment, vec2(0.0, 0.0))
+    }
+
+    /// Add a child on top of the existing children which is moved by the
+    /// offset after it's aligned. The offset doesn't change the stack's
+    /// size, so the child can hang outside of it.
+    pub fn child_with_offset<W>(
+        mut self,
+        child: W,
+        h_alignment: HAlignment,
+        v_alignment: VAlignment,
+        offset: Vec2,
+    ) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.children.push(StackChild {
+            element: child.into(),
+            h_alignment,
+            v_alignment,
+            offset,
+            size: Dimensions::new(0.0, 0.0),
+        });
+        self
+    }
+}
+
+/// The distance from the top left of the stack to the top left of a child
+/// with the given alignment.
+fn aligned_offset(
+    h_alignment: HAlignment,
+    v_alignment: VAlignment,
+    stack: Dimensions,
+    child: Dimensions,
+) -> Vec2 {
+    let x = match h_alignment {
+        HAlignment::Left => 0.0,
+        HAlignment::Center => 0.5 * (stack.width - child.width),
+        HAlignment::Right => stack.width - child.width,
+    };
+    let y = match v_alignment {
+        VAlignment::Top => 0.0,
+        VAlignment::Center => 0.5 * (stack.height - child.height),
+        VAlignment::Bottom => stack.height - child.height,
+    };
+    vec2(x, y)
+}
+
+impl<Message> Widget<Message> for Stack<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        // later children are drawn on top, so they see events first
+        for child in self.children.iter_mut().rev() {
+            if let Some(message) =
+                child.element.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for child in &self.children {
+            child.element.draw_frame(internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let mut size = Dimensions::new(0.0, 0.0);
+        for child in &mut self.children {
+            child.size = child.element.dimensions(internal_state, max_size);
+            size = size.max(&child.size);
+        }
+        self.size = size.min(max_size);
+        self.size
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        for child in &mut self.children {
+            let offset = aligned_offset(
+                child.h_alignment,
+                child.v_alignment,
+                self.size,
+                child.size,
+            );
+            child.element.set_top_left_position(
+                internal_state,
+                position + offset + child.offset,
+            );
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        for child in self.children.iter_mut().rev() {
+            let result = child.element.handle_overlay_event(
+                internal_state,
+                input,
+                event,
+            )?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for child in &self.children {
+            child.element.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Stack<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ui::widgets::Spacer;
+
+    #[test]
+    fn test_children_are_aligned_in_the_largest_child() {
+        let mut internal_state = InternalState::new();
+        let mut stack = Stack::<()>::new()
+            .child(Spacer::new(40.0, 30.0), HAlignment::Left, VAlignment::Top)
+            .child(Spacer::new(10.0, 50.0), HAlignment::Right, VAlignment::Top);
+        let size = stack
+            .dimensions(&mut internal_state, &Dimensions::new(100.0, 40.0));
+        assert_eq!((size.width, size.height), (40.0, 40.0));
+
+        let stack = Dimensions::new(40.0, 30.0);
+        let badge = Dimensions::new(10.0, 10.0);
+        let at = |h, v| aligned_offset(h, v, stack, badge);
+        assert_eq!(at(HAlignment::Right, VAlignment::Top), vec2(30.0, 0.0));
+        assert_eq!(
+            at(HAlignment::Center, VAlignment::Center),
+            vec2(15.0, 10.0)
+        );
+        assert_eq!(at(HAlignment::Left, VAlignment::Bottom), vec2(0.0, 20.0));
+    }
+}
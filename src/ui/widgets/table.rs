@@ -0,0 +1,668 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{DimensionList, Dimensions, Justify, Line, Rect, Tile},
+        widgets::{Element, Label, OverlayEvent, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, Vec2, Vec4,
+};
+
+/// The order of a sorted column.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    /// The opposite order.
+    pub fn reversed(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+}
+
+/// A Table's hover and scroll state is stored in the UI InternalState.
+#[derive(Debug, Copy, Clone, Default)]
+struct TableState {
+    /// The index of the row under the cursor.
+    hovered_row: Option<usize>,
+
+    /// The index of the column whose header is under the cursor.
+    hovered_header: Option<usize>,
+
+    /// How far the rows have been scrolled down, in pixels.
+    scroll: f32,
+}
+
+/// A single column in a table.
+struct Column {
+    /// The column's title, drawn in the header row.
+    header: Label,
+
+    /// The size of the header's text.
+    header_size: Dimensions,
+
+    /// How each cell is aligned within the column.
+    justify: Justify,
+
+    /// The column's width, including padding.
+    width: f32,
+
+    /// The column's offset from the left side of the table.
+    left: f32,
+}
+
+/// A Table arranges cells in rows below a fixed header row. Each column is
+/// as wide as its widest cell.
+///
+/// The table does not own the data it shows. Selection and sorting belong to
+/// the application: the table emits messages when a row or header is clicked
+/// and draws whichever row and sort order the application reports.
+///
+/// # Controls
+///
+/// - Click a row to select it, double-click a row to open it.
+/// - Click a header to sort by that column. Clicking the sorted column again
+///   reverses the order.
+/// - Scroll the mouse wheel to scroll the rows. The header stays in place.
+pub struct Table<Message> {
+    /// The ID used to look up the table's hover and scroll state.
+    id: Id,
+
+    /// The font used for each column's header.
+    font: Font,
+
+    /// The table's columns, from left to right.
+    columns: Vec<Column>,
+
+    /// The cells in each row. Rows can have fewer cells than there are
+    /// columns, extra cells are ignored.
+    rows: Vec<Vec<Element<Message>>>,
+
+    /// The size of each cell, computed when laying out the table.
+    cell_sizes: Vec<Vec<Dimensions>>,
+
+    /// The height of each row, including padding.
+    row_heights: Vec<f32>,
+
+    /// The space occupied by each row on screen.
+    row_bounds: Vec<Rect>,
+
+    /// The space occupied by the table on screen.
+    bounds: Rect,
+
+    /// The space occupied by the header row on screen.
+    header_bounds: Rect,
+
+    /// The visible region the rows are drawn within.
+    body_bounds: Rect,
+
+    /// The space between each cell's contents and the edge of the cell.
+    /// None uses the theme's padding.
+    padding: Option<f32>,
+
+    /// The number of pixels scrolled for each step of the mouse wheel.
+    scroll_speed: f32,
+
+    /// The row drawn as selected.
+    selected: Option<usize>,
+
+    /// The column and order the application has sorted the rows by.
+    sorted_by: Option<(usize, SortOrder)>,
+
+    /// The color behind the header row.
+    header_color: Option<Vec4>,
+
+    /// The color behind the hovered row or header.
+    hover_color: Option<Vec4>,
+
+    /// The color behind the selected row.
+    selected_color: Option<Vec4>,
+
+    /// The color of the line between the header and the rows, and the sort
+    /// arrow.
+    accent_color: Option<Vec4>,
+
+    /// The function used to build a message when a row is selected.
+    on_select: Option<Box<dyn Fn(usize) -> Message>>,
+
+    /// The function used to build a message when a header is clicked.
+    on_sort: Option<Box<dyn Fn(usize, SortOrder) -> Message>>,
+
+    /// The function used to build a message when a row is double-clicked.
+    on_open: Option<Box<dyn Fn(usize) -> Message>>,
+}
+
+impl<Message> Table<Message> {
+    /// Create a new table without any columns or rows.
+    pub fn new(id: Id, font: &Font) -> Self {
+        Self {
+            id,
+            font: font.clone(),
+            columns: vec![],
+            rows: vec![],
+            cell_sizes: vec![],
+            row_heights: vec![],
+            row_bounds: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            header_bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            body_bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            padding: None,
+            scroll_speed: 40.0,
+            selected: None,
+            sorted_by: None,
+            header_color: None,
+            hover_color: None,
+            selected_color: None,
+            accent_color: None,
+            on_select: None,
+            on_sort: None,
+            on_open: None,
+        }
+    }
+
+    builder_field_some!(padding, f32);
+    builder_field!(scroll_speed, f32);
+    builder_field_some!(selected, usize);
+    builder_field_some!(header_color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(selected_color, Vec4);
+    builder_field_some!(accent_color, Vec4);
+
+    /// Draw the given column's header as sorted in the given order.
+    pub fn sorted_by(self, column: usize, order: SortOrder) -> Self {
+        Self {
+            sorted_by: Some((column, order)),
+            ..self
+        }
+    }
+
+    /// Add a column with cells aligned to the left.
+    pub fn column<T>(self, title: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.justified_column(title, Justify::Begin)
+    }
+
+    /// Add a column with cells aligned within the column.
+    pub fn justified_column<T>(mut self, title: T, justify: Justify) -> Self
+    where
+        T: AsRef<str>,
+    {
+        self.columns.push(Column {
+            header: Label::new(&self.font, title),
+            header_size: Dimensions::new(0.0, 0.0),
+            justify,
+            width: 0.0,
+            left: 0.0,
+        });
+        self
+    }
+
+    /// Add a row of cells, one for each column.
+    pub fn row<I, E>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Element<Message>>,
+    {
+        self.rows
+            .push(cells.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+
+    /// Set the function used to build a message when the user selects a row.
+    pub fn on_select<F>(self, on_select_fn: F) -> Self
+    where
+        F: 'static + Fn(usize) -> Message,
+    {
+        Self {
+            on_select: Some(Box::new(on_select_fn)),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user double-clicks
+    /// a row. The first click of the double-click selects the row as usual.
+    pub fn on_open<F>(self, on_open_fn: F) -> Self
+    where
+        F: 'static + Fn(usize) -> Message,
+    {
+        Self {
+            on_open: Some(Box::new(on_open_fn)),
+            ..self
+        }
+    }
+
+    /// Set the function used to build a message when the user clicks a
+    /// header. The function is given the column and the order the user asked
+    /// for.
+    pub fn on_sort<F>(self, on_sort_fn: F) -> Self
+    where
+        F: 'static + Fn(usize, SortOrder) -> Message,
+    {
+        Self {
+            on_sort: Some(Box::new(on_sort_fn)),
+            ..self
+        }
+    }
+
+    /// The space reserved to the right of each header for the sort arrow.
+    fn arrow_space(&self) -> f32 {
+        0.75 * self.font.line_height()
+    }
+
+    /// The total height of every row.
+    fn content_height(&self) -> f32 {
+        self.row_heights.iter().sum()
+    }
+
+    /// The furthest the rows can be scrolled.
+    fn max_scroll(&self) -> f32 {
+        (self.content_height() - self.body_bounds.height()).max(0.0)
+    }
+
+    /// The space occupied by a column's header on screen.
+    fn header_cell(&self, column: &Column) -> Rect {
+        let left = self.bounds.left() + column.left;
+        Rect::new(
+            self.header_bounds.top(),
+            left,
+            self.header_bounds.bottom(),
+            left + column.width,
+        )
+    }
+
+    /// The index of the header under the given point.
+    fn header_at(&self, point: Vec2) -> Option<usize> {
+        self.columns
+            .iter()
+            .position(|column| self.header_cell(column).contains(point))
+    }
+
+    /// The index of the row under the given point. Only rows in the visible
+    /// part of the table are considered.
+    fn row_at(&self, point: Vec2) -> Option<usize> {
+        if !self.body_bounds.contains(point) {
+            return None;
+        }
+        self.row_bounds.iter().position(|row| row.contains(point))
+    }
+
+    /// Mouse buttons are only forwarded to the cells when the mouse is over
+    /// the visible part of the rows. Otherwise scrolled-out cells could still
+    /// be clicked.
+    fn is_hidden_from_cells(
+        &self,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> bool {
+        use glfw::{Action, WindowEvent};
+
+        match *event {
+            WindowEvent::MouseButton(_, Action::Press, _)
+            | WindowEvent::Scroll(..) => {
+                !self.body_bounds.contains(input.mouse_position)
+            }
+            _ => false,
+        }
+    }
+
+    /// The message for a click on the given column's header.
+    fn sort_requested(&self, column: usize) -> Option<Message> {
+        let order = match self.sorted_by {
+            Some((sorted, order)) if sorted == column => order.reversed(),
+            _ => SortOrder::Ascending,
+        };
+        self.on_sort.as_ref().map(|on_sort| on_sort(column, order))
+    }
+
+    /// The space between each cell's contents and the edge of the cell.
+    fn themed_padding(&self, theme: &Theme) -> f32 {
+        self.padding
+            .unwrap_or(theme.padding * self.font.line_height())
+    }
+
+    fn draw_sort_arrow(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        column: &Column,
+        order: SortOrder,
+    ) -> Result<()> {
+        let cell = self.header_cell(column);
+        let size = 0.2 * self.font.line_height();
+        let center = vec2(
+            cell.right()
+                - self.themed_padding(theme)
+                - 0.5 * self.arrow_space(),
+            0.5 * (cell.top() + cell.bottom()),
+        );
+
+        // a chevron pointing up when ascending and down when descending
+        let direction = match order {
+            SortOrder::Ascending => -1.0,
+            SortOrder::Descending => 1.0,
+        };
+        let tip = center + vec2(0.0, 0.5 * size * direction);
+        let line = Line {
+            color: self.accent_color.unwrap_or(theme.detail_color),
+            width: 1.5,
+            ..Default::default()
+        };
+        for &side in &[-1.0, 1.0] {
+            Line {
+                start: center + vec2(size * side, -0.5 * size * direction),
+                end: tip,
+                ..line
+            }
+            .draw(frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Widget<Message> for Table<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mut state = *internal_state.get_state::<TableState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.hovered_row = self.row_at(input.mouse_position);
+                state.hovered_header = self.header_at(input.mouse_position);
+            }
+            WindowEvent::Scroll(_, y)
+                if self.body_bounds.contains(input.mouse_position) =>
+            {
+                state.scroll = (state.scroll - y as f32 * self.scroll_speed)
+                    .clamp(0.0, self.max_scroll());
+                *internal_state.get_state_mut::<TableState>(&self.id) = state;
+                return Ok(None);
+            }
+            _ => (),
+        }
+        *internal_state.get_state_mut::<TableState>(&self.id) = state;
+
+        if !self.is_hidden_from_cells(input, event) {
+            for cell in self.rows.iter_mut().flatten() {
+                if let Some(message) =
+                    cell.handle_event(internal_state, input, event)?
+                {
+                    return Ok(Some(message));
+                }
+            }
+        }
+
+        match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if let Some(column) = self.header_at(input.mouse_position) {
+                    return Ok(self.sort_requested(column));
+                }
+                let row = match self.row_at(input.mouse_position) {
+                    Some(row) => row,
+                    None => return Ok(None),
+                };
+                match &self.on_open {
+                    Some(on_open) if input.is_double_click() => {
+                        Ok(Some(on_open(row)))
+                    }
+                    _ => Ok(self
+                        .on_select
+                        .as_ref()
+                        .map(|on_select| on_select(row))),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<TableState>(&self.id);
+
+        frame.push_clip_rect(self.body_bounds);
+        for (index, row_bounds) in self.row_bounds.iter().enumerate() {
+            let background = if self.selected == Some(index) {
+                Some(self.selected_color.unwrap_or(theme.selection_color))
+            } else if state.hovered_row == Some(index) {
+                Some(self.hover_color.unwrap_or(theme.highlight_color))
+            } else {
+                None
+            };
+            if let Some(color) = background {
+                Tile {
+                    model: *row_bounds,
+                    color,
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+        }
+        for cell in self.rows.iter().flatten() {
+            cell.draw_frame(internal_state, frame)?;
+        }
+        frame.pop_clip_rect();
+
+        Tile {
+            model: self.header_bounds,
+            color: self.header_color.unwrap_or(theme.highlight_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+        for (index, column) in self.columns.iter().enumerate() {
+            if state.hovered_header == Some(index) {
+                Tile {
+                    model: self.header_cell(column),
+                    color: self.hover_color.unwrap_or(theme.highlight_color),
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+            Widget::<Message>::draw_frame(
+                &column.header,
+                internal_state,
+                frame,
+            )?;
+            match self.sorted_by {
+                Some((sorted, order)) if sorted == index => {
+                    self.draw_sort_arrow(frame, &theme, column, order)?;
+                }
+                _ => (),
+            }
+        }
+        Line {
+            start: vec2(self.header_bounds.left(), self.header_bounds.bottom()),
+            end: vec2(self.header_bounds.right(), self.header_bounds.bottom()),
+            color: self.accent_color.unwrap_or(theme.detail_color),
+            ..Default::default()
+        }
+        .draw(frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let line_height = self.font.line_height();
+        let padding = self.themed_padding(internal_state.theme());
+        let arrow_space = self.arrow_space();
+
+        let mut header_height = line_height;
+        for column in &mut self.columns {
+            column.header_size = Widget::<Message>::dimensions(
+                &mut column.header,
+                internal_state,
+                max_size,
+            );
+            column.width = column.header_size.width + arrow_space;
+            header_height = header_height.max(column.header_size.height);
+        }
+        let header_height = header_height + 2.0 * padding;
+
+        self.cell_sizes.clear();
+        self.row_heights.clear();
+        for row in &mut self.rows {
+            let mut row_height = line_height;
+            let mut sizes = Vec::with_capacity(row.len());
+            for (cell, column) in row.iter_mut().zip(self.columns.iter_mut()) {
+                let size = cell.dimensions(internal_state, max_size);
+                column.width = column.width.max(size.width);
+                row_height = row_height.max(size.height);
+                sizes.push(size);
+            }
+            self.cell_sizes.push(sizes);
+            self.row_heights.push(row_height + 2.0 * padding);
+        }
+
+        let mut column_dimensions = DimensionList::horizontal();
+        for column in &mut self.columns {
+            column.width += 2.0 * padding;
+            column_dimensions.add_child_dimensions(
+                Dimensions::new(column.width, header_height),
+                Justify::Begin,
+            );
+        }
+        let positions = column_dimensions.compute_child_positions();
+        for (column, position) in self.columns.iter_mut().zip(positions.iter())
+        {
+            column.left = position.x;
+        }
+
+        let width = column_dimensions.dimensions().width;
+        let height = header_height + self.content_height();
+        self.bounds = Dimensions::new(width, height).min(max_size).as_rect();
+        self.header_bounds =
+            Dimensions::new(self.bounds.width(), header_height)
+                .min(&self.bounds.dimensions())
+                .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.header_bounds = self.header_bounds.set_top_left_position(position);
+        self.body_bounds = Rect::new(
+            self.header_bounds.bottom(),
+            self.bounds.left(),
+            self.bounds.bottom(),
+            self.bounds.right(),
+        );
+
+        // the rows could have shrunk since the table was last scrolled
+        let scroll = {
+            let state = internal_state.get_state_mut::<TableState>(&self.id);
+            state.scroll = state.scroll.clamp(0.0, self.max_scroll());
+            state.scroll
+        };
+
+        let padding = self.themed_padding(internal_state.theme());
+        for column in &mut self.columns {
+            let offset = vec2(
+                column.left + padding,
+                0.5 * (self.header_bounds.height() - column.header_size.height),
+            );
+            let header_position = position + offset;
+            Widget::<Message>::set_top_left_position(
+                &mut column.header,
+                internal_state,
+                vec2(header_position.x.round(), header_position.y.round()),
+            );
+        }
+
+        self.row_bounds.clear();
+        let mut top = self.body_bounds.top() - scroll;
+        for (row_index, row) in self.rows.iter_mut().enumerate() {
+            let row_height = self.row_heights[row_index];
+            self.row_bounds.push(Rect::new(
+                top,
+                self.bounds.left(),
+                top + row_height,
+                self.bounds.right(),
+            ));
+
+            let cells = row
+                .iter_mut()
+                .zip(self.columns.iter())
+                .zip(self.cell_sizes[row_index].iter());
+            for ((cell, column), size) in cells {
+                let free_width = column.width - 2.0 * padding - size.width;
+                let justify_offset = match column.justify {
+                    Justify::Begin => 0.0,
+                    Justify::Center => 0.5 * free_width,
+                    Justify::End => free_width,
+                };
+                let cell_position = vec2(
+                    self.bounds.left() + column.left + padding + justify_offset,
+                    top + 0.5 * (row_height - size.height),
+                );
+                cell.set_top_left_position(
+                    internal_state,
+                    vec2(cell_position.x.round(), cell_position.y.round()),
+                );
+            }
+            top += row_height;
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        for cell in self.rows.iter_mut().flatten() {
+            let result =
+                cell.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for cell in self.rows.iter().flatten() {
+            cell.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Table<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,446 @@
+use std::ops::Range;
+
+use crate::ui::{Clipboard, Input};
+
+/// One row of wrapped text, as a range of character indices into the text.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(super) struct Row {
+    /// The index of the row's first character.
+    pub(super) start: usize,
+
+    /// The index after the row's last character. Newlines are never part of
+    /// a row.
+    pub(super) end: usize,
+
+    /// True when the row's line was too long and continues on the next row.
+    pub(super) wrapped: bool,
+}
+
+/// The cursor and the selection it makes with the anchor.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub(super) struct Cursor {
+    /// The end of the selection which doesn't move, as a character index.
+    pub(super) anchor: usize,
+
+    /// Where typed text is inserted, as a character index.
+    pub(super) position: usize,
+
+    /// The x position kept while moving up and down, so the cursor returns
+    /// to the same column after passing a shorter row.
+    pub(super) column_x: Option<f32>,
+}
+
+impl Cursor {
+    /// The selected characters. Empty when nothing is selected.
+    pub(super) fn selection(&self) -> Range<usize> {
+        self.anchor.min(self.position)..self.anchor.max(self.position)
+    }
+
+    /// True when at least one character is selected.
+    pub(super) fn has_selection(&self) -> bool {
+        self.anchor != self.position
+    }
+
+    /// Move the cursor, extending the selection or clearing it.
+    pub(super) fn move_to(&mut self, position: usize, extend_selection: bool) {
+        self.position = position;
+        if !extend_selection {
+            self.anchor = position;
+        }
+    }
+}
+
+/// The outcome of applying a window event to a TextArea's text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum AreaEdit {
+    /// The event doesn't edit text.
+    Ignored,
+
+    /// The cursor or selection moved but the text didn't change.
+    Moved,
+
+    /// The text changed, this is the new text.
+    Changed(String),
+
+    /// Escape was pressed.
+    Cancel,
+}
+
+/// Split text into rows which are no wider than `max_width`. Lines are
+/// wrapped after the last space which fits, or mid-word when a single word
+/// doesn't fit. Every line has at least one row, even when it's empty.
+pub(super) fn wrap_rows<F>(text: &str, max_width: f32, measure: F) -> Vec<Row>
+where
+    F: Fn(&str) -> f32,
+{
+    let mut rows = vec![];
+    let mut line_start = 0;
+    for line in text.split('\n') {
+        let chars: Vec<char> = line.chars().collect();
+        let mut row_start = 0;
+        loop {
+            let end = row_start
+                + wrap_point(&chars[row_start..], max_width, &measure);
+            let wrapped = end < chars.len();
+            rows.push(Row {
+                start: line_start + row_start,
+                end: line_start + end,
+                wrapped,
+            });
+            if !wrapped {
+                break;
+            }
+            row_start = end;
+        }
+        line_start += chars.len() + 1;
+    }
+    rows
+}
+
+/// The number of characters which fit on a row. Spaces are allowed to hang
+/// past the edge so rows never start with the space they wrapped at.
+fn wrap_point<F>(chars: &[char], max_width: f32, measure: &F) -> usize
+where
+    F: Fn(&str) -> f32,
+{
+    let mut last_space = None;
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == ' ' {
+            i += 1;
+            last_space = Some(i);
+            continue;
+        }
+        let word_end = i + chars[i..].iter().take_while(|&&c| c != ' ').count();
+        if measure(&collect(&chars[..word_end])) > max_width {
+            if let Some(space) = last_space {
+                return space;
+            }
+            // the first word doesn't fit, so it's broken at the last
+            // character which does
+            return (1..word_end)
+                .rev()
+                .find(|&end| measure(&collect(&chars[..end])) <= max_width)
+                .unwrap_or(1);
+        }
+        i = word_end;
+    }
+    chars.len()
+}
+
+/// The index of the row which contains the character index.
+pub(super) fn row_of(rows: &[Row], position: usize) -> usize {
+    rows.iter()
+        .rposition(|row| row.start <= position)
+        .unwrap_or(0)
+}
+
+/// The furthest the cursor can be placed on a row. The cursor can't be
+/// placed after a wrapped row's last character because that position is the
+/// start of the next row.
+fn row_last_position(row: &Row) -> usize {
+    if row.wrapped {
+        (row.end - 1).max(row.start)
+    } else {
+        row.end
+    }
+}
+
+/// The x position of a character index, relative to the start of its row.
+pub(super) fn position_x<F>(
+    chars: &[char],
+    rows: &[Row],
+    position: usize,
+    measure: F,
+) -> f32
+where
+    F: Fn(&str) -> f32,
+{
+    let row = rows[row_of(rows, position)];
+    measure(&collect(&chars[row.start..position.max(row.start)]))
+}
+
+/// The character index on a row which is closest to the x position.
+pub(super) fn position_at_x<F>(
+    chars: &[char],
+    row: &Row,
+    x: f32,
+    measure: F,
+) -> usize
+where
+    F: Fn(&str) -> f32,
+{
+    (row.start..=row_last_position(row))
+        .map(|position| {
+            let width = measure(&collect(&chars[row.start..position]));
+            (position, (width - x).abs())
+        })
+        .fold((row.start, f32::MAX), |closest, candidate| {
+            if candidate.1 < closest.1 {
+                candidate
+            } else {
+                closest
+            }
+        })
+        .0
+}
+
+/// Apply a typed character or editing key to the text. The cursor is
+/// updated to follow the edit. Rows must be the current text's rows, they're
+/// used to move the cursor up and down. Copy, cut, and paste use the
+/// clipboard.
+pub(super) fn edit_area<F>(
+    text: &str,
+    rows: &[Row],
+    cursor: &mut Cursor,
+    event: &glfw::WindowEvent,
+    clipboard: &mut dyn Clipboard,
+    measure: F,
+) -> AreaEdit
+where
+    F: Fn(&str) -> f32,
+{
+    use glfw::{Action, Key, Modifiers, WindowEvent};
+
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    cursor.position = cursor.position.min(len);
+    cursor.anchor = cursor.anchor.min(len);
+
+    if let Some(c) = Input::typed_char(event) {
+        return replace_selection(&chars, cursor, &c.to_string());
+    }
+    let (key, modifiers) = match *event {
+        WindowEvent::Key(key, _, Action::Press | Action::Repeat, modifiers) => {
+            (key, modifiers)
+        }
+        _ => return AreaEdit::Ignored,
+    };
+    let shift = modifiers.contains(Modifiers::Shift);
+    let control = modifiers.intersects(Modifiers::Control | Modifiers::Super);
+    let row = rows[row_of(rows, cursor.position)];
+
+    let column_x = cursor.column_x.take();
+    match key {
+        Key::Enter | Key::KpEnter => replace_selection(&chars, cursor, "\n"),
+        Key::Tab => replace_selection(&chars, cursor, "    "),
+        Key::Backspace if cursor.has_selection() || cursor.position > 0 => {
+            if !cursor.has_selection() {
+                cursor.anchor = cursor.position - 1;
+            }
+            replace_selection(&chars, cursor, "")
+        }
+        Key::Delete if cursor.has_selection() || cursor.position < len => {
+            if !cursor.has_selection() {
+                cursor.anchor = cursor.position + 1;
+            }
+            replace_selection(&chars, cursor, "")
+        }
+        Key::Left => {
+            let position = if cursor.has_selection() && !shift {
+                cursor.selection().start
+            } else {
+                cursor.position.saturating_sub(1)
+            };
+            cursor.move_to(position, shift);
+            AreaEdit::Moved
+        }
+        Key::Right => {
+            let position = if cursor.has_selection() && !shift {
+                cursor.selection().end
+            } else {
+                (cursor.position + 1).min(len)
+            };
+            cursor.move_to(position, shift);
+            AreaEdit::Moved
+        }
+        Key::Up | Key::Down => {
+            let row_index = row_of(rows, cursor.position);
+            let target = if key == Key::Up {
+                row_index.checked_sub(1)
+            } else {
+                Some(row_index + 1).filter(|&target| target < rows.len())
+            };
+            let x = column_x.unwrap_or_else(|| {
+                position_x(&chars, rows, cursor.position, &measure)
+            });
+            let position = match target {
+                Some(target) => {
+                    position_at_x(&chars, &rows[target], x, &measure)
+                }
+                None if key == Key::Up => 0,
+                None => len,
+            };
+            cursor.move_to(position, shift);
+            cursor.column_x = Some(x);
+            AreaEdit::Moved
+        }
+        Key::Home => {
+            cursor.move_to(if control { 0 } else { row.start }, shift);
+            AreaEdit::Moved
+        }
+        Key::End => {
+            let position = if control {
+                len
+            } else {
+                row_last_position(&row)
+            };
+            cursor.move_to(position, shift);
+            AreaEdit::Moved
+        }
+        Key::A if control => {
+            cursor.anchor = 0;
+            cursor.position = len;
+            AreaEdit::Moved
+        }
+        Key::C if control && cursor.has_selection() => {
+            clipboard.set_text(&collect(&chars[cursor.selection()]));
+            AreaEdit::Ignored
+        }
+        Key::X if control && cursor.has_selection() => {
+            clipboard.set_text(&collect(&chars[cursor.selection()]));
+            replace_selection(&chars, cursor, "")
+        }
+        Key::V if control => match clipboard.text() {
+            Some(pasted) => {
+                let pasted: String = pasted
+                    .replace("\r\n", "\n")
+                    .replace('\t', "    ")
+                    .chars()
+                    .filter(|&c| c == '\n' || !c.is_control())
+                    .collect();
+                replace_selection(&chars, cursor, &pasted)
+            }
+            None => AreaEdit::Ignored,
+        },
+        Key::Escape => AreaEdit::Cancel,
+        _ => {
+            cursor.column_x = column_x;
+            AreaEdit::Ignored
+        }
+    }
+}
+
+/// Replace the selected characters, then place the cursor after the new
+/// text.
+fn replace_selection(
+    chars: &[char],
+    cursor: &mut Cursor,
+    replacement: &str,
+) -> AreaEdit {
+    let selection = cursor.selection();
+    let mut edited = collect(&chars[..selection.start]);
+    edited.push_str(replacement);
+    edited.extend(&chars[selection.end..]);
+    cursor.move_to(selection.start + replacement.chars().count(), false);
+    cursor.column_x = None;
+    AreaEdit::Changed(edited)
+}
+
+fn collect(chars: &[char]) -> String {
+    chars.iter().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use glfw::{Action, Key, Modifiers, WindowEvent};
+
+    use crate::ui::MemoryClipboard;
+
+    fn measure(text: &str) -> f32 {
+        text.chars().count() as f32 * 10.0
+    }
+
+    fn press(key: Key, modifiers: Modifiers) -> WindowEvent {
+        WindowEvent::Key(key, 0, Action::Press, modifiers)
+    }
+
+    fn row(start: usize, end: usize, wrapped: bool) -> Row {
+        Row {
+            start,
+            end,
+            wrapped,
+        }
+    }
+
+    #[test]
+    fn test_wrap_rows_at_spaces_and_inside_long_words() {
+        let rows = wrap_rows("hello world\n\nabcdefghijkl", 50.0, measure);
+        assert_eq!(
+            rows,
+            vec![
+                row(0, 6, true),
+                row(6, 11, false),
+                row(12, 12, false),
+                row(13, 18, true),
+                row(18, 23, true),
+                row(23, 25, false),
+            ]
+        );
+        assert_eq!(row_of(&rows, 6), 1);
+        assert_eq!(row_of(&rows, 11), 1);
+        assert_eq!(row_of(&rows, 12), 2);
+    }
+
+    #[test]
+    fn test_vertical_movement_keeps_the_column() {
+        let text = "abcd\nx\nabcd";
+        let rows = wrap_rows(text, 100.0, measure);
+        let mut clipboard = MemoryClipboard::default();
+        let mut cursor = Cursor {
+            anchor: 3,
+            position: 3,
+            column_x: None,
+        };
+
+        let down = press(Key::Down, Modifiers::empty());
+        edit_area(text, &rows, &mut cursor, &down, &mut clipboard, measure);
+        assert_eq!(cursor.position, 6);
+        edit_area(text, &rows, &mut cursor, &down, &mut clipboard, measure);
+        assert_eq!(cursor.position, 10);
+        assert!(!cursor.has_selection());
+
+        let up = press(Key::Up, Modifiers::Shift);
+        edit_area(text, &rows, &mut cursor, &up, &mut clipboard, measure);
+        assert_eq!(cursor.position, 6);
+        assert_eq!(cursor.selection(), 6..10);
+    }
+
+    #[test]
+    fn test_typing_replaces_the_selection() {
+        let text = "one two";
+        let rows = wrap_rows(text, 100.0, measure);
+        let mut clipboard = MemoryClipboard::default();
+        let mut cursor = Cursor {
+            anchor: 4,
+            position: 7,
+            column_x: None,
+        };
+
+        let result = edit_area(
+            text,
+            &rows,
+            &mut cursor,
+            &WindowEvent::Char('2'),
+            &mut clipboard,
+            measure,
+        );
+        assert_eq!(result, AreaEdit::Changed("one 2".to_owned()));
+        assert_eq!(cursor.position, 5);
+
+        let rows = wrap_rows("one 2", 100.0, measure);
+        let enter = press(Key::Enter, Modifiers::empty());
+        let result = edit_area(
+            "one 2",
+            &rows,
+            &mut cursor,
+            &enter,
+            &mut clipboard,
+            measure,
+        );
+        assert_eq!(result, AreaEdit::Changed("one 2\n".to_owned()));
+        assert_eq!(cursor.position, 6);
+    }
+}
@@ -0,0 +1,484 @@
+//! A TextArea is a box of editable text which spans multiple lines.
+//!
+//! Like the TextInput, a TextArea doesn't own its value. Each edit emits a
+//! message with the new text, and the value is provided again when the view
+//! is rebuilt. Wrapping, cursor movement, and selection live in [`editor`]
+//! so they can be tested without a font.
+
+mod editor;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{Element, Widget},
+        CursorShape, Font, Id, Input, InternalState, Theme,
+    },
+    vec2, Vec2, Vec4,
+};
+
+use self::editor::{
+    edit_area, position_at_x, position_x, row_of, wrap_rows, AreaEdit, Cursor,
+    Row,
+};
+
+/// A TextArea's cursor and scroll position are stored in the UI
+/// InternalState so they persist between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct TextAreaState {
+    /// True while the mouse is selecting text.
+    dragging: bool,
+
+    /// The cursor and selection.
+    cursor: Cursor,
+
+    /// How far the text has been scrolled down, in pixels.
+    scroll: f32,
+}
+
+/// A box of editable text which wraps long lines and scrolls once the text
+/// doesn't fit.
+///
+/// # Controls
+///
+/// - Click to focus the text area and place the cursor, click anywhere else
+///   to release focus. Drag or shift-click to select text. Control+Tab moves
+///   keyboard focus to the next widget.
+/// - Type to replace the selection or insert text at the cursor. Enter adds
+///   a new line and Tab adds four spaces.
+/// - The arrow keys, Home, and End move the cursor. Hold shift to select
+///   while moving and control to jump to the start or end of the text.
+///   Control+A selects everything.
+/// - Control+C, Control+X, and Control+V copy, cut, and paste with the UI's
+///   clipboard.
+/// - The mouse wheel scrolls, and the view follows the cursor.
+/// - Escape releases focus.
+///
+/// Typed characters are only received when the window has char polling
+/// enabled, see [`Input::typed_char`].
+pub struct TextArea<Message> {
+    /// The ID uniquely identifies this text area when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The font used to build each row of text.
+    font: Font,
+
+    /// The current text.
+    value: String,
+
+    /// The wrapped rows of text.
+    rows: Vec<Row>,
+
+    /// The tiles for each row, relative to the top left of the row.
+    row_tiles: Vec<Vec<Tile>>,
+
+    /// The number of rows visible at once.
+    visible_rows: usize,
+
+    /// The space occupied by the text area on screen.
+    bounds: Rect,
+
+    /// The space between the text and the edges of the text area. None uses
+    /// the theme's padding.
+    padding: Option<f32>,
+
+    /// The padding used by the last layout.
+    layout_padding: f32,
+
+    /// The color behind the text.
+    background_color: Option<Vec4>,
+
+    /// The color of the outline while the text area is focused.
+    focus_color: Option<Vec4>,
+
+    /// The color of the cursor.
+    cursor_color: Option<Vec4>,
+
+    /// The color behind selected text.
+    selection_color: Option<Vec4>,
+
+    /// The function used to build a message when the text is edited.
+    on_change: Option<Box<dyn Fn(String) -> Message>>,
+}
+
+impl<Message> TextArea<Message> {
+    /// Create a new text area which shows the given text.
+    pub fn new<T>(id: Id, font: &Font, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let value = value.into();
+        Self {
+            id,
+            rows: wrap_rows(&value, f32::MAX, |text| font.text_width(text)),
+            font: font.clone(),
+            value,
+            row_tiles: vec![],
+            visible_rows: 8,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            padding: None,
+            layout_padding: 0.0,
+            background_color: None,
+            focus_color: None,
+            cursor_color: None,
+            selection_color: None,
+            on_change: None,
+        }
+    }
+
+    builder_field!(visible_rows, usize);
+    builder_field_some!(padding, f32);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(focus_color, Vec4);
+    builder_field_some!(cursor_color, Vec4);
+    builder_field_some!(selection_color, Vec4);
+
+    /// Emit a message with the new text each time it's edited.
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(String) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    /// The region inside the padding where text is drawn.
+    fn text_bounds(&self) -> Rect {
+        Rect::new(
+            self.bounds.top() + self.layout_padding,
+            self.bounds.left() + self.layout_padding,
+            self.bounds.bottom() - self.layout_padding,
+            self.bounds.right() - self.layout_padding,
+        )
+    }
+
+    /// Wrap the text to fit inside the text area and build the tiles for
+    /// every row.
+    fn wrap_text(&mut self, theme: &Theme) {
+        let font = &self.font;
+        let width = self.text_bounds().width();
+        self.rows = wrap_rows(&self.value, width, |text| font.text_width(text));
+
+        // glyphs are laid out below the top of the first line, this moves
+        // them back up to the top of the row
+        let (_, reference) = font.build_text_tiles("x");
+        let text_color = font.text_color_or(theme);
+        let chars: Vec<char> = self.value.chars().collect();
+        self.row_tiles = self
+            .rows
+            .iter()
+            .map(|row| {
+                let text: String = chars[row.start..row.end].iter().collect();
+                let (mut tiles, _) = font.build_text_tiles(text);
+                for tile in &mut tiles {
+                    tile.model =
+                        tile.model.translate(vec2(0.0, -reference.top()));
+                    tile.color = text_color;
+                }
+                tiles
+            })
+            .collect();
+    }
+
+    /// The position of a row's top left corner on screen.
+    fn row_position(&self, row: usize, scroll: f32) -> Vec2 {
+        let text_bounds = self.text_bounds();
+        vec2(
+            text_bounds.left(),
+            text_bounds.top() + row as f32 * self.font.line_height() - scroll,
+        )
+        .map(f32::round)
+    }
+
+    /// The furthest the text can be scrolled.
+    fn max_scroll(&self) -> f32 {
+        let content = self.rows.len() as f32 * self.font.line_height();
+        (content - self.text_bounds().height()).max(0.0)
+    }
+
+    /// Scroll just far enough to show the cursor.
+    fn scroll_to_cursor(&self, state: &mut TextAreaState) {
+        let line_height = self.font.line_height();
+        let top =
+            row_of(&self.rows, state.cursor.position) as f32 * line_height;
+        let visible_height = self.text_bounds().height();
+        if top < state.scroll {
+            state.scroll = top;
+        } else if top + line_height > state.scroll + visible_height {
+            state.scroll = top + line_height - visible_height;
+        }
+        state.scroll = state.scroll.clamp(0.0, self.max_scroll());
+    }
+
+    /// The character index closest to a point on screen.
+    fn position_at(&self, point: Vec2, scroll: f32) -> usize {
+        let text_bounds = self.text_bounds();
+        let row = ((point.y - text_bounds.top() + scroll)
+            / self.font.line_height())
+        .floor()
+        .clamp(0.0, (self.rows.len() - 1) as f32) as usize;
+        let chars: Vec<char> = self.value.chars().collect();
+        position_at_x(
+            &chars,
+            &self.rows[row],
+            point.x - text_bounds.left(),
+            |text| self.font.text_width(text),
+        )
+    }
+
+    /// Draw the selection, the visible rows, and the cursor.
+    fn draw_text(
+        &self,
+        state: &TextAreaState,
+        focused: bool,
+        theme: &Theme,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let chars: Vec<char> = self.value.chars().collect();
+        if focused && state.cursor.has_selection() {
+            self.draw_selection(state, &chars, theme, frame)?;
+        }
+
+        let line_height = self.font.line_height();
+        let first = (state.scroll / line_height).floor() as usize;
+        let count = self.visible_rows + 2;
+        for (index, tiles) in
+            self.row_tiles.iter().enumerate().skip(first).take(count)
+        {
+            let offset = self.row_position(index, state.scroll);
+            for tile in tiles {
+                Tile {
+                    model: tile.model.translate(offset),
+                    ..*tile
+                }
+                .fill(frame)?;
+            }
+        }
+
+        if focused {
+            let position = state.cursor.position.min(chars.len());
+            let row = row_of(&self.rows, position);
+            let x = position_x(&chars, &self.rows, position, |text| {
+                self.font.text_width(text)
+            });
+            let top_left = self.row_position(row, state.scroll);
+            Line {
+                start: vec2(top_left.x + x, top_left.y),
+                end: vec2(top_left.x + x, top_left.y + line_height),
+                color: self.cursor_color.unwrap_or(theme.text_color),
+                ..Default::default()
+            }
+            .draw(frame)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a highlight behind the selected part of each row.
+    fn draw_selection(
+        &self,
+        state: &TextAreaState,
+        chars: &[char],
+        theme: &Theme,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let selection = state.cursor.selection();
+        let measure = |text: &str| self.font.text_width(text);
+        for (index, row) in self.rows.iter().enumerate() {
+            if row.end < selection.start || row.start > selection.end {
+                continue;
+            }
+            let start = selection.start.max(row.start);
+            let end = selection.end.min(row.end);
+            if start == end && selection.end <= row.end {
+                continue;
+            }
+            let left =
+                measure(&chars[row.start..start].iter().collect::<String>());
+            let mut right =
+                measure(&chars[row.start..end].iter().collect::<String>());
+            if selection.end > row.end && !row.wrapped {
+                // show that the newline is selected too
+                right += self.font.text_width(" ");
+            }
+            let top_left = self.row_position(index, state.scroll);
+            Tile {
+                model: Rect::new(
+                    top_left.y,
+                    top_left.x + left,
+                    top_left.y + self.font.line_height(),
+                    top_left.x + right,
+                ),
+                color: self.selection_color.unwrap_or(theme.selection_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Widget<Message> for TextArea<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, Modifiers, MouseButton, WindowEvent};
+
+        let focused = internal_state.is_focused(&self.id);
+        let mut state = *internal_state.get_state::<TextAreaState>(&self.id);
+        let hovered = self.bounds.contains(input.mouse_position);
+        if matches!(*event, WindowEvent::CursorPos(..))
+            && (state.dragging || hovered)
+        {
+            internal_state.request_cursor(CursorShape::IBeam);
+        }
+        let mut message = None;
+        match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                modifiers,
+            ) => {
+                state.dragging = hovered;
+                if hovered {
+                    internal_state.set_focus(self.id);
+                    let position =
+                        self.position_at(input.mouse_position, state.scroll);
+                    let extend =
+                        focused && modifiers.contains(Modifiers::Shift);
+                    state.cursor.move_to(position, extend);
+                    state.cursor.column_x = None;
+                } else {
+                    internal_state.release_focus(&self.id);
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = false;
+            }
+            WindowEvent::CursorPos(..) if state.dragging => {
+                let position =
+                    self.position_at(input.mouse_position, state.scroll);
+                state.cursor.move_to(position, true);
+                self.scroll_to_cursor(&mut state);
+            }
+            WindowEvent::Scroll(_, y) if hovered => {
+                state.scroll = (state.scroll
+                    - y as f32 * 3.0 * self.font.line_height())
+                .clamp(0.0, self.max_scroll());
+            }
+            _ if focused => {
+                let font = &self.font;
+                let edit = edit_area(
+                    &self.value,
+                    &self.rows,
+                    &mut state.cursor,
+                    event,
+                    internal_state.clipboard(),
+                    |text| font.text_width(text),
+                );
+                match edit {
+                    AreaEdit::Changed(text) => {
+                        message =
+                            self.on_change.as_ref().map(|f| f(text.clone()));
+
+                        // keep showing the edit until the view is rebuilt
+                        // with the new value
+                        self.value = text;
+                        self.wrap_text(internal_state.theme());
+                        self.scroll_to_cursor(&mut state);
+                    }
+                    AreaEdit::Moved => self.scroll_to_cursor(&mut state),
+                    AreaEdit::Cancel => {
+                        internal_state.release_focus(&self.id);
+                        state.dragging = false;
+                    }
+                    AreaEdit::Ignored => (),
+                }
+            }
+            _ => (),
+        }
+        *internal_state.get_state_mut::<TextAreaState>(&self.id) = state;
+        Ok(message)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let focused = internal_state.is_focused(&self.id);
+        let state = *internal_state.get_state::<TextAreaState>(&self.id);
+        Tile {
+            model: self.bounds,
+            color: self.background_color.unwrap_or(theme.field_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        frame.push_clip_rect(self.bounds);
+        let result = self.draw_text(&state, focused, &theme, frame);
+        frame.pop_clip_rect();
+        result?;
+
+        if focused {
+            Tile {
+                model: self.bounds,
+                color: self.focus_color.unwrap_or(theme.accent_color),
+                ..Default::default()
+            }
+            .outline(frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let theme = *internal_state.theme();
+        self.layout_padding = self
+            .padding
+            .unwrap_or(theme.padding * self.font.line_height());
+        self.bounds = Dimensions::new(
+            max_size.width,
+            self.visible_rows as f32 * self.font.line_height()
+                + 2.0 * self.layout_padding,
+        )
+        .min(max_size)
+        .as_rect();
+        self.wrap_text(&theme);
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        internal_state.register_focusable_with_tab(self.id, self.bounds);
+    }
+}
+
+impl<Message> Into<Element<Message>> for TextArea<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,367 @@
+//! A TextInput is a single line of editable text.
+//!
+//! Like the Slider, a TextInput doesn't own its value. Each edit emits a
+//! message with the new text, and the value is provided again when the view
+//! is rebuilt. The editing itself lives in [`text_edit`] so other widgets
+//! which accept typed text can share it.
+
+pub(crate) mod text_edit;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{Element, Label, Widget},
+        CursorShape, Font, Id, Input, InternalState, Theme,
+    },
+    vec2, Vec4,
+};
+
+use self::text_edit::{edit_selection, Selection, TextEdit};
+
+/// A TextInput's selection is stored in the UI InternalState so it persists
+/// between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct TextInputState {
+    /// True while the mouse is held down to select text.
+    dragging: bool,
+
+    /// The cursor and selected text.
+    selection: Selection,
+}
+
+/// A single line of editable text.
+///
+/// # Controls
+///
+/// - Click the input, or Tab to it, to focus it. Click anywhere else to
+///   release focus.
+/// - Type to insert text at the cursor. Backspace and Delete remove text.
+/// - Left, Right, Home, and End move the cursor.
+/// - Drag, or hold Shift while moving the cursor, to select text. Ctrl+A
+///   selects everything.
+/// - Ctrl+C, Ctrl+X, and Ctrl+V copy, cut, and paste with the UI's
+///   clipboard.
+/// - Enter submits the text, Escape releases focus.
+///
+/// Typed characters are only received when the window has char polling
+/// enabled, see [`Input::typed_char`].
+pub struct TextInput<Message> {
+    /// The ID uniquely identifies this input when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The font used to build the text each layout, it changes as the text is
+    /// edited.
+    font: Font,
+
+    /// The current text.
+    value: String,
+
+    /// The text's label, rebuilt each layout.
+    label: Label,
+
+    /// The space occupied by the input on screen.
+    bounds: Rect,
+
+    /// The space between the text and the edges of the input. None uses the
+    /// theme's padding.
+    padding: Option<f32>,
+
+    /// The color behind the text.
+    background_color: Option<Vec4>,
+
+    /// The color of the outline while the input is focused.
+    focus_color: Option<Vec4>,
+
+    /// The color of the cursor.
+    cursor_color: Option<Vec4>,
+
+    /// The color behind selected text.
+    selection_color: Option<Vec4>,
+
+    /// The function used to build a message when the text is edited.
+    on_change: Option<Box<dyn Fn(String) -> Message>>,
+
+    /// The function used to build a message when Enter is pressed.
+    on_submit: Option<Box<dyn Fn(String) -> Message>>,
+}
+
+impl<Message> TextInput<Message> {
+    /// Create a new text input which shows the given text.
+    pub fn new<T>(id: Id, font: &Font, value: T) -> Self
+    where
+        T: Into<String>,
+    {
+        let value = value.into();
+        Self {
+            id,
+            font: font.clone(),
+            label: Label::new(font, &value),
+            value,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            padding: None,
+            background_color: None,
+            focus_color: None,
+            cursor_color: None,
+            selection_color: None,
+            on_change: None,
+            on_submit: None,
+        }
+    }
+
+    builder_field_some!(padding, f32);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(focus_color, Vec4);
+    builder_field_some!(cursor_color, Vec4);
+    builder_field_some!(selection_color, Vec4);
+
+    /// Apply a key or typed character to the focused input's text.
+    fn edit(
+        &mut self,
+        internal_state: &mut InternalState,
+        state: &mut TextInputState,
+        event: &glfw::WindowEvent,
+    ) -> Option<Message> {
+        let edit = edit_selection(
+            &self.value,
+            &mut state.selection,
+            event,
+            internal_state.clipboard(),
+        );
+        match edit {
+            TextEdit::Changed(text) => {
+                let message = self.on_change.as_ref().map(|f| f(text.clone()));
+
+                // keep showing the edit until the view is rebuilt with the
+                // new value
+                self.value = text;
+                message
+            }
+            TextEdit::Submit => {
+                self.on_submit.as_ref().map(|f| f(self.value.clone()))
+            }
+            TextEdit::Cancel => {
+                internal_state.release_focus(&self.id);
+                None
+            }
+            TextEdit::Moved | TextEdit::Ignored => None,
+        }
+    }
+
+    /// The space between the text and the edges of the input.
+    fn themed_padding(&self, theme: &Theme) -> f32 {
+        self.padding
+            .unwrap_or(theme.padding * self.font.line_height())
+    }
+
+    /// The x position of a character index, in screen space.
+    fn position_x(&self, theme: &Theme, position: usize) -> f32 {
+        let before: String = self.value.chars().take(position).collect();
+        (self.bounds.left()
+            + self.themed_padding(theme)
+            + self.font.text_width(before))
+        .round()
+    }
+
+    /// The character index closest to an x position in screen space.
+    fn position_at_x(&self, theme: &Theme, x: f32) -> usize {
+        (0..=self.value.chars().count())
+            .map(|position| {
+                (position, (self.position_x(theme, position) - x).abs())
+            })
+            .fold((0, f32::MAX), |closest, candidate| {
+                if candidate.1 < closest.1 {
+                    candidate
+                } else {
+                    closest
+                }
+            })
+            .0
+    }
+
+    /// Emit a message with the new text each time it's edited.
+    pub fn on_change<F>(self, on_change_fn: F) -> Self
+    where
+        F: 'static + Fn(String) -> Message,
+    {
+        Self {
+            on_change: Some(Box::new(on_change_fn)),
+            ..self
+        }
+    }
+
+    /// Emit a message with the text when Enter is pressed.
+    pub fn on_submit<F>(self, on_submit_fn: F) -> Self
+    where
+        F: 'static + Fn(String) -> Message,
+    {
+        Self {
+            on_submit: Some(Box::new(on_submit_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> Widget<Message> for TextInput<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, Modifiers, MouseButton, WindowEvent};
+
+        let theme = *internal_state.theme();
+        let focused = internal_state.is_focused(&self.id);
+        let mut state = *internal_state.get_state::<TextInputState>(&self.id);
+        if matches!(*event, WindowEvent::CursorPos(..))
+            && (state.dragging || self.bounds.contains(input.mouse_position))
+        {
+            internal_state.request_cursor(CursorShape::IBeam);
+        }
+        let message = match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                modifiers,
+            ) => {
+                if self.bounds.contains(input.mouse_position) {
+                    internal_state.set_focus(self.id);
+                    let position =
+                        self.position_at_x(&theme, input.mouse_position.x);
+                    let extend =
+                        focused && modifiers.contains(Modifiers::Shift);
+                    state.selection.cursor = position;
+                    if !extend {
+                        state.selection.anchor = position;
+                    }
+                    state.dragging = true;
+                } else {
+                    internal_state.release_focus(&self.id);
+                }
+                None
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.dragging = false;
+                None
+            }
+            WindowEvent::CursorPos(..) if state.dragging => {
+                state.selection.cursor =
+                    self.position_at_x(&theme, input.mouse_position.x);
+                None
+            }
+            _ if focused => self.edit(internal_state, &mut state, event),
+            _ => None,
+        };
+        *internal_state.get_state_mut::<TextInputState>(&self.id) = state;
+        Ok(message)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let padding = self.themed_padding(&theme);
+        let focused = internal_state.is_focused(&self.id);
+        let state = *internal_state.get_state::<TextInputState>(&self.id);
+        Tile {
+            model: self.bounds,
+            color: self.background_color.unwrap_or(theme.field_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        frame.push_clip_rect(self.bounds);
+        let selection = state.selection.range();
+        if focused && !selection.is_empty() {
+            Tile {
+                model: Rect::new(
+                    self.bounds.top() + padding,
+                    self.position_x(&theme, selection.start),
+                    self.bounds.bottom() - padding,
+                    self.position_x(&theme, selection.end),
+                ),
+                color: self.selection_color.unwrap_or(theme.selection_color),
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        Widget::<Message>::draw_frame(&self.label, internal_state, frame)?;
+        if focused {
+            let x = self.position_x(&theme, state.selection.cursor);
+            Line {
+                start: vec2(x, self.bounds.top() + padding),
+                end: vec2(x, self.bounds.bottom() - padding),
+                color: self.cursor_color.unwrap_or(theme.text_color),
+                ..Default::default()
+            }
+            .draw(frame)?;
+        }
+        frame.pop_clip_rect();
+
+        if focused {
+            Tile {
+                model: self.bounds,
+                color: self.focus_color.unwrap_or(theme.accent_color),
+                ..Default::default()
+            }
+            .outline(frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.label = Label::new(&self.font, &self.value);
+        Widget::<Message>::dimensions(
+            &mut self.label,
+            internal_state,
+            &Dimensions::new(f32::MAX, f32::MAX),
+        );
+        self.bounds = Dimensions::new(
+            max_size.width,
+            self.font.line_height()
+                + 2.0 * self.themed_padding(internal_state.theme()),
+        )
+        .min(max_size)
+        .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: crate::Vec2,
+    ) {
+        let padding = self.themed_padding(internal_state.theme());
+        self.bounds = self.bounds.set_top_left_position(position);
+        internal_state.register_focusable(self.id, self.bounds);
+        Widget::<Message>::set_top_left_position(
+            &mut self.label,
+            internal_state,
+            position + vec2(padding, padding),
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for TextInput<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
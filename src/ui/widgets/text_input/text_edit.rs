@@ -0,0 +1,292 @@
+use std::ops::Range;
+
+use crate::ui::{Clipboard, Input, MemoryClipboard};
+
+/// The outcome of applying a window event to editable text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TextEdit {
+    /// The event doesn't edit text.
+    Ignored,
+
+    /// The cursor moved but the text didn't change.
+    Moved,
+
+    /// The text changed, this is the new text.
+    Changed(String),
+
+    /// Enter was pressed.
+    Submit,
+
+    /// Escape was pressed.
+    Cancel,
+}
+
+/// The cursor and the selection it makes with the anchor, both as character
+/// indices into the text.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Selection {
+    /// The end of the selection which doesn't move.
+    pub(crate) anchor: usize,
+
+    /// Where typed text is inserted.
+    pub(crate) cursor: usize,
+}
+
+impl Selection {
+    /// A cursor with nothing selected.
+    pub(crate) fn at(cursor: usize) -> Self {
+        Self {
+            anchor: cursor,
+            cursor,
+        }
+    }
+
+    /// The selected characters. Empty when nothing is selected.
+    pub(crate) fn range(&self) -> Range<usize> {
+        self.anchor.min(self.cursor)..self.anchor.max(self.cursor)
+    }
+
+    /// True when no characters are selected.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.anchor == self.cursor
+    }
+
+    /// Move the cursor, extending the selection or clearing it.
+    fn move_to(&mut self, cursor: usize, extend_selection: bool) {
+        self.cursor = cursor;
+        if !extend_selection {
+            self.anchor = cursor;
+        }
+    }
+}
+
+/// Apply a typed character or editing key to the text. The cursor is a
+/// character index into the text, it's updated to follow the edit.
+///
+/// This is for inputs which don't select text, see [`edit_selection`].
+pub(crate) fn edit_text(
+    text: &str,
+    cursor: &mut usize,
+    event: &glfw::WindowEvent,
+) -> TextEdit {
+    let mut selection = Selection::at(*cursor);
+    let result = edit_selection(
+        text,
+        &mut selection,
+        event,
+        &mut MemoryClipboard::default(),
+    );
+    *cursor = selection.cursor;
+    result
+}
+
+/// Apply a typed character or editing key to the text. The selection is
+/// updated to follow the edit.
+///
+/// Holding Shift while moving the cursor selects text, and typing replaces
+/// the selection. Ctrl+A selects everything, Ctrl+C, Ctrl+X, and Ctrl+V copy,
+/// cut, and paste with the clipboard.
+pub(crate) fn edit_selection(
+    text: &str,
+    selection: &mut Selection,
+    event: &glfw::WindowEvent,
+    clipboard: &mut dyn Clipboard,
+) -> TextEdit {
+    use glfw::{Action, Key, Modifiers, WindowEvent};
+
+    let chars: Vec<char> = text.chars().collect();
+    let len = chars.len();
+    selection.cursor = selection.cursor.min(len);
+    selection.anchor = selection.anchor.min(len);
+
+    if let Some(c) = Input::typed_char(event) {
+        return replace_selection(&chars, selection, &c.to_string());
+    }
+    let (key, modifiers) = match *event {
+        WindowEvent::Key(key, _, Action::Press | Action::Repeat, modifiers) => {
+            (key, modifiers)
+        }
+        _ => return TextEdit::Ignored,
+    };
+    let shift = modifiers.contains(Modifiers::Shift);
+    let control = modifiers.intersects(Modifiers::Control | Modifiers::Super);
+    let selected: String = chars[selection.range()].iter().collect();
+
+    match key {
+        Key::Backspace if !selection.is_empty() || selection.cursor > 0 => {
+            if selection.is_empty() {
+                selection.anchor = selection.cursor - 1;
+            }
+            replace_selection(&chars, selection, "")
+        }
+        Key::Delete if !selection.is_empty() || selection.cursor < len => {
+            if selection.is_empty() {
+                selection.anchor = selection.cursor + 1;
+            }
+            replace_selection(&chars, selection, "")
+        }
+        Key::Left if !selection.is_empty() || selection.cursor > 0 => {
+            let cursor = if !selection.is_empty() && !shift {
+                selection.range().start
+            } else {
+                selection.cursor.saturating_sub(1)
+            };
+            selection.move_to(cursor, shift);
+            TextEdit::Moved
+        }
+        Key::Right if !selection.is_empty() || selection.cursor < len => {
+            let cursor = if !selection.is_empty() && !shift {
+                selection.range().end
+            } else {
+                (selection.cursor + 1).min(len)
+            };
+            selection.move_to(cursor, shift);
+            TextEdit::Moved
+        }
+        Key::Home => {
+            selection.move_to(0, shift);
+            TextEdit::Moved
+        }
+        Key::End => {
+            selection.move_to(len, shift);
+            TextEdit::Moved
+        }
+        Key::A if control => {
+            *selection = Selection {
+                anchor: 0,
+                cursor: len,
+            };
+            TextEdit::Moved
+        }
+        Key::C if control && !selected.is_empty() => {
+            clipboard.set_text(&selected);
+            TextEdit::Ignored
+        }
+        Key::X if control && !selected.is_empty() => {
+            clipboard.set_text(&selected);
+            replace_selection(&chars, selection, "")
+        }
+        Key::V if control => match clipboard.text() {
+            Some(pasted) => {
+                // the input is a single line, so line breaks become spaces
+                let pasted: String = pasted
+                    .replace("\r\n", " ")
+                    .chars()
+                    .map(|c| if c == '\n' || c == '\t' { ' ' } else { c })
+                    .filter(|c| !c.is_control())
+                    .collect();
+                replace_selection(&chars, selection, &pasted)
+            }
+            None => TextEdit::Ignored,
+        },
+        Key::Enter | Key::KpEnter => TextEdit::Submit,
+        Key::Escape => TextEdit::Cancel,
+        _ => TextEdit::Ignored,
+    }
+}
+
+/// Replace the selected characters, then place the cursor after the new
+/// text.
+fn replace_selection(
+    chars: &[char],
+    selection: &mut Selection,
+    replacement: &str,
+) -> TextEdit {
+    let range = selection.range();
+    let mut edited: String = chars[..range.start].iter().collect();
+    edited.push_str(replacement);
+    edited.extend(&chars[range.end..]);
+    *selection = Selection::at(range.start + replacement.chars().count());
+    TextEdit::Changed(edited)
+}
+
+/// The text before the cursor. Used to find where the cursor is drawn.
+pub(crate) fn text_before_cursor(text: &str, cursor: usize) -> String {
+    text.chars().take(cursor).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use glfw::{Action, Key, Modifiers, WindowEvent};
+
+    fn press(key: Key) -> WindowEvent {
+        WindowEvent::Key(key, 0, Action::Press, Modifiers::empty())
+    }
+
+    #[test]
+    fn test_typing_inserts_at_the_cursor() {
+        let mut cursor = 1;
+        let result = edit_text("ac", &mut cursor, &WindowEvent::Char('b'));
+        assert_eq!(result, TextEdit::Changed("abc".to_owned()));
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn test_backspace_and_delete_remove_around_the_cursor() {
+        let mut cursor = 2;
+        let result = edit_text("añc", &mut cursor, &press(Key::Backspace));
+        assert_eq!(result, TextEdit::Changed("ac".to_owned()));
+        assert_eq!(cursor, 1);
+
+        let result = edit_text("ac", &mut cursor, &press(Key::Delete));
+        assert_eq!(result, TextEdit::Changed("a".to_owned()));
+        assert_eq!(cursor, 1);
+
+        // nothing to delete past the end
+        let result = edit_text("a", &mut cursor, &press(Key::Delete));
+        assert_eq!(result, TextEdit::Ignored);
+    }
+
+    #[test]
+    fn test_cursor_is_clamped_to_the_text() {
+        let mut cursor = 10;
+        let result = edit_text("abc", &mut cursor, &press(Key::Left));
+        assert_eq!(result, TextEdit::Moved);
+        assert_eq!(cursor, 2);
+    }
+
+    #[test]
+    fn test_selections_are_replaced_copied_and_pasted() {
+        let control =
+            |key| WindowEvent::Key(key, 0, Action::Press, Modifiers::Control);
+        let mut clipboard = MemoryClipboard::default();
+        let mut selection = Selection::at(5);
+
+        let shift_left =
+            WindowEvent::Key(Key::Left, 0, Action::Press, Modifiers::Shift);
+        edit_selection(
+            "hello world",
+            &mut selection,
+            &shift_left,
+            &mut clipboard,
+        );
+        edit_selection(
+            "hello world",
+            &mut selection,
+            &shift_left,
+            &mut clipboard,
+        );
+        assert_eq!(selection.range(), 3..5);
+
+        let result = edit_selection(
+            "hello world",
+            &mut selection,
+            &control(Key::X),
+            &mut clipboard,
+        );
+        assert_eq!(result, TextEdit::Changed("hel world".to_owned()));
+        assert_eq!(clipboard.text(), Some("lo".to_owned()));
+
+        clipboard.set_text("p\nme");
+        let result = edit_selection(
+            "hel world",
+            &mut selection,
+            &control(Key::V),
+            &mut clipboard,
+        );
+        assert_eq!(result, TextEdit::Changed("help me world".to_owned()));
+        assert_eq!(selection, Selection::at(7));
+    }
+}
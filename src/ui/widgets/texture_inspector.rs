@@ -0,0 +1,532 @@
+use ::anyhow::Result;
+
+use crate::{
+    asset_loader::{AssetLoader, TextureId, TextureInfo},
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Label, Widget},
+        Font, Id, Input, InternalState,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A TextureInspector's selection is stored in the UI InternalState so it
+/// persists between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TextureInspectorState {
+    /// The position of the selected texture in the list.
+    selected: usize,
+
+    /// The mip level shown in the preview.
+    mip_level: u32,
+
+    /// The position of the row under the mouse, if any.
+    hovered: Option<usize>,
+}
+
+/// A TextureInspector is a developer panel which lists every texture owned by
+/// an [`AssetLoader`] along with its size, mip levels, and gpu memory. The
+/// selected texture is previewed beside the list.
+///
+/// Mip levels can only be previewed for textures which have views created by
+/// [`AssetLoader::create_mip_level_views`]. Font atlases registered with
+/// [`TextureInspector::atlas`] are drawn with the outline of every packed
+/// glyph.
+///
+/// # Controls
+///
+/// - Click a texture in the list to preview it.
+/// - Scroll over the preview to change the mip level.
+pub struct TextureInspector<Message> {
+    /// The ID uniquely identifies this inspector when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The font used to build the inspector's labels.
+    font: Font,
+
+    /// A description of every texture, indexed by texture index.
+    texture_info: Vec<TextureInfo>,
+
+    /// Every listed texture. Mip level views are not listed, they're shown
+    /// in the preview instead.
+    listed: Vec<TextureId>,
+
+    /// The glyph texture coords for each registered font atlas.
+    atlases: Vec<(TextureId, Vec<Rect>)>,
+
+    /// The summary shown above the list.
+    header: Label,
+
+    /// One label per listed texture.
+    rows: Vec<Label>,
+
+    /// The details about the previewed texture, one label per line.
+    details: Vec<Label>,
+
+    /// The space occupied by the entire inspector on screen.
+    bounds: Rect,
+
+    /// The space occupied by each row in the list.
+    row_bounds: Vec<Rect>,
+
+    /// The space available for the preview.
+    preview: Rect,
+
+    /// The width and height of the preview area.
+    preview_size: f32,
+
+    /// The space between the list and the preview.
+    spacing: f32,
+
+    /// The color behind the preview.
+    background_color: Vec4,
+
+    /// The color behind the selected and hovered rows.
+    highlight_color: Vec4,
+
+    /// The color used to outline the preview and packed glyphs.
+    accent_color: Vec4,
+
+    _phantom_data: std::marker::PhantomData<Message>,
+}
+
+impl<Message> TextureInspector<Message> {
+    /// Create an inspector for the textures which currently exist in the
+    /// asset loader.
+    pub fn new(id: Id, font: &Font, asset_loader: &AssetLoader) -> Self {
+        let texture_info = asset_loader.texture_info().to_vec();
+        let listed: Vec<TextureId> = texture_info
+            .iter()
+            .enumerate()
+            .filter(|(_, info)| info.mip_view.is_none())
+            .map(|(index, _)| TextureId::from_index(index as i32))
+            .collect();
+        let total_bytes: u64 =
+            texture_info.iter().map(|info| info.byte_size).sum();
+
+        let header = Label::new(
+            font,
+            format!(
+                "{} textures, {} of gpu memory",
+                listed.len(),
+                format_bytes(total_bytes)
+            ),
+        );
+        let rows = listed
+            .iter()
+            .map(|&index| {
+                let info = &texture_info[index.index() as usize];
+                Label::new(
+                    font,
+                    format!(
+                        "{}: {}  {}x{}  {} mips  {}",
+                        index,
+                        info.name,
+                        info.width,
+                        info.height,
+                        info.mip_levels,
+                        format_bytes(info.byte_size)
+                    ),
+                )
+            })
+            .collect();
+
+        let line_height = font.line_height();
+        Self {
+            id,
+            font: font.clone(),
+            texture_info,
+            listed,
+            atlases: vec![],
+            header,
+            rows,
+            details: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            row_bounds: vec![],
+            preview: Rect::new(0.0, 0.0, 0.0, 0.0),
+            preview_size: 16.0 * line_height,
+            spacing: line_height,
+            background_color: vec4(0.1, 0.1, 0.1, 1.0),
+            highlight_color: vec4(1.0, 1.0, 1.0, 0.1),
+            accent_color: vec4(0.4, 0.8, 1.0, 0.6),
+            _phantom_data: Default::default(),
+        }
+    }
+
+    builder_field!(preview_size, f32);
+    builder_field!(spacing, f32);
+    builder_field!(background_color, Vec4);
+    builder_field!(highlight_color, Vec4);
+    builder_field!(accent_color, Vec4);
+
+    /// Show the glyph packing for the given font's atlas texture.
+    pub fn atlas(mut self, font: &Font) -> Self {
+        self.atlases.push((
+            font.texture_index(),
+            font.glyph_texture_coords().copied().collect(),
+        ));
+        self
+    }
+
+    /// The info for the selected texture along with its id.
+    fn selected(
+        &self,
+        state: &TextureInspectorState,
+    ) -> Option<(TextureId, &TextureInfo)> {
+        self.listed
+            .get(state.selected)
+            .map(|&index| (index, &self.texture_info[index.index() as usize]))
+    }
+
+    /// The texture used to draw a single mip level of a texture, if there
+    /// is one.
+    fn mip_level_view(
+        &self,
+        texture_index: TextureId,
+        level: u32,
+    ) -> Option<TextureId> {
+        if level == 0 {
+            return Some(texture_index);
+        }
+        self.texture_info
+            .iter()
+            .position(|info| info.mip_view == Some((texture_index, level)))
+            .map(|index| TextureId::from_index(index as i32))
+    }
+
+    /// The glyph texture coords for the given texture, if it's an atlas.
+    fn glyphs(&self, texture_index: TextureId) -> Option<&[Rect]> {
+        self.atlases
+            .iter()
+            .find(|(index, _)| *index == texture_index)
+            .map(|(_, glyphs)| glyphs.as_slice())
+    }
+
+    /// The text shown below the preview.
+    fn describe(&self, state: &TextureInspectorState) -> Vec<String> {
+        let (index, info) = match self.selected(state) {
+            Some(selected) => selected,
+            None => return vec!["No textures".to_owned()],
+        };
+
+        let (width, height) = info.mip_extent(state.mip_level);
+        let mut lines = vec![
+            format!("{}: {}", index, info.name),
+            format!(
+                "Mip {} of {}, {}x{}",
+                state.mip_level, info.mip_levels, width, height
+            ),
+        ];
+        if self.mip_level_view(index, state.mip_level).is_none() {
+            lines.push("No view for this mip level".to_owned());
+        } else if info.mip_levels > 1 {
+            lines.push("Scroll to change the mip level".to_owned());
+        }
+        if let Some(glyphs) = self.glyphs(index) {
+            let used: f32 = glyphs
+                .iter()
+                .map(|glyph| glyph.width().abs() * glyph.height().abs())
+                .sum();
+            lines.push(format!(
+                "{} glyphs, {:.0}% of the atlas is used",
+                glyphs.len(),
+                100.0 * used
+            ));
+        }
+        lines
+    }
+
+    /// The space used to draw the texture inside of the preview area. The
+    /// texture keeps its aspect ratio.
+    fn image_bounds(&self, info: &TextureInfo) -> Rect {
+        let aspect = info.width as f32 / info.height as f32;
+        let (width, height) = if aspect >= 1.0 {
+            (self.preview.width(), self.preview.width() / aspect)
+        } else {
+            (self.preview.height() * aspect, self.preview.height())
+        };
+        let left = self.preview.left() + 0.5 * (self.preview.width() - width);
+        let top = self.preview.top() + 0.5 * (self.preview.height() - height);
+        Rect::new(
+            top.round(),
+            left.round(),
+            (top + height).round(),
+            (left + width).round(),
+        )
+    }
+}
+
+impl<Message> Widget<Message> for TextureInspector<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mut state =
+            *internal_state.get_state::<TextureInspectorState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.hovered = self
+                    .row_bounds
+                    .iter()
+                    .position(|row| row.contains(input.mouse_position));
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if let Some(hovered) = state.hovered {
+                    state.selected = hovered;
+                    state.mip_level = 0;
+                }
+            }
+            WindowEvent::Scroll(_, y)
+                if self.preview.contains(input.mouse_position) =>
+            {
+                if let Some((_, info)) = self.selected(&state) {
+                    let max_level = info.mip_levels.saturating_sub(1);
+                    state.mip_level = if y > 0.0 {
+                        (state.mip_level + 1).min(max_level)
+                    } else if y < 0.0 {
+                        state.mip_level.saturating_sub(1)
+                    } else {
+                        state.mip_level
+                    };
+                }
+            }
+            _ => (),
+        }
+        *internal_state.get_state_mut::<TextureInspectorState>(&self.id) =
+            state;
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state =
+            *internal_state.get_state::<TextureInspectorState>(&self.id);
+
+        for (i, row) in self.row_bounds.iter().enumerate() {
+            if i == state.selected || Some(i) == state.hovered {
+                Tile {
+                    model: *row,
+                    color: self.highlight_color,
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+        }
+
+        Widget::<Message>::draw_frame(&self.header, internal_state, frame)?;
+        for label in self.rows.iter().chain(self.details.iter()) {
+            Widget::<Message>::draw_frame(label, internal_state, frame)?;
+        }
+
+        Tile {
+            model: self.preview,
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let (index, info) = match self.selected(&state) {
+            Some(selected) => selected,
+            None => return Ok(()),
+        };
+        let image = self.image_bounds(info);
+        if let Some(texture_index) = self.mip_level_view(index, state.mip_level)
+        {
+            Tile {
+                model: image,
+                texture_index,
+                ..Default::default()
+            }
+            .fill(frame)?;
+        }
+        Tile {
+            model: image,
+            color: self.accent_color,
+            ..Default::default()
+        }
+        .outline(frame)?;
+
+        if let Some(glyphs) = self.glyphs(index) {
+            let to_preview = |uv: f32, start: f32, size: f32| start + uv * size;
+            for glyph in glyphs {
+                Tile {
+                    model: Rect::new(
+                        to_preview(glyph.top(), image.top(), image.height()),
+                        to_preview(glyph.left(), image.left(), image.width()),
+                        to_preview(glyph.bottom(), image.top(), image.height()),
+                        to_preview(glyph.right(), image.left(), image.width()),
+                    ),
+                    color: self.accent_color,
+                    ..Default::default()
+                }
+                .outline(frame)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let state =
+            *internal_state.get_state::<TextureInspectorState>(&self.id);
+        let font = &self.font;
+        self.details = self
+            .describe(&state)
+            .iter()
+            .map(|text| Label::new(font, text))
+            .collect();
+
+        let line_height = self.font.line_height();
+        let header_size = Widget::<Message>::dimensions(
+            &mut self.header,
+            internal_state,
+            max_size,
+        );
+
+        let list_max_size = Dimensions::new(
+            0f32.max(max_size.width - self.preview_size - self.spacing),
+            max_size.height,
+        );
+        let mut list_width: f32 = 0.0;
+        for row in &mut self.rows {
+            let size = Widget::<Message>::dimensions(
+                row,
+                internal_state,
+                &list_max_size,
+            );
+            list_width = list_width.max(size.width);
+        }
+        self.row_bounds = vec![
+            Dimensions::new(list_width, line_height)
+                .as_rect();
+            self.rows.len()
+        ];
+
+        let preview_max_size =
+            Dimensions::new(self.preview_size, max_size.height);
+        let mut details_width: f32 = 0.0;
+        for label in &mut self.details {
+            let size = Widget::<Message>::dimensions(
+                label,
+                internal_state,
+                &preview_max_size,
+            );
+            details_width = details_width.max(size.width);
+        }
+        self.preview =
+            Dimensions::new(self.preview_size, self.preview_size).as_rect();
+
+        let list_height = self.rows.len() as f32 * line_height;
+        let preview_height =
+            self.preview_size + self.details.len() as f32 * line_height;
+        self.bounds = Dimensions::new(
+            header_size
+                .width
+                .max(list_width + self.spacing + self.preview_size)
+                .max(list_width + self.spacing + details_width),
+            line_height + list_height.max(preview_height),
+        )
+        .min(max_size)
+        .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let line_height = self.font.line_height();
+        self.bounds = self.bounds.set_top_left_position(position);
+        Widget::<Message>::set_top_left_position(
+            &mut self.header,
+            internal_state,
+            position,
+        );
+
+        let list_position = position + vec2(0.0, line_height);
+        let mut list_width: f32 = 0.0;
+        for (i, (row, label)) in
+            self.row_bounds.iter_mut().zip(&mut self.rows).enumerate()
+        {
+            let row_position =
+                list_position + vec2(0.0, i as f32 * line_height);
+            *row = row.set_top_left_position(row_position);
+            list_width = list_width.max(row.width());
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                row_position,
+            );
+        }
+
+        let preview_position =
+            list_position + vec2(list_width + self.spacing, 0.0);
+        self.preview = self.preview.set_top_left_position(preview_position);
+        let mut label_position =
+            preview_position + vec2(0.0, self.preview.height());
+        for label in &mut self.details {
+            Widget::<Message>::set_top_left_position(
+                label,
+                internal_state,
+                label_position,
+            );
+            label_position.y += line_height;
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for TextureInspector<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Format a number of bytes with a binary unit suffix.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_bytes;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(4 * 1024 * 1024), "4.0 MiB");
+    }
+}
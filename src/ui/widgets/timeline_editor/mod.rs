@@ -0,0 +1,676 @@
+//! A timeline editor shows tracks of keyframes along a shared time axis.
+//!
+//! Like the rest of the UI, the editor does not own any animation data. The
+//! application provides the tracks, keyframe times, and playhead each time the
+//! view is built, and the editor emits [`TimelineEdit`] messages which the
+//! application applies to its own model. Passing the application's current
+//! time as the playhead every frame keeps the playhead synchronized with the
+//! frame clock.
+
+mod track;
+
+use ::anyhow::Result;
+
+use crate::{
+    asset_loader::TextureId,
+    builder_field,
+    immediate_mode_graphics::{triangles::Frame, Vertex, VertexStream},
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, vec3, vec4, Vec2, Vec4,
+};
+
+pub use self::track::Track;
+
+/// The smallest space, in pixels, between two ticks on the time ruler.
+const MIN_TICK_SPACING: f32 = 64.0;
+
+/// Edits made by the user in a [`TimelineEditor`]. Tracks and keyframes are
+/// referred to by their index in the order they were given to the editor.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TimelineEdit {
+    /// The user right-clicked an empty part of a track.
+    KeyframeAdded { track: usize, time: f32 },
+
+    /// The user dragged a keyframe to a new time.
+    KeyframeMoved {
+        track: usize,
+        keyframe: usize,
+        time: f32,
+    },
+
+    /// The user right-clicked a keyframe.
+    KeyframeDeleted { track: usize, keyframe: usize },
+
+    /// The user clicked or dragged on the time ruler.
+    PlayheadMoved(f32),
+}
+
+/// How the user is currently interacting with the timeline.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Interaction {
+    Idle,
+
+    /// The playhead is being dragged along the ruler.
+    Scrubbing,
+
+    /// A keyframe is being dragged along its track.
+    DraggingKeyframe {
+        track: usize,
+        keyframe: usize,
+    },
+
+    /// The visible time range is being dragged. The last mouse x position is
+    /// kept so the view can follow the mouse.
+    Panning {
+        last_x: f32,
+    },
+}
+
+impl Default for Interaction {
+    /// Defaults to Idle
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+/// A TimelineEditor's state is stored in the UI InternalState so the visible
+/// time range persists between views.
+#[derive(Debug, Copy, Clone)]
+pub struct TimelineEditorState {
+    /// The time at the left edge of the visible range.
+    view_start: f32,
+
+    /// How far the time axis is zoomed in. 1.0 shows the full duration.
+    zoom: f32,
+
+    /// The current interaction.
+    interaction: Interaction,
+}
+
+impl Default for TimelineEditorState {
+    fn default() -> Self {
+        Self {
+            view_start: 0.0,
+            zoom: 1.0,
+            interaction: Interaction::Idle,
+        }
+    }
+}
+
+/// A TimelineEditor lays out [`Track`]s of keyframes below a time ruler with
+/// a playhead.
+///
+/// # Controls
+///
+/// - Click or drag the ruler to move the playhead.
+/// - Drag a keyframe to move it.
+/// - Right-click a track to add a keyframe, or a keyframe to delete it.
+/// - Scroll to zoom the time axis around the mouse.
+/// - Drag with the middle mouse button to pan the time axis.
+pub struct TimelineEditor<Message> {
+    /// The ID uniquely identifies this editor when constructing and modifying
+    /// state.
+    id: Id,
+
+    /// The font used for the ruler's time labels.
+    font: Font,
+
+    /// The tracks, drawn top to bottom.
+    tracks: Vec<Track>,
+
+    /// The length of the timeline. Times are always in [0, duration].
+    duration: f32,
+
+    /// The time under the playhead.
+    playhead: f32,
+
+    /// The space occupied by the editor on screen.
+    bounds: Rect,
+
+    /// The width of the column of track names.
+    label_width: f32,
+
+    /// The space between text and the edges of each row.
+    padding: f32,
+
+    /// The most the time axis can be zoomed in.
+    max_zoom: f32,
+
+    /// The color behind the tracks.
+    background_color: Vec4,
+
+    /// The color of every other track, to make rows easy to follow.
+    alternate_color: Vec4,
+
+    /// The color of the ruler and its ticks.
+    ruler_color: Vec4,
+
+    /// The color of the playhead.
+    playhead_color: Vec4,
+
+    /// The function used to build a message when the user edits the timeline.
+    on_edit: Option<Box<dyn Fn(TimelineEdit) -> Message>>,
+}
+
+impl<Message> TimelineEditor<Message> {
+    pub fn new(id: Id, font: &Font, duration: f32) -> Self {
+        Self {
+            id,
+            font: font.clone(),
+            tracks: vec![],
+            duration: duration.max(f32::EPSILON),
+            playhead: 0.0,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            label_width: 0.0,
+            padding: 0.25 * font.line_height(),
+            max_zoom: 100.0,
+            background_color: vec4(0.08, 0.08, 0.08, 1.0),
+            alternate_color: vec4(0.12, 0.12, 0.12, 1.0),
+            ruler_color: vec4(0.2, 0.2, 0.2, 1.0),
+            playhead_color: vec4(0.9, 0.2, 0.2, 1.0),
+            on_edit: None,
+        }
+    }
+
+    builder_field!(padding, f32);
+    builder_field!(max_zoom, f32);
+    builder_field!(background_color, Vec4);
+    builder_field!(alternate_color, Vec4);
+    builder_field!(ruler_color, Vec4);
+    builder_field!(playhead_color, Vec4);
+
+    /// Set the playhead's time. Clamped to the timeline's duration.
+    pub fn playhead(self, playhead: f32) -> Self {
+        Self {
+            playhead: playhead.clamp(0.0, self.duration),
+            ..self
+        }
+    }
+
+    /// Add a track below all existing tracks.
+    pub fn track(mut self, track: Track) -> Self {
+        self.tracks.push(track);
+        self
+    }
+
+    /// Set the function used to build a message when the user edits the
+    /// timeline.
+    pub fn on_edit<F>(self, on_edit_fn: F) -> Self
+    where
+        F: 'static + Fn(TimelineEdit) -> Message,
+    {
+        Self {
+            on_edit: Some(Box::new(on_edit_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> TimelineEditor<Message> {
+    /// The height of the ruler and of each track.
+    fn row_height(&self) -> f32 {
+        self.font.line_height() + 2.0 * self.padding
+    }
+
+    /// The part of the editor which displays time, to the right of the track
+    /// names.
+    fn time_area(&self) -> Rect {
+        Rect::new(
+            self.bounds.top(),
+            self.bounds.left() + self.label_width,
+            self.bounds.bottom(),
+            self.bounds.right(),
+        )
+    }
+
+    /// The time ruler along the top of the time area.
+    fn ruler(&self) -> Rect {
+        let area = self.time_area();
+        Rect::new(
+            area.top(),
+            area.left(),
+            area.top() + self.row_height(),
+            area.right(),
+        )
+    }
+
+    /// The full-width row for a track.
+    fn track_row(&self, track: usize) -> Rect {
+        let top = self.bounds.top() + (track + 1) as f32 * self.row_height();
+        Rect::new(
+            top,
+            self.bounds.left(),
+            top + self.row_height(),
+            self.bounds.right(),
+        )
+    }
+
+    /// The length of time visible in the editor.
+    fn visible_span(&self, state: &TimelineEditorState) -> f32 {
+        self.duration / state.zoom
+    }
+
+    fn time_to_x(&self, state: &TimelineEditorState, time: f32) -> f32 {
+        let area = self.time_area();
+        let t = (time - state.view_start) / self.visible_span(state);
+        area.left() + t * area.width()
+    }
+
+    fn x_to_time(&self, state: &TimelineEditorState, x: f32) -> f32 {
+        let area = self.time_area();
+        let t = (x - area.left()) / area.width().max(1.0);
+        (state.view_start + t * self.visible_span(state))
+            .clamp(0.0, self.duration)
+    }
+
+    /// Keep the visible range inside of the timeline's duration.
+    fn clamp_view(&self, state: &mut TimelineEditorState) {
+        state.zoom = state.zoom.clamp(1.0, self.max_zoom.max(1.0));
+        let max_start = self.duration - self.visible_span(state);
+        state.view_start = state.view_start.clamp(0.0, max_start.max(0.0));
+    }
+
+    /// The half-width of the diamond drawn for each keyframe.
+    fn keyframe_radius(&self) -> f32 {
+        0.35 * self.row_height()
+    }
+
+    /// Get the keyframe closest to a point, if the point is on top of it.
+    fn keyframe_at(
+        &self,
+        state: &TimelineEditorState,
+        point: Vec2,
+    ) -> Option<(usize, usize)> {
+        let track = self.track_at(point)?;
+        let radius = self.keyframe_radius();
+        self.tracks[track]
+            .keyframes
+            .iter()
+            .enumerate()
+            .map(|(index, time)| {
+                (index, (self.time_to_x(state, *time) - point.x).abs())
+            })
+            .filter(|(_, distance)| *distance <= radius)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| (track, index))
+    }
+
+    /// Get the track under a point in the time area.
+    fn track_at(&self, point: Vec2) -> Option<usize> {
+        if !self.time_area().contains(point) {
+            return None;
+        }
+        (0..self.tracks.len()).find(|track| {
+            let row = self.track_row(*track);
+            row.top() <= point.y && point.y <= row.bottom()
+        })
+    }
+
+    fn edit(&self, edit: TimelineEdit) -> Option<Message> {
+        self.on_edit.as_ref().map(|on_edit| on_edit(edit))
+    }
+
+    /// Draw a diamond centered at the given point.
+    fn draw_diamond(
+        &self,
+        center: Vec2,
+        radius: f32,
+        color: Vec4,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let vertex = |x: f32, y: f32| {
+            Vertex::new(
+                vec3(x, y, 0.0),
+                color,
+                vec2(0.0, 0.0),
+                TextureId::WHITE_PIXEL,
+            )
+        };
+        frame.push_vertices(
+            &[
+                vertex(center.x, center.y - radius),
+                vertex(center.x + radius, center.y),
+                vertex(center.x, center.y + radius),
+                vertex(center.x - radius, center.y),
+            ],
+            &[0, 1, 2, 0, 2, 3],
+        )
+    }
+
+    /// Draw ticks and time labels along the ruler, and faint grid lines down
+    /// through each track.
+    fn draw_ruler(
+        &self,
+        state: &TimelineEditorState,
+        theme: &Theme,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let ruler = self.ruler();
+        Tile {
+            model: ruler,
+            color: self.ruler_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let span = self.visible_span(state);
+        let step = tick_step(span, ruler.width(), MIN_TICK_SPACING);
+        let decimals = (-step.log10().floor()).max(0.0) as usize;
+        let view_end = state.view_start + span;
+
+        let mut tick = (state.view_start / step).ceil() * step;
+        while tick <= view_end {
+            let x = self.time_to_x(state, tick).round();
+            Tile {
+                model: Rect::new(
+                    ruler.top() + 0.5 * ruler.height(),
+                    x,
+                    ruler.bottom(),
+                    x + 1.0,
+                ),
+                color: vec4(0.6, 0.6, 0.6, 1.0),
+                ..Default::default()
+            }
+            .fill(frame)?;
+            Tile {
+                model: Rect::new(
+                    ruler.bottom(),
+                    x,
+                    self.bounds.bottom(),
+                    x + 1.0,
+                ),
+                color: vec4(1.0, 1.0, 1.0, 0.05),
+                ..Default::default()
+            }
+            .fill(frame)?;
+
+            let text = format!("{:.*}", decimals, tick);
+            let (tiles, bounds) = self.font.build_text_tiles(text);
+            let offset = vec2(x + self.padding, ruler.top()) - bounds.top_left;
+            for mut tile in tiles {
+                tile.color = self.font.text_color_or(theme);
+                tile.model = tile
+                    .model
+                    .translate(vec2(offset.x.round(), offset.y.round()));
+                if tile.model.right() <= ruler.right() {
+                    tile.fill(frame)?;
+                }
+            }
+
+            tick += step;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Widget<Message> for TimelineEditor<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let mut state =
+            *internal_state.get_state::<TimelineEditorState>(&self.id);
+        let mouse = input.mouse_position;
+        let inside = self.time_area().contains(mouse);
+
+        let message_opt = match *event {
+            WindowEvent::CursorPos(x, _) => match state.interaction {
+                Interaction::Scrubbing => {
+                    let time = self.x_to_time(&state, x as f32);
+                    self.edit(TimelineEdit::PlayheadMoved(time))
+                }
+                Interaction::DraggingKeyframe { track, keyframe } => {
+                    let time = self.x_to_time(&state, x as f32);
+                    self.edit(TimelineEdit::KeyframeMoved {
+                        track,
+                        keyframe,
+                        time,
+                    })
+                }
+                Interaction::Panning { last_x } => {
+                    let area_width = self.time_area().width().max(1.0);
+                    let offset = (x as f32 - last_x) / area_width
+                        * self.visible_span(&state);
+                    state.view_start -= offset;
+                    state.interaction =
+                        Interaction::Panning { last_x: x as f32 };
+                    self.clamp_view(&mut state);
+                    None
+                }
+                Interaction::Idle => None,
+            },
+            WindowEvent::Scroll(_, y_offset) if inside => {
+                let anchor_time = self.x_to_time(&state, mouse.x);
+                let area = self.time_area();
+                let anchor_t = (mouse.x - area.left()) / area.width().max(1.0);
+                state.zoom *= 1.1f32.powf(y_offset as f32);
+                self.clamp_view(&mut state);
+
+                // keep the time under the mouse fixed while zooming
+                state.view_start =
+                    anchor_time - anchor_t * self.visible_span(&state);
+                self.clamp_view(&mut state);
+                None
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if inside && state.interaction == Interaction::Idle => {
+                if self.ruler().contains(mouse) {
+                    state.interaction = Interaction::Scrubbing;
+                    let time = self.x_to_time(&state, mouse.x);
+                    self.edit(TimelineEdit::PlayheadMoved(time))
+                } else {
+                    if let Some((track, keyframe)) =
+                        self.keyframe_at(&state, mouse)
+                    {
+                        state.interaction =
+                            Interaction::DraggingKeyframe { track, keyframe };
+                    }
+                    None
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button2,
+                Action::Press,
+                _,
+            ) if inside && state.interaction == Interaction::Idle => {
+                if let Some((track, keyframe)) = self.keyframe_at(&state, mouse)
+                {
+                    self.edit(TimelineEdit::KeyframeDeleted { track, keyframe })
+                } else if let Some(track) = self.track_at(mouse) {
+                    let time = self.x_to_time(&state, mouse.x);
+                    self.edit(TimelineEdit::KeyframeAdded { track, time })
+                } else {
+                    None
+                }
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button3,
+                Action::Press,
+                _,
+            ) if inside && state.interaction == Interaction::Idle => {
+                state.interaction = Interaction::Panning { last_x: mouse.x };
+                None
+            }
+            WindowEvent::MouseButton(_, Action::Release, _) => {
+                state.interaction = Interaction::Idle;
+                None
+            }
+            _ => None,
+        };
+
+        *internal_state.get_state_mut::<TimelineEditorState>(&self.id) = state;
+        Ok(message_opt)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let state = *internal_state.get_state::<TimelineEditorState>(&self.id);
+
+        Tile {
+            model: self.bounds,
+            color: self.background_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+        for (index, track) in self.tracks.iter().enumerate() {
+            if index % 2 == 1 {
+                Tile {
+                    model: self.track_row(index),
+                    color: self.alternate_color,
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+            Widget::<Message>::draw_frame(&track.label, internal_state, frame)?;
+        }
+
+        let theme = *internal_state.theme();
+        self.draw_ruler(&state, &theme, frame)?;
+
+        let area = self.time_area();
+        let radius = self.keyframe_radius();
+        for (track_index, track) in self.tracks.iter().enumerate() {
+            let row = self.track_row(track_index);
+            let y = 0.5 * (row.top() + row.bottom());
+            for (keyframe_index, time) in track.keyframes.iter().enumerate() {
+                let x = self.time_to_x(&state, *time);
+                if x < area.left() || x > area.right() {
+                    continue;
+                }
+                let dragging = state.interaction
+                    == Interaction::DraggingKeyframe {
+                        track: track_index,
+                        keyframe: keyframe_index,
+                    };
+                let color = if dragging {
+                    vec4(1.0, 1.0, 1.0, 1.0)
+                } else {
+                    track.color
+                };
+                self.draw_diamond(vec2(x, y), radius, color, frame)?;
+            }
+        }
+
+        let playhead_x = self.time_to_x(&state, self.playhead).round();
+        if area.left() <= playhead_x && playhead_x <= area.right() {
+            Tile {
+                model: Rect::new(
+                    area.top(),
+                    playhead_x - 1.0,
+                    area.bottom(),
+                    playhead_x + 1.0,
+                ),
+                color: self.playhead_color,
+                ..Default::default()
+            }
+            .fill(frame)?;
+            let ruler = self.ruler();
+            self.draw_diamond(
+                vec2(playhead_x, ruler.bottom()),
+                0.25 * ruler.height(),
+                self.playhead_color,
+                frame,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let widest_label = self
+            .tracks
+            .iter_mut()
+            .map(|track| {
+                Widget::<Message>::dimensions(
+                    &mut track.label,
+                    internal_state,
+                    max_size,
+                )
+                .width
+            })
+            .fold(0.0, f32::max);
+        self.label_width = widest_label + 2.0 * self.padding;
+
+        let height = (self.tracks.len() + 1) as f32 * self.row_height();
+        self.bounds = Dimensions::new(max_size.width, height)
+            .min(max_size)
+            .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        for index in 0..self.tracks.len() {
+            let row = self.track_row(index);
+            Widget::<Message>::set_top_left_position(
+                &mut self.tracks[index].label,
+                internal_state,
+                row.top_left + vec2(self.padding, self.padding),
+            );
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for TimelineEditor<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Pick a 'nice' time step - 1, 2, or 5 times a power of ten - so that ticks
+/// are at least `min_spacing` pixels apart.
+pub(super) fn tick_step(span: f32, width: f32, min_spacing: f32) -> f32 {
+    let min_step = span * min_spacing / width.max(1.0);
+    let magnitude = 10f32.powf(min_step.log10().floor());
+    for multiplier in &[1.0, 2.0, 5.0] {
+        let step = multiplier * magnitude;
+        if step >= min_step {
+            return step;
+        }
+    }
+    10.0 * magnitude
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tick_step_picks_nice_values() {
+        assert_eq!(tick_step(10.0, 640.0, 64.0), 1.0);
+        assert_eq!(tick_step(10.0, 320.0, 64.0), 2.0);
+        assert_eq!(tick_step(10.0, 200.0, 64.0), 5.0);
+        assert_eq!(tick_step(10.0, 100.0, 64.0), 10.0);
+    }
+
+    #[test]
+    fn test_tick_step_respects_min_spacing() {
+        let step = tick_step(3.7, 500.0, 64.0);
+        assert!(step / 3.7 * 500.0 >= 64.0);
+    }
+}
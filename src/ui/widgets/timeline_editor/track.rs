@@ -0,0 +1,49 @@
+use crate::{
+    builder_field,
+    ui::{widgets::Label, Font},
+    vec4, Vec4,
+};
+
+/// A single named row of keyframes in a [`super::TimelineEditor`].
+pub struct Track {
+    /// The track's name, rendered to the left of the keyframes.
+    pub(super) label: Label,
+
+    /// The time of each keyframe. Edits refer to keyframes by their index in
+    /// this list.
+    pub(super) keyframes: Vec<f32>,
+
+    /// The color used to draw the track's keyframes.
+    pub(super) color: Vec4,
+}
+
+impl Track {
+    /// Create a new track without any keyframes.
+    pub fn new<T>(font: &Font, name: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            label: Label::new(font, name),
+            keyframes: vec![],
+            color: vec4(0.9, 0.7, 0.2, 1.0),
+        }
+    }
+
+    builder_field!(color, Vec4);
+
+    /// Add a keyframe at the given time.
+    pub fn keyframe(mut self, time: f32) -> Self {
+        self.keyframes.push(time);
+        self
+    }
+
+    /// Add a keyframe for each time.
+    pub fn keyframes<I>(mut self, times: I) -> Self
+    where
+        I: IntoIterator<Item = f32>,
+    {
+        self.keyframes.extend(times);
+        self
+    }
+}
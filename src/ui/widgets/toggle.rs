@@ -0,0 +1,327 @@
+use ::{anyhow::Result, std::time::Duration};
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{button::ButtonState, Element, Label, Widget},
+        Font, Id, Input, InternalState, UISound,
+    },
+    vec2, vec4, Vec2, Vec4,
+};
+
+/// A Toggle's state is stored in the UI InternalState so the value and the
+/// knob's animation persist between views.
+#[derive(Debug, Copy, Clone, Default)]
+struct ToggleState {
+    /// The toggle's value. This is None until the user first flips the
+    /// switch, in which case the Toggle's initial value is used.
+    on: Option<bool>,
+
+    /// The knob's position, 0 when off and 1 when on. None until the toggle
+    /// is first drawn, so the knob doesn't animate into place when the
+    /// toggle appears.
+    knob: Option<f32>,
+
+    /// The mouse interaction state.
+    interaction: ButtonState,
+}
+
+/// A Toggle is an on/off switch with a text label. Clicking anywhere on the
+/// switch or the label flips the value, and the knob slides to its new side.
+/// Space or Enter flips the value while the toggle has keyboard focus.
+pub struct Toggle<Message> {
+    /// The ID uniquely identifies this toggle when constructing and
+    /// modifying state.
+    id: Id,
+
+    /// The label rendered beside the switch.
+    label: Label,
+
+    /// The space occupied by the entire toggle on screen.
+    bounds: Rect,
+
+    /// The space occupied by the switch's track.
+    track: Rect,
+
+    /// The horizontal space between the switch and the label.
+    spacing: f32,
+
+    /// The value used before the user has flipped the switch.
+    initial_value: bool,
+
+    /// The track's color when the toggle is off.
+    off_color: Option<Vec4>,
+
+    /// The track's color when the toggle is on.
+    on_color: Option<Vec4>,
+
+    /// The knob's color.
+    knob_color: Vec4,
+
+    /// How long the knob takes to slide from one side to the other.
+    animation_duration: Duration,
+
+    /// The function used to build a message when the value is flipped.
+    on_toggle: Option<Box<dyn Fn(bool) -> Message>>,
+}
+
+impl<Message> Toggle<Message> {
+    pub fn new<T>(id: Id, font: &Font, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            id,
+            label: Label::new(font, text),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            track: Rect::new(0.0, 0.0, 0.0, 0.0),
+            spacing: 0.5 * font.line_height(),
+            initial_value: false,
+            off_color: None,
+            on_color: None,
+            knob_color: vec4(1.0, 1.0, 1.0, 1.0),
+            animation_duration: Duration::from_millis(120),
+            on_toggle: None,
+        }
+    }
+
+    builder_field!(initial_value, bool);
+    builder_field!(spacing, f32);
+    builder_field_some!(off_color, Vec4);
+    builder_field_some!(on_color, Vec4);
+    builder_field!(knob_color, Vec4);
+    builder_field!(animation_duration, Duration);
+
+    /// Set the function used to build a message when the toggle is flipped.
+    /// The function is given the new value.
+    pub fn on_toggle<F>(self, on_toggle_fn: F) -> Self
+    where
+        F: 'static + Fn(bool) -> Message,
+    {
+        Self {
+            on_toggle: Some(Box::new(on_toggle_fn)),
+            ..self
+        }
+    }
+}
+
+impl<Message> Widget<Message> for Toggle<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, Key, MouseButton, WindowEvent};
+
+        let focused = internal_state.is_focused(&self.id);
+        let state = internal_state.get_state_mut::<ToggleState>(&self.id);
+        match *event {
+            WindowEvent::CursorPos(x, y) => {
+                if self.bounds.contains(vec2(x as f32, y as f32)) {
+                    if state.interaction == ButtonState::Inactive {
+                        state.interaction = ButtonState::Hover;
+                        internal_state.play_sound(UISound::Hover);
+                    }
+                } else {
+                    state.interaction = ButtonState::Inactive;
+                }
+                Ok(None)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                if state.interaction == ButtonState::Hover {
+                    state.interaction = ButtonState::Pressed;
+                    internal_state.set_focus(self.id);
+                }
+                Ok(None)
+            }
+            WindowEvent::Key(
+                Key::Space | Key::Enter | Key::KpEnter,
+                _,
+                Action::Press,
+                _,
+            ) if focused => {
+                let on = !state.on.unwrap_or(self.initial_value);
+                state.on = Some(on);
+                internal_state.play_sound(UISound::Toggle);
+                Ok(self.on_toggle.as_ref().map(|on_toggle| on_toggle(on)))
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                if state.interaction != ButtonState::Pressed {
+                    // the release was unrelated to the toggle
+                    return Ok(None);
+                }
+                if !self.bounds.contains(input.mouse_position) {
+                    // the press started here, but the mouse was released
+                    // somewhere else so it doesn't count as a click
+                    state.interaction = ButtonState::Inactive;
+                    return Ok(None);
+                }
+                state.interaction = ButtonState::Hover;
+
+                let on = !state.on.unwrap_or(self.initial_value);
+                state.on = Some(on);
+                internal_state.play_sound(UISound::Toggle);
+                Ok(self.on_toggle.as_ref().map(|on_toggle| on_toggle(on)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let frame_delta = internal_state.frame_delta();
+        let state = internal_state.get_state_mut::<ToggleState>(&self.id);
+        let knob = slide_knob(
+            state.knob,
+            state.on.unwrap_or(self.initial_value),
+            frame_delta,
+            self.animation_duration,
+        );
+        state.knob = Some(knob);
+        let state = *state;
+
+        let off_color = self.off_color.unwrap_or(theme.hover_color);
+        let on_color = self.on_color.unwrap_or(theme.accent_color);
+        let mut track_color = off_color + (on_color - off_color) * knob;
+        if state.interaction != ButtonState::Inactive {
+            // brighten the track while it's hovered
+            track_color += vec4(0.1, 0.1, 0.1, 0.0);
+        }
+        Tile {
+            model: self.track,
+            color: track_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        let inset = (0.15 * self.track.height()).round();
+        let knob_size = self.track.height() - 2.0 * inset;
+        let travel = self.track.width() - knob_size - 2.0 * inset;
+        let left = self.track.left() + inset + knob * travel;
+        Tile {
+            model: Rect::new(
+                self.track.top() + inset,
+                left,
+                self.track.bottom() - inset,
+                left + knob_size,
+            ),
+            color: self.knob_color,
+            ..Default::default()
+        }
+        .fill(frame)?;
+
+        Widget::<Message>::draw_frame(&self.label, internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let label_max_size = Dimensions::new(
+            0f32.max(max_size.width - self.spacing),
+            max_size.height,
+        );
+        let label_dimensions = Widget::<Message>::dimensions(
+            &mut self.label,
+            internal_state,
+            &label_max_size,
+        );
+
+        // The track is twice as wide as the label is tall, giving the knob a
+        // square on either side.
+        let track_height = label_dimensions.height;
+        self.track =
+            Dimensions::new(2.0 * track_height, track_height).as_rect();
+
+        self.bounds = Dimensions::new(
+            self.track.width() + self.spacing + label_dimensions.width,
+            track_height,
+        )
+        .min(max_size)
+        .as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        internal_state.register_focusable(self.id, self.bounds);
+        self.track = self.track.set_top_left_position(position);
+        Widget::<Message>::set_top_left_position(
+            &mut self.label,
+            internal_state,
+            position + vec2(self.track.width() + self.spacing, 0.0),
+        );
+    }
+}
+
+impl<Message> Into<Element<Message>> for Toggle<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Move the knob towards the side for the toggle's value. The knob covers
+/// the whole distance in the animation's duration. Knobs which haven't been
+/// drawn yet start on the correct side.
+fn slide_knob(
+    knob: Option<f32>,
+    on: bool,
+    frame_delta: Duration,
+    animation_duration: Duration,
+) -> f32 {
+    let target = if on { 1.0 } else { 0.0 };
+    let knob = match knob {
+        Some(knob) => knob,
+        None => return target,
+    };
+    let duration = animation_duration.as_secs_f32();
+    let step = if duration > 0.0 {
+        frame_delta.as_secs_f32() / duration
+    } else {
+        1.0
+    };
+    if knob < target {
+        (knob + step).min(target)
+    } else {
+        (knob - step).max(target)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_knob_slides_towards_the_value_over_the_animation() {
+        let duration = Duration::from_millis(100);
+        let frame = Duration::from_millis(25);
+
+        assert_eq!(slide_knob(None, true, frame, duration), 1.0);
+        assert_eq!(slide_knob(Some(0.0), true, frame, duration), 0.25);
+        assert_eq!(slide_knob(Some(0.1), false, frame, duration), 0.0);
+        assert_eq!(slide_knob(Some(0.0), true, frame, Duration::ZERO), 1.0);
+    }
+}
@@ -0,0 +1,267 @@
+use ::{anyhow::Result, std::time::Duration};
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        id_hash,
+        primitives::{Dimensions, Rect, Tile},
+        widgets::{Element, Label, OverlayEvent, Widget},
+        Font, Id, Input, InternalState,
+    },
+    vec2, Vec2, Vec4,
+};
+
+/// A Tooltip's state is stored in the UI InternalState so it keeps running
+/// between views. The hover timer is [`InternalState::hover_duration`], so it
+/// runs on the UI clock.
+///
+/// Tooltips with the same text share state. The state remembers which
+/// widget's bounds are hovered, so widgets sharing a tooltip don't reset each
+/// other's timer.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct TooltipState {
+    /// The bounds of the hovered widget.
+    hovered: Option<Rect>,
+
+    /// True after the hovered widget is clicked. The tooltip stays hidden
+    /// until the mouse leaves the widget.
+    dismissed: bool,
+
+    /// The most recent mouse position, used to place the tooltip.
+    mouse_position: Vec2,
+}
+
+/// A Tooltip wraps another widget and shows a small floating label near the
+/// cursor after the widget has been hovered for a moment. Tooltips are drawn
+/// in the overlay so they're always on top of the rest of the UI.
+pub struct Tooltip<Message, W> {
+    /// The ID used to look up the hover timer.
+    id: Id,
+
+    /// The widget the tooltip describes.
+    child: W,
+
+    /// The tooltip's text.
+    label: Label,
+
+    /// The space occupied by the child on screen.
+    bounds: Rect,
+
+    /// How long the child must be hovered before the tooltip appears.
+    delay: Duration,
+
+    /// The space between the text and the edge of the tooltip. None uses
+    /// the theme's padding.
+    padding: Option<f32>,
+
+    /// The font's line height, which the theme's padding is scaled by.
+    line_height: f32,
+
+    /// The tooltip's offset from the cursor.
+    offset: Vec2,
+
+    /// The color behind the tooltip's text.
+    background_color: Option<Vec4>,
+
+    /// The color of the tooltip's outline.
+    border_color: Option<Vec4>,
+
+    _phantom_data: std::marker::PhantomData<Message>,
+}
+
+impl<Message, W> Tooltip<Message, W> {
+    /// Create a new tooltip for the given widget.
+    pub fn new<T>(child: W, font: &Font, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let line_height = font.line_height();
+        Self {
+            id: Id::new(id_hash(file!(), line!(), column!(), text.as_ref())),
+            child,
+            label: Label::new(font, text),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            delay: Duration::from_millis(500),
+            padding: None,
+            line_height,
+            offset: vec2(0.5 * line_height, line_height),
+            background_color: None,
+            border_color: None,
+            _phantom_data: Default::default(),
+        }
+    }
+
+    builder_field!(delay, Duration);
+    builder_field_some!(padding, f32);
+    builder_field!(offset, Vec2);
+    builder_field_some!(background_color, Vec4);
+    builder_field_some!(border_color, Vec4);
+
+    /// True when this tooltip's child is the one being hovered.
+    fn owns_hover(&self, state: &TooltipState) -> bool {
+        state.hovered == Some(self.bounds)
+    }
+}
+
+impl<Message, W> Widget<Message> for Tooltip<Message, W>
+where
+    W: Widget<Message>,
+{
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, WindowEvent};
+
+        let mut state = *internal_state.get_state::<TooltipState>(&self.id);
+        let inside = self.bounds.contains(input.mouse_position);
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                state.mouse_position = input.mouse_position;
+                if inside && !self.owns_hover(&state) {
+                    // another widget sharing the tooltip may have been
+                    // hovered, so the timer starts over
+                    internal_state.set_hovered(&self.id, false);
+                    state.hovered = Some(self.bounds);
+                    state.dismissed = false;
+                } else if !inside && self.owns_hover(&state) {
+                    state.hovered = None;
+                    state.dismissed = false;
+                }
+                if inside {
+                    // widgets outside the tooltip don't report, so one which
+                    // shares the tooltip and is hovered keeps its timer
+                    internal_state.set_hovered(&self.id, true);
+                }
+            }
+            WindowEvent::MouseButton(_, Action::Press, _) if inside => {
+                state.dismissed = true;
+            }
+            _ => (),
+        }
+        *internal_state.get_state_mut::<TooltipState>(&self.id) = state;
+
+        self.child.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let dimensions = self.child.dimensions(internal_state, max_size);
+        self.bounds = dimensions.as_rect();
+        dimensions
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.child.set_top_left_position(internal_state, position);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)?;
+
+        let state = *internal_state.get_state::<TooltipState>(&self.id);
+        let visible = self.owns_hover(&state)
+            && !state.dismissed
+            && matches!(
+                internal_state.hover_duration(&self.id),
+                Some(duration) if duration >= self.delay
+            );
+        if !visible {
+            return Ok(());
+        }
+
+        let theme = *internal_state.theme();
+        let padding = self.padding.unwrap_or(theme.padding * self.line_height);
+        let mut label = self.label.clone();
+        let text_position =
+            state.mouse_position + self.offset + vec2(padding, padding);
+        let text_size = Widget::<Message>::dimensions(
+            &mut label,
+            internal_state,
+            &Dimensions::new(f32::MAX, f32::MAX),
+        );
+        Widget::<Message>::set_top_left_position(
+            &mut label,
+            internal_state,
+            text_position,
+        );
+        let background = Rect::new(
+            text_position.y - padding,
+            text_position.x - padding,
+            text_position.y + text_size.height + padding,
+            text_position.x + text_size.width + padding,
+        );
+
+        Tile {
+            model: background,
+            color: self.background_color.unwrap_or(theme.widget_color),
+            ..Default::default()
+        }
+        .fill(frame)?;
+        Tile {
+            model: background,
+            color: self.border_color.unwrap_or(theme.detail_color),
+            ..Default::default()
+        }
+        .outline(frame)?;
+        Widget::<Message>::draw_frame(&label, internal_state, frame)
+    }
+}
+
+impl<Message, W> Into<Element<Message>> for Tooltip<Message, W>
+where
+    Message: 'static,
+    W: 'static + Widget<Message>,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Define an extra associated method which wraps any widget with a tooltip.
+pub trait WithTooltip<Message, W: Widget<Message>> {
+    fn tooltip<T>(self, font: &Font, text: T) -> Tooltip<Message, W>
+    where
+        T: AsRef<str>;
+}
+
+impl<Message, W: Widget<Message>> WithTooltip<Message, W> for W {
+    fn tooltip<T>(self, font: &Font, text: T) -> Tooltip<Message, W>
+    where
+        T: AsRef<str>,
+    {
+        Tooltip::new(self, font, text)
+    }
+}
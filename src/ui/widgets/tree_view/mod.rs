@@ -0,0 +1,401 @@
+//! A tree view shows a hierarchy of nodes which can be expanded and collapsed.
+//!
+//! The application provides the nodes each time the view is built. Whether a
+//! node is expanded is stored in the UI InternalState using the node's Id, so
+//! nodes stay open or closed even as the tree is rebuilt. Selection belongs to
+//! the application: the view emits a message when a node is clicked and draws
+//! whichever node the application reports as selected.
+
+mod tree_node;
+
+use ::anyhow::Result;
+
+use crate::{
+    builder_field, builder_field_some,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Line, Rect, Tile},
+        widgets::{Element, Label, Widget},
+        Font, Id, Input, InternalState, Theme,
+    },
+    vec2, Vec2, Vec4,
+};
+
+pub use self::tree_node::TreeNode;
+
+/// A node's expansion state is stored in the UI InternalState using the
+/// node's Id.
+#[derive(Debug, Copy, Clone, Default)]
+struct TreeNodeState {
+    /// Whether the user has expanded the node. None until the node is first
+    /// toggled, in which case the node's default is used.
+    expanded: Option<bool>,
+}
+
+/// The state for the view itself, stored using the view's Id.
+#[derive(Debug, Copy, Clone, Default)]
+struct TreeViewState {
+    /// The Id of the node under the cursor.
+    hovered: Option<Id>,
+}
+
+/// A single node which is visible in the current layout.
+struct VisibleRow {
+    /// The node's Id.
+    id: Id,
+
+    /// How many ancestors the node has.
+    depth: usize,
+
+    /// True when the node has children and can be expanded.
+    has_children: bool,
+
+    /// True when the node's children are visible.
+    expanded: bool,
+
+    /// The node's text.
+    label: Label,
+
+    /// The height of the node's text, used to center it in the row.
+    label_height: f32,
+
+    /// The space occupied by the row on screen. Rows span the full width of
+    /// the view.
+    bounds: Rect,
+}
+
+/// A TreeView shows nested nodes as indented rows. Nodes with children have
+/// an arrow which expands or collapses them.
+///
+/// # Controls
+///
+/// - Click a node's arrow to expand or collapse it.
+/// - Click anywhere else on a node's row to select it.
+pub struct TreeView<Message> {
+    /// The ID used to look up the view's hover state.
+    id: Id,
+
+    /// The top-level nodes.
+    nodes: Vec<TreeNode>,
+
+    /// Every node which is visible, in the order they're drawn.
+    rows: Vec<VisibleRow>,
+
+    /// The space occupied by the view on screen.
+    bounds: Rect,
+
+    /// The height of each row.
+    row_height: f32,
+
+    /// The horizontal space added for each level of nesting.
+    indent: f32,
+
+    /// The node drawn as selected.
+    selected: Option<Id>,
+
+    /// The color behind the selected node.
+    selected_color: Option<Vec4>,
+
+    /// The color behind the hovered node.
+    hover_color: Option<Vec4>,
+
+    /// The color of the expand and collapse arrows.
+    arrow_color: Option<Vec4>,
+
+    /// The function used to build a message when a node is selected.
+    on_select: Option<Box<dyn Fn(Id) -> Message>>,
+}
+
+impl<Message> TreeView<Message> {
+    /// Create a new tree view without any nodes.
+    pub fn new(id: Id, font: &Font) -> Self {
+        let line_height = font.line_height();
+        Self {
+            id,
+            nodes: vec![],
+            rows: vec![],
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            row_height: line_height,
+            indent: line_height,
+            selected: None,
+            selected_color: None,
+            hover_color: None,
+            arrow_color: None,
+            on_select: None,
+        }
+    }
+
+    builder_field!(indent, f32);
+    builder_field_some!(selected, Id);
+    builder_field_some!(selected_color, Vec4);
+    builder_field_some!(hover_color, Vec4);
+    builder_field_some!(arrow_color, Vec4);
+
+    /// Add a top-level node.
+    pub fn node(mut self, node: TreeNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Add each of the nodes at the top level.
+    pub fn nodes<I>(mut self, nodes: I) -> Self
+    where
+        I: IntoIterator<Item = TreeNode>,
+    {
+        self.nodes.extend(nodes);
+        self
+    }
+
+    /// Set the function used to build a message when the user selects a
+    /// node.
+    pub fn on_select<F>(self, on_select_fn: F) -> Self
+    where
+        F: 'static + Fn(Id) -> Message,
+    {
+        Self {
+            on_select: Some(Box::new(on_select_fn)),
+            ..self
+        }
+    }
+
+    /// The space occupied by a row's expand and collapse arrow.
+    fn arrow_area(&self, row: &VisibleRow) -> Rect {
+        let left = row.bounds.left() + row.depth as f32 * self.indent;
+        Rect::new(
+            row.bounds.top(),
+            left,
+            row.bounds.bottom(),
+            left + self.row_height,
+        )
+    }
+
+    /// The row under the given point.
+    fn row_at(&self, point: Vec2) -> Option<&VisibleRow> {
+        self.rows.iter().find(|row| row.bounds.contains(point))
+    }
+
+    fn draw_arrow(
+        &self,
+        frame: &mut Frame,
+        theme: &Theme,
+        row: &VisibleRow,
+    ) -> Result<()> {
+        let area = self.arrow_area(row);
+        let center = vec2(
+            0.5 * (area.left() + area.right()),
+            0.5 * (area.top() + area.bottom()),
+        );
+        let size = 0.2 * self.row_height;
+
+        // a chevron pointing right when collapsed and down when expanded
+        let (a, tip, b) = if row.expanded {
+            (
+                center + vec2(-size, -0.5 * size),
+                center + vec2(0.0, 0.5 * size),
+                center + vec2(size, -0.5 * size),
+            )
+        } else {
+            (
+                center + vec2(-0.5 * size, -size),
+                center + vec2(0.5 * size, 0.0),
+                center + vec2(-0.5 * size, size),
+            )
+        };
+        let line = Line {
+            color: self.arrow_color.unwrap_or(theme.detail_color),
+            width: 1.5,
+            ..Default::default()
+        };
+        Line {
+            start: a,
+            end: tip,
+            ..line
+        }
+        .draw(frame)?;
+        Line {
+            start: tip,
+            end: b,
+            ..line
+        }
+        .draw(frame)
+    }
+}
+
+/// True when the node's children should be visible.
+fn is_expanded(internal_state: &mut InternalState, node: &TreeNode) -> bool {
+    internal_state
+        .get_state::<TreeNodeState>(&node.id)
+        .expanded
+        .unwrap_or(node.expanded_by_default)
+}
+
+/// Toggle whether the node's children are visible.
+fn toggle(internal_state: &mut InternalState, row: &VisibleRow) {
+    internal_state
+        .get_state_mut::<TreeNodeState>(&row.id)
+        .expanded = Some(!row.expanded);
+}
+
+/// Append a row for each of the nodes, and each of their visible
+/// descendants, in depth-first order.
+fn collect_visible_rows(
+    internal_state: &mut InternalState,
+    nodes: &[TreeNode],
+    depth: usize,
+    rows: &mut Vec<VisibleRow>,
+) {
+    for node in nodes {
+        let has_children = !node.children.is_empty();
+        let expanded = has_children && is_expanded(internal_state, node);
+        rows.push(VisibleRow {
+            id: node.id,
+            depth,
+            has_children,
+            expanded,
+            label: node.label.clone(),
+            label_height: 0.0,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        });
+        if expanded {
+            collect_visible_rows(
+                internal_state,
+                &node.children,
+                depth + 1,
+                rows,
+            );
+        }
+    }
+}
+
+impl<Message> Widget<Message> for TreeView<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        match *event {
+            WindowEvent::CursorPos(..) => {
+                let hovered =
+                    self.row_at(input.mouse_position).map(|row| row.id);
+                internal_state
+                    .get_state_mut::<TreeViewState>(&self.id)
+                    .hovered = hovered;
+                Ok(None)
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) => {
+                let row = match self.row_at(input.mouse_position) {
+                    Some(row) => row,
+                    None => return Ok(None),
+                };
+                let on_arrow =
+                    self.arrow_area(row).contains(input.mouse_position);
+                if row.has_children && on_arrow {
+                    toggle(internal_state, row);
+                    return Ok(None);
+                }
+                Ok(self.on_select.as_ref().map(|on_select| on_select(row.id)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        let theme = *internal_state.theme();
+        let state = *internal_state.get_state::<TreeViewState>(&self.id);
+        for row in &self.rows {
+            let background = if self.selected == Some(row.id) {
+                Some(self.selected_color.unwrap_or(theme.selection_color))
+            } else if state.hovered == Some(row.id) {
+                Some(self.hover_color.unwrap_or(theme.highlight_color))
+            } else {
+                None
+            };
+            if let Some(color) = background {
+                Tile {
+                    model: row.bounds,
+                    color,
+                    ..Default::default()
+                }
+                .fill(frame)?;
+            }
+            if row.has_children {
+                self.draw_arrow(frame, &theme, row)?;
+            }
+            Widget::<Message>::draw_frame(&row.label, internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let mut rows = vec![];
+        collect_visible_rows(internal_state, &self.nodes, 0, &mut rows);
+
+        let mut width: f32 = 0.0;
+        for row in &mut rows {
+            let label_size = Widget::<Message>::dimensions(
+                &mut row.label,
+                internal_state,
+                max_size,
+            );
+            let text_left = row.depth as f32 * self.indent + self.row_height;
+            width = width.max(text_left + label_size.width);
+            row.label_height = label_size.height;
+        }
+        self.rows = rows;
+
+        let height = self.rows.len() as f32 * self.row_height;
+        self.bounds = Dimensions::new(width, height).min(max_size).as_rect();
+        self.bounds.dimensions()
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        for (index, row) in self.rows.iter_mut().enumerate() {
+            let top = position.y + index as f32 * self.row_height;
+            row.bounds = Rect::new(
+                top,
+                position.x,
+                top + self.row_height,
+                position.x + self.bounds.width(),
+            );
+
+            let text_position = vec2(
+                position.x + row.depth as f32 * self.indent + self.row_height,
+                (top + 0.5 * (self.row_height - row.label_height)).round(),
+            );
+            Widget::<Message>::set_top_left_position(
+                &mut row.label,
+                internal_state,
+                text_position,
+            );
+        }
+    }
+}
+
+impl<Message> Into<Element<Message>> for TreeView<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    builder_field,
+    ui::{widgets::Label, Font, Id},
+};
+
+/// A single node in a [`super::TreeView`]. Nodes can have any number of
+/// children.
+pub struct TreeNode {
+    /// The ID uniquely identifies this node. It's used to look up whether the
+    /// node is expanded and it's passed to the TreeView's select function.
+    pub(super) id: Id,
+
+    /// The node's text.
+    pub(super) label: Label,
+
+    /// The node's children, drawn below the node when it's expanded.
+    pub(super) children: Vec<TreeNode>,
+
+    /// Whether the node is expanded before the user has toggled it.
+    pub(super) expanded_by_default: bool,
+}
+
+impl TreeNode {
+    /// Create a new node without any children.
+    pub fn new<T>(id: Id, font: &Font, text: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self {
+            id,
+            label: Label::new(font, text),
+            children: vec![],
+            expanded_by_default: false,
+        }
+    }
+
+    builder_field!(expanded_by_default, bool);
+
+    /// Add a child node.
+    pub fn child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add each of the nodes as a child.
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = TreeNode>,
+    {
+        self.children.extend(children);
+        self
+    }
+}
@@ -2,16 +2,18 @@ use ::anyhow::Result;
 
 use crate::{
     gen_id,
+    immediate_mode_graphics::triangles::Frame,
     ui::{
         id_hash,
-        primitives::{Justify, SpaceBetween},
+        primitives::{Dimensions, Justify, Rect, SpaceBetween},
         widgets::{
             Button, Col, ComposedMessage, Composite, CompositeWidget,
-            Container, Element, Label, Row, WithContainer,
+            Container, Element, Label, OverlayEvent, Row, Widget,
+            WithContainer,
         },
-        Font, Id,
+        Font, Id, Input, InternalState,
     },
-    vec4,
+    vec2, vec4, Vec2,
 };
 
 /// This type represents the ['Window']'s current visibity state.
@@ -36,10 +38,27 @@ pub enum WindowEvent {
     HideWindow,
 }
 
+/// A Window's position is stored in the UI InternalState so it persists
+/// between views.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct WindowPosition {
+    /// How far the window has been dragged from its place in the layout.
+    pub offset: Vec2,
+
+    /// The cursor's position relative to the offset while the title bar is
+    /// being dragged.
+    drag_anchor: Option<Vec2>,
+}
+
 /// A Window is a collapsable panel with a title button which toggles the
 /// visibility of the contents.
+///
+/// The title bar can be dragged to move the window. Windows are still
+/// measured as part of the normal layout, so put them in a
+/// [`crate::ui::widgets::Layer`] to let them float over the rest of the UI.
 pub struct Window<Message> {
     id: Id,
+    position_id: Id,
     font: Font,
     title: String,
     contents: Option<Element<Message>>,
@@ -53,6 +72,7 @@ where
         let owned_title = title.into();
         Self {
             id: gen_id!(&owned_title),
+            position_id: gen_id!(&format!("{} position", owned_title)),
             font,
             title: owned_title,
             contents: None,
@@ -85,6 +105,18 @@ where
             .hover_color(vec4(1.0, 1.0, 1.0, 0.2))
             .pressed_color(vec4(1.0, 1.0, 1.0, 0.5))
     }
+
+    /// Make the top bar drag the window.
+    fn title_bar(
+        &self,
+        top_bar: Row<ComposedMessage<WindowEvent, Message>>,
+    ) -> Element<ComposedMessage<WindowEvent, Message>> {
+        Element::new(TitleBar {
+            position_id: self.position_id,
+            child: top_bar.into(),
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+        })
+    }
 }
 
 impl<Message> CompositeWidget<WindowEvent, Message> for Window<Message>
@@ -122,7 +154,9 @@ where
                     .space_between(SpaceBetween::EvenSpaceBetween);
 
                 // render just the top bar
-                Col::new().child(top_bar, Justify::End).into()
+                Col::new()
+                    .child(self.title_bar(top_bar), Justify::End)
+                    .into()
             }
             WindowState::Visible => {
                 let top_bar = Row::new()
@@ -137,12 +171,11 @@ where
                     )
                     .space_between(SpaceBetween::EvenSpaceBetween);
 
-                let contents: Element<Message> =
-                    self.contents.take().unwrap().into();
+                let contents = self.contents.take().unwrap();
 
                 // render the visible part of the window
                 Col::new()
-                    .child(top_bar, Justify::End)
+                    .child(self.title_bar(top_bar), Justify::End)
                     .child(contents, Justify::Center)
                     .into()
             }
@@ -181,7 +214,10 @@ where
     Message: 'static + std::fmt::Debug + Copy + Clone,
 {
     fn into(self) -> Element<Message> {
-        Element::new(Composite::new(self))
+        Element::new(Dragged {
+            position_id: self.position_id,
+            child: Element::new(Composite::new(self)),
+        })
     }
 }
 
@@ -194,3 +230,156 @@ where
         Element::new(self)
     }
 }
+
+/// The Window's title bar. Dragging it updates the window's position.
+struct TitleBar<Message> {
+    position_id: Id,
+    child: Element<Message>,
+    bounds: Rect,
+}
+
+impl<Message> Widget<Message> for TitleBar<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        use glfw::{Action, MouseButton, WindowEvent};
+
+        let state =
+            internal_state.get_state_mut::<WindowPosition>(&self.position_id);
+        match *event {
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Press,
+                _,
+            ) if self.bounds.contains(input.mouse_position) => {
+                state.drag_anchor = Some(input.mouse_position - state.offset);
+            }
+            WindowEvent::MouseButton(
+                MouseButton::Button1,
+                Action::Release,
+                _,
+            ) => {
+                state.drag_anchor = None;
+            }
+            WindowEvent::CursorPos(..) => {
+                if let Some(anchor) = state.drag_anchor {
+                    let offset = input.mouse_position - anchor;
+                    state.offset = vec2(offset.x.round(), offset.y.round());
+                }
+            }
+            _ => (),
+        }
+        self.child.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        let dimensions = self.child.dimensions(internal_state, max_size);
+        self.bounds = dimensions.as_rect();
+        dimensions
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        self.bounds = self.bounds.set_top_left_position(position);
+        self.child.set_top_left_position(internal_state, position);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)
+    }
+}
+
+/// Moves the whole Window by the offset its title bar has been dragged.
+struct Dragged<Message> {
+    position_id: Id,
+    child: Element<Message>,
+}
+
+impl<Message> Widget<Message> for Dragged<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        self.child.handle_event(internal_state, input, event)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_frame(internal_state, frame)
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        self.child.dimensions(internal_state, max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        let offset = internal_state
+            .get_state::<WindowPosition>(&self.position_id)
+            .offset;
+        self.child
+            .set_top_left_position(internal_state, position + offset);
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        self.child
+            .handle_overlay_event(internal_state, input, event)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        self.child.draw_overlay(internal_state, frame)
+    }
+}
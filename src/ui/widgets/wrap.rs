@@ -0,0 +1,245 @@
+use ::anyhow::Result;
+
+use crate::{
+    builder_field,
+    immediate_mode_graphics::triangles::Frame,
+    ui::{
+        primitives::{Dimensions, Justify},
+        widgets::{Element, OverlayEvent, Widget},
+        Input, InternalState,
+    },
+    vec2, Vec2,
+};
+
+/// A Wrap arranges widgets in a horizontal row which wraps onto a new line
+/// any time the next child would exceed the max width.
+///
+/// This is useful for collections of small widgets like tags, color swatches,
+/// and toolbars, which should use as few lines as the window allows.
+pub struct Wrap<Message> {
+    children: Vec<(Element<Message>, Justify)>,
+
+    /// The horizontal space between children on the same line.
+    spacing: f32,
+
+    /// The vertical space between lines.
+    line_spacing: f32,
+
+    /// Each child's top left corner, relative to the wrap's top left corner.
+    child_positions: Vec<Vec2>,
+}
+
+impl<Message> Default for Wrap<Message> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message> Wrap<Message> {
+    pub fn new() -> Self {
+        Self {
+            children: vec![],
+            spacing: 0.0,
+            line_spacing: 0.0,
+            child_positions: vec![],
+        }
+    }
+
+    builder_field!(spacing, f32);
+    builder_field!(line_spacing, f32);
+
+    /// Add a child element to the end of the wrap. The justification aligns
+    /// the child vertically within its line.
+    pub fn child<W>(mut self, child: W, justify: Justify) -> Self
+    where
+        W: Into<Element<Message>>,
+    {
+        self.children.push((child.into(), justify));
+        self
+    }
+}
+
+impl<Message> Widget<Message> for Wrap<Message> {
+    fn handle_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<Option<Message>> {
+        for (child, _) in &mut self.children {
+            if let Some(message) =
+                child.handle_event(internal_state, input, event)?
+            {
+                return Ok(Some(message));
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw_frame(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for (child, _) in &self.children {
+            child.draw_frame(internal_state, frame)?;
+        }
+        Ok(())
+    }
+
+    fn dimensions(
+        &mut self,
+        internal_state: &mut InternalState,
+        max_size: &Dimensions,
+    ) -> Dimensions {
+        if self.children.is_empty() {
+            return Dimensions::new(0.0, 0.0);
+        }
+
+        // Every child may use a full line, so each is measured against the
+        // whole max size.
+        let mut sizes = Vec::with_capacity(self.children.len());
+        for (child, justify) in &mut self.children {
+            sizes.push((child.dimensions(internal_state, max_size), *justify));
+        }
+
+        let (child_positions, dimensions) = wrap_children(
+            &sizes,
+            max_size.width,
+            self.spacing,
+            self.line_spacing,
+        );
+        self.child_positions = child_positions;
+        dimensions.min(max_size)
+    }
+
+    fn set_top_left_position(
+        &mut self,
+        internal_state: &mut InternalState,
+        position: Vec2,
+    ) {
+        for ((child, _), child_pos) in
+            self.children.iter_mut().zip(self.child_positions.iter())
+        {
+            child.set_top_left_position(internal_state, position + child_pos);
+        }
+    }
+
+    fn handle_overlay_event(
+        &mut self,
+        internal_state: &mut InternalState,
+        input: &Input,
+        event: &glfw::WindowEvent,
+    ) -> Result<OverlayEvent<Message>> {
+        // later children are drawn on top, so they see overlay events first
+        for (child, _) in self.children.iter_mut().rev() {
+            let result =
+                child.handle_overlay_event(internal_state, input, event)?;
+            if result.is_captured() {
+                return Ok(result);
+            }
+        }
+        Ok(OverlayEvent::Ignored)
+    }
+
+    fn draw_overlay(
+        &self,
+        internal_state: &mut InternalState,
+        frame: &mut Frame,
+    ) -> Result<()> {
+        for (child, _) in &self.children {
+            child.draw_overlay(internal_state, frame)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Message> Into<Element<Message>> for Wrap<Message>
+where
+    Message: 'static,
+{
+    fn into(self) -> Element<Message> {
+        Element::new(self)
+    }
+}
+
+/// Place children on lines which are at most max_width wide. A child which
+/// is wider than a line gets a line to itself.
+///
+/// # Returns
+///
+/// Each child's top left corner, and the size of the whole arrangement.
+fn wrap_children(
+    sizes: &[(Dimensions, Justify)],
+    max_width: f32,
+    spacing: f32,
+    line_spacing: f32,
+) -> (Vec<Vec2>, Dimensions) {
+    let mut positions = Vec::with_capacity(sizes.len());
+    let mut width: f32 = 0.0;
+    let mut line_top = 0.0;
+    let mut line_start = 0;
+    let mut cursor = 0.0;
+
+    // children are justified once their line's height is known
+    let justify_line = |positions: &mut Vec<Vec2>, start: usize, top: f32| {
+        let line = &sizes[start..positions.len()];
+        let height =
+            line.iter().map(|(size, _)| size.height).fold(0.0, f32::max);
+        for (position, (size, justify)) in
+            positions[start..].iter_mut().zip(line)
+        {
+            position.y = top
+                + match justify {
+                    Justify::Begin => 0.0,
+                    Justify::Center => 0.5 * (height - size.height),
+                    Justify::End => height - size.height,
+                };
+        }
+        height
+    };
+
+    for (size, _) in sizes {
+        if positions.len() > line_start && cursor + size.width > max_width {
+            line_top += justify_line(&mut positions, line_start, line_top)
+                + line_spacing;
+            line_start = positions.len();
+            cursor = 0.0;
+        }
+        positions.push(vec2(cursor, line_top));
+        width = width.max(cursor + size.width);
+        cursor += size.width + spacing;
+    }
+    let height = line_top + justify_line(&mut positions, line_start, line_top);
+
+    (positions, Dimensions::new(width, height))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_children_wrap_onto_new_lines() {
+        let sizes = [
+            (Dimensions::new(40.0, 10.0), Justify::Begin),
+            (Dimensions::new(40.0, 20.0), Justify::Begin),
+            (Dimensions::new(40.0, 10.0), Justify::End),
+            (Dimensions::new(200.0, 10.0), Justify::Begin),
+        ];
+        let (positions, dimensions) = wrap_children(&sizes, 100.0, 5.0, 2.0);
+
+        assert_eq!(
+            positions,
+            vec![
+                vec2(0.0, 0.0),
+                vec2(45.0, 0.0),
+                // the third child doesn't fit, the line is 20 tall
+                vec2(0.0, 22.0),
+                // a child which is too wide still gets its own line
+                vec2(0.0, 34.0),
+            ]
+        );
+        assert_eq!(dimensions, Dimensions::new(200.0, 44.0));
+    }
+}
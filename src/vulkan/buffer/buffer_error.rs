@@ -1,6 +1,6 @@
 use ::{ash::vk, thiserror::Error};
 
-use crate::vulkan::errors::AllocatorError;
+use crate::vulkan::errors::{AllocatorError, VulkanDebugError};
 
 #[derive(Debug, Error)]
 pub enum BufferError {
@@ -28,4 +28,7 @@ pub enum BufferError {
 
     #[error("Unable to bind device memory to buffer")]
     UnableToBindDeviceMemory(#[source] vk::Result),
+
+    #[error(transparent)]
+    UnableToSetDebugName(#[from] VulkanDebugError),
 }
@@ -0,0 +1,205 @@
+use crate::{Mat4, Vec2, Vec3, Vec4};
+
+/// Types which can be copied directly into a GPU buffer and read by shaders
+/// which use the std430 layout.
+///
+/// Structs should implement this trait with the [`crate::gpu_data`] macro,
+/// which makes the struct `#[repr(C)]` and checks its layout at compile time.
+///
+/// Note: arrays use the alignment of their elements, so `[f32; 4]` matches a
+/// `float[4]` in the shader. Use nalgebra vectors for fields which are
+/// vectors in the shader so they're checked with vector alignment.
+///
+/// # Safety
+///
+/// Implementors must have a stable layout where every field is at the offset
+/// std430 expects, and must not contain any padding the shader doesn't know
+/// about.
+pub unsafe trait GpuData: Copy + 'static {
+    /// The type's base alignment, in bytes, under the std430 layout rules.
+    const STD430_ALIGNMENT: usize;
+}
+
+unsafe impl GpuData for f32 {
+    const STD430_ALIGNMENT: usize = 4;
+}
+
+unsafe impl GpuData for i32 {
+    const STD430_ALIGNMENT: usize = 4;
+}
+
+unsafe impl GpuData for u32 {
+    const STD430_ALIGNMENT: usize = 4;
+}
+
+/// Bytes are only readable by shaders which enable 8-bit storage, but they're
+/// also used for staging buffers which shaders never read.
+unsafe impl GpuData for u8 {
+    const STD430_ALIGNMENT: usize = 1;
+}
+
+unsafe impl GpuData for Vec2 {
+    const STD430_ALIGNMENT: usize = 8;
+}
+
+/// A vec3 is aligned like a vec4 but only occupies 12 bytes, so a scalar
+/// can follow it without padding.
+unsafe impl GpuData for Vec3 {
+    const STD430_ALIGNMENT: usize = 16;
+}
+
+unsafe impl GpuData for Vec4 {
+    const STD430_ALIGNMENT: usize = 16;
+}
+
+unsafe impl GpuData for Mat4 {
+    const STD430_ALIGNMENT: usize = 16;
+}
+
+unsafe impl<T: GpuData, const N: usize> GpuData for [T; N] {
+    const STD430_ALIGNMENT: usize = T::STD430_ALIGNMENT;
+}
+
+/// The std430 alignment of a struct is the largest alignment of its members.
+/// Used by the [`crate::gpu_data`] macro.
+#[doc(hidden)]
+pub const fn std430_struct_alignment(member_alignments: &[usize]) -> usize {
+    let mut alignment = 1;
+    let mut i = 0;
+    while i < member_alignments.len() {
+        if member_alignments[i] > alignment {
+            alignment = member_alignments[i];
+        }
+        i += 1;
+    }
+    alignment
+}
+
+/// Define a struct which can be stored in a [`crate::vulkan::GpuVec`].
+///
+/// The struct is made `#[repr(C)]` and implements
+/// [`crate::vulkan::GpuData`]. Compilation fails if any field isn't at the
+/// offset std430 expects, if the compiler inserts padding between fields,
+/// or if the struct's size isn't a multiple of its alignment. Add explicit
+/// padding fields to fix the layout.
+///
+/// # Example
+///
+///     use ccthw::{gpu_data, Vec4};
+///
+///     gpu_data! {
+///         #[derive(Debug, Copy, Clone)]
+///         pub struct Particle {
+///             pub position: Vec4,
+///             pub lifetime: f32,
+///             pub _pad: [f32; 3],
+///         }
+///     }
+///
+#[macro_export]
+macro_rules! gpu_data {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $(#[$field_attr:meta])*
+                $field_vis:vis $field:ident : $field_type:ty
+            ),* $(,)?
+        }
+    ) => {
+        #[repr(C)]
+        $(#[$attr])*
+        $vis struct $name {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $field_type,
+            )*
+        }
+
+        unsafe impl $crate::vulkan::GpuData for $name {
+            const STD430_ALIGNMENT: usize =
+                $crate::vulkan::std430_struct_alignment(&[$(
+                    <$field_type as $crate::vulkan::GpuData>::STD430_ALIGNMENT
+                ),*]);
+        }
+
+        const _: () = {
+            $(
+                assert!(
+                    ::core::mem::offset_of!($name, $field)
+                        % <$field_type as $crate::vulkan::GpuData>
+                            ::STD430_ALIGNMENT
+                        == 0,
+                    concat!(
+                        stringify!($name), ".", stringify!($field),
+                        " is not at a std430 aligned offset"
+                    )
+                );
+            )*
+            assert!(
+                ::core::mem::size_of::<$name>()
+                    == 0 $(+ ::core::mem::size_of::<$field_type>())*,
+                concat!(
+                    stringify!($name),
+                    " has implicit padding, add explicit padding fields"
+                )
+            );
+            assert!(
+                ::core::mem::size_of::<$name>()
+                    % <$name as $crate::vulkan::GpuData>::STD430_ALIGNMENT
+                    == 0,
+                concat!(
+                    stringify!($name),
+                    "'s size is not a multiple of its std430 alignment"
+                )
+            );
+        };
+    };
+}
+
+/// Fail compilation unless the type's stride in a GPU buffer is exactly the
+/// given number of bytes. Use this to keep a struct in sync with the stride
+/// the shader expects.
+///
+/// # Example
+///
+///     use ccthw::{assert_gpu_stride, immediate_mode_graphics::Vertex};
+///
+///     assert_gpu_stride!(Vertex, 48);
+///
+#[macro_export]
+macro_rules! assert_gpu_stride {
+    ($type:ty, $stride:expr) => {
+        const _: () = assert!(
+            ::core::mem::size_of::<$type>() == $stride,
+            concat!(
+                stringify!($type),
+                "'s stride doesn't match the shader's stride of ",
+                stringify!($stride),
+                " bytes"
+            )
+        );
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    crate::gpu_data! {
+        #[derive(Debug, Copy, Clone)]
+        struct Particle {
+            position: Vec3,
+            lifetime: f32,
+            velocity: Vec2,
+            _pad: [f32; 2],
+        }
+    }
+
+    #[test]
+    fn test_struct_alignment_is_the_largest_member_alignment() {
+        assert_eq!(Particle::STD430_ALIGNMENT, 16);
+        assert_eq!(<[Particle; 2]>::STD430_ALIGNMENT, 16);
+        assert_eq!(std430_struct_alignment(&[]), 1);
+    }
+}
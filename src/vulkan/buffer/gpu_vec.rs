@@ -1,13 +1,18 @@
-use ::{ash::vk, std::sync::Arc};
+use ::{
+    ash::vk,
+    std::{cell::RefCell, sync::Arc},
+};
 
 use crate::vulkan::{
-    buffer::{Buffer, BufferError},
+    buffer::{Buffer, BufferError, GpuData},
     errors::VulkanDebugError,
     MemoryAllocator, RenderDevice, VulkanDebug,
 };
 
 /// A resizable GPU Buffer which holds a contiguous slice of T's.
-pub struct GpuVec<T: Copy> {
+///
+/// T must implement [`GpuData`] so its layout matches what shaders expect.
+pub struct GpuVec<T: GpuData> {
     /// The device buffer which holds the actual data.
     pub buffer: Buffer,
 
@@ -22,10 +27,15 @@ pub struct GpuVec<T: Copy> {
     /// reallocated.
     usage_flags: vk::BufferUsageFlags,
 
+    /// The debug name given to the underlying buffer. It's reapplied each
+    /// time the buffer is reallocated so the name survives growth.
+    debug_name: RefCell<String>,
+
     _phantom_data: std::marker::PhantomData<T>,
 }
 
-impl<T: Copy> GpuVec<T> {
+impl<T: GpuData> GpuVec<T> {
+    /// Create a new GpuVec with a debug name derived from the element type.
     pub fn new(
         vk_dev: Arc<RenderDevice>,
         vk_alloc: Arc<dyn MemoryAllocator>,
@@ -41,15 +51,34 @@ impl<T: Copy> GpuVec<T> {
             Self::element_count_to_bytes(initial_capacity),
         )?;
         buffer.map()?;
+        let type_name = std::any::type_name::<T>();
+        let debug_name =
+            format!("GpuVec<{}>", type_name.rsplit("::").next().unwrap());
+        buffer.set_debug_name(debug_name.clone())?;
         Ok(Self {
             buffer,
             capacity: initial_capacity,
             length: 0,
             usage_flags: buffer_usage_flags,
+            debug_name: RefCell::new(debug_name),
             _phantom_data: std::marker::PhantomData::default(),
         })
     }
 
+    /// Create a new GpuVec with the given debug name.
+    pub fn new_named(
+        vk_dev: Arc<RenderDevice>,
+        vk_alloc: Arc<dyn MemoryAllocator>,
+        buffer_usage_flags: vk::BufferUsageFlags,
+        initial_capacity: u32,
+        debug_name: impl Into<String>,
+    ) -> Result<Self, BufferError> {
+        let vec =
+            Self::new(vk_dev, vk_alloc, buffer_usage_flags, initial_capacity)?;
+        vec.set_debug_name(debug_name)?;
+        Ok(vec)
+    }
+
     /// Append a value to the data in the buffer. Grows the buffer
     /// automatically if more memory is needed.
     ///
@@ -65,7 +94,7 @@ impl<T: Copy> GpuVec<T> {
         }
         let data = self.buffer.data_mut()?;
         data[self.len()] = value;
-        self.length = self.length + 1;
+        self.length += 1;
         Ok(replaced)
     }
 
@@ -79,6 +108,11 @@ impl<T: Copy> GpuVec<T> {
         self.length as usize
     }
 
+    /// True when the buffer has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
     /// The number of bytes in use by the buffer.
     /// e.g. `self.len() * size_of<T>()`
     pub fn len_bytes(&self) -> u64 {
@@ -86,16 +120,19 @@ impl<T: Copy> GpuVec<T> {
     }
 }
 
-impl<T: Copy> VulkanDebug for GpuVec<T> {
+impl<T: GpuData> VulkanDebug for GpuVec<T> {
     fn set_debug_name(
         &self,
         debug_name: impl Into<String>,
     ) -> Result<(), VulkanDebugError> {
-        self.buffer.set_debug_name(debug_name)
+        let debug_name = debug_name.into();
+        self.buffer.set_debug_name(debug_name.clone())?;
+        *self.debug_name.borrow_mut() = debug_name;
+        Ok(())
     }
 }
 
-impl<T: Copy> GpuVec<T> {
+impl<T: GpuData> GpuVec<T> {
     /// Return the number of bytes required to hold a given number of elements.
     fn element_count_to_bytes(count: u32) -> u64 {
         count as u64 * std::mem::size_of::<T>() as u64
@@ -113,6 +150,7 @@ impl<T: Copy> GpuVec<T> {
             Self::element_count_to_bytes(desired_capacity),
         )?;
         buffer.map()?;
+        buffer.set_debug_name(self.debug_name.borrow().clone())?;
         self.capacity = desired_capacity;
 
         // copy the contents of the existing buffer to the new buffer
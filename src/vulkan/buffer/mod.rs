@@ -1,5 +1,11 @@
 mod buffer;
 mod buffer_error;
+mod gpu_data;
 mod gpu_vec;
 
-pub use self::{buffer::Buffer, buffer_error::BufferError, gpu_vec::GpuVec};
+pub use self::{
+    buffer::Buffer,
+    buffer_error::BufferError,
+    gpu_data::{std430_struct_alignment, GpuData},
+    gpu_vec::GpuVec,
+};
@@ -49,6 +49,17 @@ impl CommandPool {
         )
     }
 
+    /// Create a new transient command pool for submitting compute commands.
+    pub fn new_transient_compute_pool(
+        vk_dev: Arc<RenderDevice>,
+    ) -> Result<Self, CommandBufferError> {
+        Self::new(
+            vk_dev.clone(),
+            &vk_dev.compute_queue,
+            vk::CommandPoolCreateFlags::TRANSIENT,
+        )
+    }
+
     /// Allocate raw vulkan command buffers.
     ///
     /// # Unsafe
@@ -24,9 +24,25 @@ impl DescriptorPool {
         vk_dev: Arc<RenderDevice>,
         descriptor_count: u32,
         sizes: &[vk::DescriptorPoolSize],
+    ) -> Result<Self, DescriptorSetError> {
+        Self::new_with_flags(
+            vk_dev,
+            vk::DescriptorPoolCreateFlags::empty(),
+            descriptor_count,
+            sizes,
+        )
+    }
+
+    /// Create a new descriptor pool with the given creation flags, e.g.
+    /// `UPDATE_AFTER_BIND` for pools which allocate update-after-bind sets.
+    pub fn new_with_flags(
+        vk_dev: Arc<RenderDevice>,
+        flags: vk::DescriptorPoolCreateFlags,
+        descriptor_count: u32,
+        sizes: &[vk::DescriptorPoolSize],
     ) -> Result<Self, DescriptorSetError> {
         let create_info = vk::DescriptorPoolCreateInfo {
-            flags: vk::DescriptorPoolCreateFlags::empty(),
+            flags,
             max_sets: descriptor_count,
             pool_size_count: sizes.len() as u32,
             p_pool_sizes: sizes.as_ptr(),
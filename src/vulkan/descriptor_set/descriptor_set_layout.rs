@@ -35,6 +35,24 @@ impl DescriptorSetLayout {
             vk::DescriptorSetLayoutBinding,
             vk::DescriptorBindingFlags,
         )],
+    ) -> Result<Self, DescriptorSetError> {
+        Self::new_with_create_flags(
+            vk_dev,
+            vk::DescriptorSetLayoutCreateFlags::empty(),
+            bindings_and_flags,
+        )
+    }
+
+    /// Create a layout with the given creation flags, e.g.
+    /// `UPDATE_AFTER_BIND_POOL` for layouts with bindings which are updated
+    /// after the set is bound.
+    pub fn new_with_create_flags(
+        vk_dev: Arc<RenderDevice>,
+        create_flags: vk::DescriptorSetLayoutCreateFlags,
+        bindings_and_flags: &[(
+            vk::DescriptorSetLayoutBinding,
+            vk::DescriptorBindingFlags,
+        )],
     ) -> Result<Self, DescriptorSetError> {
         let flags: Vec<vk::DescriptorBindingFlags> = bindings_and_flags
             .iter()
@@ -54,7 +72,7 @@ impl DescriptorSetLayout {
             p_next: &binding_flags_create_info
                 as *const vk::DescriptorSetLayoutBindingFlagsCreateInfo
                 as *const c_void,
-            flags: vk::DescriptorSetLayoutCreateFlags::empty(),
+            flags: create_flags,
             p_bindings: bindings.as_ptr(),
             binding_count: bindings.len() as u32,
             ..Default::default()
@@ -44,6 +44,16 @@ impl ImageView {
         image: Arc<Image>,
         format: vk::Format,
         aspect_mask: vk::ImageAspectFlags,
+    ) -> Result<Self, ImageError> {
+        Self::new_2d_mip_level(image, format, aspect_mask, 0)
+    }
+
+    /// Create a new 2d image view which targets a single mipmap level.
+    pub fn new_2d_mip_level(
+        image: Arc<Image>,
+        format: vk::Format,
+        aspect_mask: vk::ImageAspectFlags,
+        mip_level: u32,
     ) -> Result<Self, ImageError> {
         let create_info = vk::ImageViewCreateInfo {
             flags: vk::ImageViewCreateFlags::empty(),
@@ -52,7 +62,7 @@ impl ImageView {
             format,
             subresource_range: vk::ImageSubresourceRange {
                 aspect_mask,
-                base_mip_level: 0,
+                base_mip_level: mip_level,
                 level_count: 1,
                 base_array_layer: 0,
                 layer_count: 1,
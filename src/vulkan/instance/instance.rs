@@ -78,6 +78,9 @@ impl Instance {
                 shader_sampled_image_array_non_uniform_indexing: vk::TRUE,
                 runtime_descriptor_array: vk::TRUE,
                 descriptor_binding_variable_descriptor_count: vk::TRUE,
+                descriptor_binding_partially_bound: vk::TRUE,
+                descriptor_binding_sampled_image_update_after_bind: vk::TRUE,
+                descriptor_binding_update_unused_while_pending: vk::TRUE,
                 ..Default::default()
             };
         let physical_device_features = vk::PhysicalDeviceFeatures2 {
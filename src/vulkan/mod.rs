@@ -15,7 +15,7 @@ mod window_surface;
 pub mod sync;
 
 pub use self::{
-    buffer::{Buffer, GpuVec},
+    buffer::{std430_struct_alignment, Buffer, GpuData, GpuVec},
     command_buffer::{CommandBuffer, CommandPool, OneTimeSubmitCommandPool},
     descriptor_set::{DescriptorPool, DescriptorSet, DescriptorSetLayout},
     device_allocator::{
@@ -25,7 +25,10 @@ pub use self::{
     framebuffer::Framebuffer,
     image::{Image, ImageView, Sampler},
     instance::Instance,
-    pipeline::{Pipeline, PipelineLayout, ShaderModule},
+    pipeline::{
+        BlendMode, GraphicsPipelineBuilder, Pipeline, PipelineLayout,
+        ShaderModule,
+    },
     render_device::{GpuQueue, RenderDevice},
     render_pass::RenderPass,
     vulkan_debug::VulkanDebug,
@@ -0,0 +1,322 @@
+use std::sync::Arc;
+
+use ash::vk;
+
+use crate::{
+    builder_field, builder_field_some,
+    math::{CoordinateSystem, Culling},
+    vulkan::{
+        pipeline::PipelineError, Pipeline, PipelineLayout, RenderDevice,
+        ShaderModule,
+    },
+};
+
+/// How a pipeline's output is combined with the contents of the color
+/// attachment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Output replaces the attachment's contents.
+    Opaque,
+
+    /// Output is blended using its alpha channel. This is the default.
+    ///
+    /// Like every blended mode, the attachment's alpha accumulates coverage
+    /// the same way premultiplied colors do, so frames presented to a
+    /// transparent window are composited correctly.
+    Alpha,
+
+    /// Output is blended assuming the color has already been multiplied by
+    /// alpha.
+    PremultipliedAlpha,
+
+    /// Output is added to the attachment's contents. The attachment's alpha
+    /// is left as it was, added light doesn't cover anything.
+    Additive,
+}
+
+impl BlendMode {
+    fn attachment_state(&self) -> vk::PipelineColorBlendAttachmentState {
+        use vk::BlendFactor as Factor;
+
+        let (blend_enable, src_color, dst_color, src_alpha, dst_alpha) =
+            match *self {
+                BlendMode::Opaque => (
+                    vk::FALSE,
+                    Factor::ONE,
+                    Factor::ZERO,
+                    Factor::ONE,
+                    Factor::ZERO,
+                ),
+                BlendMode::Alpha => (
+                    vk::TRUE,
+                    Factor::SRC_ALPHA,
+                    Factor::ONE_MINUS_SRC_ALPHA,
+                    Factor::ONE,
+                    Factor::ONE_MINUS_SRC_ALPHA,
+                ),
+                BlendMode::PremultipliedAlpha => (
+                    vk::TRUE,
+                    Factor::ONE,
+                    Factor::ONE_MINUS_SRC_ALPHA,
+                    Factor::ONE,
+                    Factor::ONE_MINUS_SRC_ALPHA,
+                ),
+                BlendMode::Additive => (
+                    vk::TRUE,
+                    Factor::SRC_ALPHA,
+                    Factor::ONE,
+                    Factor::ZERO,
+                    Factor::ONE,
+                ),
+            };
+        vk::PipelineColorBlendAttachmentState {
+            color_write_mask: vk::ColorComponentFlags::R
+                | vk::ColorComponentFlags::G
+                | vk::ColorComponentFlags::B
+                | vk::ColorComponentFlags::A,
+            blend_enable,
+            src_color_blend_factor: src_color,
+            dst_color_blend_factor: dst_color,
+            color_blend_op: vk::BlendOp::ADD,
+            src_alpha_blend_factor: src_alpha,
+            dst_alpha_blend_factor: dst_alpha,
+            alpha_blend_op: vk::BlendOp::ADD,
+        }
+    }
+}
+
+/// A builder for graphics pipelines with defaults which suit most of the
+/// renderers in this crate.
+///
+/// By default pipelines draw triangle lists with alpha blending, no depth
+/// testing, no culling, and a single sample per pixel. The viewport and
+/// scissor are dynamic, so they must be set while recording commands, unless
+/// a static viewport is provided.
+pub struct GraphicsPipelineBuilder<'a> {
+    /// The device used to create the pipeline.
+    vk_dev: Arc<RenderDevice>,
+
+    /// The layout of the pipeline's descriptor sets and push constants.
+    pipeline_layout: Arc<PipelineLayout>,
+
+    /// The render pass the pipeline will be used with.
+    render_pass: vk::RenderPass,
+
+    /// The shader modules and the stage each one is used for. The modules
+    /// are borrowed because they only need to live until the pipeline is
+    /// built.
+    stages: Vec<(&'a ShaderModule, vk::ShaderStageFlags)>,
+
+    /// The kind of primitives assembled from vertices.
+    topology: vk::PrimitiveTopology,
+
+    /// How output is combined with the color attachment.
+    blend_mode: BlendMode,
+
+    /// When true, fragments are depth tested and write to the depth buffer.
+    depth_test: bool,
+
+    /// How depth tested fragments are compared with the depth buffer. See
+    /// [`crate::math::DepthMode::compare_op`].
+    depth_compare_op: vk::CompareOp,
+
+    /// The number of samples per pixel, must match the render pass.
+    samples: vk::SampleCountFlags,
+
+    /// Which faces are discarded.
+    cull_mode: vk::CullModeFlags,
+
+    /// The winding order of front facing triangles. See
+    /// [`crate::math::CoordinateSystem::front_face`].
+    front_face: vk::FrontFace,
+
+    /// The extent used for a fixed viewport and scissor. When None, the
+    /// viewport and scissor are dynamic state.
+    static_viewport: Option<vk::Extent2D>,
+}
+
+impl<'a> GraphicsPipelineBuilder<'a> {
+    /// Create a new builder for a pipeline which is compatible with the given
+    /// render pass.
+    pub fn new(
+        vk_dev: Arc<RenderDevice>,
+        pipeline_layout: Arc<PipelineLayout>,
+        render_pass: vk::RenderPass,
+    ) -> Self {
+        Self {
+            vk_dev,
+            pipeline_layout,
+            render_pass,
+            stages: vec![],
+            topology: vk::PrimitiveTopology::TRIANGLE_LIST,
+            blend_mode: BlendMode::Alpha,
+            depth_test: false,
+            depth_compare_op: vk::CompareOp::LESS,
+            samples: vk::SampleCountFlags::TYPE_1,
+            cull_mode: vk::CullModeFlags::NONE,
+            front_face: vk::FrontFace::CLOCKWISE,
+            static_viewport: None,
+        }
+    }
+
+    builder_field!(topology, vk::PrimitiveTopology);
+    builder_field!(blend_mode, BlendMode);
+    builder_field!(depth_test, bool);
+    builder_field!(depth_compare_op, vk::CompareOp);
+    builder_field!(samples, vk::SampleCountFlags);
+    builder_field!(cull_mode, vk::CullModeFlags);
+    builder_field!(front_face, vk::FrontFace);
+    builder_field_some!(static_viewport, vk::Extent2D);
+
+    /// Set the cull mode and front face for geometry in the given
+    /// coordinate system.
+    pub fn culling(
+        self,
+        culling: Culling,
+        coordinate_system: CoordinateSystem,
+    ) -> Self {
+        self.cull_mode(culling.cull_mode())
+            .front_face(culling.front_face(coordinate_system))
+    }
+
+    /// Use the shader module for the given pipeline stage.
+    ///
+    /// Note: assumes "main" entrypoint.
+    pub fn shader(
+        mut self,
+        module: &'a ShaderModule,
+        stage: vk::ShaderStageFlags,
+    ) -> Self {
+        self.stages.push((module, stage));
+        self
+    }
+
+    /// Use the shader module for the vertex stage.
+    pub fn vertex_shader(self, module: &'a ShaderModule) -> Self {
+        self.shader(module, vk::ShaderStageFlags::VERTEX)
+    }
+
+    /// Use the shader module for the fragment stage.
+    pub fn fragment_shader(self, module: &'a ShaderModule) -> Self {
+        self.shader(module, vk::ShaderStageFlags::FRAGMENT)
+    }
+
+    /// Create the pipeline.
+    pub fn build(self) -> Result<Pipeline, PipelineError> {
+        if self.stages.is_empty() {
+            return Err(PipelineError::NoShaderStages);
+        }
+
+        let stages: Vec<vk::PipelineShaderStageCreateInfo> = self
+            .stages
+            .iter()
+            .map(|(module, stage)| module.stage_create_info(*stage))
+            .collect();
+
+        // vertices are read from storage buffers, so there are no vertex
+        // input bindings
+        let vertex_input_state = vk::PipelineVertexInputStateCreateInfo {
+            ..Default::default()
+        };
+        let input_assembly = vk::PipelineInputAssemblyStateCreateInfo {
+            topology: self.topology,
+            primitive_restart_enable: 0,
+            ..Default::default()
+        };
+
+        let extent = self.static_viewport.unwrap_or_default();
+        let viewport = vk::Viewport {
+            x: 0.0,
+            y: 0.0,
+            width: extent.width as f32,
+            height: extent.height as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        };
+        let scissors = vk::Rect2D {
+            offset: vk::Offset2D { x: 0, y: 0 },
+            extent,
+        };
+        let viewport_state = if self.static_viewport.is_some() {
+            vk::PipelineViewportStateCreateInfo {
+                p_viewports: &viewport,
+                viewport_count: 1,
+                p_scissors: &scissors,
+                scissor_count: 1,
+                ..Default::default()
+            }
+        } else {
+            // the counts are still required when the state is dynamic
+            vk::PipelineViewportStateCreateInfo {
+                viewport_count: 1,
+                scissor_count: 1,
+                ..Default::default()
+            }
+        };
+        let dynamic_states = if self.static_viewport.is_some() {
+            vec![]
+        } else {
+            vec![vk::DynamicState::VIEWPORT, vk::DynamicState::SCISSOR]
+        };
+        let dynamic_state = vk::PipelineDynamicStateCreateInfo {
+            p_dynamic_states: dynamic_states.as_ptr(),
+            dynamic_state_count: dynamic_states.len() as u32,
+            ..Default::default()
+        };
+
+        let raster_state = vk::PipelineRasterizationStateCreateInfo {
+            depth_clamp_enable: 0,
+            rasterizer_discard_enable: 0,
+            polygon_mode: vk::PolygonMode::FILL,
+            line_width: 1.0,
+            cull_mode: self.cull_mode,
+            front_face: self.front_face,
+            ..Default::default()
+        };
+        let multisample_state = vk::PipelineMultisampleStateCreateInfo {
+            sample_shading_enable: 0,
+            rasterization_samples: self.samples,
+            p_sample_mask: std::ptr::null(),
+            min_sample_shading: 1.0,
+            ..Default::default()
+        };
+        let blend_attachment = self.blend_mode.attachment_state();
+        let blend_state = vk::PipelineColorBlendStateCreateInfo {
+            p_attachments: &blend_attachment,
+            attachment_count: 1,
+            ..Default::default()
+        };
+        let depth_enabled = if self.depth_test { vk::TRUE } else { vk::FALSE };
+        let depth_stencil_state = vk::PipelineDepthStencilStateCreateInfo {
+            flags: vk::PipelineDepthStencilStateCreateFlags::empty(),
+            depth_test_enable: depth_enabled,
+            depth_write_enable: depth_enabled,
+            min_depth_bounds: 0.0,
+            max_depth_bounds: 1.0,
+            depth_compare_op: self.depth_compare_op,
+            stencil_test_enable: vk::FALSE,
+            ..Default::default()
+        };
+
+        let pipeline_create_info = vk::GraphicsPipelineCreateInfo {
+            p_stages: stages.as_ptr(),
+            stage_count: stages.len() as u32,
+            p_vertex_input_state: &vertex_input_state,
+            p_input_assembly_state: &input_assembly,
+            p_viewport_state: &viewport_state,
+            p_rasterization_state: &raster_state,
+            p_multisample_state: &multisample_state,
+            p_color_blend_state: &blend_state,
+            p_depth_stencil_state: &depth_stencil_state,
+            p_dynamic_state: &dynamic_state,
+            render_pass: self.render_pass,
+            layout: self.pipeline_layout.raw,
+            ..Default::default()
+        };
+        Pipeline::new_graphics_pipeline(
+            pipeline_create_info,
+            self.pipeline_layout.clone(),
+            self.vk_dev.clone(),
+        )
+    }
+}
@@ -1,9 +1,13 @@
+mod graphics_pipeline_builder;
 mod pipeline;
 mod pipeline_error;
 mod pipeline_layout;
 mod shader_module;
 
 pub use self::{
-    pipeline::Pipeline, pipeline_error::PipelineError,
-    pipeline_layout::PipelineLayout, shader_module::ShaderModule,
+    graphics_pipeline_builder::{BlendMode, GraphicsPipelineBuilder},
+    pipeline::Pipeline,
+    pipeline_error::PipelineError,
+    pipeline_layout::PipelineLayout,
+    shader_module::ShaderModule,
 };
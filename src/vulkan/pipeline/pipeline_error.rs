@@ -16,6 +16,9 @@ pub enum PipelineError {
     #[error("Unable to create the pipeline layout")]
     UnableToCreatePipelineLayout(#[source] vk::Result),
 
+    #[error("A graphics pipeline needs at least one shader stage")]
+    NoShaderStages,
+
     #[error("Unable to create graphics pipeline")]
     UnableToCreateGraphicsPipeline(#[source] vk::Result),
 }
@@ -14,6 +14,10 @@ pub struct QueueFamilyIndices {
 
     /// the index for the presentation queue
     present_family_index: u32,
+
+    /// the index for the compute queue, this is the graphics queue's family
+    /// unless the device has a dedicated compute family
+    compute_family_index: u32,
 }
 
 impl QueueFamilyIndices {
@@ -34,10 +38,15 @@ impl QueueFamilyIndices {
 
         let mut graphics_family = None;
         let mut present_family = None;
+        let mut dedicated_compute_family = None;
 
         queue_families.iter().enumerate().for_each(|(i, family)| {
             if family.queue_flags.contains(vk::QueueFlags::GRAPHICS) {
                 graphics_family = Some(i as u32);
+            } else if family.queue_flags.contains(vk::QueueFlags::COMPUTE) {
+                // compute families without graphics support can run work
+                // asynchronously alongside the graphics queue
+                dedicated_compute_family = Some(i as u32);
             }
 
             let present_support = unsafe {
@@ -69,9 +78,14 @@ impl QueueFamilyIndices {
         let present_family_index = present_family
             .ok_or(QueueSelectionError::UnableToFindPresentQueue)?;
 
+        // graphics queues always support compute operations
+        let compute_family_index =
+            dedicated_compute_family.unwrap_or(graphics_family_index);
+
         Ok(Self {
             graphics_family_index,
             present_family_index,
+            compute_family_index,
         })
     }
 
@@ -95,6 +109,18 @@ impl QueueFamilyIndices {
             });
         }
 
+        let is_new_family = self.compute_family_index
+            != self.graphics_family_index
+            && self.compute_family_index != self.present_family_index;
+        if is_new_family {
+            create_infos.push(vk::DeviceQueueCreateInfo {
+                queue_family_index: self.compute_family_index,
+                p_queue_priorities: SINGLE_QUEUE_PRIORITY.as_ptr(),
+                queue_count: 1,
+                ..Default::default()
+            });
+        }
+
         create_infos
     }
 
@@ -104,12 +130,12 @@ impl QueueFamilyIndices {
     ///
     /// # Returns
     ///
-    /// A tuple of `(graphics_queue, present_queue)`.
+    /// A tuple of `(graphics_queue, present_queue, compute_queue)`.
     ///
     pub fn get_queues(
         &self,
         logical_device: &ash::Device,
-    ) -> (GpuQueue, GpuQueue) {
+    ) -> (GpuQueue, GpuQueue, GpuQueue) {
         let raw_graphics_queue = unsafe {
             logical_device.get_device_queue(self.graphics_family_index, 0)
         };
@@ -130,6 +156,19 @@ impl QueueFamilyIndices {
             GpuQueue::from_raw(raw_present_queue, self.present_family_index, 0)
         };
 
-        (graphics_queue, present_queue)
+        let compute_queue = if self.compute_family_index
+            == self.graphics_family_index
+        {
+            graphics_queue
+        } else if self.compute_family_index == self.present_family_index {
+            present_queue
+        } else {
+            let raw_compute_queue = unsafe {
+                logical_device.get_device_queue(self.compute_family_index, 0)
+            };
+            GpuQueue::from_raw(raw_compute_queue, self.compute_family_index, 0)
+        };
+
+        (graphics_queue, present_queue, compute_queue)
     }
 }
@@ -1,4 +1,10 @@
-use ::{ash::vk, std::sync::Mutex};
+use ::{
+    ash::vk,
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
 use crate::vulkan::{
     render_device::{
@@ -24,6 +30,11 @@ pub struct RenderDevice {
     /// The GPU queue used to submit presentation commands.
     pub present_queue: GpuQueue,
 
+    /// The GPU queue used to submit compute commands. This is the graphics
+    /// queue unless the device has a dedicated compute queue, see
+    /// [`RenderDevice::has_async_compute`].
+    pub compute_queue: GpuQueue,
+
     /// The window's swapchain and related resources.
     pub swapchain: Mutex<Option<Swapchain>>,
 
@@ -32,6 +43,10 @@ pub struct RenderDevice {
 
     /// The Vulkan library instance.
     pub instance: Instance,
+
+    /// When true, the swapchain is built to blend with whatever is behind
+    /// the window, see [`Self::set_transparent_swapchain`].
+    transparent_swapchain: AtomicBool,
 }
 
 impl RenderDevice {
@@ -56,7 +71,7 @@ impl RenderDevice {
             &physical_device::required_extensions(),
             &queue_family_indices.as_queue_create_infos(),
         )?;
-        let (graphics_queue, present_queue) =
+        let (graphics_queue, present_queue, compute_queue) =
             queue_family_indices.get_queues(&logical_device);
 
         let vk_dev = Self {
@@ -65,8 +80,10 @@ impl RenderDevice {
             logical_device,
             graphics_queue,
             present_queue,
+            compute_queue,
             window_surface,
             swapchain: Mutex::new(None),
+            transparent_swapchain: AtomicBool::new(false),
         };
 
         vk_dev.name_vulkan_object(
@@ -82,9 +99,81 @@ impl RenderDevice {
             )?;
         }
 
+        if vk_dev.has_async_compute() {
+            vk_dev.name_vulkan_object(
+                "Compute Queue",
+                vk::ObjectType::QUEUE,
+                vk_dev.compute_queue.queue,
+            )?;
+        }
+
         Ok(vk_dev)
     }
 
+    /// Build swapchains whose images are blended with whatever is behind the
+    /// window, using their alpha channel. Takes effect the next time the
+    /// swapchain is rebuilt. The window needs a transparent framebuffer, see
+    /// [`crate::glfw_window::GlfwWindow::new_transparent`].
+    ///
+    /// Surfaces which can't blend fall back to an opaque swapchain, check
+    /// [`Swapchain::composite_alpha`] to see which mode was used.
+    pub fn set_transparent_swapchain(&self, transparent: bool) {
+        self.transparent_swapchain
+            .store(transparent, Ordering::Relaxed);
+    }
+
+    /// True when swapchains are built to blend with what's behind the window.
+    pub fn is_transparent_swapchain(&self) -> bool {
+        self.transparent_swapchain.load(Ordering::Relaxed)
+    }
+
+    /// True when compute commands run on a dedicated queue, so they can
+    /// overlap with graphics work instead of running before it.
+    pub fn has_async_compute(&self) -> bool {
+        !self.compute_queue.is_same(&self.graphics_queue)
+    }
+
+    /// The queue families which use resources shared by graphics and compute
+    /// work. Buffers and images written by compute and read by graphics, or
+    /// the reverse, should be created with `vk::SharingMode::CONCURRENT` and
+    /// these families when there's more than one.
+    pub fn graphics_and_compute_families(&self) -> Vec<u32> {
+        if self.has_async_compute() {
+            vec![self.graphics_queue.family_id, self.compute_queue.family_id]
+        } else {
+            vec![self.graphics_queue.family_id]
+        }
+    }
+
+    /// A human-readable description of the physical device and its driver.
+    /// Useful when reporting bugs.
+    pub fn describe(&self) -> String {
+        let props = unsafe {
+            self.instance
+                .ash
+                .get_physical_device_properties(self.physical_device)
+        };
+        let name = unsafe {
+            std::ffi::CStr::from_ptr(props.device_name.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        };
+        format!(
+            "{} ({:?})\n\
+             vendor id: {:#06x}, device id: {:#06x}\n\
+             vulkan api version: {}.{}.{}\n\
+             driver version: {:#010x}",
+            name,
+            props.device_type,
+            props.vendor_id,
+            props.device_id,
+            vk::api_version_major(props.api_version),
+            vk::api_version_minor(props.api_version),
+            vk::api_version_patch(props.api_version),
+            props.driver_version,
+        )
+    }
+
     /// Give a debug name for a vulkan object owned by this device.
     ///
     /// Whatever name is provided here will show up in the debug logs if there
@@ -17,6 +17,9 @@ pub struct Swapchain {
     /// The Vulkan SwapchainKHR used for most swapchain operations.
     pub khr: vk::SwapchainKHR,
 
+    /// The swapchain's images. These are owned by the swapchain itself.
+    pub images: Vec<vk::Image>,
+
     /// The array of image views for this swapchain's images.
     pub image_views: Vec<vk::ImageView>,
 
@@ -28,6 +31,15 @@ pub struct Swapchain {
 
     /// The hardware pixel extent for this swapchain's images.
     pub extent: vk::Extent2D,
+
+    /// How this swapchain's images are combined with what's behind the
+    /// window. Opaque unless the render device asked for a transparent
+    /// swapchain and the surface supports one.
+    pub composite_alpha: vk::CompositeAlphaFlagsKHR,
+
+    /// How this swapchain's images can be used. Images can be used as a
+    /// transfer source when supported by the surface.
+    pub image_usage: vk::ImageUsageFlags,
 }
 
 impl RenderDevice {
@@ -116,6 +128,8 @@ impl RenderDevice {
         let present_mode = self.choose_present_mode();
         let extent = self.choose_swap_extent(framebuffer_size)?;
         let image_count = self.choose_image_count()?;
+        let image_usage = self.choose_image_usage()?;
+        let composite_alpha = self.choose_composite_alpha()?;
 
         let mut create_info = vk::SwapchainCreateInfoKHR {
             surface: self.window_surface.khr,
@@ -126,11 +140,11 @@ impl RenderDevice {
             image_extent: extent,
             min_image_count: image_count,
             image_array_layers: 1,
-            image_usage: vk::ImageUsageFlags::COLOR_ATTACHMENT,
+            image_usage,
 
             // window system presentation settings
             present_mode,
-            composite_alpha: vk::CompositeAlphaFlagsKHR::OPAQUE,
+            composite_alpha,
             pre_transform: vk::SurfaceTransformFlagsKHR::IDENTITY,
             old_swapchain: if current_swapchain.is_some() {
                 current_swapchain.as_ref().unwrap().khr
@@ -174,10 +188,13 @@ impl RenderDevice {
         let previous_swapchain = current_swapchain.replace(Swapchain {
             loader,
             khr: swapchain,
+            images: swapchain_images,
             image_views,
             format: format.format,
             color_space: format.color_space,
             extent,
+            composite_alpha,
+            image_usage,
         });
 
         if let Some(old_swapchain) = previous_swapchain {
@@ -29,6 +29,64 @@ impl RenderDevice {
         }
     }
 
+    /// Choose how the swapchain's images will be used. Images are always
+    /// color attachments and are also transfer sources when the surface
+    /// allows it, so rendered frames can be read back.
+    pub(super) fn choose_image_usage(
+        &self,
+    ) -> Result<vk::ImageUsageFlags, SwapchainError> {
+        //! querying surface capabilities is safe in this context because the
+        //! physical device will not be selected unless it supports the swapchain
+        //! extension
+        let capabilities = unsafe {
+            self.window_surface
+                .surface_capabilities(&self.physical_device)?
+        };
+
+        let mut usage = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        if capabilities
+            .supported_usage_flags
+            .contains(vk::ImageUsageFlags::TRANSFER_SRC)
+        {
+            usage |= vk::ImageUsageFlags::TRANSFER_SRC;
+        }
+
+        log::debug!("chosen image usage {:?}", usage);
+
+        Ok(usage)
+    }
+
+    /// Choose how the swapchain's images are combined with what's behind the
+    /// window.
+    pub(super) fn choose_composite_alpha(
+        &self,
+    ) -> Result<vk::CompositeAlphaFlagsKHR, SwapchainError> {
+        //! querying surface capabilities is safe in this context because the
+        //! physical device will not be selected unless it supports the swapchain
+        //! extension
+        let capabilities = unsafe {
+            self.window_surface
+                .surface_capabilities(&self.physical_device)?
+        };
+
+        let transparent = self.is_transparent_swapchain();
+        let composite_alpha = composite_alpha_for(
+            capabilities.supported_composite_alpha,
+            transparent,
+        );
+        if transparent && composite_alpha == vk::CompositeAlphaFlagsKHR::OPAQUE
+        {
+            log::warn!(
+                "the surface can't blend with the desktop, using an \
+                 opaque swapchain"
+            );
+        }
+
+        log::debug!("chosen composite alpha {:?}", composite_alpha);
+
+        Ok(composite_alpha)
+    }
+
     /// Choose a surface format for the swapchain based on the window and chosen
     /// physical device.
     pub(super) fn choose_surface_format(&self) -> vk::SurfaceFormatKHR {
@@ -110,3 +168,60 @@ impl RenderDevice {
         }
     }
 }
+
+/// Pick the supported composite alpha mode. Transparent swapchains prefer
+/// premultiplied alpha, which is what the blended pipelines write, then
+/// postmultiplied alpha. Opaque is always preferred otherwise, and used when
+/// it's the only choice.
+fn composite_alpha_for(
+    supported: vk::CompositeAlphaFlagsKHR,
+    transparent: bool,
+) -> vk::CompositeAlphaFlagsKHR {
+    use vk::CompositeAlphaFlagsKHR as Alpha;
+
+    let preferences: &[Alpha] = if transparent {
+        &[
+            Alpha::PRE_MULTIPLIED,
+            Alpha::POST_MULTIPLIED,
+            Alpha::OPAQUE,
+            Alpha::INHERIT,
+        ]
+    } else {
+        &[
+            Alpha::OPAQUE,
+            Alpha::INHERIT,
+            Alpha::PRE_MULTIPLIED,
+            Alpha::POST_MULTIPLIED,
+        ]
+    };
+    preferences
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(*mode))
+        .unwrap_or(Alpha::OPAQUE)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_transparent_swapchains_prefer_premultiplied_alpha() {
+        use vk::CompositeAlphaFlagsKHR as Alpha;
+
+        let all = Alpha::OPAQUE
+            | Alpha::PRE_MULTIPLIED
+            | Alpha::POST_MULTIPLIED
+            | Alpha::INHERIT;
+        assert_eq!(composite_alpha_for(all, true), Alpha::PRE_MULTIPLIED);
+        assert_eq!(composite_alpha_for(all, false), Alpha::OPAQUE);
+        assert_eq!(
+            composite_alpha_for(Alpha::OPAQUE | Alpha::POST_MULTIPLIED, true),
+            Alpha::POST_MULTIPLIED
+        );
+
+        // some surfaces can only inherit the mode from the window system
+        assert_eq!(composite_alpha_for(Alpha::INHERIT, false), Alpha::INHERIT);
+        assert_eq!(composite_alpha_for(Alpha::OPAQUE, true), Alpha::OPAQUE);
+    }
+}